@@ -6,21 +6,23 @@
 //! structured source diagnostic instead of a backend-internal lowering error.
 
 use crate::backends::external_package_validation::BackendTarget;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
 use crate::compiler_frontend::compiler_messages::{
     CompilerDiagnostic, UnsupportedBackendFeatureReason,
 };
-use crate::compiler_frontend::datatypes::definitions::TypeDefinition;
 use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
-use crate::compiler_frontend::hir::expressions::{HirExpression, HirExpressionKind};
+use crate::compiler_frontend::hir::expressions::{
+    HirExpression, HirExpressionKind, HirVariantCarrier,
+};
+use crate::compiler_frontend::hir::hir_datatypes::{HirTypeClass, classify_hir_type};
 use crate::compiler_frontend::hir::ids::{BlockId, FunctionId};
 use crate::compiler_frontend::hir::module::HirModule;
 use crate::compiler_frontend::hir::numeric::HirNumericOperands;
+use crate::compiler_frontend::hir::operators::HirBinOp;
 use crate::compiler_frontend::hir::reachability::{
-    HirReachability, HirReachabilityInput, ReachableFloatStatementKind, ReachableFloatStatementUse,
-    ReachableMapUse, ReachableMapUseKind, ReachableNumericOpUse, ReachableReactiveSinkKind,
-    ReachableReactiveSinkUse, ReachableReactiveTemplateUse, ReachableRuntimeCastUse,
-    collect_hir_reachability, collect_reachability_from_start,
+    HirReachability, HirReachabilityInput, ReachableReactiveSinkKind, ReachableReactiveSinkUse,
+    ReachableReactiveTemplateUse, collect_hir_reachability, collect_reachability_from_start,
 };
 use crate::compiler_frontend::hir::statements::{HirStatement, HirStatementKind};
 use crate::compiler_frontend::hir::terminators::HirTerminator;
@@ -65,10 +67,10 @@ pub struct BackendFeatureValidationInput<'a> {
 
 /// Validates HIR runtime features that are target-specific after frontend semantics are complete.
 ///
-/// WHAT: hashmap construction/use, reactive runtime features, runtime casts, checked numeric
-///       operations, and generic runtime values are legal HIR, but only the JS backend lowers them
-///       for Alpha. HTML-Wasm must reject reachable unsupported operations; unused functions stay
-///       type checked but do not block the experimental Wasm build path.
+/// WHAT: reactive runtime features, Option values and scalar collection literals are legal HIR,
+///       but only the JS backend lowers them for Alpha. HTML-Wasm must reject reachable
+///       unsupported operations; unused functions stay type checked but do not block the
+///       experimental Wasm build path.
 /// WHY: fail early with a structured Rule error at the source location instead of a vague
 ///      backend-internal lowering failure.
 pub fn validate_hir_backend_feature_support(
@@ -79,31 +81,15 @@ pub fn validate_hir_backend_feature_support(
         .map_err(|error| BackendFeatureValidationError::Infrastructure(Box::new(error)))?;
 
     match input.target {
-        BackendTarget::Wasm => {
-            // Wasm does not yet lower hashmaps, reactive runtime features, runtime casts, checked
-            // numeric operations, or generic runtime values.
-            validate_wasm_maps(&reachability.reachable_map_uses, input.target, string_table)?;
+        BackendTarget::Wasm | BackendTarget::Wasi => {
+            // Wasm does not yet lower reactive runtime features, Option values or collection
+            // literals with scalar elements.
             validate_wasm_reactive_features(
                 &reachability.reachable_reactive_templates,
                 input.target,
                 string_table,
             )?;
-            validate_wasm_runtime_casts(
-                &reachability.reachable_runtime_casts,
-                input.target,
-                string_table,
-            )?;
-            validate_wasm_checked_numeric_ops(
-                &reachability.reachable_numeric_ops,
-                input.target,
-                string_table,
-            )?;
-            validate_wasm_float_statements(
-                &reachability.reachable_float_statements,
-                input.target,
-                string_table,
-            )?;
-            validate_wasm_generic_runtime_values(
+            validate_wasm_value_shapes(
                 input.hir,
                 input.type_environment,
                 &reachability.reachable_blocks,
@@ -144,39 +130,6 @@ fn collect_reachability_for_validation(
     }
 }
 
-/// Reports the first reachable unsupported hashmap operation for the Wasm target.
-///
-/// WHAT: hashmap literals and operations are valid HIR, but Wasm lowering does not yet
-/// support them.
-/// WHY: reject early with a structured diagnostic at the source location instead of a
-/// backend-internal lowering failure.
-fn validate_wasm_maps(
-    map_uses: &[ReachableMapUse],
-    target: BackendTarget,
-    string_table: &mut StringTable,
-) -> Result<(), BackendFeatureValidationError> {
-    let Some(map_use) = map_uses.first() else {
-        return Ok(());
-    };
-
-    let reason = match &map_use.kind {
-        ReachableMapUseKind::Literal => UnsupportedBackendFeatureReason::HashmapConstruction,
-        ReachableMapUseKind::Operation(_) => UnsupportedBackendFeatureReason::HashmapOperation,
-    };
-
-    // Only the first reachable unsupported operation is reported. Unreachable helpers remain
-    // valid typed HIR and do not block the build.
-    let diagnostic = CompilerDiagnostic::unsupported_backend_feature(
-        string_table.intern(target.as_str()),
-        reason,
-        map_use.location.clone(),
-    );
-
-    Err(BackendFeatureValidationError::Diagnostic(Box::new(
-        diagnostic,
-    )))
-}
-
 /// Reports the first reachable reactive runtime feature for the Wasm target.
 ///
 /// WHAT: reactive template values with runtime dependencies are valid HIR, but HTML-Wasm does
@@ -204,87 +157,45 @@ fn validate_wasm_reactive_features(
     )))
 }
 
-/// Reports the first reachable runtime cast for the Wasm target.
+/// Reports the first reachable value shape the Wasm lowering has no representation for.
 ///
-/// WHAT: compiler-owned builtin runtime casts are valid HIR, but HTML-Wasm does not yet lower
-///       them.
-/// WHY: reject early with a structured diagnostic at the cast source location instead of a
+/// WHAT: Option values (`none`, `some`, and `is |value|` payload reads) and non-empty collection
+///       literals whose elements are scalars are valid HIR, but Wasm has no runtime layout for
+///       them yet. Collection literals of handle values (strings, records, nested collections)
+///       lower to the vec runtime and pass.
+/// WHY: reject early with a structured diagnostic at the source location instead of a
 ///      backend-internal lowering failure.
-fn validate_wasm_runtime_casts(
-    runtime_casts: &[ReachableRuntimeCastUse],
+fn validate_wasm_value_shapes(
+    hir: &HirModule,
+    type_environment: Option<&TypeEnvironment>,
+    reachable_blocks: &FxHashSet<BlockId>,
     target: BackendTarget,
     string_table: &mut StringTable,
 ) -> Result<(), BackendFeatureValidationError> {
-    let Some(runtime_cast) = runtime_casts.first() else {
-        return Ok(());
+    let Some(type_environment) = type_environment else {
+        // Collection element classification requires semantic type information. Without it we
+        // cannot safely classify expressions, so we treat the absence as an internal invariant
+        // failure rather than silently allowing unsupported values through.
+        return Err(BackendFeatureValidationError::Infrastructure(Box::new(
+            CompilerError::compiler_error(
+                "Backend feature validation for Wasm requires a TypeEnvironment to classify runtime values",
+            ),
+        )));
     };
 
-    let diagnostic = CompilerDiagnostic::unsupported_backend_feature(
-        string_table.intern(target.as_str()),
-        UnsupportedBackendFeatureReason::RuntimeCasts,
-        runtime_cast.location.clone(),
-    );
-
-    Err(BackendFeatureValidationError::Diagnostic(Box::new(
-        diagnostic,
-    )))
-}
-
-/// Reports the first reachable checked numeric operation for the Wasm target.
-///
-/// WHAT: checked arithmetic is valid HIR, but HTML-Wasm does not yet implement the helper and
-///       trap/recoverability contract for `HirStatementKind::NumericOp`.
-/// WHY: reject early with a structured unsupported-backend diagnostic instead of letting Wasm LIR
-///      lowering report an infrastructure failure.
-fn validate_wasm_checked_numeric_ops(
-    numeric_ops: &[ReachableNumericOpUse],
-    target: BackendTarget,
-    string_table: &mut StringTable,
-) -> Result<(), BackendFeatureValidationError> {
-    let Some(numeric_op) = numeric_ops.first() else {
-        return Ok(());
+    let scan = WasmValueShapeScan {
+        module: hir,
+        type_environment,
+        wasm_gc: false,
     };
-
-    let diagnostic = CompilerDiagnostic::unsupported_backend_feature(
-        string_table.intern(target.as_str()),
-        UnsupportedBackendFeatureReason::CheckedNumericOperations,
-        numeric_op.location.clone(),
-    );
-
-    Err(BackendFeatureValidationError::Diagnostic(Box::new(
-        diagnostic,
-    )))
-}
-
-/// Reports the first reachable Float formatting or validation statement for the Wasm target.
-///
-/// WHAT: Beanstalk Float formatting and external-Float boundary validation are valid HIR, but
-///       HTML-Wasm does not yet implement the helper and trap/recoverability contract for
-///       `HirStatementKind::FormatFloat` or `HirStatementKind::ValidateFloat`.
-/// WHY: reject early with a structured unsupported-backend diagnostic instead of letting Wasm LIR
-///      lowering report an infrastructure failure.
-fn validate_wasm_float_statements(
-    float_statements: &[ReachableFloatStatementUse],
-    target: BackendTarget,
-    string_table: &mut StringTable,
-) -> Result<(), BackendFeatureValidationError> {
-    let Some(float_statement) = float_statements.first() else {
+    let Some((reason, location)) = scan.first_module_shape(reachable_blocks) else {
         return Ok(());
     };
 
-    let reason = match float_statement.kind {
-        ReachableFloatStatementKind::FormatFloat => {
-            UnsupportedBackendFeatureReason::FloatFormatting
-        }
-        ReachableFloatStatementKind::ValidateFloat => {
-            UnsupportedBackendFeatureReason::FloatBoundaryValidation
-        }
-    };
-
     let diagnostic = CompilerDiagnostic::unsupported_backend_feature(
         string_table.intern(target.as_str()),
         reason,
-        float_statement.location.clone(),
+        location,
     );
 
     Err(BackendFeatureValidationError::Diagnostic(Box::new(
@@ -292,229 +203,329 @@ fn validate_wasm_float_statements(
     )))
 }
 
-/// Reports the first reachable generic runtime value for the Wasm target.
+/// Backend name reported by diagnostics from the opt-in Wasm GC emission mode.
+const WASM_GC_BACKEND_NAME: &str = "wasm-gc";
+
+/// Validates reachable HIR features the opt-in Wasm GC emission mode cannot lower yet.
 ///
-/// WHAT: generic nominal instances such as `Box of String` are valid HIR, but HTML-Wasm does not
-///       yet have a generic runtime representation.
-/// WHY: reject early with a structured diagnostic at the source location instead of a
-///      backend-internal lowering failure.
-fn validate_wasm_generic_runtime_values(
+/// WHAT: GC mode maps records, strings and vecs to GC structs and arrays, but hash maps,
+///       fallible results and `Error` values, checked numeric operations, Float formatting and
+///       validation, and every runtime cast except `Int -> Float` and `Char -> Int` still depend
+///       on linear-memory runtime helpers. Everything the linear-memory Wasm path rejects is
+///       rejected here as well.
+/// WHY: GC mode is selected per backend request rather than by a builder target, so the Wasm
+///      backend runs this check from the request's exported functions before lowering instead
+///      of failing inside GC emission.
+pub fn validate_wasm_gc_feature_support(
     hir: &HirModule,
-    type_environment: Option<&TypeEnvironment>,
-    reachable_blocks: &FxHashSet<BlockId>,
-    target: BackendTarget,
+    root_functions: Vec<FunctionId>,
+    type_environment: &TypeEnvironment,
     string_table: &mut StringTable,
 ) -> Result<(), BackendFeatureValidationError> {
-    let Some(type_environment) = type_environment else {
-        // Generic runtime value detection requires semantic type information. Without it we
-        // cannot safely classify expressions, so we treat the absence as an internal invariant
-        // failure rather than silently allowing unsupported values through.
-        return Err(BackendFeatureValidationError::Infrastructure(Box::new(
-            CompilerError::compiler_error(
-                "Backend feature validation for Wasm requires a TypeEnvironment to detect generic runtime values",
-            ),
-        )));
+    let reachability = collect_hir_reachability(HirReachabilityInput {
+        hir,
+        root_functions,
+    })
+    .map_err(|error| BackendFeatureValidationError::Infrastructure(Box::new(error)))?;
+
+    let backend_name = string_table.intern(WASM_GC_BACKEND_NAME);
+    let scan = WasmValueShapeScan {
+        module: hir,
+        type_environment,
+        wasm_gc: true,
     };
-
-    let Some(location) =
-        first_generic_runtime_module_location(hir, type_environment, reachable_blocks)
-    else {
+    let unsupported = reachability
+        .reachable_reactive_templates
+        .first()
+        .map(|template| {
+            (
+                UnsupportedBackendFeatureReason::ReactiveTemplateRuntime,
+                template.location.clone(),
+            )
+        })
+        .or_else(|| scan.first_module_shape(&reachability.reachable_blocks));
+    let Some((reason, location)) = unsupported else {
         return Ok(());
     };
 
-    let diagnostic = CompilerDiagnostic::unsupported_backend_feature(
-        string_table.intern(target.as_str()),
-        UnsupportedBackendFeatureReason::GenericRuntimeValues,
-        location,
-    );
+    let diagnostic =
+        CompilerDiagnostic::unsupported_backend_feature(backend_name, reason, location);
 
     Err(BackendFeatureValidationError::Diagnostic(Box::new(
         diagnostic,
     )))
 }
 
-/// Finds the first reachable expression whose type is a generic instance.
+/// One unsupported Wasm value shape and where it appears.
+type UnsupportedShape = (UnsupportedBackendFeatureReason, SourceLocation);
+
+/// Walks reachable HIR for value shapes the Wasm lowering rejects.
 ///
-/// WHAT: scans only reachable blocks so dead helper bodies do not fail backend validation.
-/// WHY: generic runtime value detection needs the module TypeEnvironment, which is not available
-///      in backend-neutral HIR reachability collection.
-fn first_generic_runtime_module_location(
-    module: &HirModule,
-    type_environment: &TypeEnvironment,
-    reachable_blocks: &FxHashSet<BlockId>,
-) -> Option<SourceLocation> {
-    for block in &module.blocks {
-        if !reachable_blocks.contains(&block.id) {
-            continue;
-        }
+/// With `wasm_gc` set, the scan also rejects the statements and values GC emission has no
+/// lowering for yet.
+struct WasmValueShapeScan<'a> {
+    module: &'a HirModule,
+    type_environment: &'a TypeEnvironment,
+    wasm_gc: bool,
+}
 
-        for statement in &block.statements {
-            if let Some(location) =
-                first_generic_runtime_statement_location(statement, module, type_environment)
-            {
-                return Some(location);
+impl WasmValueShapeScan<'_> {
+    /// Finds the first unsupported shape, scanning only reachable blocks so dead helper bodies
+    /// do not fail backend validation.
+    fn first_module_shape(
+        &self,
+        reachable_blocks: &FxHashSet<BlockId>,
+    ) -> Option<UnsupportedShape> {
+        for block in &self.module.blocks {
+            if !reachable_blocks.contains(&block.id) {
+                continue;
             }
-        }
 
-        if let Some(location) =
-            first_generic_runtime_terminator_location(&block.terminator, module, type_environment)
-        {
-            return Some(location);
+            for statement in &block.statements {
+                if let Some(shape) = self.first_statement_shape(statement) {
+                    return Some(shape);
+                }
+            }
+
+            if let Some(shape) = self.first_terminator_shape(&block.terminator) {
+                return Some(shape);
+            }
         }
-    }
 
-    None
-}
+        None
+    }
 
-fn first_generic_runtime_statement_location(
-    statement: &HirStatement,
-    module: &HirModule,
-    type_environment: &TypeEnvironment,
-) -> Option<SourceLocation> {
-    match &statement.kind {
-        HirStatementKind::Assign { value, .. }
-        | HirStatementKind::Expr(value)
-        | HirStatementKind::PushRuntimeFragment { value, .. } => {
-            first_generic_runtime_expression_location(value, module, type_environment)
+    fn first_statement_shape(&self, statement: &HirStatement) -> Option<UnsupportedShape> {
+        if let Some(reason) = self.unsupported_gc_statement_reason(statement) {
+            return Some((reason, statement.location.clone()));
         }
-        HirStatementKind::Call { args, .. } => args.iter().find_map(|arg| {
-            first_generic_runtime_expression_location(arg, module, type_environment)
-        }),
-        HirStatementKind::CastOp { source, .. } => {
-            first_generic_runtime_expression_location(source, module, type_environment)
+
+        match &statement.kind {
+            HirStatementKind::Assign { value, .. }
+            | HirStatementKind::Expr(value)
+            | HirStatementKind::PushRuntimeFragment { value, .. } => {
+                self.first_expression_shape(value)
+            }
+            HirStatementKind::Call { args, .. } => self.first_expressions_shape(args),
+            HirStatementKind::CastOp { source, .. }
+            | HirStatementKind::FormatFloat { source, .. }
+            | HirStatementKind::ValidateFloat { source, .. } => self.first_expression_shape(source),
+            HirStatementKind::MapOp { receiver, args, .. } => self
+                .first_expression_shape(receiver)
+                .or_else(|| self.first_expressions_shape(args)),
+            HirStatementKind::NumericOp { operands, .. } => match operands {
+                HirNumericOperands::Unary { operand } => self.first_expression_shape(operand),
+                HirNumericOperands::Binary { left, right } => self
+                    .first_expression_shape(left)
+                    .or_else(|| self.first_expression_shape(right)),
+            },
+            HirStatementKind::Drop(_) => None,
         }
-        HirStatementKind::FormatFloat { source, .. }
-        | HirStatementKind::ValidateFloat { source, .. } => {
-            first_generic_runtime_expression_location(source, module, type_environment)
+    }
+
+    fn first_terminator_shape(&self, terminator: &HirTerminator) -> Option<UnsupportedShape> {
+        if self.wasm_gc
+            && let HirTerminator::FallibleBranch { result, .. }
+            | HirTerminator::ReturnSuccess(result)
+            | HirTerminator::ReturnError(result) = terminator
+        {
+            return Some((
+                UnsupportedBackendFeatureReason::FallibleResults,
+                self.expression_location(result),
+            ));
         }
-        HirStatementKind::MapOp { receiver, args, .. } => {
-            first_generic_runtime_expression_location(receiver, module, type_environment).or_else(
-                || {
-                    args.iter().find_map(|arg| {
-                        first_generic_runtime_expression_location(arg, module, type_environment)
+
+        match terminator {
+            HirTerminator::If { condition, .. } => self.first_expression_shape(condition),
+            HirTerminator::FallibleBranch { result, .. }
+            | HirTerminator::Return(result)
+            | HirTerminator::ReturnSuccess(result)
+            | HirTerminator::ReturnError(result) => self.first_expression_shape(result),
+            HirTerminator::Match { scrutinee, arms } => {
+                self.first_expression_shape(scrutinee).or_else(|| {
+                    arms.iter().find_map(|arm| {
+                        arm.guard
+                            .as_ref()
+                            .and_then(|guard| self.first_expression_shape(guard))
                     })
-                },
-            )
-        }
-        HirStatementKind::NumericOp { operands, .. } => match operands {
-            HirNumericOperands::Unary { operand } => {
-                first_generic_runtime_expression_location(operand, module, type_environment)
-            }
-            HirNumericOperands::Binary { left, right } => {
-                first_generic_runtime_expression_location(left, module, type_environment).or_else(
-                    || first_generic_runtime_expression_location(right, module, type_environment),
-                )
+                })
             }
-        },
-        HirStatementKind::Drop(_) => None,
+            HirTerminator::Jump { .. }
+            | HirTerminator::Break { .. }
+            | HirTerminator::Continue { .. }
+            | HirTerminator::Uninitialized
+            | HirTerminator::RuntimeFailure { .. }
+            | HirTerminator::AssertFailure { .. } => None,
+        }
     }
-}
 
-fn first_generic_runtime_terminator_location(
-    terminator: &HirTerminator,
-    module: &HirModule,
-    type_environment: &TypeEnvironment,
-) -> Option<SourceLocation> {
-    match terminator {
-        HirTerminator::If { condition, .. } => {
-            first_generic_runtime_expression_location(condition, module, type_environment)
+    fn first_expressions_shape(&self, expressions: &[HirExpression]) -> Option<UnsupportedShape> {
+        expressions
+            .iter()
+            .find_map(|expression| self.first_expression_shape(expression))
+    }
+
+    fn first_expression_shape(&self, expression: &HirExpression) -> Option<UnsupportedShape> {
+        if let Some(reason) = self
+            .unsupported_reason(expression)
+            .or_else(|| self.unsupported_gc_expression_reason(expression))
+        {
+            return Some((reason, self.expression_location(expression)));
         }
-        HirTerminator::FallibleBranch { result, .. }
-        | HirTerminator::Return(result)
-        | HirTerminator::ReturnSuccess(result)
-        | HirTerminator::ReturnError(result) => {
-            first_generic_runtime_expression_location(result, module, type_environment)
+
+        match &expression.kind {
+            HirExpressionKind::BinOp { left, right, .. }
+            | HirExpressionKind::Range {
+                start: left,
+                end: right,
+            } => self
+                .first_expression_shape(left)
+                .or_else(|| self.first_expression_shape(right)),
+            HirExpressionKind::UnaryOp { operand, .. }
+            | HirExpressionKind::TupleGet { tuple: operand, .. }
+            | HirExpressionKind::FallibleUnwrapSuccess { result: operand }
+            | HirExpressionKind::FallibleUnwrapError { result: operand }
+            | HirExpressionKind::Cast {
+                source: operand, ..
+            }
+            | HirExpressionKind::VariantPayloadGet {
+                source: operand, ..
+            } => self.first_expression_shape(operand),
+            HirExpressionKind::StructConstruct { fields, .. } => fields
+                .iter()
+                .find_map(|(_, value)| self.first_expression_shape(value)),
+            HirExpressionKind::Collection(items)
+            | HirExpressionKind::TupleConstruct { elements: items } => {
+                self.first_expressions_shape(items)
+            }
+            HirExpressionKind::MapLiteral(entries) => entries.iter().find_map(|entry| {
+                self.first_expression_shape(&entry.key)
+                    .or_else(|| self.first_expression_shape(&entry.value))
+            }),
+            HirExpressionKind::VariantConstruct { fields, .. } => fields
+                .iter()
+                .find_map(|field| self.first_expression_shape(&field.value)),
+            HirExpressionKind::Int(_)
+            | HirExpressionKind::Float(_)
+            | HirExpressionKind::Bool(_)
+            | HirExpressionKind::Char(_)
+            | HirExpressionKind::StringLiteral(_)
+            | HirExpressionKind::Load(_)
+            | HirExpressionKind::Copy(_) => None,
         }
-        HirTerminator::Match { scrutinee, arms } => first_generic_runtime_expression_location(
-            scrutinee,
-            module,
-            type_environment,
-        )
-        .or_else(|| {
-            arms.iter().find_map(|arm| {
-                arm.guard.as_ref().and_then(|guard| {
-                    first_generic_runtime_expression_location(guard, module, type_environment)
-                })
-            })
-        }),
-        HirTerminator::Jump { .. }
-        | HirTerminator::Break { .. }
-        | HirTerminator::Continue { .. }
-        | HirTerminator::Uninitialized
-        | HirTerminator::RuntimeFailure { .. }
-        | HirTerminator::AssertFailure { .. } => None,
     }
-}
 
-fn first_generic_runtime_expression_location(
-    expression: &HirExpression,
-    module: &HirModule,
-    type_environment: &TypeEnvironment,
-) -> Option<SourceLocation> {
-    if matches!(
-        type_environment.get(expression.ty),
-        Some(TypeDefinition::GenericInstance(_))
-    ) {
-        return Some(
-            module
-                .side_table
-                .value_source_location(expression.id)
-                .cloned()
-                .unwrap_or_default(),
-        );
+    /// Classifies `expression` itself, without looking at its children.
+    fn unsupported_reason(
+        &self,
+        expression: &HirExpression,
+    ) -> Option<UnsupportedBackendFeatureReason> {
+        match &expression.kind {
+            HirExpressionKind::VariantConstruct {
+                carrier: HirVariantCarrier::Option,
+                ..
+            }
+            | HirExpressionKind::VariantPayloadGet {
+                carrier: HirVariantCarrier::Option,
+                ..
+            } => Some(UnsupportedBackendFeatureReason::OptionValues),
+            HirExpressionKind::Collection(items) if !items.is_empty() => {
+                let element = self
+                    .type_environment
+                    .collection_element_type(expression.ty)?;
+                match classify_hir_type(element, self.type_environment) {
+                    HirTypeClass::HeapAllocated | HirTypeClass::Function => None,
+                    HirTypeClass::Unit
+                    | HirTypeClass::Bool
+                    | HirTypeClass::Char
+                    | HirTypeClass::Int
+                    | HirTypeClass::Float => {
+                        Some(UnsupportedBackendFeatureReason::ScalarCollectionElements)
+                    }
+                }
+            }
+            _ => None,
+        }
     }
 
-    match &expression.kind {
-        HirExpressionKind::BinOp { left, right, .. } => {
-            first_generic_runtime_expression_location(left, module, type_environment).or_else(
-                || first_generic_runtime_expression_location(right, module, type_environment),
-            )
+    /// Classifies statements GC emission cannot lower; always `None` outside GC mode.
+    fn unsupported_gc_statement_reason(
+        &self,
+        statement: &HirStatement,
+    ) -> Option<UnsupportedBackendFeatureReason> {
+        if !self.wasm_gc {
+            return None;
         }
-        HirExpressionKind::UnaryOp { operand, .. }
-        | HirExpressionKind::TupleGet { tuple: operand, .. }
-        | HirExpressionKind::FallibleUnwrapSuccess { result: operand }
-        | HirExpressionKind::FallibleUnwrapError { result: operand }
-        | HirExpressionKind::Cast {
-            source: operand, ..
+
+        match &statement.kind {
+            HirStatementKind::MapOp { .. } => {
+                Some(UnsupportedBackendFeatureReason::HashmapOperation)
+            }
+            HirStatementKind::CastOp { .. } => Some(UnsupportedBackendFeatureReason::RuntimeCasts),
+            HirStatementKind::NumericOp { .. } => {
+                Some(UnsupportedBackendFeatureReason::CheckedNumericOperations)
+            }
+            HirStatementKind::FormatFloat { .. } => {
+                Some(UnsupportedBackendFeatureReason::FloatFormatting)
+            }
+            HirStatementKind::ValidateFloat { .. } => {
+                Some(UnsupportedBackendFeatureReason::FloatBoundaryValidation)
+            }
+            _ => None,
         }
-        | HirExpressionKind::VariantPayloadGet {
-            source: operand, ..
-        } => first_generic_runtime_expression_location(operand, module, type_environment),
-        HirExpressionKind::StructConstruct { fields, .. } => {
-            fields.iter().find_map(|(_, value)| {
-                first_generic_runtime_expression_location(value, module, type_environment)
-            })
+    }
+
+    /// Classifies values GC emission cannot lower, without looking at their children; always
+    /// `None` outside GC mode.
+    ///
+    /// WHY: `String` concatenation converts `Bool`, `Char` and `Float` chunks through their
+    /// `String` casts, which GC emission does not implement yet either.
+    fn unsupported_gc_expression_reason(
+        &self,
+        expression: &HirExpression,
+    ) -> Option<UnsupportedBackendFeatureReason> {
+        if !self.wasm_gc {
+            return None;
         }
-        HirExpressionKind::Collection(items)
-        | HirExpressionKind::TupleConstruct { elements: items } => items.iter().find_map(|item| {
-            first_generic_runtime_expression_location(item, module, type_environment)
-        }),
-        HirExpressionKind::MapLiteral(entries) => entries.iter().find_map(|entry| {
-            first_generic_runtime_expression_location(&entry.key, module, type_environment).or_else(
-                || {
-                    first_generic_runtime_expression_location(
-                        &entry.value,
-                        module,
-                        type_environment,
-                    )
-                },
-            )
-        }),
-        HirExpressionKind::Range { start, end } => {
-            first_generic_runtime_expression_location(start, module, type_environment).or_else(
-                || first_generic_runtime_expression_location(end, module, type_environment),
-            )
+
+        let builtins = self.type_environment.builtins();
+        match &expression.kind {
+            HirExpressionKind::MapLiteral(_) => {
+                Some(UnsupportedBackendFeatureReason::HashmapConstruction)
+            }
+            HirExpressionKind::FallibleUnwrapSuccess { .. }
+            | HirExpressionKind::FallibleUnwrapError { .. } => {
+                Some(UnsupportedBackendFeatureReason::FallibleResults)
+            }
+            HirExpressionKind::Cast { policy, .. }
+                if !matches!(
+                    policy,
+                    BuiltinCastPolicyId::IntToFloat | BuiltinCastPolicyId::CharToInt
+                ) =>
+            {
+                Some(UnsupportedBackendFeatureReason::RuntimeCasts)
+            }
+            HirExpressionKind::BinOp {
+                left,
+                op: HirBinOp::Add,
+                right,
+            } if expression.ty == builtins.string
+                && [left, right].into_iter().any(|operand| {
+                    [builtins.bool, builtins.char, builtins.float].contains(&operand.ty)
+                }) =>
+            {
+                Some(UnsupportedBackendFeatureReason::RuntimeCasts)
+            }
+            _ => None,
         }
-        HirExpressionKind::VariantConstruct { fields, .. } => fields.iter().find_map(|field| {
-            first_generic_runtime_expression_location(&field.value, module, type_environment)
-        }),
-        HirExpressionKind::Int(_)
-        | HirExpressionKind::Float(_)
-        | HirExpressionKind::Bool(_)
-        | HirExpressionKind::Char(_)
-        | HirExpressionKind::StringLiteral(_)
-        | HirExpressionKind::Load(_)
-        | HirExpressionKind::Copy(_) => None,
+    }
+
+    fn expression_location(&self, expression: &HirExpression) -> SourceLocation {
+        self.module
+            .side_table
+            .value_source_location(expression.id)
+            .cloned()
+            .unwrap_or_default()
     }
 }
 
@@ -8,7 +8,9 @@
 
 use crate::compiler_frontend::compiler_messages::CompilerDiagnostic;
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
-use crate::compiler_frontend::external_packages::{ExternalFunctionId, ExternalPackageRegistry};
+use crate::compiler_frontend::external_packages::{
+    ExternalFunctionId, ExternalPackageRegistry, ExternalWasmLowering,
+};
 use crate::compiler_frontend::hir::module::HirModule;
 use crate::compiler_frontend::hir::reachability::{
     ReachableExternalCall, collect_reachability_from_start,
@@ -19,7 +21,10 @@ use crate::compiler_frontend::symbols::string_interning::StringTable;
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BackendTarget {
     Js,
+    /// HTML-Wasm page modules, which import host functions from the page's JS glue.
     Wasm,
+    /// Standalone WASI preview1 command modules.
+    Wasi,
 }
 
 /// Failure mode for external-package backend support validation.
@@ -36,6 +41,7 @@ impl BackendTarget {
         match self {
             BackendTarget::Js => "JavaScript",
             BackendTarget::Wasm => "Wasm",
+            BackendTarget::Wasi => "WASI",
         }
     }
 }
@@ -89,9 +95,12 @@ fn has_backend_lowering(
                         | crate::compiler_frontend::external_packages::ExternalJsLowering::ExternalModuleExport { .. }
                 )
             }),
-        BackendTarget::Wasm => registry
-            .get_function_by_id(id)
-            .is_some_and(|def| def.lowerings.wasm.is_some()),
+        BackendTarget::Wasm => registry.get_function_by_id(id).is_some_and(|def| {
+            matches!(def.lowerings.wasm, Some(ExternalWasmLowering::HostFunction(_)))
+        }),
+        BackendTarget::Wasi => registry.get_function_by_id(id).is_some_and(|def| {
+            matches!(def.lowerings.wasm, Some(ExternalWasmLowering::WasiAdapter(_)))
+        }),
     }
 }
 
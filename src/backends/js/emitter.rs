@@ -4,6 +4,7 @@
 //! WHY: JS is the stable near-term backend and needs deterministic lowering output.
 
 use crate::backends::js::JsModule;
+use crate::backends::js::output::JsSourceLine;
 use crate::backends::js::runtime::NumericRuntimeHelperUsage;
 use crate::backends::js::{JsFunctionEmissionPolicy, JsLoweringConfig};
 use crate::compiler_frontend::analysis::borrow_checker::BorrowCheckReport;
@@ -17,7 +18,9 @@ use crate::compiler_frontend::hir::ids::{BlockId, FieldId, FunctionId, HirValueI
 use crate::compiler_frontend::hir::module::HirModule;
 use crate::compiler_frontend::hir::numeric::HirNumericOperands;
 use crate::compiler_frontend::hir::patterns::HirPattern;
-use crate::compiler_frontend::hir::reachability::collect_reachability_from_start;
+use crate::compiler_frontend::hir::reachability::{
+    HirReachabilityInput, collect_hir_reachability, collect_reachability_from_start,
+};
 use crate::compiler_frontend::hir::reactivity::ReactiveSourceId;
 use crate::compiler_frontend::hir::statements::HirStatementKind;
 use crate::compiler_frontend::hir::terminators::HirTerminator;
@@ -44,6 +47,10 @@ pub(crate) struct JsEmitter<'hir> {
     pub(crate) type_environment: &'hir TypeEnvironment,
     pub(crate) out: String,
     pub(crate) indent: usize,
+    /// Number of complete lines written to `out`.
+    pub(crate) emitted_lines: usize,
+    /// Generated-line to source-location table, filled when `track_source_lines` is enabled.
+    pub(crate) source_lines: Vec<JsSourceLine>,
     pub(crate) blocks_by_id: HashMap<BlockId, &'hir HirBlock>,
     pub(crate) function_name_by_id: HashMap<FunctionId, String>,
     pub(crate) local_name_by_id: HashMap<LocalId, String>,
@@ -90,6 +97,8 @@ impl<'hir> JsEmitter<'hir> {
             type_environment,
             out: String::new(),
             indent: 0,
+            emitted_lines: 0,
+            source_lines: Vec::new(),
             blocks_by_id,
             function_name_by_id: HashMap::new(),
             local_name_by_id: HashMap::new(),
@@ -157,7 +166,9 @@ impl<'hir> JsEmitter<'hir> {
         Ok(JsModule {
             source: self.out.clone(),
             function_name_by_id: self.function_name_by_id.clone(),
+            field_name_by_id: self.field_name_by_id.clone(),
             referenced_external_functions: self.referenced_external_functions.clone(),
+            source_lines: self.source_lines.clone(),
         })
     }
 
@@ -167,6 +178,9 @@ impl<'hir> JsEmitter<'hir> {
             JsFunctionEmissionPolicy::ReachableFromStart => {
                 Some(self.collect_js_reachable_functions()?)
             }
+            JsFunctionEmissionPolicy::ReachableFromStartAndExports => {
+                Some(self.collect_js_reachable_functions_from_exports()?)
+            }
         };
 
         let mut functions = self
@@ -734,6 +748,22 @@ impl<'hir> JsEmitter<'hir> {
         let reachability = collect_reachability_from_start(self.hir)?;
         Ok(reachability.reachable_functions)
     }
+
+    fn collect_js_reachable_functions_from_exports(
+        &self,
+    ) -> Result<rustc_hash::FxHashSet<FunctionId>, CompilerError> {
+        let mut root_functions = vec![self.hir.start_function];
+        root_functions.extend(self.hir.function_ids_by_origin.values().copied());
+        // Hash-map order must not leak into the traversal.
+        root_functions.sort_by_key(|function_id| function_id.0);
+        root_functions.dedup();
+
+        let reachability = collect_hir_reachability(HirReachabilityInput {
+            hir: self.hir,
+            root_functions,
+        })?;
+        Ok(reachability.reachable_functions)
+    }
 }
 
 fn collect_statement_cast_policies(
@@ -60,6 +60,7 @@ impl<'hir> JsEmitter<'hir> {
                 }
             }
             self.indent -= 1;
+            self.record_source_location(None);
             self.emit_line("} catch (__bs_err) {");
             self.indent += 1;
             self.emit_line("if (__bs_err && __bs_err.__bs_result_propagate === true) {");
@@ -85,6 +86,7 @@ impl<'hir> JsEmitter<'hir> {
         self.current_function = None;
         emit_body_result?;
 
+        self.record_source_location(None);
         self.indent -= 1;
         self.emit_line("}");
 
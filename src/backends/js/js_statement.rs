@@ -10,6 +10,7 @@ use crate::compiler_frontend::analysis::borrow_checker::LocalMode;
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
 use crate::compiler_frontend::hir::expressions::{HirExpression, HirExpressionKind, HirMapOp};
 use crate::compiler_frontend::hir::functions::HirFunction;
+use crate::compiler_frontend::hir::hir_side_table::HirLocation;
 use crate::compiler_frontend::hir::ids::{BlockId, HirNodeId, LocalId};
 use crate::compiler_frontend::hir::numeric::{
     HirNumericOp, HirNumericOperands, NumericFailureMode,
@@ -28,11 +29,19 @@ impl<'hir> JsEmitter<'hir> {
             self.emit_statement(statement)?;
         }
 
+        // Terminators are emitted by the caller right after the block's statements.
+        let terminator_location = self
+            .hir
+            .side_table
+            .hir_source_location_for_hir(HirLocation::Terminator(block.id));
+        self.record_source_location(terminator_location);
+
         Ok(())
     }
 
     pub(crate) fn emit_statement(&mut self, statement: &HirStatement) -> Result<(), CompilerError> {
         self.emit_location_comment(&statement.location);
+        self.record_source_location(Some(&statement.location));
 
         match &statement.kind {
             HirStatementKind::Assign { target, value } => {
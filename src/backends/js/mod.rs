@@ -15,6 +15,7 @@ mod output;
 mod package_bindings;
 mod reachability;
 mod runtime;
+mod source_map;
 mod symbols;
 mod value_use;
 
@@ -26,10 +27,14 @@ mod tests;
 
 pub(crate) use emitter::JsEmitter;
 pub use emitter::lower_hir_to_js;
+pub(crate) use identifiers::is_js_reserved;
+pub(crate) use js_expr::escape_js_string;
+pub use output::{JsSourceLine, JsSourceOrigin};
+pub use source_map::JsSourceMap;
 pub(crate) use symbols::{builtin_error_code_js_field_name, builtin_error_message_js_field_name};
 
 use crate::compiler_frontend::external_packages::{ExternalFunctionId, ExternalPackageRegistry};
-use crate::compiler_frontend::hir::ids::FunctionId;
+use crate::compiler_frontend::hir::ids::{FieldId, FunctionId};
 use std::collections::{HashMap, HashSet};
 
 /// Policy controlling which HIR functions are emitted in a JS bundle.
@@ -48,6 +53,25 @@ pub enum JsFunctionEmissionPolicy {
     /// WHY: HTML page bundles execute from one entry point, and unreachable source-backed package
     /// wrappers must not request runtime glue or assets.
     ReachableFromStart,
+
+    /// Emit only functions reachable from `start` or from a directly exported function.
+    ///
+    /// WHY: ES module bundles are entered both by evaluating the module and by callers of its
+    /// exports, which `HirModule::function_ids_by_origin` records.
+    ReachableFromStartAndExports,
+}
+
+/// Where `@core/io` console helpers write their output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsConsoleOutput {
+    /// The host `console` object (browsers and any other JS host).
+    Console,
+
+    /// Node's `process.stdout` and `process.stderr` streams, one line per call.
+    ///
+    /// WHY: CLI scripts need plain stream output that pipes and redirects like any other
+    /// command, without the console's formatting or buffering.
+    ProcessStreams,
 }
 
 /// Configuration for JS lowering.
@@ -59,6 +83,10 @@ pub struct JsLoweringConfig {
     /// Emit source location comments.
     pub emit_locations: bool,
 
+    /// Record which `.bst` source position each generated line came from.
+    /// WHY: the dev server maps runtime errors back to source lines with this table.
+    pub track_source_lines: bool,
+
     /// Automatically invoke the module start function.
     pub auto_invoke_start: bool,
 
@@ -72,6 +100,9 @@ pub struct JsLoweringConfig {
     /// WHY: only the HTML builder can emit the matching ES module glue. Direct JS backend
     /// lowering must reject these exports unless that builder path explicitly opts in.
     pub external_module_export_glue_enabled: bool,
+
+    /// Output target of the `@core/io` console helpers.
+    pub console_output: JsConsoleOutput,
 }
 
 impl JsLoweringConfig {
@@ -85,10 +116,12 @@ impl JsLoweringConfig {
         JsLoweringConfig {
             pretty: !release_build,
             emit_locations: false,
+            track_source_lines: false,
             auto_invoke_start: false,
             function_emission_policy: JsFunctionEmissionPolicy::AllFunctions,
             external_package_registry: Arc::new(ExternalPackageRegistry::new()),
             external_module_export_glue_enabled: false,
+            console_output: JsConsoleOutput::Console,
         }
     }
 
@@ -109,6 +142,23 @@ impl JsLoweringConfig {
         config
     }
 
+    /// Standalone ES module lowering config.
+    ///
+    /// WHAT: emits functions reachable from `start` or the module's exports and writes console
+    /// output to the process streams.
+    /// WHY: the JS module builder runs bundles under Node, where there is no HTML glue and
+    /// exported functions are entry points of their own.
+    pub(crate) fn es_module(
+        release_build: bool,
+        external_package_registry: Arc<ExternalPackageRegistry>,
+    ) -> Self {
+        let mut config = Self::direct_js(release_build);
+        config.function_emission_policy = JsFunctionEmissionPolicy::ReachableFromStartAndExports;
+        config.external_package_registry = external_package_registry;
+        config.console_output = JsConsoleOutput::ProcessStreams;
+        config
+    }
+
     /// HTML-Wasm companion-JS lowering config.
     ///
     /// WHAT: emits only entry-reachable JS used by the Wasm bootstrap while keeping generated
@@ -147,7 +197,13 @@ pub struct JsModule {
     /// Complete JS source code.
     pub source: String,
     pub function_name_by_id: HashMap<FunctionId, String>,
+    /// Emitted object key of every struct field.
+    /// WHY: the JS module builder declares struct interfaces with the keys the runtime uses.
+    pub field_name_by_id: HashMap<FieldId, String>,
     /// Set of external function IDs referenced while lowering emitted JS functions.
     /// WHY: the HTML builder uses this to decide which generated glue wrappers to emit.
     pub referenced_external_functions: HashSet<ExternalFunctionId>,
+    /// Source positions of generated lines, ordered by `generated_line`.
+    /// Empty unless `JsLoweringConfig::track_source_lines` is enabled.
+    pub source_lines: Vec<JsSourceLine>,
 }
@@ -1,7 +1,7 @@
 //! JS source text emission and indentation.
 //!
-//! WHAT: owns emitting formatted lines and source-location comments into the
-//! output buffer.
+//! WHAT: owns emitting formatted lines, source-location comments and the generated-line to
+//! source-location table into the output buffer.
 //! WHY: every JS backend emission path writes through here, so indentation and
 //! location tracking live in one place.
 //!
@@ -9,7 +9,34 @@
 //! identifier generation. Those responsibilities belong to their focused owners.
 
 use crate::backends::js::JsEmitter;
+use crate::compiler_frontend::compiler_messages::render::{
+    display_column_number, display_line_number, source_file_scope_path,
+};
 use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
+use std::path::PathBuf;
+
+/// Start of a run of generated JS lines lowered from one Beanstalk source position.
+///
+/// The run continues until the next entry, so looking up a generated line means finding the
+/// last entry whose `generated_line` is not after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsSourceLine {
+    /// 1-based line in [`crate::backends::js::JsModule::source`] where the run starts.
+    pub generated_line: usize,
+    /// Originating source position, or `None` for runtime helpers and other generated code.
+    pub origin: Option<JsSourceOrigin>,
+}
+
+/// A Beanstalk source position in display form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsSourceOrigin {
+    /// Logical source file path taken from the location scope.
+    pub file: PathBuf,
+    /// 1-based source line.
+    pub line: usize,
+    /// 1-based source column.
+    pub column: usize,
+}
 
 impl<'hir> JsEmitter<'hir> {
     pub(crate) fn emit_line(&mut self, line: &str) {
@@ -21,6 +48,7 @@ impl<'hir> JsEmitter<'hir> {
 
         self.out.push_str(line);
         self.out.push('\n');
+        self.emitted_lines += 1 + line.matches('\n').count();
     }
 
     pub(crate) fn emit_location_comment(&mut self, location: &SourceLocation) {
@@ -34,6 +62,37 @@ impl<'hir> JsEmitter<'hir> {
         self.emit_line(&format!("// source {line}:{start}-{end}"));
     }
 
+    /// Record that the next emitted line was lowered from `location`.
+    ///
+    /// WHAT: appends to the source line table when `track_source_lines` is enabled. `None` marks
+    ///       the start of generated code without a source origin, such as trailing helpers.
+    /// WHY: runtime error reporting maps a thrown JS line back to the `.bst` line that produced it.
+    pub(crate) fn record_source_location(&mut self, location: Option<&SourceLocation>) {
+        if !self.config.track_source_lines {
+            return;
+        }
+
+        let origin = location
+            .filter(|location| !location.scope.as_components().is_empty())
+            .map(|location| JsSourceOrigin {
+                file: source_file_scope_path(&location.scope, self.string_table),
+                line: display_line_number(location.start_pos.line_number) as usize,
+                column: display_column_number(location.start_pos.char_column) as usize,
+            });
+        let generated_line = self.emitted_lines + 1;
+
+        match self.source_lines.last_mut() {
+            Some(last) if last.origin == origin => {}
+            // Nothing was emitted for the previous position, so the new one replaces it.
+            Some(last) if last.generated_line == generated_line => last.origin = origin,
+            None if origin.is_none() => {}
+            _ => self.source_lines.push(JsSourceLine {
+                generated_line,
+                origin,
+            }),
+        }
+    }
+
     pub(crate) fn with_indent<F>(&mut self, mut callback: F)
     where
         F: FnMut(&mut Self),
@@ -1,12 +1,12 @@
 //! JavaScript helpers for `@core/io` console functions and input polling.
 //!
-//! WHAT: emits the browser console helpers used by `io.print`, `io.line`, `io.debug`,
-//! `io.warn`, and `io.error`, and the browser input polling helpers used by `io.input.*`,
-//! only when the corresponding external function is reachable.
+//! WHAT: emits the browser console (or Node stream) helpers used by `io.print`, `io.line`,
+//! `io.debug`, `io.warn`, and `io.error`, and the browser input polling helpers used by
+//! `io.input.*`, only when the corresponding external function is reachable.
 //! WHY: keeping IO helper emission demand-driven prevents the runtime prelude from
 //! unconditionally including console output or input code in programs that never call it.
 
-use crate::backends::js::JsEmitter;
+use crate::backends::js::{JsConsoleOutput, JsEmitter};
 
 /// Stable JS runtime names for the `@core/io` input helpers.
 ///
@@ -39,6 +39,11 @@ impl<'hir> JsEmitter<'hir> {
     }
 
     fn emit_core_io_console_helpers(&mut self) {
+        if self.config.console_output == JsConsoleOutput::ProcessStreams {
+            self.emit_core_io_stream_helpers();
+            return;
+        }
+
         let helpers: &[(&str, &str)] = &[
             (
                 "__bs_io_print",
@@ -76,6 +81,47 @@ impl<'hir> JsEmitter<'hir> {
         self.emit_referenced_core_helpers(helpers);
     }
 
+    /// Node stream variants of the console helpers.
+    ///
+    /// WHAT: each call writes one line, like `console.log`; `warn` and `error` go to stderr.
+    fn emit_core_io_stream_helpers(&mut self) {
+        let helpers: &[(&str, &str)] = &[
+            (
+                "__bs_io_print",
+                "function __bs_io_print(value) { __bs_io_write(process.stdout, value); }",
+            ),
+            (
+                "__bs_io_line",
+                "function __bs_io_line(value) { __bs_io_write(process.stdout, value); }",
+            ),
+            (
+                "__bs_io_debug",
+                "function __bs_io_debug(value) { __bs_io_write(process.stdout, value); }",
+            ),
+            (
+                "__bs_io_warn",
+                "function __bs_io_warn(value) { __bs_io_write(process.stderr, value); }",
+            ),
+            (
+                "__bs_io_error",
+                "function __bs_io_error(value) { __bs_io_write(process.stderr, value); }",
+            ),
+        ];
+
+        if helpers
+            .iter()
+            .any(|(js_name, _)| self.referenced_external_runtime_function(js_name))
+        {
+            self.emit_line("function __bs_io_write(stream, value) {");
+            self.with_indent(|emitter| {
+                emitter.emit_line("stream.write(__bs_value_to_string(value) + \"\\n\");");
+            });
+            self.emit_line("}");
+        }
+
+        self.emit_referenced_core_helpers(helpers);
+    }
+
     fn emit_core_io_input_helpers(&mut self) {
         if !INPUT_HELPER_NAMES
             .iter()
@@ -13,7 +13,7 @@ impl<'hir> JsEmitter<'hir> {
     ///
     /// WHAT: `__bs_reactive_binding` constructs a binding record tagged with a stable source id;
     /// `__bs_reactive_schedule` marks a source dirty and batches a flush; `__bs_reactive_flush`
-    /// rerenders every mounted fragment that depends on a dirty source.
+    /// rerenders every mounted fragment that depends on a dirty source by patching its slot.
     /// WHY: reactive source tracking is an opt-in runtime subsystem. Emitting it only when reachable
     /// emitted code declares or reads reactive sources keeps non-reactive bundles small.
     pub(crate) fn emit_runtime_reactive_source_helpers(&mut self) {
@@ -39,9 +39,11 @@ impl<'hir> JsEmitter<'hir> {
     /// non-reactive bootstrap, but treats reactive template-string values as mounted fragments: it
     /// renders the initial HTML, registers the fragment against every dependency source, and gives
     /// the fragment a `render()` function that the scheduler calls during a dirty-source flush.
+    /// Rerenders patch the slot's existing DOM through `__bs_dom_patch_slot`.
     /// WHY: Phase 7 needs a single slot-hydration path that handles both plain-string
     ///      fragments and reactive template objects without leaking backend details into HIR.
     pub(crate) fn emit_runtime_mount_helper(&mut self) {
+        self.emit_dom_patch_helpers();
         self.emit_mount_helper();
     }
 
@@ -223,23 +225,112 @@ impl<'hir> JsEmitter<'hir> {
         self.emit_line("");
     }
 
+    /// Emits the DOM patching helpers used when a mounted fragment rerenders.
+    ///
+    /// WHAT: `__bs_dom_patch_slot(slot, html)` parses the new HTML into a detached `<template>`
+    /// and morphs the slot's children towards it. Nodes are matched by position: a node of the
+    /// same type and tag is kept and patched in place (text values, attributes, then children),
+    /// anything else is replaced, and surplus nodes are removed. Hosts without a DOM parser fall
+    /// back to replacing the slot's HTML.
+    /// WHY: replacing the whole slot destroys focus, text selection and typed input values. Only
+    ///      changed text nodes and attributes are written, so an `<input>` whose `value` attribute
+    ///      did not change keeps what the user typed. Keyed loop diffing and nested live regions
+    ///      can later replace the positional match without changing the flush.
+    fn emit_dom_patch_helpers(&mut self) {
+        self.emit_line("function __bs_dom_patch_slot(slot, html) {");
+        self.with_indent(|emitter| {
+            emitter.emit_line("if (!slot.childNodes || typeof document === \"undefined\" || typeof document.createElement !== \"function\") {");
+            emitter.with_indent(|em| {
+                em.emit_line("slot.innerHTML = \"\";");
+                em.emit_line("slot.insertAdjacentHTML(\"beforeend\", html);");
+                em.emit_line("return;");
+            });
+            emitter.emit_line("}");
+            emitter.emit_line("const parsed = document.createElement(\"template\");");
+            emitter.emit_line("parsed.innerHTML = html;");
+            emitter.emit_line("__bs_dom_patch_children(slot, parsed.content);");
+        });
+        self.emit_line("}");
+        self.emit_line("");
+
+        self.emit_line("function __bs_dom_patch_children(target, source) {");
+        self.with_indent(|emitter| {
+            emitter.emit_line("const next = Array.from(source.childNodes);");
+            emitter.emit_line("let current = target.firstChild;");
+            emitter.emit_line("for (const node of next) {");
+            emitter.with_indent(|em| {
+                em.emit_line("if (current && current.nodeType === node.nodeType && current.nodeName === node.nodeName) {");
+                em.with_indent(|inner| {
+                    inner.emit_line("__bs_dom_patch_node(current, node);");
+                    inner.emit_line("current = current.nextSibling;");
+                });
+                em.emit_line("} else {");
+                em.with_indent(|inner| inner.emit_line("target.insertBefore(node, current);"));
+                em.emit_line("}");
+            });
+            emitter.emit_line("}");
+            emitter.emit_line("while (current) {");
+            emitter.with_indent(|em| {
+                em.emit_line("const following = current.nextSibling;");
+                em.emit_line("target.removeChild(current);");
+                em.emit_line("current = following;");
+            });
+            emitter.emit_line("}");
+        });
+        self.emit_line("}");
+        self.emit_line("");
+
+        self.emit_line("function __bs_dom_patch_node(target, source) {");
+        self.with_indent(|emitter| {
+            emitter.emit_line("if (target.nodeType !== 1) {");
+            emitter.with_indent(|em| {
+                em.emit_line("if (target.nodeValue !== source.nodeValue) {");
+                em.with_indent(|inner| inner.emit_line("target.nodeValue = source.nodeValue;"));
+                em.emit_line("}");
+                em.emit_line("return;");
+            });
+            emitter.emit_line("}");
+            emitter.emit_line("for (const attribute of Array.from(target.attributes)) {");
+            emitter.with_indent(|em| {
+                em.emit_line("if (!source.hasAttribute(attribute.name)) {");
+                em.with_indent(|inner| inner.emit_line("target.removeAttribute(attribute.name);"));
+                em.emit_line("}");
+            });
+            emitter.emit_line("}");
+            emitter.emit_line("for (const attribute of Array.from(source.attributes)) {");
+            emitter.with_indent(|em| {
+                em.emit_line("if (target.getAttribute(attribute.name) !== attribute.value) {");
+                em.with_indent(|inner| {
+                    inner.emit_line("target.setAttribute(attribute.name, attribute.value);");
+                });
+                em.emit_line("}");
+            });
+            emitter.emit_line("}");
+            emitter.emit_line("__bs_dom_patch_children(target, source);");
+        });
+        self.emit_line("}");
+        self.emit_line("");
+    }
+
     /// Emits `__bs_mount_template_fragment`, the bridge between `start()` fragments and the DOM.
     ///
     /// WHAT: for a plain string fragment the helper behaves exactly like the direct bootstrap
     /// (`insertAdjacentHTML("beforeend", ...)`). For a reactive template-string value it creates a
     /// mounted fragment record, renders the initial HTML, registers the record against every
-    /// dependency source, and exposes a `render()` closure that rerenders the whole slot.
+    /// dependency source, and exposes a `render()` closure that patches the slot to the new HTML.
+    /// With `hydrate` set the slot already holds prerendered HTML: plain fragments are left alone
+    /// and template fragments patch the existing nodes instead of inserting new ones.
     /// WHY: keeping this logic in one backend helper lets the HTML bootstrap stay a simple loop
     ///      while preserving source-order slot behavior and making rerendering a scheduler concern.
     fn emit_mount_helper(&mut self) {
-        self.emit_line("function __bs_mount_template_fragment(slot, fragment) {");
+        self.emit_line("function __bs_mount_template_fragment(slot, fragment, hydrate) {");
         self.with_indent(|emitter| {
             emitter.emit_line("if (!slot) {");
             emitter.with_indent(|em| em.emit_line("throw new Error(\"Missing runtime mount slot\");"));
             emitter.emit_line("}");
             emitter.emit_line("if (fragment === null || fragment === undefined || typeof fragment === \"string\") {");
             emitter.with_indent(|em| {
-                em.emit_line("slot.insertAdjacentHTML(\"beforeend\", fragment || \"\");");
+                em.emit_line("if (!hydrate) slot.insertAdjacentHTML(\"beforeend\", fragment || \"\");");
                 em.emit_line("return;");
             });
             emitter.emit_line("}");
@@ -251,14 +342,19 @@ impl<'hir> JsEmitter<'hir> {
                     em.emit_line("dependencies: fragment.dependencies || [],");
                     em.emit_line("render: function() {");
                     em.with_indent(|inner| {
-                        inner.emit_line("mounted.slot.innerHTML = \"\";");
-                        inner.emit_line("mounted.slot.insertAdjacentHTML(\"beforeend\", fragment.snapshot());");
+                        inner.emit_line("__bs_dom_patch_slot(mounted.slot, fragment.snapshot());");
                     });
                     em.emit_line("}");
                 });
                 mounted_emitter.emit_line("};");
-                mounted_emitter.emit_line("mounted.slot.innerHTML = \"\";");
-                mounted_emitter.emit_line("mounted.slot.insertAdjacentHTML(\"beforeend\", fragment.snapshot());");
+                mounted_emitter.emit_line("if (hydrate) {");
+                mounted_emitter.with_indent(|em| em.emit_line("mounted.render();"));
+                mounted_emitter.emit_line("} else {");
+                mounted_emitter.with_indent(|em| {
+                    em.emit_line("mounted.slot.innerHTML = \"\";");
+                    em.emit_line("mounted.slot.insertAdjacentHTML(\"beforeend\", fragment.snapshot());");
+                });
+                mounted_emitter.emit_line("}");
                 mounted_emitter.emit_line("for (var i = 0; i < mounted.dependencies.length; i++) {");
                 mounted_emitter.with_indent(|em| {
                     em.emit_line("var sourceId = mounted.dependencies[i];");
@@ -276,7 +372,7 @@ impl<'hir> JsEmitter<'hir> {
                 mounted_emitter.emit_line("return;");
             });
             emitter.emit_line("}");
-            emitter.emit_line("slot.insertAdjacentHTML(\"beforeend\", String(fragment));");
+            emitter.emit_line("if (!hydrate) slot.insertAdjacentHTML(\"beforeend\", String(fragment));");
         });
         self.emit_line("}");
         self.emit_line("");
@@ -0,0 +1,147 @@
+//! Source Map v3 encoding for lowered JS bundles.
+//!
+//! WHAT: turns the source line table recorded during lowering into a standard v3 source map,
+//!       with one segment per generated line at its first non-whitespace column.
+//! WHY: browser devtools use source maps to show `.bst` files, set breakpoints in them and
+//!      symbolize stack traces. HIR already splits nested expressions into their own
+//!      located statements, so one segment per generated line follows the source closely.
+
+use crate::backends::js::output::{JsSourceLine, JsSourceOrigin};
+use serde_json::json;
+use std::path::PathBuf;
+
+const BASE64_DIGITS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encoded mappings for one generated script and the source files they refer to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsSourceMap {
+    /// Source files in index order, as recorded in [`JsSourceOrigin::file`].
+    pub sources: Vec<PathBuf>,
+    /// The v3 `mappings` field.
+    pub mappings: String,
+}
+
+impl JsSourceMap {
+    /// Builds mappings for `generated_script`, which holds the lowered bundle starting at
+    /// 0-based line `bundle_start_line`.
+    ///
+    /// Lines after the last recorded entry keep its origin until the script ends, so callers
+    /// should record a `None` origin where mapped code stops.
+    pub fn from_source_lines(
+        generated_script: &str,
+        bundle_start_line: usize,
+        source_lines: &[JsSourceLine],
+    ) -> Self {
+        let mut sources: Vec<PathBuf> = Vec::new();
+        let mut mappings = String::new();
+        let mut previous = SegmentState::default();
+        let mut entries = source_lines.iter().peekable();
+        let mut current: Option<&JsSourceOrigin> = None;
+        let mut mapped = false;
+
+        for (script_line, text) in generated_script.split('\n').enumerate() {
+            if script_line > 0 {
+                mappings.push(';');
+            }
+            let Some(bundle_line) = (script_line + 1)
+                .checked_sub(bundle_start_line)
+                .filter(|line| *line > 0)
+            else {
+                continue;
+            };
+            while let Some(entry) = entries.next_if(|entry| entry.generated_line <= bundle_line) {
+                current = entry.origin.as_ref();
+            }
+            let Some(column) = text.find(|character: char| !character.is_whitespace()) else {
+                continue;
+            };
+
+            match current {
+                Some(origin) => {
+                    let source_index = match sources.iter().position(|file| *file == origin.file) {
+                        Some(index) => index,
+                        None => {
+                            sources.push(origin.file.clone());
+                            sources.len() - 1
+                        }
+                    };
+                    let segment = SegmentState {
+                        source_index: source_index as i64,
+                        source_line: origin.line.saturating_sub(1) as i64,
+                        source_column: origin.column.saturating_sub(1) as i64,
+                    };
+                    encode_vlq(&mut mappings, column as i64);
+                    encode_vlq(&mut mappings, segment.source_index - previous.source_index);
+                    encode_vlq(&mut mappings, segment.source_line - previous.source_line);
+                    encode_vlq(
+                        &mut mappings,
+                        segment.source_column - previous.source_column,
+                    );
+                    previous = segment;
+                    mapped = true;
+                }
+                // A single-field segment ends the previous mapping, so devtools do not attribute
+                // unmapped runtime code to the last mapped statement.
+                None if mapped => {
+                    encode_vlq(&mut mappings, column as i64);
+                    mapped = false;
+                }
+                None => {}
+            }
+        }
+
+        Self { sources, mappings }
+    }
+
+    /// Renders the map as v3 JSON.
+    ///
+    /// `source_urls` and `sources_content` are parallel to [`JsSourceMap::sources`]; missing
+    /// contents are written as `null` so devtools fetch the source URL instead.
+    pub fn to_json(
+        &self,
+        file: &str,
+        source_urls: &[String],
+        sources_content: &[Option<String>],
+    ) -> String {
+        json!({
+            "version": 3,
+            "file": file,
+            "sources": source_urls,
+            "sourcesContent": sources_content,
+            "names": [],
+            "mappings": self.mappings,
+        })
+        .to_string()
+    }
+}
+
+/// Source fields of the previous segment; v3 encodes them relative to it across lines.
+#[derive(Debug, Clone, Copy, Default)]
+struct SegmentState {
+    source_index: i64,
+    source_line: i64,
+    source_column: i64,
+}
+
+/// Appends `value` as a base64 VLQ: sign in the lowest bit, then 5-bit groups with a
+/// continuation bit, least significant group first.
+pub(crate) fn encode_vlq(output: &mut String, value: i64) {
+    let mut remaining = if value < 0 {
+        ((-value as u64) << 1) | 1
+    } else {
+        (value as u64) << 1
+    };
+
+    loop {
+        let mut digit = (remaining & 0b1_1111) as usize;
+        remaining >>= 5;
+        if remaining > 0 {
+            digit |= 0b10_0000;
+        }
+        output.push(BASE64_DIGITS[digit] as char);
+        if remaining == 0 {
+            break;
+        }
+    }
+}
@@ -78,10 +78,12 @@ fn host_io_reads_the_underlying_value_before_logging() {
         JsLoweringConfig {
             pretty: true,
             emit_locations: false,
+            track_source_lines: false,
             auto_invoke_start: true,
             function_emission_policy: JsFunctionEmissionPolicy::AllFunctions,
             external_package_registry: Arc::new(ExternalPackageRegistry::new()),
             external_module_export_glue_enabled: false,
+            console_output: JsConsoleOutput::Console,
         },
         &type_environment,
     )
@@ -134,10 +136,12 @@ fn auto_invokes_start_function_when_enabled() {
         JsLoweringConfig {
             pretty: true,
             emit_locations: false,
+            track_source_lines: false,
             auto_invoke_start: true,
             function_emission_policy: JsFunctionEmissionPolicy::AllFunctions,
             external_package_registry: Arc::new(ExternalPackageRegistry::new()),
             external_module_export_glue_enabled: false,
+            console_output: JsConsoleOutput::Console,
         },
         &type_environment,
     )
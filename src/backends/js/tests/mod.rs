@@ -20,5 +20,6 @@ mod reactivity;
 mod receiver_methods;
 mod results;
 mod runtime_helpers;
+mod source_maps;
 mod symbols;
 mod value_use;
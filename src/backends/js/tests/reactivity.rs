@@ -524,3 +524,138 @@ fn reactive_template_module_emits_mount_helper() {
         "reactive template modules must emit __bs_mount_template_fragment for HTML slot mounting"
     );
 }
+
+/// Verifies that mounted fragments rerender by patching the slot rather than replacing its HTML.
+#[test]
+fn mounted_fragment_render_patches_slot() {
+    let source = lower_minimal_reactive_template_module("main");
+
+    let mount = helper_source(&source, "__bs_mount_template_fragment");
+    assert!(
+        mount.contains("__bs_dom_patch_slot(mounted.slot, fragment.snapshot());"),
+        "mounted fragment rerenders must go through __bs_dom_patch_slot"
+    );
+    let patch_slot = helper_source(&source, "__bs_dom_patch_slot");
+    assert!(
+        patch_slot.contains("document.createElement(\"template\")")
+            && patch_slot.contains("slot.insertAdjacentHTML(\"beforeend\", html);"),
+        "__bs_dom_patch_slot must parse into a template and fall back to replacement without a DOM"
+    );
+}
+
+/// Verifies that hydrating mounts leave prerendered slot HTML in place.
+#[test]
+fn hydrating_mount_patches_prerendered_slot_instead_of_inserting() {
+    let source = lower_minimal_reactive_template_module("main");
+
+    let mount = helper_source(&source, "__bs_mount_template_fragment");
+    assert!(
+        mount.contains("function __bs_mount_template_fragment(slot, fragment, hydrate)"),
+        "the mount helper must accept a hydrate flag"
+    );
+    assert!(
+        mount.contains("if (!hydrate) slot.insertAdjacentHTML(\"beforeend\", fragment || \"\");"),
+        "hydrating plain fragments must not insert their HTML again"
+    );
+    assert!(
+        mount.contains("if (hydrate) {\n") && mount.contains("mounted.render();"),
+        "hydrating template fragments must patch the existing slot"
+    );
+}
+
+/// Minimal DOM node model for running the patch helpers under Node.
+const FAKE_DOM_JS: &str = r##"
+class FakeNode {
+    constructor(nodeType, nodeName, nodeValue) {
+        this.nodeType = nodeType;
+        this.nodeName = nodeName;
+        this.nodeValue = nodeValue === undefined ? null : nodeValue;
+        this.childNodes = [];
+        this.parentNode = null;
+        this.attributeMap = new Map();
+    }
+    get firstChild() { return this.childNodes[0] || null; }
+    get nextSibling() {
+        if (!this.parentNode) return null;
+        const siblings = this.parentNode.childNodes;
+        return siblings[siblings.indexOf(this) + 1] || null;
+    }
+    get attributes() {
+        return Array.from(this.attributeMap, ([name, value]) => ({ name, value }));
+    }
+    hasAttribute(name) { return this.attributeMap.has(name); }
+    getAttribute(name) { return this.attributeMap.has(name) ? this.attributeMap.get(name) : null; }
+    setAttribute(name, value) { this.attributeMap.set(name, String(value)); }
+    removeAttribute(name) { this.attributeMap.delete(name); }
+    insertBefore(node, reference) {
+        if (node.parentNode) node.parentNode.removeChild(node);
+        const index = reference ? this.childNodes.indexOf(reference) : this.childNodes.length;
+        this.childNodes.splice(index, 0, node);
+        node.parentNode = this;
+        return node;
+    }
+    removeChild(node) {
+        this.childNodes.splice(this.childNodes.indexOf(node), 1);
+        node.parentNode = null;
+        return node;
+    }
+}
+const text = (value) => new FakeNode(3, "#text", value);
+const element = (name, attributes, ...children) => {
+    const node = new FakeNode(1, name);
+    for (const [key, value] of Object.entries(attributes)) node.setAttribute(key, value);
+    for (const child of children) node.insertBefore(child, null);
+    return node;
+};
+"##;
+
+/// Verifies that patching keeps matching nodes alive and only rewrites what changed.
+#[test]
+fn dom_patch_keeps_unchanged_nodes_and_updates_text_and_attributes() {
+    let source = lower_minimal_reactive_template_module("main");
+    let helpers = ["__bs_dom_patch_children", "__bs_dom_patch_node"]
+        .map(|name| helper_source(&source, name))
+        .join("\n");
+
+    let script = format!(
+        "{FAKE_DOM_JS}\n{helpers}\n\
+         const label = text(\"count 1\");\n\
+         const paragraph = element(\"P\", {{ class: \"old\", title: \"gone\" }}, label);\n\
+         const input = element(\"INPUT\", {{ type: \"text\" }});\n\
+         input.value = \"typed\";\n\
+         const slot = element(\"DIV\", {{}}, paragraph, input, element(\"SPAN\", {{}}, text(\"stale\")));\n\
+         const next = element(\"#fragment\", {{}},\n\
+             element(\"P\", {{ class: \"new\" }}, text(\"count 2\")),\n\
+             element(\"INPUT\", {{ type: \"text\" }}),\n\
+             element(\"EM\", {{}}, text(\"fresh\")),\n\
+             text(\"tail\"));\n\
+         __bs_dom_patch_children(slot, next);\n\
+         console.log(slot.childNodes[0] === paragraph, paragraph.firstChild === label, label.nodeValue);\n\
+         console.log(paragraph.getAttribute(\"class\"), paragraph.hasAttribute(\"title\"));\n\
+         console.log(slot.childNodes[1] === input, input.value);\n\
+         console.log(slot.childNodes.map(node => node.nodeName).join(\",\"));\n"
+    );
+
+    let output = std::process::Command::new("node")
+        .arg("-e")
+        .arg(&script)
+        .output()
+        .expect("node should be on PATH to run JS runtime tests");
+    assert!(
+        output.status.success(),
+        "DOM patch script failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .collect::<Vec<_>>(),
+        vec![
+            "true true count 2",
+            "new false",
+            "true typed",
+            "P,INPUT,EM,#text",
+        ]
+    );
+}
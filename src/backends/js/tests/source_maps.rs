@@ -0,0 +1,96 @@
+//! Source Map v3 encoding tests.
+
+use crate::backends::js::source_map::encode_vlq;
+use crate::backends::js::{JsSourceLine, JsSourceMap, JsSourceOrigin};
+use std::path::PathBuf;
+
+fn origin(file: &str, line: usize, column: usize) -> Option<JsSourceOrigin> {
+    Some(JsSourceOrigin {
+        file: PathBuf::from(file),
+        line,
+        column,
+    })
+}
+
+#[test]
+fn vlq_encodes_sign_and_continuation_digits() {
+    let encode = |value| {
+        let mut output = String::new();
+        encode_vlq(&mut output, value);
+        output
+    };
+
+    assert_eq!(encode(0), "A");
+    assert_eq!(encode(1), "C");
+    assert_eq!(encode(-1), "D");
+    assert_eq!(encode(16), "gB");
+    assert_eq!(encode(-17), "jB");
+    assert_eq!(encode(123), "2H");
+}
+
+#[test]
+fn mappings_start_at_the_bundle_line_and_use_relative_fields() {
+    let script = "\n// marker\nfoo();\n  bar();\n\nbaz();\n";
+    let source_lines = [
+        JsSourceLine {
+            generated_line: 1,
+            origin: origin("a.bst", 3, 5),
+        },
+        JsSourceLine {
+            generated_line: 2,
+            origin: origin("b.bst", 1, 1),
+        },
+        JsSourceLine {
+            generated_line: 4,
+            origin: None,
+        },
+    ];
+
+    let map = JsSourceMap::from_source_lines(script, 2, &source_lines);
+
+    assert_eq!(
+        map.sources,
+        vec![PathBuf::from("a.bst"), PathBuf::from("b.bst")]
+    );
+    // Blank lines get no segment and the unmapped run is closed with a one-field segment.
+    assert_eq!(map.mappings, ";;AAEI;ECFJ;;A;");
+}
+
+#[test]
+fn runs_repeat_their_origin_on_every_generated_line() {
+    let script = "a();\nb();\nc();";
+    let source_lines = [JsSourceLine {
+        generated_line: 2,
+        origin: origin("#page.bst", 2, 1),
+    }];
+
+    let map = JsSourceMap::from_source_lines(script, 0, &source_lines);
+
+    assert_eq!(map.mappings, ";AACA;AAAA");
+}
+
+#[test]
+fn json_output_is_a_v3_map() {
+    let map = JsSourceMap::from_source_lines(
+        "x();",
+        0,
+        &[JsSourceLine {
+            generated_line: 1,
+            origin: origin("#page.bst", 1, 1),
+        }],
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&map.to_json(
+        "index.html",
+        &[String::from("%23page.bst")],
+        &[Some(String::from("x()\n"))],
+    ))
+    .expect("source map should be JSON");
+
+    assert_eq!(json["version"], 3);
+    assert_eq!(json["file"], "index.html");
+    assert_eq!(json["sources"][0], "%23page.bst");
+    assert_eq!(json["sourcesContent"][0], "x()\n");
+    assert_eq!(json["names"], serde_json::json!([]));
+    assert_eq!(json["mappings"], "AAAA");
+}
@@ -8,7 +8,7 @@ pub(super) use crate::backends::js::JsFunctionEmissionPolicy;
 pub(super) use crate::backends::js::test_symbol_helpers::{
     expected_dev_field_name, expected_dev_function_name, expected_dev_local_name,
 };
-pub(super) use crate::backends::js::{JsLoweringConfig, lower_hir_to_js};
+pub(super) use crate::backends::js::{JsConsoleOutput, JsLoweringConfig, lower_hir_to_js};
 pub(super) use crate::compiler_frontend::analysis::borrow_checker::{
     BorrowCheckReport, BorrowStateSnapshot, LocalBorrowSnapshot, LocalMode,
 };
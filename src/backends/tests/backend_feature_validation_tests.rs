@@ -6,16 +6,19 @@
 
 use crate::backends::backend_feature_validation::{
     BackendFeatureValidationError, BackendFeatureValidationInput, BackendFeatureValidationRoot,
-    validate_hir_backend_feature_support,
+    validate_hir_backend_feature_support, validate_wasm_gc_feature_support,
 };
 use crate::backends::external_package_validation::BackendTarget;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
 use crate::compiler_frontend::compiler_messages::{
-    DiagnosticKind, DiagnosticPayload, RuleDiagnosticKind, UnsupportedBackendFeatureReason,
+    DiagnosticPayload, UnsupportedBackendFeatureReason,
 };
 use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
 use crate::compiler_frontend::datatypes::ids::builtin_type_ids;
 use crate::compiler_frontend::hir::blocks::HirBlock;
-use crate::compiler_frontend::hir::expressions::{HirExpression, HirExpressionKind, ValueKind};
+use crate::compiler_frontend::hir::expressions::{
+    HirExpression, HirExpressionKind, HirVariantCarrier, ValueKind,
+};
 use crate::compiler_frontend::hir::functions::HirFunction;
 use crate::compiler_frontend::hir::ids::{
     BlockId, FunctionId, HirNodeId, HirValueId, LocalId, RegionId,
@@ -31,98 +34,51 @@ use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::tokenizer::tokens::{CharPosition, SourceLocation};
 
 #[test]
-fn wasm_feature_validation_rejects_reachable_format_float() {
-    let location = location_at(30, 2);
-    let mut string_table = StringTable::new();
-    let type_environment = TypeEnvironment::new();
-    let module = hir_module(
-        FunctionId(0),
-        vec![function(FunctionId(0), BlockId(0))],
-        vec![block(
-            BlockId(0),
-            vec![float_statement(
-                10,
-                ReachableFloatStatementKind::FormatFloat,
-                location,
+fn wasm_feature_validation_accepts_reachable_float_statements() {
+    for kind in [
+        ReachableFloatStatementKind::FormatFloat,
+        ReachableFloatStatementKind::ValidateFloat,
+    ] {
+        let module = hir_module(
+            FunctionId(0),
+            vec![function(FunctionId(0), BlockId(0))],
+            vec![block(
+                BlockId(0),
+                vec![float_statement(10, kind.clone(), location_at(30, 2))],
+                HirTerminator::Return(unit_expression(0)),
             )],
-            HirTerminator::Return(unit_expression(0)),
-        )],
-    );
+        );
 
-    let diagnostic = wasm_feature_validation_diagnostic(
-        &module,
-        &type_environment,
-        &mut string_table,
-        "Wasm validation should reject reachable FormatFloat",
-    );
-
-    assert_unsupported_feature(
-        &diagnostic,
-        &mut string_table,
-        UnsupportedBackendFeatureReason::FloatFormatting,
-    );
+        assert!(
+            wasm_feature_validation_passes(&module),
+            "Wasm validation should accept reachable {kind:?} now that the Wasm runtime lowers it"
+        );
+    }
 }
 
 #[test]
-fn wasm_feature_validation_rejects_reachable_validate_float() {
-    let location = location_at(30, 2);
-    let mut string_table = StringTable::new();
-    let type_environment = TypeEnvironment::new();
-    let module = hir_module(
-        FunctionId(0),
-        vec![function(FunctionId(0), BlockId(0))],
-        vec![block(
-            BlockId(0),
-            vec![float_statement(
-                10,
-                ReachableFloatStatementKind::ValidateFloat,
-                location,
+fn wasm_feature_validation_accepts_reachable_checked_numeric_ops() {
+    for op in [
+        HirNumericOp::IntAdd,
+        HirNumericOp::IntDiv,
+        HirNumericOp::FloatMod,
+        HirNumericOp::FloatPow,
+    ] {
+        let module = hir_module(
+            FunctionId(0),
+            vec![function(FunctionId(0), BlockId(0))],
+            vec![block(
+                BlockId(0),
+                vec![numeric_op_statement(10, op, location_at(30, 2))],
+                HirTerminator::Return(unit_expression(0)),
             )],
-            HirTerminator::Return(unit_expression(0)),
-        )],
-    );
-
-    let diagnostic = wasm_feature_validation_diagnostic(
-        &module,
-        &type_environment,
-        &mut string_table,
-        "Wasm validation should reject reachable ValidateFloat",
-    );
-
-    assert_unsupported_feature(
-        &diagnostic,
-        &mut string_table,
-        UnsupportedBackendFeatureReason::FloatBoundaryValidation,
-    );
-}
-
-#[test]
-fn wasm_feature_validation_rejects_reachable_checked_numeric_op() {
-    let location = location_at(30, 2);
-    let mut string_table = StringTable::new();
-    let type_environment = TypeEnvironment::new();
-    let module = hir_module(
-        FunctionId(0),
-        vec![function(FunctionId(0), BlockId(0))],
-        vec![block(
-            BlockId(0),
-            vec![numeric_op_statement(10, HirNumericOp::IntAdd, location)],
-            HirTerminator::Return(unit_expression(0)),
-        )],
-    );
+        );
 
-    let diagnostic = wasm_feature_validation_diagnostic(
-        &module,
-        &type_environment,
-        &mut string_table,
-        "Wasm validation should reject reachable checked numeric operations",
-    );
-
-    assert_unsupported_feature(
-        &diagnostic,
-        &mut string_table,
-        UnsupportedBackendFeatureReason::CheckedNumericOperations,
-    );
+        assert!(
+            wasm_feature_validation_passes(&module),
+            "Wasm validation should accept reachable checked numeric operation {op:?}"
+        );
+    }
 }
 
 #[test]
@@ -211,51 +167,227 @@ fn wasm_feature_validation_ignores_unreachable_float_statements() {
     );
 }
 
-fn wasm_feature_validation_diagnostic(
-    module: &HirModule,
-    type_environment: &TypeEnvironment,
-    string_table: &mut StringTable,
-    expectation: &str,
-) -> crate::compiler_frontend::compiler_messages::CompilerDiagnostic {
-    let error = validate_hir_backend_feature_support(
+#[test]
+fn wasm_feature_validation_rejects_reachable_option_values() {
+    let module = hir_module(
+        FunctionId(0),
+        vec![function(FunctionId(0), BlockId(0))],
+        vec![block(
+            BlockId(0),
+            vec![option_none_statement(10, location_at(30, 2))],
+            HirTerminator::Return(unit_expression(0)),
+        )],
+    );
+
+    assert!(
+        !wasm_feature_validation_passes(&module),
+        "Wasm validation should reject a reachable Option value"
+    );
+}
+
+#[test]
+fn wasm_feature_validation_ignores_unreachable_option_values() {
+    let module = hir_module(
+        FunctionId(0),
+        vec![
+            function(FunctionId(0), BlockId(0)),
+            function(FunctionId(1), BlockId(1)),
+        ],
+        vec![
+            block(
+                BlockId(0),
+                vec![],
+                HirTerminator::Return(unit_expression(0)),
+            ),
+            block(
+                BlockId(1),
+                vec![option_none_statement(10, location_at(50, 4))],
+                HirTerminator::Return(unit_expression(1)),
+            ),
+        ],
+    );
+
+    assert!(
+        wasm_feature_validation_passes(&module),
+        "Wasm validation should ignore unreachable Option values"
+    );
+}
+
+#[test]
+fn wasm_feature_validation_requires_a_type_environment() {
+    let mut string_table = StringTable::new();
+    let module = hir_module(
+        FunctionId(0),
+        vec![function(FunctionId(0), BlockId(0))],
+        vec![block(
+            BlockId(0),
+            vec![],
+            HirTerminator::Return(unit_expression(0)),
+        )],
+    );
+
+    let result = validate_hir_backend_feature_support(
         BackendFeatureValidationInput {
-            hir: module,
+            hir: &module,
             target: BackendTarget::Wasm,
             root: BackendFeatureValidationRoot::StartFunction,
-            type_environment: Some(type_environment),
+            type_environment: None,
         },
-        string_table,
-    )
-    .expect_err(expectation);
+        &mut string_table,
+    );
 
-    match error {
-        BackendFeatureValidationError::Diagnostic(diagnostic) => *diagnostic,
-        BackendFeatureValidationError::Infrastructure(_) => {
-            panic!("expected a user-facing Rule diagnostic, not an infrastructure error")
-        }
+    assert!(matches!(
+        result,
+        Err(BackendFeatureValidationError::Infrastructure(_))
+    ));
+}
+
+#[test]
+fn wasm_gc_feature_validation_rejects_linear_memory_only_statements() {
+    let cases = [
+        (
+            numeric_op_statement(10, HirNumericOp::IntAdd, location_at(30, 2)),
+            UnsupportedBackendFeatureReason::CheckedNumericOperations,
+        ),
+        (
+            float_statement(
+                10,
+                ReachableFloatStatementKind::FormatFloat,
+                location_at(30, 2),
+            ),
+            UnsupportedBackendFeatureReason::FloatFormatting,
+        ),
+        (
+            float_statement(
+                10,
+                ReachableFloatStatementKind::ValidateFloat,
+                location_at(30, 2),
+            ),
+            UnsupportedBackendFeatureReason::FloatBoundaryValidation,
+        ),
+    ];
+
+    for (statement, expected) in cases {
+        let module = hir_module(
+            FunctionId(0),
+            vec![function(FunctionId(0), BlockId(0))],
+            vec![block(
+                BlockId(0),
+                vec![statement],
+                HirTerminator::Return(unit_expression(0)),
+            )],
+        );
+
+        assert_eq!(wasm_gc_rejection_reason(&module), Some(expected));
     }
 }
 
-fn assert_unsupported_feature(
-    diagnostic: &crate::compiler_frontend::compiler_messages::CompilerDiagnostic,
-    string_table: &mut StringTable,
-    expected_reason: UnsupportedBackendFeatureReason,
-) {
+#[test]
+fn wasm_gc_feature_validation_rejects_casts_without_gc_lowering() {
+    let cast_module = |policy| {
+        hir_module(
+            FunctionId(0),
+            vec![function(FunctionId(0), BlockId(0))],
+            vec![block(
+                BlockId(0),
+                vec![cast_statement(10, policy, location_at(30, 2))],
+                HirTerminator::Return(unit_expression(0)),
+            )],
+        )
+    };
+
+    assert_eq!(
+        wasm_gc_rejection_reason(&cast_module(BuiltinCastPolicyId::IntToString)),
+        Some(UnsupportedBackendFeatureReason::RuntimeCasts)
+    );
     assert_eq!(
-        diagnostic.kind,
-        DiagnosticKind::Rule(RuleDiagnosticKind::UnsupportedBackendFeature)
+        wasm_gc_rejection_reason(&cast_module(BuiltinCastPolicyId::IntToFloat)),
+        None,
+        "GC emission lowers Int -> Float casts directly"
     );
+}
 
-    let DiagnosticPayload::UnsupportedBackendFeature {
-        backend_name,
-        reason,
-    } = &diagnostic.payload
-    else {
-        panic!("expected UnsupportedBackendFeature payload");
-    };
+#[test]
+fn wasm_gc_feature_validation_rejects_fallible_returns() {
+    let module = hir_module(
+        FunctionId(0),
+        vec![function(FunctionId(0), BlockId(0))],
+        vec![block(
+            BlockId(0),
+            vec![],
+            HirTerminator::ReturnError(unit_expression(0)),
+        )],
+    );
+
+    assert_eq!(
+        wasm_gc_rejection_reason(&module),
+        Some(UnsupportedBackendFeatureReason::FallibleResults)
+    );
+}
+
+#[test]
+fn wasm_gc_feature_validation_ignores_unreachable_statements() {
+    let module = hir_module(
+        FunctionId(0),
+        vec![
+            function(FunctionId(0), BlockId(0)),
+            function(FunctionId(1), BlockId(1)),
+        ],
+        vec![
+            block(
+                BlockId(0),
+                vec![],
+                HirTerminator::Return(unit_expression(0)),
+            ),
+            block(
+                BlockId(1),
+                vec![numeric_op_statement(
+                    10,
+                    HirNumericOp::IntMul,
+                    location_at(50, 4),
+                )],
+                HirTerminator::Return(unit_expression(1)),
+            ),
+        ],
+    );
+
+    assert_eq!(wasm_gc_rejection_reason(&module), None);
+}
 
-    assert_eq!(*backend_name, string_table.intern("Wasm"));
-    assert_eq!(*reason, expected_reason);
+fn wasm_feature_validation_passes(module: &HirModule) -> bool {
+    let mut string_table = StringTable::new();
+    let type_environment = TypeEnvironment::new();
+    validate_hir_backend_feature_support(
+        BackendFeatureValidationInput {
+            hir: module,
+            target: BackendTarget::Wasm,
+            root: BackendFeatureValidationRoot::StartFunction,
+            type_environment: Some(&type_environment),
+        },
+        &mut string_table,
+    )
+    .is_ok()
+}
+
+/// Runs GC-mode validation from the start function and returns the rejection reason, if any.
+fn wasm_gc_rejection_reason(module: &HirModule) -> Option<UnsupportedBackendFeatureReason> {
+    let mut string_table = StringTable::new();
+    let type_environment = TypeEnvironment::new();
+    match validate_wasm_gc_feature_support(
+        module,
+        vec![module.start_function],
+        &type_environment,
+        &mut string_table,
+    ) {
+        Ok(()) => None,
+        Err(BackendFeatureValidationError::Diagnostic(diagnostic)) => match diagnostic.payload {
+            DiagnosticPayload::UnsupportedBackendFeature { reason, .. } => Some(reason),
+            payload => panic!("unexpected diagnostic payload {payload:?}"),
+        },
+        Err(BackendFeatureValidationError::Infrastructure(error)) => {
+            panic!("GC validation should not fail internally: {}", error.msg)
+        }
+    }
 }
 
 fn hir_module(
@@ -353,6 +485,49 @@ fn float_statement(
     }
 }
 
+fn cast_statement(id: u32, policy: BuiltinCastPolicyId, location: SourceLocation) -> HirStatement {
+    let source = HirExpression {
+        id: HirValueId(id + 100),
+        kind: HirExpressionKind::Int(1),
+        ty: builtin_type_ids::INT,
+        value_kind: ValueKind::Const,
+        region: RegionId(0),
+    };
+
+    HirStatement {
+        id: HirNodeId(id),
+        kind: HirStatementKind::Expr(HirExpression {
+            id: HirValueId(id + 101),
+            kind: HirExpressionKind::Cast {
+                source: Box::new(source),
+                policy,
+            },
+            ty: builtin_type_ids::NONE,
+            value_kind: ValueKind::RValue,
+            region: RegionId(0),
+        }),
+        location,
+    }
+}
+
+fn option_none_statement(id: u32, location: SourceLocation) -> HirStatement {
+    HirStatement {
+        id: HirNodeId(id),
+        kind: HirStatementKind::Expr(HirExpression {
+            id: HirValueId(id + 100),
+            kind: HirExpressionKind::VariantConstruct {
+                carrier: HirVariantCarrier::Option,
+                variant_index: 0,
+                fields: vec![],
+            },
+            ty: builtin_type_ids::NONE,
+            value_kind: ValueKind::RValue,
+            region: RegionId(0),
+        }),
+        location,
+    }
+}
+
 fn unit_expression(id: u32) -> HirExpression {
     HirExpression {
         id: HirValueId(id),
@@ -1,10 +1,13 @@
 //! Top-level orchestration for HIR -> LIR and optional LIR -> Wasm emission.
 
+use crate::backends::backend_feature_validation::{
+    BackendFeatureValidationError, validate_wasm_gc_feature_support,
+};
 use crate::backends::error_types::{BackendErrorType, lir_transformation_error};
 use crate::backends::wasm::debug::build_debug_outputs;
 use crate::backends::wasm::emit::module::emit_lir_to_wasm_module;
 use crate::backends::wasm::hir_to_lir::module::lower_hir_module_to_lir;
-use crate::backends::wasm::request::{WasmBackendRequest, WasmCfgLoweringStrategy};
+use crate::backends::wasm::request::{WasmBackendRequest, WasmFunctionEmissionPolicy};
 use crate::backends::wasm::result::WasmLirBackendResult;
 use crate::compiler_frontend::analysis::borrow_checker::BorrowFacts;
 use crate::compiler_frontend::compiler_messages::compiler_errors::{
@@ -27,6 +30,9 @@ pub(crate) fn lower_hir_to_wasm_lir(
     // WHY: avoid partial lowering and keep diagnostics deterministic.
     validate_request(hir_module, request)
         .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
+    if request.target_features.use_wasm_gc {
+        validate_gc_feature_support(hir_module, request, string_table, type_environment)?;
+    }
 
     // WHAT: perform full module lowering using HIR + borrow side tables.
     // WHY: LIR is the stable inspection and emission seam for the experimental Wasm backend.
@@ -90,6 +96,56 @@ pub(crate) fn lower_hir_to_wasm_module(
     Ok(result)
 }
 
+/// Rejects reachable features GC emission has no lowering for before any lowering starts.
+///
+/// WHY: GC mode is an opt-in request feature, so unsupported maps, fallible results, checked
+/// numeric operations and casts surface as structured Rule diagnostics rather than compiler
+/// errors from inside GC emission.
+fn validate_gc_feature_support(
+    hir_module: &HirModule,
+    request: &WasmBackendRequest,
+    string_table: &StringTable,
+    type_environment: &TypeEnvironment,
+) -> Result<(), CompilerMessages> {
+    // WHAT: validate from the same roots `select_functions_for_lowering` emits.
+    let root_functions = match request.function_emission_policy {
+        WasmFunctionEmissionPolicy::AllFunctions => hir_module
+            .functions
+            .iter()
+            .map(|function| function.id)
+            .collect(),
+        WasmFunctionEmissionPolicy::ReachableFromExports => request
+            .export_policy
+            .exported_functions
+            .iter()
+            .copied()
+            .chain(
+                request
+                    .export_policy
+                    .command_start
+                    .as_ref()
+                    .map(|start| start.function),
+            )
+            .collect(),
+    };
+
+    let mut string_table = string_table.clone();
+    validate_wasm_gc_feature_support(
+        hir_module,
+        root_functions,
+        type_environment,
+        &mut string_table,
+    )
+    .map_err(|error| match error {
+        BackendFeatureValidationError::Diagnostic(diagnostic) => {
+            CompilerMessages::from_diagnostic(*diagnostic, string_table)
+        }
+        BackendFeatureValidationError::Infrastructure(error) => {
+            CompilerMessages::from_error(*error, string_table)
+        }
+    })
+}
+
 fn validate_request(
     hir_module: &HirModule,
     request: &WasmBackendRequest,
@@ -144,8 +200,6 @@ fn validate_request(
         }
     }
 
-    validate_feature_flags(request)?;
-    validate_emit_options(request)?;
     validate_helper_export_policy(&mut export_name_set, request)?;
 
     Ok(())
@@ -158,50 +212,6 @@ fn contains_function(hir_module: &HirModule, function_id: FunctionId) -> bool {
         .any(|function| function.id == function_id)
 }
 
-fn validate_feature_flags(request: &WasmBackendRequest) -> Result<(), CompilerError> {
-    // WHAT: reject feature toggles that would require a different emitter/runtime model.
-    // WHY: rejecting incompatible toggles early keeps backend behavior explicit and predictable.
-    if request.target_features.use_wasm_gc {
-        return Err(CompilerError::compiler_error(
-            "Wasm backend request enables use_wasm_gc, but the current emitter targets core linear-memory Wasm only",
-        )
-        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
-    }
-
-    if request.target_features.enable_multi_value {
-        return Err(CompilerError::compiler_error(
-            "Wasm backend request enables multi-value, but the current ABI supports single-result only",
-        )
-        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
-    }
-
-    if request.target_features.enable_reference_types {
-        return Err(CompilerError::compiler_error(
-            "Wasm backend request enables reference types, but the current emitter targets core value types only",
-        )
-        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
-    }
-
-    // Note: bulk-memory (memory.copy) is now used internally by runtime string helpers.
-    // The enable_bulk_memory flag is reserved for user-facing feature gating if needed.
-
-    Ok(())
-}
-
-fn validate_emit_options(request: &WasmBackendRequest) -> Result<(), CompilerError> {
-    if matches!(
-        request.emit_options.cfg_lowering_strategy,
-        WasmCfgLoweringStrategy::Structured
-    ) {
-        return Err(CompilerError::compiler_error(
-            "Wasm backend request selected structured CFG lowering, but the current emitter implements dispatcher-loop CFG lowering only",
-        )
-        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
-    }
-
-    Ok(())
-}
-
 fn validate_helper_export_policy(
     export_name_set: &mut HashSet<String>,
     request: &WasmBackendRequest,
@@ -210,11 +220,29 @@ fn validate_helper_export_policy(
     // WHY: keeping names/combinations strict here avoids backend-side guessing later.
     let helpers = &request.export_policy.helper_exports;
 
-    if helpers.export_str_ptr != helpers.export_str_len {
-        return Err(CompilerError::compiler_error(
-            "Wasm helper exports must request both bst_str_ptr and bst_str_len together",
-        )
-        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+    if request.target_features.use_wasm_gc {
+        validate_gc_helper_export_policy(request)?;
+    } else {
+        if helpers.export_str_byte {
+            return Err(CompilerError::compiler_error(
+                "Wasm helper export bst_str_byte requires use_wasm_gc; linear-memory hosts read strings through bst_str_ptr and memory",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+
+        if helpers.export_str_ptr != helpers.export_str_len {
+            return Err(CompilerError::compiler_error(
+                "Wasm helper exports must request both bst_str_ptr and bst_str_len together",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+
+        if (helpers.export_str_ptr || helpers.export_str_len) && !helpers.export_memory {
+            return Err(CompilerError::compiler_error(
+                "Wasm helper exports requesting string pointer/length must also export memory",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
     }
 
     if helpers.export_vec_new != helpers.export_vec_push {
@@ -231,13 +259,6 @@ fn validate_helper_export_policy(
         .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
     }
 
-    if (helpers.export_str_ptr || helpers.export_str_len) && !helpers.export_memory {
-        return Err(CompilerError::compiler_error(
-            "Wasm helper exports requesting string pointer/length must also export memory",
-        )
-        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
-    }
-
     let mut add_reserved_export =
         |enabled: bool, name: &'static str| -> Result<(), CompilerError> {
             if !enabled {
@@ -262,6 +283,36 @@ fn validate_helper_export_policy(
     add_reserved_export(helpers.export_vec_len, "bst_vec_len")?;
     add_reserved_export(helpers.export_vec_get, "bst_vec_get")?;
     add_reserved_export(helpers.export_release, "bst_release")?;
+    add_reserved_export(helpers.export_str_byte, "bst_str_byte")?;
+    add_reserved_export(helpers.export_live_allocations, "bst_live_allocations")?;
+
+    Ok(())
+}
+
+fn validate_gc_helper_export_policy(request: &WasmBackendRequest) -> Result<(), CompilerError> {
+    // WHAT: Wasm GC modules have no linear memory, so memory-addressed helpers do not exist.
+    // WHY: hosts read GC strings byte-by-byte through `bst_str_len` plus `bst_str_byte`.
+    let helpers = &request.export_policy.helper_exports;
+    for (enabled, name) in [
+        (helpers.export_memory, "memory"),
+        (helpers.export_str_ptr, "bst_str_ptr"),
+        (helpers.export_release, "bst_release"),
+        (helpers.export_live_allocations, "bst_live_allocations"),
+    ] {
+        if enabled {
+            return Err(CompilerError::compiler_error(format!(
+                "Wasm helper export '{name}' is unavailable with use_wasm_gc because Wasm GC modules have no linear memory",
+            ))
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    }
+
+    if helpers.export_str_len != helpers.export_str_byte {
+        return Err(CompilerError::compiler_error(
+            "Wasm GC helper exports must request both bst_str_len and bst_str_byte together",
+        )
+        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+    }
 
     Ok(())
 }
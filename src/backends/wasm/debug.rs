@@ -4,7 +4,7 @@
 //! They are not a stability guarantee for external tooling.
 
 use crate::backends::wasm::lir::debug_dump::dump_lir_module;
-use crate::backends::wasm::lir::module::WasmLirModule;
+use crate::backends::wasm::lir::module::{WasmGenericKind, WasmLirModule};
 use crate::backends::wasm::request::WasmBackendRequest;
 use crate::backends::wasm::result::WasmDebugOutputs;
 use std::fmt::Write as _;
@@ -32,6 +32,10 @@ pub(crate) fn build_debug_outputs(
         outputs.runtime_layout_text = Some(render_runtime_layout_text(module));
     }
 
+    if request.debug_flags.show_wasm_generic_instances {
+        outputs.generic_instances_text = Some(render_generic_instances_text(module));
+    }
+
     outputs
 }
 
@@ -95,3 +99,27 @@ fn render_runtime_layout_text(module: &WasmLirModule) -> String {
 
     out
 }
+
+fn render_generic_instances_text(module: &WasmLirModule) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Wasm generic instances");
+    for summary in &module.generic_instances {
+        let (kind, unit) = match summary.kind {
+            WasmGenericKind::Function => ("function", "LIR statements"),
+            WasmGenericKind::Struct => ("struct", "bytes"),
+            WasmGenericKind::Choice => ("choice", "bytes"),
+        };
+        let _ = writeln!(
+            out,
+            "  {kind} {}: {} instance(s)",
+            summary.generic_name,
+            summary.instances.len(),
+        );
+        for instance in &summary.instances {
+            let _ = writeln!(out, "    {}: {} {unit}", instance.name, instance.size);
+        }
+    }
+
+    out
+}
@@ -0,0 +1,233 @@
+//! Linear-memory allocator helper emission.
+//!
+//! WHAT: synthesizes `rt_alloc`, `rt_release` and `rt_drop_if_owned` over the size-class
+//! free lists described in `runtime::memory`.
+//! WHY: long-running pages allocate on every reactive update, so released blocks must be reused
+//! instead of growing `heap_top` forever.
+
+use crate::backends::wasm::emit::map_helpers::{emit, helper_index, memarg_i32};
+use crate::backends::wasm::emit::sections::WasmEmitPlan;
+use crate::backends::wasm::runtime::memory::{
+    ALLOC_HEADER_SIZE, BLOCK_CLASS_OFFSET, BLOCK_LINK_OFFSET, FREED_BLOCK_FLAG, MIN_SIZE_CLASS,
+    WASM_PAGE_SIZE_LOG2, heap_start,
+};
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
+use wasm_encoder::{BlockType, Function, Instruction, ValType};
+
+/// Global indices the allocator helpers read and update.
+pub(crate) struct AllocatorGlobals {
+    pub heap_top: u32,
+    pub live_allocations: u32,
+}
+
+/// Emit the function body for `rt_alloc`, `rt_release` or `rt_drop_if_owned`.
+pub(crate) fn emit_alloc_helper(
+    helper: WasmRuntimeHelper,
+    plan: &WasmEmitPlan,
+    globals: &AllocatorGlobals,
+) -> Result<Function, CompilerError> {
+    // Free-list heads live in front of the first block (see `runtime::memory`).
+    let free_lists = plan.heap_base as i32;
+
+    let function = match helper {
+        WasmRuntimeHelper::Alloc => {
+            // WHAT: pop a block of the requested size class, or carve one from `heap_top`.
+            const SIZE: u32 = 0;
+            const CLASS: u32 = 1;
+            const HEAD: u32 = 2;
+            const PAYLOAD: u32 = 3;
+            const MISSING_PAGES: u32 = 4;
+
+            // param 0: size | locals 1..=4 listed above
+            let mut function = Function::new(vec![(4, ValType::I32)]);
+            emit(
+                &mut function,
+                &[
+                    // class = max(ceil(log2(size + header)), MIN_SIZE_CLASS)
+                    Instruction::I32Const(32),
+                    Instruction::LocalGet(SIZE),
+                    Instruction::I32Const(ALLOC_HEADER_SIZE - 1),
+                    Instruction::I32Add,
+                    Instruction::I32Clz,
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(CLASS),
+                    Instruction::I32Const(MIN_SIZE_CLASS),
+                    Instruction::LocalGet(CLASS),
+                    Instruction::I32Const(MIN_SIZE_CLASS),
+                    Instruction::I32GtU,
+                    Instruction::Select,
+                    Instruction::LocalTee(CLASS),
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::I32Const(free_lists),
+                    Instruction::I32Add,
+                    Instruction::LocalTee(HEAD),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalTee(PAYLOAD),
+                    Instruction::If(BlockType::Empty),
+                    // Reuse: unlink the head and clear the stale payload.
+                    Instruction::LocalGet(HEAD),
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::I32Const(ALLOC_HEADER_SIZE),
+                    Instruction::I32Sub,
+                    Instruction::I32Load(memarg_i32(BLOCK_LINK_OFFSET)),
+                    Instruction::I32Store(memarg_i32(0)),
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::I32Const(0),
+                    Instruction::I32Const(1),
+                    Instruction::LocalGet(CLASS),
+                    Instruction::I32Shl,
+                    Instruction::I32Const(ALLOC_HEADER_SIZE),
+                    Instruction::I32Sub,
+                    Instruction::MemoryFill(0),
+                    Instruction::Else,
+                    // Carve: bump `heap_top` by the block size, growing memory to cover it.
+                    Instruction::GlobalGet(globals.heap_top),
+                    Instruction::I32Const(ALLOC_HEADER_SIZE),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(PAYLOAD),
+                    Instruction::GlobalGet(globals.heap_top),
+                    Instruction::I32Const(1),
+                    Instruction::LocalGet(CLASS),
+                    Instruction::I32Shl,
+                    Instruction::I32Add,
+                    Instruction::GlobalSet(globals.heap_top),
+                    Instruction::GlobalGet(globals.heap_top),
+                    Instruction::I32Const((1 << WASM_PAGE_SIZE_LOG2) - 1),
+                    Instruction::I32Add,
+                    Instruction::I32Const(WASM_PAGE_SIZE_LOG2),
+                    Instruction::I32ShrU,
+                    Instruction::MemorySize(0),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(MISSING_PAGES),
+                    Instruction::I32Const(0),
+                    Instruction::I32GtS,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(MISSING_PAGES),
+                    Instruction::MemoryGrow(0),
+                    Instruction::I32Const(-1),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::Unreachable,
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::End,
+                    // Header: live class word, no owned block yet.
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::I32Const(ALLOC_HEADER_SIZE),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(HEAD),
+                    Instruction::LocalGet(CLASS),
+                    Instruction::I32Store(memarg_i32(BLOCK_CLASS_OFFSET)),
+                    Instruction::LocalGet(HEAD),
+                    Instruction::I32Const(0),
+                    Instruction::I32Store(memarg_i32(BLOCK_LINK_OFFSET)),
+                    Instruction::GlobalGet(globals.live_allocations),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::GlobalSet(globals.live_allocations),
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        WasmRuntimeHelper::Release => {
+            // WHAT: push a block and its owned chain onto their free lists.
+            // WHY: releasing a freed block again would corrupt a free list, so it traps instead.
+            const PAYLOAD: u32 = 0;
+            const BLOCK: u32 = 1;
+            const CLASS: u32 = 2;
+            const HEAD: u32 = 3;
+
+            // param 0: payload | locals 1..=3 listed above
+            let mut function = Function::new(vec![(3, ValType::I32)]);
+            emit(
+                &mut function,
+                &[
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    // Null, static data and runtime strings are not heap blocks.
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::I32Const((heap_start(plan.heap_base) as i32) + ALLOC_HEADER_SIZE),
+                    Instruction::I32LtU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::I32Const(ALLOC_HEADER_SIZE),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(BLOCK),
+                    Instruction::I32Load(memarg_i32(BLOCK_CLASS_OFFSET)),
+                    Instruction::LocalTee(CLASS),
+                    Instruction::I32Const(FREED_BLOCK_FLAG),
+                    Instruction::I32And,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::Unreachable,
+                    Instruction::End,
+                    Instruction::LocalGet(BLOCK),
+                    Instruction::LocalGet(CLASS),
+                    Instruction::I32Const(FREED_BLOCK_FLAG),
+                    Instruction::I32Or,
+                    Instruction::I32Store(memarg_i32(BLOCK_CLASS_OFFSET)),
+                    // Swap the owned link for the free-list link, keeping the next payload.
+                    Instruction::LocalGet(BLOCK),
+                    Instruction::I32Load(memarg_i32(BLOCK_LINK_OFFSET)),
+                    Instruction::LocalGet(BLOCK),
+                    Instruction::LocalGet(CLASS),
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::I32Const(free_lists),
+                    Instruction::I32Add,
+                    Instruction::LocalTee(HEAD),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::I32Store(memarg_i32(BLOCK_LINK_OFFSET)),
+                    Instruction::LocalGet(HEAD),
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::I32Store(memarg_i32(0)),
+                    Instruction::GlobalGet(globals.live_allocations),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::GlobalSet(globals.live_allocations),
+                    Instruction::LocalSet(PAYLOAD),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        WasmRuntimeHelper::DropIfOwned => {
+            // WHAT: release a handle at a drop site.
+            // WHY: lowering keeps `DropIfOwned` only where the dropped local provably owns its
+            // value alone (see `hir_to_lir::ownership`), so the drop is an unconditional release.
+            let mut function = Function::new(Vec::new());
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(0),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::Release)?),
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        _ => unreachable!("non-allocator helpers are dispatched elsewhere"),
+    };
+
+    Ok(function)
+}
+
+/// Record that `owner` exclusively owns `owned`, so releasing `owner` also releases it.
+pub(crate) fn emit_link_owned_block(function: &mut Function, owner: u32, owned: u32) {
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(owner),
+            Instruction::I32Const(ALLOC_HEADER_SIZE),
+            Instruction::I32Sub,
+            Instruction::LocalGet(owned),
+            Instruction::I32Store(memarg_i32(BLOCK_LINK_OFFSET)),
+        ],
+    );
+}
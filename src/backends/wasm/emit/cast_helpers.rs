@@ -0,0 +1,1018 @@
+//! Builtin cast runtime helper emission.
+//!
+//! WHAT: synthesizes the `rt_cast_*` helpers behind builtin `cast`/`cast!` policies, plus the
+//! whitespace scanner used by `String -> Bool`.
+//! WHY: separated from helpers.rs because every fallible helper here returns a fallible carrier
+//! built through `rt_fallible_error`, and each check mirrors one JS runtime helper in
+//! `backends/js/runtime/casts.rs` so both backends reject the same inputs with the same codes.
+//!
+//! Strings are valid UTF-8, so once a lead byte is read its continuation bytes are in bounds.
+
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::emit::map_helpers::{emit, helper_index, memarg_byte, memarg_i32};
+use crate::backends::wasm::emit::sections::WasmEmitPlan;
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::maps::FALLIBLE_TAG_SUCCESS;
+use crate::backends::wasm::runtime::numeric::{INT_MAX, INT_MIN};
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
+use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use wasm_encoder::{BlockType, Function, Instruction, MemArg, ValType};
+
+const STRING_PTR_OFFSET: u64 = 0;
+const STRING_LEN_OFFSET: u64 = 4;
+const STRING_HEADER_SIZE: i32 = 8;
+
+/// `"true"` and `"fals"` read as little-endian i32 words.
+const TRUE_WORD: i32 = i32::from_le_bytes(*b"true");
+const FALS_WORD: i32 = i32::from_le_bytes(*b"fals");
+
+/// Emit the function body for one cast helper.
+///
+/// `alloc_index` is the Wasm function index of `rt_alloc`; other helper indices come from `plan`.
+pub(crate) fn emit_cast_helper(
+    helper: WasmRuntimeHelper,
+    plan: &WasmEmitPlan,
+    alloc_index: u32,
+) -> Result<Function, CompilerError> {
+    let mut function = match helper {
+        // param 0: scalar | local 1: string, local 2: len
+        WasmRuntimeHelper::CastCharToString => Function::new(vec![(2, ValType::I32)]),
+        // param 0: value | local 1: truncated
+        WasmRuntimeHelper::CastFloatToInt => Function::new(vec![(1, ValType::F64)]),
+        // param 0: value
+        WasmRuntimeHelper::CastIntToChar => Function::new(Vec::new()),
+        // param 0: address | local 1: lead, local 2: scalar
+        WasmRuntimeHelper::StringWhitespaceLen => Function::new(vec![(2, ValType::I32)]),
+        // param 0: text | local 1: position, local 2: end, local 3: byte, local 4: negative,
+        // local 5: after_digit, local 6: overflow, local 7: value
+        WasmRuntimeHelper::CastStringToInt => {
+            Function::new(vec![(6, ValType::I32), (1, ValType::I64)])
+        }
+        // param 0: text | local 1: position, local 2: end, local 3: byte, local 4: need_digit,
+        // local 5: sign_allowed, local 6: phase, local 7: stripped, local 8: cursor,
+        // local 9: value
+        WasmRuntimeHelper::CastStringToFloat => {
+            Function::new(vec![(8, ValType::I32), (1, ValType::F64)])
+        }
+        // param 0: text | local 1: position, local 2: end, local 3: width, local 4: value
+        WasmRuntimeHelper::CastStringToBool => Function::new(vec![(4, ValType::I32)]),
+        // param 0: text | local 1: ptr, local 2: len, local 3: scalar, local 4: count,
+        // local 5: index
+        WasmRuntimeHelper::CastStringToChar => Function::new(vec![(5, ValType::I32)]),
+        _ => {
+            return Err(CompilerError::compiler_error(
+                "emit_cast_helper called with non-cast helper variant",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    };
+
+    match helper {
+        WasmRuntimeHelper::CastCharToString => {
+            // WHAT: UTF-8 encode one scalar into a fresh finalized string.
+            const SCALAR: u32 = 0;
+            const STRING: u32 = 1;
+            const LEN: u32 = 2;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(STRING_HEADER_SIZE + 4),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalTee(STRING),
+                    Instruction::LocalGet(STRING),
+                    Instruction::I32Const(STRING_HEADER_SIZE),
+                    Instruction::I32Add,
+                    Instruction::I32Store(memarg_i32(STRING_PTR_OFFSET)),
+                ],
+            );
+
+            // Each branch stores the lead byte and continuation bytes, then the byte count.
+            let encodings: [(i32, i32, u32); 4] = [
+                (0x80, 0x00, 0),
+                (0x800, 0xC0, 6),
+                (0x10000, 0xE0, 12),
+                (0, 0xF0, 18),
+            ];
+            for (index, (limit, lead_marker, lead_shift)) in encodings.into_iter().enumerate() {
+                let is_last = index == encodings.len() - 1;
+                if !is_last {
+                    emit(
+                        &mut function,
+                        &[
+                            Instruction::LocalGet(SCALAR),
+                            Instruction::I32Const(limit),
+                            Instruction::I32LtU,
+                            Instruction::If(BlockType::Empty),
+                        ],
+                    );
+                }
+                emit(
+                    &mut function,
+                    &[
+                        Instruction::LocalGet(STRING),
+                        Instruction::LocalGet(SCALAR),
+                        Instruction::I32Const(lead_shift as i32),
+                        Instruction::I32ShrU,
+                        Instruction::I32Const(lead_marker),
+                        Instruction::I32Or,
+                        Instruction::I32Store8(memarg_byte_at(STRING_HEADER_SIZE as u64)),
+                    ],
+                );
+                for continuation in 1..=index as u64 {
+                    let shift = 6 * (index as u64 - continuation);
+                    emit(
+                        &mut function,
+                        &[
+                            Instruction::LocalGet(STRING),
+                            Instruction::LocalGet(SCALAR),
+                            Instruction::I32Const(shift as i32),
+                            Instruction::I32ShrU,
+                            Instruction::I32Const(0x3F),
+                            Instruction::I32And,
+                            Instruction::I32Const(0x80),
+                            Instruction::I32Or,
+                            Instruction::I32Store8(memarg_byte_at(
+                                STRING_HEADER_SIZE as u64 + continuation,
+                            )),
+                        ],
+                    );
+                }
+                emit(
+                    &mut function,
+                    &[
+                        Instruction::I32Const(index as i32 + 1),
+                        Instruction::LocalSet(LEN),
+                    ],
+                );
+                if !is_last {
+                    function.instruction(&Instruction::Else);
+                }
+            }
+            for _ in 0..encodings.len() - 1 {
+                function.instruction(&Instruction::End);
+            }
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(STRING),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32Store(memarg_i32(STRING_LEN_OFFSET)),
+                    Instruction::LocalGet(STRING),
+                ],
+            );
+        }
+        WasmRuntimeHelper::CastFloatToInt => {
+            // WHAT: `Math.trunc`, rejecting non-finite input and results outside the Int range.
+            const VALUE: u32 = 0;
+            const TRUNCATED: u32 = 1;
+
+            emit(
+                &mut function,
+                &[
+                    // `x - x` is NaN exactly when `x` is NaN or infinite.
+                    Instruction::LocalGet(VALUE),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::F64Sub,
+                    Instruction::F64Const(0.0.into()),
+                    Instruction::F64Ne,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(
+                &mut function,
+                plan,
+                BuiltinErrorCode::FloatCastToIntInvalidValue,
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::LocalGet(VALUE),
+                    Instruction::F64Trunc,
+                    Instruction::LocalTee(TRUNCATED),
+                    Instruction::F64Const((INT_MIN as f64).into()),
+                    Instruction::F64Lt,
+                    Instruction::LocalGet(TRUNCATED),
+                    Instruction::F64Const((INT_MAX as f64).into()),
+                    Instruction::F64Gt,
+                    Instruction::I32Or,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(
+                &mut function,
+                plan,
+                BuiltinErrorCode::FloatCastToIntOutOfRange,
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(TRUNCATED),
+                    Instruction::I64TruncF64S,
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleNew)?),
+                ],
+            );
+        }
+        WasmRuntimeHelper::CastIntToChar => {
+            // WHAT: accept Unicode scalar values only, rejecting negatives and surrogates.
+            const VALUE: u32 = 0;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Const(0),
+                    Instruction::I64LtS,
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Const(0x10_FFFF),
+                    Instruction::I64GtS,
+                    Instruction::I32Or,
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Const(0xD800),
+                    Instruction::I64GeS,
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Const(0xDFFF),
+                    Instruction::I64LeS,
+                    Instruction::I32And,
+                    Instruction::I32Or,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(
+                &mut function,
+                plan,
+                BuiltinErrorCode::IntCastToCharInvalidCodepoint,
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleNew)?),
+                ],
+            );
+        }
+        WasmRuntimeHelper::StringWhitespaceLen => {
+            // WHAT: match the whitespace set of `String.prototype.trim`: ASCII tab through
+            // carriage return, space, U+00A0, and the three-byte spaces U+1680, U+2000-U+200A,
+            // U+2028, U+2029, U+202F, U+205F, U+3000 and U+FEFF.
+            const ADDRESS: u32 = 0;
+            const LEAD: u32 = 1;
+            const SCALAR: u32 = 2;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(ADDRESS),
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::LocalTee(LEAD),
+                    Instruction::I32Const(0x09),
+                    Instruction::I32Sub,
+                    Instruction::I32Const(5),
+                    Instruction::I32LtU,
+                    Instruction::LocalGet(LEAD),
+                    Instruction::I32Const(0x20),
+                    Instruction::I32Eq,
+                    Instruction::I32Or,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(1),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(LEAD),
+                    Instruction::I32Const(0xC2),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(2),
+                    Instruction::I32Const(0),
+                    Instruction::LocalGet(ADDRESS),
+                    Instruction::I32Load8U(memarg_byte_at(1)),
+                    Instruction::I32Const(0xA0),
+                    Instruction::I32Eq,
+                    Instruction::Select,
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(LEAD),
+                    Instruction::I32Const(0xF0),
+                    Instruction::I32And,
+                    Instruction::I32Const(0xE0),
+                    Instruction::I32Ne,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(0),
+                    Instruction::Return,
+                    Instruction::End,
+                    // Decode the three-byte scalar.
+                    Instruction::LocalGet(LEAD),
+                    Instruction::I32Const(0x0F),
+                    Instruction::I32And,
+                    Instruction::I32Const(12),
+                    Instruction::I32Shl,
+                    Instruction::LocalGet(ADDRESS),
+                    Instruction::I32Load8U(memarg_byte_at(1)),
+                    Instruction::I32Const(0x3F),
+                    Instruction::I32And,
+                    Instruction::I32Const(6),
+                    Instruction::I32Shl,
+                    Instruction::I32Or,
+                    Instruction::LocalGet(ADDRESS),
+                    Instruction::I32Load8U(memarg_byte_at(2)),
+                    Instruction::I32Const(0x3F),
+                    Instruction::I32And,
+                    Instruction::I32Or,
+                    Instruction::LocalSet(SCALAR),
+                    Instruction::I32Const(3),
+                    Instruction::I32Const(0),
+                    Instruction::LocalGet(SCALAR),
+                    Instruction::I32Const(0x2000),
+                    Instruction::I32Sub,
+                    Instruction::I32Const(0x0B),
+                    Instruction::I32LtU,
+                ],
+            );
+            for scalar in [0x1680, 0x2028, 0x2029, 0x202F, 0x205F, 0x3000, 0xFEFF] {
+                emit(
+                    &mut function,
+                    &[
+                        Instruction::LocalGet(SCALAR),
+                        Instruction::I32Const(scalar),
+                        Instruction::I32Eq,
+                        Instruction::I32Or,
+                    ],
+                );
+            }
+            function.instruction(&Instruction::Select);
+        }
+        WasmRuntimeHelper::CastStringToInt => {
+            // WHAT: accept `-?\d+(_\d+)*` and report values outside the Int range.
+            // WHY: digits stop accumulating once the magnitude passes 2^31, so any length of
+            // digits is range-checked without i64 overflow; format errors still win.
+            const TEXT: u32 = 0;
+            const POSITION: u32 = 1;
+            const END: u32 = 2;
+            const BYTE: u32 = 3;
+            const NEGATIVE: u32 = 4;
+            const AFTER_DIGIT: u32 = 5;
+            const OVERFLOW: u32 = 6;
+            const VALUE: u32 = 7;
+
+            emit_text_bounds(&mut function, TEXT, POSITION, END);
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(END),
+                    Instruction::I32LtU,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Const(i32::from(b'-')),
+                    Instruction::I32Eq,
+                    Instruction::LocalSet(NEGATIVE),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(NEGATIVE),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(POSITION),
+                    Instruction::End,
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(END),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Const(i32::from(b'0')),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(BYTE),
+                    Instruction::I32Const(10),
+                    Instruction::I32LtU,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(1),
+                    Instruction::LocalSet(AFTER_DIGIT),
+                    Instruction::LocalGet(OVERFLOW),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Const(10),
+                    Instruction::I64Mul,
+                    Instruction::LocalGet(BYTE),
+                    Instruction::I64ExtendI32U,
+                    Instruction::I64Add,
+                    Instruction::LocalTee(VALUE),
+                    Instruction::I64Const(-INT_MIN),
+                    Instruction::I64GtS,
+                    Instruction::LocalSet(OVERFLOW),
+                    Instruction::End,
+                    Instruction::Else,
+                    // `BYTE` holds the byte minus '0' here.
+                    Instruction::LocalGet(BYTE),
+                    Instruction::I32Const(i32::from(b'_') - i32::from(b'0')),
+                    Instruction::I32Ne,
+                    Instruction::LocalGet(AFTER_DIGIT),
+                    Instruction::I32Eqz,
+                    Instruction::I32Or,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(&mut function, plan, BuiltinErrorCode::IntParseInvalidFormat)?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::I32Const(0),
+                    Instruction::LocalSet(AFTER_DIGIT),
+                    Instruction::End,
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(POSITION),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::LocalGet(AFTER_DIGIT),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(&mut function, plan, BuiltinErrorCode::IntParseInvalidFormat)?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::I64Const(0),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Sub,
+                    Instruction::LocalGet(VALUE),
+                    Instruction::LocalGet(NEGATIVE),
+                    Instruction::Select,
+                    Instruction::LocalTee(VALUE),
+                    Instruction::I64Const(INT_MAX),
+                    Instruction::I64GtS,
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Const(INT_MIN),
+                    Instruction::I64LtS,
+                    Instruction::I32Or,
+                    Instruction::LocalGet(OVERFLOW),
+                    Instruction::I32Or,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(&mut function, plan, BuiltinErrorCode::IntParseOutOfRange)?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleNew)?),
+                ],
+            );
+        }
+        WasmRuntimeHelper::CastStringToFloat => {
+            emit_string_to_float(&mut function, plan, alloc_index)?;
+        }
+        WasmRuntimeHelper::CastStringToBool => {
+            // WHAT: `value.trim()` must be exactly `true` or `false`.
+            // WHY: trimming both ends equals skipping leading whitespace, matching the literal,
+            // then requiring that only whitespace remains.
+            const TEXT: u32 = 0;
+            const POSITION: u32 = 1;
+            const END: u32 = 2;
+            const WIDTH: u32 = 3;
+            const VALUE: u32 = 4;
+            let whitespace_len = helper_index(plan, WasmRuntimeHelper::StringWhitespaceLen)?;
+
+            emit_text_bounds(&mut function, TEXT, POSITION, END);
+            emit(
+                &mut function,
+                &[
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(END),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::Call(whitespace_len),
+                    Instruction::LocalTee(WIDTH),
+                    Instruction::I32Eqz,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(WIDTH),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(POSITION),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    // `true`: four bytes remain and they spell the literal.
+                    Instruction::LocalGet(END),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Sub,
+                    Instruction::I32Const(4),
+                    Instruction::I32GeU,
+                    Instruction::If(BlockType::Result(ValType::I32)),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Load(memarg_unaligned_i32()),
+                    Instruction::I32Const(TRUE_WORD),
+                    Instruction::I32Eq,
+                    Instruction::Else,
+                    Instruction::I32Const(0),
+                    Instruction::End,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(1),
+                    Instruction::LocalSet(VALUE),
+                    Instruction::I32Const(4),
+                    Instruction::LocalSet(WIDTH),
+                    Instruction::Else,
+                    Instruction::LocalGet(END),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Sub,
+                    Instruction::I32Const(5),
+                    Instruction::I32GeU,
+                    Instruction::If(BlockType::Result(ValType::I32)),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Load(memarg_unaligned_i32()),
+                    Instruction::I32Const(FALS_WORD),
+                    Instruction::I32Eq,
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Load8U(memarg_byte_at(4)),
+                    Instruction::I32Const(i32::from(b'e')),
+                    Instruction::I32Eq,
+                    Instruction::I32And,
+                    Instruction::Else,
+                    Instruction::I32Const(0),
+                    Instruction::End,
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(
+                &mut function,
+                plan,
+                BuiltinErrorCode::StringParseBoolInvalidFormat,
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::I32Const(5),
+                    Instruction::LocalSet(WIDTH),
+                    Instruction::End,
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(WIDTH),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(POSITION),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(END),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::Call(whitespace_len),
+                    Instruction::LocalTee(WIDTH),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(
+                &mut function,
+                plan,
+                BuiltinErrorCode::StringParseBoolInvalidFormat,
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::LocalGet(POSITION),
+                    Instruction::LocalGet(WIDTH),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(POSITION),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64ExtendI32U,
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleNew)?),
+                ],
+            );
+        }
+        WasmRuntimeHelper::CastStringToChar => {
+            // WHAT: the text must hold exactly one scalar, which is decoded from UTF-8.
+            const TEXT: u32 = 0;
+            const PTR: u32 = 1;
+            const LEN: u32 = 2;
+            const SCALAR: u32 = 3;
+            const COUNT: u32 = 4;
+            const INDEX: u32 = 5;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(TEXT),
+                    Instruction::I32Load(memarg_i32(STRING_PTR_OFFSET)),
+                    Instruction::LocalSet(PTR),
+                    Instruction::LocalGet(TEXT),
+                    Instruction::I32Load(memarg_i32(STRING_LEN_OFFSET)),
+                    Instruction::LocalTee(LEN),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(
+                &mut function,
+                plan,
+                BuiltinErrorCode::StringParseCharInvalidFormat,
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    // Sequence length from the lead byte: 1, 2, 3 or 4.
+                    Instruction::I32Const(1),
+                    Instruction::I32Const(2),
+                    Instruction::I32Const(3),
+                    Instruction::I32Const(4),
+                    Instruction::LocalGet(PTR),
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::LocalTee(SCALAR),
+                    Instruction::I32Const(0xF0),
+                    Instruction::I32LtU,
+                    Instruction::Select,
+                    Instruction::LocalGet(SCALAR),
+                    Instruction::I32Const(0xE0),
+                    Instruction::I32LtU,
+                    Instruction::Select,
+                    Instruction::LocalGet(SCALAR),
+                    Instruction::I32Const(0x80),
+                    Instruction::I32LtU,
+                    Instruction::Select,
+                    Instruction::LocalTee(COUNT),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32Ne,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_fail(
+                &mut function,
+                plan,
+                BuiltinErrorCode::StringParseCharInvalidFormat,
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    // Keep the payload bits of the lead byte: 0xFF >> (count + 1) for multi-byte
+                    // sequences, all seven bits for ASCII.
+                    Instruction::LocalGet(SCALAR),
+                    Instruction::I32Const(0xFF),
+                    Instruction::LocalGet(COUNT),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::I32ShrU,
+                    Instruction::I32Const(0x7F),
+                    Instruction::LocalGet(COUNT),
+                    Instruction::I32Const(1),
+                    Instruction::I32Ne,
+                    Instruction::Select,
+                    Instruction::I32And,
+                    Instruction::LocalSet(SCALAR),
+                    Instruction::I32Const(1),
+                    Instruction::LocalSet(INDEX),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(INDEX),
+                    Instruction::LocalGet(COUNT),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(SCALAR),
+                    Instruction::I32Const(6),
+                    Instruction::I32Shl,
+                    Instruction::LocalGet(PTR),
+                    Instruction::LocalGet(INDEX),
+                    Instruction::I32Add,
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Const(0x3F),
+                    Instruction::I32And,
+                    Instruction::I32Or,
+                    Instruction::LocalSet(SCALAR),
+                    Instruction::LocalGet(INDEX),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(INDEX),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(SCALAR),
+                    Instruction::I64ExtendI32U,
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleNew)?),
+                ],
+            );
+        }
+        _ => unreachable!("non-cast helpers are rejected above"),
+    }
+
+    function.instruction(&Instruction::End);
+    Ok(function)
+}
+
+fn emit_string_to_float(
+    function: &mut Function,
+    plan: &WasmEmitPlan,
+    alloc_index: u32,
+) -> Result<(), CompilerError> {
+    // WHAT: validate `-?\d+(_\d+)*(\.\d+(_\d+)*)?(e[+-]?\d+(_\d+)*)?`, copy the text without
+    // underscores, and convert it with the host's `Number.parseFloat`.
+    // WHY: the grammar is tracked with three flags instead of a state table: `need_digit` is set
+    // wherever the next byte must be a digit, `sign_allowed` only at the start and right after
+    // `e`, and `phase` moves from integer (0) to fraction (1) to exponent (2).
+    const TEXT: u32 = 0;
+    const POSITION: u32 = 1;
+    const END: u32 = 2;
+    const BYTE: u32 = 3;
+    const NEED_DIGIT: u32 = 4;
+    const SIGN_ALLOWED: u32 = 5;
+    const PHASE: u32 = 6;
+    const STRIPPED: u32 = 7;
+    const CURSOR: u32 = 8;
+    const VALUE: u32 = 9;
+
+    let parse_float = plan
+        .host_function_indices
+        .get(&WasmHostFunction::ParseFloat)
+        .copied()
+        .ok_or_else(|| {
+            CompilerError::compiler_error(
+                "Wasm emission missing the host parse_float import for String -> Float casts",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })?;
+
+    emit_text_bounds(function, TEXT, POSITION, END);
+    emit(
+        function,
+        &[
+            Instruction::I32Const(1),
+            Instruction::LocalSet(NEED_DIGIT),
+            Instruction::I32Const(1),
+            Instruction::LocalSet(SIGN_ALLOWED),
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(POSITION),
+            Instruction::LocalGet(END),
+            Instruction::I32GeU,
+            Instruction::BrIf(1),
+            Instruction::LocalGet(POSITION),
+            Instruction::I32Load8U(memarg_byte()),
+            Instruction::LocalTee(BYTE),
+            Instruction::I32Const(i32::from(b'0')),
+            Instruction::I32Sub,
+            Instruction::I32Const(10),
+            Instruction::I32LtU,
+            Instruction::If(BlockType::Empty),
+            Instruction::I32Const(0),
+            Instruction::LocalSet(NEED_DIGIT),
+            Instruction::I32Const(0),
+            Instruction::LocalSet(SIGN_ALLOWED),
+            Instruction::Else,
+            // `_` must follow a digit.
+            Instruction::LocalGet(BYTE),
+            Instruction::I32Const(i32::from(b'_')),
+            Instruction::I32Eq,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(NEED_DIGIT),
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_fail(function, plan, BuiltinErrorCode::FloatParseInvalidFormat)?;
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::I32Const(1),
+            Instruction::LocalSet(NEED_DIGIT),
+            Instruction::Else,
+            // `-` at the start or after `e`; `+` only after `e`.
+            Instruction::LocalGet(BYTE),
+            Instruction::I32Const(i32::from(b'-')),
+            Instruction::I32Eq,
+            Instruction::LocalGet(BYTE),
+            Instruction::I32Const(i32::from(b'+')),
+            Instruction::I32Eq,
+            Instruction::LocalGet(PHASE),
+            Instruction::I32Const(2),
+            Instruction::I32Eq,
+            Instruction::I32And,
+            Instruction::I32Or,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(SIGN_ALLOWED),
+            Instruction::I32Eqz,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_fail(function, plan, BuiltinErrorCode::FloatParseInvalidFormat)?;
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::I32Const(0),
+            Instruction::LocalSet(SIGN_ALLOWED),
+            Instruction::I32Const(1),
+            Instruction::LocalSet(NEED_DIGIT),
+            Instruction::Else,
+            // `.` once, after integer digits.
+            Instruction::LocalGet(BYTE),
+            Instruction::I32Const(i32::from(b'.')),
+            Instruction::I32Eq,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(NEED_DIGIT),
+            Instruction::LocalGet(PHASE),
+            Instruction::I32Const(0),
+            Instruction::I32Ne,
+            Instruction::I32Or,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_fail(function, plan, BuiltinErrorCode::FloatParseInvalidFormat)?;
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::I32Const(1),
+            Instruction::LocalSet(PHASE),
+            Instruction::I32Const(1),
+            Instruction::LocalSet(NEED_DIGIT),
+            Instruction::Else,
+            // `e` once, after integer or fraction digits; anything else is invalid.
+            Instruction::LocalGet(BYTE),
+            Instruction::I32Const(i32::from(b'e')),
+            Instruction::I32Ne,
+            Instruction::LocalGet(NEED_DIGIT),
+            Instruction::I32Or,
+            Instruction::LocalGet(PHASE),
+            Instruction::I32Const(2),
+            Instruction::I32Eq,
+            Instruction::I32Or,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_fail(function, plan, BuiltinErrorCode::FloatParseInvalidFormat)?;
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::I32Const(2),
+            Instruction::LocalSet(PHASE),
+            Instruction::I32Const(1),
+            Instruction::LocalSet(NEED_DIGIT),
+            Instruction::I32Const(1),
+            Instruction::LocalSet(SIGN_ALLOWED),
+            Instruction::End,
+            Instruction::End,
+            Instruction::End,
+            Instruction::End,
+            Instruction::LocalGet(POSITION),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(POSITION),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+            Instruction::LocalGet(NEED_DIGIT),
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_fail(function, plan, BuiltinErrorCode::FloatParseInvalidFormat)?;
+    emit(
+        function,
+        &[
+            Instruction::End,
+            // Copy the text without underscores into a fresh finalized string.
+            Instruction::LocalGet(TEXT),
+            Instruction::I32Load(memarg_i32(STRING_LEN_OFFSET)),
+            Instruction::I32Const(STRING_HEADER_SIZE),
+            Instruction::I32Add,
+            Instruction::Call(alloc_index),
+            Instruction::LocalTee(STRIPPED),
+            Instruction::I32Const(STRING_HEADER_SIZE),
+            Instruction::I32Add,
+            Instruction::LocalSet(CURSOR),
+            Instruction::LocalGet(STRIPPED),
+            Instruction::LocalGet(CURSOR),
+            Instruction::I32Store(memarg_i32(STRING_PTR_OFFSET)),
+            Instruction::LocalGet(TEXT),
+            Instruction::I32Load(memarg_i32(STRING_PTR_OFFSET)),
+            Instruction::LocalSet(POSITION),
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(POSITION),
+            Instruction::LocalGet(END),
+            Instruction::I32GeU,
+            Instruction::BrIf(1),
+            Instruction::LocalGet(POSITION),
+            Instruction::I32Load8U(memarg_byte()),
+            Instruction::LocalTee(BYTE),
+            Instruction::I32Const(i32::from(b'_')),
+            Instruction::I32Ne,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(CURSOR),
+            Instruction::LocalGet(BYTE),
+            Instruction::I32Store8(memarg_byte()),
+            Instruction::LocalGet(CURSOR),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(CURSOR),
+            Instruction::End,
+            Instruction::LocalGet(POSITION),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(POSITION),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+            Instruction::LocalGet(STRIPPED),
+            Instruction::LocalGet(CURSOR),
+            Instruction::LocalGet(STRIPPED),
+            Instruction::I32Sub,
+            Instruction::I32Const(STRING_HEADER_SIZE),
+            Instruction::I32Sub,
+            Instruction::I32Store(memarg_i32(STRING_LEN_OFFSET)),
+            Instruction::LocalGet(STRIPPED),
+            Instruction::Call(parse_float),
+            Instruction::LocalTee(VALUE),
+            Instruction::LocalGet(VALUE),
+            Instruction::F64Sub,
+            Instruction::F64Const(0.0.into()),
+            Instruction::F64Ne,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_fail(function, plan, BuiltinErrorCode::FloatParseOutOfRange)?;
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+            Instruction::LocalGet(VALUE),
+            Instruction::I64ReinterpretF64,
+            Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleNew)?),
+        ],
+    );
+    Ok(())
+}
+
+/// Loads a string's content pointer into `position_local` and its end into `end_local`.
+fn emit_text_bounds(function: &mut Function, text_local: u32, position_local: u32, end_local: u32) {
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(text_local),
+            Instruction::I32Load(memarg_i32(STRING_PTR_OFFSET)),
+            Instruction::LocalTee(position_local),
+            Instruction::LocalGet(text_local),
+            Instruction::I32Load(memarg_i32(STRING_LEN_OFFSET)),
+            Instruction::I32Add,
+            Instruction::LocalSet(end_local),
+        ],
+    );
+}
+
+/// Returns an error carrier for `code` from the current helper.
+fn emit_fail(
+    function: &mut Function,
+    plan: &WasmEmitPlan,
+    code: BuiltinErrorCode,
+) -> Result<(), CompilerError> {
+    emit(
+        function,
+        &[
+            Instruction::I32Const(code.as_i32()),
+            Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleError)?),
+            Instruction::Return,
+        ],
+    );
+    Ok(())
+}
+
+fn memarg_byte_at(offset: u64) -> MemArg {
+    MemArg {
+        offset,
+        align: 0,
+        memory_index: 0,
+    }
+}
+
+fn memarg_unaligned_i32() -> MemArg {
+    MemArg {
+        offset: 0,
+        align: 0,
+        memory_index: 0,
+    }
+}
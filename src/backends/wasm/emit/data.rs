@@ -32,5 +32,18 @@ pub(crate) fn build_data_section(
         );
     }
 
+    // WHAT: emitter-owned strings follow static data as `{ptr, len}` headers plus UTF-8 bytes.
+    for runtime_string in &plan.runtime_strings {
+        let mut bytes = Vec::with_capacity(8 + runtime_string.text.len());
+        bytes.extend_from_slice(&(runtime_string.handle + 8).to_le_bytes());
+        bytes.extend_from_slice(&(runtime_string.text.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(runtime_string.text.as_bytes());
+        section.active(
+            0,
+            &ConstExpr::i32_const(runtime_string.handle as i32),
+            bytes,
+        );
+    }
+
     Ok(section)
 }
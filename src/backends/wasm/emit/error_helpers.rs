@@ -0,0 +1,118 @@
+//! Builtin `Error` runtime helper emission.
+//!
+//! WHAT: synthesizes `rt_error_new`, `rt_error_from_code` and `rt_fallible_error`, which build the
+//! `Error` records carried by error carriers.
+//! WHY: maps, checked numerics and casts all fail with builtin codes, and each failure must carry
+//! the same `{message, code}` record as a source-level `Error` so `catch` can read its fields.
+
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::emit::map_helpers::{emit, helper_index, memarg_i32};
+use crate::backends::wasm::emit::sections::{WasmEmitPlan, runtime_string_handle};
+use crate::backends::wasm::runtime::errors::{
+    ERROR_CODE_OFFSET, ERROR_MESSAGE_OFFSET, ERROR_RECORD_SIZE, RUNTIME_ERROR_CODES,
+};
+use crate::backends::wasm::runtime::maps::FALLIBLE_TAG_ERROR;
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
+use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use wasm_encoder::{BlockType, Function, Instruction, ValType};
+
+/// Emit the function body for one `Error` helper.
+///
+/// `alloc_index` is the Wasm function index of `rt_alloc`; other helper indices come from `plan`.
+pub(crate) fn emit_error_helper(
+    helper: WasmRuntimeHelper,
+    plan: &WasmEmitPlan,
+    alloc_index: u32,
+) -> Result<Function, CompilerError> {
+    let mut function = match helper {
+        // param 0: message, param 1: code | local 2: error
+        WasmRuntimeHelper::ErrorNew => Function::new(vec![(1, ValType::I32)]),
+        // param 0: code
+        WasmRuntimeHelper::ErrorFromCode | WasmRuntimeHelper::FallibleError => {
+            Function::new(Vec::new())
+        }
+        _ => {
+            return Err(CompilerError::compiler_error(
+                "emit_error_helper called with non-Error helper variant",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    };
+
+    match helper {
+        WasmRuntimeHelper::ErrorNew => {
+            const MESSAGE: u32 = 0;
+            const CODE: u32 = 1;
+            const ERROR: u32 = 2;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(ERROR_RECORD_SIZE),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalTee(ERROR),
+                    Instruction::LocalGet(MESSAGE),
+                    Instruction::I32Store(memarg_i32(ERROR_MESSAGE_OFFSET)),
+                    Instruction::LocalGet(ERROR),
+                    Instruction::LocalGet(CODE),
+                    Instruction::I32Store(memarg_i32(ERROR_CODE_OFFSET)),
+                    Instruction::LocalGet(ERROR),
+                ],
+            );
+        }
+        WasmRuntimeHelper::ErrorFromCode => {
+            // WHAT: pick the default message for known runtime codes, keeping the code as given.
+            const CODE: u32 = 0;
+            let error_new = helper_index(plan, WasmRuntimeHelper::ErrorNew)?;
+
+            for code in RUNTIME_ERROR_CODES {
+                let message = runtime_string_handle(plan, code.default_message())?;
+                emit(
+                    &mut function,
+                    &[
+                        Instruction::LocalGet(CODE),
+                        Instruction::I32Const(code.as_i32()),
+                        Instruction::I32Eq,
+                        Instruction::If(BlockType::Empty),
+                        Instruction::I32Const(message as i32),
+                        Instruction::LocalGet(CODE),
+                        Instruction::Call(error_new),
+                        Instruction::Return,
+                        Instruction::End,
+                    ],
+                );
+            }
+            let unknown = runtime_string_handle(
+                plan,
+                BuiltinErrorCode::UnknownOrUnassigned.default_message(),
+            )?;
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(unknown as i32),
+                    Instruction::LocalGet(CODE),
+                    Instruction::Call(error_new),
+                ],
+            );
+        }
+        WasmRuntimeHelper::FallibleError => {
+            const CODE: u32 = 0;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(FALLIBLE_TAG_ERROR),
+                    Instruction::LocalGet(CODE),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::ErrorFromCode)?),
+                    Instruction::I64ExtendI32U,
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleNew)?),
+                ],
+            );
+        }
+        _ => unreachable!("non-Error helpers are rejected above"),
+    }
+
+    function.instruction(&Instruction::End);
+    Ok(function)
+}
@@ -95,6 +95,15 @@ pub(crate) fn build_export_section(
             helper_index(plan, WasmRuntimeHelper::Release)?,
         );
     }
+    if helper_exports.export_live_allocations {
+        let global_index = plan.live_allocations_global_index.ok_or_else(|| {
+            CompilerError::compiler_error(
+                "Wasm emission missing live_allocations global for bst_live_allocations",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })?;
+        section.export("bst_live_allocations", ExportKind::Global, global_index);
+    }
 
     Ok(section)
 }
@@ -0,0 +1,1186 @@
+//! Float formatting runtime helper emission.
+//!
+//! WHAT: synthesizes `rt_float_format`, which renders a finite f64 exactly like JS
+//! `String(value)` (with `-0` shown as `0`), plus the fixed-width bignum helpers it uses.
+//! WHY: Float text must be byte-identical across the `html` and `html_wasm` backends. The
+//! formatter generates the shortest round-trip digits with Burger-Dybvig over exact bignums,
+//! picking the closest candidate and breaking ties to even the same way V8's bignum fallback
+//! does, then lays the digits out per ECMAScript `Number::toString`.
+
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::emit::map_helpers::{emit, helper_index, memarg_byte, memarg_i32};
+use crate::backends::wasm::emit::sections::WasmEmitPlan;
+use crate::backends::wasm::runtime::numeric::{
+    BIGNUM_SIZE, FLOAT_FORMAT_MAX_LEN, NUMERIC_STATUS_OK,
+};
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
+use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use wasm_encoder::{BlockType, Function, Instruction, MemArg, ValType};
+
+/// `log10(2)`, used to estimate the decimal exponent before the exact fix-up step.
+const LOG10_2: f64 = 0.301_029_995_663_981_14;
+/// Shortest round-trip output never needs more than 17 significant digits.
+const DIGIT_BUFFER_SIZE: i32 = 32;
+/// `Number::toString` switches to exponent form beyond 21 integer digits.
+const MAX_FIXED_DIGITS: i32 = 21;
+/// `Number::toString` switches to exponent form below `1e-6`.
+const MIN_FIXED_POINT: i32 = -6;
+
+/// Emit the function body for `rt_float_format` or one of its bignum helpers.
+pub(crate) fn emit_float_format_helper(
+    helper: WasmRuntimeHelper,
+    plan: &WasmEmitPlan,
+    alloc_index: u32,
+    release_index: u32,
+    status_global: u32,
+) -> Result<Function, CompilerError> {
+    let mut function = match helper {
+        // param 0: bignum, param 1: value
+        WasmRuntimeHelper::BignumSet => Function::new(Vec::new()),
+        // param 0: bignum, param 1: factor | local 2: offset, local 3: carry, local 4: product
+        WasmRuntimeHelper::BignumMulSmall => {
+            Function::new(vec![(1, ValType::I32), (2, ValType::I64)])
+        }
+        // param 0: bignum, param 1: power | local 2: factor
+        WasmRuntimeHelper::BignumMulPow10 => Function::new(vec![(1, ValType::I32)]),
+        // param 0: bignum, param 1: bits | local 2: word_bytes, local 3: offset,
+        // local 4: source, local 5: shift, local 6: high, local 7: low
+        WasmRuntimeHelper::BignumShl => Function::new(vec![(3, ValType::I32), (3, ValType::I64)]),
+        // param 0: lhs, param 1: rhs | local 2: offset, local 3: lhs_limb, local 4: rhs_limb
+        WasmRuntimeHelper::BignumCompare => Function::new(vec![(3, ValType::I32)]),
+        // param 0: dst, param 1: lhs, param 2: rhs | local 3: offset, local 4: sum
+        WasmRuntimeHelper::BignumAdd => Function::new(vec![(1, ValType::I32), (1, ValType::I64)]),
+        // param 0: lhs, param 1: rhs | local 2: offset, local 3: borrow, local 4: difference
+        WasmRuntimeHelper::BignumSub => Function::new(vec![(1, ValType::I32), (2, ValType::I64)]),
+        // param 0: value | locals listed in `format_locals`
+        WasmRuntimeHelper::FloatFormat => {
+            Function::new(vec![(2, ValType::I64), (19, ValType::I32)])
+        }
+        _ => {
+            return Err(CompilerError::compiler_error(
+                "emit_float_format_helper called with non-formatting helper variant",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    };
+
+    match helper {
+        WasmRuntimeHelper::BignumSet => {
+            const BIGNUM: u32 = 0;
+            const VALUE: u32 = 1;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::I32Const(0),
+                    Instruction::I32Const(BIGNUM_SIZE),
+                    Instruction::MemoryFill(0),
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Store(memarg_limb_pair()),
+                ],
+            );
+        }
+        WasmRuntimeHelper::BignumMulSmall => {
+            const BIGNUM: u32 = 0;
+            const FACTOR: u32 = 1;
+            const OFFSET: u32 = 2;
+            const CARRY: u32 = 3;
+            const PRODUCT: u32 = 4;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(BIGNUM_SIZE),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I64Load32U(memarg_limb()),
+                    Instruction::LocalGet(FACTOR),
+                    Instruction::I64ExtendI32U,
+                    Instruction::I64Mul,
+                    Instruction::LocalGet(CARRY),
+                    Instruction::I64Add,
+                    Instruction::LocalTee(PRODUCT),
+                    Instruction::I64Store32(memarg_limb()),
+                    Instruction::LocalGet(PRODUCT),
+                    Instruction::I64Const(32),
+                    Instruction::I64ShrU,
+                    Instruction::LocalSet(CARRY),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(4),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                ],
+            );
+        }
+        WasmRuntimeHelper::BignumMulPow10 => {
+            // WHAT: multiply by 10^9 chunks, then by the remaining smaller power.
+            const BIGNUM: u32 = 0;
+            const POWER: u32 = 1;
+            const FACTOR: u32 = 2;
+            let mul_small = helper_index(plan, WasmRuntimeHelper::BignumMulSmall)?;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(POWER),
+                    Instruction::I32Const(9),
+                    Instruction::I32LtS,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::I32Const(1_000_000_000),
+                    Instruction::Call(mul_small),
+                    Instruction::LocalGet(POWER),
+                    Instruction::I32Const(9),
+                    Instruction::I32Sub,
+                    Instruction::LocalSet(POWER),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::I32Const(1),
+                    Instruction::LocalSet(FACTOR),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(POWER),
+                    Instruction::I32Const(0),
+                    Instruction::I32LeS,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(FACTOR),
+                    Instruction::I32Const(10),
+                    Instruction::I32Mul,
+                    Instruction::LocalSet(FACTOR),
+                    Instruction::LocalGet(POWER),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::LocalSet(POWER),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::LocalGet(FACTOR),
+                    Instruction::Call(mul_small),
+                ],
+            );
+        }
+        WasmRuntimeHelper::BignumShl => {
+            // WHAT: walk limbs from the top, building each one from the two source limbs it
+            // straddles. Limbs shifted in from below the bignum are zero.
+            const BIGNUM: u32 = 0;
+            const BITS: u32 = 1;
+            const WORD_BYTES: u32 = 2;
+            const OFFSET: u32 = 3;
+            const SOURCE: u32 = 4;
+            const SHIFT: u32 = 5;
+            const HIGH: u32 = 6;
+            const LOW: u32 = 7;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(BITS),
+                    Instruction::I32Const(5),
+                    Instruction::I32ShrU,
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::LocalSet(WORD_BYTES),
+                    Instruction::LocalGet(BITS),
+                    Instruction::I32Const(31),
+                    Instruction::I32And,
+                    Instruction::I64ExtendI32U,
+                    Instruction::LocalSet(SHIFT),
+                    Instruction::I32Const(BIGNUM_SIZE - 4),
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(0),
+                    Instruction::I32LtS,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::LocalGet(WORD_BYTES),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(SOURCE),
+                    Instruction::I32Const(0),
+                    Instruction::I32GeS,
+                    Instruction::If(BlockType::Result(ValType::I64)),
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::LocalGet(SOURCE),
+                    Instruction::I32Add,
+                    Instruction::I64Load32U(memarg_limb()),
+                    Instruction::Else,
+                    Instruction::I64Const(0),
+                    Instruction::End,
+                    Instruction::LocalSet(HIGH),
+                    Instruction::LocalGet(SOURCE),
+                    Instruction::I32Const(4),
+                    Instruction::I32GeS,
+                    Instruction::If(BlockType::Result(ValType::I64)),
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::LocalGet(SOURCE),
+                    Instruction::I32Add,
+                    Instruction::I32Const(4),
+                    Instruction::I32Sub,
+                    Instruction::I64Load32U(memarg_limb()),
+                    Instruction::Else,
+                    Instruction::I64Const(0),
+                    Instruction::End,
+                    Instruction::LocalSet(LOW),
+                    Instruction::LocalGet(BIGNUM),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::LocalGet(HIGH),
+                    Instruction::I64Const(32),
+                    Instruction::I64Shl,
+                    Instruction::LocalGet(LOW),
+                    Instruction::I64Or,
+                    Instruction::LocalGet(SHIFT),
+                    Instruction::I64Shl,
+                    Instruction::I64Const(32),
+                    Instruction::I64ShrU,
+                    Instruction::I64Store32(memarg_limb()),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(4),
+                    Instruction::I32Sub,
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                ],
+            );
+        }
+        WasmRuntimeHelper::BignumCompare => {
+            const LHS: u32 = 0;
+            const RHS: u32 = 1;
+            const OFFSET: u32 = 2;
+            const LHS_LIMB: u32 = 3;
+            const RHS_LIMB: u32 = 4;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(BIGNUM_SIZE - 4),
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(0),
+                    Instruction::I32LtS,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalTee(LHS_LIMB),
+                    Instruction::LocalGet(RHS),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalTee(RHS_LIMB),
+                    Instruction::I32Ne,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(-1),
+                    Instruction::I32Const(1),
+                    Instruction::LocalGet(LHS_LIMB),
+                    Instruction::LocalGet(RHS_LIMB),
+                    Instruction::I32LtU,
+                    Instruction::Select,
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(4),
+                    Instruction::I32Sub,
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::I32Const(0),
+                ],
+            );
+        }
+        WasmRuntimeHelper::BignumAdd => {
+            const DST: u32 = 0;
+            const LHS: u32 = 1;
+            const RHS: u32 = 2;
+            const OFFSET: u32 = 3;
+            const SUM: u32 = 4;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(BIGNUM_SIZE),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(DST),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I64Load32U(memarg_limb()),
+                    Instruction::LocalGet(RHS),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I64Load32U(memarg_limb()),
+                    Instruction::I64Add,
+                    Instruction::LocalGet(SUM),
+                    Instruction::I64Const(32),
+                    Instruction::I64ShrU,
+                    Instruction::I64Add,
+                    Instruction::LocalTee(SUM),
+                    Instruction::I64Store32(memarg_limb()),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(4),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                ],
+            );
+        }
+        WasmRuntimeHelper::BignumSub => {
+            // WHAT: in-place `lhs -= rhs`; callers guarantee `lhs >= rhs`.
+            const LHS: u32 = 0;
+            const RHS: u32 = 1;
+            const OFFSET: u32 = 2;
+            const BORROW: u32 = 3;
+            const DIFFERENCE: u32 = 4;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(BIGNUM_SIZE),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I64Load32U(memarg_limb()),
+                    Instruction::LocalGet(RHS),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I64Load32U(memarg_limb()),
+                    Instruction::I64Sub,
+                    Instruction::LocalGet(BORROW),
+                    Instruction::I64Sub,
+                    Instruction::LocalTee(DIFFERENCE),
+                    Instruction::I64Store32(memarg_limb()),
+                    Instruction::LocalGet(DIFFERENCE),
+                    Instruction::I64Const(63),
+                    Instruction::I64ShrU,
+                    Instruction::LocalSet(BORROW),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(4),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                ],
+            );
+        }
+        WasmRuntimeHelper::FloatFormat => {
+            let helpers = FormatHelpers {
+                alloc: alloc_index,
+                release: release_index,
+                set: helper_index(plan, WasmRuntimeHelper::BignumSet)?,
+                mul_small: helper_index(plan, WasmRuntimeHelper::BignumMulSmall)?,
+                mul_pow10: helper_index(plan, WasmRuntimeHelper::BignumMulPow10)?,
+                shl: helper_index(plan, WasmRuntimeHelper::BignumShl)?,
+                compare: helper_index(plan, WasmRuntimeHelper::BignumCompare)?,
+                add: helper_index(plan, WasmRuntimeHelper::BignumAdd)?,
+                sub: helper_index(plan, WasmRuntimeHelper::BignumSub)?,
+            };
+            emit_float_format(&mut function, &helpers, status_global);
+        }
+        _ => unreachable!("non-formatting helpers are rejected above"),
+    }
+
+    function.instruction(&Instruction::End);
+    Ok(function)
+}
+
+struct FormatHelpers {
+    alloc: u32,
+    release: u32,
+    set: u32,
+    mul_small: u32,
+    mul_pow10: u32,
+    shl: u32,
+    compare: u32,
+    add: u32,
+    sub: u32,
+}
+
+/// Local layout of `rt_float_format`.
+mod format_locals {
+    pub(super) const VALUE: u32 = 0;
+    pub(super) const BITS: u32 = 1;
+    pub(super) const SIGNIFICAND: u32 = 2;
+    pub(super) const RESULT: u32 = 3;
+    /// Absolute address of the next output byte.
+    pub(super) const CURSOR: u32 = 4;
+    /// Remaining numerator `r`, denominator `s`, and the distances to the neighbouring
+    /// doubles' midpoints (`m+`, `m-`), all scaled so `value = r / s` at each digit.
+    pub(super) const R: u32 = 5;
+    pub(super) const S: u32 = 6;
+    pub(super) const M_PLUS: u32 = 7;
+    pub(super) const M_MINUS: u32 = 8;
+    pub(super) const SCRATCH: u32 = 9;
+    pub(super) const DIGITS: u32 = 10;
+    pub(super) const DIGIT_COUNT: u32 = 11;
+    /// Binary exponent `e` of `value = significand * 2^e`.
+    pub(super) const EXPONENT: u32 = 12;
+    pub(super) const ESTIMATE: u32 = 13;
+    /// Decimal point position `n` of `value = 0.digits * 10^n`.
+    pub(super) const POINT: u32 = 14;
+    pub(super) const EVEN: u32 = 15;
+    pub(super) const DIGIT: u32 = 16;
+    pub(super) const LOW: u32 = 17;
+    pub(super) const HIGH: u32 = 18;
+    pub(super) const INDEX: u32 = 19;
+    pub(super) const COMPARISON: u32 = 20;
+    pub(super) const LOWER_CLOSER: u32 = 21;
+}
+
+fn emit_float_format(function: &mut Function, helpers: &FormatHelpers, status_global: u32) {
+    use format_locals::*;
+
+    // Defensive invariant: valid Beanstalk Floats are finite.
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(VALUE),
+            Instruction::F64Abs,
+            Instruction::F64Const(f64::INFINITY.into()),
+            Instruction::F64Lt,
+            Instruction::I32Eqz,
+            Instruction::If(BlockType::Empty),
+            Instruction::I32Const(BuiltinErrorCode::FloatFormatInvariant.as_i32()),
+            Instruction::GlobalSet(status_global),
+            Instruction::I32Const(0),
+            Instruction::Return,
+            Instruction::End,
+            Instruction::I32Const(NUMERIC_STATUS_OK),
+            Instruction::GlobalSet(status_global),
+            // Finalized `{ptr, len}` handle followed by its content bytes.
+            Instruction::I32Const(8 + FLOAT_FORMAT_MAX_LEN),
+            Instruction::Call(helpers.alloc),
+            Instruction::LocalTee(RESULT),
+            Instruction::I32Const(8),
+            Instruction::I32Add,
+            Instruction::LocalSet(CURSOR),
+            // Both zeros print as "0".
+            Instruction::LocalGet(VALUE),
+            Instruction::F64Const(0.0.into()),
+            Instruction::F64Eq,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_byte(function, b'0');
+    function.instruction(&Instruction::Else);
+
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(VALUE),
+            Instruction::I64ReinterpretF64,
+            Instruction::LocalTee(BITS),
+            Instruction::I64Const(0),
+            Instruction::I64LtS,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_byte(function, b'-');
+    emit(
+        function,
+        &[
+            Instruction::End,
+            // Decompose into `significand * 2^EXPONENT`.
+            Instruction::LocalGet(BITS),
+            Instruction::I64Const((1 << 52) - 1),
+            Instruction::I64And,
+            Instruction::LocalSet(SIGNIFICAND),
+            Instruction::LocalGet(BITS),
+            Instruction::I64Const(52),
+            Instruction::I64ShrU,
+            Instruction::I32WrapI64,
+            Instruction::I32Const(0x7ff),
+            Instruction::I32And,
+            Instruction::LocalTee(EXPONENT),
+            Instruction::If(BlockType::Empty),
+            // The gap below a power of two is half the gap above it (except at the
+            // smallest normal exponent, which borders the evenly spaced subnormals).
+            Instruction::LocalGet(SIGNIFICAND),
+            Instruction::I64Eqz,
+            Instruction::LocalGet(EXPONENT),
+            Instruction::I32Const(1),
+            Instruction::I32GtU,
+            Instruction::I32And,
+            Instruction::LocalSet(LOWER_CLOSER),
+            Instruction::LocalGet(SIGNIFICAND),
+            Instruction::I64Const(1 << 52),
+            Instruction::I64Or,
+            Instruction::LocalSet(SIGNIFICAND),
+            Instruction::LocalGet(EXPONENT),
+            Instruction::I32Const(1075),
+            Instruction::I32Sub,
+            Instruction::LocalSet(EXPONENT),
+            Instruction::Else,
+            Instruction::I32Const(-1074),
+            Instruction::LocalSet(EXPONENT),
+            Instruction::End,
+            // Boundaries are inclusive when the significand is even (round-half-even input).
+            Instruction::LocalGet(SIGNIFICAND),
+            Instruction::I64Const(1),
+            Instruction::I64And,
+            Instruction::I64Eqz,
+            Instruction::LocalSet(EVEN),
+            // Scratch bignums are separate blocks, released once the digits are laid out.
+        ],
+    );
+    for local in [R, S, M_PLUS, M_MINUS, SCRATCH] {
+        emit(
+            function,
+            &[
+                Instruction::I32Const(BIGNUM_SIZE),
+                Instruction::Call(helpers.alloc),
+                Instruction::LocalSet(local),
+            ],
+        );
+    }
+    emit(
+        function,
+        &[
+            Instruction::I32Const(DIGIT_BUFFER_SIZE),
+            Instruction::Call(helpers.alloc),
+            Instruction::LocalSet(DIGITS),
+            // r = 2f * 2^max(e, 0), s = 2 * 2^max(-e, 0), m- = m+ = 2^max(e, 0)
+            Instruction::LocalGet(R),
+            Instruction::LocalGet(SIGNIFICAND),
+            Instruction::Call(helpers.set),
+            Instruction::LocalGet(S),
+            Instruction::I64Const(1),
+            Instruction::Call(helpers.set),
+            Instruction::LocalGet(M_MINUS),
+            Instruction::I64Const(1),
+            Instruction::Call(helpers.set),
+            Instruction::LocalGet(EXPONENT),
+            Instruction::I32Const(0),
+            Instruction::I32GeS,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(R),
+            Instruction::LocalGet(EXPONENT),
+            Instruction::Call(helpers.shl),
+            Instruction::LocalGet(M_MINUS),
+            Instruction::LocalGet(EXPONENT),
+            Instruction::Call(helpers.shl),
+            Instruction::Else,
+            Instruction::LocalGet(S),
+            Instruction::I32Const(0),
+            Instruction::LocalGet(EXPONENT),
+            Instruction::I32Sub,
+            Instruction::Call(helpers.shl),
+            Instruction::End,
+            Instruction::LocalGet(R),
+            Instruction::I32Const(1),
+            Instruction::Call(helpers.shl),
+            Instruction::LocalGet(S),
+            Instruction::I32Const(1),
+            Instruction::Call(helpers.shl),
+            Instruction::LocalGet(M_PLUS),
+            Instruction::LocalGet(M_MINUS),
+            Instruction::I32Const(BIGNUM_SIZE),
+            Instruction::MemoryCopy {
+                src_mem: 0,
+                dst_mem: 0,
+            },
+            Instruction::LocalGet(LOWER_CLOSER),
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(R),
+            Instruction::I32Const(1),
+            Instruction::Call(helpers.shl),
+            Instruction::LocalGet(S),
+            Instruction::I32Const(1),
+            Instruction::Call(helpers.shl),
+            Instruction::LocalGet(M_PLUS),
+            Instruction::I32Const(1),
+            Instruction::Call(helpers.shl),
+            Instruction::End,
+            // Estimate k = ceil(log10(value)); it is exact or one too small.
+            Instruction::LocalGet(EXPONENT),
+            Instruction::I32Const(63),
+            Instruction::LocalGet(SIGNIFICAND),
+            Instruction::I64Clz,
+            Instruction::I32WrapI64,
+            Instruction::I32Sub,
+            Instruction::I32Add,
+            Instruction::F64ConvertI32S,
+            Instruction::F64Const(LOG10_2.into()),
+            Instruction::F64Mul,
+            Instruction::F64Const(1e-10.into()),
+            Instruction::F64Sub,
+            Instruction::F64Ceil,
+            Instruction::I32TruncF64S,
+            Instruction::LocalTee(ESTIMATE),
+            Instruction::I32Const(0),
+            Instruction::I32GeS,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(S),
+            Instruction::LocalGet(ESTIMATE),
+            Instruction::Call(helpers.mul_pow10),
+            Instruction::Else,
+        ],
+    );
+    for local in [R, M_PLUS, M_MINUS] {
+        emit(
+            function,
+            &[
+                Instruction::LocalGet(local),
+                Instruction::I32Const(0),
+                Instruction::LocalGet(ESTIMATE),
+                Instruction::I32Sub,
+                Instruction::Call(helpers.mul_pow10),
+            ],
+        );
+    }
+    function.instruction(&Instruction::End);
+
+    // Fix up the estimate: if r + m+ reaches s the first digit is already in place.
+    emit_sum_compare(function, helpers, R, M_PLUS);
+    emit_above_boundary(function);
+    emit(
+        function,
+        &[
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(ESTIMATE),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(POINT),
+            Instruction::Else,
+            Instruction::LocalGet(ESTIMATE),
+            Instruction::LocalSet(POINT),
+        ],
+    );
+    emit_scale_remainders(function, helpers);
+    emit(
+        function,
+        &[
+            Instruction::End,
+            // Digit generation.
+            Instruction::Loop(BlockType::Empty),
+            Instruction::I32Const(0),
+            Instruction::LocalSet(DIGIT),
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(R),
+            Instruction::LocalGet(S),
+            Instruction::Call(helpers.compare),
+            Instruction::I32Const(0),
+            Instruction::I32LtS,
+            Instruction::BrIf(1),
+            Instruction::LocalGet(R),
+            Instruction::LocalGet(S),
+            Instruction::Call(helpers.sub),
+            Instruction::LocalGet(DIGIT),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(DIGIT),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+            Instruction::LocalGet(DIGITS),
+            Instruction::LocalGet(DIGIT_COUNT),
+            Instruction::I32Add,
+            Instruction::LocalGet(DIGIT),
+            Instruction::I32Store8(memarg_byte()),
+            Instruction::LocalGet(DIGIT_COUNT),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(DIGIT_COUNT),
+            // low: rounding down stays within the lower boundary.
+            Instruction::LocalGet(R),
+            Instruction::LocalGet(M_MINUS),
+            Instruction::Call(helpers.compare),
+            Instruction::LocalTee(COMPARISON),
+            Instruction::I32Const(0),
+            Instruction::I32LtS,
+            Instruction::LocalGet(EVEN),
+            Instruction::LocalGet(COMPARISON),
+            Instruction::I32Eqz,
+            Instruction::I32And,
+            Instruction::I32Or,
+            Instruction::LocalSet(LOW),
+        ],
+    );
+    // high: rounding up stays within the upper boundary.
+    emit_sum_compare(function, helpers, R, M_PLUS);
+    emit_above_boundary(function);
+    emit(
+        function,
+        &[
+            Instruction::LocalSet(HIGH),
+            Instruction::LocalGet(LOW),
+            Instruction::LocalGet(HIGH),
+            Instruction::I32Or,
+            Instruction::I32Eqz,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_scale_remainders(function, helpers);
+    emit(
+        function,
+        &[
+            Instruction::Br(1),
+            Instruction::End,
+            // Both directions work: pick the closer one, ties to an even digit.
+            Instruction::LocalGet(LOW),
+            Instruction::LocalGet(HIGH),
+            Instruction::I32And,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_sum_compare(function, helpers, R, R);
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(COMPARISON),
+            Instruction::I32Const(0),
+            Instruction::I32GtS,
+            Instruction::LocalGet(COMPARISON),
+            Instruction::I32Eqz,
+            Instruction::LocalGet(DIGIT),
+            Instruction::I32Const(1),
+            Instruction::I32And,
+            Instruction::I32And,
+            Instruction::I32Or,
+            Instruction::LocalSet(HIGH),
+            Instruction::End,
+            Instruction::LocalGet(HIGH),
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(DIGITS),
+            Instruction::LocalGet(DIGIT_COUNT),
+            Instruction::I32Add,
+            Instruction::I32Const(1),
+            Instruction::I32Sub,
+            Instruction::LocalTee(INDEX),
+            Instruction::LocalGet(INDEX),
+            Instruction::I32Load8U(memarg_byte()),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::I32Store8(memarg_byte()),
+            Instruction::End,
+            Instruction::End,
+        ],
+    );
+
+    emit_layout(function);
+
+    for local in [R, S, M_PLUS, M_MINUS, SCRATCH, DIGITS] {
+        emit(
+            function,
+            &[
+                Instruction::LocalGet(local),
+                Instruction::Call(helpers.release),
+            ],
+        );
+    }
+    emit(
+        function,
+        &[
+            Instruction::End,
+            // Finalize the `{ptr, len}` handle.
+            Instruction::LocalGet(RESULT),
+            Instruction::LocalGet(RESULT),
+            Instruction::I32Const(8),
+            Instruction::I32Add,
+            Instruction::I32Store(memarg_i32(0)),
+            Instruction::LocalGet(RESULT),
+            Instruction::LocalGet(CURSOR),
+            Instruction::LocalGet(RESULT),
+            Instruction::I32Const(8),
+            Instruction::I32Add,
+            Instruction::I32Sub,
+            Instruction::I32Store(memarg_i32(4)),
+            Instruction::LocalGet(RESULT),
+        ],
+    );
+}
+
+/// Lay the generated digits out per ECMAScript `Number::toString`.
+fn emit_layout(function: &mut Function) {
+    use format_locals::*;
+
+    // digits followed by zeros: 123000
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(DIGIT_COUNT),
+            Instruction::LocalGet(POINT),
+            Instruction::I32LeS,
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(MAX_FIXED_DIGITS),
+            Instruction::I32LeS,
+            Instruction::I32And,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_digits(
+        function,
+        Instruction::I32Const(0),
+        Instruction::LocalGet(DIGIT_COUNT),
+    );
+    emit_write_zeros(
+        function,
+        &[
+            Instruction::LocalGet(POINT),
+            Instruction::LocalGet(DIGIT_COUNT),
+            Instruction::I32Sub,
+        ],
+    );
+
+    // point inside the digits: 123.45
+    emit(
+        function,
+        &[
+            Instruction::Else,
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(0),
+            Instruction::I32GtS,
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(MAX_FIXED_DIGITS),
+            Instruction::I32LeS,
+            Instruction::I32And,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_digits(
+        function,
+        Instruction::I32Const(0),
+        Instruction::LocalGet(POINT),
+    );
+    emit_write_byte(function, b'.');
+    emit_write_digits(
+        function,
+        Instruction::LocalGet(POINT),
+        Instruction::LocalGet(DIGIT_COUNT),
+    );
+
+    // small fractions: 0.000123
+    emit(
+        function,
+        &[
+            Instruction::Else,
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(MIN_FIXED_POINT),
+            Instruction::I32GtS,
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(0),
+            Instruction::I32LeS,
+            Instruction::I32And,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_byte(function, b'0');
+    emit_write_byte(function, b'.');
+    emit_write_zeros(
+        function,
+        &[
+            Instruction::I32Const(0),
+            Instruction::LocalGet(POINT),
+            Instruction::I32Sub,
+        ],
+    );
+    emit_write_digits(
+        function,
+        Instruction::I32Const(0),
+        Instruction::LocalGet(DIGIT_COUNT),
+    );
+
+    // exponent form: 1.23e+21, 1e-7
+    function.instruction(&Instruction::Else);
+    emit_write_digits(function, Instruction::I32Const(0), Instruction::I32Const(1));
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(DIGIT_COUNT),
+            Instruction::I32Const(1),
+            Instruction::I32GtS,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_byte(function, b'.');
+    emit_write_digits(
+        function,
+        Instruction::I32Const(1),
+        Instruction::LocalGet(DIGIT_COUNT),
+    );
+    function.instruction(&Instruction::End);
+    emit_write_byte(function, b'e');
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(1),
+            Instruction::I32Sub,
+            Instruction::LocalTee(POINT),
+            Instruction::I32Const(0),
+            Instruction::I32LtS,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_byte(function, b'-');
+    emit(
+        function,
+        &[
+            Instruction::I32Const(0),
+            Instruction::LocalGet(POINT),
+            Instruction::I32Sub,
+            Instruction::LocalSet(POINT),
+            Instruction::Else,
+        ],
+    );
+    emit_write_byte(function, b'+');
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(100),
+            Instruction::I32GeS,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_digit_value(
+        function,
+        &[
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(100),
+            Instruction::I32DivS,
+        ],
+    );
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(10),
+            Instruction::I32GeS,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_write_digit_value(
+        function,
+        &[
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(10),
+            Instruction::I32DivS,
+            Instruction::I32Const(10),
+            Instruction::I32RemS,
+        ],
+    );
+    function.instruction(&Instruction::End);
+    emit_write_digit_value(
+        function,
+        &[
+            Instruction::LocalGet(POINT),
+            Instruction::I32Const(10),
+            Instruction::I32RemS,
+        ],
+    );
+    emit(
+        function,
+        &[Instruction::End, Instruction::End, Instruction::End],
+    );
+}
+
+/// Store `scratch = lhs + rhs` and `comparison = compare(scratch, s)`.
+fn emit_sum_compare(function: &mut Function, helpers: &FormatHelpers, lhs: u32, rhs: u32) {
+    use format_locals::*;
+
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(SCRATCH),
+            Instruction::LocalGet(lhs),
+            Instruction::LocalGet(rhs),
+            Instruction::Call(helpers.add),
+            Instruction::LocalGet(SCRATCH),
+            Instruction::LocalGet(S),
+            Instruction::Call(helpers.compare),
+            Instruction::LocalSet(COMPARISON),
+        ],
+    );
+}
+
+/// Push whether `comparison` reaches the upper boundary (inclusive for even significands).
+fn emit_above_boundary(function: &mut Function) {
+    use format_locals::*;
+
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(COMPARISON),
+            Instruction::I32Const(0),
+            Instruction::I32GtS,
+            Instruction::LocalGet(EVEN),
+            Instruction::LocalGet(COMPARISON),
+            Instruction::I32Eqz,
+            Instruction::I32And,
+            Instruction::I32Or,
+        ],
+    );
+}
+
+/// Multiply `r`, `m+` and `m-` by ten to expose the next digit.
+fn emit_scale_remainders(function: &mut Function, helpers: &FormatHelpers) {
+    use format_locals::*;
+
+    for local in [R, M_PLUS, M_MINUS] {
+        emit(
+            function,
+            &[
+                Instruction::LocalGet(local),
+                Instruction::I32Const(10),
+                Instruction::Call(helpers.mul_small),
+            ],
+        );
+    }
+}
+
+fn emit_write_byte(function: &mut Function, byte: u8) {
+    emit_write_digit_value(function, &[Instruction::I32Const(i32::from(byte) - 48)]);
+}
+
+/// Write the ASCII digit for the value pushed by `value`.
+fn emit_write_digit_value(function: &mut Function, value: &[Instruction<'_>]) {
+    use format_locals::*;
+
+    function.instruction(&Instruction::LocalGet(CURSOR));
+    emit(function, value);
+    emit(
+        function,
+        &[
+            Instruction::I32Const(48),
+            Instruction::I32Add,
+            Instruction::I32Store8(memarg_byte()),
+            Instruction::LocalGet(CURSOR),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(CURSOR),
+        ],
+    );
+}
+
+/// Copy digits `[start, end)` from the digit buffer as ASCII.
+fn emit_write_digits(function: &mut Function, start: Instruction<'_>, end: Instruction<'_>) {
+    use format_locals::*;
+
+    emit(
+        function,
+        &[
+            start,
+            Instruction::LocalSet(INDEX),
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(INDEX),
+            end,
+            Instruction::I32GeS,
+            Instruction::BrIf(1),
+        ],
+    );
+    emit_write_digit_value(
+        function,
+        &[
+            Instruction::LocalGet(DIGITS),
+            Instruction::LocalGet(INDEX),
+            Instruction::I32Add,
+            Instruction::I32Load8U(memarg_byte()),
+        ],
+    );
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(INDEX),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalSet(INDEX),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+        ],
+    );
+}
+
+/// Write `count` ASCII zeros, where `count` is pushed by the given instructions.
+fn emit_write_zeros(function: &mut Function, count: &[Instruction<'_>]) {
+    use format_locals::*;
+
+    emit(function, count);
+    emit(
+        function,
+        &[
+            Instruction::LocalSet(INDEX),
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(INDEX),
+            Instruction::I32Const(0),
+            Instruction::I32LeS,
+            Instruction::BrIf(1),
+        ],
+    );
+    emit_write_byte(function, b'0');
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(INDEX),
+            Instruction::I32Const(1),
+            Instruction::I32Sub,
+            Instruction::LocalSet(INDEX),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+        ],
+    );
+}
+
+fn memarg_limb() -> MemArg {
+    MemArg {
+        offset: 0,
+        align: 2,
+        memory_index: 0,
+    }
+}
+
+fn memarg_limb_pair() -> MemArg {
+    MemArg {
+        offset: 0,
+        align: 3,
+        memory_index: 0,
+    }
+}
@@ -6,12 +6,14 @@ use crate::backends::wasm::emit::instructions::{
     LirBodyEmitContext, emit_statement, emit_terminator,
 };
 use crate::backends::wasm::emit::sections::{DefinedFunctionKey, WasmEmitPlan};
+use crate::backends::wasm::emit::structured_cfg::{WasmStructuredNode, structure_function_body};
 use crate::backends::wasm::emit::types::abi_to_val_type;
 use crate::backends::wasm::lir::function::WasmLirFunction;
-use crate::backends::wasm::lir::instructions::WasmLirTerminator;
+use crate::backends::wasm::lir::instructions::{WasmLirStmt, WasmLirTerminator};
 use crate::backends::wasm::lir::types::{
     WasmAbiType, WasmLirBlockId, WasmLirLocalId, WasmLocalRole,
 };
+use crate::backends::wasm::request::WasmCfgLoweringStrategy;
 use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
 use rustc_hash::FxHashMap;
 use wasm_encoder::{CodeSection, Function, Instruction, ValType};
@@ -19,6 +21,7 @@ use wasm_encoder::{CodeSection, Function, Instruction, ValType};
 pub(crate) fn build_code_section(
     lir_functions: &FxHashMap<u32, &WasmLirFunction>,
     plan: &WasmEmitPlan,
+    cfg_lowering_strategy: WasmCfgLoweringStrategy,
 ) -> Result<CodeSection, CompilerError> {
     let mut section = CodeSection::new();
 
@@ -33,7 +36,7 @@ pub(crate) fn build_code_section(
                     ))
                     .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
                 })?;
-                emit_lir_function(function, plan)?
+                emit_lir_function(function, plan, cfg_lowering_strategy)?
             }
             DefinedFunctionKey::Helper(helper) => emit_helper_function(*helper, plan)?,
         };
@@ -44,13 +47,147 @@ pub(crate) fn build_code_section(
     Ok(section)
 }
 
-fn emit_lir_function(
+/// Memory-model-specific parts of LIR function body emission.
+///
+/// WHAT: the linear-memory plan and the Wasm GC plan both implement this, so CFG structuring,
+///       local layout and terminators are shared between the two emitters.
+pub(crate) trait WasmBodyEmitter {
+    /// Wasm value type used for locals of the given ABI type.
+    fn local_val_type(&self, abi: WasmAbiType) -> Result<ValType, CompilerError>;
+
+    fn emit_statement(
+        &self,
+        function: &mut Function,
+        statement: &WasmLirStmt,
+        context: &LirBodyEmitContext<'_>,
+    ) -> Result<(), CompilerError>;
+}
+
+impl WasmBodyEmitter for WasmEmitPlan {
+    fn local_val_type(&self, abi: WasmAbiType) -> Result<ValType, CompilerError> {
+        abi_to_val_type(abi)
+    }
+
+    fn emit_statement(
+        &self,
+        function: &mut Function,
+        statement: &WasmLirStmt,
+        context: &LirBodyEmitContext<'_>,
+    ) -> Result<(), CompilerError> {
+        emit_statement(function, statement, context, self)
+    }
+}
+
+pub(crate) fn emit_lir_function(
     function: &WasmLirFunction,
-    plan: &WasmEmitPlan,
+    emitter: &impl WasmBodyEmitter,
+    cfg_lowering_strategy: WasmCfgLoweringStrategy,
+) -> Result<Function, CompilerError> {
+    let entry_block = determine_entry_block(function)?;
+
+    // WHAT: irreducible bodies keep the dispatcher loop even when structuring is requested.
+    // WHY: they have no nested block/loop form without duplicating blocks.
+    if cfg_lowering_strategy == WasmCfgLoweringStrategy::Structured
+        && let Some(body) = structure_function_body(function, entry_block)
+    {
+        return emit_structured_function(function, &body, emitter);
+    }
+
+    emit_dispatcher_function(function, entry_block, emitter)
+}
+
+fn emit_structured_function(
+    function: &WasmLirFunction,
+    body: &[WasmStructuredNode<'_>],
+    emitter: &impl WasmBodyEmitter,
+) -> Result<Function, CompilerError> {
+    let local_layout = build_local_layout(function, false, emitter)?;
+    let mut wasm_function = Function::new(local_layout.local_decls);
+    let no_block_indices = FxHashMap::default();
+    let context = LirBodyEmitContext {
+        function_id: function.id,
+        local_index_by_id: &local_layout.local_index_by_id,
+        local_type_by_id: &local_layout.local_type_by_id,
+        block_index_by_id: &no_block_indices,
+        dispatch_local_index: None,
+    };
+
+    emit_structured_nodes(&mut wasm_function, body, &context, emitter)?;
+
+    // Every path leaves through `return`, `unreachable` or a branch, but the validator still
+    // types the end of a trailing `loop` or `if` as reachable.
+    wasm_function.instruction(&Instruction::Unreachable);
+    wasm_function.instruction(&Instruction::End);
+
+    Ok(wasm_function)
+}
+
+fn emit_structured_nodes(
+    wasm_function: &mut Function,
+    nodes: &[WasmStructuredNode<'_>],
+    context: &LirBodyEmitContext<'_>,
+    emitter: &impl WasmBodyEmitter,
+) -> Result<(), CompilerError> {
+    for node in nodes {
+        match node {
+            WasmStructuredNode::Block(body) => {
+                wasm_function.instruction(&Instruction::Block(wasm_encoder::BlockType::Empty));
+                emit_structured_nodes(wasm_function, body, context, emitter)?;
+                wasm_function.instruction(&Instruction::End);
+            }
+            WasmStructuredNode::Loop(body) => {
+                wasm_function.instruction(&Instruction::Loop(wasm_encoder::BlockType::Empty));
+                emit_structured_nodes(wasm_function, body, context, emitter)?;
+                wasm_function.instruction(&Instruction::End);
+            }
+            WasmStructuredNode::If {
+                condition,
+                then_body,
+                else_body,
+            } => {
+                let condition_index = context
+                    .local_index_by_id
+                    .get(condition)
+                    .copied()
+                    .ok_or_else(|| {
+                        CompilerError::compiler_error(format!(
+                            "Wasm emission missing local index for branch condition {:?} in {:?}",
+                            condition, context.function_id
+                        ))
+                        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+                    })?;
+                wasm_function.instruction(&Instruction::LocalGet(condition_index));
+                wasm_function.instruction(&Instruction::If(wasm_encoder::BlockType::Empty));
+                emit_structured_nodes(wasm_function, then_body, context, emitter)?;
+                wasm_function.instruction(&Instruction::Else);
+                emit_structured_nodes(wasm_function, else_body, context, emitter)?;
+                wasm_function.instruction(&Instruction::End);
+            }
+            WasmStructuredNode::Statements(block) => {
+                for statement in &block.statements {
+                    emitter.emit_statement(wasm_function, statement, context)?;
+                }
+            }
+            WasmStructuredNode::Br(depth) => {
+                wasm_function.instruction(&Instruction::Br(*depth));
+            }
+            WasmStructuredNode::Exit(terminator) => {
+                emit_terminator(wasm_function, terminator, context)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn emit_dispatcher_function(
+    function: &WasmLirFunction,
+    entry_block: WasmLirBlockId,
+    emitter: &impl WasmBodyEmitter,
 ) -> Result<Function, CompilerError> {
     // WHAT: build deterministic local declarations and local-id -> local-index maps.
     // WHY: statement/terminator lowering depends on stable local index lookup.
-    let local_layout = build_local_layout(function)?;
+    let local_layout = build_local_layout(function, true, emitter)?;
     let mut wasm_function = Function::new(local_layout.local_decls);
 
     let mut ordered_blocks = function.blocks.iter().collect::<Vec<_>>();
@@ -61,7 +198,6 @@ fn emit_lir_function(
         block_index_by_id.insert(block.id, index as u32);
     }
 
-    let entry_block = determine_entry_block(function)?;
     let entry_block_index = block_index_by_id
         .get(&entry_block)
         .copied()
@@ -72,33 +208,40 @@ fn emit_lir_function(
             ))
             .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
         })?;
+    let dispatch_local_index = local_layout.dispatch_local_index.ok_or_else(|| {
+        CompilerError::compiler_error(format!(
+            "Wasm emission did not reserve a dispatch local for {:?}",
+            function.id
+        ))
+        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+    })?;
 
     wasm_function.instruction(&Instruction::I32Const(entry_block_index as i32));
-    wasm_function.instruction(&Instruction::LocalSet(local_layout.dispatch_local_index));
+    wasm_function.instruction(&Instruction::LocalSet(dispatch_local_index));
     wasm_function.instruction(&Instruction::Block(wasm_encoder::BlockType::Empty));
     wasm_function.instruction(&Instruction::Loop(wasm_encoder::BlockType::Empty));
     // WHAT: use a dispatcher-loop CFG strategy.
-    // WHY: this supports arbitrary lowered CFG now while keeping a clean seam for a future
-    // direct structured pass (`if/else/loop` region construction).
+    // WHY: this supports arbitrary lowered CFG, including irreducible graphs that the
+    // structured strategy cannot nest.
 
     let context = LirBodyEmitContext {
         function_id: function.id,
         local_index_by_id: &local_layout.local_index_by_id,
         local_type_by_id: &local_layout.local_type_by_id,
         block_index_by_id: &block_index_by_id,
-        dispatch_local_index: local_layout.dispatch_local_index,
+        dispatch_local_index: Some(dispatch_local_index),
     };
 
     for (index, block) in ordered_blocks.iter().enumerate() {
         // WHAT: execute only the currently selected block in each loop iteration.
         // WHY: the dispatch local acts as a program counter for structured Wasm control flow.
-        wasm_function.instruction(&Instruction::LocalGet(local_layout.dispatch_local_index));
+        wasm_function.instruction(&Instruction::LocalGet(dispatch_local_index));
         wasm_function.instruction(&Instruction::I32Const(index as i32));
         wasm_function.instruction(&Instruction::I32Eq);
         wasm_function.instruction(&Instruction::If(wasm_encoder::BlockType::Empty));
 
         for statement in &block.statements {
-            emit_statement(&mut wasm_function, statement, &context, plan)?;
+            emitter.emit_statement(&mut wasm_function, statement, &context)?;
         }
         emit_terminator(&mut wasm_function, &block.terminator, &context)?;
 
@@ -121,10 +264,14 @@ struct LocalLayout {
     local_decls: Vec<(u32, ValType)>,
     local_index_by_id: FxHashMap<WasmLirLocalId, u32>,
     local_type_by_id: FxHashMap<WasmLirLocalId, WasmAbiType>,
-    dispatch_local_index: u32,
+    dispatch_local_index: Option<u32>,
 }
 
-fn build_local_layout(function: &WasmLirFunction) -> Result<LocalLayout, CompilerError> {
+fn build_local_layout(
+    function: &WasmLirFunction,
+    with_dispatch_local: bool,
+    emitter: &impl WasmBodyEmitter,
+) -> Result<LocalLayout, CompilerError> {
     // WHAT: local ids are sorted and mapped once for the entire function.
     // WHY: all statement lowering can then use fast, deterministic lookups.
     let mut sorted_locals = function.locals.iter().collect::<Vec<_>>();
@@ -174,7 +321,7 @@ fn build_local_layout(function: &WasmLirFunction) -> Result<LocalLayout, Compile
     for local in sorted_locals.iter().skip(parameter_count) {
         let val_type = match local.ty {
             WasmAbiType::Void => ValType::I32,
-            _ => abi_to_val_type(local.ty)?,
+            _ => emitter.local_val_type(local.ty)?,
         };
 
         if current_type == Some(val_type) {
@@ -193,8 +340,10 @@ fn build_local_layout(function: &WasmLirFunction) -> Result<LocalLayout, Compile
     }
 
     // Dispatcher-local used by dispatcher-loop CFG lowering.
-    local_decls.push((1, ValType::I32));
-    let dispatch_local_index = sorted_locals.len() as u32;
+    let dispatch_local_index = with_dispatch_local.then(|| {
+        local_decls.push((1, ValType::I32));
+        sorted_locals.len() as u32
+    });
 
     Ok(LocalLayout {
         local_decls,
@@ -233,7 +382,9 @@ fn determine_entry_block(function: &WasmLirFunction) -> Result<WasmLirBlockId, C
                 *incoming_counts.entry(then_block).or_insert(0) += 1;
                 *incoming_counts.entry(else_block).or_insert(0) += 1;
             }
-            WasmLirTerminator::Return { .. } | WasmLirTerminator::Trap => {}
+            WasmLirTerminator::Return { .. }
+            | WasmLirTerminator::ReturnMultiValue { .. }
+            | WasmLirTerminator::Trap => {}
         }
     }
 
@@ -0,0 +1,421 @@
+//! Wasm GC runtime helper emission.
+//!
+//! WHAT: synthesizes the string-buffer, integer-formatting and vec helpers of the Wasm GC
+//!       emission mode (object model in `runtime::gc`).
+//! WHY: GC objects are created and grown with `struct.new`/`array.new_default`/`array.copy`, so
+//!      these helpers need no allocator, but growth logic is still worth sharing across call
+//!      sites rather than inlining at every template push.
+
+use crate::backends::wasm::runtime::gc::{
+    GC_BYTES_TYPE, GC_HANDLES_TYPE, GC_LENGTH_FIELD, GC_STORAGE_FIELD, GC_STRING_BUFFER_TYPE,
+    GC_VEC_INITIAL_CAPACITY, GC_VEC_TYPE, WasmGcHelper,
+};
+use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
+use wasm_encoder::{BlockType, Function, HeapType, Instruction, RefType, ValType};
+
+/// Emit the function body for one GC helper.
+pub(crate) fn emit_gc_helper(helper: WasmGcHelper) -> Result<Function, CompilerError> {
+    let function = match helper {
+        WasmGcHelper::BufferPush => emit_buffer_push(),
+        WasmGcHelper::StringFinish => emit_string_finish(),
+        WasmGcHelper::StringFromI64 => emit_string_from_i64(),
+        WasmGcHelper::StringLen => {
+            // param 0: string
+            let mut function = Function::new(Vec::new());
+            function.instruction(&Instruction::LocalGet(0));
+            function.instruction(&cast_to(GC_BYTES_TYPE));
+            function.instruction(&Instruction::ArrayLen);
+            function.instruction(&Instruction::End);
+            function
+        }
+        WasmGcHelper::StringByte => {
+            // param 0: string, param 1: index
+            let mut function = Function::new(Vec::new());
+            function.instruction(&Instruction::LocalGet(0));
+            function.instruction(&cast_to(GC_BYTES_TYPE));
+            function.instruction(&Instruction::LocalGet(1));
+            function.instruction(&Instruction::ArrayGetU(GC_BYTES_TYPE));
+            function.instruction(&Instruction::End);
+            function
+        }
+        WasmGcHelper::VecNew => {
+            let mut function = Function::new(Vec::new());
+            function.instruction(&Instruction::I32Const(GC_VEC_INITIAL_CAPACITY));
+            function.instruction(&Instruction::ArrayNewDefault(GC_HANDLES_TYPE));
+            function.instruction(&Instruction::I32Const(0));
+            function.instruction(&Instruction::StructNew(GC_VEC_TYPE));
+            function.instruction(&Instruction::End);
+            function
+        }
+        WasmGcHelper::VecPush => emit_vec_push(),
+        WasmGcHelper::VecLen => {
+            // param 0: vec
+            let mut function = Function::new(Vec::new());
+            function.instruction(&Instruction::LocalGet(0));
+            function.instruction(&cast_to(GC_VEC_TYPE));
+            function.instruction(&struct_get(GC_VEC_TYPE, GC_LENGTH_FIELD));
+            function.instruction(&Instruction::End);
+            function
+        }
+        WasmGcHelper::VecGet => emit_vec_get(),
+    };
+
+    Ok(function)
+}
+
+fn emit_buffer_push() -> Function {
+    const BUFFER_PARAM: u32 = 0;
+    const SOURCE_PARAM: u32 = 1;
+    const BUFFER: u32 = 2;
+    const SOURCE: u32 = 3;
+    const LEN: u32 = 4;
+    const SOURCE_LEN: u32 = 5;
+    const NEEDED: u32 = 6;
+    const CAPACITY: u32 = 7;
+    const GROWN: u32 = 8;
+
+    let mut function = Function::new(vec![
+        (1, nullable_ref(GC_STRING_BUFFER_TYPE)),
+        (1, nullable_ref(GC_BYTES_TYPE)),
+        (4, ValType::I32),
+        (1, nullable_ref(GC_BYTES_TYPE)),
+    ]);
+
+    function.instruction(&Instruction::LocalGet(BUFFER_PARAM));
+    function.instruction(&cast_to(GC_STRING_BUFFER_TYPE));
+    function.instruction(&Instruction::LocalSet(BUFFER));
+    function.instruction(&Instruction::LocalGet(SOURCE_PARAM));
+    function.instruction(&cast_to(GC_BYTES_TYPE));
+    function.instruction(&Instruction::LocalSet(SOURCE));
+
+    // needed = len + source_len
+    function.instruction(&Instruction::LocalGet(BUFFER));
+    function.instruction(&struct_get(GC_STRING_BUFFER_TYPE, GC_LENGTH_FIELD));
+    function.instruction(&Instruction::LocalSet(LEN));
+    function.instruction(&Instruction::LocalGet(SOURCE));
+    function.instruction(&Instruction::ArrayLen);
+    function.instruction(&Instruction::LocalSet(SOURCE_LEN));
+    function.instruction(&Instruction::LocalGet(LEN));
+    function.instruction(&Instruction::LocalGet(SOURCE_LEN));
+    function.instruction(&Instruction::I32Add);
+    function.instruction(&Instruction::LocalSet(NEEDED));
+
+    // if needed > capacity, grow to max(capacity * 2, needed) and copy the existing content.
+    function.instruction(&Instruction::LocalGet(BUFFER));
+    function.instruction(&struct_get(GC_STRING_BUFFER_TYPE, GC_STORAGE_FIELD));
+    function.instruction(&Instruction::ArrayLen);
+    function.instruction(&Instruction::LocalSet(CAPACITY));
+    function.instruction(&Instruction::LocalGet(NEEDED));
+    function.instruction(&Instruction::LocalGet(CAPACITY));
+    function.instruction(&Instruction::I32GtU);
+    function.instruction(&Instruction::If(BlockType::Empty));
+    emit_grow(
+        &mut function,
+        GC_STRING_BUFFER_TYPE,
+        GC_BYTES_TYPE,
+        BUFFER,
+        LEN,
+        CAPACITY,
+        NEEDED,
+        GROWN,
+    );
+    function.instruction(&Instruction::End);
+
+    // bytes[len..needed] = source
+    function.instruction(&Instruction::LocalGet(BUFFER));
+    function.instruction(&struct_get(GC_STRING_BUFFER_TYPE, GC_STORAGE_FIELD));
+    function.instruction(&Instruction::LocalGet(LEN));
+    function.instruction(&Instruction::LocalGet(SOURCE));
+    function.instruction(&Instruction::I32Const(0));
+    function.instruction(&Instruction::LocalGet(SOURCE_LEN));
+    function.instruction(&copy(GC_BYTES_TYPE));
+
+    function.instruction(&Instruction::LocalGet(BUFFER));
+    function.instruction(&Instruction::LocalGet(NEEDED));
+    function.instruction(&struct_set(GC_STRING_BUFFER_TYPE, GC_LENGTH_FIELD));
+    function.instruction(&Instruction::End);
+    function
+}
+
+fn emit_string_finish() -> Function {
+    const BUFFER_PARAM: u32 = 0;
+    const BUFFER: u32 = 1;
+    const OUT: u32 = 2;
+
+    let mut function = Function::new(vec![
+        (1, nullable_ref(GC_STRING_BUFFER_TYPE)),
+        (1, nullable_ref(GC_BYTES_TYPE)),
+    ]);
+
+    function.instruction(&Instruction::LocalGet(BUFFER_PARAM));
+    function.instruction(&cast_to(GC_STRING_BUFFER_TYPE));
+    function.instruction(&Instruction::LocalSet(BUFFER));
+
+    function.instruction(&Instruction::LocalGet(BUFFER));
+    function.instruction(&struct_get(GC_STRING_BUFFER_TYPE, GC_LENGTH_FIELD));
+    function.instruction(&Instruction::ArrayNewDefault(GC_BYTES_TYPE));
+    function.instruction(&Instruction::LocalSet(OUT));
+
+    function.instruction(&Instruction::LocalGet(OUT));
+    function.instruction(&Instruction::I32Const(0));
+    function.instruction(&Instruction::LocalGet(BUFFER));
+    function.instruction(&struct_get(GC_STRING_BUFFER_TYPE, GC_STORAGE_FIELD));
+    function.instruction(&Instruction::I32Const(0));
+    function.instruction(&Instruction::LocalGet(BUFFER));
+    function.instruction(&struct_get(GC_STRING_BUFFER_TYPE, GC_LENGTH_FIELD));
+    function.instruction(&copy(GC_BYTES_TYPE));
+
+    function.instruction(&Instruction::LocalGet(OUT));
+    function.instruction(&Instruction::End);
+    function
+}
+
+fn emit_string_from_i64() -> Function {
+    const VALUE: u32 = 0;
+    const DIGITS: u32 = 1;
+    const OUT: u32 = 2;
+    const POS: u32 = 3;
+    const NEGATIVE: u32 = 4;
+    const MAGNITUDE: u32 = 5;
+    // i64::MIN needs 19 digits plus the sign.
+    const MAX_LEN: i32 = 20;
+
+    let mut function = Function::new(vec![
+        (2, nullable_ref(GC_BYTES_TYPE)),
+        (2, ValType::I32),
+        (1, ValType::I64),
+    ]);
+
+    function.instruction(&Instruction::I32Const(MAX_LEN));
+    function.instruction(&Instruction::ArrayNewDefault(GC_BYTES_TYPE));
+    function.instruction(&Instruction::LocalSet(DIGITS));
+    function.instruction(&Instruction::I32Const(MAX_LEN));
+    function.instruction(&Instruction::LocalSet(POS));
+
+    // magnitude = |value| as u64; `0 - i64::MIN` wraps to 2^63, which is right when unsigned.
+    function.instruction(&Instruction::LocalGet(VALUE));
+    function.instruction(&Instruction::I64Const(0));
+    function.instruction(&Instruction::I64LtS);
+    function.instruction(&Instruction::LocalTee(NEGATIVE));
+    function.instruction(&Instruction::If(BlockType::Result(ValType::I64)));
+    function.instruction(&Instruction::I64Const(0));
+    function.instruction(&Instruction::LocalGet(VALUE));
+    function.instruction(&Instruction::I64Sub);
+    function.instruction(&Instruction::Else);
+    function.instruction(&Instruction::LocalGet(VALUE));
+    function.instruction(&Instruction::End);
+    function.instruction(&Instruction::LocalSet(MAGNITUDE));
+
+    // Write digits right to left; the loop runs at least once so zero prints "0".
+    function.instruction(&Instruction::Loop(BlockType::Empty));
+    function.instruction(&Instruction::LocalGet(POS));
+    function.instruction(&Instruction::I32Const(1));
+    function.instruction(&Instruction::I32Sub);
+    function.instruction(&Instruction::LocalSet(POS));
+    function.instruction(&Instruction::LocalGet(DIGITS));
+    function.instruction(&Instruction::LocalGet(POS));
+    function.instruction(&Instruction::LocalGet(MAGNITUDE));
+    function.instruction(&Instruction::I64Const(10));
+    function.instruction(&Instruction::I64RemU);
+    function.instruction(&Instruction::I32WrapI64);
+    function.instruction(&Instruction::I32Const(i32::from(b'0')));
+    function.instruction(&Instruction::I32Add);
+    function.instruction(&Instruction::ArraySet(GC_BYTES_TYPE));
+    function.instruction(&Instruction::LocalGet(MAGNITUDE));
+    function.instruction(&Instruction::I64Const(10));
+    function.instruction(&Instruction::I64DivU);
+    function.instruction(&Instruction::LocalTee(MAGNITUDE));
+    function.instruction(&Instruction::I64Const(0));
+    function.instruction(&Instruction::I64Ne);
+    function.instruction(&Instruction::BrIf(0));
+    function.instruction(&Instruction::End);
+
+    function.instruction(&Instruction::LocalGet(NEGATIVE));
+    function.instruction(&Instruction::If(BlockType::Empty));
+    function.instruction(&Instruction::LocalGet(POS));
+    function.instruction(&Instruction::I32Const(1));
+    function.instruction(&Instruction::I32Sub);
+    function.instruction(&Instruction::LocalSet(POS));
+    function.instruction(&Instruction::LocalGet(DIGITS));
+    function.instruction(&Instruction::LocalGet(POS));
+    function.instruction(&Instruction::I32Const(i32::from(b'-')));
+    function.instruction(&Instruction::ArraySet(GC_BYTES_TYPE));
+    function.instruction(&Instruction::End);
+
+    // out = digits[pos..MAX_LEN]
+    function.instruction(&Instruction::I32Const(MAX_LEN));
+    function.instruction(&Instruction::LocalGet(POS));
+    function.instruction(&Instruction::I32Sub);
+    function.instruction(&Instruction::ArrayNewDefault(GC_BYTES_TYPE));
+    function.instruction(&Instruction::LocalSet(OUT));
+    function.instruction(&Instruction::LocalGet(OUT));
+    function.instruction(&Instruction::I32Const(0));
+    function.instruction(&Instruction::LocalGet(DIGITS));
+    function.instruction(&Instruction::LocalGet(POS));
+    function.instruction(&Instruction::I32Const(MAX_LEN));
+    function.instruction(&Instruction::LocalGet(POS));
+    function.instruction(&Instruction::I32Sub);
+    function.instruction(&copy(GC_BYTES_TYPE));
+
+    function.instruction(&Instruction::LocalGet(OUT));
+    function.instruction(&Instruction::End);
+    function
+}
+
+fn emit_vec_push() -> Function {
+    const VEC_PARAM: u32 = 0;
+    const ITEM: u32 = 1;
+    const VEC: u32 = 2;
+    const LEN: u32 = 3;
+    const CAPACITY: u32 = 4;
+    const NEEDED: u32 = 5;
+    const GROWN: u32 = 6;
+
+    let mut function = Function::new(vec![
+        (1, nullable_ref(GC_VEC_TYPE)),
+        (3, ValType::I32),
+        (1, nullable_ref(GC_HANDLES_TYPE)),
+    ]);
+
+    function.instruction(&Instruction::LocalGet(VEC_PARAM));
+    function.instruction(&cast_to(GC_VEC_TYPE));
+    function.instruction(&Instruction::LocalSet(VEC));
+
+    function.instruction(&Instruction::LocalGet(VEC));
+    function.instruction(&struct_get(GC_VEC_TYPE, GC_LENGTH_FIELD));
+    function.instruction(&Instruction::LocalTee(LEN));
+    function.instruction(&Instruction::I32Const(1));
+    function.instruction(&Instruction::I32Add);
+    function.instruction(&Instruction::LocalSet(NEEDED));
+
+    function.instruction(&Instruction::LocalGet(VEC));
+    function.instruction(&struct_get(GC_VEC_TYPE, GC_STORAGE_FIELD));
+    function.instruction(&Instruction::ArrayLen);
+    function.instruction(&Instruction::LocalSet(CAPACITY));
+    function.instruction(&Instruction::LocalGet(NEEDED));
+    function.instruction(&Instruction::LocalGet(CAPACITY));
+    function.instruction(&Instruction::I32GtU);
+    function.instruction(&Instruction::If(BlockType::Empty));
+    emit_grow(
+        &mut function,
+        GC_VEC_TYPE,
+        GC_HANDLES_TYPE,
+        VEC,
+        LEN,
+        CAPACITY,
+        NEEDED,
+        GROWN,
+    );
+    function.instruction(&Instruction::End);
+
+    function.instruction(&Instruction::LocalGet(VEC));
+    function.instruction(&struct_get(GC_VEC_TYPE, GC_STORAGE_FIELD));
+    function.instruction(&Instruction::LocalGet(LEN));
+    function.instruction(&Instruction::LocalGet(ITEM));
+    function.instruction(&Instruction::ArraySet(GC_HANDLES_TYPE));
+
+    function.instruction(&Instruction::LocalGet(VEC));
+    function.instruction(&Instruction::LocalGet(NEEDED));
+    function.instruction(&struct_set(GC_VEC_TYPE, GC_LENGTH_FIELD));
+    function.instruction(&Instruction::End);
+    function
+}
+
+fn emit_vec_get() -> Function {
+    const VEC_PARAM: u32 = 0;
+    const INDEX: u32 = 1;
+    const VEC: u32 = 2;
+
+    let mut function = Function::new(vec![(1, nullable_ref(GC_VEC_TYPE))]);
+
+    function.instruction(&Instruction::LocalGet(VEC_PARAM));
+    function.instruction(&cast_to(GC_VEC_TYPE));
+    function.instruction(&Instruction::LocalSet(VEC));
+
+    // Spare capacity holds nulls, so reads past the logical length trap like the linear path.
+    function.instruction(&Instruction::LocalGet(INDEX));
+    function.instruction(&Instruction::LocalGet(VEC));
+    function.instruction(&struct_get(GC_VEC_TYPE, GC_LENGTH_FIELD));
+    function.instruction(&Instruction::I32GeU);
+    function.instruction(&Instruction::If(BlockType::Empty));
+    function.instruction(&Instruction::Unreachable);
+    function.instruction(&Instruction::End);
+
+    function.instruction(&Instruction::LocalGet(VEC));
+    function.instruction(&struct_get(GC_VEC_TYPE, GC_STORAGE_FIELD));
+    function.instruction(&Instruction::LocalGet(INDEX));
+    function.instruction(&Instruction::ArrayGet(GC_HANDLES_TYPE));
+    function.instruction(&Instruction::End);
+    function
+}
+
+/// Replaces the storage array of a buffer or vec with one of `max(capacity * 2, needed)`
+/// elements holding the first `len` elements of the old one.
+#[allow(clippy::too_many_arguments)]
+fn emit_grow(
+    function: &mut Function,
+    owner_type: u32,
+    array_type: u32,
+    owner: u32,
+    len: u32,
+    capacity: u32,
+    needed: u32,
+    grown: u32,
+) {
+    function.instruction(&Instruction::LocalGet(capacity));
+    function.instruction(&Instruction::I32Const(1));
+    function.instruction(&Instruction::I32Shl);
+    function.instruction(&Instruction::LocalTee(capacity));
+    function.instruction(&Instruction::LocalGet(needed));
+    function.instruction(&Instruction::LocalGet(capacity));
+    function.instruction(&Instruction::LocalGet(needed));
+    function.instruction(&Instruction::I32GtU);
+    function.instruction(&Instruction::Select);
+    function.instruction(&Instruction::ArrayNewDefault(array_type));
+    function.instruction(&Instruction::LocalSet(grown));
+
+    function.instruction(&Instruction::LocalGet(grown));
+    function.instruction(&Instruction::I32Const(0));
+    function.instruction(&Instruction::LocalGet(owner));
+    function.instruction(&struct_get(owner_type, GC_STORAGE_FIELD));
+    function.instruction(&Instruction::I32Const(0));
+    function.instruction(&Instruction::LocalGet(len));
+    function.instruction(&copy(array_type));
+
+    function.instruction(&Instruction::LocalGet(owner));
+    function.instruction(&Instruction::LocalGet(grown));
+    function.instruction(&Instruction::RefAsNonNull);
+    function.instruction(&struct_set(owner_type, GC_STORAGE_FIELD));
+}
+
+pub(crate) fn nullable_ref(type_index: u32) -> ValType {
+    ValType::Ref(RefType {
+        nullable: true,
+        heap_type: HeapType::Concrete(type_index),
+    })
+}
+
+pub(crate) fn cast_to(type_index: u32) -> Instruction<'static> {
+    Instruction::RefCastNonNull(HeapType::Concrete(type_index))
+}
+
+pub(crate) fn struct_get(struct_type_index: u32, field_index: u32) -> Instruction<'static> {
+    Instruction::StructGet {
+        struct_type_index,
+        field_index,
+    }
+}
+
+pub(crate) fn struct_set(struct_type_index: u32, field_index: u32) -> Instruction<'static> {
+    Instruction::StructSet {
+        struct_type_index,
+        field_index,
+    }
+}
+
+fn copy(array_type: u32) -> Instruction<'static> {
+    Instruction::ArrayCopy {
+        array_type_index_dst: array_type,
+        array_type_index_src: array_type,
+    }
+}
@@ -0,0 +1,550 @@
+//! Wasm GC emission mode: index planning and module orchestration.
+//!
+//! WHAT: emits LIR as a module whose records, strings and vecs are Wasm GC `struct`/`array`
+//!       objects (object model in `runtime::gc`), selected by `WasmTargetFeatures::use_wasm_gc`.
+//! WHY: the host collector owns object lifetime, so the module has no memory, allocator or
+//!      globals. Keeping both emitters on the same LIR lets builds compare code size and speed
+//!      between the two memory models.
+
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::emit::functions::emit_lir_function;
+use crate::backends::wasm::emit::gc_helpers::emit_gc_helper;
+use crate::backends::wasm::emit::imports::build_import_section;
+use crate::backends::wasm::emit::names::build_name_custom_section;
+use crate::backends::wasm::emit::types::gc_abi_to_val_type;
+use crate::backends::wasm::emit::validate::validate_emitted_module;
+use crate::backends::wasm::emit::{WasmEmitDebugOutputs, WasmEmitResult};
+use crate::backends::wasm::lir::instructions::WasmLirStmt;
+use crate::backends::wasm::lir::linkage::{WasmExportKind, WasmImportKind};
+use crate::backends::wasm::lir::module::{WasmLirModule, WasmRecordType, WasmStaticData};
+use crate::backends::wasm::lir::types::{
+    WasmAbiType, WasmImportId, WasmLirFunctionId, WasmLirSignature, WasmRecordTypeId,
+    WasmStaticDataId,
+};
+use crate::backends::wasm::request::WasmBackendRequest;
+use crate::backends::wasm::runtime::gc::{
+    GC_BYTES_TYPE, GC_HANDLES_TYPE, GC_RECORD_TYPE_BASE, GC_STRING_BUFFER_TYPE, GC_VEC_TYPE,
+    WasmGcHelper, gc_helper_emit_order,
+};
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
+use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use rustc_hash::FxHashMap;
+use std::fmt::Write as _;
+use wasm_encoder::{
+    CodeSection, DataCountSection, DataSection, ExportKind, ExportSection, FieldType,
+    FunctionSection, HeapType, Module, RefType, StorageType, TypeSection, ValType,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasmGcFunctionKey {
+    /// User/runtime-template function lowered into LIR.
+    Lir(WasmLirFunctionId),
+    /// Synthetic GC runtime helper.
+    Helper(WasmGcHelper),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct WasmGcEmitPlan {
+    /// Record types indexed by `WasmRecordTypeId`; each is declared at
+    /// `GC_RECORD_TYPE_BASE + id`.
+    pub record_types: Vec<WasmRecordType>,
+    /// Interned function signatures; entry `i` is declared at type index
+    /// `signature_type_base + i`.
+    pub type_entries: Vec<WasmLirSignature>,
+    pub type_index_by_signature: FxHashMap<WasmLirSignature, u32>,
+    /// First type index after the fixed and record struct/array types.
+    pub signature_type_base: u32,
+    pub import_function_indices: FxHashMap<WasmImportId, u32>,
+    pub function_indices: FxHashMap<WasmLirFunctionId, u32>,
+    pub helper_indices: FxHashMap<WasmGcHelper, u32>,
+    /// Defined-function order shared by function and code sections.
+    pub defined_function_order: Vec<WasmGcFunctionKey>,
+    pub defined_function_type_indices: Vec<u32>,
+    /// Passive data segment index per static data id.
+    pub data_segment_indices: FxHashMap<WasmStaticDataId, u32>,
+    pub data_lengths: FxHashMap<WasmStaticDataId, u32>,
+}
+
+impl WasmGcEmitPlan {
+    pub(crate) fn record_type(
+        &self,
+        record_type: WasmRecordTypeId,
+    ) -> Result<&WasmRecordType, CompilerError> {
+        self.record_types
+            .get(record_type.0 as usize)
+            .ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "Wasm GC emission missing record type {record_type:?}"
+                ))
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+            })
+    }
+
+    pub(crate) fn helper_index(&self, helper: WasmGcHelper) -> Result<u32, CompilerError> {
+        self.helper_indices.get(&helper).copied().ok_or_else(|| {
+            CompilerError::compiler_error(format!(
+                "Wasm GC emission missing helper function index for {}",
+                helper.name()
+            ))
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })
+    }
+}
+
+/// Struct type index of a record type.
+pub(crate) fn gc_record_type_index(record_type: WasmRecordTypeId) -> u32 {
+    GC_RECORD_TYPE_BASE + record_type.0
+}
+
+pub(crate) fn emit_lir_to_wasm_gc_module(
+    lir_module: &WasmLirModule,
+    request: &WasmBackendRequest,
+) -> Result<WasmEmitResult, CompilerError> {
+    let plan = build_gc_emit_plan(lir_module, request)?;
+    let mut wasm_module = Module::new();
+
+    // WHAT: fixed GC types, then one struct per record type, then function signatures.
+    // WHY: statements and helpers address the fixed types by constant index.
+    let mut type_section = TypeSection::new();
+    type_section.ty().array(&StorageType::I8, true);
+    type_section
+        .ty()
+        .array(&StorageType::Val(ValType::Ref(RefType::ANYREF)), true);
+    type_section.ty().struct_(vec![
+        storage_field(GC_BYTES_TYPE),
+        field(StorageType::Val(ValType::I32)),
+    ]);
+    type_section.ty().struct_(vec![
+        storage_field(GC_HANDLES_TYPE),
+        field(StorageType::Val(ValType::I32)),
+    ]);
+    for record_type in &plan.record_types {
+        let mut fields = Vec::with_capacity(record_type.slots.len());
+        for slot in &record_type.slots {
+            fields.push(field(StorageType::Val(gc_abi_to_val_type(slot.abi)?)));
+        }
+        type_section.ty().struct_(fields);
+    }
+    for signature in &plan.type_entries {
+        let mut params = Vec::with_capacity(signature.params.len());
+        for param in &signature.params {
+            params.push(gc_abi_to_val_type(*param)?);
+        }
+
+        let mut results = Vec::with_capacity(signature.results.len());
+        for result in &signature.results {
+            results.push(gc_abi_to_val_type(*result)?);
+        }
+
+        type_section.ty().function(params, results);
+    }
+    wasm_module.section(&type_section);
+
+    if !lir_module.imports.is_empty() {
+        let import_section = build_import_section(lir_module, &plan.type_index_by_signature)?;
+        wasm_module.section(&import_section);
+    }
+
+    if !plan.defined_function_order.is_empty() {
+        let mut function_section = FunctionSection::new();
+        for type_index in &plan.defined_function_type_indices {
+            function_section.function(*type_index);
+        }
+        wasm_module.section(&function_section);
+    }
+
+    let has_exports = !lir_module.exports.is_empty() || gc_helper_exports_requested(request);
+    if has_exports {
+        let export_section = build_gc_export_section(lir_module, &plan, request)?;
+        wasm_module.section(&export_section);
+    }
+
+    // WHAT: `array.new_data` in code bodies requires the data count section.
+    if !lir_module.static_data.is_empty() {
+        wasm_module.section(&DataCountSection {
+            count: lir_module.static_data.len() as u32,
+        });
+    }
+
+    if !plan.defined_function_order.is_empty() {
+        let mut lir_functions = FxHashMap::default();
+        for function in &lir_module.functions {
+            lir_functions.insert(function.id, function);
+        }
+
+        let mut code_section = CodeSection::new();
+        for key in &plan.defined_function_order {
+            let body = match key {
+                WasmGcFunctionKey::Lir(function_id) => {
+                    let function = lir_functions.get(function_id).copied().ok_or_else(|| {
+                        CompilerError::compiler_error(format!(
+                            "Wasm GC emission missing lowered function body for {function_id:?}"
+                        ))
+                        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+                    })?;
+                    emit_lir_function(function, &plan, request.emit_options.cfg_lowering_strategy)?
+                }
+                WasmGcFunctionKey::Helper(helper) => emit_gc_helper(*helper)?,
+            };
+            code_section.function(&body);
+        }
+        wasm_module.section(&code_section);
+    }
+
+    // WHAT: static data becomes passive segments in id order.
+    // WHY: there is no memory to place them in; `array.new_data` copies them into GC arrays.
+    if !lir_module.static_data.is_empty() {
+        let mut data_section = DataSection::new();
+        for segment in sorted_static_data(lir_module) {
+            data_section.passive(segment.bytes.to_owned());
+        }
+        wasm_module.section(&data_section);
+    }
+
+    if request.emit_options.emit_name_section {
+        let name_section = build_name_custom_section();
+        wasm_module.section(&name_section);
+    }
+
+    let wasm_bytes = wasm_module.finish();
+    let validation_text = if request.emit_options.validate_emitted_module {
+        Some(validate_emitted_module(&wasm_bytes)?)
+    } else {
+        None
+    };
+
+    Ok(WasmEmitResult {
+        wasm_bytes,
+        debug_outputs: WasmEmitDebugOutputs {
+            sections_text: gc_sections_text(lir_module, &plan),
+            indices_text: gc_indices_text(lir_module, &plan),
+            data_layout_text: gc_data_layout_text(lir_module, &plan),
+            validation_text,
+        },
+    })
+}
+
+fn build_gc_emit_plan(
+    module: &WasmLirModule,
+    request: &WasmBackendRequest,
+) -> Result<WasmGcEmitPlan, CompilerError> {
+    let mut record_types = module.record_types.clone();
+    record_types.sort_by_key(|record_type| record_type.id.0);
+    for (index, record_type) in record_types.iter().enumerate() {
+        if record_type.id.0 as usize != index {
+            return Err(CompilerError::compiler_error(format!(
+                "Wasm GC emission expected dense record type ids but found {:?} at position {index}",
+                record_type.id
+            ))
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    }
+
+    let signature_type_base = GC_RECORD_TYPE_BASE + record_types.len() as u32;
+    let mut type_entries = Vec::new();
+    let mut type_index_by_signature = FxHashMap::default();
+    let mut intern = |signature: &WasmLirSignature| -> u32 {
+        if let Some(existing) = type_index_by_signature.get(signature).copied() {
+            return existing;
+        }
+        let index = signature_type_base + type_entries.len() as u32;
+        type_entries.push(signature.clone());
+        type_index_by_signature.insert(signature.clone(), index);
+        index
+    };
+
+    let mut import_function_indices = FxHashMap::default();
+    let mut function_indices = FxHashMap::default();
+    let mut helper_indices = FxHashMap::default();
+    let mut defined_function_order = Vec::new();
+    let mut defined_function_type_indices = Vec::new();
+    let mut next_function_index = 0u32;
+
+    let mut imports = module.imports.iter().collect::<Vec<_>>();
+    imports.sort_by_key(|import| import.id.0);
+    for import in imports {
+        let WasmImportKind::Function(signature) = &import.kind;
+        intern(signature);
+        import_function_indices.insert(import.id, next_function_index);
+        next_function_index += 1;
+    }
+
+    let mut lir_functions = module.functions.iter().collect::<Vec<_>>();
+    lir_functions.sort_by_key(|function| function.id.0);
+    for function in lir_functions {
+        let type_index = intern(&function.signature);
+        function_indices.insert(function.id, next_function_index);
+        defined_function_order.push(WasmGcFunctionKey::Lir(function.id));
+        defined_function_type_indices.push(type_index);
+        next_function_index += 1;
+    }
+
+    // WHAT: the helper set is all-or-nothing in a fixed order, like the linear base helpers.
+    if module_uses_gc_helpers(module) || gc_helper_exports_requested(request) {
+        for helper in gc_helper_emit_order() {
+            let type_index = intern(&gc_helper_signature(helper));
+            helper_indices.insert(helper, next_function_index);
+            defined_function_order.push(WasmGcFunctionKey::Helper(helper));
+            defined_function_type_indices.push(type_index);
+            next_function_index += 1;
+        }
+    }
+
+    let mut data_segment_indices = FxHashMap::default();
+    let mut data_lengths = FxHashMap::default();
+    for (index, segment) in sorted_static_data(module).into_iter().enumerate() {
+        data_segment_indices.insert(segment.id, index as u32);
+        data_lengths.insert(segment.id, segment.bytes.len() as u32);
+    }
+
+    Ok(WasmGcEmitPlan {
+        record_types,
+        type_entries,
+        type_index_by_signature,
+        signature_type_base,
+        import_function_indices,
+        function_indices,
+        helper_indices,
+        defined_function_order,
+        defined_function_type_indices,
+        data_segment_indices,
+        data_lengths,
+    })
+}
+
+pub(crate) fn gc_helper_signature(helper: WasmGcHelper) -> WasmLirSignature {
+    use WasmAbiType::{Handle, I32, I64};
+
+    let (params, results) = match helper {
+        WasmGcHelper::BufferPush | WasmGcHelper::VecPush => (vec![Handle, Handle], vec![]),
+        WasmGcHelper::StringFinish => (vec![Handle], vec![Handle]),
+        WasmGcHelper::StringFromI64 => (vec![I64], vec![Handle]),
+        WasmGcHelper::StringLen | WasmGcHelper::VecLen => (vec![Handle], vec![I32]),
+        WasmGcHelper::StringByte => (vec![Handle, I32], vec![I32]),
+        WasmGcHelper::VecNew => (vec![], vec![Handle]),
+        WasmGcHelper::VecGet => (vec![Handle, I32], vec![Handle]),
+    };
+    WasmLirSignature { params, results }
+}
+
+pub(crate) fn gc_helper_exports_requested(request: &WasmBackendRequest) -> bool {
+    let helpers = &request.export_policy.helper_exports;
+    helpers.export_str_len
+        || helpers.export_str_byte
+        || helpers.export_vec_new
+        || helpers.export_vec_push
+        || helpers.export_vec_len
+        || helpers.export_vec_get
+}
+
+fn build_gc_export_section(
+    module: &WasmLirModule,
+    plan: &WasmGcEmitPlan,
+    request: &WasmBackendRequest,
+) -> Result<ExportSection, CompilerError> {
+    let mut section = ExportSection::new();
+
+    for export in &module.exports {
+        match export.kind {
+            WasmExportKind::Function(function_id) => {
+                let function_index = plan
+                    .function_indices
+                    .get(&function_id)
+                    .copied()
+                    .ok_or_else(|| {
+                        CompilerError::compiler_error(format!(
+                            "Wasm GC emission could not resolve function index for export '{function_id:?}'"
+                        ))
+                        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+                    })?;
+                section.export(
+                    export.export_name.as_str(),
+                    ExportKind::Func,
+                    function_index,
+                );
+            }
+        }
+    }
+
+    // WHAT: handle accessors keep the linear-memory export names where the contract is shared.
+    // WHY: strings are opaque GC arrays to the host, so `bst_str_byte` replaces `bst_str_ptr`
+    // plus a memory view.
+    let helper_exports = &request.export_policy.helper_exports;
+    let exports = [
+        (
+            helper_exports.export_str_len,
+            "bst_str_len",
+            WasmGcHelper::StringLen,
+        ),
+        (
+            helper_exports.export_str_byte,
+            "bst_str_byte",
+            WasmGcHelper::StringByte,
+        ),
+        (
+            helper_exports.export_vec_new,
+            "bst_vec_new",
+            WasmGcHelper::VecNew,
+        ),
+        (
+            helper_exports.export_vec_push,
+            "bst_vec_push",
+            WasmGcHelper::VecPush,
+        ),
+        (
+            helper_exports.export_vec_len,
+            "bst_vec_len",
+            WasmGcHelper::VecLen,
+        ),
+        (
+            helper_exports.export_vec_get,
+            "bst_vec_get",
+            WasmGcHelper::VecGet,
+        ),
+    ];
+    for (enabled, name, helper) in exports {
+        if enabled {
+            section.export(name, ExportKind::Func, plan.helper_index(helper)?);
+        }
+    }
+
+    Ok(section)
+}
+
+fn module_uses_gc_helpers(module: &WasmLirModule) -> bool {
+    module.functions.iter().any(|function| {
+        function.blocks.iter().any(|block| {
+            block.statements.iter().any(|statement| {
+                matches!(
+                    statement,
+                    WasmLirStmt::StringNewBuffer { .. }
+                        | WasmLirStmt::StringPushLiteral { .. }
+                        | WasmLirStmt::StringPushHandle { .. }
+                        | WasmLirStmt::StringFromI64 { .. }
+                        | WasmLirStmt::StringFinish { .. }
+                        | WasmLirStmt::VecNew { .. }
+                        | WasmLirStmt::VecPushHandle { .. }
+                        | WasmLirStmt::Cast {
+                            policy: BuiltinCastPolicyId::IntToString,
+                            ..
+                        }
+                )
+            })
+        })
+    })
+}
+
+fn sorted_static_data(module: &WasmLirModule) -> Vec<&WasmStaticData> {
+    let mut static_data = module.static_data.iter().collect::<Vec<_>>();
+    static_data.sort_by_key(|segment| segment.id.0);
+    static_data
+}
+
+fn field(element_type: StorageType) -> FieldType {
+    FieldType {
+        element_type,
+        mutable: true,
+    }
+}
+
+fn storage_field(array_type: u32) -> FieldType {
+    field(StorageType::Val(ValType::Ref(RefType {
+        nullable: false,
+        heap_type: HeapType::Concrete(array_type),
+    })))
+}
+
+fn gc_sections_text(module: &WasmLirModule, plan: &WasmGcEmitPlan) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Wasm section plan (Wasm GC)");
+    let _ = writeln!(
+        out,
+        "  type: {}",
+        plan.signature_type_base as usize + plan.type_entries.len()
+    );
+    let _ = writeln!(out, "  import: {}", module.imports.len());
+    let _ = writeln!(out, "  function: {}", plan.defined_function_order.len());
+    let _ = writeln!(out, "  memory: 0");
+    let _ = writeln!(out, "  global: 0");
+    let _ = writeln!(out, "  export: {}", module.exports.len());
+    let _ = writeln!(out, "  code: {}", plan.defined_function_order.len());
+    let _ = writeln!(out, "  data: {}", module.static_data.len());
+    out
+}
+
+fn gc_indices_text(module: &WasmLirModule, plan: &WasmGcEmitPlan) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Wasm index maps (Wasm GC)");
+    let _ = writeln!(out, "  type[{GC_BYTES_TYPE}] bytes array(mut i8)");
+    let _ = writeln!(out, "  type[{GC_HANDLES_TYPE}] handles array(mut anyref)");
+    let _ = writeln!(out, "  type[{GC_STRING_BUFFER_TYPE}] string_buffer struct");
+    let _ = writeln!(out, "  type[{GC_VEC_TYPE}] vec struct");
+    for record_type in &plan.record_types {
+        let _ = writeln!(
+            out,
+            "  type[{}] record {} fields={}",
+            gc_record_type_index(record_type.id),
+            record_type.debug_name,
+            record_type.slots.len()
+        );
+    }
+    for (offset, signature) in plan.type_entries.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "  type[{}] params={:?} results={:?}",
+            plan.signature_type_base as usize + offset,
+            signature.params,
+            signature.results
+        );
+    }
+
+    let mut imports = module.imports.iter().collect::<Vec<_>>();
+    imports.sort_by_key(|import| import.id.0);
+    for import in imports {
+        if let Some(index) = plan.import_function_indices.get(&import.id) {
+            let _ = writeln!(
+                out,
+                "  import.func[{index}] {:?} {}.{}",
+                import.id, import.module_name, import.item_name
+            );
+        }
+    }
+
+    let mut functions = module.functions.iter().collect::<Vec<_>>();
+    functions.sort_by_key(|function| function.id.0);
+    for function in functions {
+        if let Some(index) = plan.function_indices.get(&function.id) {
+            let _ = writeln!(
+                out,
+                "  func[{index}] {:?} {}",
+                function.id, function.debug_name
+            );
+        }
+    }
+
+    let mut helpers = plan.helper_indices.iter().collect::<Vec<_>>();
+    helpers.sort_by_key(|(_, index)| **index);
+    for (helper, index) in helpers {
+        let _ = writeln!(out, "  helper[{index}] {}", helper.name());
+    }
+
+    out
+}
+
+fn gc_data_layout_text(module: &WasmLirModule, plan: &WasmGcEmitPlan) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Wasm data layout (Wasm GC)");
+    for segment in sorted_static_data(module) {
+        if let Some(index) = plan.data_segment_indices.get(&segment.id) {
+            let _ = writeln!(
+                out,
+                "  data[{}] passive segment={index} len={} name={}",
+                segment.id.0,
+                segment.bytes.len(),
+                segment.debug_name
+            );
+        }
+    }
+    let _ = writeln!(out, "  no linear memory; objects are host-collected");
+    out
+}
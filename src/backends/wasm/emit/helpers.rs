@@ -4,20 +4,26 @@ use crate::backends::error_types::BackendErrorType;
 use crate::backends::wasm::emit::sections::WasmEmitPlan;
 use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
 use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
-use wasm_encoder::{Function, Instruction, MemArg, ValType};
+use wasm_encoder::{BlockType, Function, Instruction, MemArg, ValType};
 
 pub(crate) fn emit_helper_function(
     helper: WasmRuntimeHelper,
     plan: &WasmEmitPlan,
 ) -> Result<Function, CompilerError> {
-    // WHAT: helpers share one bump-allocation/global model.
-    // WHY: correctness-first runtime scaffolding until richer ownership/runtime logic lands.
+    // WHAT: helpers share one free-list allocator (see `runtime::memory`).
+    // WHY: every runtime object is a `rt_alloc` block that `rt_release` can hand back.
     let heap_top_global = plan.heap_top_global_index.ok_or_else(|| {
         CompilerError::compiler_error(
             "Wasm emission expected heap_top global while synthesizing runtime helpers",
         )
         .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
     })?;
+    let live_allocations_global = plan.live_allocations_global_index.ok_or_else(|| {
+        CompilerError::compiler_error(
+            "Wasm emission expected live_allocations global while synthesizing runtime helpers",
+        )
+        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+    })?;
 
     let alloc_index = plan
         .helper_indices
@@ -28,6 +34,29 @@ pub(crate) fn emit_helper_function(
                 .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
         })?;
 
+    if matches!(
+        helper,
+        WasmRuntimeHelper::Alloc | WasmRuntimeHelper::Release | WasmRuntimeHelper::DropIfOwned
+    ) {
+        return super::alloc_helpers::emit_alloc_helper(
+            helper,
+            plan,
+            &super::alloc_helpers::AllocatorGlobals {
+                heap_top: heap_top_global,
+                live_allocations: live_allocations_global,
+            },
+        );
+    }
+
+    let release_index = plan
+        .helper_indices
+        .get(&WasmRuntimeHelper::Release)
+        .copied()
+        .ok_or_else(|| {
+            CompilerError::compiler_error("Wasm emission missing rt_release helper index")
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })?;
+
     // Vec-handle helpers have their own focused emitter.
     if matches!(
         helper,
@@ -36,16 +65,68 @@ pub(crate) fn emit_helper_function(
             | WasmRuntimeHelper::VecLen
             | WasmRuntimeHelper::VecGet
     ) {
-        return super::vec_helpers::emit_vec_helper(helper, alloc_index);
+        return super::vec_helpers::emit_vec_helper(helper, alloc_index, release_index);
+    }
+
+    // Map and fallible-carrier helpers live in their own emitter.
+    if matches!(
+        helper,
+        WasmRuntimeHelper::MapNew
+            | WasmRuntimeHelper::MapGet
+            | WasmRuntimeHelper::MapContains
+            | WasmRuntimeHelper::MapSet
+            | WasmRuntimeHelper::MapRemove
+            | WasmRuntimeHelper::MapClear
+            | WasmRuntimeHelper::MapLength
+            | WasmRuntimeHelper::MapHashKey
+            | WasmRuntimeHelper::MapKeysEqual
+            | WasmRuntimeHelper::MapFind
+            | WasmRuntimeHelper::MapRebuild
+            | WasmRuntimeHelper::FallibleNew
+    ) {
+        return super::map_helpers::emit_map_helper(helper, plan, alloc_index);
+    }
+
+    // Builtin `Error` constructors share the carrier layout with map helpers.
+    if matches!(
+        helper,
+        WasmRuntimeHelper::ErrorNew
+            | WasmRuntimeHelper::ErrorFromCode
+            | WasmRuntimeHelper::FallibleError
+    ) {
+        return super::error_helpers::emit_error_helper(helper, plan, alloc_index);
+    }
+
+    if is_cast_helper(helper) {
+        return super::cast_helpers::emit_cast_helper(helper, plan, alloc_index);
+    }
+
+    // Checked numeric helpers report failures through the numeric status global.
+    if is_numeric_helper(helper) {
+        return super::numeric_helpers::emit_numeric_helper(
+            helper,
+            plan,
+            numeric_status_global(plan)?,
+        );
+    }
+
+    if is_wasi_helper(helper) {
+        return super::wasi_helpers::emit_wasi_helper(helper, plan, alloc_index, release_index);
+    }
+
+    if is_float_format_helper(helper) {
+        return super::float_format_helpers::emit_float_format_helper(
+            helper,
+            plan,
+            alloc_index,
+            release_index,
+            numeric_status_global(plan)?,
+        );
     }
 
     // Local declarations: each helper declares only non-parameter locals.
     // Named constants below document the local index layout per helper.
     let mut function = match helper {
-        WasmRuntimeHelper::Alloc => {
-            // param 0: size  |  local 1: old_top (scratch)
-            Function::new(vec![(1, ValType::I32)])
-        }
         WasmRuntimeHelper::StringNewBuffer => {
             // no params  |  local 0: buffer_handle (scratch)
             Function::new(vec![(1, ValType::I32)])
@@ -61,40 +142,90 @@ pub(crate) fn emit_helper_function(
             Function::new(vec![(5, ValType::I32)])
         }
         WasmRuntimeHelper::StringFinish => {
-            // param 0: buffer  |  local 1: result_handle (scratch)
-            Function::new(vec![(1, ValType::I32)])
+            // param 0: buffer  |  local 1: result_handle (scratch), local 2: content
+            Function::new(vec![(2, ValType::I32)])
         }
         WasmRuntimeHelper::StringFromI64 => {
-            // param 0: value_i64 | local 1: buffer_handle
-            Function::new(vec![(1, ValType::I32)])
+            // param 0: value_i64 | local 1: result_handle, local 2: content, local 3: position
+            // local 4: magnitude
+            Function::new(vec![(3, ValType::I32), (1, ValType::I64)])
+        }
+        WasmRuntimeHelper::StringPtr | WasmRuntimeHelper::StringLen => Function::new(Vec::new()),
+        WasmRuntimeHelper::Alloc | WasmRuntimeHelper::Release | WasmRuntimeHelper::DropIfOwned => {
+            unreachable!("allocator helpers are dispatched early to alloc_helpers")
         }
-        WasmRuntimeHelper::StringPtr
-        | WasmRuntimeHelper::StringLen
-        | WasmRuntimeHelper::Release
-        | WasmRuntimeHelper::DropIfOwned => Function::new(Vec::new()),
         WasmRuntimeHelper::VecNew
         | WasmRuntimeHelper::VecPushHandle
         | WasmRuntimeHelper::VecLen
         | WasmRuntimeHelper::VecGet => {
             unreachable!("vec helpers are dispatched early to vec_helpers::emit_vec_helper")
         }
+        WasmRuntimeHelper::MapNew
+        | WasmRuntimeHelper::MapGet
+        | WasmRuntimeHelper::MapContains
+        | WasmRuntimeHelper::MapSet
+        | WasmRuntimeHelper::MapRemove
+        | WasmRuntimeHelper::MapClear
+        | WasmRuntimeHelper::MapLength
+        | WasmRuntimeHelper::MapHashKey
+        | WasmRuntimeHelper::MapKeysEqual
+        | WasmRuntimeHelper::MapFind
+        | WasmRuntimeHelper::MapRebuild
+        | WasmRuntimeHelper::FallibleNew => {
+            unreachable!("map helpers are dispatched early to map_helpers::emit_map_helper")
+        }
+        WasmRuntimeHelper::NumericCarrier
+        | WasmRuntimeHelper::IntAdd
+        | WasmRuntimeHelper::IntSub
+        | WasmRuntimeHelper::IntMul
+        | WasmRuntimeHelper::IntDiv
+        | WasmRuntimeHelper::IntMod
+        | WasmRuntimeHelper::IntPow
+        | WasmRuntimeHelper::IntNeg
+        | WasmRuntimeHelper::FloatAdd
+        | WasmRuntimeHelper::FloatSub
+        | WasmRuntimeHelper::FloatMul
+        | WasmRuntimeHelper::FloatDiv
+        | WasmRuntimeHelper::FloatMod
+        | WasmRuntimeHelper::FloatNeg
+        | WasmRuntimeHelper::FloatCheckFinite
+        | WasmRuntimeHelper::FloatValidate
+        | WasmRuntimeHelper::FloatFormat
+        | WasmRuntimeHelper::BignumSet
+        | WasmRuntimeHelper::BignumMulSmall
+        | WasmRuntimeHelper::BignumMulPow10
+        | WasmRuntimeHelper::BignumShl
+        | WasmRuntimeHelper::BignumCompare
+        | WasmRuntimeHelper::BignumAdd
+        | WasmRuntimeHelper::BignumSub => {
+            unreachable!("numeric helpers are dispatched early to their own emitters")
+        }
+        WasmRuntimeHelper::ErrorNew
+        | WasmRuntimeHelper::ErrorFromCode
+        | WasmRuntimeHelper::FallibleError => {
+            unreachable!("Error helpers are dispatched early to error_helpers::emit_error_helper")
+        }
+        WasmRuntimeHelper::CastCharToString
+        | WasmRuntimeHelper::CastFloatToInt
+        | WasmRuntimeHelper::CastIntToChar
+        | WasmRuntimeHelper::CastStringToInt
+        | WasmRuntimeHelper::CastStringToFloat
+        | WasmRuntimeHelper::CastStringToBool
+        | WasmRuntimeHelper::CastStringToChar
+        | WasmRuntimeHelper::StringWhitespaceLen => {
+            unreachable!("cast helpers are dispatched early to cast_helpers::emit_cast_helper")
+        }
+        WasmRuntimeHelper::WasiWriteAll
+        | WasmRuntimeHelper::WasiWriteLine
+        | WasmRuntimeHelper::WasiReadLine
+        | WasmRuntimeHelper::WasiInputUpdate
+        | WasmRuntimeHelper::WasiInputKeyMatches
+        | WasmRuntimeHelper::WasiClockMillis => {
+            unreachable!("WASI helpers are dispatched early to wasi_helpers::emit_wasi_helper")
+        }
     };
 
     match helper {
-        WasmRuntimeHelper::Alloc => {
-            // WHAT: return current heap_top and then advance by requested size.
-            // WHY: simple monotonic bump allocator for runtime objects.
-            const SIZE: u32 = 0;
-            const OLD_TOP: u32 = 1;
-
-            function.instruction(&Instruction::GlobalGet(heap_top_global));
-            function.instruction(&Instruction::LocalTee(OLD_TOP));
-            function.instruction(&Instruction::LocalGet(SIZE));
-            function.instruction(&Instruction::I32Add);
-            function.instruction(&Instruction::GlobalSet(heap_top_global));
-            function.instruction(&Instruction::LocalGet(OLD_TOP));
-            function.instruction(&Instruction::Return);
-        }
         WasmRuntimeHelper::StringNewBuffer => {
             // WHAT: allocate and initialize a 12-byte `{content_ptr, content_len, capacity}` buffer header.
             // WHY: the 3-field layout supports true append semantics for multi-fragment concatenation.
@@ -168,6 +299,11 @@ pub(crate) fn emit_helper_function(
                     dst_mem: 0,
                 });
 
+                // The buffer owns its content region alone, so the old one can be released.
+                function.instruction(&Instruction::LocalGet(BUFFER));
+                function.instruction(&Instruction::I32Load(memarg(0)));
+                function.instruction(&Instruction::Call(release_index));
+
                 // buffer.content_ptr = new_region
                 function.instruction(&Instruction::LocalGet(BUFFER));
                 function.instruction(&Instruction::LocalGet(NEW_REGION));
@@ -253,6 +389,11 @@ pub(crate) fn emit_helper_function(
                     dst_mem: 0,
                 });
 
+                // The buffer owns its content region alone, so the old one can be released.
+                function.instruction(&Instruction::LocalGet(BUFFER));
+                function.instruction(&Instruction::I32Load(memarg(0)));
+                function.instruction(&Instruction::Call(release_index));
+
                 function.instruction(&Instruction::LocalGet(BUFFER));
                 function.instruction(&Instruction::LocalGet(NEW_REGION));
                 function.instruction(&Instruction::I32Store(memarg(0)));
@@ -292,6 +433,7 @@ pub(crate) fn emit_helper_function(
             //   offset 4: len (i32) — byte length
             const BUFFER: u32 = 0;
             const RESULT_HANDLE: u32 = 1;
+            const CONTENT: u32 = 2;
 
             // result_handle = rt_alloc(8)
             function.instruction(&Instruction::I32Const(8));
@@ -310,6 +452,15 @@ pub(crate) fn emit_helper_function(
             function.instruction(&Instruction::I32Load(memarg(4)));
             function.instruction(&Instruction::I32Store(memarg(4)));
 
+            // The string takes over the content region and the consumed buffer header is
+            // released; lowering never touches a buffer after finishing it.
+            function.instruction(&Instruction::LocalGet(BUFFER));
+            function.instruction(&Instruction::I32Load(memarg(0)));
+            function.instruction(&Instruction::LocalSet(CONTENT));
+            super::alloc_helpers::emit_link_owned_block(&mut function, RESULT_HANDLE, CONTENT);
+            function.instruction(&Instruction::LocalGet(BUFFER));
+            function.instruction(&Instruction::Call(release_index));
+
             function.instruction(&Instruction::LocalGet(RESULT_HANDLE));
             function.instruction(&Instruction::Return);
         }
@@ -328,49 +479,92 @@ pub(crate) fn emit_helper_function(
             function.instruction(&Instruction::Return);
         }
         WasmRuntimeHelper::StringFromI64 => {
-            // WHAT: materialize a string handle from an i64 interpolation chunk.
-            // WHY: frontend template coercion currently models numeric->string as `"" + value`.
-            // A dedicated helper keeps lowering deterministic while scalar formatting support is
-            // incrementally implemented.
-            let string_new_buffer_index = plan
-                .helper_indices
-                .get(&WasmRuntimeHelper::StringNewBuffer)
-                .copied()
-                .ok_or_else(|| {
-                    CompilerError::compiler_error(
-                        "Wasm emission missing rt_string_new_buffer helper index",
-                    )
-                    .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
-                })?;
-            let string_finish_index = plan
-                .helper_indices
-                .get(&WasmRuntimeHelper::StringFinish)
-                .copied()
-                .ok_or_else(|| {
-                    CompilerError::compiler_error(
-                        "Wasm emission missing rt_string_finish helper index",
-                    )
-                    .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
-                })?;
-
+            // WHAT: format a signed decimal Int into a finalized string handle.
+            // WHY: template coercion renders Ints as `"" + value`, matching JS `String(value)`.
+            // Digits are written backwards into a fixed 20-byte region (enough for i64::MIN),
+            // and the handle points at the written suffix.
             const VALUE_I64: u32 = 0;
-            const BUFFER_HANDLE: u32 = 1;
-
-            // Keep the input value consumed so helper semantics remain explicit.
-            function.instruction(&Instruction::LocalGet(VALUE_I64));
-            function.instruction(&Instruction::Drop);
-
-            // Temporary phase behavior: emit an empty finalized string handle.
-            function.instruction(&Instruction::Call(string_new_buffer_index));
-            function.instruction(&Instruction::LocalSet(BUFFER_HANDLE));
-            function.instruction(&Instruction::LocalGet(BUFFER_HANDLE));
-            function.instruction(&Instruction::Call(string_finish_index));
-            function.instruction(&Instruction::Return);
+            const RESULT_HANDLE: u32 = 1;
+            const CONTENT: u32 = 2;
+            const POSITION: u32 = 3;
+            const MAGNITUDE: u32 = 4;
+            const MAX_DIGITS: i32 = 20;
+
+            super::map_helpers::emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(8 + MAX_DIGITS),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalTee(RESULT_HANDLE),
+                    Instruction::I32Const(8),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(CONTENT),
+                    Instruction::I32Const(MAX_DIGITS),
+                    Instruction::LocalSet(POSITION),
+                    // Unsigned magnitude; wrapping negation keeps i64::MIN correct.
+                    Instruction::LocalGet(VALUE_I64),
+                    Instruction::I64Const(0),
+                    Instruction::I64LtS,
+                    Instruction::If(BlockType::Result(ValType::I64)),
+                    Instruction::I64Const(0),
+                    Instruction::LocalGet(VALUE_I64),
+                    Instruction::I64Sub,
+                    Instruction::Else,
+                    Instruction::LocalGet(VALUE_I64),
+                    Instruction::End,
+                    Instruction::LocalSet(MAGNITUDE),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(POSITION),
+                    Instruction::LocalGet(CONTENT),
+                    Instruction::I32Add,
+                    Instruction::LocalGet(MAGNITUDE),
+                    Instruction::I64Const(10),
+                    Instruction::I64RemU,
+                    Instruction::I32WrapI64,
+                    Instruction::I32Const(i32::from(b'0')),
+                    Instruction::I32Add,
+                    Instruction::I32Store8(super::map_helpers::memarg_byte()),
+                    Instruction::LocalGet(MAGNITUDE),
+                    Instruction::I64Const(10),
+                    Instruction::I64DivU,
+                    Instruction::LocalTee(MAGNITUDE),
+                    Instruction::I64Const(0),
+                    Instruction::I64Ne,
+                    Instruction::BrIf(0),
+                    Instruction::End,
+                    Instruction::LocalGet(VALUE_I64),
+                    Instruction::I64Const(0),
+                    Instruction::I64LtS,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(POSITION),
+                    Instruction::LocalGet(CONTENT),
+                    Instruction::I32Add,
+                    Instruction::I32Const(i32::from(b'-')),
+                    Instruction::I32Store8(super::map_helpers::memarg_byte()),
+                    Instruction::End,
+                    Instruction::LocalGet(RESULT_HANDLE),
+                    Instruction::LocalGet(CONTENT),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Add,
+                    Instruction::I32Store(memarg(0)),
+                    Instruction::LocalGet(RESULT_HANDLE),
+                    Instruction::I32Const(MAX_DIGITS),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Sub,
+                    Instruction::I32Store(memarg(4)),
+                    Instruction::LocalGet(RESULT_HANDLE),
+                    Instruction::Return,
+                ],
+            );
         }
-        WasmRuntimeHelper::Release | WasmRuntimeHelper::DropIfOwned => {
-            // WHAT: release/drop helpers are conservative no-ops for both string and vec handles.
-            // WHY: ownership-eliding/free semantics are introduced incrementally after baseline correctness.
-            function.instruction(&Instruction::Return);
+        WasmRuntimeHelper::Alloc | WasmRuntimeHelper::Release | WasmRuntimeHelper::DropIfOwned => {
+            unreachable!("allocator helpers are dispatched early to alloc_helpers")
         }
         WasmRuntimeHelper::VecNew
         | WasmRuntimeHelper::VecPushHandle
@@ -378,12 +572,146 @@ pub(crate) fn emit_helper_function(
         | WasmRuntimeHelper::VecGet => {
             unreachable!("vec helpers are dispatched early to vec_helpers::emit_vec_helper")
         }
+        WasmRuntimeHelper::MapNew
+        | WasmRuntimeHelper::MapGet
+        | WasmRuntimeHelper::MapContains
+        | WasmRuntimeHelper::MapSet
+        | WasmRuntimeHelper::MapRemove
+        | WasmRuntimeHelper::MapClear
+        | WasmRuntimeHelper::MapLength
+        | WasmRuntimeHelper::MapHashKey
+        | WasmRuntimeHelper::MapKeysEqual
+        | WasmRuntimeHelper::MapFind
+        | WasmRuntimeHelper::MapRebuild
+        | WasmRuntimeHelper::FallibleNew => {
+            unreachable!("map helpers are dispatched early to map_helpers::emit_map_helper")
+        }
+        WasmRuntimeHelper::NumericCarrier
+        | WasmRuntimeHelper::IntAdd
+        | WasmRuntimeHelper::IntSub
+        | WasmRuntimeHelper::IntMul
+        | WasmRuntimeHelper::IntDiv
+        | WasmRuntimeHelper::IntMod
+        | WasmRuntimeHelper::IntPow
+        | WasmRuntimeHelper::IntNeg
+        | WasmRuntimeHelper::FloatAdd
+        | WasmRuntimeHelper::FloatSub
+        | WasmRuntimeHelper::FloatMul
+        | WasmRuntimeHelper::FloatDiv
+        | WasmRuntimeHelper::FloatMod
+        | WasmRuntimeHelper::FloatNeg
+        | WasmRuntimeHelper::FloatCheckFinite
+        | WasmRuntimeHelper::FloatValidate
+        | WasmRuntimeHelper::FloatFormat
+        | WasmRuntimeHelper::BignumSet
+        | WasmRuntimeHelper::BignumMulSmall
+        | WasmRuntimeHelper::BignumMulPow10
+        | WasmRuntimeHelper::BignumShl
+        | WasmRuntimeHelper::BignumCompare
+        | WasmRuntimeHelper::BignumAdd
+        | WasmRuntimeHelper::BignumSub => {
+            unreachable!("numeric helpers are dispatched early to their own emitters")
+        }
+        WasmRuntimeHelper::ErrorNew
+        | WasmRuntimeHelper::ErrorFromCode
+        | WasmRuntimeHelper::FallibleError => {
+            unreachable!("Error helpers are dispatched early to error_helpers::emit_error_helper")
+        }
+        WasmRuntimeHelper::CastCharToString
+        | WasmRuntimeHelper::CastFloatToInt
+        | WasmRuntimeHelper::CastIntToChar
+        | WasmRuntimeHelper::CastStringToInt
+        | WasmRuntimeHelper::CastStringToFloat
+        | WasmRuntimeHelper::CastStringToBool
+        | WasmRuntimeHelper::CastStringToChar
+        | WasmRuntimeHelper::StringWhitespaceLen => {
+            unreachable!("cast helpers are dispatched early to cast_helpers::emit_cast_helper")
+        }
+        WasmRuntimeHelper::WasiWriteAll
+        | WasmRuntimeHelper::WasiWriteLine
+        | WasmRuntimeHelper::WasiReadLine
+        | WasmRuntimeHelper::WasiInputUpdate
+        | WasmRuntimeHelper::WasiInputKeyMatches
+        | WasmRuntimeHelper::WasiClockMillis => {
+            unreachable!("WASI helpers are dispatched early to wasi_helpers::emit_wasi_helper")
+        }
     }
 
     function.instruction(&Instruction::End);
     Ok(function)
 }
 
+fn is_numeric_helper(helper: WasmRuntimeHelper) -> bool {
+    matches!(
+        helper,
+        WasmRuntimeHelper::NumericCarrier
+            | WasmRuntimeHelper::IntAdd
+            | WasmRuntimeHelper::IntSub
+            | WasmRuntimeHelper::IntMul
+            | WasmRuntimeHelper::IntDiv
+            | WasmRuntimeHelper::IntMod
+            | WasmRuntimeHelper::IntPow
+            | WasmRuntimeHelper::IntNeg
+            | WasmRuntimeHelper::FloatAdd
+            | WasmRuntimeHelper::FloatSub
+            | WasmRuntimeHelper::FloatMul
+            | WasmRuntimeHelper::FloatDiv
+            | WasmRuntimeHelper::FloatMod
+            | WasmRuntimeHelper::FloatNeg
+            | WasmRuntimeHelper::FloatCheckFinite
+            | WasmRuntimeHelper::FloatValidate
+    )
+}
+
+fn is_float_format_helper(helper: WasmRuntimeHelper) -> bool {
+    matches!(
+        helper,
+        WasmRuntimeHelper::FloatFormat
+            | WasmRuntimeHelper::BignumSet
+            | WasmRuntimeHelper::BignumMulSmall
+            | WasmRuntimeHelper::BignumMulPow10
+            | WasmRuntimeHelper::BignumShl
+            | WasmRuntimeHelper::BignumCompare
+            | WasmRuntimeHelper::BignumAdd
+            | WasmRuntimeHelper::BignumSub
+    )
+}
+
+fn is_cast_helper(helper: WasmRuntimeHelper) -> bool {
+    matches!(
+        helper,
+        WasmRuntimeHelper::CastCharToString
+            | WasmRuntimeHelper::CastFloatToInt
+            | WasmRuntimeHelper::CastIntToChar
+            | WasmRuntimeHelper::CastStringToInt
+            | WasmRuntimeHelper::CastStringToFloat
+            | WasmRuntimeHelper::CastStringToBool
+            | WasmRuntimeHelper::CastStringToChar
+            | WasmRuntimeHelper::StringWhitespaceLen
+    )
+}
+
+fn is_wasi_helper(helper: WasmRuntimeHelper) -> bool {
+    matches!(
+        helper,
+        WasmRuntimeHelper::WasiWriteAll
+            | WasmRuntimeHelper::WasiWriteLine
+            | WasmRuntimeHelper::WasiReadLine
+            | WasmRuntimeHelper::WasiInputUpdate
+            | WasmRuntimeHelper::WasiInputKeyMatches
+            | WasmRuntimeHelper::WasiClockMillis
+    )
+}
+
+fn numeric_status_global(plan: &WasmEmitPlan) -> Result<u32, CompilerError> {
+    plan.numeric_status_global_index.ok_or_else(|| {
+        CompilerError::compiler_error(
+            "Wasm emission expected numeric_status global while synthesizing numeric helpers",
+        )
+        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+    })
+}
+
 fn memarg(offset: u64) -> MemArg {
     // WHAT: all helper runtime memory accesses target memory index 0 with 4-byte alignment.
     // WHY: uses one internal 32-bit linear memory and i32 load/store fields.
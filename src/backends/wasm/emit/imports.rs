@@ -1,15 +1,16 @@
 //! Import section emission.
 
 use crate::backends::error_types::BackendErrorType;
-use crate::backends::wasm::emit::sections::WasmEmitPlan;
 use crate::backends::wasm::lir::linkage::WasmImportKind;
 use crate::backends::wasm::lir::module::WasmLirModule;
+use crate::backends::wasm::lir::types::WasmLirSignature;
 use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use rustc_hash::FxHashMap;
 use wasm_encoder::{EntityType, ImportSection};
 
 pub(crate) fn build_import_section(
     module: &WasmLirModule,
-    plan: &WasmEmitPlan,
+    type_index_by_signature: &FxHashMap<WasmLirSignature, u32>,
 ) -> Result<ImportSection, CompilerError> {
     let mut section = ImportSection::new();
     let mut imports = module.imports.iter().collect::<Vec<_>>();
@@ -20,17 +21,17 @@ pub(crate) fn build_import_section(
     for import in imports {
         match &import.kind {
             WasmImportKind::Function(signature) => {
-                let type_index = plan
-                    .type_index_by_signature
-                    .get(signature)
-                    .copied()
-                    .ok_or_else(|| {
-                        CompilerError::compiler_error(format!(
-                            "Wasm emission could not resolve type index for import {:?}",
-                            import.id
-                        ))
-                        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
-                    })?;
+                let type_index =
+                    type_index_by_signature
+                        .get(signature)
+                        .copied()
+                        .ok_or_else(|| {
+                            CompilerError::compiler_error(format!(
+                                "Wasm emission could not resolve type index for import {:?}",
+                                import.id
+                            ))
+                            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+                        })?;
                 section.import(
                     import.module_name.as_str(),
                     import.item_name.as_str(),
@@ -1,22 +1,37 @@
 //! Instruction lowering from Wasm LIR to wasm-encoder instructions.
 
 use crate::backends::error_types::BackendErrorType;
-use crate::backends::wasm::emit::sections::WasmEmitPlan;
+use crate::backends::wasm::emit::map_helpers::memarg_i32;
+use crate::backends::wasm::emit::sections::{WasmEmitPlan, runtime_string_handle};
 use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
 use crate::backends::wasm::lir::types::{
     WasmAbiType, WasmLirBlockId, WasmLirFunctionId, WasmLirLocalId,
 };
+use crate::backends::wasm::runtime::casts::cast_helper;
+use crate::backends::wasm::runtime::errors::{
+    ERROR_CODE_OFFSET, ERROR_MESSAGE_OFFSET, WasmErrorField,
+};
+use crate::backends::wasm::runtime::maps::{
+    FALLIBLE_PAYLOAD_OFFSET, FALLIBLE_TAG_ERROR, FALLIBLE_TAG_OFFSET, FALLIBLE_TAG_SUCCESS,
+};
+use crate::backends::wasm::runtime::numeric::{WasmNumericFailureMode, WasmNumericOp};
 use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::backends::wasm::runtime::wasi::{
+    INPUT_CLOSED_OFFSET, INPUT_STATE_SIZE, WASI_STDOUT, WasiAdapter, WasiClock,
+};
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
 use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
 use rustc_hash::FxHashMap;
-use wasm_encoder::{BlockType, Function, Instruction, ValType};
+use wasm_encoder::{BlockType, Function, Instruction, MemArg, ValType};
 
 pub(crate) struct LirBodyEmitContext<'a> {
     pub function_id: WasmLirFunctionId,
     pub local_index_by_id: &'a FxHashMap<WasmLirLocalId, u32>,
     pub local_type_by_id: &'a FxHashMap<WasmLirLocalId, WasmAbiType>,
     pub block_index_by_id: &'a FxHashMap<WasmLirBlockId, u32>,
-    pub dispatch_local_index: u32,
+    /// Program-counter local of dispatcher-loop bodies. `None` in structured bodies.
+    pub dispatch_local_index: Option<u32>,
 }
 
 pub(crate) fn emit_statement(
@@ -28,22 +43,6 @@ pub(crate) fn emit_statement(
     // WHAT: lower each LIR statement into explicit Wasm stack-machine instructions.
     // WHY: statement lowering is the only place that maps semantic LIR ops to concrete opcodes.
     match statement {
-        WasmLirStmt::ConstI32 { dst, value } => {
-            function.instruction(&Instruction::I32Const(*value));
-            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
-        }
-        WasmLirStmt::ConstI64 { dst, value } => {
-            function.instruction(&Instruction::I64Const(*value));
-            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
-        }
-        WasmLirStmt::ConstF32 { dst, value } => {
-            function.instruction(&Instruction::F32Const((*value).into()));
-            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
-        }
-        WasmLirStmt::ConstF64 { dst, value } => {
-            function.instruction(&Instruction::F64Const((*value).into()));
-            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
-        }
         WasmLirStmt::ConstStaticPtr { dst, data } => {
             let offset = plan.data_offsets.get(data).copied().ok_or_else(|| {
                 CompilerError::compiler_error(format!(
@@ -66,47 +65,27 @@ pub(crate) fn emit_statement(
             function.instruction(&Instruction::I32Const(*value as i32));
             function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
         }
-        WasmLirStmt::Copy { dst, src } | WasmLirStmt::Move { dst, src } => {
-            // WHAT: the current emitter models copy/move as local assignment in emitted Wasm.
-            // WHY: ownership specialization remains in runtime/helper behavior for now.
-            function.instruction(&Instruction::LocalGet(local_index(*src, context)?));
-            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
-        }
         WasmLirStmt::Call { dst, callee, args } => {
             for arg in args {
                 function.instruction(&Instruction::LocalGet(local_index(*arg, context)?));
             }
-
-            let callee_index = match callee {
-                WasmCalleeRef::Function(function_id) => plan
-                    .function_indices
-                    .get(function_id)
-                    .copied()
-                    .ok_or_else(|| {
-                        CompilerError::compiler_error(format!(
-                            "Wasm emission missing callee function index for {:?} in {:?}",
-                            function_id, context.function_id
-                        ))
-                        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
-                    })?,
-                WasmCalleeRef::Import(import_id) => plan
-                    .import_function_indices
-                    .get(import_id)
-                    .copied()
-                    .ok_or_else(|| {
-                        CompilerError::compiler_error(format!(
-                            "Wasm emission missing import function index for {:?} in {:?}",
-                            import_id, context.function_id
-                        ))
-                        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
-                    })?,
-            };
-            function.instruction(&Instruction::Call(callee_index));
+            function.instruction(&Instruction::Call(callee_index(*callee, context, plan)?));
 
             if let Some(dst) = dst {
                 function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
             }
         }
+        WasmLirStmt::CallMultiValue { dsts, callee, args } => {
+            for arg in args {
+                function.instruction(&Instruction::LocalGet(local_index(*arg, context)?));
+            }
+            function.instruction(&Instruction::Call(callee_index(*callee, context, plan)?));
+
+            // Results sit on the stack in order, so the last one is popped first.
+            for dst in dsts.iter().rev() {
+                function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+            }
+        }
         WasmLirStmt::StringNewBuffer { dst } => {
             function.instruction(&Instruction::Call(helper_index(
                 plan,
@@ -176,17 +155,344 @@ pub(crate) fn emit_statement(
                 WasmRuntimeHelper::VecPushHandle,
             )?));
         }
-        WasmLirStmt::DropIfOwned { value } => {
+        WasmLirStmt::MapNew { dst, key_kind } => {
+            function.instruction(&Instruction::I32Const(key_kind.as_i32()));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::MapNew,
+            )?));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::MapGet { dst, map, key } => {
+            function.instruction(&Instruction::LocalGet(local_index(*map, context)?));
+            emit_widened_map_slot(function, *key, context)?;
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::MapGet,
+            )?));
+            emit_optional_carrier_result(function, *dst, plan, context)?;
+        }
+        WasmLirStmt::MapContains { dst, map, key } => {
+            function.instruction(&Instruction::LocalGet(local_index(*map, context)?));
+            emit_widened_map_slot(function, *key, context)?;
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::MapContains,
+            )?));
+            emit_optional_result(function, *dst, context)?;
+        }
+        WasmLirStmt::MapSet {
+            dst,
+            map,
+            key,
+            value,
+        } => {
+            function.instruction(&Instruction::LocalGet(local_index(*map, context)?));
+            emit_widened_map_slot(function, *key, context)?;
+            emit_widened_map_slot(function, *value, context)?;
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::MapSet,
+            )?));
+            emit_optional_carrier_result(function, *dst, plan, context)?;
+        }
+        WasmLirStmt::MapRemove { dst, map, key } => {
+            function.instruction(&Instruction::LocalGet(local_index(*map, context)?));
+            emit_widened_map_slot(function, *key, context)?;
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::MapRemove,
+            )?));
+            emit_optional_carrier_result(function, *dst, plan, context)?;
+        }
+        WasmLirStmt::MapClear { map } => {
+            function.instruction(&Instruction::LocalGet(local_index(*map, context)?));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::MapClear,
+            )?));
+        }
+        WasmLirStmt::MapLength { dst, map } => {
+            function.instruction(&Instruction::LocalGet(local_index(*map, context)?));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::MapLength,
+            )?));
+            emit_optional_result(function, *dst, context)?;
+        }
+        WasmLirStmt::FallibleIsSuccess { dst, carrier } => {
+            function.instruction(&Instruction::LocalGet(local_index(*carrier, context)?));
+            function.instruction(&Instruction::I32Load(carrier_memarg(
+                FALLIBLE_TAG_OFFSET,
+                2,
+            )));
+            function.instruction(&Instruction::I32Const(FALLIBLE_TAG_SUCCESS));
+            function.instruction(&Instruction::I32Eq);
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::FallibleUnwrapSuccess { dst, carrier } => {
+            function.instruction(&Instruction::LocalGet(local_index(*carrier, context)?));
+            function.instruction(&Instruction::I64Load(carrier_memarg(
+                FALLIBLE_PAYLOAD_OFFSET,
+                3,
+            )));
+            emit_narrowed_map_slot(function, *dst, context)?;
+        }
+        WasmLirStmt::FallibleUnwrapError { dst, carrier } => {
+            function.instruction(&Instruction::LocalGet(local_index(*carrier, context)?));
+            function.instruction(&Instruction::I64Load(carrier_memarg(
+                FALLIBLE_PAYLOAD_OFFSET,
+                3,
+            )));
+            function.instruction(&Instruction::I32WrapI64);
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::FallibleWrapSuccess { dst, value } => {
+            function.instruction(&Instruction::I32Const(FALLIBLE_TAG_SUCCESS));
+            emit_widened_map_slot(function, *value, context)?;
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::FallibleNew,
+            )?));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::FallibleWrapError { dst, error } => {
+            function.instruction(&Instruction::I32Const(FALLIBLE_TAG_ERROR));
+            function.instruction(&Instruction::LocalGet(local_index(*error, context)?));
+            function.instruction(&Instruction::I64ExtendI32U);
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::FallibleNew,
+            )?));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::ErrorField { dst, error, field } => {
+            function.instruction(&Instruction::LocalGet(local_index(*error, context)?));
+            match field {
+                WasmErrorField::Message => {
+                    function.instruction(&Instruction::I32Load(carrier_memarg(
+                        ERROR_MESSAGE_OFFSET,
+                        2,
+                    )));
+                }
+                WasmErrorField::Code => {
+                    function
+                        .instruction(&Instruction::I32Load(carrier_memarg(ERROR_CODE_OFFSET, 2)));
+                    function.instruction(&Instruction::I64ExtendI32S);
+                }
+            }
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::RecordNew { dst, record_type } => {
+            let size = plan.record_sizes.get(record_type).copied().ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "Wasm emission missing size for record type {:?} in {:?}",
+                    record_type, context.function_id
+                ))
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+            })?;
+            function.instruction(&Instruction::I32Const(size as i32));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::Alloc,
+            )?));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::RecordStore {
+            record,
+            offset,
+            value,
+            ..
+        } => {
+            function.instruction(&Instruction::LocalGet(local_index(*record, context)?));
+            function.instruction(&Instruction::LocalGet(local_index(*value, context)?));
+            let offset = u64::from(*offset);
+            match local_type(*value, context, "record store value")? {
+                WasmAbiType::I32 | WasmAbiType::Handle => {
+                    function.instruction(&Instruction::I32Store(carrier_memarg(offset, 2)))
+                }
+                WasmAbiType::I64 => {
+                    function.instruction(&Instruction::I64Store(carrier_memarg(offset, 3)))
+                }
+                WasmAbiType::F32 => {
+                    function.instruction(&Instruction::F32Store(carrier_memarg(offset, 2)))
+                }
+                WasmAbiType::F64 => {
+                    function.instruction(&Instruction::F64Store(carrier_memarg(offset, 3)))
+                }
+                abi @ (WasmAbiType::Void | WasmAbiType::ExternRef) => {
+                    return Err(CompilerError::compiler_error(format!(
+                        "Wasm emission cannot store a {abi:?} value into a linear-memory record in function {:?}",
+                        context.function_id
+                    ))
+                    .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+                }
+            };
+        }
+        WasmLirStmt::RecordLoad {
+            dst,
+            record,
+            offset,
+            ..
+        } => {
+            function.instruction(&Instruction::LocalGet(local_index(*record, context)?));
+            let offset = u64::from(*offset);
+            match local_type(*dst, context, "record load destination")? {
+                WasmAbiType::I32 | WasmAbiType::Handle => {
+                    function.instruction(&Instruction::I32Load(carrier_memarg(offset, 2)))
+                }
+                WasmAbiType::I64 => {
+                    function.instruction(&Instruction::I64Load(carrier_memarg(offset, 3)))
+                }
+                WasmAbiType::F32 => {
+                    function.instruction(&Instruction::F32Load(carrier_memarg(offset, 2)))
+                }
+                WasmAbiType::F64 => {
+                    function.instruction(&Instruction::F64Load(carrier_memarg(offset, 3)))
+                }
+                abi @ (WasmAbiType::Void | WasmAbiType::ExternRef) => {
+                    return Err(CompilerError::compiler_error(format!(
+                        "Wasm emission cannot load a {abi:?} value from a linear-memory record in function {:?}",
+                        context.function_id
+                    ))
+                    .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+                }
+            };
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::Cast { dst, policy, value } => {
+            emit_cast(function, *dst, *policy, *value, context, plan)?;
+        }
+        WasmLirStmt::CheckedNumeric {
+            dst,
+            op,
+            lhs,
+            rhs,
+            failure_mode,
+        } => {
+            if op.is_unary() != rhs.is_none() {
+                return Err(CompilerError::compiler_error(format!(
+                    "Wasm emission received CheckedNumeric {op:?} with mismatched operand count"
+                ))
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+            }
+            function.instruction(&Instruction::LocalGet(local_index(*lhs, context)?));
+            if let Some(rhs) = rhs {
+                function.instruction(&Instruction::LocalGet(local_index(*rhs, context)?));
+            }
+            function.instruction(&Instruction::Call(helper_index(plan, op.helper())?));
+            let value_type = match op {
+                WasmNumericOp::IntAdd
+                | WasmNumericOp::IntSub
+                | WasmNumericOp::IntMul
+                | WasmNumericOp::IntDiv
+                | WasmNumericOp::IntMod
+                | WasmNumericOp::IntPow
+                | WasmNumericOp::IntNeg => WasmAbiType::I64,
+                _ => WasmAbiType::F64,
+            };
+            emit_numeric_result(function, *dst, value_type, *failure_mode, context, plan)?;
+        }
+        WasmLirStmt::FormatFloat {
+            dst,
+            value,
+            failure_mode,
+        } => {
             function.instruction(&Instruction::LocalGet(local_index(*value, context)?));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::FloatFormat,
+            )?));
+            emit_numeric_result(
+                function,
+                *dst,
+                WasmAbiType::Handle,
+                *failure_mode,
+                context,
+                plan,
+            )?;
+        }
+        WasmLirStmt::WasiCall { dst, adapter, args } => {
+            emit_wasi_call(function, *dst, *adapter, args, context, plan)?;
+        }
+        WasmLirStmt::DropIfOwned { value } => {
+            // WHAT: release the handle, then clear the local.
+            // WHY: several drop sites can cover one path (an explicit drop and a block exit), and
+            // the released address must not reach `rt_release` twice.
+            let value_index = local_index(*value, context)?;
+            function.instruction(&Instruction::LocalGet(value_index));
             function.instruction(&Instruction::Call(helper_index(
                 plan,
                 WasmRuntimeHelper::DropIfOwned,
             )?));
+            function.instruction(&Instruction::I32Const(0));
+            function.instruction(&Instruction::LocalSet(value_index));
         }
         WasmLirStmt::RetainHandle { .. } => {
-            // WHAT: retain is currently a no-op at codegen level.
-            // WHY: GC-first semantics and conservative helper runtime make extra retain
-            // unnecessary in the current emitter.
+            // WHAT: retain is a no-op at codegen level.
+            // WHY: lowering treats a retained local as escaping and removes its drops instead.
+        }
+        WasmLirStmt::ConstI32 { .. }
+        | WasmLirStmt::ConstI64 { .. }
+        | WasmLirStmt::ConstF32 { .. }
+        | WasmLirStmt::ConstF64 { .. }
+        | WasmLirStmt::Copy { .. }
+        | WasmLirStmt::Move { .. }
+        | WasmLirStmt::IntEq { .. }
+        | WasmLirStmt::IntNe { .. }
+        | WasmLirStmt::IntAdd { .. }
+        | WasmLirStmt::IntSub { .. }
+        | WasmLirStmt::IntMod { .. }
+        | WasmLirStmt::IntMul { .. }
+        | WasmLirStmt::IntFloorDiv { .. }
+        | WasmLirStmt::IntToFloatDiv { .. }
+        | WasmLirStmt::FloatAdd { .. }
+        | WasmLirStmt::FloatSub { .. }
+        | WasmLirStmt::FloatMul { .. }
+        | WasmLirStmt::FloatDiv { .. }
+        | WasmLirStmt::FloatMod { .. }
+        | WasmLirStmt::BoolAnd { .. }
+        | WasmLirStmt::BoolOr { .. }
+        | WasmLirStmt::OrderedLt { .. }
+        | WasmLirStmt::OrderedLe { .. }
+        | WasmLirStmt::OrderedGt { .. }
+        | WasmLirStmt::OrderedGe { .. } => emit_scalar_statement(function, statement, context)?,
+    }
+
+    Ok(())
+}
+
+/// Emits constants, local copies and scalar arithmetic.
+///
+/// WHY: these statements lower identically in the linear-memory and Wasm GC emitters; only
+/// handle-backed statements depend on the memory model.
+pub(crate) fn emit_scalar_statement(
+    function: &mut Function,
+    statement: &WasmLirStmt,
+    context: &LirBodyEmitContext<'_>,
+) -> Result<(), CompilerError> {
+    match statement {
+        WasmLirStmt::ConstI32 { dst, value } => {
+            function.instruction(&Instruction::I32Const(*value));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::ConstI64 { dst, value } => {
+            function.instruction(&Instruction::I64Const(*value));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::ConstF32 { dst, value } => {
+            function.instruction(&Instruction::F32Const((*value).into()));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::ConstF64 { dst, value } => {
+            function.instruction(&Instruction::F64Const((*value).into()));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
+        }
+        WasmLirStmt::Copy { dst, src } | WasmLirStmt::Move { dst, src } => {
+            // WHAT: the current emitter models copy/move as local assignment in emitted Wasm.
+            // WHY: ownership specialization remains in runtime/helper behavior for now.
+            function.instruction(&Instruction::LocalGet(local_index(*src, context)?));
+            function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
         }
         WasmLirStmt::IntEq { dst, lhs, rhs } => {
             emit_compare(function, *lhs, *rhs, context, true)?;
@@ -336,6 +642,13 @@ pub(crate) fn emit_statement(
             emit_ordered_compare(function, *lhs, *rhs, context, OrderedCompareKind::Ge)?;
             function.instruction(&Instruction::LocalSet(local_index(*dst, context)?));
         }
+        other => {
+            return Err(CompilerError::compiler_error(format!(
+                "Wasm emission routed non-scalar statement {other:?} to scalar lowering in {:?}",
+                context.function_id
+            ))
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
     }
 
     Ok(())
@@ -372,6 +685,12 @@ pub(crate) fn emit_terminator(
             }
             function.instruction(&Instruction::Return);
         }
+        WasmLirTerminator::ReturnMultiValue { values } => {
+            for value in values {
+                function.instruction(&Instruction::LocalGet(local_index(*value, context)?));
+            }
+            function.instruction(&Instruction::Return);
+        }
         WasmLirTerminator::Trap => {
             function.instruction(&Instruction::Unreachable);
         }
@@ -385,6 +704,14 @@ fn set_dispatch_target(
     target: WasmLirBlockId,
     context: &LirBodyEmitContext<'_>,
 ) -> Result<(), CompilerError> {
+    let Some(dispatch_local_index) = context.dispatch_local_index else {
+        return Err(CompilerError::compiler_error(format!(
+            "Wasm emission reached a dispatcher jump to {:?} in structured function {:?}",
+            target, context.function_id
+        ))
+        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+    };
+
     // WHAT: convert target block id into dispatcher-table index.
     // WHY: branch/jump terminators communicate control flow via dispatch-local updates.
     let target_index = context
@@ -400,7 +727,7 @@ fn set_dispatch_target(
         })?;
 
     function.instruction(&Instruction::I32Const(target_index as i32));
-    function.instruction(&Instruction::LocalSet(context.dispatch_local_index));
+    function.instruction(&Instruction::LocalSet(dispatch_local_index));
     Ok(())
 }
 
@@ -466,9 +793,9 @@ fn emit_compare(
                 &Instruction::F64Ne
             });
         }
-        WasmAbiType::Void => {
+        WasmAbiType::Void | WasmAbiType::ExternRef => {
             return Err(CompilerError::compiler_error(format!(
-                "Wasm emission cannot compare Void-typed locals in function {:?}",
+                "Wasm emission cannot compare {lhs_type:?}-typed locals in function {:?}",
                 context.function_id
             ))
             .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
@@ -591,7 +918,7 @@ fn emit_ordered_compare(
                 OrderedCompareKind::Ge => &Instruction::F64Ge,
             });
         }
-        WasmAbiType::Handle | WasmAbiType::Void => {
+        WasmAbiType::Handle | WasmAbiType::ExternRef | WasmAbiType::Void => {
             return Err(CompilerError::compiler_error(format!(
                 "Wasm emission cannot lower ordered comparison for ABI type {:?} in function {:?}",
                 lhs_type, context.function_id
@@ -753,7 +1080,443 @@ fn emit_numeric_div(
     Ok(())
 }
 
-fn local_type(
+fn emit_cast(
+    function: &mut Function,
+    dst: WasmLirLocalId,
+    policy: BuiltinCastPolicyId,
+    value: WasmLirLocalId,
+    context: &LirBodyEmitContext<'_>,
+    plan: &WasmEmitPlan,
+) -> Result<(), CompilerError> {
+    // WHAT: apply one builtin cast policy, inline where a single opcode suffices.
+    // WHY: helper-backed casts leave their result (a value or a fallible carrier) on the stack,
+    // so every arm ends with the same store into `dst`.
+    let value_index = local_index(value, context)?;
+    match policy {
+        BuiltinCastPolicyId::IntToFloat => {
+            function.instruction(&Instruction::LocalGet(value_index));
+            function.instruction(&Instruction::F64ConvertI64S);
+        }
+        BuiltinCastPolicyId::CharToInt => {
+            function.instruction(&Instruction::LocalGet(value_index));
+            function.instruction(&Instruction::I64ExtendI32U);
+        }
+        BuiltinCastPolicyId::BoolToString => {
+            function.instruction(&Instruction::I32Const(
+                runtime_string_handle(plan, "true")? as i32
+            ));
+            function.instruction(&Instruction::I32Const(
+                runtime_string_handle(plan, "false")? as i32,
+            ));
+            function.instruction(&Instruction::LocalGet(value_index));
+            function.instruction(&Instruction::Select);
+        }
+        BuiltinCastPolicyId::ErrorToString => {
+            function.instruction(&Instruction::LocalGet(value_index));
+            function.instruction(&Instruction::I32Load(carrier_memarg(
+                ERROR_MESSAGE_OFFSET,
+                2,
+            )));
+        }
+        BuiltinCastPolicyId::StringToError => {
+            function.instruction(&Instruction::LocalGet(value_index));
+            function.instruction(&Instruction::I32Const(
+                BuiltinErrorCode::UnknownOrUnassigned.as_i32(),
+            ));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::ErrorNew,
+            )?));
+        }
+        BuiltinCastPolicyId::FloatToString => {
+            // Formatting an in-range Float cannot fail, so a failure is an invariant trap as in
+            // the JS runtime's `__bs_numeric_trap`.
+            function.instruction(&Instruction::LocalGet(value_index));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::FloatFormat,
+            )?));
+            return emit_numeric_result(
+                function,
+                dst,
+                WasmAbiType::Handle,
+                WasmNumericFailureMode::Trap,
+                context,
+                plan,
+            );
+        }
+        _ => {
+            let helper = cast_helper(policy).ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "Wasm emission has no runtime helper for cast policy {policy:?}"
+                ))
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+            })?;
+            function.instruction(&Instruction::LocalGet(value_index));
+            function.instruction(&Instruction::Call(helper_index(plan, helper)?));
+        }
+    }
+    function.instruction(&Instruction::LocalSet(local_index(dst, context)?));
+    Ok(())
+}
+
+fn emit_wasi_call(
+    function: &mut Function,
+    dst: Option<WasmLirLocalId>,
+    adapter: WasiAdapter,
+    args: &[WasmLirLocalId],
+    context: &LirBodyEmitContext<'_>,
+    plan: &WasmEmitPlan,
+) -> Result<(), CompilerError> {
+    // WHAT: adapters that reach the runtime call a WASI helper; time arithmetic on the `f64`
+    // millisecond values is inline.
+    let arg = |index: usize| -> Result<Instruction<'static>, CompilerError> {
+        let local = args.get(index).copied().ok_or_else(|| {
+            CompilerError::compiler_error(format!(
+                "Wasm emission received WASI adapter {adapter:?} without argument {index} in {:?}",
+                context.function_id
+            ))
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })?;
+        Ok(Instruction::LocalGet(local_index(local, context)?))
+    };
+
+    let produces_value = match adapter {
+        WasiAdapter::WriteLine { fd } => {
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::I32Const(fd));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::WasiWriteLine,
+            )?));
+            false
+        }
+        WasiAdapter::WriteFragmentLine { index } => {
+            // WHY: the header-stage fragment count is an upper bound; templates folded to
+            // constants later leave their runtime slot empty, like an unfilled HTML slot.
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::VecLen,
+            )?));
+            function.instruction(&Instruction::I32Const(index as i32));
+            function.instruction(&Instruction::I32GtU);
+            function.instruction(&Instruction::If(BlockType::Empty));
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::I32Const(index as i32));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::VecGet,
+            )?));
+            function.instruction(&Instruction::I32Const(WASI_STDOUT));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::WasiWriteLine,
+            )?));
+            function.instruction(&Instruction::End);
+            false
+        }
+        WasiAdapter::InputNew => {
+            // WHY: fresh payloads are zero-filled, which is the open state with no keys.
+            function.instruction(&Instruction::I32Const(INPUT_STATE_SIZE));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::Alloc,
+            )?));
+            true
+        }
+        WasiAdapter::InputUpdate => {
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::WasiInputUpdate,
+            )?));
+            false
+        }
+        WasiAdapter::InputClose => {
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::I32Const(1));
+            function.instruction(&Instruction::I32Store(memarg_i32(INPUT_CLOSED_OFFSET)));
+            false
+        }
+        WasiAdapter::InputKey(slot) => {
+            function.instruction(&arg(0)?);
+            function.instruction(&arg(1)?);
+            function.instruction(&Instruction::I32Const(slot.index()));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::WasiInputKeyMatches,
+            )?));
+            true
+        }
+        WasiAdapter::NoPointerCoordinate => {
+            function.instruction(&Instruction::F64Const(0.0.into()));
+            true
+        }
+        WasiAdapter::NoPointerButton => {
+            function.instruction(&Instruction::I32Const(0));
+            true
+        }
+        WasiAdapter::ClockNow(clock) => {
+            function.instruction(&Instruction::I32Const(clock.id()));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::WasiClockMillis,
+            )?));
+            true
+        }
+        WasiAdapter::ElapsedSince => {
+            function.instruction(&Instruction::I32Const(WasiClock::Monotonic.id()));
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::WasiClockMillis,
+            )?));
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::F64Sub);
+            true
+        }
+        WasiAdapter::Difference => {
+            function.instruction(&arg(1)?);
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::F64Sub);
+            true
+        }
+        WasiAdapter::SecondsToMillis | WasiAdapter::MillisToSeconds => {
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::F64Const(1000.0.into()));
+            function.instruction(&if adapter == WasiAdapter::SecondsToMillis {
+                Instruction::F64Mul
+            } else {
+                Instruction::F64Div
+            });
+            true
+        }
+        WasiAdapter::Identity => {
+            function.instruction(&arg(0)?);
+            true
+        }
+        WasiAdapter::IsNegative => {
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::F64Const(0.0.into()));
+            function.instruction(&Instruction::F64Lt);
+            true
+        }
+        WasiAdapter::Abs => {
+            function.instruction(&arg(0)?);
+            function.instruction(&Instruction::F64Abs);
+            true
+        }
+        WasiAdapter::Clamp => {
+            function.instruction(&arg(0)?);
+            function.instruction(&arg(1)?);
+            function.instruction(&Instruction::F64Max);
+            function.instruction(&arg(2)?);
+            function.instruction(&Instruction::F64Min);
+            true
+        }
+    };
+
+    match (dst, produces_value) {
+        (Some(dst), true) => {
+            function.instruction(&Instruction::LocalSet(local_index(dst, context)?));
+        }
+        (None, true) if adapter == WasiAdapter::InputNew => {
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::Release,
+            )?));
+        }
+        (None, true) => {
+            function.instruction(&Instruction::Drop);
+        }
+        (None, false) => {}
+        (Some(_), false) => {
+            return Err(CompilerError::compiler_error(format!(
+                "Wasm emission received a destination for result-less WASI adapter {adapter:?}"
+            ))
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    }
+    Ok(())
+}
+
+fn emit_widened_map_slot(
+    function: &mut Function,
+    local_id: WasmLirLocalId,
+    context: &LirBodyEmitContext<'_>,
+) -> Result<(), CompilerError> {
+    // WHAT: push a key or value local as the i64 used by map entry slots.
+    // WHY: one slot shape keeps the map helpers independent of the key and value types.
+    let abi = local_type(local_id, context, "map slot")?;
+    function.instruction(&Instruction::LocalGet(local_index(local_id, context)?));
+    match abi {
+        WasmAbiType::I32 | WasmAbiType::Handle | WasmAbiType::Void => {
+            function.instruction(&Instruction::I64ExtendI32U);
+        }
+        WasmAbiType::I64 => {}
+        WasmAbiType::F32 => {
+            function.instruction(&Instruction::I32ReinterpretF32);
+            function.instruction(&Instruction::I64ExtendI32U);
+        }
+        WasmAbiType::F64 => {
+            function.instruction(&Instruction::I64ReinterpretF64);
+        }
+        WasmAbiType::ExternRef => return Err(externref_map_slot_error(context)),
+    }
+    Ok(())
+}
+
+fn emit_narrowed_map_slot(
+    function: &mut Function,
+    dst: WasmLirLocalId,
+    context: &LirBodyEmitContext<'_>,
+) -> Result<(), CompilerError> {
+    // WHAT: store an i64 slot value from the stack into `dst`, undoing `emit_widened_map_slot`.
+    // WHY: unit payloads have no value, so they are dropped instead of stored.
+    match local_type(dst, context, "map slot destination")? {
+        WasmAbiType::Void => {
+            function.instruction(&Instruction::Drop);
+            return Ok(());
+        }
+        WasmAbiType::I32 | WasmAbiType::Handle => {
+            function.instruction(&Instruction::I32WrapI64);
+        }
+        WasmAbiType::I64 => {}
+        WasmAbiType::F32 => {
+            function.instruction(&Instruction::I32WrapI64);
+            function.instruction(&Instruction::F32ReinterpretI32);
+        }
+        WasmAbiType::F64 => {
+            function.instruction(&Instruction::F64ReinterpretI64);
+        }
+        WasmAbiType::ExternRef => return Err(externref_map_slot_error(context)),
+    }
+    function.instruction(&Instruction::LocalSet(local_index(dst, context)?));
+    Ok(())
+}
+
+fn externref_map_slot_error(context: &LirBodyEmitContext<'_>) -> CompilerError {
+    CompilerError::compiler_error(format!(
+        "Wasm emission cannot keep a host reference in a linear-memory map slot in function {:?}",
+        context.function_id
+    ))
+    .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+}
+
+fn emit_numeric_result(
+    function: &mut Function,
+    dst: WasmLirLocalId,
+    value_type: WasmAbiType,
+    failure_mode: WasmNumericFailureMode,
+    context: &LirBodyEmitContext<'_>,
+    plan: &WasmEmitPlan,
+) -> Result<(), CompilerError> {
+    // WHAT: surface the status written by a checked helper at its call site.
+    // WHY: trap mode halts on any failure; return-error mode hands both outcomes to the
+    // fallible carrier so a following `FallibleBranch` can inspect them.
+    let status_global = plan.numeric_status_global_index.ok_or_else(|| {
+        CompilerError::compiler_error(
+            "Wasm emission expected numeric_status global for a checked numeric operation",
+        )
+        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+    })?;
+
+    match failure_mode {
+        WasmNumericFailureMode::Trap => {
+            function.instruction(&Instruction::GlobalGet(status_global));
+            function.instruction(&Instruction::If(BlockType::Empty));
+            function.instruction(&Instruction::Unreachable);
+            function.instruction(&Instruction::End);
+        }
+        WasmNumericFailureMode::ReturnError => {
+            match value_type {
+                WasmAbiType::I64 => {}
+                WasmAbiType::F64 => {
+                    function.instruction(&Instruction::I64ReinterpretF64);
+                }
+                _ => {
+                    function.instruction(&Instruction::I64ExtendI32U);
+                }
+            }
+            function.instruction(&Instruction::Call(helper_index(
+                plan,
+                WasmRuntimeHelper::NumericCarrier,
+            )?));
+        }
+    }
+    function.instruction(&Instruction::LocalSet(local_index(dst, context)?));
+    Ok(())
+}
+
+fn emit_optional_result(
+    function: &mut Function,
+    dst: Option<WasmLirLocalId>,
+    context: &LirBodyEmitContext<'_>,
+) -> Result<(), CompilerError> {
+    match dst {
+        Some(dst) => function.instruction(&Instruction::LocalSet(local_index(dst, context)?)),
+        None => function.instruction(&Instruction::Drop),
+    };
+    Ok(())
+}
+
+fn callee_index(
+    callee: WasmCalleeRef,
+    context: &LirBodyEmitContext<'_>,
+    plan: &WasmEmitPlan,
+) -> Result<u32, CompilerError> {
+    match callee {
+        WasmCalleeRef::Function(function_id) => plan
+            .function_indices
+            .get(&function_id)
+            .copied()
+            .ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "Wasm emission missing callee function index for {:?} in {:?}",
+                    function_id, context.function_id
+                ))
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+            }),
+        WasmCalleeRef::Import(import_id) => plan
+            .import_function_indices
+            .get(&import_id)
+            .copied()
+            .ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "Wasm emission missing import function index for {:?} in {:?}",
+                    import_id, context.function_id
+                ))
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+            }),
+    }
+}
+
+/// Store a helper's fresh result carrier, or release it when the statement discards it.
+fn emit_optional_carrier_result(
+    function: &mut Function,
+    dst: Option<WasmLirLocalId>,
+    plan: &WasmEmitPlan,
+    context: &LirBodyEmitContext<'_>,
+) -> Result<(), CompilerError> {
+    match dst {
+        Some(dst) => function.instruction(&Instruction::LocalSet(local_index(dst, context)?)),
+        None => function.instruction(&Instruction::Call(helper_index(
+            plan,
+            WasmRuntimeHelper::Release,
+        )?)),
+    };
+    Ok(())
+}
+
+fn carrier_memarg(offset: u64, align: u32) -> MemArg {
+    MemArg {
+        offset,
+        align,
+        memory_index: 0,
+    }
+}
+
+pub(crate) fn local_type(
     local_id: WasmLirLocalId,
     context: &LirBodyEmitContext<'_>,
     label: &str,
@@ -771,7 +1534,7 @@ fn local_type(
         })
 }
 
-fn local_index(
+pub(crate) fn local_index(
     local_id: WasmLirLocalId,
     context: &LirBodyEmitContext<'_>,
 ) -> Result<u32, CompilerError> {
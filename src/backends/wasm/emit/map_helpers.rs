@@ -0,0 +1,854 @@
+//! Map and fallible-carrier runtime helper emission.
+//!
+//! WHAT: synthesizes the insertion-ordered map helpers (`rt_map_*`) and the carrier constructor
+//! they return through (`rt_fallible_new`). Missing keys fail through `rt_fallible_error`.
+//! WHY: separated from helpers.rs because maps carry their own hashed layout, documented in
+//! `runtime::maps`. Keys and values arrive widened to i64 so one helper set serves every scalar
+//! and handle type; string keys are hashed and compared by content.
+
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::emit::alloc_helpers::emit_link_owned_block;
+use crate::backends::wasm::emit::sections::{WasmEmitPlan, helper_name};
+use crate::backends::wasm::runtime::maps::{
+    FALLIBLE_CARRIER_SIZE, FALLIBLE_PAYLOAD_OFFSET, FALLIBLE_TAG_OFFSET, FALLIBLE_TAG_SUCCESS,
+    MAP_ENTRIES_PTR_OFFSET, MAP_ENTRY_CAPACITY_OFFSET, MAP_ENTRY_COUNT_OFFSET,
+    MAP_ENTRY_HASH_OFFSET, MAP_ENTRY_KEY_OFFSET, MAP_ENTRY_LIVE_OFFSET, MAP_ENTRY_SIZE,
+    MAP_ENTRY_VALUE_OFFSET, MAP_HEADER_SIZE, MAP_INDEX_MASK_OFFSET, MAP_INDEX_PTR_OFFSET,
+    MAP_INITIAL_CAPACITY, MAP_KEY_KIND_OFFSET, MAP_LEN_OFFSET, WasmMapKeyKind,
+};
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
+use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use wasm_encoder::{BlockType, Function, Instruction, MemArg, ValType};
+
+const FNV_OFFSET_BASIS: u32 = 0x811c_9dc5;
+const FNV_PRIME: i32 = 0x0100_0193;
+const SCALAR_HASH_MULTIPLIER: u32 = 0x9e37_79b1;
+
+/// Emit the function body for one map or fallible-carrier helper.
+///
+/// `alloc_index` is the Wasm function index of `rt_alloc`; other helper indices come from `plan`.
+pub(crate) fn emit_map_helper(
+    helper: WasmRuntimeHelper,
+    plan: &WasmEmitPlan,
+    alloc_index: u32,
+) -> Result<Function, CompilerError> {
+    let mut function = match helper {
+        // param 0: tag, param 1: payload | local 2: carrier
+        WasmRuntimeHelper::FallibleNew => Function::new(vec![(1, ValType::I32)]),
+        // param 0: key_kind | local 1: map
+        WasmRuntimeHelper::MapNew => Function::new(vec![(1, ValType::I32)]),
+        // param 0: map, param 1: key | local 2: hash, local 3: cursor, local 4: end
+        WasmRuntimeHelper::MapHashKey => Function::new(vec![(3, ValType::I32)]),
+        // param 0: map, param 1: lhs, param 2: rhs
+        // local 3: lhs_ptr, local 4: rhs_ptr, local 5: len, local 6: offset
+        WasmRuntimeHelper::MapKeysEqual => Function::new(vec![(4, ValType::I32)]),
+        // param 0: map, param 1: key | local 2: hash, local 3: slot, local 4: position,
+        // local 5: entry
+        WasmRuntimeHelper::MapFind => Function::new(vec![(4, ValType::I32)]),
+        // param 0: map, param 1: new_capacity | local 2: new_entries, local 3: new_index,
+        // local 4: source_position, local 5: target_position, local 6: source, local 7: target,
+        // local 8: slot, local 9: mask
+        WasmRuntimeHelper::MapRebuild => Function::new(vec![(8, ValType::I32)]),
+        // param 0: map, param 1: key | local 2: position
+        WasmRuntimeHelper::MapGet | WasmRuntimeHelper::MapContains => {
+            Function::new(vec![(1, ValType::I32)])
+        }
+        // param 0: map, param 1: key | local 2: position, local 3: entry
+        WasmRuntimeHelper::MapRemove => Function::new(vec![(2, ValType::I32)]),
+        // param 0: map, param 1: key, param 2: value
+        // local 3: position, local 4: capacity, local 5: entry, local 6: hash, local 7: slot,
+        // local 8: index, local 9: mask
+        WasmRuntimeHelper::MapSet => Function::new(vec![(7, ValType::I32)]),
+        WasmRuntimeHelper::MapClear | WasmRuntimeHelper::MapLength => Function::new(Vec::new()),
+        _ => {
+            return Err(CompilerError::compiler_error(
+                "emit_map_helper called with non-map helper variant",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    };
+
+    match helper {
+        WasmRuntimeHelper::FallibleNew => {
+            const TAG: u32 = 0;
+            const PAYLOAD: u32 = 1;
+            const CARRIER: u32 = 2;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(FALLIBLE_CARRIER_SIZE),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalTee(CARRIER),
+                    Instruction::LocalGet(TAG),
+                    Instruction::I32Store(memarg_i32(FALLIBLE_TAG_OFFSET)),
+                    Instruction::LocalGet(CARRIER),
+                    Instruction::LocalGet(PAYLOAD),
+                    Instruction::I64Store(memarg_i64(FALLIBLE_PAYLOAD_OFFSET)),
+                    Instruction::LocalGet(CARRIER),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapNew => {
+            // WHAT: zero the header and record the key kind; storage is allocated on first insert.
+            const KEY_KIND: u32 = 0;
+            const MAP: u32 = 1;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(MAP_HEADER_SIZE),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalTee(MAP),
+                    Instruction::I32Const(0),
+                    Instruction::I32Const(MAP_HEADER_SIZE),
+                    Instruction::MemoryFill(0),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(KEY_KIND),
+                    Instruction::I32Store(memarg_i32(MAP_KEY_KIND_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapHashKey => {
+            // WHAT: FNV-1a over string bytes, or a multiplicative mix of the folded scalar bits.
+            // WHY: probing masks the low bits, so both paths spread entropy into them.
+            const MAP: u32 = 0;
+            const KEY: u32 = 1;
+            const HASH: u32 = 2;
+            const CURSOR: u32 = 3;
+            const END: u32 = 4;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_KEY_KIND_OFFSET)),
+                    Instruction::I32Const(WasmMapKeyKind::String.as_i32()),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(FNV_OFFSET_BASIS as i32),
+                    Instruction::LocalSet(HASH),
+                    // end = ptr + len, read from the `{ptr, len}` string handle.
+                    Instruction::LocalGet(KEY),
+                    Instruction::I32WrapI64,
+                    Instruction::LocalTee(CURSOR),
+                    Instruction::I32Load(memarg_i32(4)),
+                    Instruction::LocalSet(END),
+                    Instruction::LocalGet(CURSOR),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalTee(CURSOR),
+                    Instruction::LocalGet(END),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(END),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(CURSOR),
+                    Instruction::LocalGet(END),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(HASH),
+                    Instruction::LocalGet(CURSOR),
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Xor,
+                    Instruction::I32Const(FNV_PRIME),
+                    Instruction::I32Mul,
+                    Instruction::LocalSet(HASH),
+                    Instruction::LocalGet(CURSOR),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(CURSOR),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::Else,
+                    Instruction::LocalGet(KEY),
+                    Instruction::LocalGet(KEY),
+                    Instruction::I64Const(32),
+                    Instruction::I64ShrU,
+                    Instruction::I64Xor,
+                    Instruction::I32WrapI64,
+                    Instruction::I32Const(SCALAR_HASH_MULTIPLIER as i32),
+                    Instruction::I32Mul,
+                    Instruction::LocalTee(HASH),
+                    Instruction::LocalGet(HASH),
+                    Instruction::I32Const(16),
+                    Instruction::I32ShrU,
+                    Instruction::I32Xor,
+                    Instruction::LocalSet(HASH),
+                    Instruction::End,
+                    Instruction::LocalGet(HASH),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapKeysEqual => {
+            // WHAT: scalar keys compare bits; string keys compare length and then bytes.
+            const MAP: u32 = 0;
+            const LHS: u32 = 1;
+            const RHS: u32 = 2;
+            const LHS_PTR: u32 = 3;
+            const RHS_PTR: u32 = 4;
+            const LEN: u32 = 5;
+            const OFFSET: u32 = 6;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_KEY_KIND_OFFSET)),
+                    Instruction::I32Const(WasmMapKeyKind::Scalar.as_i32()),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(RHS),
+                    Instruction::I64Eq,
+                    Instruction::Return,
+                    Instruction::End,
+                    // Different byte lengths can never be equal.
+                    Instruction::LocalGet(LHS),
+                    Instruction::I32WrapI64,
+                    Instruction::LocalTee(LHS_PTR),
+                    Instruction::I32Load(memarg_i32(4)),
+                    Instruction::LocalSet(LEN),
+                    Instruction::LocalGet(RHS),
+                    Instruction::I32WrapI64,
+                    Instruction::LocalTee(RHS_PTR),
+                    Instruction::I32Load(memarg_i32(4)),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32Ne,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(0),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(LHS_PTR),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalSet(LHS_PTR),
+                    Instruction::LocalGet(RHS_PTR),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalSet(RHS_PTR),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(LHS_PTR),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::LocalGet(RHS_PTR),
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Ne,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(0),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(OFFSET),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(OFFSET),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::I32Const(1),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapFind => {
+            // WHAT: linear probing from `hash & mask` until a live matching entry or an empty slot.
+            // WHY: the index is at most half full, so probing always reaches an empty slot.
+            let hash_index = helper_index(plan, WasmRuntimeHelper::MapHashKey)?;
+            let keys_equal_index = helper_index(plan, WasmRuntimeHelper::MapKeysEqual)?;
+            const MAP: u32 = 0;
+            const KEY: u32 = 1;
+            const HASH: u32 = 2;
+            const SLOT: u32 = 3;
+            const POSITION: u32 = 4;
+            const ENTRY: u32 = 5;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_CAPACITY_OFFSET)),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(-1),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(KEY),
+                    Instruction::Call(hash_index),
+                    Instruction::LocalTee(HASH),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_INDEX_MASK_OFFSET)),
+                    Instruction::I32And,
+                    Instruction::LocalSet(SLOT),
+                    Instruction::Loop(BlockType::Empty),
+                    // position = index[slot]; zero marks an empty slot.
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_INDEX_PTR_OFFSET)),
+                    Instruction::LocalGet(SLOT),
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::I32Add,
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalTee(POSITION),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(-1),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::LocalTee(POSITION),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::LocalTee(ENTRY),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_LIVE_OFFSET)),
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(ENTRY),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_HASH_OFFSET)),
+                    Instruction::LocalGet(HASH),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(ENTRY),
+                    Instruction::I64Load(memarg_i64(MAP_ENTRY_KEY_OFFSET)),
+                    Instruction::LocalGet(KEY),
+                    Instruction::Call(keys_equal_index),
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::LocalGet(SLOT),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_INDEX_MASK_OFFSET)),
+                    Instruction::I32And,
+                    Instruction::LocalSet(SLOT),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::Unreachable,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapRebuild => {
+            // WHAT: copy live entries in order into fresh storage and re-index them.
+            // WHY: compaction drops removed entries without disturbing insertion order.
+            const MAP: u32 = 0;
+            const NEW_CAPACITY: u32 = 1;
+            const NEW_ENTRIES: u32 = 2;
+            const NEW_INDEX: u32 = 3;
+            const SOURCE_POSITION: u32 = 4;
+            const TARGET_POSITION: u32 = 5;
+            const SOURCE: u32 = 6;
+            const TARGET: u32 = 7;
+            const SLOT: u32 = 8;
+            const MASK: u32 = 9;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(NEW_CAPACITY),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalSet(NEW_ENTRIES),
+                    // The index has two i32 slots per entry: capacity * 8 bytes.
+                    Instruction::LocalGet(NEW_CAPACITY),
+                    Instruction::I32Const(3),
+                    Instruction::I32Shl,
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalTee(NEW_INDEX),
+                    Instruction::I32Const(0),
+                    Instruction::LocalGet(NEW_CAPACITY),
+                    Instruction::I32Const(3),
+                    Instruction::I32Shl,
+                    Instruction::MemoryFill(0),
+                    Instruction::LocalGet(NEW_CAPACITY),
+                    Instruction::I32Const(1),
+                    Instruction::I32Shl,
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::LocalSet(MASK),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(SOURCE_POSITION),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_COUNT_OFFSET)),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::LocalGet(SOURCE_POSITION),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::LocalTee(SOURCE),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_LIVE_OFFSET)),
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(NEW_ENTRIES),
+                    Instruction::LocalGet(TARGET_POSITION),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::LocalTee(TARGET),
+                    Instruction::LocalGet(SOURCE),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::MemoryCopy {
+                        src_mem: 0,
+                        dst_mem: 0,
+                    },
+                    Instruction::LocalGet(TARGET),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_HASH_OFFSET)),
+                    Instruction::LocalGet(MASK),
+                    Instruction::I32And,
+                    Instruction::LocalSet(SLOT),
+                ],
+            );
+            emit_probe_for_empty_slot(&mut function, NEW_INDEX, MASK, SLOT);
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(NEW_INDEX),
+                    Instruction::LocalGet(SLOT),
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::I32Add,
+                    Instruction::LocalGet(TARGET_POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalTee(TARGET_POSITION),
+                    Instruction::I32Store(memarg_i32(0)),
+                    Instruction::End,
+                    Instruction::LocalGet(SOURCE_POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(SOURCE_POSITION),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    // The old entries own the old index, so one release frees both.
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::Release)?),
+                ],
+            );
+            emit_link_owned_block(&mut function, NEW_ENTRIES, NEW_INDEX);
+            emit_link_owned_block(&mut function, MAP, NEW_ENTRIES);
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(NEW_ENTRIES),
+                    Instruction::I32Store(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(TARGET_POSITION),
+                    Instruction::I32Store(memarg_i32(MAP_ENTRY_COUNT_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(NEW_CAPACITY),
+                    Instruction::I32Store(memarg_i32(MAP_ENTRY_CAPACITY_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(NEW_INDEX),
+                    Instruction::I32Store(memarg_i32(MAP_INDEX_PTR_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(MASK),
+                    Instruction::I32Store(memarg_i32(MAP_INDEX_MASK_OFFSET)),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapGet => {
+            let find_index = helper_index(plan, WasmRuntimeHelper::MapFind)?;
+            let fallible_new_index = helper_index(plan, WasmRuntimeHelper::FallibleNew)?;
+            const MAP: u32 = 0;
+            const POSITION: u32 = 2;
+
+            emit_find_or_key_not_found(
+                &mut function,
+                find_index,
+                helper_index(plan, WasmRuntimeHelper::FallibleError)?,
+                POSITION,
+            );
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::I64Load(memarg_i64(MAP_ENTRY_VALUE_OFFSET)),
+                    Instruction::Call(fallible_new_index),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapContains => {
+            let find_index = helper_index(plan, WasmRuntimeHelper::MapFind)?;
+            const MAP: u32 = 0;
+            const KEY: u32 = 1;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(KEY),
+                    Instruction::Call(find_index),
+                    Instruction::I32Const(0),
+                    Instruction::I32GeS,
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapSet => {
+            // WHAT: replace in place when the key exists, otherwise append a new entry.
+            // WHY: matches the JS runtime, where `set` on an existing key keeps its position.
+            let find_index = helper_index(plan, WasmRuntimeHelper::MapFind)?;
+            let hash_index = helper_index(plan, WasmRuntimeHelper::MapHashKey)?;
+            let rebuild_index = helper_index(plan, WasmRuntimeHelper::MapRebuild)?;
+            let fallible_new_index = helper_index(plan, WasmRuntimeHelper::FallibleNew)?;
+            const MAP: u32 = 0;
+            const KEY: u32 = 1;
+            const VALUE: u32 = 2;
+            const POSITION: u32 = 3;
+            const CAPACITY: u32 = 4;
+            const ENTRY: u32 = 5;
+            const HASH: u32 = 6;
+            const SLOT: u32 = 7;
+            const INDEX: u32 = 8;
+            const MASK: u32 = 9;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(KEY),
+                    Instruction::Call(find_index),
+                    Instruction::LocalTee(POSITION),
+                    Instruction::I32Const(0),
+                    Instruction::I32GeS,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Store(memarg_i64(MAP_ENTRY_VALUE_OFFSET)),
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::I64Const(0),
+                    Instruction::Call(fallible_new_index),
+                    Instruction::Return,
+                    Instruction::End,
+                    // When every entry slot is used, compact in place if at most half the
+                    // entries are live and double the capacity otherwise.
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_COUNT_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_CAPACITY_OFFSET)),
+                    Instruction::LocalTee(CAPACITY),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(CAPACITY),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Result(ValType::I32)),
+                    Instruction::I32Const(MAP_INITIAL_CAPACITY),
+                    Instruction::Else,
+                    Instruction::LocalGet(CAPACITY),
+                    Instruction::I32Const(1),
+                    Instruction::I32Shl,
+                    Instruction::LocalGet(CAPACITY),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_LEN_OFFSET)),
+                    Instruction::I32Const(1),
+                    Instruction::I32Shl,
+                    Instruction::LocalGet(CAPACITY),
+                    Instruction::I32GeU,
+                    Instruction::Select,
+                    Instruction::End,
+                    Instruction::LocalSet(CAPACITY),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(CAPACITY),
+                    Instruction::Call(rebuild_index),
+                    Instruction::End,
+                    // Append the entry at `entry_count`.
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(KEY),
+                    Instruction::Call(hash_index),
+                    Instruction::LocalSet(HASH),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_COUNT_OFFSET)),
+                    Instruction::LocalSet(POSITION),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::LocalTee(ENTRY),
+                    Instruction::LocalGet(KEY),
+                    Instruction::I64Store(memarg_i64(MAP_ENTRY_KEY_OFFSET)),
+                    Instruction::LocalGet(ENTRY),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::I64Store(memarg_i64(MAP_ENTRY_VALUE_OFFSET)),
+                    Instruction::LocalGet(ENTRY),
+                    Instruction::LocalGet(HASH),
+                    Instruction::I32Store(memarg_i32(MAP_ENTRY_HASH_OFFSET)),
+                    Instruction::LocalGet(ENTRY),
+                    Instruction::I32Const(1),
+                    Instruction::I32Store(memarg_i32(MAP_ENTRY_LIVE_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::I32Store(memarg_i32(MAP_ENTRY_COUNT_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_LEN_OFFSET)),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::I32Store(memarg_i32(MAP_LEN_OFFSET)),
+                    // Index the new entry.
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_INDEX_PTR_OFFSET)),
+                    Instruction::LocalSet(INDEX),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_INDEX_MASK_OFFSET)),
+                    Instruction::LocalTee(MASK),
+                    Instruction::LocalGet(HASH),
+                    Instruction::I32And,
+                    Instruction::LocalSet(SLOT),
+                ],
+            );
+            emit_probe_for_empty_slot(&mut function, INDEX, MASK, SLOT);
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(INDEX),
+                    Instruction::LocalGet(SLOT),
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::I32Add,
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::I32Store(memarg_i32(0)),
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::I64Const(0),
+                    Instruction::Call(fallible_new_index),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapRemove => {
+            // WHAT: mark the entry removed and return its value.
+            // WHY: the index keeps pointing at removed entries so later probes still pass them.
+            let find_index = helper_index(plan, WasmRuntimeHelper::MapFind)?;
+            let fallible_new_index = helper_index(plan, WasmRuntimeHelper::FallibleNew)?;
+            const MAP: u32 = 0;
+            const POSITION: u32 = 2;
+            const ENTRY: u32 = 3;
+
+            emit_find_or_key_not_found(
+                &mut function,
+                find_index,
+                helper_index(plan, WasmRuntimeHelper::FallibleError)?,
+                POSITION,
+            );
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRIES_PTR_OFFSET)),
+                    Instruction::LocalGet(POSITION),
+                    Instruction::I32Const(MAP_ENTRY_SIZE),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::LocalTee(ENTRY),
+                    Instruction::I32Const(0),
+                    Instruction::I32Store(memarg_i32(MAP_ENTRY_LIVE_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_LEN_OFFSET)),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::I32Store(memarg_i32(MAP_LEN_OFFSET)),
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(ENTRY),
+                    Instruction::I64Load(memarg_i64(MAP_ENTRY_VALUE_OFFSET)),
+                    Instruction::Call(fallible_new_index),
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapClear => {
+            // WHAT: forget all entries and empty the index, keeping the allocated storage.
+            const MAP: u32 = 0;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Const(0),
+                    Instruction::I32Store(memarg_i32(MAP_ENTRY_COUNT_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Const(0),
+                    Instruction::I32Store(memarg_i32(MAP_LEN_OFFSET)),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_ENTRY_CAPACITY_OFFSET)),
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_INDEX_PTR_OFFSET)),
+                    Instruction::I32Const(0),
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_INDEX_MASK_OFFSET)),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::MemoryFill(0),
+                    Instruction::End,
+                    Instruction::Return,
+                ],
+            );
+        }
+        WasmRuntimeHelper::MapLength => {
+            const MAP: u32 = 0;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(MAP),
+                    Instruction::I32Load(memarg_i32(MAP_LEN_OFFSET)),
+                    Instruction::I64ExtendI32U,
+                    Instruction::Return,
+                ],
+            );
+        }
+        _ => {}
+    }
+
+    function.instruction(&Instruction::End);
+    Ok(function)
+}
+
+/// Calls `rt_map_find` with params 0 and 1, storing the position in `position_local`, and
+/// returns a `MapKeyNotFound` error carrier when the key is missing.
+fn emit_find_or_key_not_found(
+    function: &mut Function,
+    find_index: u32,
+    fallible_error_index: u32,
+    position_local: u32,
+) {
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(0),
+            Instruction::LocalGet(1),
+            Instruction::Call(find_index),
+            Instruction::LocalTee(position_local),
+            Instruction::I32Const(0),
+            Instruction::I32LtS,
+            Instruction::If(BlockType::Empty),
+            Instruction::I32Const(BuiltinErrorCode::MapKeyNotFound.as_i32()),
+            Instruction::Call(fallible_error_index),
+            Instruction::Return,
+            Instruction::End,
+        ],
+    );
+}
+
+/// Advances `slot_local` with linear probing until `index[slot]` is empty.
+fn emit_probe_for_empty_slot(
+    function: &mut Function,
+    index_local: u32,
+    mask_local: u32,
+    slot_local: u32,
+) {
+    emit(
+        function,
+        &[
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(index_local),
+            Instruction::LocalGet(slot_local),
+            Instruction::I32Const(2),
+            Instruction::I32Shl,
+            Instruction::I32Add,
+            Instruction::I32Load(memarg_i32(0)),
+            Instruction::I32Eqz,
+            Instruction::BrIf(1),
+            Instruction::LocalGet(slot_local),
+            Instruction::I32Const(1),
+            Instruction::I32Add,
+            Instruction::LocalGet(mask_local),
+            Instruction::I32And,
+            Instruction::LocalSet(slot_local),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+        ],
+    );
+}
+
+pub(super) fn emit(function: &mut Function, instructions: &[Instruction<'_>]) {
+    for instruction in instructions {
+        function.instruction(instruction);
+    }
+}
+
+pub(super) fn helper_index(
+    plan: &WasmEmitPlan,
+    helper: WasmRuntimeHelper,
+) -> Result<u32, CompilerError> {
+    plan.helper_indices.get(&helper).copied().ok_or_else(|| {
+        CompilerError::compiler_error(format!(
+            "Wasm emission missing {} helper index",
+            helper_name(helper)
+        ))
+        .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+    })
+}
+
+pub(super) fn memarg_i32(offset: u64) -> MemArg {
+    MemArg {
+        offset,
+        align: 2,
+        memory_index: 0,
+    }
+}
+
+pub(super) fn memarg_i64(offset: u64) -> MemArg {
+    MemArg {
+        offset,
+        align: 3,
+        memory_index: 0,
+    }
+}
+
+pub(super) fn memarg_byte() -> MemArg {
+    MemArg {
+        offset: 0,
+        align: 0,
+        memory_index: 0,
+    }
+}
@@ -2,18 +2,29 @@
 //!
 //! This layer owns binary encoding only. It does not reinterpret frontend semantics.
 
+pub(crate) mod alloc_helpers;
+pub(crate) mod cast_helpers;
 pub(crate) mod data;
+pub(crate) mod error_helpers;
 pub(crate) mod exports;
+pub(crate) mod float_format_helpers;
 pub(crate) mod functions;
+pub(crate) mod gc_helpers;
+pub(crate) mod gc_instructions;
+pub(crate) mod gc_module;
 pub(crate) mod helpers;
 pub(crate) mod imports;
 pub(crate) mod instructions;
+pub(crate) mod map_helpers;
 pub(crate) mod module;
 pub(crate) mod names;
+pub(crate) mod numeric_helpers;
 pub(crate) mod sections;
+pub(crate) mod structured_cfg;
 pub(crate) mod types;
 pub(crate) mod validate;
 pub(crate) mod vec_helpers;
+pub(crate) mod wasi_helpers;
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct WasmEmitDebugOutputs {
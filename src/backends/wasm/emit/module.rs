@@ -3,6 +3,7 @@
 use crate::backends::wasm::emit::data::build_data_section;
 use crate::backends::wasm::emit::exports::build_export_section;
 use crate::backends::wasm::emit::functions::build_code_section;
+use crate::backends::wasm::emit::gc_module::emit_lir_to_wasm_gc_module;
 use crate::backends::wasm::emit::imports::build_import_section;
 use crate::backends::wasm::emit::names::build_name_custom_section;
 use crate::backends::wasm::emit::sections::{
@@ -14,6 +15,8 @@ use crate::backends::wasm::emit::validate::validate_emitted_module;
 use crate::backends::wasm::emit::{WasmEmitDebugOutputs, WasmEmitResult};
 use crate::backends::wasm::lir::module::WasmLirModule;
 use crate::backends::wasm::request::WasmBackendRequest;
+use crate::backends::wasm::runtime::memory::{WASM_PAGE_SIZE_LOG2, heap_start};
+use crate::backends::wasm::runtime::numeric::NUMERIC_STATUS_OK;
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
 use rustc_hash::FxHashMap;
 use wasm_encoder::{
@@ -25,6 +28,11 @@ pub(crate) fn emit_lir_to_wasm_module(
     lir_module: &WasmLirModule,
     request: &WasmBackendRequest,
 ) -> Result<WasmEmitResult, CompilerError> {
+    // WHAT: the Wasm GC mode replaces the linear-memory object model wholesale.
+    if request.target_features.use_wasm_gc {
+        return emit_lir_to_wasm_gc_module(lir_module, request);
+    }
+
     // WHAT: precompute all indices/layout before section writing starts.
     // WHY: index spaces cross-reference each other, so deterministic preplanning avoids
     // accidental order-dependent bugs during section assembly.
@@ -54,7 +62,7 @@ pub(crate) fn emit_lir_to_wasm_module(
     // WHAT: emit imports only when present in the lowered module.
     // WHY: the current emitter keeps binary output minimal and deterministic.
     if !lir_module.imports.is_empty() {
-        let import_section = build_import_section(lir_module, &plan)?;
+        let import_section = build_import_section(lir_module, &plan.type_index_by_signature)?;
         wasm_module.section(&import_section);
     }
 
@@ -68,9 +76,17 @@ pub(crate) fn emit_lir_to_wasm_module(
         wasm_module.section(&function_section);
     }
 
+    // WHAT: the initial memory always covers static data and the allocator's free-list table.
+    // WHY: `rt_alloc` grows memory for blocks, but the table itself is read before any growth.
+    let reserved_end = if plan.heap_top_global_index.is_some() {
+        heap_start(plan.heap_base)
+    } else {
+        plan.heap_base
+    };
+    let reserved_pages = reserved_end.div_ceil(1 << WASM_PAGE_SIZE_LOG2);
     let mut memory_section = MemorySection::new();
     memory_section.memory(MemoryType {
-        minimum: u64::from(lir_module.memory_plan.initial_pages),
+        minimum: u64::from(lir_module.memory_plan.initial_pages.max(reserved_pages)),
         maximum: lir_module.memory_plan.max_pages.map(u64::from),
         memory64: false,
         shared: false,
@@ -78,8 +94,8 @@ pub(crate) fn emit_lir_to_wasm_module(
     });
     wasm_module.section(&memory_section);
 
-    // WHAT: heap_top global is synthesized only when helper runtime support is needed.
-    // WHY: helpers use bump allocation, while modules with no helper usage can skip globals.
+    // WHAT: allocator globals are synthesized only when helper runtime support is needed.
+    // WHY: modules with no helper usage have no heap, so they can skip globals.
     if plan.heap_top_global_index.is_some() {
         let mut global_section = GlobalSection::new();
         global_section.global(
@@ -88,8 +104,31 @@ pub(crate) fn emit_lir_to_wasm_module(
                 mutable: true,
                 shared: false,
             },
-            &ConstExpr::i32_const(plan.heap_base as i32),
+            &ConstExpr::i32_const(heap_start(plan.heap_base) as i32),
         );
+        // Checked numeric helpers report failures through a second i32 global
+        // (see `runtime::numeric`).
+        if plan.numeric_status_global_index.is_some() {
+            global_section.global(
+                GlobalType {
+                    val_type: wasm_encoder::ValType::I32,
+                    mutable: true,
+                    shared: false,
+                },
+                &ConstExpr::i32_const(NUMERIC_STATUS_OK),
+            );
+        }
+        // Blocks handed out by `rt_alloc` and not yet released (see `runtime::memory`).
+        if plan.live_allocations_global_index.is_some() {
+            global_section.global(
+                GlobalType {
+                    val_type: wasm_encoder::ValType::I32,
+                    mutable: true,
+                    shared: false,
+                },
+                &ConstExpr::i32_const(0),
+            );
+        }
         wasm_module.section(&global_section);
     }
 
@@ -106,13 +145,17 @@ pub(crate) fn emit_lir_to_wasm_module(
     // WHAT: code section mirrors `defined_function_order` exactly.
     // WHY: function section and code section must stay index-aligned.
     if !plan.defined_function_order.is_empty() {
-        let code_section = build_code_section(&lir_functions, &plan)?;
+        let code_section = build_code_section(
+            &lir_functions,
+            &plan,
+            request.emit_options.cfg_lowering_strategy,
+        )?;
         wasm_module.section(&code_section);
     }
 
-    // WHAT: static data is emitted as active segments into memory index 0.
+    // WHAT: static data and emitter-owned strings are emitted as active segments into memory 0.
     // WHY: the current emitter uses one internal linear memory and deterministic static placement.
-    if !lir_module.static_data.is_empty() {
+    if !lir_module.static_data.is_empty() || !plan.runtime_strings.is_empty() {
         let data_section = build_data_section(lir_module, &plan)?;
         wasm_module.section(&data_section);
     }
@@ -0,0 +1,724 @@
+//! Checked numeric runtime helper emission.
+//!
+//! WHAT: synthesizes the checked `Int`/`Float` arithmetic helpers (`rt_int_*`, `rt_float_*`) and
+//! `rt_numeric_carrier`, which wraps a value or the pending failure's `Error` in a carrier.
+//! WHY: separated from helpers.rs because every helper here shares the `numeric_status` contract
+//! documented in `runtime::numeric`, and each check mirrors one JS runtime helper so both
+//! backends fail on the same inputs with the same error codes.
+
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::emit::map_helpers::{emit, helper_index};
+use crate::backends::wasm::emit::sections::WasmEmitPlan;
+use crate::backends::wasm::runtime::maps::FALLIBLE_TAG_SUCCESS;
+use crate::backends::wasm::runtime::numeric::{INT_MAX, INT_MIN, NUMERIC_STATUS_OK};
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
+use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use wasm_encoder::{BlockType, Function, Instruction, ValType};
+
+const F64_EXPONENT_MASK: i32 = 0x7ff;
+const F64_MANTISSA_MASK: i64 = (1 << 52) - 1;
+const F64_HIDDEN_BIT: i64 = 1 << 52;
+
+/// Emit the function body for one checked numeric helper.
+///
+/// `status_global` is the index of the `numeric_status` global every helper writes.
+pub(crate) fn emit_numeric_helper(
+    helper: WasmRuntimeHelper,
+    plan: &WasmEmitPlan,
+    status_global: u32,
+) -> Result<Function, CompilerError> {
+    let mut function = match helper {
+        // param 0: value
+        WasmRuntimeHelper::NumericCarrier => Function::new(Vec::new()),
+        // param 0: lhs, param 1: rhs (param 1 doubles as the remaining exponent) | local 2: result
+        WasmRuntimeHelper::IntAdd
+        | WasmRuntimeHelper::IntSub
+        | WasmRuntimeHelper::IntMul
+        | WasmRuntimeHelper::IntDiv
+        | WasmRuntimeHelper::IntMod
+        | WasmRuntimeHelper::IntPow => Function::new(vec![(1, ValType::I64)]),
+        // param 0: operand | local 1: result
+        WasmRuntimeHelper::IntNeg => Function::new(vec![(1, ValType::I64)]),
+        // param 0: lhs, param 1: rhs | local 2: result
+        WasmRuntimeHelper::FloatAdd
+        | WasmRuntimeHelper::FloatSub
+        | WasmRuntimeHelper::FloatMul
+        | WasmRuntimeHelper::FloatDiv => Function::new(vec![(1, ValType::F64)]),
+        // param 0: x, param 1: y | local 2: result, local 3: x_bits, local 4: y_bits,
+        // local 5: difference, local 6: x_exponent, local 7: y_exponent, local 8: x_sign
+        WasmRuntimeHelper::FloatMod => Function::new(vec![
+            (1, ValType::F64),
+            (3, ValType::I64),
+            (3, ValType::I32),
+        ]),
+        // param 0: operand | local 1: result
+        WasmRuntimeHelper::FloatNeg
+        | WasmRuntimeHelper::FloatCheckFinite
+        | WasmRuntimeHelper::FloatValidate => Function::new(vec![(1, ValType::F64)]),
+        _ => {
+            return Err(CompilerError::compiler_error(
+                "emit_numeric_helper called with non-numeric helper variant",
+            )
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration)));
+        }
+    };
+
+    match helper {
+        WasmRuntimeHelper::NumericCarrier => {
+            // WHAT: turn the status left by the preceding checked helper into a carrier.
+            const VALUE: u32 = 0;
+            let fallible_new = helper_index(plan, WasmRuntimeHelper::FallibleNew)?;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::GlobalGet(status_global),
+                    Instruction::I32Eqz,
+                    Instruction::If(BlockType::Result(ValType::I32)),
+                    Instruction::I32Const(FALLIBLE_TAG_SUCCESS),
+                    Instruction::LocalGet(VALUE),
+                    Instruction::Call(fallible_new),
+                    Instruction::Else,
+                    Instruction::GlobalGet(status_global),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::FallibleError)?),
+                    Instruction::End,
+                ],
+            );
+        }
+        WasmRuntimeHelper::IntAdd | WasmRuntimeHelper::IntSub | WasmRuntimeHelper::IntMul => {
+            // WHAT: operands are valid i32 values, so the i64 result is exact and only the
+            // range check can fail.
+            const LHS: u32 = 0;
+            const RHS: u32 = 1;
+            const RESULT: u32 = 2;
+
+            let operation = match helper {
+                WasmRuntimeHelper::IntAdd => Instruction::I64Add,
+                WasmRuntimeHelper::IntSub => Instruction::I64Sub,
+                _ => Instruction::I64Mul,
+            };
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(RHS),
+                    operation,
+                    Instruction::LocalSet(RESULT),
+                ],
+            );
+            emit_int_range_result(&mut function, status_global, RESULT);
+        }
+        WasmRuntimeHelper::IntDiv | WasmRuntimeHelper::IntMod => {
+            // WHAT: truncating division and remainder, matching `Math.trunc(a / b)` and `a % b`.
+            // WHY: `MIN / -1` leaves the i32 range and is caught by the range check, but the JS
+            // runtime also reports `MIN % -1` as an overflow, so that pair is rejected up front.
+            const LHS: u32 = 0;
+            const RHS: u32 = 1;
+            const RESULT: u32 = 2;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(RHS),
+                    Instruction::I64Eqz,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_failure(
+                &mut function,
+                status_global,
+                BuiltinErrorCode::DivideByZero,
+                Instruction::I64Const(0),
+            );
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::LocalGet(LHS),
+                    Instruction::I64Const(INT_MIN),
+                    Instruction::I64Eq,
+                    Instruction::LocalGet(RHS),
+                    Instruction::I64Const(-1),
+                    Instruction::I64Eq,
+                    Instruction::I32And,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_failure(
+                &mut function,
+                status_global,
+                BuiltinErrorCode::IntOverflow,
+                Instruction::I64Const(0),
+            );
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(RHS),
+                    if helper == WasmRuntimeHelper::IntDiv {
+                        Instruction::I64DivS
+                    } else {
+                        Instruction::I64RemS
+                    },
+                    Instruction::LocalSet(RESULT),
+                ],
+            );
+            emit_int_range_result(&mut function, status_global, RESULT);
+        }
+        WasmRuntimeHelper::IntPow => {
+            // WHAT: exact repeated multiplication with a range check after every step.
+            // WHY: `Math.pow` is exact for every in-range Int result, and any base other than
+            // 0, 1 and -1 overflows within 32 steps, so those bases are answered directly.
+            const BASE: u32 = 0;
+            const EXPONENT: u32 = 1;
+            const RESULT: u32 = 2;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(EXPONENT),
+                    Instruction::I64Const(0),
+                    Instruction::I64LtS,
+                    Instruction::If(BlockType::Empty),
+                ],
+            );
+            emit_failure(
+                &mut function,
+                status_global,
+                BuiltinErrorCode::InvalidExponent,
+                Instruction::I64Const(0),
+            );
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::I32Const(NUMERIC_STATUS_OK),
+                    Instruction::GlobalSet(status_global),
+                    // 1^n = 1
+                    Instruction::LocalGet(BASE),
+                    Instruction::I64Const(1),
+                    Instruction::I64Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I64Const(1),
+                    Instruction::Return,
+                    Instruction::End,
+                    // 0^0 = 1, 0^n = 0
+                    Instruction::LocalGet(BASE),
+                    Instruction::I64Eqz,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(EXPONENT),
+                    Instruction::I64Eqz,
+                    Instruction::I64ExtendI32U,
+                    Instruction::Return,
+                    Instruction::End,
+                    // (-1)^n alternates with the exponent's parity
+                    Instruction::LocalGet(BASE),
+                    Instruction::I64Const(-1),
+                    Instruction::I64Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I64Const(-1),
+                    Instruction::I64Const(1),
+                    Instruction::LocalGet(EXPONENT),
+                    Instruction::I64Const(1),
+                    Instruction::I64And,
+                    Instruction::I32WrapI64,
+                    Instruction::Select,
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::I64Const(1),
+                    Instruction::LocalSet(RESULT),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(EXPONENT),
+                    Instruction::I64Eqz,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(RESULT),
+                    Instruction::LocalGet(BASE),
+                    Instruction::I64Mul,
+                    Instruction::LocalSet(RESULT),
+                ],
+            );
+            emit_int_out_of_range(&mut function, RESULT);
+            emit(&mut function, &[Instruction::If(BlockType::Empty)]);
+            emit_failure(
+                &mut function,
+                status_global,
+                BuiltinErrorCode::IntOverflow,
+                Instruction::I64Const(0),
+            );
+            emit(
+                &mut function,
+                &[
+                    Instruction::End,
+                    Instruction::LocalGet(EXPONENT),
+                    Instruction::I64Const(1),
+                    Instruction::I64Sub,
+                    Instruction::LocalSet(EXPONENT),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::LocalGet(RESULT),
+                ],
+            );
+        }
+        WasmRuntimeHelper::IntNeg => {
+            const OPERAND: u32 = 0;
+            const RESULT: u32 = 1;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::I64Const(0),
+                    Instruction::LocalGet(OPERAND),
+                    Instruction::I64Sub,
+                    Instruction::LocalSet(RESULT),
+                ],
+            );
+            emit_int_range_result(&mut function, status_global, RESULT);
+        }
+        WasmRuntimeHelper::FloatAdd
+        | WasmRuntimeHelper::FloatSub
+        | WasmRuntimeHelper::FloatMul
+        | WasmRuntimeHelper::FloatDiv => {
+            const LHS: u32 = 0;
+            const RHS: u32 = 1;
+            const RESULT: u32 = 2;
+
+            if helper == WasmRuntimeHelper::FloatDiv {
+                emit_float_zero_divisor_check(&mut function, status_global, RHS);
+            }
+            let operation = match helper {
+                WasmRuntimeHelper::FloatAdd => Instruction::F64Add,
+                WasmRuntimeHelper::FloatSub => Instruction::F64Sub,
+                WasmRuntimeHelper::FloatMul => Instruction::F64Mul,
+                _ => Instruction::F64Div,
+            };
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(LHS),
+                    Instruction::LocalGet(RHS),
+                    operation,
+                    Instruction::LocalSet(RESULT),
+                ],
+            );
+            emit_float_finite_result(
+                &mut function,
+                status_global,
+                RESULT,
+                BuiltinErrorCode::FloatNonFinite,
+            );
+        }
+        WasmRuntimeHelper::FloatMod => {
+            const Y: u32 = 1;
+            const RESULT: u32 = 2;
+
+            emit_float_zero_divisor_check(&mut function, status_global, Y);
+            emit_fmod(&mut function);
+            emit_float_finite_result(
+                &mut function,
+                status_global,
+                RESULT,
+                BuiltinErrorCode::FloatNonFinite,
+            );
+        }
+        WasmRuntimeHelper::FloatNeg => {
+            const OPERAND: u32 = 0;
+            const RESULT: u32 = 1;
+
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(OPERAND),
+                    Instruction::F64Neg,
+                    Instruction::LocalSet(RESULT),
+                ],
+            );
+            emit_float_finite_result(
+                &mut function,
+                status_global,
+                RESULT,
+                BuiltinErrorCode::FloatNonFinite,
+            );
+        }
+        WasmRuntimeHelper::FloatCheckFinite | WasmRuntimeHelper::FloatValidate => {
+            const OPERAND: u32 = 0;
+
+            let code = if helper == WasmRuntimeHelper::FloatValidate {
+                BuiltinErrorCode::FloatBoundaryNonFinite
+            } else {
+                BuiltinErrorCode::FloatNonFinite
+            };
+            emit_float_finite_result(&mut function, status_global, OPERAND, code);
+        }
+        _ => unreachable!("non-numeric helpers are rejected above"),
+    }
+
+    function.instruction(&Instruction::End);
+    Ok(function)
+}
+
+/// Record `code` in the status global and return `zero` from the helper.
+fn emit_failure(
+    function: &mut Function,
+    status_global: u32,
+    code: BuiltinErrorCode,
+    zero: Instruction<'_>,
+) {
+    emit(
+        function,
+        &[
+            Instruction::I32Const(code.as_i32()),
+            Instruction::GlobalSet(status_global),
+            zero,
+            Instruction::Return,
+        ],
+    );
+}
+
+/// Push 1 when the i64 `local` lies outside the Beanstalk `Int` range.
+fn emit_int_out_of_range(function: &mut Function, local: u32) {
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(local),
+            Instruction::I64Const(INT_MIN),
+            Instruction::I64LtS,
+            Instruction::LocalGet(local),
+            Instruction::I64Const(INT_MAX),
+            Instruction::I64GtS,
+            Instruction::I32Or,
+        ],
+    );
+}
+
+/// Finish an Int helper: report `IntOverflow` or leave `result` on the stack.
+fn emit_int_range_result(function: &mut Function, status_global: u32, result: u32) {
+    emit_int_out_of_range(function, result);
+    function.instruction(&Instruction::If(BlockType::Empty));
+    emit_failure(
+        function,
+        status_global,
+        BuiltinErrorCode::IntOverflow,
+        Instruction::I64Const(0),
+    );
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::I32Const(NUMERIC_STATUS_OK),
+            Instruction::GlobalSet(status_global),
+            Instruction::LocalGet(result),
+        ],
+    );
+}
+
+/// Finish a Float helper: report `code` for NaN or infinity, or leave `result` on the stack.
+fn emit_float_finite_result(
+    function: &mut Function,
+    status_global: u32,
+    result: u32,
+    code: BuiltinErrorCode,
+) {
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(result),
+            Instruction::F64Abs,
+            Instruction::F64Const(f64::INFINITY.into()),
+            Instruction::F64Lt,
+            Instruction::I32Eqz,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_failure(
+        function,
+        status_global,
+        code,
+        Instruction::F64Const(0.0.into()),
+    );
+    emit(
+        function,
+        &[
+            Instruction::End,
+            Instruction::I32Const(NUMERIC_STATUS_OK),
+            Instruction::GlobalSet(status_global),
+            Instruction::LocalGet(result),
+        ],
+    );
+}
+
+/// Report `DivideByZero` when the f64 `divisor` is +0 or -0, like the JS `b === 0` check.
+fn emit_float_zero_divisor_check(function: &mut Function, status_global: u32, divisor: u32) {
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(divisor),
+            Instruction::F64Const(0.0.into()),
+            Instruction::F64Eq,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit_failure(
+        function,
+        status_global,
+        BuiltinErrorCode::DivideByZero,
+        Instruction::F64Const(0.0.into()),
+    );
+    function.instruction(&Instruction::End);
+}
+
+/// Store the exact IEEE remainder `fmod(x, y)` (JS `x % y`) into local 2 of `rt_float_mod`.
+///
+/// Port of musl's bitwise `fmod`. Operands are finite Beanstalk Floats and `y` is non-zero,
+/// so the NaN and infinity entry cases are not needed.
+fn emit_fmod(function: &mut Function) {
+    const X: u32 = 0;
+    const Y: u32 = 1;
+    const RESULT: u32 = 2;
+    const X_BITS: u32 = 3;
+    const Y_BITS: u32 = 4;
+    const DIFFERENCE: u32 = 5;
+    const X_EXPONENT: u32 = 6;
+    const Y_EXPONENT: u32 = 7;
+    const X_SIGN: u32 = 8;
+
+    // `0 * x` keeps the sign of x for exact-multiple results.
+    let signed_zero = [
+        Instruction::F64Const(0.0.into()),
+        Instruction::LocalGet(X),
+        Instruction::F64Mul,
+        Instruction::LocalSet(RESULT),
+    ];
+
+    // Outer block: every early result branches here with RESULT set.
+    emit(
+        function,
+        &[
+            Instruction::Block(BlockType::Empty),
+            Instruction::LocalGet(X),
+            Instruction::I64ReinterpretF64,
+            Instruction::LocalSet(X_BITS),
+            Instruction::LocalGet(Y),
+            Instruction::I64ReinterpretF64,
+            Instruction::LocalSet(Y_BITS),
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(52),
+            Instruction::I64ShrU,
+            Instruction::I32WrapI64,
+            Instruction::I32Const(F64_EXPONENT_MASK),
+            Instruction::I32And,
+            Instruction::LocalSet(X_EXPONENT),
+            Instruction::LocalGet(Y_BITS),
+            Instruction::I64Const(52),
+            Instruction::I64ShrU,
+            Instruction::I32WrapI64,
+            Instruction::I32Const(F64_EXPONENT_MASK),
+            Instruction::I32And,
+            Instruction::LocalSet(Y_EXPONENT),
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(63),
+            Instruction::I64ShrU,
+            Instruction::I32WrapI64,
+            Instruction::LocalSet(X_SIGN),
+            // |x| <= |y|: the result is x, or a signed zero when |x| == |y|.
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(1),
+            Instruction::I64Shl,
+            Instruction::LocalGet(Y_BITS),
+            Instruction::I64Const(1),
+            Instruction::I64Shl,
+            Instruction::I64LeU,
+            Instruction::If(BlockType::Empty),
+            Instruction::F64Const(0.0.into()),
+            Instruction::LocalGet(X),
+            Instruction::F64Mul,
+            Instruction::LocalGet(X),
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(1),
+            Instruction::I64Shl,
+            Instruction::LocalGet(Y_BITS),
+            Instruction::I64Const(1),
+            Instruction::I64Shl,
+            Instruction::I64Eq,
+            Instruction::Select,
+            Instruction::LocalSet(RESULT),
+            Instruction::Br(1),
+            Instruction::End,
+        ],
+    );
+
+    emit_fmod_normalize(function, X_BITS, X_EXPONENT, DIFFERENCE);
+    emit_fmod_normalize(function, Y_BITS, Y_EXPONENT, DIFFERENCE);
+
+    // Long division one exponent step at a time.
+    emit(
+        function,
+        &[
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(X_EXPONENT),
+            Instruction::LocalGet(Y_EXPONENT),
+            Instruction::I32GtS,
+            Instruction::I32Eqz,
+            Instruction::BrIf(1),
+            Instruction::LocalGet(X_BITS),
+            Instruction::LocalGet(Y_BITS),
+            Instruction::I64Sub,
+            Instruction::LocalTee(DIFFERENCE),
+            Instruction::I64Const(0),
+            Instruction::I64GeS,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(DIFFERENCE),
+            Instruction::I64Eqz,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit(function, &signed_zero);
+    emit(
+        function,
+        &[
+            Instruction::Br(4),
+            Instruction::End,
+            Instruction::LocalGet(DIFFERENCE),
+            Instruction::LocalSet(X_BITS),
+            Instruction::End,
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(1),
+            Instruction::I64Shl,
+            Instruction::LocalSet(X_BITS),
+            Instruction::LocalGet(X_EXPONENT),
+            Instruction::I32Const(1),
+            Instruction::I32Sub,
+            Instruction::LocalSet(X_EXPONENT),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+            // Final subtraction step.
+            Instruction::LocalGet(X_BITS),
+            Instruction::LocalGet(Y_BITS),
+            Instruction::I64Sub,
+            Instruction::LocalTee(DIFFERENCE),
+            Instruction::I64Const(0),
+            Instruction::I64GeS,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(DIFFERENCE),
+            Instruction::I64Eqz,
+            Instruction::If(BlockType::Empty),
+        ],
+    );
+    emit(function, &signed_zero);
+    emit(
+        function,
+        &[
+            Instruction::Br(2),
+            Instruction::End,
+            Instruction::LocalGet(DIFFERENCE),
+            Instruction::LocalSet(X_BITS),
+            Instruction::End,
+            // Renormalize so the hidden bit is set again.
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(52),
+            Instruction::I64ShrU,
+            Instruction::I64Eqz,
+            Instruction::I32Eqz,
+            Instruction::BrIf(1),
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(1),
+            Instruction::I64Shl,
+            Instruction::LocalSet(X_BITS),
+            Instruction::LocalGet(X_EXPONENT),
+            Instruction::I32Const(1),
+            Instruction::I32Sub,
+            Instruction::LocalSet(X_EXPONENT),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+            // Rebuild the f64, producing a subnormal when the exponent ran out.
+            Instruction::LocalGet(X_EXPONENT),
+            Instruction::I32Const(0),
+            Instruction::I32GtS,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(X_BITS),
+            Instruction::I64Const(F64_HIDDEN_BIT),
+            Instruction::I64Sub,
+            Instruction::LocalGet(X_EXPONENT),
+            Instruction::I64ExtendI32U,
+            Instruction::I64Const(52),
+            Instruction::I64Shl,
+            Instruction::I64Or,
+            Instruction::LocalSet(X_BITS),
+            Instruction::Else,
+            Instruction::LocalGet(X_BITS),
+            Instruction::I32Const(1),
+            Instruction::LocalGet(X_EXPONENT),
+            Instruction::I32Sub,
+            Instruction::I64ExtendI32U,
+            Instruction::I64ShrU,
+            Instruction::LocalSet(X_BITS),
+            Instruction::End,
+            Instruction::LocalGet(X_BITS),
+            Instruction::LocalGet(X_SIGN),
+            Instruction::I64ExtendI32U,
+            Instruction::I64Const(63),
+            Instruction::I64Shl,
+            Instruction::I64Or,
+            Instruction::F64ReinterpretI64,
+            Instruction::LocalSet(RESULT),
+            Instruction::End,
+        ],
+    );
+}
+
+/// Replace `bits` with its significand (hidden bit included), adjusting `exponent` for
+/// subnormals so the significand is normalized. `scratch` is an i64 local.
+fn emit_fmod_normalize(function: &mut Function, bits: u32, exponent: u32, scratch: u32) {
+    emit(
+        function,
+        &[
+            Instruction::LocalGet(exponent),
+            Instruction::I32Eqz,
+            Instruction::If(BlockType::Empty),
+            Instruction::LocalGet(bits),
+            Instruction::I64Const(12),
+            Instruction::I64Shl,
+            Instruction::LocalSet(scratch),
+            Instruction::Block(BlockType::Empty),
+            Instruction::Loop(BlockType::Empty),
+            Instruction::LocalGet(scratch),
+            Instruction::I64Const(0),
+            Instruction::I64LtS,
+            Instruction::BrIf(1),
+            Instruction::LocalGet(exponent),
+            Instruction::I32Const(1),
+            Instruction::I32Sub,
+            Instruction::LocalSet(exponent),
+            Instruction::LocalGet(scratch),
+            Instruction::I64Const(1),
+            Instruction::I64Shl,
+            Instruction::LocalSet(scratch),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::End,
+            Instruction::LocalGet(bits),
+            Instruction::I32Const(1),
+            Instruction::LocalGet(exponent),
+            Instruction::I32Sub,
+            Instruction::I64ExtendI32U,
+            Instruction::I64Shl,
+            Instruction::LocalSet(bits),
+            Instruction::Else,
+            Instruction::LocalGet(bits),
+            Instruction::I64Const(F64_MANTISSA_MASK),
+            Instruction::I64And,
+            Instruction::I64Const(F64_HIDDEN_BIT),
+            Instruction::I64Or,
+            Instruction::LocalSet(bits),
+            Instruction::End,
+        ],
+    );
+}
@@ -6,10 +6,18 @@ use crate::backends::wasm::lir::instructions::WasmLirStmt;
 use crate::backends::wasm::lir::linkage::WasmImportKind;
 use crate::backends::wasm::lir::module::WasmLirModule;
 use crate::backends::wasm::lir::types::{
-    WasmAbiType, WasmImportId, WasmLirFunctionId, WasmLirSignature, WasmStaticDataId,
+    WasmAbiType, WasmImportId, WasmLirFunctionId, WasmLirSignature, WasmRecordTypeId,
+    WasmStaticDataId,
 };
 use crate::backends::wasm::request::WasmBackendRequest;
+use crate::backends::wasm::runtime::casts::{cast_helper, cast_returns_carrier};
+use crate::backends::wasm::runtime::errors::RUNTIME_ERROR_CODES;
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::memory::heap_start;
 use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::backends::wasm::runtime::wasi::WasiAdapter;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
 use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
 use rustc_hash::FxHashMap;
 use std::fmt::Write as _;
@@ -22,6 +30,14 @@ pub(crate) enum DefinedFunctionKey {
     Helper(WasmRuntimeHelper),
 }
 
+/// An emitter-owned constant string, such as a builtin error message.
+#[derive(Debug, Clone)]
+pub(crate) struct WasmRuntimeString {
+    pub text: &'static str,
+    /// Address of the 8-byte finalized `{ptr, len}` header; the UTF-8 bytes follow it.
+    pub handle: u32,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct WasmEmitPlan {
     /// Interned signature table in deterministic insertion order.
@@ -34,6 +50,8 @@ pub(crate) struct WasmEmitPlan {
     pub function_indices: FxHashMap<WasmLirFunctionId, u32>,
     /// Function indices assigned to synthesized helpers.
     pub helper_indices: FxHashMap<WasmRuntimeHelper, u32>,
+    /// Function indices of host imports that synthesized helpers call directly.
+    pub host_function_indices: FxHashMap<WasmHostFunction, u32>,
     /// Canonical order used by function/code section emission.
     pub defined_function_order: Vec<DefinedFunctionKey>,
     /// Type indices for each defined function, aligned with `defined_function_order`.
@@ -42,10 +60,19 @@ pub(crate) struct WasmEmitPlan {
     pub data_offsets: FxHashMap<WasmStaticDataId, u32>,
     /// Static-data segment lengths cached for literal helper calls.
     pub data_lengths: FxHashMap<WasmStaticDataId, u32>,
-    /// First aligned address available for dynamic heap allocation.
+    /// Allocation size of each struct/choice record type.
+    pub record_sizes: FxHashMap<WasmRecordTypeId, u32>,
+    /// Emitter-owned strings placed after static data, in address order.
+    pub runtime_strings: Vec<WasmRuntimeString>,
+    /// First aligned address after static data: the allocator's free-list table, followed by
+    /// the first block at `heap_start(heap_base)` (see `runtime::memory`).
     pub heap_base: u32,
     /// `heap_top` mutable global index when runtime helpers are emitted.
     pub heap_top_global_index: Option<u32>,
+    /// `numeric_status` mutable global index when checked numeric helpers are emitted.
+    pub numeric_status_global_index: Option<u32>,
+    /// `live_allocations` mutable global index when runtime helpers are emitted.
+    pub live_allocations_global_index: Option<u32>,
 }
 
 pub(crate) fn build_emit_plan(
@@ -58,6 +85,7 @@ pub(crate) fn build_emit_plan(
     let mut type_entries = Vec::new();
     let mut type_index_by_signature = FxHashMap::default();
     let mut import_function_indices = FxHashMap::default();
+    let mut host_function_indices = FxHashMap::default();
     let mut function_indices = FxHashMap::default();
     let mut helper_indices = FxHashMap::default();
     let mut defined_function_order = Vec::new();
@@ -74,6 +102,11 @@ pub(crate) fn build_emit_plan(
 
         intern_signature(signature, &mut type_entries, &mut type_index_by_signature);
         import_function_indices.insert(import.id, next_function_index);
+        if let Some(host_function) =
+            WasmHostFunction::from_import_name(&import.module_name, &import.item_name)
+        {
+            host_function_indices.insert(host_function, next_function_index);
+        }
         next_function_index += 1;
     }
 
@@ -94,12 +127,53 @@ pub(crate) fn build_emit_plan(
         next_function_index += 1;
     }
 
-    let should_emit_helpers =
-        module_uses_runtime_helpers(module) || helper_exports_requested(request);
+    let used_casts = module_cast_policies(module);
+    let should_emit_map_helpers = module_uses_map_helpers(module);
+    let should_emit_float_format_helpers = module_uses_float_format_helpers(module)
+        || used_casts.contains(&BuiltinCastPolicyId::FloatToString);
+    let should_emit_numeric_helpers =
+        should_emit_float_format_helpers || module_uses_numeric_helpers(module);
+    let should_emit_fallible_helpers = should_emit_map_helpers
+        || should_emit_numeric_helpers
+        || module_uses_fallible_helpers(module, &used_casts);
+    let cast_helpers = cast_helper_emit_order()
+        .iter()
+        .copied()
+        .filter(|helper| cast_helper_is_used(*helper, &used_casts))
+        .collect::<Vec<_>>();
+    let used_wasi_adapters = module_wasi_adapters(module);
+    let wasi_helpers = wasi_helper_emit_order()
+        .iter()
+        .copied()
+        .filter(|helper| wasi_helper_is_used(*helper, &used_wasi_adapters))
+        .collect::<Vec<_>>();
+    let should_emit_helpers = should_emit_fallible_helpers
+        || !cast_helpers.is_empty()
+        || !wasi_helpers.is_empty()
+        || module_uses_runtime_helpers(module)
+        || helper_exports_requested(request);
     if should_emit_helpers {
         // WHAT: helper ordering is fixed and independent of usage count.
         // WHY: stable helper indices simplify wrapper exports and future host glue assumptions.
-        for helper in helper_emit_order() {
+        // Optional groups are appended after the base set, so modules without maps, checked
+        // numerics or casts keep their indices. The carrier and `Error` constructors are shared
+        // by every group that can fail.
+        let mut helpers = helper_emit_order().to_vec();
+        if should_emit_fallible_helpers {
+            helpers.extend_from_slice(fallible_helper_emit_order());
+        }
+        if should_emit_map_helpers {
+            helpers.extend_from_slice(map_helper_emit_order());
+        }
+        if should_emit_numeric_helpers {
+            helpers.extend_from_slice(numeric_helper_emit_order());
+        }
+        if should_emit_float_format_helpers {
+            helpers.extend_from_slice(float_format_helper_emit_order());
+        }
+        helpers.extend(cast_helpers);
+        helpers.extend(wasi_helpers.iter().copied());
+        for helper in helpers {
             let signature = helper_signature(helper);
             let type_index =
                 intern_signature(&signature, &mut type_entries, &mut type_index_by_signature);
@@ -113,9 +187,38 @@ pub(crate) fn build_emit_plan(
     let StaticDataLayoutResult {
         data_offsets,
         data_lengths,
-        heap_base,
+        static_data_end,
     } = plan_static_data_layout(module)?;
+
+    // WHAT: error messages are needed by `rt_error_from_code`, `true`/`false` by `Bool -> String`
+    // and the line terminator by `rt_wasi_write_line`.
+    let mut runtime_string_texts = Vec::new();
+    if should_emit_fallible_helpers {
+        runtime_string_texts.push(BuiltinErrorCode::UnknownOrUnassigned.default_message());
+        runtime_string_texts.extend(
+            RUNTIME_ERROR_CODES
+                .iter()
+                .map(|code| code.default_message()),
+        );
+    }
+    if used_casts.contains(&BuiltinCastPolicyId::BoolToString) {
+        runtime_string_texts.extend(["true", "false"]);
+    }
+    if wasi_helpers.contains(&WasmRuntimeHelper::WasiWriteLine) {
+        runtime_string_texts.push("\n");
+    }
+    let (runtime_strings, heap_base) =
+        plan_runtime_string_layout(&runtime_string_texts, static_data_end)?;
     let heap_top_global_index = should_emit_helpers.then_some(0);
+    let numeric_status_global_index = should_emit_numeric_helpers.then_some(1);
+    // The allocator counter follows the optional status global so existing indices stay put.
+    let live_allocations_global_index =
+        should_emit_helpers.then_some(if should_emit_numeric_helpers { 2 } else { 1 });
+    let record_sizes = module
+        .record_types
+        .iter()
+        .map(|record| (record.id, record.size))
+        .collect();
 
     Ok(WasmEmitPlan {
         type_entries,
@@ -123,12 +226,17 @@ pub(crate) fn build_emit_plan(
         import_function_indices,
         function_indices,
         helper_indices,
+        host_function_indices,
         defined_function_order,
         defined_function_type_indices,
         data_offsets,
         data_lengths,
+        record_sizes,
+        runtime_strings,
         heap_base,
         heap_top_global_index,
+        numeric_status_global_index,
+        live_allocations_global_index,
     })
 }
 
@@ -153,8 +261,135 @@ pub(crate) fn helper_emit_order() -> [WasmRuntimeHelper; 14] {
     ]
 }
 
+pub(crate) fn fallible_helper_emit_order() -> &'static [WasmRuntimeHelper; 4] {
+    // WHAT: canonical declaration order for the carrier and builtin `Error` constructors.
+    // WHY: emitted for any module that can produce an error carrier, before the groups using them.
+    &[
+        WasmRuntimeHelper::FallibleNew,
+        WasmRuntimeHelper::ErrorNew,
+        WasmRuntimeHelper::ErrorFromCode,
+        WasmRuntimeHelper::FallibleError,
+    ]
+}
+
+pub(crate) fn map_helper_emit_order() -> &'static [WasmRuntimeHelper; 11] {
+    // WHAT: canonical declaration order for map helpers.
+    // WHY: these are only emitted for modules with map statements, after the fallible group.
+    &[
+        WasmRuntimeHelper::MapHashKey,
+        WasmRuntimeHelper::MapKeysEqual,
+        WasmRuntimeHelper::MapFind,
+        WasmRuntimeHelper::MapRebuild,
+        WasmRuntimeHelper::MapNew,
+        WasmRuntimeHelper::MapGet,
+        WasmRuntimeHelper::MapContains,
+        WasmRuntimeHelper::MapSet,
+        WasmRuntimeHelper::MapRemove,
+        WasmRuntimeHelper::MapClear,
+        WasmRuntimeHelper::MapLength,
+    ]
+}
+
+pub(crate) fn numeric_helper_emit_order() -> &'static [WasmRuntimeHelper; 16] {
+    // WHAT: canonical declaration order for checked numeric helpers.
+    // WHY: emitted for modules with checked arithmetic or Float formatting, after map helpers.
+    &[
+        WasmRuntimeHelper::NumericCarrier,
+        WasmRuntimeHelper::IntAdd,
+        WasmRuntimeHelper::IntSub,
+        WasmRuntimeHelper::IntMul,
+        WasmRuntimeHelper::IntDiv,
+        WasmRuntimeHelper::IntMod,
+        WasmRuntimeHelper::IntPow,
+        WasmRuntimeHelper::IntNeg,
+        WasmRuntimeHelper::FloatAdd,
+        WasmRuntimeHelper::FloatSub,
+        WasmRuntimeHelper::FloatMul,
+        WasmRuntimeHelper::FloatDiv,
+        WasmRuntimeHelper::FloatMod,
+        WasmRuntimeHelper::FloatNeg,
+        WasmRuntimeHelper::FloatCheckFinite,
+        WasmRuntimeHelper::FloatValidate,
+    ]
+}
+
+pub(crate) fn float_format_helper_emit_order() -> &'static [WasmRuntimeHelper; 8] {
+    // WHAT: canonical declaration order for the Float formatter and its bignum helpers.
+    // WHY: the formatter is the largest helper, so only modules that format Floats pay for it.
+    &[
+        WasmRuntimeHelper::BignumSet,
+        WasmRuntimeHelper::BignumMulSmall,
+        WasmRuntimeHelper::BignumMulPow10,
+        WasmRuntimeHelper::BignumShl,
+        WasmRuntimeHelper::BignumCompare,
+        WasmRuntimeHelper::BignumAdd,
+        WasmRuntimeHelper::BignumSub,
+        WasmRuntimeHelper::FloatFormat,
+    ]
+}
+
+pub(crate) fn cast_helper_emit_order() -> &'static [WasmRuntimeHelper; 8] {
+    // WHAT: canonical declaration order for builtin cast helpers.
+    // WHY: each helper is emitted only when a reachable cast calls it, mirroring the JS backend's
+    // demand-driven cast prelude, so it is appended last.
+    &[
+        WasmRuntimeHelper::StringWhitespaceLen,
+        WasmRuntimeHelper::CastCharToString,
+        WasmRuntimeHelper::CastFloatToInt,
+        WasmRuntimeHelper::CastIntToChar,
+        WasmRuntimeHelper::CastStringToInt,
+        WasmRuntimeHelper::CastStringToFloat,
+        WasmRuntimeHelper::CastStringToBool,
+        WasmRuntimeHelper::CastStringToChar,
+    ]
+}
+
+fn cast_helper_is_used(helper: WasmRuntimeHelper, used_casts: &[BuiltinCastPolicyId]) -> bool {
+    // `rt_string_whitespace_len` only serves the `String -> Bool` trim.
+    let helper = match helper {
+        WasmRuntimeHelper::StringWhitespaceLen => WasmRuntimeHelper::CastStringToBool,
+        helper => helper,
+    };
+    used_casts
+        .iter()
+        .any(|policy| cast_helper(*policy) == Some(helper))
+}
+
+pub(crate) fn wasi_helper_emit_order() -> &'static [WasmRuntimeHelper; 6] {
+    // WHAT: canonical declaration order for WASI command helpers.
+    // WHY: only WASI command modules reach these, so they are appended after every other group.
+    &[
+        WasmRuntimeHelper::WasiWriteAll,
+        WasmRuntimeHelper::WasiWriteLine,
+        WasmRuntimeHelper::WasiReadLine,
+        WasmRuntimeHelper::WasiInputUpdate,
+        WasmRuntimeHelper::WasiInputKeyMatches,
+        WasmRuntimeHelper::WasiClockMillis,
+    ]
+}
+
+fn wasi_helper_is_used(helper: WasmRuntimeHelper, used_adapters: &[WasiAdapter]) -> bool {
+    used_adapters.iter().any(|adapter| match helper {
+        // `rt_wasi_write_all` only serves `rt_wasi_write_line`.
+        WasmRuntimeHelper::WasiWriteAll | WasmRuntimeHelper::WasiWriteLine => matches!(
+            adapter,
+            WasiAdapter::WriteLine { .. } | WasiAdapter::WriteFragmentLine { .. }
+        ),
+        // `rt_wasi_read_line` only serves `rt_wasi_input_update`.
+        WasmRuntimeHelper::WasiReadLine | WasmRuntimeHelper::WasiInputUpdate => {
+            *adapter == WasiAdapter::InputUpdate
+        }
+        WasmRuntimeHelper::WasiInputKeyMatches => matches!(adapter, WasiAdapter::InputKey(_)),
+        WasmRuntimeHelper::WasiClockMillis => matches!(
+            adapter,
+            WasiAdapter::ClockNow(_) | WasiAdapter::ElapsedSince
+        ),
+        _ => false,
+    })
+}
+
 pub(crate) fn helper_signature(helper: WasmRuntimeHelper) -> WasmLirSignature {
-    use WasmAbiType::{Handle, I32, I64};
+    use WasmAbiType::{F64, Handle, I32, I64};
 
     match helper {
         WasmRuntimeHelper::Alloc => WasmLirSignature {
@@ -213,6 +448,153 @@ pub(crate) fn helper_signature(helper: WasmRuntimeHelper) -> WasmLirSignature {
             params: vec![Handle],
             results: vec![],
         },
+        WasmRuntimeHelper::MapNew => WasmLirSignature {
+            params: vec![I32],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::MapGet | WasmRuntimeHelper::MapRemove => WasmLirSignature {
+            params: vec![Handle, I64],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::MapContains
+        | WasmRuntimeHelper::MapHashKey
+        | WasmRuntimeHelper::MapFind => WasmLirSignature {
+            params: vec![Handle, I64],
+            results: vec![I32],
+        },
+        WasmRuntimeHelper::MapSet => WasmLirSignature {
+            params: vec![Handle, I64, I64],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::MapClear => WasmLirSignature {
+            params: vec![Handle],
+            results: vec![],
+        },
+        WasmRuntimeHelper::MapLength => WasmLirSignature {
+            params: vec![Handle],
+            results: vec![I64],
+        },
+        WasmRuntimeHelper::MapKeysEqual => WasmLirSignature {
+            params: vec![Handle, I64, I64],
+            results: vec![I32],
+        },
+        WasmRuntimeHelper::MapRebuild => WasmLirSignature {
+            params: vec![Handle, I32],
+            results: vec![],
+        },
+        WasmRuntimeHelper::FallibleNew => WasmLirSignature {
+            params: vec![I32, I64],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::ErrorNew => WasmLirSignature {
+            params: vec![Handle, I32],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::ErrorFromCode | WasmRuntimeHelper::FallibleError => WasmLirSignature {
+            params: vec![I32],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::NumericCarrier => WasmLirSignature {
+            params: vec![I64],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::IntAdd
+        | WasmRuntimeHelper::IntSub
+        | WasmRuntimeHelper::IntMul
+        | WasmRuntimeHelper::IntDiv
+        | WasmRuntimeHelper::IntMod
+        | WasmRuntimeHelper::IntPow => WasmLirSignature {
+            params: vec![I64, I64],
+            results: vec![I64],
+        },
+        WasmRuntimeHelper::IntNeg => WasmLirSignature {
+            params: vec![I64],
+            results: vec![I64],
+        },
+        WasmRuntimeHelper::FloatAdd
+        | WasmRuntimeHelper::FloatSub
+        | WasmRuntimeHelper::FloatMul
+        | WasmRuntimeHelper::FloatDiv
+        | WasmRuntimeHelper::FloatMod => WasmLirSignature {
+            params: vec![F64, F64],
+            results: vec![F64],
+        },
+        WasmRuntimeHelper::FloatNeg
+        | WasmRuntimeHelper::FloatCheckFinite
+        | WasmRuntimeHelper::FloatValidate => WasmLirSignature {
+            params: vec![F64],
+            results: vec![F64],
+        },
+        WasmRuntimeHelper::FloatFormat => WasmLirSignature {
+            params: vec![F64],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::BignumSet => WasmLirSignature {
+            params: vec![I32, I64],
+            results: vec![],
+        },
+        WasmRuntimeHelper::BignumMulSmall
+        | WasmRuntimeHelper::BignumMulPow10
+        | WasmRuntimeHelper::BignumShl
+        | WasmRuntimeHelper::BignumSub => WasmLirSignature {
+            params: vec![I32, I32],
+            results: vec![],
+        },
+        WasmRuntimeHelper::BignumCompare => WasmLirSignature {
+            params: vec![I32, I32],
+            results: vec![I32],
+        },
+        WasmRuntimeHelper::BignumAdd => WasmLirSignature {
+            params: vec![I32, I32, I32],
+            results: vec![],
+        },
+        WasmRuntimeHelper::CastCharToString => WasmLirSignature {
+            params: vec![I32],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::CastFloatToInt => WasmLirSignature {
+            params: vec![F64],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::CastIntToChar => WasmLirSignature {
+            params: vec![I64],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::CastStringToInt
+        | WasmRuntimeHelper::CastStringToFloat
+        | WasmRuntimeHelper::CastStringToBool
+        | WasmRuntimeHelper::CastStringToChar => WasmLirSignature {
+            params: vec![Handle],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::StringWhitespaceLen => WasmLirSignature {
+            params: vec![I32],
+            results: vec![I32],
+        },
+        WasmRuntimeHelper::WasiWriteAll => WasmLirSignature {
+            params: vec![I32, I32, I32],
+            results: vec![],
+        },
+        WasmRuntimeHelper::WasiWriteLine => WasmLirSignature {
+            params: vec![Handle, I32],
+            results: vec![],
+        },
+        WasmRuntimeHelper::WasiReadLine => WasmLirSignature {
+            params: vec![],
+            results: vec![Handle],
+        },
+        WasmRuntimeHelper::WasiInputUpdate => WasmLirSignature {
+            params: vec![Handle],
+            results: vec![],
+        },
+        WasmRuntimeHelper::WasiInputKeyMatches => WasmLirSignature {
+            params: vec![Handle, Handle, I32],
+            results: vec![I32],
+        },
+        WasmRuntimeHelper::WasiClockMillis => WasmLirSignature {
+            params: vec![I32],
+            results: vec![F64],
+        },
     }
 }
 
@@ -226,6 +608,8 @@ pub(crate) fn helper_exports_requested(request: &WasmBackendRequest) -> bool {
         || helpers.export_vec_len
         || helpers.export_vec_get
         || helpers.export_release
+        || helpers.export_str_byte
+        || helpers.export_live_allocations
 }
 
 pub(crate) fn helper_name(helper: WasmRuntimeHelper) -> &'static str {
@@ -244,6 +628,59 @@ pub(crate) fn helper_name(helper: WasmRuntimeHelper) -> &'static str {
         WasmRuntimeHelper::VecGet => "rt_vec_get",
         WasmRuntimeHelper::Release => "rt_release",
         WasmRuntimeHelper::DropIfOwned => "rt_drop_if_owned",
+        WasmRuntimeHelper::MapNew => "rt_map_new",
+        WasmRuntimeHelper::MapGet => "rt_map_get",
+        WasmRuntimeHelper::MapContains => "rt_map_contains",
+        WasmRuntimeHelper::MapSet => "rt_map_set",
+        WasmRuntimeHelper::MapRemove => "rt_map_remove",
+        WasmRuntimeHelper::MapClear => "rt_map_clear",
+        WasmRuntimeHelper::MapLength => "rt_map_length",
+        WasmRuntimeHelper::MapHashKey => "rt_map_hash_key",
+        WasmRuntimeHelper::MapKeysEqual => "rt_map_keys_equal",
+        WasmRuntimeHelper::MapFind => "rt_map_find",
+        WasmRuntimeHelper::MapRebuild => "rt_map_rebuild",
+        WasmRuntimeHelper::FallibleNew => "rt_fallible_new",
+        WasmRuntimeHelper::ErrorNew => "rt_error_new",
+        WasmRuntimeHelper::ErrorFromCode => "rt_error_from_code",
+        WasmRuntimeHelper::FallibleError => "rt_fallible_error",
+        WasmRuntimeHelper::NumericCarrier => "rt_numeric_carrier",
+        WasmRuntimeHelper::IntAdd => "rt_int_add",
+        WasmRuntimeHelper::IntSub => "rt_int_sub",
+        WasmRuntimeHelper::IntMul => "rt_int_mul",
+        WasmRuntimeHelper::IntDiv => "rt_int_div",
+        WasmRuntimeHelper::IntMod => "rt_int_mod",
+        WasmRuntimeHelper::IntPow => "rt_int_pow",
+        WasmRuntimeHelper::IntNeg => "rt_int_neg",
+        WasmRuntimeHelper::FloatAdd => "rt_float_add",
+        WasmRuntimeHelper::FloatSub => "rt_float_sub",
+        WasmRuntimeHelper::FloatMul => "rt_float_mul",
+        WasmRuntimeHelper::FloatDiv => "rt_float_div",
+        WasmRuntimeHelper::FloatMod => "rt_float_mod",
+        WasmRuntimeHelper::FloatNeg => "rt_float_neg",
+        WasmRuntimeHelper::FloatCheckFinite => "rt_float_check_finite",
+        WasmRuntimeHelper::FloatValidate => "rt_float_validate",
+        WasmRuntimeHelper::FloatFormat => "rt_float_format",
+        WasmRuntimeHelper::BignumSet => "rt_bignum_set",
+        WasmRuntimeHelper::BignumMulSmall => "rt_bignum_mul_small",
+        WasmRuntimeHelper::BignumMulPow10 => "rt_bignum_mul_pow10",
+        WasmRuntimeHelper::BignumShl => "rt_bignum_shl",
+        WasmRuntimeHelper::BignumCompare => "rt_bignum_compare",
+        WasmRuntimeHelper::BignumAdd => "rt_bignum_add",
+        WasmRuntimeHelper::BignumSub => "rt_bignum_sub",
+        WasmRuntimeHelper::CastCharToString => "rt_cast_char_to_string",
+        WasmRuntimeHelper::CastFloatToInt => "rt_cast_float_to_int",
+        WasmRuntimeHelper::CastIntToChar => "rt_cast_int_to_char",
+        WasmRuntimeHelper::CastStringToInt => "rt_cast_string_to_int",
+        WasmRuntimeHelper::CastStringToFloat => "rt_cast_string_to_float",
+        WasmRuntimeHelper::CastStringToBool => "rt_cast_string_to_bool",
+        WasmRuntimeHelper::CastStringToChar => "rt_cast_string_to_char",
+        WasmRuntimeHelper::StringWhitespaceLen => "rt_string_whitespace_len",
+        WasmRuntimeHelper::WasiWriteAll => "rt_wasi_write_all",
+        WasmRuntimeHelper::WasiWriteLine => "rt_wasi_write_line",
+        WasmRuntimeHelper::WasiReadLine => "rt_wasi_read_line",
+        WasmRuntimeHelper::WasiInputUpdate => "rt_wasi_input_update",
+        WasmRuntimeHelper::WasiInputKeyMatches => "rt_wasi_input_key_matches",
+        WasmRuntimeHelper::WasiClockMillis => "rt_wasi_clock_millis",
     }
 }
 
@@ -258,11 +695,17 @@ pub(crate) fn plan_sections_text(module: &WasmLirModule, plan: &WasmEmitPlan) ->
         out,
         "  global: {}",
         usize::from(plan.heap_top_global_index.is_some())
+            + usize::from(plan.numeric_status_global_index.is_some())
+            + usize::from(plan.live_allocations_global_index.is_some())
     );
     // Includes only LIR-declared exports. Helper exports are controlled by request policy.
     let _ = writeln!(out, "  export: {}", module.exports.len());
     let _ = writeln!(out, "  code: {}", plan.defined_function_order.len());
-    let _ = writeln!(out, "  data: {}", module.static_data.len());
+    let _ = writeln!(
+        out,
+        "  data: {}",
+        module.static_data.len() + plan.runtime_strings.len()
+    );
     out
 }
 
@@ -319,6 +762,12 @@ pub(crate) fn plan_indices_text(
     if let Some(global_index) = plan.heap_top_global_index {
         let _ = writeln!(out, "  global[{global_index}] heap_top");
     }
+    if let Some(global_index) = plan.numeric_status_global_index {
+        let _ = writeln!(out, "  global[{global_index}] numeric_status");
+    }
+    if let Some(global_index) = plan.live_allocations_global_index {
+        let _ = writeln!(out, "  global[{global_index}] live_allocations");
+    }
 
     out
 }
@@ -349,7 +798,17 @@ pub(crate) fn plan_data_layout_text(module: &WasmLirModule, plan: &WasmEmitPlan)
             );
         }
     }
+    for runtime_string in &plan.runtime_strings {
+        let _ = writeln!(
+            out,
+            "  runtime_string offset={} len={} text={:?}",
+            runtime_string.handle,
+            runtime_string.text.len(),
+            runtime_string.text
+        );
+    }
     let _ = writeln!(out, "  heap_base={}", plan.heap_base);
+    let _ = writeln!(out, "  heap_start={}", heap_start(plan.heap_base));
     out
 }
 
@@ -369,6 +828,11 @@ fn module_uses_runtime_helpers(module: &WasmLirModule) -> bool {
                         | WasmLirStmt::VecNew { .. }
                         | WasmLirStmt::VecPushHandle { .. }
                         | WasmLirStmt::DropIfOwned { .. }
+                        | WasmLirStmt::RecordNew { .. }
+                        | WasmLirStmt::Cast {
+                            policy: BuiltinCastPolicyId::IntToString,
+                            ..
+                        }
                 ) {
                     return true;
                 }
@@ -379,10 +843,114 @@ fn module_uses_runtime_helpers(module: &WasmLirModule) -> bool {
     false
 }
 
+fn module_cast_policies(module: &WasmLirModule) -> Vec<BuiltinCastPolicyId> {
+    // WHAT: collect the distinct builtin cast policies in first-use order.
+    // WHY: cast helpers and their constant strings are emitted only for policies the module uses.
+    let mut policies = Vec::new();
+    for function in &module.functions {
+        for block in &function.blocks {
+            for statement in &block.statements {
+                if let WasmLirStmt::Cast { policy, .. } = statement
+                    && !policies.contains(policy)
+                {
+                    policies.push(*policy);
+                }
+            }
+        }
+    }
+    policies
+}
+
+fn module_wasi_adapters(module: &WasmLirModule) -> Vec<WasiAdapter> {
+    // WHAT: collect the distinct WASI adapters in first-use order.
+    let mut adapters = Vec::new();
+    for function in &module.functions {
+        for block in &function.blocks {
+            for statement in &block.statements {
+                if let WasmLirStmt::WasiCall { adapter, .. } = statement
+                    && !adapters.contains(adapter)
+                {
+                    adapters.push(*adapter);
+                }
+            }
+        }
+    }
+    adapters
+}
+
+fn module_uses_fallible_helpers(
+    module: &WasmLirModule,
+    used_casts: &[BuiltinCastPolicyId],
+) -> bool {
+    // WHAT: scan for statements that build carriers or `Error` records outside maps and numerics.
+    if used_casts.iter().any(|policy| {
+        cast_returns_carrier(*policy) || *policy == BuiltinCastPolicyId::StringToError
+    }) {
+        return true;
+    }
+
+    module.functions.iter().any(|function| {
+        function.blocks.iter().any(|block| {
+            block.statements.iter().any(|statement| {
+                matches!(
+                    statement,
+                    WasmLirStmt::FallibleWrapSuccess { .. } | WasmLirStmt::FallibleWrapError { .. }
+                )
+            })
+        })
+    })
+}
+
+fn module_uses_map_helpers(module: &WasmLirModule) -> bool {
+    // WHAT: scan for map statements, which need the map and fallible-carrier helpers.
+    // WHY: map helpers are large, so only modules that build or query maps pay for them.
+    module.functions.iter().any(|function| {
+        function.blocks.iter().any(|block| {
+            block.statements.iter().any(|statement| {
+                matches!(
+                    statement,
+                    WasmLirStmt::MapNew { .. }
+                        | WasmLirStmt::MapGet { .. }
+                        | WasmLirStmt::MapContains { .. }
+                        | WasmLirStmt::MapSet { .. }
+                        | WasmLirStmt::MapRemove { .. }
+                        | WasmLirStmt::MapClear { .. }
+                        | WasmLirStmt::MapLength { .. }
+                )
+            })
+        })
+    })
+}
+
+fn module_uses_numeric_helpers(module: &WasmLirModule) -> bool {
+    // WHAT: scan for checked arithmetic, which needs the numeric helpers and status global.
+    module.functions.iter().any(|function| {
+        function.blocks.iter().any(|block| {
+            block
+                .statements
+                .iter()
+                .any(|statement| matches!(statement, WasmLirStmt::CheckedNumeric { .. }))
+        })
+    })
+}
+
+fn module_uses_float_format_helpers(module: &WasmLirModule) -> bool {
+    // WHAT: scan for Float formatting, which needs the bignum formatter on top of the
+    // numeric helpers.
+    module.functions.iter().any(|function| {
+        function.blocks.iter().any(|block| {
+            block
+                .statements
+                .iter()
+                .any(|statement| matches!(statement, WasmLirStmt::FormatFloat { .. }))
+        })
+    })
+}
+
 struct StaticDataLayoutResult {
     data_offsets: FxHashMap<WasmStaticDataId, u32>,
     data_lengths: FxHashMap<WasmStaticDataId, u32>,
-    heap_base: u32,
+    static_data_end: u32,
 }
 
 fn plan_static_data_layout(
@@ -415,10 +983,44 @@ fn plan_static_data_layout(
     Ok(StaticDataLayoutResult {
         data_offsets,
         data_lengths,
-        heap_base: align_to(cursor, 8),
+        static_data_end: cursor,
     })
 }
 
+fn plan_runtime_string_layout(
+    texts: &[&'static str],
+    static_data_end: u32,
+) -> Result<(Vec<WasmRuntimeString>, u32), CompilerError> {
+    // WHAT: place each emitter-owned string as a finalized string header followed by its bytes.
+    // WHY: the header address is then a ready string handle, so helpers never allocate messages.
+    let mut runtime_strings = Vec::with_capacity(texts.len());
+    let mut cursor = static_data_end;
+    for text in texts {
+        cursor = align_to(cursor, 8);
+        runtime_strings.push(WasmRuntimeString {
+            text,
+            handle: cursor,
+        });
+        cursor = cursor.checked_add(8 + text.len() as u32).ok_or_else(|| {
+            CompilerError::compiler_error("Wasm runtime string layout overflowed u32 address space")
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })?;
+    }
+
+    Ok((runtime_strings, align_to(cursor, 8)))
+}
+
+pub(crate) fn runtime_string_handle(plan: &WasmEmitPlan, text: &str) -> Result<u32, CompilerError> {
+    plan.runtime_strings
+        .iter()
+        .find(|runtime_string| runtime_string.text == text)
+        .map(|runtime_string| runtime_string.handle)
+        .ok_or_else(|| {
+            CompilerError::compiler_error(format!("Wasm emission missing runtime string {text:?}"))
+                .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })
+}
+
 fn intern_signature(
     signature: &WasmLirSignature,
     type_entries: &mut Vec<WasmLirSignature>,
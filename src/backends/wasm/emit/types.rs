@@ -3,14 +3,15 @@
 use crate::backends::error_types::BackendErrorType;
 use crate::backends::wasm::lir::types::WasmAbiType;
 use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
-use wasm_encoder::ValType;
+use wasm_encoder::{RefType, ValType};
 
 pub(crate) fn abi_to_val_type(abi: WasmAbiType) -> Result<ValType, CompilerError> {
     // WHAT: map backend ABI types onto concrete core-Wasm value types.
     // WHY: the current emitter uses an `i32` handle ABI for linear-memory objects to keep interop
-    // simple.
+    // simple. Host-owned handles stay `externref` so the host never needs a lookup table.
     match abi {
         WasmAbiType::I32 | WasmAbiType::Handle => Ok(ValType::I32),
+        WasmAbiType::ExternRef => Ok(ValType::Ref(RefType::EXTERNREF)),
         WasmAbiType::I64 => Ok(ValType::I64),
         WasmAbiType::F32 => Ok(ValType::F32),
         WasmAbiType::F64 => Ok(ValType::F64),
@@ -20,3 +21,11 @@ pub(crate) fn abi_to_val_type(abi: WasmAbiType) -> Result<ValType, CompilerError
         .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))),
     }
 }
+
+/// Wasm GC emission variant of [`abi_to_val_type`]: handles are `anyref` GC references.
+pub(crate) fn gc_abi_to_val_type(abi: WasmAbiType) -> Result<ValType, CompilerError> {
+    match abi {
+        WasmAbiType::Handle => Ok(ValType::Ref(RefType::ANYREF)),
+        _ => abi_to_val_type(abi),
+    }
+}
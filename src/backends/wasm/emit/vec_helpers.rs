@@ -11,6 +11,9 @@
 //!   offset 0: data_ptr  (i32) — pointer to contiguous i32 string-handle array
 //!   offset 4: len       (i32) — logical element count
 //!   offset 8: capacity  (i32) — allocated element capacity (in elements, not bytes)
+//!
+//! The header block owns the element array (see `runtime::memory`), so releasing a vec also
+//! releases its storage. Element handles are not released with it.
 
 use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
@@ -19,9 +22,11 @@ use wasm_encoder::{Function, Instruction, MemArg, ValType};
 /// Emit the function body for one of the four vec-handle runtime helpers.
 ///
 /// `alloc_index` is the Wasm function index of `rt_alloc`, required by VecNew and VecPushHandle.
+/// `release_index` is the index of `rt_release`, which VecPushHandle uses for outgrown storage.
 pub(crate) fn emit_vec_helper(
     helper: WasmRuntimeHelper,
     alloc_index: u32,
+    release_index: u32,
 ) -> Result<Function, CompilerError> {
     let mut function = match helper {
         WasmRuntimeHelper::VecNew => {
@@ -125,6 +130,12 @@ pub(crate) fn emit_vec_helper(
                 }
                 function.instruction(&Instruction::End);
 
+                // The vec owns its storage alone: release the old array and link the new one.
+                function.instruction(&Instruction::LocalGet(VEC_HANDLE));
+                function.instruction(&Instruction::I32Load(memarg(0)));
+                function.instruction(&Instruction::Call(release_index));
+                super::alloc_helpers::emit_link_owned_block(&mut function, VEC_HANDLE, NEW_REGION);
+
                 function.instruction(&Instruction::LocalGet(VEC_HANDLE));
                 function.instruction(&Instruction::LocalGet(NEW_REGION));
                 function.instruction(&Instruction::I32Store(memarg(0)));
@@ -0,0 +1,557 @@
+//! WASI command runtime helper emission.
+//!
+//! WHAT: synthesizes `rt_wasi_write_all`, `rt_wasi_write_line`, `rt_wasi_read_line`, the
+//! terminal input helpers and `rt_wasi_clock_millis` over the `wasi_snapshot_preview1` imports
+//! (see `runtime::wasi`).
+//! WHY: WASI calls take iovecs and out-pointers in linear memory, so each helper stages them in
+//! a short-lived `rt_alloc` block and releases it before returning.
+
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::emit::map_helpers::{
+    emit, helper_index, memarg_byte, memarg_i32, memarg_i64,
+};
+use crate::backends::wasm::emit::sections::{WasmEmitPlan, runtime_string_handle};
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::backends::wasm::runtime::wasi::{
+    INPUT_CLOSED_OFFSET, INPUT_KEY_BYTES_OFFSET, INPUT_KEY_CAPACITY, INPUT_KEY_LEN_OFFSET,
+    WASI_STDIN,
+};
+use crate::compiler_frontend::compiler_messages::compiler_errors::{CompilerError, ErrorType};
+use wasm_encoder::{BlockType, Function, Instruction, MemArg, ValType};
+
+/// Staging block layout shared by the fd helpers: one iovec, the byte count out-pointer, and
+/// a one-byte read buffer.
+const IOVEC_BUF_OFFSET: u64 = 0;
+const IOVEC_LEN_OFFSET: u64 = 4;
+const IO_COUNT_OFFSET: i32 = 8;
+const READ_BYTE_OFFSET: i32 = 12;
+const IO_STAGING_SIZE: i32 = 16;
+
+/// Offset of the previous key bytes in the input state, right after the current key.
+const INPUT_PREVIOUS_KEY_OFFSET: u64 = INPUT_KEY_BYTES_OFFSET + INPUT_KEY_CAPACITY as u64;
+/// First four bytes of the normalized `"Enter"` and `"Space"` key names, as little-endian words.
+const ENTER_KEY_PREFIX: i32 = i32::from_le_bytes(*b"Ente");
+const SPACE_KEY_PREFIX: i32 = i32::from_le_bytes(*b"Spac");
+
+/// Emit the function body for one WASI helper.
+pub(crate) fn emit_wasi_helper(
+    helper: WasmRuntimeHelper,
+    plan: &WasmEmitPlan,
+    alloc_index: u32,
+    release_index: u32,
+) -> Result<Function, CompilerError> {
+    let function = match helper {
+        WasmRuntimeHelper::WasiWriteAll => {
+            // WHAT: write `len` bytes from `ptr`, advancing past partial writes.
+            // WHY: `fd_write` may accept fewer bytes than offered (pipes, terminals). A write
+            // error or a zero-byte write ends the loop, since `console.log` cannot fail either.
+            const FD: u32 = 0;
+            const PTR: u32 = 1;
+            const LEN: u32 = 2;
+            const STAGING: u32 = 3;
+            const WRITTEN: u32 = 4;
+
+            let fd_write = wasi_import(plan, WasmHostFunction::WasiFdWrite)?;
+            // params 0..=2: fd, ptr, len | locals 3..=4 listed above
+            let mut function = Function::new(vec![(2, ValType::I32)]);
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(IO_STAGING_SIZE),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalSet(STAGING),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32Eqz,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::LocalGet(PTR),
+                    Instruction::I32Store(memarg_i32(IOVEC_BUF_OFFSET)),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32Store(memarg_i32(IOVEC_LEN_OFFSET)),
+                    Instruction::LocalGet(FD),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(1),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(IO_COUNT_OFFSET),
+                    Instruction::I32Add,
+                    Instruction::Call(fd_write),
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Load(memarg_i32(IO_COUNT_OFFSET as u64)),
+                    Instruction::LocalTee(WRITTEN),
+                    Instruction::I32Eqz,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(PTR),
+                    Instruction::LocalGet(WRITTEN),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(PTR),
+                    Instruction::LocalGet(LEN),
+                    Instruction::LocalGet(WRITTEN),
+                    Instruction::I32Sub,
+                    Instruction::LocalSet(LEN),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::LocalGet(STAGING),
+                    Instruction::Call(release_index),
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        WasmRuntimeHelper::WasiWriteLine => {
+            // WHAT: write a finalized string, then the emitter-owned "\n" string.
+            const HANDLE: u32 = 0;
+            const FD: u32 = 1;
+
+            let write_all = helper_index(plan, WasmRuntimeHelper::WasiWriteAll)?;
+            let newline = runtime_string_handle(plan, "\n")? as i32;
+            let mut function = Function::new(Vec::new());
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(FD),
+                    Instruction::LocalGet(HANDLE),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalGet(HANDLE),
+                    Instruction::I32Load(memarg_i32(4)),
+                    Instruction::Call(write_all),
+                    Instruction::LocalGet(FD),
+                    Instruction::I32Const(newline),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::I32Const(newline),
+                    Instruction::I32Load(memarg_i32(4)),
+                    Instruction::Call(write_all),
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        WasmRuntimeHelper::WasiReadLine => {
+            // WHAT: append stdin bytes one at a time until a newline (kept) or end of input.
+            // WHY: reading byte-wise never consumes input past the line, so later reads and
+            // child processes sharing stdin see the rest.
+            const BUFFER: u32 = 0;
+            const STAGING: u32 = 1;
+
+            let fd_read = wasi_import(plan, WasmHostFunction::WasiFdRead)?;
+            // locals 0..=1 listed above
+            let mut function = Function::new(vec![(2, ValType::I32)]);
+            emit(
+                &mut function,
+                &[
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::StringNewBuffer)?),
+                    Instruction::LocalSet(BUFFER),
+                    Instruction::I32Const(IO_STAGING_SIZE),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalTee(STAGING),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(READ_BYTE_OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I32Store(memarg_i32(IOVEC_BUF_OFFSET)),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(1),
+                    Instruction::I32Store(memarg_i32(IOVEC_LEN_OFFSET)),
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::I32Const(WASI_STDIN),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(1),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(IO_COUNT_OFFSET),
+                    Instruction::I32Add,
+                    Instruction::Call(fd_read),
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Load(memarg_i32(IO_COUNT_OFFSET as u64)),
+                    Instruction::I32Eqz,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(BUFFER),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(READ_BYTE_OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I32Const(1),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::StringPushLiteral)?),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I32Const(READ_BYTE_OFFSET),
+                    Instruction::I32Add,
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Const(i32::from(b'\n')),
+                    Instruction::I32Ne,
+                    Instruction::BrIf(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::LocalGet(STAGING),
+                    Instruction::Call(release_index),
+                    Instruction::LocalGet(BUFFER),
+                    Instruction::Call(helper_index(plan, WasmRuntimeHelper::StringFinish)?),
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        WasmRuntimeHelper::WasiInputUpdate => {
+            // WHAT: move the current key to the previous slot, then read one stdin line and
+            // store it as the current key, normalized like the JS runtime's key names.
+            // WHY: a terminal delivers whole lines, so each line stands for one key press. Keys
+            // live inline in the state block, which keeps it a single allocation to release.
+            const STATE: u32 = 0;
+            const LINE: u32 = 1;
+            const PTR: u32 = 2;
+            const LEN: u32 = 3;
+            const INDEX: u32 = 4;
+            const BYTE: u32 = 5;
+
+            let mut function = Function::new(vec![(5, ValType::I32)]);
+            let mut instructions = vec![
+                Instruction::LocalGet(STATE),
+                Instruction::I32Load(memarg_i32(INPUT_CLOSED_OFFSET)),
+                Instruction::If(BlockType::Empty),
+                Instruction::Return,
+                Instruction::End,
+                Instruction::LocalGet(STATE),
+                Instruction::LocalGet(STATE),
+                Instruction::I32Load(memarg_i32(INPUT_KEY_LEN_OFFSET)),
+                Instruction::I32Store(memarg_i32(INPUT_KEY_LEN_OFFSET + 4)),
+            ];
+            for word in 0..(INPUT_KEY_CAPACITY as u64 / 8) {
+                instructions.extend([
+                    Instruction::LocalGet(STATE),
+                    Instruction::LocalGet(STATE),
+                    Instruction::I64Load(memarg_i64(INPUT_KEY_BYTES_OFFSET + word * 8)),
+                    Instruction::I64Store(memarg_i64(INPUT_PREVIOUS_KEY_OFFSET + word * 8)),
+                ]);
+            }
+            instructions.extend([
+                Instruction::Call(helper_index(plan, WasmRuntimeHelper::WasiReadLine)?),
+                Instruction::LocalTee(LINE),
+                Instruction::I32Load(memarg_i32(0)),
+                Instruction::LocalSet(PTR),
+                Instruction::LocalGet(LINE),
+                Instruction::I32Load(memarg_i32(4)),
+                Instruction::LocalSet(LEN),
+            ]);
+            // Drop the trailing "\n", then a "\r" before it.
+            for terminator in [b'\n', b'\r'] {
+                instructions.extend([
+                    Instruction::LocalGet(LEN),
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(PTR),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32Add,
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Const(i32::from(terminator)),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(LEN),
+                    Instruction::I32Const(1),
+                    Instruction::I32Sub,
+                    Instruction::LocalSet(LEN),
+                    Instruction::End,
+                    Instruction::End,
+                ]);
+            }
+            instructions.extend([
+                Instruction::Block(BlockType::Empty),
+                // No current key at end of input or for lines that do not fit a key slot.
+                Instruction::LocalGet(STATE),
+                Instruction::I32Const(0),
+                Instruction::I32Store(memarg_i32(INPUT_KEY_LEN_OFFSET)),
+                Instruction::LocalGet(LINE),
+                Instruction::I32Load(memarg_i32(4)),
+                Instruction::I32Eqz,
+                Instruction::BrIf(0),
+                Instruction::LocalGet(LEN),
+                Instruction::I32Const(INPUT_KEY_CAPACITY),
+                Instruction::I32GtU,
+                Instruction::BrIf(0),
+                Instruction::LocalGet(LEN),
+                Instruction::I32Eqz,
+                Instruction::If(BlockType::Empty),
+            ]);
+            instructions.extend(store_key_name(STATE, ENTER_KEY_PREFIX, b'r'));
+            instructions.extend([
+                Instruction::Br(1),
+                Instruction::End,
+                Instruction::I32Const(0),
+                Instruction::LocalSet(INDEX),
+                Instruction::Block(BlockType::Empty),
+                Instruction::Loop(BlockType::Empty),
+                Instruction::LocalGet(INDEX),
+                Instruction::LocalGet(LEN),
+                Instruction::I32GeU,
+                Instruction::BrIf(1),
+                Instruction::LocalGet(STATE),
+                Instruction::LocalGet(INDEX),
+                Instruction::I32Add,
+                Instruction::LocalGet(PTR),
+                Instruction::LocalGet(INDEX),
+                Instruction::I32Add,
+                Instruction::I32Load8U(memarg_byte()),
+                Instruction::I32Store8(memarg_byte_at(INPUT_KEY_BYTES_OFFSET)),
+                Instruction::LocalGet(INDEX),
+                Instruction::I32Const(1),
+                Instruction::I32Add,
+                Instruction::LocalSet(INDEX),
+                Instruction::Br(0),
+                Instruction::End,
+                Instruction::End,
+                Instruction::LocalGet(STATE),
+                Instruction::LocalGet(LEN),
+                Instruction::I32Store(memarg_i32(INPUT_KEY_LEN_OFFSET)),
+                Instruction::LocalGet(LEN),
+                Instruction::I32Const(1),
+                Instruction::I32Ne,
+                Instruction::BrIf(0),
+                Instruction::LocalGet(STATE),
+                Instruction::I32Load8U(memarg_byte_at(INPUT_KEY_BYTES_OFFSET)),
+                Instruction::LocalTee(BYTE),
+                Instruction::I32Const(i32::from(b' ')),
+                Instruction::I32Eq,
+                Instruction::If(BlockType::Empty),
+            ]);
+            instructions.extend(store_key_name(STATE, SPACE_KEY_PREFIX, b'e'));
+            instructions.extend([
+                Instruction::Br(1),
+                Instruction::End,
+                Instruction::LocalGet(BYTE),
+                Instruction::I32Const(i32::from(b'A')),
+                Instruction::I32Sub,
+                Instruction::I32Const(26),
+                Instruction::I32LtU,
+                Instruction::If(BlockType::Empty),
+                Instruction::LocalGet(STATE),
+                Instruction::LocalGet(BYTE),
+                Instruction::I32Const(32),
+                Instruction::I32Add,
+                Instruction::I32Store8(memarg_byte_at(INPUT_KEY_BYTES_OFFSET)),
+                Instruction::End,
+                Instruction::End,
+                Instruction::LocalGet(LINE),
+                Instruction::Call(release_index),
+                Instruction::End,
+            ]);
+            emit(&mut function, &instructions);
+            function
+        }
+        WasmRuntimeHelper::WasiInputKeyMatches => {
+            // WHAT: compare a key name with one key slot, normalizing the name the way
+            // `rt_wasi_input_update` normalizes lines. A closed state matches nothing.
+            const STATE: u32 = 0;
+            const KEY: u32 = 1;
+            const SLOT: u32 = 2;
+            const SLOT_PTR: u32 = 3;
+            const SLOT_LEN: u32 = 4;
+            const KEY_PTR: u32 = 5;
+            const KEY_LEN: u32 = 6;
+            const BYTE: u32 = 7;
+            const INDEX: u32 = 8;
+
+            // params 0..=2: state, key, slot | locals 3..=8 listed above
+            let mut function = Function::new(vec![(6, ValType::I32)]);
+            emit(
+                &mut function,
+                &[
+                    Instruction::LocalGet(STATE),
+                    Instruction::I32Load(memarg_i32(INPUT_CLOSED_OFFSET)),
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(0),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(STATE),
+                    Instruction::LocalGet(SLOT),
+                    Instruction::I32Const(2),
+                    Instruction::I32Shl,
+                    Instruction::I32Add,
+                    Instruction::I32Load(memarg_i32(INPUT_KEY_LEN_OFFSET)),
+                    Instruction::LocalSet(SLOT_LEN),
+                    Instruction::LocalGet(STATE),
+                    Instruction::LocalGet(SLOT),
+                    Instruction::I32Const(INPUT_KEY_CAPACITY),
+                    Instruction::I32Mul,
+                    Instruction::I32Add,
+                    Instruction::I32Const(INPUT_KEY_BYTES_OFFSET as i32),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(SLOT_PTR),
+                    Instruction::LocalGet(KEY),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::LocalSet(KEY_PTR),
+                    Instruction::LocalGet(KEY),
+                    Instruction::I32Load(memarg_i32(4)),
+                    Instruction::LocalSet(KEY_LEN),
+                    // One-byte names: " " means "Space" and capitals match their lowercase key.
+                    Instruction::LocalGet(KEY_LEN),
+                    Instruction::I32Const(1),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(KEY_PTR),
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::LocalTee(BYTE),
+                    Instruction::I32Const(i32::from(b' ')),
+                    Instruction::I32Eq,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(SLOT_LEN),
+                    Instruction::I32Const(5),
+                    Instruction::I32Eq,
+                    Instruction::LocalGet(SLOT_PTR),
+                    Instruction::I32Load(memarg_i32(0)),
+                    Instruction::I32Const(SPACE_KEY_PREFIX),
+                    Instruction::I32Eq,
+                    Instruction::I32And,
+                    Instruction::LocalGet(SLOT_PTR),
+                    Instruction::I32Load8U(memarg_byte_at(4)),
+                    Instruction::I32Const(i32::from(b'e')),
+                    Instruction::I32Eq,
+                    Instruction::I32And,
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(BYTE),
+                    Instruction::I32Const(i32::from(b'A')),
+                    Instruction::I32Sub,
+                    Instruction::I32Const(26),
+                    Instruction::I32LtU,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::LocalGet(BYTE),
+                    Instruction::I32Const(32),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(BYTE),
+                    Instruction::End,
+                    Instruction::LocalGet(SLOT_LEN),
+                    Instruction::I32Const(1),
+                    Instruction::I32Eq,
+                    Instruction::LocalGet(SLOT_PTR),
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::LocalGet(BYTE),
+                    Instruction::I32Eq,
+                    Instruction::I32And,
+                    Instruction::Return,
+                    Instruction::End,
+                    // Longer names compare byte for byte; an empty slot matches nothing.
+                    Instruction::LocalGet(SLOT_LEN),
+                    Instruction::LocalGet(KEY_LEN),
+                    Instruction::I32Ne,
+                    Instruction::LocalGet(SLOT_LEN),
+                    Instruction::I32Eqz,
+                    Instruction::I32Or,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(0),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::Block(BlockType::Empty),
+                    Instruction::Loop(BlockType::Empty),
+                    Instruction::LocalGet(INDEX),
+                    Instruction::LocalGet(SLOT_LEN),
+                    Instruction::I32GeU,
+                    Instruction::BrIf(1),
+                    Instruction::LocalGet(SLOT_PTR),
+                    Instruction::LocalGet(INDEX),
+                    Instruction::I32Add,
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::LocalGet(KEY_PTR),
+                    Instruction::LocalGet(INDEX),
+                    Instruction::I32Add,
+                    Instruction::I32Load8U(memarg_byte()),
+                    Instruction::I32Ne,
+                    Instruction::If(BlockType::Empty),
+                    Instruction::I32Const(0),
+                    Instruction::Return,
+                    Instruction::End,
+                    Instruction::LocalGet(INDEX),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::LocalSet(INDEX),
+                    Instruction::Br(0),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::I32Const(1),
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        WasmRuntimeHelper::WasiClockMillis => {
+            // WHAT: read a clock's u64 nanoseconds and convert them to f64 milliseconds.
+            // WHY: every clock a command can ask for exists in WASI, so a failed read is an
+            // invariant trap rather than a recoverable error.
+            const CLOCK: u32 = 0;
+            const STAGING: u32 = 1;
+            const MILLIS: u32 = 2;
+
+            let clock_time_get = wasi_import(plan, WasmHostFunction::WasiClockTimeGet)?;
+            // param 0: clock id | local 1: staging, local 2: millis
+            let mut function = Function::new(vec![(1, ValType::I32), (1, ValType::F64)]);
+            emit(
+                &mut function,
+                &[
+                    Instruction::I32Const(8),
+                    Instruction::Call(alloc_index),
+                    Instruction::LocalSet(STAGING),
+                    Instruction::LocalGet(CLOCK),
+                    // Precision hint: one microsecond.
+                    Instruction::I64Const(1_000),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::Call(clock_time_get),
+                    Instruction::If(BlockType::Empty),
+                    Instruction::Unreachable,
+                    Instruction::End,
+                    Instruction::LocalGet(STAGING),
+                    Instruction::I64Load(memarg_i64(0)),
+                    Instruction::F64ConvertI64U,
+                    Instruction::F64Const(1_000_000.0.into()),
+                    Instruction::F64Div,
+                    Instruction::LocalSet(MILLIS),
+                    Instruction::LocalGet(STAGING),
+                    Instruction::Call(release_index),
+                    Instruction::LocalGet(MILLIS),
+                    Instruction::End,
+                ],
+            );
+            function
+        }
+        _ => unreachable!("non-WASI helpers are dispatched elsewhere"),
+    };
+
+    Ok(function)
+}
+
+/// Stores a five-byte key name (`prefix` word plus `last` byte) as the current key of `state`.
+fn store_key_name(state: u32, prefix: i32, last: u8) -> [Instruction<'static>; 9] {
+    [
+        Instruction::LocalGet(state),
+        Instruction::I32Const(prefix),
+        Instruction::I32Store(memarg_i32(INPUT_KEY_BYTES_OFFSET)),
+        Instruction::LocalGet(state),
+        Instruction::I32Const(i32::from(last)),
+        Instruction::I32Store8(memarg_byte_at(INPUT_KEY_BYTES_OFFSET + 4)),
+        Instruction::LocalGet(state),
+        Instruction::I32Const(5),
+        Instruction::I32Store(memarg_i32(INPUT_KEY_LEN_OFFSET)),
+    ]
+}
+
+fn memarg_byte_at(offset: u64) -> MemArg {
+    MemArg {
+        offset,
+        align: 0,
+        memory_index: 0,
+    }
+}
+
+fn wasi_import(plan: &WasmEmitPlan, function: WasmHostFunction) -> Result<u32, CompilerError> {
+    plan.host_function_indices
+        .get(&function)
+        .copied()
+        .ok_or_else(|| {
+            CompilerError::compiler_error(format!(
+                "Wasm emission missing the WASI {} import",
+                function.item_name()
+            ))
+            .with_error_type(ErrorType::Backend(BackendErrorType::WasmGeneration))
+        })
+}
@@ -1,5 +1,6 @@
 //! Lowering contexts shared across module/function lowering stages.
 
+use crate::backends::wasm::hir_to_lir::monomorphize::{WasmRecordLayouts, plan_record_layouts};
 use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
 use crate::backends::wasm::lir::instructions::WasmLirTerminator;
 use crate::backends::wasm::lir::module::WasmLirModule;
@@ -10,8 +11,10 @@ use crate::backends::wasm::lir::types::{
 use crate::backends::wasm::request::WasmBackendRequest;
 use crate::backends::wasm::runtime::imports::WasmHostFunction;
 use crate::compiler_frontend::analysis::borrow_checker::BorrowFacts;
+use crate::compiler_frontend::datatypes::definitions::TypeDefinition;
 use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
 use crate::compiler_frontend::datatypes::ids::TypeId;
+use crate::compiler_frontend::external_packages::{ExternalAbiType, ExternalFunctionId};
 use crate::compiler_frontend::hir::functions::HirFunction;
 use crate::compiler_frontend::hir::hir_datatypes::{HirTypeClass, classify_hir_type};
 use crate::compiler_frontend::hir::ids::{BlockId, FunctionId, LocalId};
@@ -42,6 +45,11 @@ pub(crate) struct WasmLirLoweringContext<'a> {
     pub static_string_pool: FxHashMap<Vec<u8>, WasmStaticDataId>,
     /// Host import registry keyed by semantic host function.
     pub host_imports: FxHashMap<WasmHostFunction, WasmImportId>,
+    /// External package function -> `host` import registry.
+    pub external_imports: FxHashMap<ExternalFunctionId, WasmImportId>,
+    /// Per-instance struct and choice record layouts.
+    /// WHY: planned once up front so every construction and field access agrees on offsets.
+    pub record_layouts: WasmRecordLayouts,
 }
 
 impl<'a> WasmLirLoweringContext<'a> {
@@ -52,7 +60,7 @@ impl<'a> WasmLirLoweringContext<'a> {
         string_table: &'a StringTable,
         type_environment: &'a TypeEnvironment,
     ) -> Self {
-        Self {
+        let mut context = Self {
             hir_module,
             borrow_facts,
             request,
@@ -62,7 +70,11 @@ impl<'a> WasmLirLoweringContext<'a> {
             function_map: FxHashMap::default(),
             static_string_pool: FxHashMap::default(),
             host_imports: FxHashMap::default(),
-        }
+            external_imports: FxHashMap::default(),
+            record_layouts: WasmRecordLayouts::default(),
+        };
+        context.record_layouts = plan_record_layouts(&context);
+        context
     }
 }
 
@@ -77,6 +89,10 @@ pub(crate) struct WasmFunctionLoweringContext<'a, 'b> {
 
     /// HIR local -> LIR local mapping for this function.
     pub local_map: FxHashMap<LocalId, WasmLirLocalId>,
+    /// Tuple-typed HIR local -> one LIR local per slot.
+    /// WHY: multi-value calls and returns keep tuples on the Wasm stack, so a tuple never has a
+    /// single handle to map.
+    pub tuple_local_slots: FxHashMap<LocalId, Vec<WasmLirLocalId>>,
     /// HIR block -> LIR block mapping for this function.
     pub block_map: FxHashMap<BlockId, WasmLirBlockId>,
     /// Fast index lookup for mutable block access.
@@ -109,6 +125,7 @@ impl<'a, 'b> WasmFunctionLoweringContext<'a, 'b> {
                 linkage: crate::backends::wasm::lir::linkage::WasmFunctionLinkage::Internal,
             },
             local_map: FxHashMap::default(),
+            tuple_local_slots: FxHashMap::default(),
             block_map: FxHashMap::default(),
             block_index_by_id: FxHashMap::default(),
             local_type_by_id: FxHashMap::default(),
@@ -178,6 +195,22 @@ pub(crate) fn lower_type_to_abi(
     context: &WasmLirLoweringContext<'_>,
     type_id: TypeId,
 ) -> WasmAbiType {
+    if let Some(TypeDefinition::External(external)) = context.type_environment.get(type_id) {
+        // Numeric external types (time values) travel as plain floats on every target.
+        let registry = &context.request.external_package_registry;
+        if registry
+            .get_type_by_id(external.type_id)
+            .is_some_and(|definition| definition.abi_type == ExternalAbiType::F64)
+        {
+            return WasmAbiType::F64;
+        }
+
+        // Opaque host handles stay host-owned references when the target supports them.
+        if context.request.target_features.enable_reference_types {
+            return WasmAbiType::ExternRef;
+        }
+    }
+
     match classify_hir_type(type_id, context.type_environment) {
         HirTypeClass::Unit => WasmAbiType::Void,
         HirTypeClass::Bool | HirTypeClass::Char => WasmAbiType::I32,
@@ -186,3 +219,17 @@ pub(crate) fn lower_type_to_abi(
         HirTypeClass::Function | HirTypeClass::HeapAllocated => WasmAbiType::Handle,
     }
 }
+
+/// Slot ABI types of a tuple (multi-return) type, or `None` for every other type.
+pub(crate) fn tuple_slot_abis(
+    context: &WasmLirLoweringContext<'_>,
+    type_id: TypeId,
+) -> Option<Vec<WasmAbiType>> {
+    let fields = context.type_environment.tuple_field_ids(type_id)?;
+    Some(
+        fields
+            .iter()
+            .map(|field| lower_type_to_abi(context, *field))
+            .collect(),
+    )
+}
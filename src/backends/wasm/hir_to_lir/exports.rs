@@ -2,12 +2,16 @@
 
 use crate::backends::error_types::lir_transformation_error;
 use crate::backends::wasm::hir_to_lir::context::WasmLirLoweringContext;
+use crate::backends::wasm::hir_to_lir::static_data::intern_static_utf8;
 use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
 use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
 use crate::backends::wasm::lir::linkage::{WasmExport, WasmExportKind, WasmFunctionLinkage};
 use crate::backends::wasm::lir::types::{
-    WasmLirBlockId, WasmLirFunctionId, WasmLirLocal, WasmLirLocalId, WasmLocalRole,
+    WasmAbiType, WasmLirBlockId, WasmLirFunctionId, WasmLirLocal, WasmLirLocalId, WasmLirSignature,
+    WasmLocalRole,
 };
+use crate::backends::wasm::request::WasmCommandOutput;
+use crate::backends::wasm::runtime::wasi::{WASI_STDOUT, WasiAdapter};
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
 use rustc_hash::FxHashSet;
 
@@ -67,12 +71,6 @@ pub(crate) fn synthesize_export_wrappers(
                 ))
             })?;
 
-        if target_signature.results.len() > 1 {
-            return Err(lir_transformation_error(format!(
-                "Wasm export wrapper synthesis does not yet support multi-value returns for {target_lir_id:?}"
-            )));
-        }
-
         // Wrapper local layout mirrors target signature params/results.
         let mut locals = Vec::new();
         let mut args = Vec::new();
@@ -90,22 +88,47 @@ pub(crate) fn synthesize_export_wrappers(
             args.push(local_id);
         }
 
-        let result_local = target_signature.results.first().copied().map(|result_abi| {
+        let mut results = Vec::with_capacity(target_signature.results.len());
+        for (index, result_abi) in target_signature.results.iter().enumerate() {
             let local_id = WasmLirLocalId(next_local_id);
+            next_local_id += 1;
             locals.push(WasmLirLocal {
                 id: local_id,
-                name: Some("result".to_owned()),
-                ty: result_abi,
+                name: Some(if target_signature.results.len() == 1 {
+                    "result".to_owned()
+                } else {
+                    format!("result{index}")
+                }),
+                ty: *result_abi,
                 role: WasmLocalRole::Temp,
             });
-            local_id
-        });
+            results.push(local_id);
+        }
 
-        let statements = vec![WasmLirStmt::Call {
-            dst: result_local,
-            callee: WasmCalleeRef::Function(target_lir_id),
-            args,
-        }];
+        let callee = WasmCalleeRef::Function(target_lir_id);
+        // Multi-value targets forward every result straight back to the host.
+        let (statement, terminator) = if results.len() > 1 {
+            (
+                WasmLirStmt::CallMultiValue {
+                    dsts: results.clone(),
+                    callee,
+                    args,
+                },
+                WasmLirTerminator::ReturnMultiValue { values: results },
+            )
+        } else {
+            let result_local = results.first().copied();
+            (
+                WasmLirStmt::Call {
+                    dst: result_local,
+                    callee,
+                    args,
+                },
+                WasmLirTerminator::Return {
+                    value: result_local,
+                },
+            )
+        };
 
         let wrapper_function_id = WasmLirFunctionId(wrapper_id);
 
@@ -117,10 +140,8 @@ pub(crate) fn synthesize_export_wrappers(
             locals,
             blocks: vec![WasmLirBlock {
                 id: WasmLirBlockId(0),
-                statements,
-                terminator: WasmLirTerminator::Return {
-                    value: result_local,
-                },
+                statements: vec![statement],
+                terminator,
             }],
             linkage: WasmFunctionLinkage::ExportedWrapper,
         });
@@ -133,3 +154,136 @@ pub(crate) fn synthesize_export_wrappers(
 
     Ok(())
 }
+
+/// Export name WASI runtimes call to run a command module.
+const WASI_COMMAND_START_EXPORT: &str = "_start";
+
+pub(crate) fn synthesize_command_start(
+    context: &mut WasmLirLoweringContext<'_>,
+) -> Result<(), CompilerError> {
+    // WHAT: create the `_start` export of a WASI command: run the entry start function, then
+    // write each top-level fragment to stdout as one line.
+    // WHY: a command has no JS bootstrap to render fragments, so `_start` prints them in the
+    // same source order the HTML builder renders them.
+    let request = context.request;
+    let Some(command_start) = &request.export_policy.command_start else {
+        return Ok(());
+    };
+
+    if context
+        .lir_module
+        .exports
+        .iter()
+        .any(|export| export.export_name == WASI_COMMAND_START_EXPORT)
+    {
+        return Err(lir_transformation_error(format!(
+            "Wasm command start synthesis encountered duplicate export name '{WASI_COMMAND_START_EXPORT}'"
+        )));
+    }
+
+    let target_lir_id = context
+        .function_map
+        .get(&command_start.function)
+        .copied()
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm command start synthesis could not resolve start function {:?}",
+                command_start.function
+            ))
+        })?;
+    let target_results = context
+        .lir_module
+        .functions
+        .iter()
+        .find(|function| function.id == target_lir_id)
+        .map(|function| function.signature.results.clone())
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm command start synthesis missing lowered start function {target_lir_id:?}"
+            ))
+        })?;
+
+    let mut locals = Vec::new();
+    let mut new_local = |name: &str| {
+        let id = WasmLirLocalId(locals.len() as u32);
+        locals.push(WasmLirLocal {
+            id,
+            name: Some(name.to_owned()),
+            ty: WasmAbiType::Handle,
+            role: WasmLocalRole::Temp,
+        });
+        id
+    };
+
+    // The entry start function returns the runtime fragment Vec, or nothing without fragments.
+    let fragments = match target_results.as_slice() {
+        [] => None,
+        [WasmAbiType::Handle] => Some(new_local("fragments")),
+        _ => {
+            return Err(lir_transformation_error(
+                "Wasm command start synthesis expected the start function to return a fragment Vec",
+            ));
+        }
+    };
+    let mut statements = vec![WasmLirStmt::Call {
+        dst: fragments,
+        callee: WasmCalleeRef::Function(target_lir_id),
+        args: vec![],
+    }];
+
+    for output in &command_start.output {
+        match output {
+            WasmCommandOutput::Const(text) => {
+                let buffer = new_local("const_buffer");
+                let line = new_local("const_line");
+                let data = intern_static_utf8(context, text, "command_const_fragment");
+                statements.extend([
+                    WasmLirStmt::StringNewBuffer { dst: buffer },
+                    WasmLirStmt::StringPushLiteral { buffer, data },
+                    WasmLirStmt::StringFinish { dst: line, buffer },
+                    WasmLirStmt::WasiCall {
+                        dst: None,
+                        adapter: WasiAdapter::WriteLine { fd: WASI_STDOUT },
+                        args: vec![line],
+                    },
+                ]);
+            }
+            WasmCommandOutput::RuntimeFragment(index) => {
+                let Some(fragments) = fragments else {
+                    return Err(lir_transformation_error(format!(
+                        "Wasm command start synthesis has runtime fragment {index} but the start function returns none"
+                    )));
+                };
+                statements.push(WasmLirStmt::WasiCall {
+                    dst: None,
+                    adapter: WasiAdapter::WriteFragmentLine { index: *index },
+                    args: vec![fragments],
+                });
+            }
+        }
+    }
+
+    let start_function_id = WasmLirFunctionId(context.lir_module.functions.len() as u32);
+    context.lir_module.functions.push(WasmLirFunction {
+        id: start_function_id,
+        debug_name: format!("export_wrapper::{WASI_COMMAND_START_EXPORT}"),
+        origin: WasmLirFunctionOrigin::ExportWrapper,
+        signature: WasmLirSignature {
+            params: vec![],
+            results: vec![],
+        },
+        locals,
+        blocks: vec![WasmLirBlock {
+            id: WasmLirBlockId(0),
+            statements,
+            terminator: WasmLirTerminator::Return { value: None },
+        }],
+        linkage: WasmFunctionLinkage::ExportedWrapper,
+    });
+    context.lir_module.exports.push(WasmExport {
+        export_name: WASI_COMMAND_START_EXPORT.to_owned(),
+        kind: WasmExportKind::Function(start_function_id),
+    });
+
+    Ok(())
+}
@@ -2,11 +2,27 @@
 
 use crate::backends::error_types::lir_transformation_error;
 use crate::backends::wasm::hir_to_lir::context::{WasmFunctionLoweringContext, lower_type_to_abi};
+use crate::backends::wasm::hir_to_lir::imports::ensure_host_import;
 use crate::backends::wasm::hir_to_lir::static_data::intern_static_utf8;
+use crate::backends::wasm::hir_to_lir::tuples::lower_tuple_slots;
 use crate::backends::wasm::lir::instructions::WasmLirStmt;
-use crate::backends::wasm::lir::types::{WasmAbiType, WasmLirLocalId, WasmLocalRole};
+use crate::backends::wasm::lir::types::{
+    WasmAbiType, WasmLirLocalId, WasmLocalRole, WasmRecordTypeId,
+};
+use crate::backends::wasm::runtime::errors::WasmErrorField;
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::maps::WasmMapKeyKind;
+use crate::backends::wasm::runtime::records::{CHOICE_TAG_OFFSET, WasmRecordField};
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
+use crate::compiler_frontend::builtins::error_type::{
+    ERROR_FIELD_CODE, ERROR_FIELD_MESSAGE, ERROR_TYPE_NAME,
+};
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
-use crate::compiler_frontend::hir::expressions::{HirExpression, HirExpressionKind};
+use crate::compiler_frontend::datatypes::ids::TypeId;
+use crate::compiler_frontend::hir::expressions::{
+    HirExpression, HirExpressionKind, HirMapEntry, HirVariantCarrier, HirVariantField,
+};
+use crate::compiler_frontend::hir::ids::{ChoiceId, FieldId, StructId};
 use crate::compiler_frontend::hir::operators::HirBinOp;
 use crate::compiler_frontend::hir::places::HirPlace;
 
@@ -40,43 +56,55 @@ pub(crate) fn lower_expression(
             })
         }
         HirExpressionKind::VariantConstruct {
-            carrier,
+            carrier: HirVariantCarrier::Choice { choice_id },
             variant_index,
             fields,
+        } => lower_choice_construct(context, *choice_id, *variant_index, fields, statements),
+        HirExpressionKind::VariantConstruct {
+            variant_index,
+            fields,
+            ..
         } => {
             if !fields.is_empty() {
                 return Err(lir_transformation_error(
                     "Wasm backend does not yet support variant payload fields",
                 ));
             }
-            match carrier {
-                crate::compiler_frontend::hir::expressions::HirVariantCarrier::Choice {
-                    ..
-                }
-                | crate::compiler_frontend::hir::expressions::HirVariantCarrier::Option => {
-                    let dst = context.alloc_temp(WasmAbiType::I64);
-                    statements.push(WasmLirStmt::ConstI64 {
-                        dst,
-                        value: *variant_index as i64,
-                    });
-                    Ok(ExprLoweringOutput {
-                        value: dst,
-                        prefer_move: false,
-                    })
-                }
-                #[cfg(test)]
-                crate::compiler_frontend::hir::expressions::HirVariantCarrier::Fallible => {
-                    let dst = context.alloc_temp(WasmAbiType::I64);
-                    statements.push(WasmLirStmt::ConstI64 {
-                        dst,
-                        value: *variant_index as i64,
-                    });
-                    Ok(ExprLoweringOutput {
-                        value: dst,
-                        prefer_move: false,
-                    })
-                }
+            let dst = context.alloc_temp(WasmAbiType::I64);
+            statements.push(WasmLirStmt::ConstI64 {
+                dst,
+                value: *variant_index as i64,
+            });
+            Ok(ExprLoweringOutput {
+                value: dst,
+                prefer_move: false,
+            })
+        }
+        HirExpressionKind::StructConstruct { struct_id, fields } => {
+            lower_struct_construct(context, *struct_id, fields, statements)
+        }
+        HirExpressionKind::VariantPayloadGet {
+            carrier: HirVariantCarrier::Choice { choice_id },
+            source,
+            variant_index,
+            field_index,
+        } => {
+            let record = lower_expression(context, source, statements)?.value;
+            let (record_type, slot) =
+                choice_field_slot(context, *choice_id, *variant_index, *field_index)?;
+            let dst = context.alloc_temp(slot.abi);
+            if slot.abi != WasmAbiType::Void {
+                statements.push(WasmLirStmt::RecordLoad {
+                    dst,
+                    record,
+                    record_type,
+                    offset: slot.offset,
+                });
             }
+            Ok(ExprLoweringOutput {
+                value: dst,
+                prefer_move: false,
+            })
         }
         // Beanstalk `Float` is finite f64. HIR validation rejects non-finite literals before
         // any backend sees them, so this path trusts the invariant without rechecking.
@@ -130,6 +158,10 @@ pub(crate) fn lower_expression(
                 prefer_move: false,
             })
         }
+        HirExpressionKind::Load(HirPlace::Field { base, field })
+        | HirExpressionKind::Copy(HirPlace::Field { base, field }) => {
+            lower_field_read(context, base, *field, statements)
+        }
         HirExpressionKind::Load(place) => {
             let local = lower_place_local(context, place)?;
             Ok(ExprLoweringOutput {
@@ -151,38 +183,354 @@ pub(crate) fn lower_expression(
             "Wasm lowering does not yet support unary operator {op:?}"
         ))),
         HirExpressionKind::Collection(items) => {
-            if is_empty_string_collection(context, expression, items) {
-                let dst =
-                    context.alloc_local(None, WasmAbiType::Handle, WasmLocalRole::ValueHandle);
-                statements.push(WasmLirStmt::VecNew { dst });
-                return Ok(ExprLoweringOutput {
-                    value: dst,
-                    prefer_move: false,
-                });
-            }
-
-            Err(lir_transformation_error(
-                "Wasm lowering only supports empty Vec<String> collection literals in this pass",
-            ))
+            lower_collection_literal(context, items, statements)
         }
-        HirExpressionKind::MapLiteral(_) => Err(lir_transformation_error(
-            "Wasm hashmap literal reached lowering before backend feature validation",
-        )),
-        HirExpressionKind::Cast { .. } => Err(lir_transformation_error(
-            "Wasm lowering does not yet support cast expressions",
-        )),
-        HirExpressionKind::StructConstruct { .. }
-        | HirExpressionKind::Range { .. }
+        HirExpressionKind::MapLiteral(entries) => {
+            lower_map_literal(context, expression, entries, statements)
+        }
+        HirExpressionKind::FallibleUnwrapSuccess { result } => {
+            // WHAT: read the success payload of a carrier the enclosing branch already checked.
+            let carrier = lower_expression(context, result, statements)?;
+            let dst = context.alloc_temp(expression_abi(context, expression));
+            statements.push(WasmLirStmt::FallibleUnwrapSuccess {
+                dst,
+                carrier: carrier.value,
+            });
+            Ok(ExprLoweringOutput {
+                value: dst,
+                prefer_move: false,
+            })
+        }
+        HirExpressionKind::FallibleUnwrapError { result } => {
+            // WHAT: read the `Error` record handle of a carrier the enclosing branch checked.
+            let carrier = lower_expression(context, result, statements)?;
+            let dst = context.alloc_temp(WasmAbiType::Handle);
+            statements.push(WasmLirStmt::FallibleUnwrapError {
+                dst,
+                carrier: carrier.value,
+            });
+            Ok(ExprLoweringOutput {
+                value: dst,
+                prefer_move: false,
+            })
+        }
+        HirExpressionKind::Cast { source, policy } => {
+            let value = lower_expression(context, source, statements)?.value;
+            let dst = context.alloc_temp(expression_abi(context, expression));
+            lower_cast(context, *policy, value, dst, statements);
+            Ok(ExprLoweringOutput {
+                value: dst,
+                prefer_move: false,
+            })
+        }
+        HirExpressionKind::TupleGet { tuple, index } => {
+            let slots = lower_tuple_slots(context, tuple, statements)?;
+            let value = slots.get(*index).copied().ok_or_else(|| {
+                lir_transformation_error(format!(
+                    "Wasm lowering received tuple slot {index} of a {}-slot tuple",
+                    slots.len()
+                ))
+            })?;
+            Ok(ExprLoweringOutput {
+                value,
+                prefer_move: false,
+            })
+        }
+        HirExpressionKind::Range { .. }
         | HirExpressionKind::TupleConstruct { .. }
-        | HirExpressionKind::TupleGet { .. }
-        | HirExpressionKind::FallibleUnwrapSuccess { .. }
-        | HirExpressionKind::FallibleUnwrapError { .. }
         | HirExpressionKind::VariantPayloadGet { .. } => Err(lir_transformation_error(
             "Wasm lowering does not yet support this HIR expression",
         )),
     }
 }
 
+/// Appends one builtin cast, registering the host import that `String -> Float` parsing needs.
+pub(crate) fn lower_cast(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    policy: BuiltinCastPolicyId,
+    value: WasmLirLocalId,
+    dst: WasmLirLocalId,
+    statements: &mut Vec<WasmLirStmt>,
+) {
+    if policy == BuiltinCastPolicyId::StringToFloat {
+        ensure_host_import(context.module_context, WasmHostFunction::ParseFloat);
+    }
+    statements.push(WasmLirStmt::Cast { dst, policy, value });
+}
+
+fn lower_field_read(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    base: &HirPlace,
+    field: FieldId,
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<ExprLoweringOutput, CompilerError> {
+    // WHAT: builtin `Error` fields read the runtime `Error` record; every other field reads a
+    // struct record slot at its monomorphized offset.
+    // WHY: `catch` handlers read `message`/`code` from the record runtime helpers build, whose
+    // layout is fixed by `runtime::errors` rather than planned from the HIR struct.
+    if let Some(error_field) = builtin_error_field(context, field) {
+        let error = lower_place_value(context, base, statements)?;
+        let dst = context.alloc_temp(match error_field {
+            WasmErrorField::Message => WasmAbiType::Handle,
+            WasmErrorField::Code => WasmAbiType::I64,
+        });
+        statements.push(WasmLirStmt::ErrorField {
+            dst,
+            error,
+            field: error_field,
+        });
+        return Ok(ExprLoweringOutput {
+            value: dst,
+            prefer_move: false,
+        });
+    }
+
+    let record = lower_place_value(context, base, statements)?;
+    let dst = load_record_field(context, record, field, statements)?;
+    Ok(ExprLoweringOutput {
+        value: dst,
+        prefer_move: false,
+    })
+}
+
+/// Loads one struct field from `record` into a fresh temp.
+fn load_record_field(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    record: WasmLirLocalId,
+    field: FieldId,
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<WasmLirLocalId, CompilerError> {
+    let (record_type, slot) = struct_field_slot(context, field)?;
+    let dst = context.alloc_temp(slot.abi);
+    if slot.abi != WasmAbiType::Void {
+        statements.push(WasmLirStmt::RecordLoad {
+            dst,
+            record,
+            record_type,
+            offset: slot.offset,
+        });
+    }
+    Ok(dst)
+}
+
+fn lower_struct_construct(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    struct_id: StructId,
+    fields: &[(FieldId, HirExpression)],
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<ExprLoweringOutput, CompilerError> {
+    // WHAT: evaluate field values in source order, then allocate the record and store each one.
+    // WHY: each concrete (possibly generic) struct instance has its own planned layout, so the
+    // record type and offsets are fixed here rather than computed at runtime.
+    let record_type = context
+        .module_context
+        .record_layouts
+        .struct_record(struct_id)
+        .map(|record| record.id)
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm lowering has no record layout for struct {struct_id:?}"
+            ))
+        })?;
+
+    let mut values = Vec::with_capacity(fields.len());
+    for (field, value) in fields {
+        let (_, slot) = struct_field_slot(context, *field)?;
+        let value = lower_expression(context, value, statements)?.value;
+        values.push((slot, value));
+    }
+
+    let dst = context.alloc_local(None, WasmAbiType::Handle, WasmLocalRole::ValueHandle);
+    statements.push(WasmLirStmt::RecordNew { dst, record_type });
+    store_record_fields(dst, record_type, values, statements);
+
+    Ok(ExprLoweringOutput {
+        value: dst,
+        prefer_move: false,
+    })
+}
+
+fn lower_choice_construct(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    choice_id: ChoiceId,
+    variant_index: usize,
+    fields: &[HirVariantField],
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<ExprLoweringOutput, CompilerError> {
+    // WHAT: choice values are records holding the variant tag plus that variant's payload.
+    // WHY: unit and payload variants share one handle representation, so locals of a choice
+    // type always hold a record handle regardless of which variant was built.
+    let record_type = context
+        .module_context
+        .record_layouts
+        .choice_layout(choice_id)
+        .map(|(record, _)| record.id)
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm lowering has no record layout for choice {choice_id:?}"
+            ))
+        })?;
+
+    let mut values = Vec::with_capacity(fields.len());
+    for (field_index, field) in fields.iter().enumerate() {
+        let (_, slot) = choice_field_slot(context, choice_id, variant_index, field_index)?;
+        let value = lower_expression(context, &field.value, statements)?.value;
+        values.push((slot, value));
+    }
+
+    let tag = context.alloc_temp(WasmAbiType::I32);
+    statements.push(WasmLirStmt::ConstI32 {
+        dst: tag,
+        value: variant_index as i32,
+    });
+    let dst = context.alloc_local(None, WasmAbiType::Handle, WasmLocalRole::ValueHandle);
+    statements.push(WasmLirStmt::RecordNew { dst, record_type });
+    statements.push(WasmLirStmt::RecordStore {
+        record: dst,
+        record_type,
+        offset: CHOICE_TAG_OFFSET,
+        value: tag,
+    });
+    store_record_fields(dst, record_type, values, statements);
+
+    Ok(ExprLoweringOutput {
+        value: dst,
+        prefer_move: false,
+    })
+}
+
+fn store_record_fields(
+    record: WasmLirLocalId,
+    record_type: WasmRecordTypeId,
+    values: Vec<(WasmRecordField, WasmLirLocalId)>,
+    statements: &mut Vec<WasmLirStmt>,
+) {
+    for (slot, value) in values {
+        // Unit fields have no storage; their value was still evaluated for side effects.
+        if slot.abi == WasmAbiType::Void {
+            continue;
+        }
+        statements.push(WasmLirStmt::RecordStore {
+            record,
+            record_type,
+            offset: slot.offset,
+            value,
+        });
+    }
+}
+
+pub(crate) fn struct_field_slot(
+    context: &WasmFunctionLoweringContext<'_, '_>,
+    field: FieldId,
+) -> Result<(WasmRecordTypeId, WasmRecordField), CompilerError> {
+    context
+        .module_context
+        .record_layouts
+        .field(field)
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm lowering has no record layout for field {field:?}"
+            ))
+        })
+}
+
+fn choice_field_slot(
+    context: &WasmFunctionLoweringContext<'_, '_>,
+    choice_id: ChoiceId,
+    variant_index: usize,
+    field_index: usize,
+) -> Result<(WasmRecordTypeId, WasmRecordField), CompilerError> {
+    context
+        .module_context
+        .record_layouts
+        .choice_layout(choice_id)
+        .and_then(|(record, layout)| {
+            let slot = layout.variants.get(variant_index)?.get(field_index)?;
+            Some((record.id, *slot))
+        })
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm lowering has no record layout for field {field_index} of variant \
+                 {variant_index} in choice {choice_id:?}"
+            ))
+        })
+}
+
+/// Resolves `field` to a builtin `Error` field, or `None` for any other struct field.
+fn builtin_error_field(
+    context: &WasmFunctionLoweringContext<'_, '_>,
+    field: FieldId,
+) -> Option<WasmErrorField> {
+    let string_table = context.module_context.string_table;
+    let path = context
+        .module_context
+        .hir_module
+        .side_table
+        .field_name_path(field)?;
+    if path.len() != 2 || path.parent()?.name_str(string_table)? != ERROR_TYPE_NAME {
+        return None;
+    }
+    match path.name_str(string_table)? {
+        ERROR_FIELD_MESSAGE => Some(WasmErrorField::Message),
+        ERROR_FIELD_CODE => Some(WasmErrorField::Code),
+        _ => None,
+    }
+}
+
+fn lower_map_literal(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    expression: &HirExpression,
+    entries: &[HirMapEntry],
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<ExprLoweringOutput, CompilerError> {
+    // WHAT: build the map with one `set` per entry, in source order.
+    // WHY: later duplicate keys then replace earlier values in place, as in the JS runtime.
+    let key_kind = map_key_kind(context, expression.ty)?;
+    let dst = context.alloc_local(None, WasmAbiType::Handle, WasmLocalRole::ValueHandle);
+    statements.push(WasmLirStmt::MapNew { dst, key_kind });
+
+    for entry in entries {
+        let key = lower_expression(context, &entry.key, statements)?;
+        let value = lower_expression(context, &entry.value, statements)?;
+        statements.push(WasmLirStmt::MapSet {
+            dst: None,
+            map: dst,
+            key: key.value,
+            value: value.value,
+        });
+    }
+
+    Ok(ExprLoweringOutput {
+        value: dst,
+        prefer_move: false,
+    })
+}
+
+/// Picks how runtime map helpers compare keys of the map type `map_type`.
+fn map_key_kind(
+    context: &WasmFunctionLoweringContext<'_, '_>,
+    map_type: TypeId,
+) -> Result<WasmMapKeyKind, CompilerError> {
+    let shape = context
+        .module_context
+        .type_environment
+        .map_shape(map_type)
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm lowering expected a map type for map construction, found {map_type:?}"
+            ))
+        })?;
+
+    // Builtin map keys are String, Int, Bool or Char, so handles are always strings.
+    match lower_type_to_abi(context.module_context, shape.key_type) {
+        WasmAbiType::Handle => Ok(WasmMapKeyKind::String),
+        WasmAbiType::I32 | WasmAbiType::I64 => Ok(WasmMapKeyKind::Scalar),
+        other => Err(lir_transformation_error(format!(
+            "Wasm lowering does not support map keys with ABI type {other:?}"
+        ))),
+    }
+}
+
 fn lower_binary_expression(
     context: &mut WasmFunctionLoweringContext<'_, '_>,
     expression: &HirExpression,
@@ -541,26 +889,8 @@ fn lower_string_concat_expression(
             }
             _ => {
                 let lowered = lower_expression(context, chunk, statements)?;
-                let chunk_handle = match expression_abi(context, chunk) {
-                    WasmAbiType::Handle => lowered.value,
-                    WasmAbiType::I64 => {
-                        let converted = context.alloc_local(
-                            None,
-                            WasmAbiType::Handle,
-                            WasmLocalRole::ValueHandle,
-                        );
-                        statements.push(WasmLirStmt::StringFromI64 {
-                            dst: converted,
-                            value: lowered.value,
-                        });
-                        converted
-                    }
-                    other => {
-                        return Err(lir_transformation_error(format!(
-                            "Wasm lowering string concatenation requires handle-compatible chunks, found {other:?}"
-                        )));
-                    }
-                };
+                let chunk_handle =
+                    lower_string_concat_chunk_handle(context, chunk, lowered.value, statements)?;
                 statements.push(WasmLirStmt::StringPushHandle {
                     buffer,
                     handle: chunk_handle,
@@ -578,6 +908,45 @@ fn lower_string_concat_expression(
     })
 }
 
+/// Converts one lowered template chunk into a string handle the buffer can append.
+///
+/// WHAT: handles pass through; scalar chunks use the same conversion as their `String` cast.
+/// WHY: template interpolation of `Bool`, `Char` and `Float` must render exactly like the JS
+/// backend's `String(...)` coercion, which the builtin cast policies already match.
+fn lower_string_concat_chunk_handle(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    chunk: &HirExpression,
+    value: WasmLirLocalId,
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<WasmLirLocalId, CompilerError> {
+    let builtins = context.module_context.type_environment.builtins();
+    let (bool_type, char_type, float_type) = (builtins.bool, builtins.char, builtins.float);
+    let policy = match expression_abi(context, chunk) {
+        WasmAbiType::Handle => return Ok(value),
+        WasmAbiType::I64 => {
+            let converted =
+                context.alloc_local(None, WasmAbiType::Handle, WasmLocalRole::ValueHandle);
+            statements.push(WasmLirStmt::StringFromI64 {
+                dst: converted,
+                value,
+            });
+            return Ok(converted);
+        }
+        WasmAbiType::I32 if chunk.ty == bool_type => BuiltinCastPolicyId::BoolToString,
+        WasmAbiType::I32 if chunk.ty == char_type => BuiltinCastPolicyId::CharToString,
+        WasmAbiType::F64 if chunk.ty == float_type => BuiltinCastPolicyId::FloatToString,
+        other => {
+            return Err(lir_transformation_error(format!(
+                "Wasm lowering string concatenation requires handle-compatible chunks, found {other:?}"
+            )));
+        }
+    };
+
+    let converted = context.alloc_local(None, WasmAbiType::Handle, WasmLocalRole::ValueHandle);
+    lower_cast(context, policy, value, converted, statements);
+    Ok(converted)
+}
+
 fn collect_string_concat_chunks<'a>(
     context: &WasmFunctionLoweringContext<'_, '_>,
     expression: &'a HirExpression,
@@ -602,24 +971,35 @@ fn is_handle_type(
     matches!(expression_abi(context, expression), WasmAbiType::Handle)
 }
 
-fn is_empty_string_collection(
-    context: &WasmFunctionLoweringContext<'_, '_>,
-    expression: &HirExpression,
+fn lower_collection_literal(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
     items: &[HirExpression],
-) -> bool {
-    if !items.is_empty() {
-        return false;
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<ExprLoweringOutput, CompilerError> {
+    // WHAT: build the vec with one push per element, in source order.
+    // WHY: the vec runtime stores i32 handles, so only handle elements fit its layout; backend
+    // feature validation rejects reachable scalar-element literals before lowering.
+    let mut elements = Vec::with_capacity(items.len());
+    for item in items {
+        let abi = expression_abi(context, item);
+        if abi != WasmAbiType::Handle {
+            return Err(lir_transformation_error(format!(
+                "Wasm lowering only supports collection literals of handle elements, found {abi:?}"
+            )));
+        }
+        elements.push(lower_expression(context, item, statements)?.value);
     }
 
-    let Some(element) = context
-        .module_context
-        .type_environment
-        .collection_element_type(expression.ty)
-    else {
-        return false;
-    };
+    let dst = context.alloc_local(None, WasmAbiType::Handle, WasmLocalRole::ValueHandle);
+    statements.push(WasmLirStmt::VecNew { dst });
+    for handle in elements {
+        statements.push(WasmLirStmt::VecPushHandle { vec: dst, handle });
+    }
 
-    element == context.module_context.type_environment.builtins().string
+    Ok(ExprLoweringOutput {
+        value: dst,
+        prefer_move: false,
+    })
 }
 
 fn expression_abi(
@@ -629,12 +1009,31 @@ fn expression_abi(
     lower_type_to_abi(context.module_context, expression.ty)
 }
 
+/// Lowers a place to the local holding its current value.
+///
+/// WHAT: direct locals resolve to their mapped local; field projections load each struct record
+/// along the path into temps.
+pub(crate) fn lower_place_value(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    place: &HirPlace,
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<WasmLirLocalId, CompilerError> {
+    match place {
+        HirPlace::Field { base, field } => {
+            let record = lower_place_value(context, base, statements)?;
+            load_record_field(context, record, *field, statements)
+        }
+        HirPlace::Local(_) | HirPlace::Index { .. } => lower_place_local(context, place),
+    }
+}
+
 fn lower_place_local(
     context: &WasmFunctionLoweringContext<'_, '_>,
     place: &HirPlace,
 ) -> Result<WasmLirLocalId, CompilerError> {
-    // WHAT: place lowering currently supports direct locals only.
-    // WHY: field/index projections require additional memory-model and layout work.
+    // WHAT: resolves places that name a local directly.
+    // WHY: field projections go through `lower_place_value`; index projections need collection
+    // layout work.
     match place {
         HirPlace::Local(local_id) => context.local_map.get(local_id).copied().ok_or_else(|| {
             lir_transformation_error(format!(
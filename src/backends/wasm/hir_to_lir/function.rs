@@ -2,9 +2,11 @@
 
 use crate::backends::error_types::lir_transformation_error;
 use crate::backends::wasm::hir_to_lir::context::{
-    WasmFunctionLoweringContext, WasmLirLoweringContext, lower_type_to_abi,
+    WasmFunctionLoweringContext, WasmLirLoweringContext, lower_type_to_abi, tuple_slot_abis,
+};
+use crate::backends::wasm::hir_to_lir::ownership::{
+    insert_advisory_drops, retain_provably_unique_drops,
 };
-use crate::backends::wasm::hir_to_lir::ownership::insert_advisory_drops;
 use crate::backends::wasm::hir_to_lir::stmt::lower_statement;
 use crate::backends::wasm::hir_to_lir::terminator::lower_terminator;
 use crate::backends::wasm::lir::function::{WasmLirFunction, WasmLirFunctionOrigin};
@@ -77,16 +79,16 @@ pub(crate) fn lower_function(
             lower_statement(&mut function_context, statement, &mut lowered_statements)?;
         }
 
-        // WHAT: materialize borrow checker advisory drop points.
-        // WHY: ownership remains optimization-only, but LIR still records possible drop sites for
-        // ownership-aware runtime lowering.
-        insert_advisory_drops(&function_context, *block_id, &mut lowered_statements);
         let lowered_terminator = lower_terminator(
             &mut function_context,
             &hir_block.terminator,
             &mut lowered_statements,
         )?;
 
+        // WHAT: materialize borrow checker drop sites after any terminator operand setup.
+        // WHY: the drop must follow the block's last read of the local it may release.
+        insert_advisory_drops(&function_context, *block_id, &mut lowered_statements);
+
         let Some(lir_block) = function_context.block_mut(*block_id) else {
             return Err(lir_transformation_error(format!(
                 "Wasm lowering could not resolve lowered block mapping for {block_id:?}",
@@ -96,6 +98,10 @@ pub(crate) fn lower_function(
         lir_block.terminator = lowered_terminator;
     }
 
+    // WHAT: keep only drops whose local provably owns its value alone.
+    // WHY: every kept `DropIfOwned` releases memory at runtime.
+    retain_provably_unique_drops(&mut function_context.lir_function);
+
     Ok(function_context.lir_function)
 }
 
@@ -103,8 +109,6 @@ fn lower_function_signature(
     context: &WasmLirLoweringContext<'_>,
     function: &HirFunction,
 ) -> Result<WasmLirSignature, CompilerError> {
-    // Multi-value lowering is intentionally incomplete. We only support zero/one result here and
-    // keep explicit error paths for unsupported shapes.
     let local_type_map = collect_local_type_map(
         context,
         &collect_reachable_blocks(context, function)?,
@@ -122,11 +126,26 @@ fn lower_function_signature(
         params.push(lower_type_to_abi(context, type_id));
     }
 
-    let result_abi = lower_type_to_abi(context, function.return_type);
-    let results = if matches!(result_abi, WasmAbiType::Void) {
-        Vec::new()
-    } else {
-        vec![result_abi]
+    // WHAT: multi-return functions return one Wasm result per tuple slot.
+    // WHY: keeps tuples on the operand stack instead of spilling them through linear memory.
+    let results = match tuple_slot_abis(context, function.return_type) {
+        Some(slot_abis) => {
+            if !context.request.target_features.enable_multi_value {
+                return Err(lir_transformation_error(format!(
+                    "Wasm lowering of multi-return function {:?} requires target_features.enable_multi_value",
+                    function.id
+                )));
+            }
+            slot_abis
+        }
+        None => {
+            let result_abi = lower_type_to_abi(context, function.return_type);
+            if matches!(result_abi, WasmAbiType::Void) {
+                Vec::new()
+            } else {
+                vec![result_abi]
+            }
+        }
     };
 
     Ok(WasmLirSignature { params, results })
@@ -172,6 +191,22 @@ fn alloc_function_locals(
         }
 
         let type_id = local_type_map[&local_id];
+        if let Some(slot_abis) = tuple_slot_abis(context.module_context, type_id) {
+            let slots = slot_abis
+                .into_iter()
+                .enumerate()
+                .map(|(slot, abi_type)| {
+                    context.alloc_local(
+                        Some(format!("local_{}_{slot}", local_id.0)),
+                        abi_type,
+                        WasmLocalRole::UserLocal,
+                    )
+                })
+                .collect();
+            context.tuple_local_slots.insert(local_id, slots);
+            continue;
+        }
+
         let abi_type = lower_type_to_abi(context.module_context, type_id);
         let lir_local = context.alloc_local(
             Some(format!("local_{}", local_id.0)),
@@ -3,11 +3,18 @@
 use crate::backends::error_types::lir_transformation_error;
 use crate::backends::wasm::hir_to_lir::context::WasmLirLoweringContext;
 use crate::backends::wasm::lir::linkage::{WasmImport, WasmImportKind};
-use crate::backends::wasm::lir::types::{WasmImportId, WasmLirSignature};
+use crate::backends::wasm::lir::types::{WasmAbiType, WasmImportId, WasmLirSignature};
+use crate::backends::wasm::request::WasmHostInterface;
 use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::wasi::WasiAdapter;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
-use crate::compiler_frontend::external_packages::{CallTarget, ExternalFunctionId};
+use crate::compiler_frontend::external_packages::{
+    CallTarget, ExternalAbiType, ExternalFunctionDef, ExternalFunctionId, ExternalSignatureType,
+    ExternalWasmLowering,
+};
 use crate::compiler_frontend::hir::ids::BlockId;
+use crate::compiler_frontend::hir::numeric::HirNumericOp;
 use crate::compiler_frontend::hir::statements::HirStatementKind;
 use rustc_hash::FxHashSet;
 
@@ -25,20 +32,102 @@ pub(crate) fn register_required_host_imports(
         }
 
         for statement in &block.statements {
-            if let HirStatementKind::Call {
-                target: CallTarget::ExternalFunction(id),
-                ..
-            } = &statement.kind
-            {
-                let host_function = resolve_host_function_id(context, *id)?;
-                ensure_host_import(context, host_function);
+            match &statement.kind {
+                HirStatementKind::Call {
+                    target: CallTarget::ExternalFunction(id),
+                    ..
+                } => {
+                    if let Some(adapter) = wasi_adapter_for(context, *id)? {
+                        if let Some(import) = adapter.required_import() {
+                            ensure_host_import(context, import);
+                        }
+                    } else {
+                        ensure_external_import(context, *id)?;
+                    }
+                }
+                HirStatementKind::NumericOp {
+                    op: HirNumericOp::FloatPow,
+                    ..
+                } => {
+                    ensure_js_host_import(context, WasmHostFunction::MathPow, "float '**'")?;
+                }
+                HirStatementKind::CastOp {
+                    policy: BuiltinCastPolicyId::StringToFloat,
+                    ..
+                } => {
+                    ensure_js_host_import(
+                        context,
+                        WasmHostFunction::ParseFloat,
+                        "String -> Float casts",
+                    )?;
+                }
+                _ => {}
             }
         }
     }
 
+    // The synthesized `_start` writes the entry fragments itself.
+    if context
+        .request
+        .export_policy
+        .command_start
+        .as_ref()
+        .is_some_and(|start| !start.output.is_empty())
+    {
+        ensure_host_import(context, WasmHostFunction::WasiFdWrite);
+    }
+
     Ok(())
 }
 
+/// WASI adapter of an external function, or `None` when it lowers to a `host` import.
+///
+/// WHY: adapter lowerings only exist for the WASI command target, so meeting one under the JS
+/// host interface means validation let an unsupported call through.
+pub(crate) fn wasi_adapter_for(
+    context: &WasmLirLoweringContext<'_>,
+    id: ExternalFunctionId,
+) -> Result<Option<WasiAdapter>, CompilerError> {
+    let registry = &context.request.external_package_registry;
+    let Some(&ExternalWasmLowering::WasiAdapter(name)) = registry
+        .get_function_by_id(id)
+        .and_then(|function| function.lowerings.wasm.as_ref())
+    else {
+        return Ok(None);
+    };
+
+    if context.request.host_interface != WasmHostInterface::WasiPreview1 {
+        return Err(lir_transformation_error(format!(
+            "Wasm lowering of '{}' requires the WASI command target",
+            id.name()
+        )));
+    }
+
+    WasiAdapter::from_lowering_name(name)
+        .map(Some)
+        .ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm lowering does not know the WASI adapter '{name}' of '{}'",
+                id.name()
+            ))
+        })
+}
+
+fn ensure_js_host_import(
+    context: &mut WasmLirLoweringContext<'_>,
+    function: WasmHostFunction,
+    feature: &str,
+) -> Result<WasmImportId, CompilerError> {
+    // WHY: `host.*` math imports come from the JS glue, which a WASI command does not have.
+    if context.request.host_interface == WasmHostInterface::WasiPreview1 {
+        return Err(lir_transformation_error(format!(
+            "The WASI command target does not support {feature} yet"
+        )));
+    }
+
+    Ok(ensure_host_import(context, function))
+}
+
 pub(crate) fn resolve_host_call_import(
     context: &mut WasmLirLoweringContext<'_>,
     target: &CallTarget,
@@ -52,28 +141,122 @@ pub(crate) fn resolve_host_call_import(
         ));
     };
 
-    let host_function = resolve_host_function_id(context, *id)?;
-    Ok(ensure_host_import(context, host_function))
+    ensure_external_import(context, *id)
 }
 
-fn resolve_host_function_id(
-    context: &WasmLirLoweringContext<'_>,
+fn ensure_external_import(
+    context: &mut WasmLirLoweringContext<'_>,
     id: ExternalFunctionId,
-) -> Result<WasmHostFunction, CompilerError> {
-    // WHAT: map a host function id to its Wasm backend import identity.
+) -> Result<WasmImportId, CompilerError> {
+    // WHAT: map an external function with a Wasm lowering to a `host` module import.
     // WHY: ensures only explicitly supported host calls are lowered.
-    let function_name = context
-        .request
-        .external_package_registry
-        .get_function_by_id(id)
-        .map(|function| function.name.clone())
-        .unwrap_or_else(|| id.name().to_owned());
-    Err(lir_transformation_error(format!(
-        "Wasm backend does not yet support host function '{function_name}'"
-    )))
+    if let Some(import_id) = context.external_imports.get(&id).copied() {
+        return Ok(import_id);
+    }
+
+    let registry = &context.request.external_package_registry;
+    let Some(function) = registry.get_function_by_id(id) else {
+        return Err(lir_transformation_error(format!(
+            "Wasm backend does not yet support host function '{}'",
+            id.name()
+        )));
+    };
+    let Some(ExternalWasmLowering::HostFunction(item_name)) = function.lowerings.wasm else {
+        return Err(lir_transformation_error(format!(
+            "Wasm backend does not yet support host function '{}'",
+            function.name
+        )));
+    };
+    if function.is_fallible() {
+        return Err(lir_transformation_error(format!(
+            "Wasm backend does not yet support fallible host function '{}'",
+            function.name
+        )));
+    }
+
+    let mut params = Vec::with_capacity(function.parameters.len());
+    for parameter in &function.parameters {
+        if let Some(abi) = external_signature_abi(context, &parameter.language_type, function)? {
+            params.push(abi);
+        }
+    }
+    let mut results = Vec::with_capacity(function.returns.len());
+    for slot in &function.returns {
+        if let Some(abi) = external_signature_abi(context, &slot.value_type, function)? {
+            results.push(abi);
+        }
+    }
+    if results.len() > 1 && !context.request.target_features.enable_multi_value {
+        return Err(lir_transformation_error(format!(
+            "Wasm lowering of multi-return host function '{}' requires target_features.enable_multi_value",
+            function.name
+        )));
+    }
+
+    let import_id = WasmImportId(context.lir_module.imports.len() as u32);
+    context.lir_module.imports.push(WasmImport {
+        id: import_id,
+        module_name: EXTERNAL_IMPORT_MODULE.to_owned(),
+        item_name: item_name.to_owned(),
+        kind: WasmImportKind::Function(WasmLirSignature { params, results }),
+    });
+    context.external_imports.insert(id, import_id);
+
+    Ok(import_id)
 }
 
-fn ensure_host_import(
+/// Import module shared by every external package host function.
+const EXTERNAL_IMPORT_MODULE: &str = "host";
+
+/// Wasm ABI of one external signature slot, or `None` for `Void`.
+///
+/// Must agree with `lower_type_to_abi` for the same Beanstalk type, because call sites pass
+/// ordinary lowered locals.
+fn external_signature_abi(
+    context: &WasmLirLoweringContext<'_>,
+    signature_type: &ExternalSignatureType,
+    function: &ExternalFunctionDef,
+) -> Result<Option<WasmAbiType>, CompilerError> {
+    let opaque = if context.request.target_features.enable_reference_types {
+        WasmAbiType::ExternRef
+    } else {
+        WasmAbiType::Handle
+    };
+
+    let abi = match signature_type {
+        ExternalSignatureType::Abi(ExternalAbiType::Void) => return Ok(None),
+        ExternalSignatureType::Abi(ExternalAbiType::I32) => WasmAbiType::I64,
+        ExternalSignatureType::Abi(ExternalAbiType::F64) => WasmAbiType::F64,
+        ExternalSignatureType::Abi(ExternalAbiType::Bool | ExternalAbiType::Char) => {
+            WasmAbiType::I32
+        }
+        ExternalSignatureType::Abi(ExternalAbiType::Utf8Str)
+        | ExternalSignatureType::StringContent => WasmAbiType::Handle,
+        ExternalSignatureType::External(type_id)
+            if context
+                .request
+                .external_package_registry
+                .get_type_by_id(*type_id)
+                .is_some_and(|definition| definition.abi_type == ExternalAbiType::F64) =>
+        {
+            WasmAbiType::F64
+        }
+        ExternalSignatureType::Abi(ExternalAbiType::Handle)
+        | ExternalSignatureType::External(_) => opaque,
+        ExternalSignatureType::Abi(ExternalAbiType::Inferred)
+        | ExternalSignatureType::BuiltinError
+        | ExternalSignatureType::Optional(_) => {
+            return Err(lir_transformation_error(format!(
+                "Wasm backend does not yet support the signature of host function '{}'",
+                function.name
+            )));
+        }
+    };
+
+    Ok(Some(abi))
+}
+
+pub(crate) fn ensure_host_import(
     context: &mut WasmLirLoweringContext<'_>,
     function: WasmHostFunction,
 ) -> WasmImportId {
@@ -101,5 +284,24 @@ fn ensure_host_import(
 fn host_function_signature(function: WasmHostFunction) -> WasmLirSignature {
     // WHAT: canonical ABI signature for each supported host function.
     // WHY: keeps import registration and signature assignment in one explicit place.
-    match function {}
+    match function {
+        WasmHostFunction::MathPow => WasmLirSignature {
+            params: vec![WasmAbiType::F64, WasmAbiType::F64],
+            results: vec![WasmAbiType::F64],
+        },
+        WasmHostFunction::ParseFloat => WasmLirSignature {
+            params: vec![WasmAbiType::Handle],
+            results: vec![WasmAbiType::F64],
+        },
+        // (fd, iovs, iovs_len, nbytes_out) -> errno
+        WasmHostFunction::WasiFdWrite | WasmHostFunction::WasiFdRead => WasmLirSignature {
+            params: vec![WasmAbiType::I32; 4],
+            results: vec![WasmAbiType::I32],
+        },
+        // (clock_id, precision, time_out) -> errno
+        WasmHostFunction::WasiClockTimeGet => WasmLirSignature {
+            params: vec![WasmAbiType::I32, WasmAbiType::I64, WasmAbiType::I32],
+            results: vec![WasmAbiType::I32],
+        },
+    }
 }
@@ -10,7 +10,9 @@ pub(crate) mod expr;
 pub(crate) mod function;
 pub(crate) mod imports;
 pub(crate) mod module;
+pub(crate) mod monomorphize;
 pub(crate) mod ownership;
 pub(crate) mod static_data;
 pub(crate) mod stmt;
 pub(crate) mod terminator;
+pub(crate) mod tuples;
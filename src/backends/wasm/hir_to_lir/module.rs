@@ -1,9 +1,12 @@
 //! Module-level orchestration for HIR -> LIR lowering.
 
 use crate::backends::wasm::hir_to_lir::context::WasmLirLoweringContext;
-use crate::backends::wasm::hir_to_lir::exports::synthesize_export_wrappers;
+use crate::backends::wasm::hir_to_lir::exports::{
+    synthesize_command_start, synthesize_export_wrappers,
+};
 use crate::backends::wasm::hir_to_lir::function::lower_function;
 use crate::backends::wasm::hir_to_lir::imports::register_required_host_imports;
+use crate::backends::wasm::hir_to_lir::monomorphize::summarize_generic_instances;
 use crate::backends::wasm::lir::types::WasmLirFunctionId;
 use crate::backends::wasm::request::{WasmBackendRequest, WasmFunctionEmissionPolicy};
 use crate::compiler_frontend::analysis::borrow_checker::BorrowFacts;
@@ -50,11 +53,16 @@ pub(crate) fn lower_hir_module_to_lir(
             .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
         context.lir_module.functions.push(lowered);
     }
+    context.lir_module.record_types = context.record_layouts.record_types().to_vec();
+    context.lir_module.generic_instances =
+        summarize_generic_instances(&context, &function_selection.functions);
 
     // WHAT: synthesize post-lowering export wrapper functions.
     // WHY: wrapper boundary keeps user bodies internal while export ABI stays stable.
     synthesize_export_wrappers(&mut context)
         .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
+    synthesize_command_start(&mut context)
+        .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
 
     Ok(context.lir_module)
 }
@@ -81,7 +89,19 @@ fn select_functions_for_lowering(
         WasmFunctionEmissionPolicy::ReachableFromExports => {
             let reachability = collect_hir_reachability(HirReachabilityInput {
                 hir: hir_module,
-                root_functions: request.export_policy.exported_functions.clone(),
+                root_functions: request
+                    .export_policy
+                    .exported_functions
+                    .iter()
+                    .copied()
+                    .chain(
+                        request
+                            .export_policy
+                            .command_start
+                            .as_ref()
+                            .map(|start| start.function),
+                    )
+                    .collect(),
             })
             .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
 
@@ -0,0 +1,326 @@
+//! Monomorphized record layouts and generic instance reporting for HIR -> Wasm LIR lowering.
+//!
+//! WHAT: plans one record layout and LIR record type per concrete HIR struct and choice, and
+//!       summarizes how many concrete instances each generic function, struct and choice produced.
+//! WHY: the AST emits one function per generic function instance, and HIR registers one struct or
+//!      choice entry per generic instantiation key. Each entry's `frontend_type_id` is the
+//!      canonical `TypeEnvironment` instance, so its field types are already substituted. Giving
+//!      every entry its own layout is what lets `Box of Int` and `Box of String` coexist in
+//!      linear memory without a boxed generic representation.
+
+use crate::backends::wasm::hir_to_lir::context::{WasmLirLoweringContext, lower_type_to_abi};
+use crate::backends::wasm::lir::module::{
+    WasmGenericInstance, WasmGenericInstanceSummary, WasmGenericKind, WasmRecordType,
+};
+use crate::backends::wasm::lir::types::{WasmAbiType, WasmRecordTypeId};
+use crate::backends::wasm::runtime::records::{
+    CHOICE_TAG_OFFSET, WasmChoiceLayout, WasmRecordField, plan_choice_layout, plan_struct_layout,
+};
+use crate::compiler_frontend::ast::generic_functions::GENERIC_FUNCTION_INSTANCE_PREFIX;
+use crate::compiler_frontend::datatypes::definitions::TypeDefinition;
+use crate::compiler_frontend::datatypes::display::display_type;
+use crate::compiler_frontend::datatypes::ids::TypeId;
+use crate::compiler_frontend::hir::functions::HirFunction;
+use crate::compiler_frontend::hir::ids::{ChoiceId, FieldId, LocalId, StructId};
+use rustc_hash::FxHashMap;
+
+/// Record layouts and record types for every concrete struct and choice in the HIR module.
+#[derive(Debug, Default)]
+pub(crate) struct WasmRecordLayouts {
+    structs: FxHashMap<StructId, WasmRecordTypeId>,
+    /// Owning record type and slot by `FieldId`; field ids are unique across all structs.
+    fields: FxHashMap<FieldId, (WasmRecordTypeId, WasmRecordField)>,
+    choices: FxHashMap<ChoiceId, (WasmRecordTypeId, WasmChoiceLayout)>,
+    /// Record types indexed by `WasmRecordTypeId`.
+    record_types: Vec<WasmRecordType>,
+}
+
+impl WasmRecordLayouts {
+    pub(crate) fn struct_record(&self, struct_id: StructId) -> Option<&WasmRecordType> {
+        let record_type = self.structs.get(&struct_id)?;
+        self.record_types.get(record_type.0 as usize)
+    }
+
+    pub(crate) fn field(&self, field_id: FieldId) -> Option<(WasmRecordTypeId, WasmRecordField)> {
+        self.fields.get(&field_id).copied()
+    }
+
+    pub(crate) fn choice_layout(
+        &self,
+        choice_id: ChoiceId,
+    ) -> Option<(&WasmRecordType, &WasmChoiceLayout)> {
+        let (record_type, layout) = self.choices.get(&choice_id)?;
+        Some((self.record_types.get(record_type.0 as usize)?, layout))
+    }
+
+    pub(crate) fn record_types(&self) -> &[WasmRecordType] {
+        &self.record_types
+    }
+
+    fn push_record_type(
+        &mut self,
+        debug_name: String,
+        size: u32,
+        slots: Vec<WasmRecordField>,
+    ) -> WasmRecordTypeId {
+        let id = WasmRecordTypeId(self.record_types.len() as u32);
+        self.record_types.push(WasmRecordType {
+            id,
+            debug_name,
+            size,
+            slots,
+        });
+        id
+    }
+}
+
+/// Plans record layouts for every HIR struct and choice entry.
+pub(crate) fn plan_record_layouts(context: &WasmLirLoweringContext<'_>) -> WasmRecordLayouts {
+    let mut layouts = WasmRecordLayouts::default();
+
+    for hir_struct in &context.hir_module.structs {
+        let field_abis = hir_struct
+            .fields
+            .iter()
+            .map(|field| lower_type_to_abi(context, field.ty))
+            .collect::<Vec<_>>();
+        let layout = plan_struct_layout(&field_abis);
+
+        let record_type = layouts.push_record_type(
+            record_debug_name(context, hir_struct.frontend_type_id),
+            layout.size,
+            storage_slots(layout.fields.iter()),
+        );
+        for (field, slot) in hir_struct.fields.iter().zip(&layout.fields) {
+            layouts.fields.insert(field.id, (record_type, *slot));
+        }
+        layouts.structs.insert(hir_struct.id, record_type);
+    }
+
+    for choice in &context.hir_module.choices {
+        let variant_field_abis = choice
+            .variants
+            .iter()
+            .map(|variant| {
+                variant
+                    .fields
+                    .iter()
+                    .map(|field| lower_type_to_abi(context, field.ty))
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        let layout = plan_choice_layout(&variant_field_abis);
+
+        let tag = WasmRecordField {
+            offset: CHOICE_TAG_OFFSET,
+            abi: WasmAbiType::I32,
+        };
+        let slots = storage_slots(std::iter::once(&tag).chain(layout.variants.iter().flatten()));
+        let record_type = layouts.push_record_type(
+            record_debug_name(context, choice.frontend_type_id),
+            layout.size,
+            slots,
+        );
+        layouts.choices.insert(choice.id, (record_type, layout));
+    }
+
+    layouts
+}
+
+/// Distinct non-unit slots in offset order.
+fn storage_slots<'a>(fields: impl Iterator<Item = &'a WasmRecordField>) -> Vec<WasmRecordField> {
+    let mut slots = Vec::new();
+    for field in fields {
+        if field.abi != WasmAbiType::Void && !slots.contains(field) {
+            slots.push(*field);
+        }
+    }
+    slots.sort_by_key(|slot| slot.offset);
+    slots
+}
+
+fn record_debug_name(context: &WasmLirLoweringContext<'_>, type_id: TypeId) -> String {
+    display_type(type_id, context.type_environment, context.string_table)
+}
+
+/// Summarizes the concrete instances lowered for each generic declaration.
+///
+/// WHAT: function instances are recognized by their synthetic instance path and sized by lowered
+///       LIR statements; struct and choice instances are recognized as `GenericInstance` types
+///       and sized by record bytes.
+/// WHY: monomorphization trades code size for a generic-free runtime, so builds need to see
+///      which generics multiply and by how much.
+pub(crate) fn summarize_generic_instances(
+    context: &WasmLirLoweringContext<'_>,
+    functions: &[HirFunction],
+) -> Vec<WasmGenericInstanceSummary> {
+    let mut summaries = Vec::new();
+
+    let instance_functions = functions
+        .iter()
+        .filter_map(|function| {
+            generic_function_name(context, function).map(|name| (name, function))
+        })
+        .collect::<Vec<_>>();
+    if !instance_functions.is_empty() {
+        let local_types = collect_local_types(context);
+
+        for (generic_name, function) in instance_functions {
+            let name = function_instance_name(context, &generic_name, function, &local_types);
+            let size = context
+                .function_map
+                .get(&function.id)
+                .and_then(|id| context.lir_module.functions.get(id.0 as usize))
+                .map(|lowered| {
+                    lowered
+                        .blocks
+                        .iter()
+                        .map(|block| block.statements.len() as u32)
+                        .sum()
+                })
+                .unwrap_or(0);
+            push_instance(
+                &mut summaries,
+                WasmGenericKind::Function,
+                generic_name,
+                WasmGenericInstance { name, size },
+            );
+        }
+    }
+
+    for hir_struct in &context.hir_module.structs {
+        let Some(generic_name) = generic_type_name(context, hir_struct.frontend_type_id) else {
+            continue;
+        };
+        let size = context
+            .record_layouts
+            .struct_record(hir_struct.id)
+            .map_or(0, |record| record.size);
+        push_instance(
+            &mut summaries,
+            WasmGenericKind::Struct,
+            generic_name,
+            type_instance(context, hir_struct.frontend_type_id, size),
+        );
+    }
+
+    for choice in &context.hir_module.choices {
+        let Some(generic_name) = generic_type_name(context, choice.frontend_type_id) else {
+            continue;
+        };
+        let size = context
+            .record_layouts
+            .choice_layout(choice.id)
+            .map_or(0, |(record, _)| record.size);
+        push_instance(
+            &mut summaries,
+            WasmGenericKind::Choice,
+            generic_name,
+            type_instance(context, choice.frontend_type_id, size),
+        );
+    }
+
+    summaries
+}
+
+fn push_instance(
+    summaries: &mut Vec<WasmGenericInstanceSummary>,
+    kind: WasmGenericKind,
+    generic_name: String,
+    instance: WasmGenericInstance,
+) {
+    if let Some(summary) = summaries
+        .iter_mut()
+        .find(|summary| summary.kind == kind && summary.generic_name == generic_name)
+    {
+        summary.instances.push(instance);
+        return;
+    }
+
+    summaries.push(WasmGenericInstanceSummary {
+        kind,
+        generic_name,
+        instances: vec![instance],
+    });
+}
+
+/// Template name of a generic function instance, or `None` for ordinary functions.
+fn generic_function_name(
+    context: &WasmLirLoweringContext<'_>,
+    function: &HirFunction,
+) -> Option<String> {
+    let string_table = context.string_table;
+    let path = context
+        .hir_module
+        .side_table
+        .function_name_path(function.id)?;
+    if !path
+        .name_str(string_table)?
+        .starts_with(GENERIC_FUNCTION_INSTANCE_PREFIX)
+    {
+        return None;
+    }
+    path.parent()?.name_str(string_table).map(str::to_owned)
+}
+
+/// Base declaration name of a generic struct or choice instance type.
+fn generic_type_name(context: &WasmLirLoweringContext<'_>, type_id: TypeId) -> Option<String> {
+    let environment = context.type_environment;
+    let Some(TypeDefinition::GenericInstance(instance)) = environment.get(type_id) else {
+        return None;
+    };
+    environment
+        .nominal_path_by_id(instance.base)?
+        .name_str(context.string_table)
+        .map(str::to_owned)
+}
+
+fn type_instance(
+    context: &WasmLirLoweringContext<'_>,
+    type_id: TypeId,
+    size: u32,
+) -> WasmGenericInstance {
+    WasmGenericInstance {
+        name: record_debug_name(context, type_id),
+        size,
+    }
+}
+
+fn function_instance_name(
+    context: &WasmLirLoweringContext<'_>,
+    generic_name: &str,
+    function: &HirFunction,
+    local_types: &FxHashMap<LocalId, TypeId>,
+) -> String {
+    let render =
+        |type_id: TypeId| display_type(type_id, context.type_environment, context.string_table);
+    let params = function
+        .params
+        .iter()
+        .map(|param| {
+            local_types
+                .get(param)
+                .map_or_else(|| "<unknown type>".to_owned(), |type_id| render(*type_id))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if function.return_type == context.type_environment.builtins().none {
+        format!("{generic_name}({params})")
+    } else {
+        format!(
+            "{generic_name}({params}) -> {}",
+            render(function.return_type)
+        )
+    }
+}
+
+fn collect_local_types(context: &WasmLirLoweringContext<'_>) -> FxHashMap<LocalId, TypeId> {
+    let mut local_types = FxHashMap::default();
+    for block in &context.hir_module.blocks {
+        for local in &block.locals {
+            local_types.entry(local.id).or_insert(local.ty);
+        }
+    }
+    local_types
+}
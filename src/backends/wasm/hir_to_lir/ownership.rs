@@ -1,9 +1,19 @@
 //! Ownership-scaffolding lowering helpers.
+//!
+//! WHAT: projects borrow checker drop sites into `DropIfOwned` statements, then keeps only the
+//! drops whose local provably owns its value alone.
+//! WHY: `DropIfOwned` releases memory (see `runtime::memory`). Borrow checker drop sites list
+//! every slot-holding local at a region exit, including locals that stay live, alias other
+//! locals or were stored elsewhere, so each drop must be proven before it may free anything.
 
 use crate::backends::wasm::hir_to_lir::context::WasmFunctionLoweringContext;
-use crate::backends::wasm::lir::instructions::WasmLirStmt;
+use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction};
+use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
+use crate::backends::wasm::lir::types::{WasmLirBlockId, WasmLirLocalId, WasmLocalRole};
+use crate::backends::wasm::runtime::wasi::WasiAdapter;
 use crate::compiler_frontend::analysis::borrow_checker::BorrowDropSiteKind;
 use crate::compiler_frontend::hir::ids::BlockId;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::BTreeSet;
 
 pub(crate) fn insert_advisory_drops(
@@ -12,8 +22,7 @@ pub(crate) fn insert_advisory_drops(
     statements: &mut Vec<WasmLirStmt>,
 ) {
     // WHAT: project borrow checker advisory sites into concrete `DropIfOwned` statements.
-    // WHY: the current backend keeps GC-first correctness while preserving ownership hooks for
-    // the runtime ownership model described in the memory-management design.
+    // WHY: `retain_provably_unique_drops` later filters them down to safe releases.
     let Some(drop_sites) = context
         .module_context
         .borrow_facts
@@ -49,3 +58,454 @@ pub(crate) fn insert_advisory_drops(
         }
     }
 }
+
+/// How one statement or terminator operand touches a local.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperandAccess {
+    /// Writes a handle to a block the statement just allocated.
+    DefineFresh,
+    /// Copies or moves another local's value in.
+    DefineFrom(WasmLirLocalId),
+    /// Any other write.
+    Define,
+    /// Reads the value without keeping it anywhere.
+    Borrow,
+    /// Reads the value into a position that may outlive the local: internal calls, stores,
+    /// carriers, returns, aliases and casts.
+    Escape,
+}
+
+/// Removes every `DropIfOwned` whose local is not provably the unique owner of its value at the
+/// drop point.
+///
+/// A local qualifies when it is not a parameter, none of its reads escape, it is dead after the
+/// drop, and every value it receives is a fresh string, vec, map or record: either allocated
+/// straight into it, or built in a temp that only borrows the value until handing it over and is
+/// dead afterwards.
+pub(crate) fn retain_provably_unique_drops(function: &mut WasmLirFunction) {
+    let mut candidates = FxHashSet::default();
+    for block in &function.blocks {
+        for statement in &block.statements {
+            if let WasmLirStmt::DropIfOwned { value } = statement {
+                candidates.insert(*value);
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return;
+    }
+
+    let facts = LocalFacts::collect(function);
+    let mut unique = candidates
+        .into_iter()
+        .filter(|local| facts.receives_only_fresh_values(*local))
+        .collect::<FxHashSet<_>>();
+    let mut tracked = unique.clone();
+    for local in &unique {
+        tracked.extend(facts.handoff_sources(*local));
+    }
+    let live_out = live_out_sets(function, &tracked);
+
+    // A temp still read after handing its value over would alias the new owner.
+    let mut shared_handoffs = FxHashSet::default();
+    for block in &function.blocks {
+        scan_block_backward(block, &live_out, &tracked, |statement, live_after| {
+            if let WasmLirStmt::Copy { dst, src } | WasmLirStmt::Move { dst, src } = statement
+                && unique.contains(dst)
+                && live_after.contains(src)
+            {
+                shared_handoffs.insert(*dst);
+            }
+        });
+    }
+    unique.retain(|local| !shared_handoffs.contains(local));
+
+    for block in &mut function.blocks {
+        let mut keep = Vec::with_capacity(block.statements.len());
+        scan_block_backward(block, &live_out, &tracked, |statement, live_after| {
+            keep.push(match statement {
+                WasmLirStmt::DropIfOwned { value } => {
+                    unique.contains(value) && !live_after.contains(value)
+                }
+                _ => true,
+            });
+        });
+
+        let mut keep = keep.into_iter().rev();
+        block.statements.retain(|_| keep.next().unwrap_or(true));
+    }
+}
+
+/// Visits a block's statements last to first with the tracked locals live after each one.
+fn scan_block_backward(
+    block: &WasmLirBlock,
+    live_out: &FxHashMap<WasmLirBlockId, FxHashSet<WasmLirLocalId>>,
+    tracked: &FxHashSet<WasmLirLocalId>,
+    mut visit: impl FnMut(&WasmLirStmt, &FxHashSet<WasmLirLocalId>),
+) {
+    let mut live = live_out.get(&block.id).cloned().unwrap_or_default();
+    visit_terminator_operands(&block.terminator, &mut |local, _| {
+        if tracked.contains(&local) {
+            live.insert(local);
+        }
+    });
+    for statement in block.statements.iter().rev() {
+        visit(statement, &live);
+        apply_backward_transfer(statement, tracked, &mut live);
+    }
+}
+
+/// Per-local definition and read summary for one function.
+struct LocalFacts {
+    params: FxHashSet<WasmLirLocalId>,
+    definitions: FxHashMap<WasmLirLocalId, Vec<OperandAccess>>,
+    escape_counts: FxHashMap<WasmLirLocalId, usize>,
+}
+
+impl LocalFacts {
+    fn collect(function: &WasmLirFunction) -> Self {
+        let mut facts = Self {
+            params: function
+                .locals
+                .iter()
+                .filter(|local| local.role == WasmLocalRole::Param)
+                .map(|local| local.id)
+                .collect(),
+            definitions: FxHashMap::default(),
+            escape_counts: FxHashMap::default(),
+        };
+
+        let mut record = |local: WasmLirLocalId, access: OperandAccess| match access {
+            OperandAccess::DefineFresh | OperandAccess::DefineFrom(_) | OperandAccess::Define => {
+                facts.definitions.entry(local).or_default().push(access);
+            }
+            OperandAccess::Borrow => {}
+            OperandAccess::Escape => {
+                *facts.escape_counts.entry(local).or_default() += 1;
+            }
+        };
+        for block in &function.blocks {
+            for statement in &block.statements {
+                visit_statement_operands(statement, &mut record);
+            }
+            visit_terminator_operands(&block.terminator, &mut record);
+        }
+
+        facts
+    }
+
+    fn receives_only_fresh_values(&self, local: WasmLirLocalId) -> bool {
+        if self.params.contains(&local) || self.escape_counts.contains_key(&local) {
+            return false;
+        }
+
+        self.definitions.get(&local).is_none_or(|definitions| {
+            definitions.iter().all(|definition| match definition {
+                OperandAccess::DefineFresh => true,
+                OperandAccess::DefineFrom(source) => {
+                    *source != local && self.is_fresh_handoff_temp(*source)
+                }
+                _ => false,
+            })
+        })
+    }
+
+    /// A temp whose every definition is fresh and whose only escaping read is the hand-over.
+    fn is_fresh_handoff_temp(&self, local: WasmLirLocalId) -> bool {
+        !self.params.contains(&local)
+            && self.escape_counts.get(&local).copied() == Some(1)
+            && self.definitions.get(&local).is_some_and(|definitions| {
+                definitions
+                    .iter()
+                    .all(|definition| *definition == OperandAccess::DefineFresh)
+            })
+    }
+
+    fn handoff_sources(&self, local: WasmLirLocalId) -> impl Iterator<Item = WasmLirLocalId> + '_ {
+        self.definitions
+            .get(&local)
+            .into_iter()
+            .flatten()
+            .filter_map(|definition| match definition {
+                OperandAccess::DefineFrom(source) => Some(*source),
+                _ => None,
+            })
+    }
+}
+
+/// Backward liveness of `tracked` locals at each block exit. Drops are neither reads nor writes.
+fn live_out_sets(
+    function: &WasmLirFunction,
+    tracked: &FxHashSet<WasmLirLocalId>,
+) -> FxHashMap<WasmLirBlockId, FxHashSet<WasmLirLocalId>> {
+    let mut live_in: FxHashMap<WasmLirBlockId, FxHashSet<WasmLirLocalId>> = FxHashMap::default();
+    let mut live_out: FxHashMap<WasmLirBlockId, FxHashSet<WasmLirLocalId>> = FxHashMap::default();
+    if tracked.is_empty() {
+        return live_out;
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in function.blocks.iter().rev() {
+            let mut live = FxHashSet::default();
+            for successor in terminator_successors(&block.terminator) {
+                if let Some(successor_live) = live_in.get(&successor) {
+                    live.extend(successor_live.iter().copied());
+                }
+            }
+            live_out.insert(block.id, live.clone());
+
+            visit_terminator_operands(&block.terminator, &mut |local, _| {
+                if tracked.contains(&local) {
+                    live.insert(local);
+                }
+            });
+            for statement in block.statements.iter().rev() {
+                apply_backward_transfer(statement, tracked, &mut live);
+            }
+
+            if live_in.get(&block.id) != Some(&live) {
+                live_in.insert(block.id, live);
+                changed = true;
+            }
+        }
+    }
+
+    live_out
+}
+
+fn apply_backward_transfer(
+    statement: &WasmLirStmt,
+    tracked: &FxHashSet<WasmLirLocalId>,
+    live: &mut FxHashSet<WasmLirLocalId>,
+) {
+    let mut defined = Vec::new();
+    let mut read = Vec::new();
+    visit_statement_operands(statement, &mut |local, access| {
+        if !tracked.contains(&local) {
+            return;
+        }
+        match access {
+            OperandAccess::DefineFresh | OperandAccess::DefineFrom(_) | OperandAccess::Define => {
+                defined.push(local);
+            }
+            OperandAccess::Borrow | OperandAccess::Escape => read.push(local),
+        }
+    });
+    for local in defined {
+        live.remove(&local);
+    }
+    live.extend(read);
+}
+
+fn terminator_successors(terminator: &WasmLirTerminator) -> Vec<WasmLirBlockId> {
+    match terminator {
+        WasmLirTerminator::Jump(target) => vec![*target],
+        WasmLirTerminator::Branch {
+            then_block,
+            else_block,
+            ..
+        } => vec![*then_block, *else_block],
+        WasmLirTerminator::Return { .. }
+        | WasmLirTerminator::ReturnMultiValue { .. }
+        | WasmLirTerminator::Trap => Vec::new(),
+    }
+}
+
+fn visit_terminator_operands(
+    terminator: &WasmLirTerminator,
+    visit: &mut impl FnMut(WasmLirLocalId, OperandAccess),
+) {
+    match terminator {
+        WasmLirTerminator::Branch { condition, .. } => visit(*condition, OperandAccess::Borrow),
+        WasmLirTerminator::Return { value: Some(value) } => visit(*value, OperandAccess::Escape),
+        WasmLirTerminator::ReturnMultiValue { values } => {
+            for value in values {
+                visit(*value, OperandAccess::Escape);
+            }
+        }
+        WasmLirTerminator::Jump(_)
+        | WasmLirTerminator::Return { value: None }
+        | WasmLirTerminator::Trap => {}
+    }
+}
+
+fn visit_statement_operands(
+    statement: &WasmLirStmt,
+    visit: &mut impl FnMut(WasmLirLocalId, OperandAccess),
+) {
+    use OperandAccess::{Borrow, Define, DefineFresh, Escape};
+
+    match statement {
+        WasmLirStmt::ConstI32 { dst, .. }
+        | WasmLirStmt::ConstI64 { dst, .. }
+        | WasmLirStmt::ConstF32 { dst, .. }
+        | WasmLirStmt::ConstF64 { dst, .. }
+        | WasmLirStmt::ConstStaticPtr { dst, .. }
+        | WasmLirStmt::ConstLength { dst, .. }
+        | WasmLirStmt::StringNewBuffer { dst } => visit(*dst, Define),
+        WasmLirStmt::VecNew { dst }
+        | WasmLirStmt::MapNew { dst, .. }
+        | WasmLirStmt::RecordNew { dst, .. } => visit(*dst, DefineFresh),
+        WasmLirStmt::Copy { dst, src } | WasmLirStmt::Move { dst, src } => {
+            visit(*src, Escape);
+            visit(*dst, OperandAccess::DefineFrom(*src));
+        }
+        // Host imports only borrow their arguments (see `js_bootstrap`): the module keeps
+        // ownership and releases them at its own drop sites. Internal callees may keep them.
+        WasmLirStmt::Call { dst, callee, args } => {
+            let access = call_argument_access(callee);
+            for arg in args {
+                visit(*arg, access);
+            }
+            if let Some(dst) = dst {
+                visit(*dst, Define);
+            }
+        }
+        WasmLirStmt::CallMultiValue { dsts, callee, args } => {
+            let access = call_argument_access(callee);
+            for arg in args {
+                visit(*arg, access);
+            }
+            for dst in dsts {
+                visit(*dst, Define);
+            }
+        }
+        WasmLirStmt::StringPushLiteral { buffer, .. } => visit(*buffer, Borrow),
+        // Pushing copies the source bytes, so the pushed string stays unshared.
+        WasmLirStmt::StringPushHandle { buffer, handle } => {
+            visit(*buffer, Borrow);
+            visit(*handle, Borrow);
+        }
+        WasmLirStmt::StringFromI64 { dst, value } => {
+            visit(*value, Borrow);
+            visit(*dst, DefineFresh);
+        }
+        WasmLirStmt::StringFinish { dst, buffer } => {
+            visit(*buffer, Borrow);
+            visit(*dst, DefineFresh);
+        }
+        WasmLirStmt::VecPushHandle { vec, handle } => {
+            visit(*vec, Borrow);
+            visit(*handle, Escape);
+        }
+        WasmLirStmt::MapGet { dst, map, key }
+        | WasmLirStmt::MapContains { dst, map, key }
+        | WasmLirStmt::MapRemove { dst, map, key } => {
+            visit(*map, Borrow);
+            visit(*key, Borrow);
+            if let Some(dst) = dst {
+                visit(*dst, Define);
+            }
+        }
+        WasmLirStmt::MapSet {
+            dst,
+            map,
+            key,
+            value,
+        } => {
+            visit(*map, Borrow);
+            visit(*key, Escape);
+            visit(*value, Escape);
+            if let Some(dst) = dst {
+                visit(*dst, Define);
+            }
+        }
+        WasmLirStmt::MapClear { map } => visit(*map, Borrow),
+        WasmLirStmt::MapLength { dst, map } => {
+            visit(*map, Borrow);
+            if let Some(dst) = dst {
+                visit(*dst, Define);
+            }
+        }
+        WasmLirStmt::FallibleIsSuccess { dst, carrier }
+        | WasmLirStmt::FallibleUnwrapSuccess { dst, carrier }
+        | WasmLirStmt::FallibleUnwrapError { dst, carrier } => {
+            visit(*carrier, Borrow);
+            visit(*dst, Define);
+        }
+        WasmLirStmt::FallibleWrapSuccess { dst, value } => {
+            visit(*value, Escape);
+            visit(*dst, Define);
+        }
+        WasmLirStmt::FallibleWrapError { dst, error } => {
+            visit(*error, Escape);
+            visit(*dst, Define);
+        }
+        WasmLirStmt::ErrorField { dst, error, .. } => {
+            visit(*error, Borrow);
+            visit(*dst, Define);
+        }
+        WasmLirStmt::RecordStore { record, value, .. } => {
+            visit(*record, Borrow);
+            visit(*value, Escape);
+        }
+        WasmLirStmt::RecordLoad { dst, record, .. } => {
+            visit(*record, Borrow);
+            visit(*dst, Define);
+        }
+        // Cast helpers may hand back the source handle or point into its content.
+        WasmLirStmt::Cast { dst, value, .. } => {
+            visit(*value, Escape);
+            visit(*dst, Define);
+        }
+        WasmLirStmt::CheckedNumeric { dst, lhs, rhs, .. } => {
+            visit(*lhs, Borrow);
+            if let Some(rhs) = rhs {
+                visit(*rhs, Borrow);
+            }
+            visit(*dst, Define);
+        }
+        WasmLirStmt::FormatFloat { dst, value, .. } => {
+            visit(*value, Borrow);
+            visit(*dst, Define);
+        }
+        // Adapters copy string bytes out to the runtime; only `io.input.new` allocates.
+        WasmLirStmt::WasiCall { dst, adapter, args } => {
+            for arg in args {
+                visit(*arg, Borrow);
+            }
+            if let Some(dst) = dst {
+                let access = if *adapter == WasiAdapter::InputNew {
+                    DefineFresh
+                } else {
+                    Define
+                };
+                visit(*dst, access);
+            }
+        }
+        WasmLirStmt::DropIfOwned { .. } => {}
+        WasmLirStmt::RetainHandle { value } => visit(*value, Escape),
+        WasmLirStmt::IntEq { dst, lhs, rhs }
+        | WasmLirStmt::IntNe { dst, lhs, rhs }
+        | WasmLirStmt::IntAdd { dst, lhs, rhs }
+        | WasmLirStmt::IntSub { dst, lhs, rhs }
+        | WasmLirStmt::IntMod { dst, lhs, rhs }
+        | WasmLirStmt::IntMul { dst, lhs, rhs }
+        | WasmLirStmt::IntFloorDiv { dst, lhs, rhs }
+        | WasmLirStmt::IntToFloatDiv { dst, lhs, rhs }
+        | WasmLirStmt::FloatAdd { dst, lhs, rhs }
+        | WasmLirStmt::FloatSub { dst, lhs, rhs }
+        | WasmLirStmt::FloatMul { dst, lhs, rhs }
+        | WasmLirStmt::FloatDiv { dst, lhs, rhs }
+        | WasmLirStmt::FloatMod { dst, lhs, rhs }
+        | WasmLirStmt::BoolAnd { dst, lhs, rhs }
+        | WasmLirStmt::BoolOr { dst, lhs, rhs }
+        | WasmLirStmt::OrderedLt { dst, lhs, rhs }
+        | WasmLirStmt::OrderedLe { dst, lhs, rhs }
+        | WasmLirStmt::OrderedGt { dst, lhs, rhs }
+        | WasmLirStmt::OrderedGe { dst, lhs, rhs } => {
+            visit(*lhs, Borrow);
+            visit(*rhs, Borrow);
+            visit(*dst, Define);
+        }
+    }
+}
+
+fn call_argument_access(callee: &WasmCalleeRef) -> OperandAccess {
+    match callee {
+        WasmCalleeRef::Import(_) => OperandAccess::Borrow,
+        WasmCalleeRef::Function(_) => OperandAccess::Escape,
+    }
+}
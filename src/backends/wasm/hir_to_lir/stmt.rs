@@ -2,13 +2,24 @@
 
 use crate::backends::error_types::lir_transformation_error;
 use crate::backends::wasm::hir_to_lir::context::WasmFunctionLoweringContext;
-use crate::backends::wasm::hir_to_lir::expr::lower_expression;
-use crate::backends::wasm::hir_to_lir::imports::resolve_host_call_import;
+use crate::backends::wasm::hir_to_lir::expr::{
+    lower_cast, lower_expression, lower_place_value, struct_field_slot,
+};
+use crate::backends::wasm::hir_to_lir::imports::{
+    ensure_host_import, resolve_host_call_import, wasi_adapter_for,
+};
+use crate::backends::wasm::hir_to_lir::tuples::lower_tuple_slots;
 use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt};
+use crate::backends::wasm::lir::types::{WasmAbiType, WasmLirLocalId};
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::numeric::{WasmNumericFailureMode, WasmNumericOp};
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
 use crate::compiler_frontend::external_packages::CallTarget;
-use crate::compiler_frontend::hir::expressions::HirExpression;
+use crate::compiler_frontend::hir::expressions::{HirExpression, HirMapOp};
 use crate::compiler_frontend::hir::ids::LocalId;
+use crate::compiler_frontend::hir::numeric::{
+    HirNumericOp, HirNumericOperands, NumericFailureMode,
+};
 use crate::compiler_frontend::hir::places::HirPlace;
 use crate::compiler_frontend::hir::statements::{HirStatement, HirStatementKind};
 
@@ -34,6 +45,38 @@ pub(crate) fn lower_statement(
                 lowered_args.push(lowered.value);
             }
 
+            // WASI command adapters lower inline instead of through an import call.
+            if let CallTarget::ExternalFunction(id) = target
+                && let Some(adapter) = wasi_adapter_for(context.module_context, *id)?
+            {
+                let dst = result
+                    .as_ref()
+                    .and_then(|local_id| context.local_map.get(local_id).copied());
+                let fallible = context
+                    .module_context
+                    .request
+                    .external_package_registry
+                    .get_function_by_id(*id)
+                    .is_some_and(|function| function.is_fallible());
+
+                // Adapters never fail, so fallible functions always produce a success carrier.
+                let value = match dst {
+                    Some(_) if fallible => Some(context.alloc_temp(WasmAbiType::Handle)),
+                    _ => dst,
+                };
+                statements.push(WasmLirStmt::WasiCall {
+                    dst: value,
+                    adapter,
+                    args: lowered_args,
+                });
+                if let (Some(dst), Some(value)) = (dst, value)
+                    && fallible
+                {
+                    statements.push(WasmLirStmt::FallibleWrapSuccess { dst, value });
+                }
+                return Ok(());
+            }
+
             let callee = match target {
                 CallTarget::UserFunction(function_id) => {
                     // User calls stay function-id based after semantic lowering.
@@ -56,6 +99,19 @@ pub(crate) fn lower_statement(
                 }
             };
 
+            // Multi-return calls write each returned value into its tuple slot.
+            if let Some(dsts) = result
+                .as_ref()
+                .and_then(|local_id| context.tuple_local_slots.get(local_id))
+            {
+                statements.push(WasmLirStmt::CallMultiValue {
+                    dsts: dsts.clone(),
+                    callee,
+                    args: lowered_args,
+                });
+                return Ok(());
+            }
+
             let dst = result
                 .as_ref()
                 .and_then(|local_id| context.local_map.get(local_id).copied());
@@ -68,21 +124,66 @@ pub(crate) fn lower_statement(
 
             Ok(())
         }
-        HirStatementKind::MapOp { .. } => Err(lir_transformation_error(
-            "Wasm hashmap operation reached lowering before backend feature validation",
-        )),
-        HirStatementKind::CastOp { .. } => Err(lir_transformation_error(
-            "Wasm lowering does not yet support cast operations",
-        )),
-        HirStatementKind::NumericOp { op, .. } => Err(lir_transformation_error(format!(
-            "Wasm lowering does not yet support checked numeric operations: {op:?}"
-        ))),
-        HirStatementKind::FormatFloat { .. } => Err(lir_transformation_error(
-            "Wasm lowering does not yet support Float formatting",
-        )),
-        HirStatementKind::ValidateFloat { .. } => Err(lir_transformation_error(
-            "Wasm lowering does not yet support Float boundary validation",
-        )),
+        HirStatementKind::MapOp {
+            op,
+            receiver,
+            args,
+            result,
+        } => lower_map_operation(context, *op, receiver, args, result.as_ref(), statements),
+        HirStatementKind::CastOp {
+            policy,
+            source,
+            result,
+        } => {
+            let value = lower_expression(context, source, statements)?.value;
+            // HIR only emits `CastOp` for fallible casts, whose result is always a carrier handle.
+            let dst = match result {
+                Some(result) => context.local_map.get(result).copied().ok_or_else(|| {
+                    lir_transformation_error(format!(
+                        "Wasm lowering could not resolve cast result local {result:?}"
+                    ))
+                })?,
+                None => context.alloc_temp(WasmAbiType::Handle),
+            };
+            lower_cast(context, *policy, value, dst, statements);
+            Ok(())
+        }
+        HirStatementKind::NumericOp {
+            op,
+            failure_mode,
+            operands,
+            result,
+        } => lower_numeric_operation(context, *op, *failure_mode, operands, result, statements),
+        HirStatementKind::FormatFloat {
+            source,
+            failure_mode,
+            result,
+        } => {
+            let value = lower_expression(context, source, statements)?.value;
+            let dst = numeric_result_local(context, result)?;
+            statements.push(WasmLirStmt::FormatFloat {
+                dst,
+                value,
+                failure_mode: lower_failure_mode(*failure_mode),
+            });
+            Ok(())
+        }
+        HirStatementKind::ValidateFloat {
+            source,
+            failure_mode,
+            result,
+        } => {
+            let value = lower_expression(context, source, statements)?.value;
+            let dst = numeric_result_local(context, result)?;
+            statements.push(WasmLirStmt::CheckedNumeric {
+                dst,
+                op: WasmNumericOp::FloatValidate,
+                lhs: value,
+                rhs: None,
+                failure_mode: lower_failure_mode(*failure_mode),
+            });
+            Ok(())
+        }
         HirStatementKind::Expr(expression) => {
             let _ = lower_expression(context, expression, statements)?;
             Ok(())
@@ -121,12 +222,40 @@ fn lower_assignment(
 ) -> Result<(), CompilerError> {
     // WHAT: preserve explicit move/copy distinction in LIR.
     // WHY: ownership optimization stays representable even under GC-first semantics.
-    let HirPlace::Local(target_local) = target else {
-        return Err(lir_transformation_error(
-            "Wasm lowering currently supports assignments only to direct locals",
-        ));
+    let target_local = match target {
+        HirPlace::Local(target_local) => target_local,
+        HirPlace::Field { base, field } => {
+            // WHAT: field targets store into the struct record reached through `base`.
+            let value = lower_expression(context, value, statements)?.value;
+            let record = lower_place_value(context, base, statements)?;
+            let (record_type, slot) = struct_field_slot(context, *field)?;
+            if slot.abi != WasmAbiType::Void {
+                statements.push(WasmLirStmt::RecordStore {
+                    record,
+                    record_type,
+                    offset: slot.offset,
+                    value,
+                });
+            }
+            return Ok(());
+        }
+        HirPlace::Index { .. } => {
+            return Err(lir_transformation_error(
+                "Wasm lowering currently supports assignments only to locals and struct fields",
+            ));
+        }
     };
 
+    if let Some(dsts) = context.tuple_local_slots.get(target_local).cloned() {
+        let sources = lower_tuple_slots(context, value, statements)?;
+        for (dst, src) in dsts.into_iter().zip(sources) {
+            if dst != src {
+                statements.push(WasmLirStmt::Copy { dst, src });
+            }
+        }
+        return Ok(());
+    }
+
     let dst = context
         .local_map
         .get(target_local)
@@ -157,6 +286,160 @@ fn lower_assignment(
     Ok(())
 }
 
+fn lower_map_operation(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    op: HirMapOp,
+    receiver: &HirExpression,
+    args: &[HirExpression],
+    result: Option<&LocalId>,
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<(), CompilerError> {
+    // WHAT: map operations become runtime helper calls on the map handle.
+    // WHY: `get`, `set` and `remove` yield fallible carriers that a following
+    // `FallibleBranch` inspects, matching the JS runtime's `{tag, value}` results.
+    let map = lower_expression(context, receiver, statements)?.value;
+    let mut lowered_args = Vec::with_capacity(args.len());
+    for arg in args {
+        lowered_args.push(lower_expression(context, arg, statements)?.value);
+    }
+
+    let dst = match result {
+        Some(local_id) => Some(context.local_map.get(local_id).copied().ok_or_else(|| {
+            lir_transformation_error(format!(
+                "Wasm lowering could not resolve map operation result local {local_id:?}"
+            ))
+        })?),
+        None => None,
+    };
+
+    let statement = match (op, lowered_args.as_slice()) {
+        (HirMapOp::Get, [key]) => WasmLirStmt::MapGet {
+            dst,
+            map,
+            key: *key,
+        },
+        (HirMapOp::Contains, [key]) => WasmLirStmt::MapContains {
+            dst,
+            map,
+            key: *key,
+        },
+        (HirMapOp::Set, [key, value]) => WasmLirStmt::MapSet {
+            dst,
+            map,
+            key: *key,
+            value: *value,
+        },
+        (HirMapOp::Remove, [key]) => WasmLirStmt::MapRemove {
+            dst,
+            map,
+            key: *key,
+        },
+        (HirMapOp::Clear, []) => WasmLirStmt::MapClear { map },
+        (HirMapOp::Length, []) => WasmLirStmt::MapLength { dst, map },
+        _ => {
+            return Err(lir_transformation_error(format!(
+                "Wasm lowering received {} arguments for map operation {op:?}",
+                lowered_args.len()
+            )));
+        }
+    };
+    statements.push(statement);
+
+    Ok(())
+}
+
+fn lower_numeric_operation(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    op: HirNumericOp,
+    failure_mode: NumericFailureMode,
+    operands: &HirNumericOperands,
+    result: &LocalId,
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<(), CompilerError> {
+    // WHAT: checked numeric ops become runtime helper calls that report through the status global.
+    // WHY: overflow, zero-divisor and non-finite checks must match the JS runtime exactly.
+    let (lhs, rhs) = match operands {
+        HirNumericOperands::Unary { operand } => {
+            (lower_expression(context, operand, statements)?.value, None)
+        }
+        HirNumericOperands::Binary { left, right } => {
+            let lhs = lower_expression(context, left, statements)?.value;
+            let rhs = lower_expression(context, right, statements)?.value;
+            (lhs, Some(rhs))
+        }
+    };
+    if op.is_unary() != rhs.is_none() {
+        return Err(lir_transformation_error(format!(
+            "Wasm lowering received NumericOp::{op:?} with operand arity that does not match the operation"
+        )));
+    }
+
+    let dst = numeric_result_local(context, result)?;
+    let failure_mode = lower_failure_mode(failure_mode);
+
+    let op = match op {
+        HirNumericOp::IntAdd => WasmNumericOp::IntAdd,
+        HirNumericOp::IntSub => WasmNumericOp::IntSub,
+        HirNumericOp::IntMul => WasmNumericOp::IntMul,
+        HirNumericOp::IntDiv => WasmNumericOp::IntDiv,
+        HirNumericOp::IntMod => WasmNumericOp::IntMod,
+        HirNumericOp::IntPow => WasmNumericOp::IntPow,
+        HirNumericOp::IntNeg => WasmNumericOp::IntNeg,
+        HirNumericOp::FloatAdd => WasmNumericOp::FloatAdd,
+        HirNumericOp::FloatSub => WasmNumericOp::FloatSub,
+        HirNumericOp::FloatMul => WasmNumericOp::FloatMul,
+        HirNumericOp::FloatDiv => WasmNumericOp::FloatDiv,
+        HirNumericOp::FloatMod => WasmNumericOp::FloatMod,
+        HirNumericOp::FloatNeg => WasmNumericOp::FloatNeg,
+        HirNumericOp::FloatPow => {
+            // Float `^` uses the host `Math.pow` so results match the JS backend bit for bit;
+            // only the finiteness check runs in Wasm.
+            let import_id = ensure_host_import(context.module_context, WasmHostFunction::MathPow);
+            let power = context.alloc_temp(WasmAbiType::F64);
+            statements.push(WasmLirStmt::Call {
+                dst: Some(power),
+                callee: WasmCalleeRef::Import(import_id),
+                args: std::iter::once(lhs).chain(rhs).collect(),
+            });
+            statements.push(WasmLirStmt::CheckedNumeric {
+                dst,
+                op: WasmNumericOp::FloatCheckFinite,
+                lhs: power,
+                rhs: None,
+                failure_mode,
+            });
+            return Ok(());
+        }
+    };
+
+    statements.push(WasmLirStmt::CheckedNumeric {
+        dst,
+        op,
+        lhs,
+        rhs,
+        failure_mode,
+    });
+    Ok(())
+}
+
+fn numeric_result_local(
+    context: &WasmFunctionLoweringContext<'_, '_>,
+    result: &LocalId,
+) -> Result<WasmLirLocalId, CompilerError> {
+    context.local_map.get(result).copied().ok_or_else(|| {
+        lir_transformation_error(format!(
+            "Wasm lowering could not resolve numeric result local {result:?}"
+        ))
+    })
+}
+
+fn lower_failure_mode(failure_mode: NumericFailureMode) -> WasmNumericFailureMode {
+    match failure_mode {
+        NumericFailureMode::ReturnError => WasmNumericFailureMode::ReturnError,
+        NumericFailureMode::Trap => WasmNumericFailureMode::Trap,
+    }
+}
+
 fn lower_push_runtime_fragment(
     context: &mut WasmFunctionLoweringContext<'_, '_>,
     vec_local: &LocalId,
@@ -3,6 +3,7 @@
 use crate::backends::error_types::lir_transformation_error;
 use crate::backends::wasm::hir_to_lir::context::{WasmFunctionLoweringContext, lower_type_to_abi};
 use crate::backends::wasm::hir_to_lir::expr::lower_expression;
+use crate::backends::wasm::hir_to_lir::tuples::lower_tuple_slots;
 use crate::backends::wasm::lir::instructions::{WasmLirStmt, WasmLirTerminator};
 use crate::backends::wasm::lir::types::WasmAbiType;
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
@@ -34,6 +35,17 @@ pub(crate) fn lower_terminator(
                 else_block: resolve_block_id(context, *else_block)?,
             })
         }
+        HirTerminator::Return(value)
+            if context
+                .module_context
+                .type_environment
+                .tuple_field_ids(value.ty)
+                .is_some() =>
+        {
+            // Multi-return functions leave one value per tuple slot on the stack.
+            let values = lower_tuple_slots(context, value, statements)?;
+            Ok(WasmLirTerminator::ReturnMultiValue { values })
+        }
         HirTerminator::Return(value) => {
             // Preserve unit-return as `Return(None)` to keep ABI shape explicit.
             let return_abi = lower_type_to_abi(context.module_context, value.ty);
@@ -46,15 +58,58 @@ pub(crate) fn lower_terminator(
                 value: Some(lowered_value.value),
             })
         }
-        HirTerminator::ReturnSuccess(_) => Err(lir_transformation_error(
-            "Wasm lowering does not yet support fallible success-return terminators",
-        )),
-        HirTerminator::ReturnError(_) => Err(lir_transformation_error(
-            "Wasm lowering does not yet support fallible error-return terminators",
-        )),
-        HirTerminator::FallibleBranch { .. } => Err(lir_transformation_error(
-            "Wasm lowering does not yet support fallible branch terminators",
-        )),
+        HirTerminator::ReturnSuccess(value) => {
+            // Fallible functions return a carrier handle; unit success carries a zero payload.
+            let payload = if matches!(
+                lower_type_to_abi(context.module_context, value.ty),
+                WasmAbiType::Void
+            ) {
+                let zero = context.alloc_temp(WasmAbiType::I64);
+                statements.push(WasmLirStmt::ConstI64 {
+                    dst: zero,
+                    value: 0,
+                });
+                zero
+            } else {
+                lower_expression(context, value, statements)?.value
+            };
+            let carrier = context.alloc_temp(WasmAbiType::Handle);
+            statements.push(WasmLirStmt::FallibleWrapSuccess {
+                dst: carrier,
+                value: payload,
+            });
+            Ok(WasmLirTerminator::Return {
+                value: Some(carrier),
+            })
+        }
+        HirTerminator::ReturnError(value) => {
+            let error = lower_expression(context, value, statements)?.value;
+            let carrier = context.alloc_temp(WasmAbiType::Handle);
+            statements.push(WasmLirStmt::FallibleWrapError {
+                dst: carrier,
+                error,
+            });
+            Ok(WasmLirTerminator::Return {
+                value: Some(carrier),
+            })
+        }
+        HirTerminator::FallibleBranch {
+            result,
+            success_block,
+            error_block,
+        } => {
+            let carrier = lower_expression(context, result, statements)?;
+            let condition = context.alloc_temp(WasmAbiType::I32);
+            statements.push(WasmLirStmt::FallibleIsSuccess {
+                dst: condition,
+                carrier: carrier.value,
+            });
+            Ok(WasmLirTerminator::Branch {
+                condition,
+                then_block: resolve_block_id(context, *success_block)?,
+                else_block: resolve_block_id(context, *error_block)?,
+            })
+        }
         HirTerminator::Uninitialized => Err(lir_transformation_error(
             "Wasm lowering encountered Uninitialized terminator",
         )),
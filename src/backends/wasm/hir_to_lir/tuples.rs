@@ -0,0 +1,43 @@
+//! Tuple (multi-return) lowering helpers.
+//!
+//! WHAT: lowers tuple-typed HIR expressions to one LIR local per slot.
+//! WHY: multi-value calls and returns move tuples through the Wasm operand stack, so tuples only
+//! ever exist as slot locals: call results, return values and multi-bind sources.
+
+use crate::backends::error_types::lir_transformation_error;
+use crate::backends::wasm::hir_to_lir::context::WasmFunctionLoweringContext;
+use crate::backends::wasm::hir_to_lir::expr::lower_expression;
+use crate::backends::wasm::lir::instructions::WasmLirStmt;
+use crate::backends::wasm::lir::types::WasmLirLocalId;
+use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerError;
+use crate::compiler_frontend::hir::expressions::{HirExpression, HirExpressionKind};
+use crate::compiler_frontend::hir::places::HirPlace;
+
+/// Lowers a tuple-typed expression and returns the locals holding its slots in order.
+pub(crate) fn lower_tuple_slots(
+    context: &mut WasmFunctionLoweringContext<'_, '_>,
+    expression: &HirExpression,
+    statements: &mut Vec<WasmLirStmt>,
+) -> Result<Vec<WasmLirLocalId>, CompilerError> {
+    match &expression.kind {
+        HirExpressionKind::TupleConstruct { elements } => {
+            let mut slots = Vec::with_capacity(elements.len());
+            for element in elements {
+                slots.push(lower_expression(context, element, statements)?.value);
+            }
+            Ok(slots)
+        }
+        HirExpressionKind::Load(HirPlace::Local(local_id)) => context
+            .tuple_local_slots
+            .get(local_id)
+            .cloned()
+            .ok_or_else(|| {
+                lir_transformation_error(format!(
+                    "Wasm lowering could not resolve tuple slots for local {local_id:?}"
+                ))
+            }),
+        _ => Err(lir_transformation_error(
+            "Wasm lowering supports tuples only as locals and tuple constructions",
+        )),
+    }
+}
@@ -5,8 +5,14 @@
 //! or reserved by the memory model, but production HIR lowering does not construct them yet.
 
 use crate::backends::wasm::lir::types::{
-    WasmImportId, WasmLirBlockId, WasmLirFunctionId, WasmLirLocalId, WasmStaticDataId,
+    WasmImportId, WasmLirBlockId, WasmLirFunctionId, WasmLirLocalId, WasmRecordTypeId,
+    WasmStaticDataId,
 };
+use crate::backends::wasm::runtime::errors::WasmErrorField;
+use crate::backends::wasm::runtime::maps::WasmMapKeyKind;
+use crate::backends::wasm::runtime::numeric::{WasmNumericFailureMode, WasmNumericOp};
+use crate::backends::wasm::runtime::wasi::WasiAdapter;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum WasmLirStmt {
@@ -53,6 +59,12 @@ pub(crate) enum WasmLirStmt {
         callee: WasmCalleeRef,
         args: Vec<WasmLirLocalId>,
     },
+    /// Call whose results land in one destination local per Wasm result (multi-value).
+    CallMultiValue {
+        dsts: Vec<WasmLirLocalId>,
+        callee: WasmCalleeRef,
+        args: Vec<WasmLirLocalId>,
+    },
     /// Runtime-template/string-building primitives.
     StringNewBuffer {
         dst: WasmLirLocalId,
@@ -82,6 +94,126 @@ pub(crate) enum WasmLirStmt {
         vec: WasmLirLocalId,
         handle: WasmLirLocalId,
     },
+    /// Insertion-ordered map primitives (layout in `runtime::maps`).
+    /// Keys and values may be any scalar or handle local; the emitter widens them to the i64
+    /// entry slots and narrows loaded values back to the destination local's ABI type.
+    MapNew {
+        dst: WasmLirLocalId,
+        key_kind: WasmMapKeyKind,
+    },
+    /// `dst` receives a fallible carrier holding the value or `MapKeyNotFound`.
+    MapGet {
+        dst: Option<WasmLirLocalId>,
+        map: WasmLirLocalId,
+        key: WasmLirLocalId,
+    },
+    MapContains {
+        dst: Option<WasmLirLocalId>,
+        map: WasmLirLocalId,
+        key: WasmLirLocalId,
+    },
+    /// `dst` receives a success carrier; `set` cannot fail.
+    MapSet {
+        dst: Option<WasmLirLocalId>,
+        map: WasmLirLocalId,
+        key: WasmLirLocalId,
+        value: WasmLirLocalId,
+    },
+    /// `dst` receives a fallible carrier holding the removed value or `MapKeyNotFound`.
+    MapRemove {
+        dst: Option<WasmLirLocalId>,
+        map: WasmLirLocalId,
+        key: WasmLirLocalId,
+    },
+    MapClear {
+        map: WasmLirLocalId,
+    },
+    /// `dst` is I64 to match Beanstalk `Int`.
+    MapLength {
+        dst: Option<WasmLirLocalId>,
+        map: WasmLirLocalId,
+    },
+    /// Fallible-carrier inspection; `dst` is I32 and set to 1 for success carriers.
+    FallibleIsSuccess {
+        dst: WasmLirLocalId,
+        carrier: WasmLirLocalId,
+    },
+    /// Read a success payload narrowed to `dst`'s ABI type.
+    FallibleUnwrapSuccess {
+        dst: WasmLirLocalId,
+        carrier: WasmLirLocalId,
+    },
+    /// Read the `Error` handle of an error carrier.
+    FallibleUnwrapError {
+        dst: WasmLirLocalId,
+        carrier: WasmLirLocalId,
+    },
+    /// Wrap `value` in a success carrier; `dst` is a Handle.
+    FallibleWrapSuccess {
+        dst: WasmLirLocalId,
+        value: WasmLirLocalId,
+    },
+    /// Wrap an `Error` handle in an error carrier; `dst` is a Handle.
+    FallibleWrapError {
+        dst: WasmLirLocalId,
+        error: WasmLirLocalId,
+    },
+    /// Read one field of a builtin `Error` record (layout in `runtime::errors`).
+    /// `dst` is a string Handle for `message` and I64 for `code`.
+    ErrorField {
+        dst: WasmLirLocalId,
+        error: WasmLirLocalId,
+        field: WasmErrorField,
+    },
+    /// Allocate a zeroed struct or choice record (layout in `runtime::records`).
+    RecordNew {
+        dst: WasmLirLocalId,
+        record_type: WasmRecordTypeId,
+    },
+    /// Store `value` at `offset` inside a record; the store width follows `value`'s ABI type.
+    RecordStore {
+        record: WasmLirLocalId,
+        record_type: WasmRecordTypeId,
+        offset: u32,
+        value: WasmLirLocalId,
+    },
+    /// Load the field at `offset` inside a record, narrowed to `dst`'s ABI type.
+    RecordLoad {
+        dst: WasmLirLocalId,
+        record: WasmLirLocalId,
+        record_type: WasmRecordTypeId,
+        offset: u32,
+    },
+    /// Builtin runtime cast (contract in `runtime::casts`). `dst` receives the target value, or
+    /// a fallible carrier for policies that can fail.
+    Cast {
+        dst: WasmLirLocalId,
+        policy: BuiltinCastPolicyId,
+        value: WasmLirLocalId,
+    },
+    /// Checked scalar arithmetic (failure contract in `runtime::numeric`).
+    /// Operands use the op's scalar ABI (I64 for Int, F64 for Float); `rhs` is `None` for unary
+    /// ops. `dst` receives the scalar in `Trap` mode or a fallible carrier in `ReturnError` mode.
+    CheckedNumeric {
+        dst: WasmLirLocalId,
+        op: WasmNumericOp,
+        lhs: WasmLirLocalId,
+        rhs: Option<WasmLirLocalId>,
+        failure_mode: WasmNumericFailureMode,
+    },
+    /// Shortest round-trip Float formatting; `dst` is a string handle or a fallible carrier.
+    FormatFloat {
+        dst: WasmLirLocalId,
+        value: WasmLirLocalId,
+        failure_mode: WasmNumericFailureMode,
+    },
+    /// WASI command adapter (contract in `runtime::wasi`). `args` follow the adapted external
+    /// function's parameters; `dst` receives its result, if any.
+    WasiCall {
+        dst: Option<WasmLirLocalId>,
+        adapter: WasiAdapter,
+        args: Vec<WasmLirLocalId>,
+    },
     DropIfOwned {
         value: WasmLirLocalId,
     },
@@ -211,6 +343,8 @@ pub(crate) enum WasmLirTerminator {
     },
     /// Function return.
     Return { value: Option<WasmLirLocalId> },
+    /// Function return of several values, in result order (multi-value).
+    ReturnMultiValue { values: Vec<WasmLirLocalId> },
     /// Fallback hard stop for unsupported/unreachable paths.
     Trap,
 }
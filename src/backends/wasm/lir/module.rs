@@ -2,8 +2,9 @@
 
 use crate::backends::wasm::lir::function::WasmLirFunction;
 use crate::backends::wasm::lir::linkage::{WasmExport, WasmImport};
-use crate::backends::wasm::lir::types::WasmStaticDataId;
+use crate::backends::wasm::lir::types::{WasmAbiType, WasmRecordTypeId, WasmStaticDataId};
 use crate::backends::wasm::runtime::memory::WasmMemoryPlan;
+use crate::backends::wasm::runtime::records::WasmRecordField;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub(crate) struct WasmLirModule {
@@ -17,6 +18,10 @@ pub(crate) struct WasmLirModule {
     pub static_data: Vec<WasmStaticData>,
     /// Planned linear-memory layout for future Wasm emission.
     pub memory_plan: WasmMemoryPlan,
+    /// Concrete struct and choice record shapes referenced by record statements.
+    pub record_types: Vec<WasmRecordType>,
+    /// Monomorphized generic instances per generic declaration, for size reporting.
+    pub generic_instances: Vec<WasmGenericInstanceSummary>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -34,3 +39,59 @@ pub(crate) enum WasmStaticDataKind {
     /// UTF-8 encoded string payload.
     Utf8StringBytes,
 }
+
+/// One concrete struct or choice record shape.
+///
+/// WHAT: the linear-memory emitter allocates `size` bytes and addresses slots by offset; the
+///       Wasm GC emitter declares one `struct` type per record with one field per slot.
+/// WHY: record statements name their record type so both memory models can resolve the same
+///      `(offset, abi)` slot without re-deriving layouts from HIR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WasmRecordType {
+    pub id: WasmRecordTypeId,
+    /// Debug label for dumps, e.g. `Box of Int`.
+    pub debug_name: String,
+    /// Linear-memory record size in bytes.
+    pub size: u32,
+    /// Distinct non-unit storage slots in offset order. Choice records start with the tag slot,
+    /// and variant payloads that share an offset and ABI type share a slot.
+    pub slots: Vec<WasmRecordField>,
+}
+
+impl WasmRecordType {
+    /// Index of the slot storing `abi` at `offset`, if any.
+    pub(crate) fn slot_index(&self, offset: u32, abi: WasmAbiType) -> Option<u32> {
+        self.slots
+            .iter()
+            .position(|slot| slot.offset == offset && slot.abi == abi)
+            .map(|index| index as u32)
+    }
+}
+
+/// Concrete instances generated for one generic declaration.
+///
+/// WHY: every instance of a generic function or nominal type becomes its own LIR function or
+/// record layout, so the instance count is the code-size cost of that generic in Wasm output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WasmGenericInstanceSummary {
+    pub kind: WasmGenericKind,
+    /// Source name of the generic declaration.
+    pub generic_name: String,
+    /// Instances in deterministic HIR order.
+    pub instances: Vec<WasmGenericInstance>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WasmGenericKind {
+    Function,
+    Struct,
+    Choice,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WasmGenericInstance {
+    /// Display name of the concrete instance, e.g. `Box of Int`.
+    pub name: String,
+    /// Record bytes for struct/choice instances; lowered LIR statements for function instances.
+    pub size: u32,
+}
@@ -19,6 +19,9 @@ pub(crate) struct WasmImportId(pub u32);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct WasmStaticDataId(pub u32);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct WasmRecordTypeId(pub u32);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct WasmLirSignature {
     /// Ordered ABI parameter list.
@@ -35,6 +38,9 @@ pub(crate) enum WasmAbiType {
     F32,
     F64,
     Handle,
+    /// Opaque host reference (`externref`) for external package handles.
+    /// WHY: the host keeps ownership of these values, so they never live in linear memory.
+    ExternRef,
     /// Explicit "no value" marker used for unit-return functions.
     Void,
 }
@@ -30,6 +30,19 @@ pub(crate) struct WasmBackendRequest {
     pub external_package_registry: Arc<ExternalPackageRegistry>,
     /// Selects which HIR functions are lowered into this Wasm module.
     pub function_emission_policy: WasmFunctionEmissionPolicy,
+    /// Embedder the module imports from.
+    /// WHY: HTML pages supply `host.*` functions from JS glue, while WASI commands may only
+    /// import `wasi_snapshot_preview1` functions.
+    pub host_interface: WasmHostInterface,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum WasmHostInterface {
+    /// Builder-generated JS glue provides the `host` import module.
+    #[default]
+    JsHost,
+    /// The module is a WASI preview1 command run by a standalone runtime.
+    WasiPreview1,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -55,6 +68,29 @@ pub(crate) struct WasmExportPolicy {
     pub export_names: FxHashMap<FunctionId, String>,
     /// Helper exports required by builder-side interop contracts.
     pub helper_exports: WasmHelperExportPolicy,
+    /// WASI command entry, exported as `_start` when set.
+    pub command_start: Option<WasmCommandStart>,
+}
+
+/// Synthesized `_start` of a WASI command module.
+///
+/// WHAT: `_start` calls the entry start function, then writes the module's top-level fragments
+/// to stdout in source order, one line each.
+/// WHY: a command has no page to render into, so top-level templates become program output.
+#[derive(Debug, Clone)]
+pub(crate) struct WasmCommandStart {
+    /// Entry start function; it returns the runtime fragment Vec like HTML-Wasm `bst_start`.
+    pub function: FunctionId,
+    /// Top-level fragments in source order.
+    pub output: Vec<WasmCommandOutput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WasmCommandOutput {
+    /// Compile-time folded fragment text.
+    Const(String),
+    /// Index into the runtime fragment Vec returned by the start function.
+    RuntimeFragment(u32),
 }
 
 #[derive(Debug, Clone, Default)]
@@ -75,36 +111,45 @@ pub(crate) struct WasmHelperExportPolicy {
     pub export_vec_get: bool,
     /// Export runtime release helper as `bst_release`.
     pub export_release: bool,
+    /// Export the Wasm GC string byte reader as `bst_str_byte`.
+    /// Wasm GC strings are opaque to the host, so this replaces `bst_str_ptr` plus `memory`.
+    pub export_str_byte: bool,
+    /// Export the allocator's live-block counter global as `bst_live_allocations`.
+    /// Debug aid: tests and hosts compare it against a baseline to spot leaks.
+    pub export_live_allocations: bool,
 }
 
 /// Controls how CFG is mapped to Wasm structured control flow.
 ///
 /// WHAT: this is an explicit strategy seam for function-body structuring.
-/// WHY: the current emitter uses dispatcher-loop lowering, but the request contract should remain
-/// stable when a richer block/loop structuring algorithm is introduced.
+/// WHY: structured lowering is the default, while dispatcher lowering stays selectable for
+/// comparing output and as the fallback for irreducible CFGs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub(crate) enum WasmCfgLoweringStrategy {
     /// Uses an internal dispatch local with `block + loop + br` to represent arbitrary CFG.
-    /// This is the current implementation.
-    #[default]
+    #[allow(dead_code)]
+    // Selected explicitly by tests; irreducible bodies reach it as a fallback.
     DispatcherLoop,
-    /// Reserved for future direct structured lowering (if/else/loop region construction).
-    #[allow(dead_code)] // Wasm roadmap: direct structured CFG lowering.
+    /// Rebuilds nested `block`/`loop`/`if` regions from the CFG. Functions with an irreducible
+    /// CFG fall back to dispatcher-loop lowering.
+    #[default]
     Structured,
 }
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct WasmTargetFeatures {
-    /// Placeholder for future GC proposal-specific codegen branches.
+    /// Emits records, strings and vecs as Wasm GC objects instead of linear-memory records
+    /// (see `runtime::gc`).
     pub use_wasm_gc: bool,
     /// Enables ownership-aware runtime scaffolding planning.
     /// The current path remains conservative/GC-first either way.
     pub enable_runtime_ownership: bool,
     /// Reserved for user-facing memory/data-segment feature gating if needed.
     pub enable_bulk_memory: bool,
-    /// Reserved for future multi-result function signature and call lowering.
+    /// Lowers multi-return functions to multi-value signatures and calls.
+    /// Without it, lowering a multi-return function is an error.
     pub enable_multi_value: bool,
-    /// Reserved for reference-type-capable host/runtime ABIs.
+    /// Passes opaque external package handles as `externref` instead of linear-memory handles.
     pub enable_reference_types: bool,
 }
 
@@ -126,7 +171,7 @@ impl Default for WasmEmitOptions {
             emit_wasm_module: true,
             validate_emitted_module: true,
             emit_name_section: false,
-            cfg_lowering_strategy: WasmCfgLoweringStrategy::DispatcherLoop,
+            cfg_lowering_strategy: WasmCfgLoweringStrategy::Structured,
         }
     }
 }
@@ -141,6 +186,8 @@ pub(crate) struct WasmDebugFlags {
     pub show_wasm_exports: bool,
     /// Emit runtime memory/layout summary.
     pub show_wasm_runtime_layout: bool,
+    /// Emit per-generic monomorphized instance counts and sizes.
+    pub show_wasm_generic_instances: bool,
     /// Emit Wasm section ordering and counts.
     pub show_wasm_sections: bool,
     /// Emit Wasm type/function/global/data index maps.
@@ -27,6 +27,8 @@ pub(crate) struct WasmDebugOutputs {
     pub exports_text: Option<String>,
     /// Runtime memory/layout summary.
     pub runtime_layout_text: Option<String>,
+    /// Monomorphized generic instance counts and sizes.
+    pub generic_instances_text: Option<String>,
     /// Wasm section counts and order summary.
     pub wasm_sections_text: Option<String>,
     /// Type/function/global/data index maps.
@@ -0,0 +1,59 @@
+//! Runtime builtin cast contracts.
+//!
+//! WHAT: maps each compiler-owned builtin cast policy to the way the Wasm backend implements it:
+//!       an inline conversion, a shared string/numeric helper, or a dedicated `rt_cast_*` helper.
+//! WHY: lowering keeps the policy id and the emitter picks helpers from it, so both sides need
+//!      one table. Semantics mirror `backends/js/runtime/casts.rs` so `html` and `html_wasm`
+//!      output match, including error codes and default messages.
+//!
+//! Infallible casts produce the target value directly. Fallible casts produce a fallible carrier
+//! holding the value or a builtin `Error` (see `runtime::errors`).
+//!
+//! Inline casts:
+//!   `Int -> Float` is `f64.convert_i64_s`, `Char -> Int` zero-extends the scalar,
+//!   `Bool -> String` selects an emitter-owned `"true"`/`"false"` string, `String -> Error` calls
+//!   `rt_error_new` with `UnknownOrUnassigned`, and `Error -> String` loads the message.
+//!
+//! Text parsing follows the JS runtime grammar: `String -> Int` accepts `-?\d+(_\d+)*` within the
+//! signed i32 range, and `String -> Float` accepts `-?\d+(_\d+)*(\.\d+(_\d+)*)?(e[+-]?\d+(_\d+)*)?`.
+//! Float text is validated in Wasm, stripped of underscores, then converted by the host's
+//! `Number.parseFloat` through [`WasmHostFunction::ParseFloat`], because correctly rounded decimal
+//! parsing needs arbitrary precision and must agree with the JS backend bit for bit.
+//!
+//! [`WasmHostFunction::ParseFloat`]: crate::backends::wasm::runtime::imports::WasmHostFunction
+
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
+
+/// The helper a cast policy calls, or `None` for casts emitted inline.
+pub(crate) fn cast_helper(policy: BuiltinCastPolicyId) -> Option<WasmRuntimeHelper> {
+    match policy {
+        BuiltinCastPolicyId::IntToFloat
+        | BuiltinCastPolicyId::CharToInt
+        | BuiltinCastPolicyId::BoolToString
+        | BuiltinCastPolicyId::ErrorToString => None,
+        BuiltinCastPolicyId::IntToString => Some(WasmRuntimeHelper::StringFromI64),
+        BuiltinCastPolicyId::FloatToString => Some(WasmRuntimeHelper::FloatFormat),
+        BuiltinCastPolicyId::StringToError => Some(WasmRuntimeHelper::ErrorNew),
+        BuiltinCastPolicyId::CharToString => Some(WasmRuntimeHelper::CastCharToString),
+        BuiltinCastPolicyId::FloatToInt => Some(WasmRuntimeHelper::CastFloatToInt),
+        BuiltinCastPolicyId::IntToChar => Some(WasmRuntimeHelper::CastIntToChar),
+        BuiltinCastPolicyId::StringToInt => Some(WasmRuntimeHelper::CastStringToInt),
+        BuiltinCastPolicyId::StringToFloat => Some(WasmRuntimeHelper::CastStringToFloat),
+        BuiltinCastPolicyId::StringToBool => Some(WasmRuntimeHelper::CastStringToBool),
+        BuiltinCastPolicyId::StringToChar => Some(WasmRuntimeHelper::CastStringToChar),
+    }
+}
+
+/// Whether a cast policy yields a fallible carrier instead of the target value.
+pub(crate) fn cast_returns_carrier(policy: BuiltinCastPolicyId) -> bool {
+    matches!(
+        policy,
+        BuiltinCastPolicyId::FloatToInt
+            | BuiltinCastPolicyId::IntToChar
+            | BuiltinCastPolicyId::StringToInt
+            | BuiltinCastPolicyId::StringToFloat
+            | BuiltinCastPolicyId::StringToBool
+            | BuiltinCastPolicyId::StringToChar
+    )
+}
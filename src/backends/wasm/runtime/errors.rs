@@ -0,0 +1,52 @@
+//! Runtime builtin `Error` layout contracts.
+//!
+//! WHAT: describes the linear-memory shape of builtin `Error` values and which builtin error
+//!       codes runtime helpers can raise on their own.
+//! WHY: fallible carriers, `cast`/`cast!` helpers and `Error` field reads all share this record,
+//!      so lowering and emission need one description. Messages match
+//!      `BuiltinErrorCode::default_message`, as in the JS runtime's `__bs_make_error` calls.
+//!
+//! Error record layout (8 bytes, allocated by `ErrorNew`):
+//!   offset 0: message (i32): finalized string handle
+//!   offset 4: code    (i32): builtin or user error code
+//!
+//! Error carriers hold the record handle, widened to i64, as their payload (see `runtime::maps`).
+//! Default messages are emitter-owned constant strings placed after static data, so
+//! `ErrorFromCode` never allocates a message.
+
+use crate::compiler_frontend::builtins::error_codes::BuiltinErrorCode;
+
+pub(crate) const ERROR_RECORD_SIZE: i32 = 8;
+pub(crate) const ERROR_MESSAGE_OFFSET: u64 = 0;
+pub(crate) const ERROR_CODE_OFFSET: u64 = 4;
+
+/// Codes that runtime helpers raise without a source-provided message.
+///
+/// `ErrorFromCode` falls back to the `UnknownOrUnassigned` message for any other code.
+pub(crate) const RUNTIME_ERROR_CODES: &[BuiltinErrorCode] = &[
+    BuiltinErrorCode::MapKeyNotFound,
+    BuiltinErrorCode::IntParseInvalidFormat,
+    BuiltinErrorCode::IntParseOutOfRange,
+    BuiltinErrorCode::FloatParseInvalidFormat,
+    BuiltinErrorCode::FloatParseOutOfRange,
+    BuiltinErrorCode::StringParseBoolInvalidFormat,
+    BuiltinErrorCode::StringParseCharInvalidFormat,
+    BuiltinErrorCode::FloatCastToIntInvalidValue,
+    BuiltinErrorCode::FloatCastToIntOutOfRange,
+    BuiltinErrorCode::IntCastToCharInvalidCodepoint,
+    BuiltinErrorCode::DivideByZero,
+    BuiltinErrorCode::IntOverflow,
+    BuiltinErrorCode::InvalidExponent,
+    BuiltinErrorCode::FloatNonFinite,
+    BuiltinErrorCode::FloatBoundaryNonFinite,
+    BuiltinErrorCode::FloatFormatInvariant,
+];
+
+/// A builtin `Error` field read lowered to a direct record load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasmErrorField {
+    /// `message`, a string handle.
+    Message,
+    /// `code`, stored as i32 and read as a Beanstalk `Int`.
+    Code,
+}
@@ -0,0 +1,100 @@
+//! Wasm GC object model contracts.
+//!
+//! WHAT: describes how the Wasm GC emission mode represents Beanstalk runtime values as GC
+//!       `struct`/`array` objects instead of linear-memory records.
+//! WHY: with `use_wasm_gc` the host collector owns every object's lifetime, so there is no
+//!      allocator, no `heap_top` global and no memory section. `DropIfOwned` and `RetainHandle`
+//!      become no-ops.
+//!
+//! Handle values:
+//!   every `WasmAbiType::Handle` local, param and result is a nullable `anyref`. Statements
+//!   `ref.cast` a handle to its concrete GC type before reading or writing it.
+//!
+//! Fixed type indices (always declared first, in this order):
+//!   0 bytes:         `array (mut i8)`: a finalized UTF-8 string, sized exactly to its content
+//!   1 handles:       `array (mut anyref)`: backing storage of a Vec
+//!   2 string buffer: `struct { bytes: mut (ref bytes), len: mut i32 }`: template builder with
+//!                    spare capacity in `bytes`
+//!   3 vec:           `struct { items: mut (ref handles), len: mut i32 }`
+//!
+//! Record types:
+//!   each LIR record type becomes one `struct` at `GC_RECORD_TYPE_BASE + id`, with one mutable
+//!   field per storage slot in slot order (`I32`/`I64`/`F32`/`F64` by value, `Handle` as
+//!   `anyref`). Choice records keep their tag in the first slot.
+//!
+//! Static data:
+//!   UTF-8 literals are passive data segments copied into fresh `bytes` arrays with
+//!   `array.new_data`.
+
+pub(crate) const GC_BYTES_TYPE: u32 = 0;
+pub(crate) const GC_HANDLES_TYPE: u32 = 1;
+pub(crate) const GC_STRING_BUFFER_TYPE: u32 = 2;
+pub(crate) const GC_VEC_TYPE: u32 = 3;
+pub(crate) const GC_RECORD_TYPE_BASE: u32 = 4;
+
+/// Field index of the backing array in string buffers and vecs.
+pub(crate) const GC_STORAGE_FIELD: u32 = 0;
+/// Field index of the logical length in string buffers and vecs.
+pub(crate) const GC_LENGTH_FIELD: u32 = 1;
+
+/// Initial byte capacity of a new string buffer.
+pub(crate) const GC_STRING_BUFFER_INITIAL_CAPACITY: i32 = 16;
+/// Initial element capacity of a new vec.
+pub(crate) const GC_VEC_INITIAL_CAPACITY: i32 = 4;
+
+/// Synthesized helpers of the Wasm GC emission mode.
+///
+/// Handle parameters and results are `anyref`, so host-facing accessors share the helpers
+/// used by lowered code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasmGcHelper {
+    /// Append a `bytes` array to a string buffer, growing its storage when needed.
+    BufferPush,
+    /// Copy a string buffer's content into an exactly sized `bytes` array.
+    StringFinish,
+    /// Format an i64 as decimal UTF-8 `bytes`.
+    StringFromI64,
+    /// Read the byte length of a finalized string (`bst_str_len`).
+    StringLen,
+    /// Read one byte of a finalized string (`bst_str_byte`).
+    StringByte,
+    /// Allocate an empty vec (`bst_vec_new`).
+    VecNew,
+    /// Append a handle to a vec, growing its storage when needed (`bst_vec_push`).
+    VecPush,
+    /// Read the element count of a vec (`bst_vec_len`).
+    VecLen,
+    /// Read one element of a vec, trapping past its length (`bst_vec_get`).
+    VecGet,
+}
+
+impl WasmGcHelper {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            WasmGcHelper::BufferPush => "rt_gc_buffer_push",
+            WasmGcHelper::StringFinish => "rt_gc_string_finish",
+            WasmGcHelper::StringFromI64 => "rt_gc_string_from_i64",
+            WasmGcHelper::StringLen => "rt_gc_string_len",
+            WasmGcHelper::StringByte => "rt_gc_string_byte",
+            WasmGcHelper::VecNew => "rt_gc_vec_new",
+            WasmGcHelper::VecPush => "rt_gc_vec_push",
+            WasmGcHelper::VecLen => "rt_gc_vec_len",
+            WasmGcHelper::VecGet => "rt_gc_vec_get",
+        }
+    }
+}
+
+/// Fixed emission order of the GC helpers.
+pub(crate) fn gc_helper_emit_order() -> [WasmGcHelper; 9] {
+    [
+        WasmGcHelper::BufferPush,
+        WasmGcHelper::StringFinish,
+        WasmGcHelper::StringFromI64,
+        WasmGcHelper::StringLen,
+        WasmGcHelper::StringByte,
+        WasmGcHelper::VecNew,
+        WasmGcHelper::VecPush,
+        WasmGcHelper::VecLen,
+        WasmGcHelper::VecGet,
+    ]
+}
@@ -1,14 +1,51 @@
 //! Host import identifiers reserved by the Wasm backend.
 
+use crate::backends::wasm::runtime::wasi::WASI_MODULE_NAME;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) enum WasmHostFunction {}
+pub(crate) enum WasmHostFunction {
+    /// `Math.pow`, used by checked Float `^` so results match the JS backend bit for bit.
+    MathPow,
+    /// `Number.parseFloat` over a string handle, used by the `String -> Float` cast.
+    ParseFloat,
+    /// WASI `fd_write(fd, iovs, iovs_len, nwritten) -> errno`.
+    WasiFdWrite,
+    /// WASI `fd_read(fd, iovs, iovs_len, nread) -> errno`.
+    WasiFdRead,
+    /// WASI `clock_time_get(id, precision, time) -> errno`.
+    WasiClockTimeGet,
+}
 
 impl WasmHostFunction {
     pub(crate) fn module_name(self) -> &'static str {
-        match self {}
+        match self {
+            WasmHostFunction::MathPow | WasmHostFunction::ParseFloat => "host",
+            WasmHostFunction::WasiFdWrite
+            | WasmHostFunction::WasiFdRead
+            | WasmHostFunction::WasiClockTimeGet => WASI_MODULE_NAME,
+        }
     }
 
     pub(crate) fn item_name(self) -> &'static str {
-        match self {}
+        match self {
+            WasmHostFunction::MathPow => "math_pow",
+            WasmHostFunction::ParseFloat => "parse_float",
+            WasmHostFunction::WasiFdWrite => "fd_write",
+            WasmHostFunction::WasiFdRead => "fd_read",
+            WasmHostFunction::WasiClockTimeGet => "clock_time_get",
+        }
+    }
+
+    /// Resolves a function import back to the host function it was registered for.
+    pub(crate) fn from_import_name(module_name: &str, item_name: &str) -> Option<Self> {
+        [
+            WasmHostFunction::MathPow,
+            WasmHostFunction::ParseFloat,
+            WasmHostFunction::WasiFdWrite,
+            WasmHostFunction::WasiFdRead,
+            WasmHostFunction::WasiClockTimeGet,
+        ]
+        .into_iter()
+        .find(|function| function.module_name() == module_name && function.item_name() == item_name)
     }
 }
@@ -0,0 +1,72 @@
+//! Runtime map and fallible-carrier layout contracts.
+//!
+//! WHAT: describes the linear-memory layout of insertion-ordered maps and of the fallible
+//!       carriers returned by map accessors.
+//! WHY: lowering picks the key comparison mode and the emitter synthesizes helpers that read
+//!      these layouts, so both sides need one shared description. Semantics mirror the JS
+//!      runtime in `backends/js/runtime/maps.rs`: insertion order, replace-in-place on `set`,
+//!      and `MapKeyNotFound` errors from `get` and `remove`.
+//!
+//! Map header layout (28 bytes, allocated by `MapNew`):
+//!   offset 0:  entries_ptr    (i32): pointer to the entry array, in insertion order
+//!   offset 4:  entry_count    (i32): used entry slots, including removed ones
+//!   offset 8:  len            (i32): live entry count
+//!   offset 12: entry_capacity (i32): allocated entry slots (0 or a power of two)
+//!   offset 16: index_ptr      (i32): pointer to the open-addressing index
+//!   offset 20: index_mask     (i32): index slot count minus one
+//!   offset 24: key_kind       (i32): [`WasmMapKeyKind`] discriminant
+//!
+//! Entry layout (24 bytes):
+//!   offset 0:  key   (i64): scalar bits, or a string handle widened to i64
+//!   offset 8:  value (i64): scalar bits, or a handle widened to i64
+//!   offset 16: hash  (i32): cached key hash
+//!   offset 20: live  (i32): 0 once the entry has been removed
+//!
+//! The index holds `entry_capacity * 2` i32 slots: 0 marks an empty slot, any other value is an
+//! entry position plus one. Removed entries stay in the index so probe chains remain intact, and
+//! growth rebuilds both arrays from the live entries, which keeps insertion order. The header
+//! block owns the entry array and the entry array owns the index (see `runtime::memory`), so a
+//! rebuild releases the outgrown pair and releasing a map frees all three blocks.
+//!
+//! Fallible carrier layout (16 bytes):
+//!   offset 0: tag     (i32): [`FALLIBLE_TAG_SUCCESS`] or [`FALLIBLE_TAG_ERROR`]
+//!   offset 8: payload (i64): success value, or an `Error` record handle (see `runtime::errors`)
+
+pub(crate) const MAP_HEADER_SIZE: i32 = 28;
+pub(crate) const MAP_ENTRIES_PTR_OFFSET: u64 = 0;
+pub(crate) const MAP_ENTRY_COUNT_OFFSET: u64 = 4;
+pub(crate) const MAP_LEN_OFFSET: u64 = 8;
+pub(crate) const MAP_ENTRY_CAPACITY_OFFSET: u64 = 12;
+pub(crate) const MAP_INDEX_PTR_OFFSET: u64 = 16;
+pub(crate) const MAP_INDEX_MASK_OFFSET: u64 = 20;
+pub(crate) const MAP_KEY_KIND_OFFSET: u64 = 24;
+
+pub(crate) const MAP_ENTRY_SIZE: i32 = 24;
+pub(crate) const MAP_ENTRY_KEY_OFFSET: u64 = 0;
+pub(crate) const MAP_ENTRY_VALUE_OFFSET: u64 = 8;
+pub(crate) const MAP_ENTRY_HASH_OFFSET: u64 = 16;
+pub(crate) const MAP_ENTRY_LIVE_OFFSET: u64 = 20;
+
+/// Entry capacity used by the first insertion into an empty map.
+pub(crate) const MAP_INITIAL_CAPACITY: i32 = 4;
+
+pub(crate) const FALLIBLE_CARRIER_SIZE: i32 = 16;
+pub(crate) const FALLIBLE_TAG_OFFSET: u64 = 0;
+pub(crate) const FALLIBLE_PAYLOAD_OFFSET: u64 = 8;
+pub(crate) const FALLIBLE_TAG_SUCCESS: i32 = 0;
+pub(crate) const FALLIBLE_TAG_ERROR: i32 = 1;
+
+/// How map helpers hash and compare keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasmMapKeyKind {
+    /// `Int`, `Bool` and `Char` keys compare by their widened bits.
+    Scalar = 0,
+    /// `String` keys compare by UTF-8 content, not by handle.
+    String = 1,
+}
+
+impl WasmMapKeyKind {
+    pub(crate) fn as_i32(self) -> i32 {
+        self as i32
+    }
+}
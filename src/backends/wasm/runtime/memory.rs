@@ -1,4 +1,28 @@
-//! Runtime memory planning structures.
+//! Runtime memory planning structures and the linear-memory allocator contract.
+//!
+//! Heap layout (from `heap_base`, see `HeapBaseStrategy`):
+//!   heap_base + 0:   free-list table, one i32 head per size class (`FREE_LIST_TABLE_SIZE` bytes)
+//!   heap_base + 128: blocks carved from `heap_top` when no free block of the class exists
+//!
+//! Block layout (`rt_alloc` returns the payload address, 8-byte aligned):
+//!   payload - 8: class word  (i32): size class `c` (block size `1 << c`), plus
+//!                `FREED_BLOCK_FLAG` while the block sits on a free list
+//!   payload - 4: link        (i32): live blocks: payload of the one block this block owns
+//!                exclusively (0 if none); free blocks: next free payload of the same class
+//!
+//! Size classes are powers of two from `MIN_SIZE_CLASS`, so a block of class `c` always holds
+//! `(1 << c) - ALLOC_HEADER_SIZE` payload bytes. Recycled payloads are zero-filled before reuse,
+//! matching the fresh pages `heap_top` grows into. `memory.grow` extends the memory when
+//! `heap_top` passes its end and traps if the host refuses.
+//!
+//! Ownership chains: `rt_release` frees a block and then every block reached through the link
+//! word (string -> content, vec -> storage, map -> entries -> index). Objects only link storage
+//! they own exclusively; handles stored inside them (vec elements, map values, record fields) are
+//! never followed, because they may be shared. Addresses below the first block (null, static
+//! data and runtime strings) are ignored, so releasing a static handle is harmless.
+//!
+//! The `live_allocations` global counts blocks handed out and not yet released. Debug builds and
+//! tests read it through the optional `bst_live_allocations` export.
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum HeapBaseStrategy {
@@ -28,3 +52,25 @@ impl Default for WasmMemoryPlan {
         }
     }
 }
+
+/// Bytes reserved in front of every payload for the class word and link word.
+pub(crate) const ALLOC_HEADER_SIZE: i32 = 8;
+/// Smallest size class: 16-byte blocks with 8 payload bytes.
+pub(crate) const MIN_SIZE_CLASS: i32 = 4;
+/// One free-list head per possible `log2` block size of a 32-bit address space.
+pub(crate) const SIZE_CLASS_COUNT: u32 = 32;
+/// Size of the free-list table at `heap_base`.
+pub(crate) const FREE_LIST_TABLE_SIZE: u32 = SIZE_CLASS_COUNT * 4;
+/// Set in the class word of a block while it is on a free list; releasing it again traps.
+pub(crate) const FREED_BLOCK_FLAG: i32 = 0x100;
+/// Offset of the class word from the block start (payload - `ALLOC_HEADER_SIZE`).
+pub(crate) const BLOCK_CLASS_OFFSET: u64 = 0;
+/// Offset of the link word from the block start.
+pub(crate) const BLOCK_LINK_OFFSET: u64 = 4;
+/// `log2` of the Wasm page size, used to turn `heap_top` into a page count.
+pub(crate) const WASM_PAGE_SIZE_LOG2: i32 = 16;
+
+/// First address `rt_alloc` can hand out for a module whose heap begins at `heap_base`.
+pub(crate) fn heap_start(heap_base: u32) -> u32 {
+    heap_base + FREE_LIST_TABLE_SIZE
+}
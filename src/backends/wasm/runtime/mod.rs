@@ -1,9 +1,18 @@
 //! Runtime-facing type contracts used by the Wasm LIR layer.
 //!
-//! WHAT: defines host imports, linear-memory layout constants, and runtime string contracts shared
-//! by lowering and byte emission.
+//! WHAT: defines host imports, linear-memory layout constants, and runtime string, map, `Error`
+//! and struct/choice record contracts shared by lowering and byte emission, plus checked numeric
+//! failure reporting, builtin cast semantics, the Wasm GC object model and the WASI command
+//! contract.
 //! WHY: runtime policy belongs here rather than in frontend HIR or project-builder code.
 
+pub(crate) mod casts;
+pub(crate) mod errors;
+pub(crate) mod gc;
 pub(crate) mod imports;
+pub(crate) mod maps;
 pub(crate) mod memory;
+pub(crate) mod numeric;
+pub(crate) mod records;
 pub(crate) mod strings;
+pub(crate) mod wasi;
@@ -0,0 +1,105 @@
+//! Checked numeric and Float formatting runtime contracts.
+//!
+//! WHAT: describes how checked `Int`/`Float` helpers report failures, and the scratch layout the
+//!       Float formatter uses for its arbitrary-precision arithmetic.
+//! WHY: lowering chooses between trapping and recoverable failure modes while the emitter
+//!      synthesizes the helpers, so both sides need one shared description. Semantics mirror the
+//!      JS runtime in `backends/js/runtime/numeric.rs` so `html` and `html_wasm` output match.
+//!
+//! Failure reporting:
+//!   Every checked helper writes the `numeric_status` global (i32) before returning: 0 on
+//!   success, otherwise the `BuiltinErrorCode` of the failure. The call site then either checks
+//!   the status inline and hits `unreachable` when it is non-zero, or calls `rt_numeric_carrier`
+//!   to wrap the value, or a builtin `Error` for the code, in a fallible carrier (see
+//!   `runtime::maps` and `runtime::errors`).
+//!
+//! Int semantics: Beanstalk `Int` values travel as i64 locals, but checked helpers enforce the
+//! signed i32 range of the JS runtime and report `IntOverflow` outside it.
+//!
+//! Float `^` calls the host's `Math.pow` through [`WasmHostFunction::MathPow`], because engine
+//! `pow` implementations differ in the last bit and text output must match the JS backend.
+//!
+//! Float formatting produces the shortest round-trip digits (ECMAScript `Number::toString`),
+//! using a Burger-Dybvig digit generator over little-endian u32-limb bignums. Scratch bignums
+//! are separate `rt_alloc` blocks that the formatter releases before returning.
+//!
+//! [`WasmHostFunction::MathPow`]: crate::backends::wasm::runtime::imports::WasmHostFunction
+
+use crate::backends::wasm::runtime::strings::WasmRuntimeHelper;
+
+pub(crate) const NUMERIC_STATUS_OK: i32 = 0;
+
+/// Smallest and largest values of Beanstalk `Int`.
+pub(crate) const INT_MIN: i64 = i32::MIN as i64;
+pub(crate) const INT_MAX: i64 = i32::MAX as i64;
+
+/// Limb count of one formatter bignum; 1280 bits covers every finite f64 scaling step.
+pub(crate) const BIGNUM_LIMBS: i32 = 40;
+pub(crate) const BIGNUM_SIZE: i32 = BIGNUM_LIMBS * 4;
+
+/// Upper bound on formatted Float length, e.g. `-1.2345678901234567e-308` is 24 bytes.
+pub(crate) const FLOAT_FORMAT_MAX_LEN: i32 = 32;
+
+/// A checked scalar operation lowered to a runtime helper call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasmNumericOp {
+    IntAdd,
+    IntSub,
+    IntMul,
+    IntDiv,
+    IntMod,
+    IntPow,
+    IntNeg,
+    FloatAdd,
+    FloatSub,
+    FloatMul,
+    FloatDiv,
+    FloatMod,
+    FloatNeg,
+    /// Reports `FloatNonFinite` for an already computed result, such as a host `Math.pow` call.
+    FloatCheckFinite,
+    /// Reports `FloatBoundaryNonFinite` for a Float entering from an external boundary.
+    FloatValidate,
+}
+
+impl WasmNumericOp {
+    pub(crate) fn is_unary(self) -> bool {
+        matches!(
+            self,
+            WasmNumericOp::IntNeg
+                | WasmNumericOp::FloatNeg
+                | WasmNumericOp::FloatCheckFinite
+                | WasmNumericOp::FloatValidate
+        )
+    }
+
+    /// Runtime helper implementing this operation.
+    pub(crate) fn helper(self) -> WasmRuntimeHelper {
+        match self {
+            WasmNumericOp::IntAdd => WasmRuntimeHelper::IntAdd,
+            WasmNumericOp::IntSub => WasmRuntimeHelper::IntSub,
+            WasmNumericOp::IntMul => WasmRuntimeHelper::IntMul,
+            WasmNumericOp::IntDiv => WasmRuntimeHelper::IntDiv,
+            WasmNumericOp::IntMod => WasmRuntimeHelper::IntMod,
+            WasmNumericOp::IntPow => WasmRuntimeHelper::IntPow,
+            WasmNumericOp::IntNeg => WasmRuntimeHelper::IntNeg,
+            WasmNumericOp::FloatAdd => WasmRuntimeHelper::FloatAdd,
+            WasmNumericOp::FloatSub => WasmRuntimeHelper::FloatSub,
+            WasmNumericOp::FloatMul => WasmRuntimeHelper::FloatMul,
+            WasmNumericOp::FloatDiv => WasmRuntimeHelper::FloatDiv,
+            WasmNumericOp::FloatMod => WasmRuntimeHelper::FloatMod,
+            WasmNumericOp::FloatNeg => WasmRuntimeHelper::FloatNeg,
+            WasmNumericOp::FloatCheckFinite => WasmRuntimeHelper::FloatCheckFinite,
+            WasmNumericOp::FloatValidate => WasmRuntimeHelper::FloatValidate,
+        }
+    }
+}
+
+/// How a checked operation surfaces a failure at its call site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasmNumericFailureMode {
+    /// `dst` is a fallible carrier handle holding the value or a builtin `Error`.
+    ReturnError,
+    /// `dst` receives the scalar value; failures trap.
+    Trap,
+}
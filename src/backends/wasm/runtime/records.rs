@@ -0,0 +1,110 @@
+//! Runtime struct and choice record layout contracts.
+//!
+//! WHAT: computes the linear-memory shape of user struct and choice values from the ABI types of
+//!       their fields.
+//! WHY: struct and choice values are handles to heap records. Lowering picks field offsets and
+//!      the emitter only loads and stores at them, so both sides share one layout rule. Generic
+//!      nominal types are monomorphized before layout: `Box of Int` and `Box of String` are
+//!      separate HIR structs with concrete field types, so each instance gets its own layout.
+//!
+//! Struct record layout:
+//!   fields in declaration order, each at its natural alignment:
+//!   `I32`, `F32` and `Handle` fields take 4 bytes, `I64` and `F64` fields take 8 bytes, and
+//!   unit (`Void`) fields take no space. The record size is rounded up to the largest alignment.
+//!
+//! Choice record layout:
+//!   offset 0: tag (i32): variant index
+//!   each variant's payload fields follow the tag using the struct rule, so variants overlap and
+//!   the record size is that of the largest variant.
+
+use crate::backends::wasm::lir::types::WasmAbiType;
+
+pub(crate) const CHOICE_TAG_OFFSET: u32 = 0;
+const CHOICE_TAG_SIZE: u32 = 4;
+
+/// One field slot inside a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct WasmRecordField {
+    /// Byte offset from the record handle.
+    pub offset: u32,
+    /// ABI type stored at `offset`; `Void` fields are never loaded or stored.
+    pub abi: WasmAbiType,
+}
+
+/// Layout of one concrete struct record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WasmRecordLayout {
+    pub size: u32,
+    /// Field slots in declaration order.
+    pub fields: Vec<WasmRecordField>,
+}
+
+/// Layout of one concrete choice record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WasmChoiceLayout {
+    pub size: u32,
+    /// Payload field slots per variant, indexed by variant index.
+    pub variants: Vec<Vec<WasmRecordField>>,
+}
+
+/// Lays out a struct record with the given field ABI types.
+pub(crate) fn plan_struct_layout(field_abis: &[WasmAbiType]) -> WasmRecordLayout {
+    let (fields, end, align) = place_fields(0, 1, field_abis);
+    WasmRecordLayout {
+        size: align_to(end, align),
+        fields,
+    }
+}
+
+/// Lays out a choice record with the given payload field ABI types per variant.
+pub(crate) fn plan_choice_layout(variant_field_abis: &[Vec<WasmAbiType>]) -> WasmChoiceLayout {
+    let mut size = CHOICE_TAG_SIZE;
+    let mut variants = Vec::with_capacity(variant_field_abis.len());
+
+    for field_abis in variant_field_abis {
+        let (fields, end, align) = place_fields(CHOICE_TAG_SIZE, CHOICE_TAG_SIZE, field_abis);
+        size = size.max(align_to(end, align));
+        variants.push(fields);
+    }
+
+    WasmChoiceLayout { size, variants }
+}
+
+/// Bytes and alignment one field of the given ABI type occupies.
+pub(crate) fn abi_slot_size(abi: WasmAbiType) -> u32 {
+    match abi {
+        // Host references cannot live in linear memory; record stores and loads reject them.
+        WasmAbiType::Void | WasmAbiType::ExternRef => 0,
+        WasmAbiType::I32 | WasmAbiType::F32 | WasmAbiType::Handle => 4,
+        WasmAbiType::I64 | WasmAbiType::F64 => 8,
+    }
+}
+
+fn place_fields(
+    start: u32,
+    start_align: u32,
+    field_abis: &[WasmAbiType],
+) -> (Vec<WasmRecordField>, u32, u32) {
+    let mut cursor = start;
+    let mut max_align = start_align;
+    let mut fields = Vec::with_capacity(field_abis.len());
+
+    for abi in field_abis {
+        let size = abi_slot_size(*abi);
+        if size > 0 {
+            cursor = align_to(cursor, size);
+            max_align = max_align.max(size);
+        }
+        fields.push(WasmRecordField {
+            offset: cursor,
+            abi: *abi,
+        });
+        cursor += size;
+    }
+
+    (fields, cursor, max_align)
+}
+
+fn align_to(value: u32, align: u32) -> u32 {
+    value.div_ceil(align) * align
+}
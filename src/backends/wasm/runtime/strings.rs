@@ -8,6 +8,9 @@
 //! Finalized string layout (8 bytes, produced by `StringFinish`):
 //!   offset 0: ptr (i32): pointer to UTF-8 byte content
 //!   offset 4: len (i32): byte length
+//!
+//! `StringFinish` consumes its buffer: the string block takes ownership of the content region
+//! and the buffer header is released (see `runtime::memory`).
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum WasmRuntimeHelper {
@@ -35,8 +38,103 @@ pub(crate) enum WasmRuntimeHelper {
     VecLen,
     /// Read one element handle from a Vec handle.
     VecGet,
-    /// Reserved release helper for ownership tuning.
+    /// Return a block and its owned chain to the allocator free lists (see `runtime::memory`).
     Release,
-    /// Conditional drop hook used at `possible_drop` sites.
+    /// Release hook used at `possible_drop` sites that lowering proved uniquely owned.
     DropIfOwned,
+    /// Allocate an empty insertion-ordered map header (see `runtime::maps`).
+    MapNew,
+    /// Look up a key, returning a fallible carrier with the value or `MapKeyNotFound`.
+    MapGet,
+    /// Report whether a live entry exists for a key.
+    MapContains,
+    /// Insert or replace a value, returning a success carrier.
+    MapSet,
+    /// Remove a key, returning a fallible carrier with the removed value or `MapKeyNotFound`.
+    MapRemove,
+    /// Drop every entry while keeping the allocated storage.
+    MapClear,
+    /// Read the live entry count as an `Int`.
+    MapLength,
+    /// Internal: hash a widened key according to the map's key kind.
+    MapHashKey,
+    /// Internal: compare two widened keys according to the map's key kind.
+    MapKeysEqual,
+    /// Internal: find the entry position for a key, or -1.
+    MapFind,
+    /// Internal: compact live entries into fresh storage with the requested capacity.
+    MapRebuild,
+    /// Internal: allocate a fallible carrier from a tag and payload.
+    FallibleNew,
+    /// Allocate a builtin `Error` record from a message handle and code (see `runtime::errors`).
+    ErrorNew,
+    /// Build a builtin `Error` with the default message for a runtime error code.
+    ErrorFromCode,
+    /// Wrap the builtin `Error` for a runtime error code in an error carrier.
+    FallibleError,
+    /// Wrap a widened value, or the pending `numeric_status` error, in a fallible carrier.
+    NumericCarrier,
+    /// Checked `Int` arithmetic (see `runtime::numeric`); each sets `numeric_status`.
+    IntAdd,
+    IntSub,
+    IntMul,
+    IntDiv,
+    IntMod,
+    IntPow,
+    IntNeg,
+    /// Checked `Float` arithmetic; each sets `numeric_status`.
+    FloatAdd,
+    FloatSub,
+    FloatMul,
+    FloatDiv,
+    FloatMod,
+    FloatNeg,
+    /// Report `FloatNonFinite` for an already computed Float result.
+    FloatCheckFinite,
+    /// Report `FloatBoundaryNonFinite` for a Float entering from an external boundary.
+    FloatValidate,
+    /// Format a finite Float as its shortest round-trip decimal string.
+    FloatFormat,
+    /// Internal: zero a formatter bignum and store a u64 into its low limbs.
+    BignumSet,
+    /// Internal: multiply a bignum in place by a u32.
+    BignumMulSmall,
+    /// Internal: multiply a bignum in place by a power of ten.
+    BignumMulPow10,
+    /// Internal: shift a bignum left in place by a bit count.
+    BignumShl,
+    /// Internal: compare two bignums, returning -1, 0 or 1.
+    BignumCompare,
+    /// Internal: store the sum of two bignums into a third.
+    BignumAdd,
+    /// Internal: subtract a smaller bignum in place.
+    BignumSub,
+    /// Builtin `Char -> String` cast: UTF-8 encode one scalar (see `runtime::casts`).
+    CastCharToString,
+    /// Builtin `Float -> Int` cast returning a fallible carrier.
+    CastFloatToInt,
+    /// Builtin `Int -> Char` cast returning a fallible carrier.
+    CastIntToChar,
+    /// Builtin `String -> Int` cast returning a fallible carrier.
+    CastStringToInt,
+    /// Builtin `String -> Float` cast returning a fallible carrier.
+    CastStringToFloat,
+    /// Builtin `String -> Bool` cast returning a fallible carrier.
+    CastStringToBool,
+    /// Builtin `String -> Char` cast returning a fallible carrier.
+    CastStringToChar,
+    /// Internal: byte length of the `String.prototype.trim` whitespace at an address, or 0.
+    StringWhitespaceLen,
+    /// Internal: write bytes to a WASI fd, retrying partial writes (see `runtime::wasi`).
+    WasiWriteAll,
+    /// Write a finalized string and a newline to a WASI fd.
+    WasiWriteLine,
+    /// Internal: read one stdin line, newline included, into a fresh string handle.
+    WasiReadLine,
+    /// Move the current terminal input key to the previous slot and read the next one.
+    WasiInputUpdate,
+    /// Report whether a string names the key in one slot of a terminal input state.
+    WasiInputKeyMatches,
+    /// Read a WASI clock as `f64` milliseconds.
+    WasiClockMillis,
 }
@@ -0,0 +1,170 @@
+//! WASI preview1 command contract.
+//!
+//! WHAT: names the adapters that `ExternalWasmLowering::WasiAdapter` functions lower to and the
+//! `wasi_snapshot_preview1` imports each one needs.
+//! WHY: a WASI command has no JS glue, so console output, stdin and clocks go straight to the
+//! runtime's file descriptors and clocks instead of `host.*` imports.
+//!
+//! Adapter semantics follow the JS lowerings of the same functions:
+//! - console output writes the string and a `\n` to stdout or stderr, like `console.log`
+//! - `io.input.*` polls a terminal: each `update` reads one stdin line and treats it as one key
+//!   press (see the input state layout below); there is no pointer, so pointer coordinates are
+//!   `0.0` and button queries are `false`
+//! - time values are `f64` milliseconds; `TimeMark`s read the monotonic clock and `Timestamp`s
+//!   the realtime clock
+//!
+//! Write errors are ignored, matching `console.log`; a failing clock read traps.
+//!
+//! Input state layout (`INPUT_STATE_SIZE` bytes, allocated by `io.input.new`):
+//!   offset 0:  closed       (i32): non-zero after `io.input.close`
+//!   offset 4:  current len  (i32): byte length of the key read by the latest `update`, 0 if none
+//!   offset 8:  previous len (i32): byte length of the key read by the update before it
+//!   offset 12: current key  (`INPUT_KEY_CAPACITY` bytes)
+//!   offset 44: previous key (`INPUT_KEY_CAPACITY` bytes)
+//!
+//! A line becomes a key after dropping its `\n` / `\r\n` and normalizing like the JS runtime's
+//! `__bs_io_input_normalize_key`: an empty line is `"Enter"`, a single space `"Space"` and a single
+//! ASCII capital its lowercase letter. The current key is both down and pressed; it counts as
+//! released on the next `update`. End of input and lines longer than `INPUT_KEY_CAPACITY` bytes
+//! leave no current key.
+
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+
+/// Import module of every WASI preview1 function.
+pub(crate) const WASI_MODULE_NAME: &str = "wasi_snapshot_preview1";
+pub(crate) const WASI_STDIN: i32 = 0;
+pub(crate) const WASI_STDOUT: i32 = 1;
+pub(crate) const WASI_STDERR: i32 = 2;
+
+pub(crate) const INPUT_CLOSED_OFFSET: u64 = 0;
+pub(crate) const INPUT_KEY_LEN_OFFSET: u64 = 4;
+pub(crate) const INPUT_KEY_BYTES_OFFSET: u64 = 12;
+pub(crate) const INPUT_KEY_CAPACITY: i32 = 32;
+pub(crate) const INPUT_STATE_SIZE: i32 = 12 + 2 * INPUT_KEY_CAPACITY;
+
+/// Which key slot of the input state a key query reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasiInputKeySlot {
+    /// Key read by the latest `update` (`key_down`, `key_pressed`).
+    Current,
+    /// Key read by the update before it (`key_released`).
+    Previous,
+}
+
+impl WasiInputKeySlot {
+    pub(crate) fn index(self) -> i32 {
+        match self {
+            WasiInputKeySlot::Current => 0,
+            WasiInputKeySlot::Previous => 1,
+        }
+    }
+}
+
+/// WASI `clockid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasiClock {
+    Realtime,
+    Monotonic,
+}
+
+impl WasiClock {
+    pub(crate) fn id(self) -> i32 {
+        match self {
+            WasiClock::Realtime => 0,
+            WasiClock::Monotonic => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum WasiAdapter {
+    /// Write the string argument and a newline to `fd`.
+    WriteLine {
+        fd: i32,
+    },
+    /// Write runtime fragment `index` of the entry fragment Vec argument and a newline to stdout.
+    /// Only the synthesized `_start` uses it.
+    WriteFragmentLine {
+        index: u32,
+    },
+    /// Allocate a fresh terminal input state.
+    InputNew,
+    /// Shift the current key to the previous slot and read the next stdin line as a key.
+    InputUpdate,
+    /// Mark an input state closed; later queries report no keys.
+    InputClose,
+    /// Whether the string argument names the key in `slot` of the input state argument.
+    InputKey(WasiInputKeySlot),
+    /// Pointer coordinate of a terminal: always `0.0`.
+    NoPointerCoordinate,
+    /// Pointer button query of a terminal: always `false`.
+    NoPointerButton,
+    /// Current time of `clock` in milliseconds.
+    ClockNow(WasiClock),
+    /// Monotonic milliseconds elapsed since the `TimeMark` argument.
+    ElapsedSince,
+    /// Second argument minus the first.
+    Difference,
+    SecondsToMillis,
+    MillisToSeconds,
+    /// Returns the argument unchanged (unit-preserving conversions).
+    Identity,
+    IsNegative,
+    Abs,
+    /// `min(max(value, low), high)`, like `Math.min(Math.max(..))`.
+    Clamp,
+}
+
+impl WasiAdapter {
+    /// Resolves the adapter name registered by a core package.
+    pub(crate) fn from_lowering_name(name: &str) -> Option<Self> {
+        let adapter = match name {
+            "stdout_line" => WasiAdapter::WriteLine { fd: WASI_STDOUT },
+            "stderr_line" => WasiAdapter::WriteLine { fd: WASI_STDERR },
+            "stdin_input_new" => WasiAdapter::InputNew,
+            "stdin_input_update" => WasiAdapter::InputUpdate,
+            "stdin_input_close" => WasiAdapter::InputClose,
+            "stdin_key_current" => WasiAdapter::InputKey(WasiInputKeySlot::Current),
+            "stdin_key_previous" => WasiAdapter::InputKey(WasiInputKeySlot::Previous),
+            "no_pointer_coordinate" => WasiAdapter::NoPointerCoordinate,
+            "no_pointer_button" => WasiAdapter::NoPointerButton,
+            "monotonic_now" => WasiAdapter::ClockNow(WasiClock::Monotonic),
+            "realtime_now" => WasiAdapter::ClockNow(WasiClock::Realtime),
+            "elapsed_since" => WasiAdapter::ElapsedSince,
+            "difference" => WasiAdapter::Difference,
+            "seconds_to_millis" => WasiAdapter::SecondsToMillis,
+            "millis_to_seconds" => WasiAdapter::MillisToSeconds,
+            "identity" => WasiAdapter::Identity,
+            "is_negative" => WasiAdapter::IsNegative,
+            "abs" => WasiAdapter::Abs,
+            "clamp" => WasiAdapter::Clamp,
+            _ => return None,
+        };
+        Some(adapter)
+    }
+
+    /// WASI import the adapter reaches through its runtime helper, if any.
+    pub(crate) fn required_import(self) -> Option<WasmHostFunction> {
+        match self {
+            WasiAdapter::WriteLine { .. } | WasiAdapter::WriteFragmentLine { .. } => {
+                Some(WasmHostFunction::WasiFdWrite)
+            }
+            WasiAdapter::InputUpdate => Some(WasmHostFunction::WasiFdRead),
+            WasiAdapter::ClockNow(_) | WasiAdapter::ElapsedSince => {
+                Some(WasmHostFunction::WasiClockTimeGet)
+            }
+            WasiAdapter::InputNew
+            | WasiAdapter::InputClose
+            | WasiAdapter::InputKey(_)
+            | WasiAdapter::NoPointerCoordinate
+            | WasiAdapter::NoPointerButton
+            | WasiAdapter::Difference
+            | WasiAdapter::SecondsToMillis
+            | WasiAdapter::MillisToSeconds
+            | WasiAdapter::Identity
+            | WasiAdapter::IsNegative
+            | WasiAdapter::Abs
+            | WasiAdapter::Clamp => None,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::backends::error_types::BackendErrorType;
 use crate::backends::wasm::backend::lower_hir_to_wasm_module;
 use crate::backends::wasm::emit::module::emit_lir_to_wasm_module;
+use crate::backends::wasm::emit::structured_cfg::{WasmStructuredNode, structure_function_body};
 use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
 use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
 use crate::backends::wasm::lir::linkage::{
@@ -17,7 +18,8 @@ use crate::backends::wasm::request::{
 };
 use crate::backends::wasm::runtime::memory::WasmMemoryPlan;
 use crate::backends::wasm::tests::lowering::test_support::{
-    build_module, build_type_environment, default_borrow_facts, int_expression,
+    build_module, build_multi_return_module, build_type_environment, default_borrow_facts,
+    int_expression,
 };
 use crate::compiler_frontend::compiler_messages::compiler_errors::ErrorType;
 use crate::compiler_frontend::hir::blocks::HirBlock;
@@ -108,6 +110,8 @@ fn rejects_invalid_helper_export_policy() {
         export_vec_len: false,
         export_vec_get: false,
         export_release: false,
+        export_str_byte: false,
+        export_live_allocations: false,
     };
 
     let mut string_table = StringTable::new();
@@ -177,97 +181,262 @@ fn rejects_mismatched_numeric_add_types() {
 }
 
 #[test]
-fn rejects_unsupported_wasm_feature_flags() {
-    let mut request = WasmBackendRequest::default();
-    request.target_features.use_wasm_gc = true;
-
+fn emits_valid_multi_value_functions_and_calls() {
     let mut string_table = StringTable::new();
-    let (type_environment, types) = build_type_environment();
-    let start_path = InternedPath::from_single_str("main", &mut string_table);
-    let start_block = HirBlock {
-        id: BlockId(0),
-        region: RegionId(0),
-        locals: vec![],
-        statements: vec![],
-        terminator: HirTerminator::Return(int_expression(1, 0, types.int, RegionId(0))),
-    };
-    let start_function = HirFunction {
-        id: FunctionId(0),
-        entry: BlockId(0),
-        params: vec![],
-        return_type: types.int,
-        return_aliases: vec![],
-    };
-    let hir_module = build_module(
-        &mut string_table,
-        vec![(start_function, start_path, HirFunctionOrigin::EntryStart)],
-        vec![start_block],
-        FunctionId(0),
-    );
+    let (mut type_environment, types) = build_type_environment();
+    let hir_module = build_multi_return_module(&mut string_table, &mut type_environment, types);
 
-    let error = lower_hir_to_wasm_module(
+    let mut request = WasmBackendRequest::default();
+    request.target_features.enable_multi_value = true;
+    request.emit_options.validate_emitted_module = false;
+    let result = lower_hir_to_wasm_module(
         &hir_module,
         &default_borrow_facts(),
         &request,
         &string_table,
         &type_environment,
     )
-    .expect_err("unsupported feature toggle should fail");
-    let (error_type, message, _location) = error
-        .first_infrastructure_error_for_tests()
-        .expect("Wasm generation failure should be wrapped for rendering");
-    assert_eq!(
-        error_type,
-        &ErrorType::Backend(BackendErrorType::WasmGeneration)
+    .expect("multi-value lowering should emit module bytes");
+    let wasm_bytes = result.wasm_bytes.expect("wasm bytes should be available");
+    validate_wasm(&wasm_bytes);
+
+    let result_counts = collect_function_type_result_counts(&wasm_bytes);
+    assert!(
+        result_counts.contains(&2),
+        "pair should declare two results"
     );
-    assert!(message.contains("use_wasm_gc"));
 }
 
 #[test]
-fn rejects_unsupported_cfg_lowering_strategy() {
-    let mut request = WasmBackendRequest::default();
-    request.emit_options.cfg_lowering_strategy = WasmCfgLoweringStrategy::Structured;
-
+fn rejects_multi_return_functions_without_multi_value_feature() {
     let mut string_table = StringTable::new();
-    let (type_environment, types) = build_type_environment();
-    let start_path = InternedPath::from_single_str("main", &mut string_table);
-    let start_block = HirBlock {
-        id: BlockId(0),
-        region: RegionId(0),
-        locals: vec![],
-        statements: vec![],
-        terminator: HirTerminator::Return(int_expression(1, 0, types.int, RegionId(0))),
-    };
-    let start_function = HirFunction {
-        id: FunctionId(0),
-        entry: BlockId(0),
-        params: vec![],
-        return_type: types.int,
-        return_aliases: vec![],
-    };
-    let hir_module = build_module(
-        &mut string_table,
-        vec![(start_function, start_path, HirFunctionOrigin::EntryStart)],
-        vec![start_block],
-        FunctionId(0),
-    );
+    let (mut type_environment, types) = build_type_environment();
+    let hir_module = build_multi_return_module(&mut string_table, &mut type_environment, types);
 
     let error = lower_hir_to_wasm_module(
         &hir_module,
         &default_borrow_facts(),
-        &request,
+        &WasmBackendRequest::default(),
         &string_table,
         &type_environment,
     )
-    .expect_err("unsupported cfg strategy should fail");
-    let (error_type, message, _location) = error
+    .expect_err("multi-return lowering without multi-value should fail");
+    let (_error_type, message, _location) = error
         .first_infrastructure_error_for_tests()
-        .expect("Wasm generation failure should be wrapped for rendering");
+        .expect("Wasm lowering failure should be wrapped for rendering");
+    assert!(message.contains("enable_multi_value"));
+}
+
+#[test]
+fn emits_valid_externref_host_imports() {
+    let mut module = build_manual_lir_module();
+    module.imports = vec![WasmImport {
+        id: WasmImportId(0),
+        module_name: "host".to_owned(),
+        item_name: "context_width".to_owned(),
+        kind: WasmImportKind::Function(WasmLirSignature {
+            params: vec![WasmAbiType::ExternRef],
+            results: vec![WasmAbiType::I64],
+        }),
+    }];
+    module.functions = vec![WasmLirFunction {
+        id: WasmLirFunctionId(0),
+        debug_name: "measure".to_owned(),
+        origin: WasmLirFunctionOrigin::Normal,
+        signature: WasmLirSignature {
+            params: vec![WasmAbiType::ExternRef],
+            results: vec![WasmAbiType::I64],
+        },
+        locals: vec![
+            WasmLirLocal {
+                id: WasmLirLocalId(0),
+                name: Some("context".to_owned()),
+                ty: WasmAbiType::ExternRef,
+                role: WasmLocalRole::Param,
+            },
+            local(1, WasmAbiType::I64, "width"),
+        ],
+        blocks: vec![lir_block(
+            0,
+            vec![WasmLirStmt::Call {
+                dst: Some(WasmLirLocalId(1)),
+                callee: WasmCalleeRef::Import(WasmImportId(0)),
+                args: vec![WasmLirLocalId(0)],
+            }],
+            WasmLirTerminator::Return {
+                value: Some(WasmLirLocalId(1)),
+            },
+        )],
+        linkage: WasmFunctionLinkage::ExportedWrapper,
+    }];
+    module.exports = vec![WasmExport {
+        export_name: "measure".to_owned(),
+        kind: WasmExportKind::Function(WasmLirFunctionId(0)),
+    }];
+    module.static_data.clear();
+
+    let emitted = emit_lir_to_wasm_module(&module, &WasmBackendRequest::default())
+        .expect("externref module should emit");
+    validate_wasm(&emitted.wasm_bytes);
+}
+
+#[test]
+fn lowers_hir_with_each_cfg_lowering_strategy() {
+    for strategy in [
+        WasmCfgLoweringStrategy::Structured,
+        WasmCfgLoweringStrategy::DispatcherLoop,
+    ] {
+        let mut request = WasmBackendRequest::default();
+        request.emit_options.cfg_lowering_strategy = strategy;
+
+        let mut string_table = StringTable::new();
+        let (type_environment, types) = build_type_environment();
+        let start_path = InternedPath::from_single_str("main", &mut string_table);
+        let start_block = HirBlock {
+            id: BlockId(0),
+            region: RegionId(0),
+            locals: vec![],
+            statements: vec![],
+            terminator: HirTerminator::Return(int_expression(1, 0, types.int, RegionId(0))),
+        };
+        let start_function = HirFunction {
+            id: FunctionId(0),
+            entry: BlockId(0),
+            params: vec![],
+            return_type: types.int,
+            return_aliases: vec![],
+        };
+        let hir_module = build_module(
+            &mut string_table,
+            vec![(start_function, start_path, HirFunctionOrigin::EntryStart)],
+            vec![start_block],
+            FunctionId(0),
+        );
+
+        let result = lower_hir_to_wasm_module(
+            &hir_module,
+            &default_borrow_facts(),
+            &request,
+            &string_table,
+            &type_environment,
+        )
+        .unwrap_or_else(|_| panic!("{strategy:?} lowering should emit a valid module"));
+        validate_wasm(&result.wasm_bytes.expect("wasm bytes should be available"));
+    }
+}
+
+#[test]
+fn structured_lowering_nests_loops_and_merges_without_a_dispatch_local() {
+    let module = single_function_module(counting_loop_blocks());
+
+    let structured = emit_lir_to_wasm_module(
+        &module,
+        &request_with_cfg_strategy(WasmCfgLoweringStrategy::Structured),
+    )
+    .expect("structured emission should succeed");
+    validate_wasm(&structured.wasm_bytes);
+    let operators = collect_operator_names(&structured.wasm_bytes);
+    assert!(operators.iter().any(|name| name == "Loop"));
+    assert!(operators.iter().any(|name| name == "If"));
+    assert!(!operators.iter().any(|name| name == "I32Eq"));
+    assert_eq!(collect_declared_local_count(&structured.wasm_bytes), 4);
+
+    let dispatcher = emit_lir_to_wasm_module(
+        &module,
+        &request_with_cfg_strategy(WasmCfgLoweringStrategy::DispatcherLoop),
+    )
+    .expect("dispatcher emission should succeed");
+    validate_wasm(&dispatcher.wasm_bytes);
+    assert!(
+        collect_operator_names(&dispatcher.wasm_bytes)
+            .iter()
+            .any(|name| name == "I32Eq")
+    );
+    assert_eq!(collect_declared_local_count(&dispatcher.wasm_bytes), 5);
+}
+
+#[test]
+fn structured_lowering_falls_back_to_dispatcher_for_irreducible_cfg() {
+    // Both loop blocks are entered from block 0, so neither dominates the other.
+    let blocks = vec![
+        lir_block(
+            0,
+            vec![WasmLirStmt::ConstI32 {
+                dst: WasmLirLocalId(2),
+                value: 1,
+            }],
+            branch(2, 1, 2),
+        ),
+        lir_block(1, vec![], branch(2, 2, 3)),
+        lir_block(2, vec![], WasmLirTerminator::Jump(WasmLirBlockId(1))),
+        lir_block(
+            3,
+            vec![],
+            WasmLirTerminator::Return {
+                value: Some(WasmLirLocalId(0)),
+            },
+        ),
+    ];
+    let module = single_function_module(blocks);
     assert_eq!(
-        error_type,
-        &ErrorType::Backend(BackendErrorType::WasmGeneration)
+        structure_function_body(&module.functions[0], WasmLirBlockId(0)),
+        None
+    );
+
+    let emitted = emit_lir_to_wasm_module(
+        &module,
+        &request_with_cfg_strategy(WasmCfgLoweringStrategy::Structured),
+    )
+    .expect("irreducible emission should fall back to the dispatcher");
+    validate_wasm(&emitted.wasm_bytes);
+    assert!(
+        collect_operator_names(&emitted.wasm_bytes)
+            .iter()
+            .any(|name| name == "I32Eq")
+    );
+}
+
+#[test]
+fn structures_diamond_merge_as_block_followed_by_merge_node() {
+    let module = single_function_module(vec![
+        lir_block(0, vec![], branch(2, 1, 2)),
+        lir_block(1, vec![], WasmLirTerminator::Jump(WasmLirBlockId(3))),
+        lir_block(2, vec![], WasmLirTerminator::Jump(WasmLirBlockId(3))),
+        lir_block(
+            3,
+            vec![],
+            WasmLirTerminator::Return {
+                value: Some(WasmLirLocalId(0)),
+            },
+        ),
+    ]);
+    let function = &module.functions[0];
+    let block = |id: usize| &function.blocks[id];
+
+    let body = structure_function_body(function, WasmLirBlockId(0))
+        .expect("diamond CFG should be reducible");
+
+    assert_eq!(
+        body,
+        vec![
+            WasmStructuredNode::Block(vec![
+                WasmStructuredNode::Statements(block(0)),
+                WasmStructuredNode::If {
+                    condition: WasmLirLocalId(2),
+                    then_body: vec![
+                        WasmStructuredNode::Statements(block(1)),
+                        WasmStructuredNode::Br(1),
+                    ],
+                    else_body: vec![
+                        WasmStructuredNode::Statements(block(2)),
+                        WasmStructuredNode::Br(1),
+                    ],
+                },
+            ]),
+            WasmStructuredNode::Statements(block(3)),
+            WasmStructuredNode::Exit(&block(3).terminator),
+        ]
     );
-    assert!(message.contains("dispatcher-loop"));
 }
 
 #[test]
@@ -309,6 +478,57 @@ fn validate_wasm(bytes: &[u8]) {
         .expect("emitted bytes should validate");
 }
 
+fn collect_operator_names(bytes: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        let payload = payload.expect("payload should parse");
+        if let wasmparser::Payload::CodeSectionEntry(body) = payload {
+            let mut reader = body.get_operators_reader().expect("operators should parse");
+            while !reader.eof() {
+                let operator = reader.read().expect("operator should parse");
+                let debug = format!("{operator:?}");
+                let name = debug
+                    .split(|character: char| !character.is_alphanumeric())
+                    .next()
+                    .unwrap_or_default();
+                names.push(name.to_owned());
+            }
+        }
+    }
+    names
+}
+
+fn collect_declared_local_count(bytes: &[u8]) -> u32 {
+    let mut count = 0;
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        let payload = payload.expect("payload should parse");
+        if let wasmparser::Payload::CodeSectionEntry(body) = payload {
+            for local in body.get_locals_reader().expect("locals should parse") {
+                count += local.expect("local should parse").0;
+            }
+        }
+    }
+    count
+}
+
+fn collect_function_type_result_counts(bytes: &[u8]) -> Vec<usize> {
+    let mut counts = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        if let wasmparser::Payload::TypeSection(reader) = payload.expect("payload should parse") {
+            for group in reader {
+                for sub_type in group.expect("rec group should parse").into_types() {
+                    if let wasmparser::CompositeInnerType::Func(func_type) =
+                        sub_type.composite_type.inner
+                    {
+                        counts.push(func_type.results().len());
+                    }
+                }
+            }
+        }
+    }
+    counts
+}
+
 fn collect_export_names(bytes: &[u8]) -> Vec<String> {
     let mut names = Vec::new();
     for payload in wasmparser::Parser::new(0).parse_all(bytes) {
@@ -371,7 +591,10 @@ fn request_with_helper_exports() -> WasmBackendRequest {
                 export_vec_len: true,
                 export_vec_get: true,
                 export_release: true,
+                export_str_byte: false,
+                export_live_allocations: false,
             },
+            command_start: None,
         },
         target_features: WasmTargetFeatures::default(),
         emit_options: WasmEmitOptions {
@@ -388,10 +611,145 @@ fn request_with_helper_exports() -> WasmBackendRequest {
             ..Default::default()
         },
         external_package_registry: Default::default(),
+        host_interface: Default::default(),
         function_emission_policy: Default::default(),
     }
 }
 
+fn request_with_cfg_strategy(strategy: WasmCfgLoweringStrategy) -> WasmBackendRequest {
+    let mut request = WasmBackendRequest::default();
+    request.emit_options.validate_emitted_module = false;
+    request.emit_options.cfg_lowering_strategy = strategy;
+    request
+}
+
+/// Counts `i` up to the `limit` parameter, then returns the larger of the two through a merge.
+fn counting_loop_blocks() -> Vec<WasmLirBlock> {
+    vec![
+        lir_block(
+            0,
+            vec![
+                WasmLirStmt::ConstI64 {
+                    dst: WasmLirLocalId(1),
+                    value: 0,
+                },
+                WasmLirStmt::ConstI64 {
+                    dst: WasmLirLocalId(3),
+                    value: 1,
+                },
+            ],
+            WasmLirTerminator::Jump(WasmLirBlockId(1)),
+        ),
+        lir_block(
+            1,
+            vec![WasmLirStmt::OrderedLt {
+                dst: WasmLirLocalId(2),
+                lhs: WasmLirLocalId(1),
+                rhs: WasmLirLocalId(0),
+            }],
+            branch(2, 2, 3),
+        ),
+        lir_block(
+            2,
+            vec![WasmLirStmt::IntAdd {
+                dst: WasmLirLocalId(1),
+                lhs: WasmLirLocalId(1),
+                rhs: WasmLirLocalId(3),
+            }],
+            WasmLirTerminator::Jump(WasmLirBlockId(1)),
+        ),
+        lir_block(
+            3,
+            vec![WasmLirStmt::OrderedGt {
+                dst: WasmLirLocalId(2),
+                lhs: WasmLirLocalId(1),
+                rhs: WasmLirLocalId(0),
+            }],
+            branch(2, 4, 5),
+        ),
+        lir_block(
+            4,
+            vec![WasmLirStmt::Copy {
+                dst: WasmLirLocalId(4),
+                src: WasmLirLocalId(1),
+            }],
+            WasmLirTerminator::Jump(WasmLirBlockId(6)),
+        ),
+        lir_block(
+            5,
+            vec![WasmLirStmt::Copy {
+                dst: WasmLirLocalId(4),
+                src: WasmLirLocalId(0),
+            }],
+            WasmLirTerminator::Jump(WasmLirBlockId(6)),
+        ),
+        lir_block(
+            6,
+            vec![],
+            WasmLirTerminator::Return {
+                value: Some(WasmLirLocalId(4)),
+            },
+        ),
+    ]
+}
+
+/// Exported `(i64) -> i64` function over `blocks`, with locals `limit`, `i`, `condition`,
+/// `one` and `result`.
+fn single_function_module(blocks: Vec<WasmLirBlock>) -> WasmLirModule {
+    let function = WasmLirFunction {
+        id: WasmLirFunctionId(0),
+        debug_name: "manual_cfg".to_owned(),
+        origin: WasmLirFunctionOrigin::Normal,
+        signature: WasmLirSignature {
+            params: vec![WasmAbiType::I64],
+            results: vec![WasmAbiType::I64],
+        },
+        locals: vec![
+            WasmLirLocal {
+                id: WasmLirLocalId(0),
+                name: Some("limit".to_owned()),
+                ty: WasmAbiType::I64,
+                role: WasmLocalRole::Param,
+            },
+            local(1, WasmAbiType::I64, "i"),
+            local(2, WasmAbiType::I32, "condition"),
+            local(3, WasmAbiType::I64, "one"),
+            local(4, WasmAbiType::I64, "result"),
+        ],
+        blocks,
+        linkage: WasmFunctionLinkage::ExportedWrapper,
+    };
+
+    WasmLirModule {
+        functions: vec![function],
+        imports: vec![],
+        exports: vec![WasmExport {
+            export_name: "manual_cfg".to_owned(),
+            kind: WasmExportKind::Function(WasmLirFunctionId(0)),
+        }],
+        static_data: vec![],
+        memory_plan: WasmMemoryPlan::default(),
+        record_types: vec![],
+        generic_instances: vec![],
+    }
+}
+
+fn lir_block(id: u32, statements: Vec<WasmLirStmt>, terminator: WasmLirTerminator) -> WasmLirBlock {
+    WasmLirBlock {
+        id: WasmLirBlockId(id),
+        statements,
+        terminator,
+    }
+}
+
+fn branch(condition: u32, then_block: u32, else_block: u32) -> WasmLirTerminator {
+    WasmLirTerminator::Branch {
+        condition: WasmLirLocalId(condition),
+        then_block: WasmLirBlockId(then_block),
+        else_block: WasmLirBlockId(else_block),
+    }
+}
+
 fn build_manual_lir_module() -> WasmLirModule {
     let callee = WasmLirFunction {
         id: WasmLirFunctionId(0),
@@ -614,6 +972,8 @@ fn build_manual_lir_module() -> WasmLirModule {
             kind: WasmStaticDataKind::Utf8StringBytes,
         }],
         memory_plan: WasmMemoryPlan::default(),
+        record_types: vec![],
+        generic_instances: vec![],
     }
 }
 
@@ -0,0 +1,508 @@
+use crate::backends::error_types::BackendErrorType;
+use crate::backends::wasm::backend::lower_hir_to_wasm_module;
+use crate::backends::wasm::emit::module::emit_lir_to_wasm_module;
+use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
+use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
+use crate::backends::wasm::lir::linkage::{
+    WasmExport, WasmExportKind, WasmFunctionLinkage, WasmImport, WasmImportKind,
+};
+use crate::backends::wasm::lir::module::{
+    WasmLirModule, WasmRecordType, WasmStaticData, WasmStaticDataKind,
+};
+use crate::backends::wasm::lir::types::{
+    WasmAbiType, WasmImportId, WasmLirBlockId, WasmLirFunctionId, WasmLirLocal, WasmLirLocalId,
+    WasmLirSignature, WasmLocalRole, WasmRecordTypeId, WasmStaticDataId,
+};
+use crate::backends::wasm::request::{WasmBackendRequest, WasmCfgLoweringStrategy};
+use crate::backends::wasm::runtime::maps::WasmMapKeyKind;
+use crate::backends::wasm::runtime::memory::WasmMemoryPlan;
+use crate::backends::wasm::runtime::records::WasmRecordField;
+use crate::backends::wasm::tests::lowering::test_support::{
+    build_module, build_type_environment, default_borrow_facts, int_expression,
+};
+use crate::compiler_frontend::compiler_messages::compiler_errors::ErrorType;
+use crate::compiler_frontend::compiler_messages::{
+    DiagnosticKind, DiagnosticPayload, RuleDiagnosticKind, UnsupportedBackendFeatureReason,
+};
+use crate::compiler_frontend::hir::blocks::HirBlock;
+use crate::compiler_frontend::hir::functions::{HirFunction, HirFunctionOrigin};
+use crate::compiler_frontend::hir::ids::{BlockId, FunctionId, RegionId};
+use crate::compiler_frontend::hir::terminators::HirTerminator;
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+
+#[test]
+fn emits_records_strings_and_vecs_as_gc_objects() {
+    for strategy in [
+        WasmCfgLoweringStrategy::Structured,
+        WasmCfgLoweringStrategy::DispatcherLoop,
+    ] {
+        let mut request = gc_request();
+        request.emit_options.cfg_lowering_strategy = strategy;
+
+        let emit_result = emit_lir_to_wasm_module(&gc_lir_module(), &request)
+            .expect("Wasm GC emission should succeed");
+        assert!(emit_result.debug_outputs.validation_text.is_some());
+
+        let bytes = &emit_result.wasm_bytes;
+        let summary = collect_module_summary(bytes);
+        assert!(!summary.has_memory, "GC modules must not declare memory");
+        assert!(!summary.has_globals, "GC modules must not declare globals");
+        assert_eq!(summary.array_types, 2);
+        // String buffer, vec and the one record type.
+        assert_eq!(summary.struct_types, 3);
+
+        let operators = collect_operator_names(bytes);
+        for expected in [
+            "StructNewDefault",
+            "StructSet",
+            "StructGet",
+            "RefCastNonNull",
+            "ArrayNewData",
+            "ArrayCopy",
+        ] {
+            assert!(
+                operators.iter().any(|operator| operator == expected),
+                "expected {expected} in GC output"
+            );
+        }
+        for unexpected in ["I32Load", "I32Store", "I64Store", "MemoryCopy", "GlobalGet"] {
+            assert!(
+                !operators.iter().any(|operator| operator == unexpected),
+                "unexpected linear-memory operator {unexpected} in GC output"
+            );
+        }
+    }
+}
+
+#[test]
+fn exports_gc_string_and_vec_accessors() {
+    let mut request = gc_request();
+    let helpers = &mut request.export_policy.helper_exports;
+    helpers.export_str_len = true;
+    helpers.export_str_byte = true;
+    helpers.export_vec_new = true;
+    helpers.export_vec_push = true;
+    helpers.export_vec_len = true;
+    helpers.export_vec_get = true;
+
+    let emit_result =
+        emit_lir_to_wasm_module(&gc_lir_module(), &request).expect("GC emission should succeed");
+    let exports = collect_export_names(&emit_result.wasm_bytes);
+    for expected in [
+        "record_main",
+        "bst_str_len",
+        "bst_str_byte",
+        "bst_vec_new",
+        "bst_vec_push",
+        "bst_vec_len",
+        "bst_vec_get",
+    ] {
+        assert!(exports.contains(&expected.to_owned()), "missing {expected}");
+    }
+    assert!(!exports.contains(&"memory".to_owned()));
+    assert!(
+        emit_result
+            .debug_outputs
+            .data_layout_text
+            .contains("no linear memory")
+    );
+}
+
+#[test]
+fn rejects_statements_without_gc_lowering() {
+    let mut module = gc_lir_module();
+    module.functions[0].blocks[0]
+        .statements
+        .push(WasmLirStmt::MapNew {
+            dst: WasmLirLocalId(3),
+            key_kind: WasmMapKeyKind::Scalar,
+        });
+
+    let error =
+        emit_lir_to_wasm_module(&module, &gc_request()).expect_err("maps have no GC lowering yet");
+    assert_eq!(
+        error.error_type,
+        ErrorType::Backend(BackendErrorType::WasmGeneration)
+    );
+    assert!(
+        error
+            .msg
+            .contains("Wasm GC emission does not yet support hash maps")
+    );
+}
+
+#[test]
+fn rejects_record_access_outside_the_record_type() {
+    let mut module = gc_lir_module();
+    module.functions[0].blocks[0]
+        .statements
+        .push(WasmLirStmt::RecordStore {
+            record: WasmLirLocalId(2),
+            record_type: WasmRecordTypeId(0),
+            offset: 4,
+            value: WasmLirLocalId(0),
+        });
+
+    let error = emit_lir_to_wasm_module(&module, &gc_request())
+        .expect_err("offset 4 is not an I64 slot of the record");
+    assert!(error.msg.contains("no I64 field at offset 4"));
+}
+
+#[test]
+fn lowers_hir_to_validated_gc_module() {
+    let mut string_table = StringTable::new();
+    let (type_environment, types) = build_type_environment();
+    let start_path = InternedPath::from_single_str("main", &mut string_table);
+    let hir_module = build_module(
+        &mut string_table,
+        vec![(
+            HirFunction {
+                id: FunctionId(0),
+                entry: BlockId(0),
+                params: vec![],
+                return_type: types.int,
+                return_aliases: vec![],
+            },
+            start_path,
+            HirFunctionOrigin::EntryStart,
+        )],
+        vec![HirBlock {
+            id: BlockId(0),
+            region: RegionId(0),
+            locals: vec![],
+            statements: vec![],
+            terminator: HirTerminator::Return(int_expression(1, 7, types.int, RegionId(0))),
+        }],
+        FunctionId(0),
+    );
+
+    let result = lower_hir_to_wasm_module(
+        &hir_module,
+        &default_borrow_facts(),
+        &gc_request(),
+        &string_table,
+        &type_environment,
+    )
+    .expect("Wasm GC lowering should emit module bytes");
+    let bytes = result.wasm_bytes.expect("wasm bytes should be available");
+    assert!(!collect_module_summary(&bytes).has_memory);
+}
+
+#[test]
+fn rejects_fallible_hir_with_a_rule_diagnostic_before_gc_lowering() {
+    let mut string_table = StringTable::new();
+    let (type_environment, types) = build_type_environment();
+    let start_path = InternedPath::from_single_str("main", &mut string_table);
+    let hir_module = build_module(
+        &mut string_table,
+        vec![(
+            HirFunction {
+                id: FunctionId(0),
+                entry: BlockId(0),
+                params: vec![],
+                return_type: types.int,
+                return_aliases: vec![],
+            },
+            start_path,
+            HirFunctionOrigin::EntryStart,
+        )],
+        vec![HirBlock {
+            id: BlockId(0),
+            region: RegionId(0),
+            locals: vec![],
+            statements: vec![],
+            terminator: HirTerminator::ReturnError(int_expression(1, 7, types.int, RegionId(0))),
+        }],
+        FunctionId(0),
+    );
+
+    let error = lower_hir_to_wasm_module(
+        &hir_module,
+        &default_borrow_facts(),
+        &gc_request(),
+        &string_table,
+        &type_environment,
+    )
+    .expect_err("fallible results have no GC lowering yet");
+    let diagnostic = &error.diagnostics[0];
+    assert_eq!(
+        diagnostic.kind,
+        DiagnosticKind::Rule(RuleDiagnosticKind::UnsupportedBackendFeature)
+    );
+    assert!(matches!(
+        diagnostic.payload,
+        DiagnosticPayload::UnsupportedBackendFeature {
+            reason: UnsupportedBackendFeatureReason::FallibleResults,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn rejects_linear_memory_helper_exports_with_wasm_gc() {
+    let mut string_table = StringTable::new();
+    let (type_environment, types) = build_type_environment();
+    let start_path = InternedPath::from_single_str("main", &mut string_table);
+    let hir_module = build_module(
+        &mut string_table,
+        vec![(
+            HirFunction {
+                id: FunctionId(0),
+                entry: BlockId(0),
+                params: vec![],
+                return_type: types.int,
+                return_aliases: vec![],
+            },
+            start_path,
+            HirFunctionOrigin::EntryStart,
+        )],
+        vec![HirBlock {
+            id: BlockId(0),
+            region: RegionId(0),
+            locals: vec![],
+            statements: vec![],
+            terminator: HirTerminator::Return(int_expression(1, 7, types.int, RegionId(0))),
+        }],
+        FunctionId(0),
+    );
+
+    let mut request = gc_request();
+    request.export_policy.helper_exports.export_memory = true;
+    let error = lower_hir_to_wasm_module(
+        &hir_module,
+        &default_borrow_facts(),
+        &request,
+        &string_table,
+        &type_environment,
+    )
+    .expect_err("GC modules have no memory to export");
+    let (_, message, _) = error
+        .first_infrastructure_error_for_tests()
+        .expect("Wasm generation failure should be wrapped for rendering");
+    assert!(message.contains("no linear memory"));
+}
+
+fn gc_request() -> WasmBackendRequest {
+    let mut request = WasmBackendRequest::default();
+    request.target_features.use_wasm_gc = true;
+    request.emit_options.validate_emitted_module = true;
+    request
+}
+
+/// One exported function that builds a string from a literal and an integer, stores it with the
+/// integer in a `{ I64 @0, Handle @8 }` record, reads both back, collects the string in a vec and
+/// passes it to a host import.
+fn gc_lir_module() -> WasmLirModule {
+    let record = WasmLirLocalId(2);
+    let main = WasmLirFunction {
+        id: WasmLirFunctionId(0),
+        debug_name: "record_main".to_owned(),
+        origin: WasmLirFunctionOrigin::EntryStart,
+        signature: WasmLirSignature {
+            params: vec![],
+            results: vec![WasmAbiType::I64],
+        },
+        locals: vec![
+            local(0, WasmAbiType::I64, "count"),
+            local(1, WasmAbiType::Handle, "buffer"),
+            local(2, WasmAbiType::Handle, "record"),
+            local(3, WasmAbiType::Handle, "text"),
+            local(4, WasmAbiType::Handle, "number"),
+            local(5, WasmAbiType::I64, "loaded_count"),
+            local(6, WasmAbiType::Handle, "loaded_text"),
+            local(7, WasmAbiType::Handle, "items"),
+        ],
+        blocks: vec![WasmLirBlock {
+            id: WasmLirBlockId(0),
+            statements: vec![
+                WasmLirStmt::ConstI64 {
+                    dst: WasmLirLocalId(0),
+                    value: -42,
+                },
+                WasmLirStmt::StringNewBuffer {
+                    dst: WasmLirLocalId(1),
+                },
+                WasmLirStmt::StringPushLiteral {
+                    buffer: WasmLirLocalId(1),
+                    data: WasmStaticDataId(0),
+                },
+                WasmLirStmt::StringFromI64 {
+                    dst: WasmLirLocalId(4),
+                    value: WasmLirLocalId(0),
+                },
+                WasmLirStmt::StringPushHandle {
+                    buffer: WasmLirLocalId(1),
+                    handle: WasmLirLocalId(4),
+                },
+                WasmLirStmt::StringFinish {
+                    dst: WasmLirLocalId(3),
+                    buffer: WasmLirLocalId(1),
+                },
+                WasmLirStmt::RecordNew {
+                    dst: record,
+                    record_type: WasmRecordTypeId(0),
+                },
+                WasmLirStmt::RecordStore {
+                    record,
+                    record_type: WasmRecordTypeId(0),
+                    offset: 0,
+                    value: WasmLirLocalId(0),
+                },
+                WasmLirStmt::RecordStore {
+                    record,
+                    record_type: WasmRecordTypeId(0),
+                    offset: 8,
+                    value: WasmLirLocalId(3),
+                },
+                WasmLirStmt::RecordLoad {
+                    dst: WasmLirLocalId(5),
+                    record,
+                    record_type: WasmRecordTypeId(0),
+                    offset: 0,
+                },
+                WasmLirStmt::RecordLoad {
+                    dst: WasmLirLocalId(6),
+                    record,
+                    record_type: WasmRecordTypeId(0),
+                    offset: 8,
+                },
+                WasmLirStmt::VecNew {
+                    dst: WasmLirLocalId(7),
+                },
+                WasmLirStmt::VecPushHandle {
+                    vec: WasmLirLocalId(7),
+                    handle: WasmLirLocalId(6),
+                },
+                WasmLirStmt::Call {
+                    dst: None,
+                    callee: WasmCalleeRef::Import(WasmImportId(0)),
+                    args: vec![WasmLirLocalId(6)],
+                },
+                WasmLirStmt::DropIfOwned {
+                    value: WasmLirLocalId(3),
+                },
+            ],
+            terminator: WasmLirTerminator::Return {
+                value: Some(WasmLirLocalId(5)),
+            },
+        }],
+        linkage: WasmFunctionLinkage::ExportedWrapper,
+    };
+
+    WasmLirModule {
+        functions: vec![main],
+        imports: vec![WasmImport {
+            id: WasmImportId(0),
+            module_name: "host".to_owned(),
+            item_name: "io".to_owned(),
+            kind: WasmImportKind::Function(WasmLirSignature {
+                params: vec![WasmAbiType::Handle],
+                results: vec![],
+            }),
+        }],
+        exports: vec![WasmExport {
+            export_name: "record_main".to_owned(),
+            kind: WasmExportKind::Function(WasmLirFunctionId(0)),
+        }],
+        static_data: vec![WasmStaticData {
+            id: WasmStaticDataId(0),
+            debug_name: "gc.literal".to_owned(),
+            bytes: b"count: ".to_vec(),
+            kind: WasmStaticDataKind::Utf8StringBytes,
+        }],
+        memory_plan: WasmMemoryPlan::default(),
+        record_types: vec![WasmRecordType {
+            id: WasmRecordTypeId(0),
+            debug_name: "Counted".to_owned(),
+            size: 16,
+            slots: vec![
+                WasmRecordField {
+                    offset: 0,
+                    abi: WasmAbiType::I64,
+                },
+                WasmRecordField {
+                    offset: 8,
+                    abi: WasmAbiType::Handle,
+                },
+            ],
+        }],
+        generic_instances: vec![],
+    }
+}
+
+struct ModuleSummary {
+    has_memory: bool,
+    has_globals: bool,
+    struct_types: usize,
+    array_types: usize,
+}
+
+fn collect_module_summary(bytes: &[u8]) -> ModuleSummary {
+    let mut summary = ModuleSummary {
+        has_memory: false,
+        has_globals: false,
+        struct_types: 0,
+        array_types: 0,
+    };
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        match payload.expect("payload should parse") {
+            wasmparser::Payload::MemorySection(_) => summary.has_memory = true,
+            wasmparser::Payload::GlobalSection(_) => summary.has_globals = true,
+            wasmparser::Payload::TypeSection(reader) => {
+                for group in reader {
+                    for sub_type in group.expect("rec group should parse").into_types() {
+                        match sub_type.composite_type.inner {
+                            wasmparser::CompositeInnerType::Struct(_) => summary.struct_types += 1,
+                            wasmparser::CompositeInnerType::Array(_) => summary.array_types += 1,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    summary
+}
+
+fn collect_operator_names(bytes: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        if let wasmparser::Payload::CodeSectionEntry(body) = payload.expect("payload should parse")
+        {
+            let mut reader = body.get_operators_reader().expect("operators should parse");
+            while !reader.eof() {
+                let operator = reader.read().expect("operator should parse");
+                let debug = format!("{operator:?}");
+                let name = debug
+                    .split(|character: char| !character.is_alphanumeric())
+                    .next()
+                    .unwrap_or_default();
+                names.push(name.to_owned());
+            }
+        }
+    }
+    names
+}
+
+fn collect_export_names(bytes: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        if let wasmparser::Payload::ExportSection(reader) = payload.expect("payload should parse") {
+            for export in reader {
+                names.push(export.expect("export should parse").name.to_owned());
+            }
+        }
+    }
+    names
+}
+
+fn local(id: u32, ty: WasmAbiType, name: &str) -> WasmLirLocal {
+    WasmLirLocal {
+        id: WasmLirLocalId(id),
+        name: Some(name.to_owned()),
+        ty,
+        role: WasmLocalRole::Temp,
+    }
+}
@@ -1 +1,2 @@
 mod emit_tests;
+mod gc_emit_tests;
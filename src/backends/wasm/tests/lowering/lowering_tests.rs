@@ -2,18 +2,24 @@ use crate::backends::wasm::backend::lower_hir_to_wasm_lir;
 use crate::backends::wasm::hir_to_lir::context::lower_type_to_abi;
 use crate::backends::wasm::lir::function::WasmLirFunctionOrigin;
 use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
-use crate::backends::wasm::lir::linkage::{WasmExportKind, WasmFunctionLinkage};
+use crate::backends::wasm::lir::linkage::{WasmExportKind, WasmFunctionLinkage, WasmImportKind};
+use crate::backends::wasm::lir::module::WasmGenericKind;
 use crate::backends::wasm::lir::types::{WasmAbiType, WasmLirFunctionId, WasmLirLocalId};
 use crate::backends::wasm::request::{
     WasmBackendRequest, WasmDebugFlags, WasmExportPolicy, WasmFunctionEmissionPolicy,
 };
 use crate::backends::wasm::tests::lowering::test_support::{
-    bool_expression, borrow_facts_with_drop_site, build_module, build_type_environment,
-    default_borrow_facts, expression, int_expression, load_local, local, statement,
-    string_expression, unit_expression,
+    bool_expression, borrow_facts_with_drop_site, build_module, build_multi_return_module,
+    build_type_environment, default_borrow_facts, expression, int_expression, load_local, local,
+    statement, string_expression, unit_expression,
 };
 use crate::compiler_frontend::analysis::borrow_checker::BorrowDropSiteKind;
-use crate::compiler_frontend::external_packages::CallTarget;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
+use crate::compiler_frontend::external_packages::{
+    CallTarget, ExternalAbiType, ExternalAccessKind, ExternalFunctionDef, ExternalFunctionId,
+    ExternalFunctionLowerings, ExternalPackageRegistry, ExternalParameter, ExternalReturnSlot,
+    ExternalSignatureType, ExternalTypeId, ExternalWasmLowering,
+};
 use crate::compiler_frontend::hir::blocks::HirBlock;
 use crate::compiler_frontend::hir::expressions::{HirExpressionKind, ValueKind};
 use crate::compiler_frontend::hir::functions::{HirFunction, HirFunctionOrigin};
@@ -532,6 +538,74 @@ fn lowers_non_runtime_string_add_with_i64_chunk_via_string_from_i64() {
     );
 }
 
+#[test]
+fn lowers_non_runtime_string_add_with_bool_chunk_via_bool_cast() {
+    let mut string_table = StringTable::new();
+    let (type_environment, types) = build_type_environment();
+    let function_path = InternedPath::from_single_str("render_runtime_bool", &mut string_table);
+
+    let concat = expression(
+        1320,
+        HirExpressionKind::BinOp {
+            left: Box::new(string_expression(1321, "", types.string, RegionId(0))),
+            op: HirBinOp::Add,
+            right: Box::new(load_local(1322, LocalId(0), types.boolean, RegionId(0))),
+        },
+        types.string,
+        RegionId(0),
+        ValueKind::RValue,
+    );
+    let block = HirBlock {
+        id: BlockId(0),
+        region: RegionId(0),
+        locals: vec![local(0, types.boolean, RegionId(0))],
+        statements: vec![],
+        terminator: HirTerminator::Return(concat),
+    };
+    let function = HirFunction {
+        id: FunctionId(0),
+        entry: BlockId(0),
+        params: vec![LocalId(0)],
+        return_type: types.string,
+        return_aliases: vec![],
+    };
+    let module = build_module(
+        &mut string_table,
+        vec![(function, function_path, HirFunctionOrigin::Normal)],
+        vec![block],
+        FunctionId(0),
+    );
+
+    let result = lower_hir_to_wasm_lir(
+        &module,
+        &default_borrow_facts(),
+        &WasmBackendRequest::default(),
+        &string_table,
+        &type_environment,
+    )
+    .expect("non-runtime string Add should bridge Bool chunks");
+    let lowered = result
+        .lir_module
+        .functions
+        .iter()
+        .find(|function| function.id == WasmLirFunctionId(0))
+        .expect("lowered function should be present");
+
+    assert!(
+        lowered.blocks[0]
+            .statements
+            .iter()
+            .any(|statement| matches!(
+                statement,
+                WasmLirStmt::Cast {
+                    policy: BuiltinCastPolicyId::BoolToString,
+                    ..
+                }
+            )),
+        "lowered statements should include the Bool-to-String cast"
+    );
+}
+
 #[test]
 fn lowers_ordered_comparison_and_numeric_add_for_control_flow() {
     let mut string_table = StringTable::new();
@@ -810,6 +884,114 @@ fn maps_advisory_drop_sites_to_drop_if_owned_statements() {
     ));
 }
 
+#[test]
+fn keeps_drop_if_owned_only_for_provably_unique_handles() {
+    let mut string_table = StringTable::new();
+    let (type_environment, types) = build_type_environment();
+    let start_path = InternedPath::from_single_str("main", &mut string_table);
+    let region = RegionId(0);
+
+    // Local 0 owns a fresh string and dies at the return; local 1 is returned, so it escapes.
+    let start_block = HirBlock {
+        id: BlockId(0),
+        region,
+        locals: vec![
+            local(0, types.string, region),
+            local(1, types.string, region),
+        ],
+        statements: vec![
+            statement(
+                410,
+                HirStatementKind::Assign {
+                    target: HirPlace::Local(LocalId(0)),
+                    value: string_expression(411, "scratch", types.string, region),
+                },
+                1,
+            ),
+            statement(
+                412,
+                HirStatementKind::Assign {
+                    target: HirPlace::Local(LocalId(1)),
+                    value: string_expression(413, "result", types.string, region),
+                },
+                2,
+            ),
+        ],
+        terminator: HirTerminator::Return(load_local(414, LocalId(1), types.string, region)),
+    };
+    let start_function = HirFunction {
+        id: FunctionId(0),
+        entry: BlockId(0),
+        params: vec![],
+        return_type: types.string,
+        return_aliases: vec![],
+    };
+    let module = build_module(
+        &mut string_table,
+        vec![(start_function, start_path, HirFunctionOrigin::EntryStart)],
+        vec![start_block],
+        FunctionId(0),
+    );
+
+    let borrow_facts = borrow_facts_with_drop_site(
+        BlockId(0),
+        BorrowDropSiteKind::Return,
+        vec![LocalId(0), LocalId(1)],
+    );
+
+    let result = lower_hir_to_wasm_lir(
+        &module,
+        &borrow_facts,
+        &WasmBackendRequest::default(),
+        &string_table,
+        &type_environment,
+    )
+    .expect("Wasm lowering should succeed");
+    let lowered_start = result
+        .lir_module
+        .functions
+        .iter()
+        .find(|function| function.id == WasmLirFunctionId(0))
+        .expect("lowered start function should be present");
+    let statements = &lowered_start.blocks[0].statements;
+
+    let dropped: Vec<WasmLirLocalId> = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            WasmLirStmt::DropIfOwned { value } => Some(*value),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        dropped.len(),
+        1,
+        "only the scratch string should be dropped"
+    );
+
+    // The kept drop must release the local that received the first finished string.
+    let scratch = statements
+        .iter()
+        .find_map(|statement| match statement {
+            WasmLirStmt::StringFinish { dst, .. } => Some(*dst),
+            _ => None,
+        })
+        .expect("the scratch literal should build a runtime string");
+    let scratch_owner = statements
+        .iter()
+        .find_map(|statement| match statement {
+            WasmLirStmt::Copy { dst, src } | WasmLirStmt::Move { dst, src } if *src == scratch => {
+                Some(*dst)
+            }
+            _ => None,
+        })
+        .unwrap_or(scratch);
+    assert_eq!(dropped[0], scratch_owner);
+    assert!(matches!(
+        lowered_start.blocks[0].terminator,
+        WasmLirTerminator::Return { value: Some(value) } if value != scratch_owner
+    ));
+}
+
 #[test]
 fn synthesizes_export_wrappers_with_stable_names() {
     let mut string_table = StringTable::new();
@@ -845,6 +1027,7 @@ fn synthesizes_export_wrappers_with_stable_names() {
             exported_functions: vec![FunctionId(0)],
             export_names,
             helper_exports: Default::default(),
+            command_start: None,
         },
         target_features: Default::default(),
         emit_options: Default::default(),
@@ -853,6 +1036,7 @@ fn synthesizes_export_wrappers_with_stable_names() {
             ..Default::default()
         },
         external_package_registry: Default::default(),
+        host_interface: Default::default(),
         function_emission_policy: Default::default(),
     };
 
@@ -929,6 +1113,7 @@ fn rejects_invalid_export_request_with_structured_diagnostic() {
             exported_functions: vec![FunctionId(0)],
             export_names: FxHashMap::default(),
             helper_exports: Default::default(),
+            command_start: None,
         },
         ..Default::default()
     };
@@ -1063,6 +1248,7 @@ fn export_reachable_policy_ignores_unreachable_host_calls() {
             exported_functions: vec![FunctionId(0)],
             export_names,
             helper_exports: Default::default(),
+            command_start: None,
         },
         function_emission_policy: WasmFunctionEmissionPolicy::ReachableFromExports,
         ..Default::default()
@@ -1376,3 +1562,325 @@ fn io_console_functions_are_unsupported_in_wasm() {
         "error must not suggest that a host import lowering exists, got: {error_message}"
     );
 }
+
+#[test]
+fn reports_generic_function_instances_per_template() {
+    let mut string_table = StringTable::new();
+    let (type_environment, types) = build_type_environment();
+
+    let main_path = InternedPath::from_single_str("main", &mut string_table);
+    let identity_path = InternedPath::from_single_str("identity", &mut string_table);
+    let int_instance_path = identity_path.join_str("__generic_instance_1", &mut string_table);
+    let string_instance_path = identity_path.join_str("__generic_instance_2", &mut string_table);
+
+    let int_identity_block = HirBlock {
+        id: BlockId(1),
+        region: RegionId(0),
+        locals: vec![local(1, types.int, RegionId(0))],
+        statements: vec![],
+        terminator: HirTerminator::Return(load_local(100, LocalId(1), types.int, RegionId(0))),
+    };
+    let string_identity_block = HirBlock {
+        id: BlockId(2),
+        region: RegionId(0),
+        locals: vec![local(2, types.string, RegionId(0))],
+        statements: vec![],
+        terminator: HirTerminator::Return(load_local(101, LocalId(2), types.string, RegionId(0))),
+    };
+    let main_block = HirBlock {
+        id: BlockId(0),
+        region: RegionId(0),
+        locals: vec![],
+        statements: vec![],
+        terminator: HirTerminator::Return(int_expression(102, 0, types.int, RegionId(0))),
+    };
+
+    let main_function = HirFunction {
+        id: FunctionId(0),
+        entry: BlockId(0),
+        params: vec![],
+        return_type: types.int,
+        return_aliases: vec![],
+    };
+    let int_identity = HirFunction {
+        id: FunctionId(1),
+        entry: BlockId(1),
+        params: vec![LocalId(1)],
+        return_type: types.int,
+        return_aliases: vec![],
+    };
+    let string_identity = HirFunction {
+        id: FunctionId(2),
+        entry: BlockId(2),
+        params: vec![LocalId(2)],
+        return_type: types.string,
+        return_aliases: vec![],
+    };
+
+    let module = build_module(
+        &mut string_table,
+        vec![
+            (main_function, main_path, HirFunctionOrigin::EntryStart),
+            (int_identity, int_instance_path, HirFunctionOrigin::Normal),
+            (
+                string_identity,
+                string_instance_path,
+                HirFunctionOrigin::Normal,
+            ),
+        ],
+        vec![main_block, int_identity_block, string_identity_block],
+        FunctionId(0),
+    );
+
+    let request = WasmBackendRequest {
+        debug_flags: WasmDebugFlags {
+            show_wasm_generic_instances: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let result = lower_hir_to_wasm_lir(
+        &module,
+        &default_borrow_facts(),
+        &request,
+        &string_table,
+        &type_environment,
+    )
+    .expect("Wasm lowering should succeed");
+
+    let summaries = &result.lir_module.generic_instances;
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].kind, WasmGenericKind::Function);
+    assert_eq!(summaries[0].generic_name, "identity");
+    let names = summaries[0]
+        .instances
+        .iter()
+        .map(|instance| instance.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec!["identity(Int) -> Int", "identity(String) -> String"]
+    );
+
+    let report = result
+        .debug_outputs
+        .generic_instances_text
+        .expect("generic instance report should be requested");
+    assert!(report.contains("function identity: 2 instance(s)"));
+}
+
+#[test]
+fn lowers_multi_return_calls_and_returns_to_multi_value() {
+    let mut string_table = StringTable::new();
+    let (mut type_environment, types) = build_type_environment();
+    let module = build_multi_return_module(&mut string_table, &mut type_environment, types);
+
+    let mut request = WasmBackendRequest::default();
+    request.target_features.enable_multi_value = true;
+    let result = lower_hir_to_wasm_lir(
+        &module,
+        &default_borrow_facts(),
+        &request,
+        &string_table,
+        &type_environment,
+    )
+    .expect("Wasm lowering should succeed");
+
+    let pair = &result.lir_module.functions[0];
+    assert_eq!(
+        pair.signature.results,
+        vec![WasmAbiType::I64, WasmAbiType::I32]
+    );
+    let WasmLirTerminator::ReturnMultiValue { values } = &pair.blocks[0].terminator else {
+        panic!("multi-return function should end in ReturnMultiValue");
+    };
+    assert_eq!(values.len(), 2);
+
+    // The tuple local splits into one local per slot; multi-bind copies out of those slots.
+    let main = &result.lir_module.functions[1];
+    let [call, bind_int, bind_bool] = main.blocks[0].statements.as_slice() else {
+        panic!("main should lower to a call and two slot copies");
+    };
+    let WasmLirStmt::CallMultiValue {
+        dsts,
+        callee: WasmCalleeRef::Function(WasmLirFunctionId(0)),
+        args,
+    } = call
+    else {
+        panic!("multi-return call should lower to CallMultiValue, got {call:?}");
+    };
+    assert!(args.is_empty());
+    assert_eq!(dsts.len(), 2);
+    assert!(matches!(bind_int, WasmLirStmt::Copy { src, .. } if *src == dsts[0]));
+    assert!(matches!(bind_bool, WasmLirStmt::Copy { src, .. } if *src == dsts[1]));
+}
+
+#[test]
+fn synthesizes_multi_value_export_wrappers() {
+    let mut string_table = StringTable::new();
+    let (mut type_environment, types) = build_type_environment();
+    let module = build_multi_return_module(&mut string_table, &mut type_environment, types);
+
+    let mut request = WasmBackendRequest::default();
+    request.target_features.enable_multi_value = true;
+    request.export_policy.exported_functions = vec![FunctionId(0)];
+    request
+        .export_policy
+        .export_names
+        .insert(FunctionId(0), "pair".to_owned());
+
+    let result = lower_hir_to_wasm_lir(
+        &module,
+        &default_borrow_facts(),
+        &request,
+        &string_table,
+        &type_environment,
+    )
+    .expect("Wasm lowering should succeed");
+
+    let WasmExportKind::Function(wrapper_id) = result.lir_module.exports[0].kind;
+    let wrapper = result
+        .lir_module
+        .functions
+        .iter()
+        .find(|function| function.id == wrapper_id)
+        .expect("wrapper function should be present");
+    assert_eq!(wrapper.signature.results.len(), 2);
+    let WasmLirStmt::CallMultiValue { dsts, .. } = &wrapper.blocks[0].statements[0] else {
+        panic!("wrapper should forward through CallMultiValue");
+    };
+    assert!(matches!(
+        &wrapper.blocks[0].terminator,
+        WasmLirTerminator::ReturnMultiValue { values } if values == dsts
+    ));
+}
+
+#[test]
+fn lowers_opaque_host_handles_to_externref_when_reference_types_are_enabled() {
+    let mut string_table = StringTable::new();
+    let (mut type_environment, types) = build_type_environment();
+    let main_path = InternedPath::from_single_str("main", &mut string_table);
+
+    let context_type_id = ExternalTypeId(0);
+    let context_type = type_environment.intern_external(context_type_id);
+    let get_context = ExternalFunctionId::Synthetic(70);
+    let context_width = ExternalFunctionId::Synthetic(71);
+
+    let mut registry = ExternalPackageRegistry::new();
+    let package_id = registry
+        .register_package(
+            "test/canvas",
+            crate::builder_surface::PackageOrigin::ProjectLocal,
+        )
+        .expect("package should register");
+    for (id, name, parameters, returns) in [
+        (
+            get_context,
+            "get_context",
+            vec![],
+            ExternalSignatureType::External(context_type_id),
+        ),
+        (
+            context_width,
+            "context_width",
+            vec![ExternalParameter {
+                language_type: ExternalSignatureType::External(context_type_id),
+                access_kind: ExternalAccessKind::Shared,
+            }],
+            ExternalSignatureType::Abi(ExternalAbiType::I32),
+        ),
+    ] {
+        registry
+            .register_function_in_package(
+                package_id,
+                id,
+                ExternalFunctionDef {
+                    name: name.to_owned(),
+                    parameters,
+                    returns: vec![ExternalReturnSlot::fresh(returns)],
+                    error_return_type: None,
+                    lowerings: ExternalFunctionLowerings {
+                        js: None,
+                        wasm: Some(ExternalWasmLowering::HostFunction(name)),
+                    },
+                },
+            )
+            .expect("function should register");
+    }
+
+    let block = HirBlock {
+        id: BlockId(0),
+        region: RegionId(0),
+        locals: vec![
+            local(0, context_type, RegionId(0)),
+            local(1, types.int, RegionId(0)),
+        ],
+        statements: vec![
+            statement(
+                1,
+                HirStatementKind::Call {
+                    target: CallTarget::ExternalFunction(get_context),
+                    args: vec![],
+                    result: Some(LocalId(0)),
+                },
+                1,
+            ),
+            statement(
+                2,
+                HirStatementKind::Call {
+                    target: CallTarget::ExternalFunction(context_width),
+                    args: vec![load_local(2, LocalId(0), context_type, RegionId(0))],
+                    result: Some(LocalId(1)),
+                },
+                2,
+            ),
+        ],
+        terminator: HirTerminator::Return(load_local(3, LocalId(1), types.int, RegionId(0))),
+    };
+    let main = HirFunction {
+        id: FunctionId(0),
+        entry: BlockId(0),
+        params: vec![],
+        return_type: types.int,
+        return_aliases: vec![],
+    };
+    let module = build_module(
+        &mut string_table,
+        vec![(main, main_path, HirFunctionOrigin::EntryStart)],
+        vec![block],
+        FunctionId(0),
+    );
+
+    let registry = std::sync::Arc::new(registry);
+    for (enable_reference_types, opaque_abi) in
+        [(true, WasmAbiType::ExternRef), (false, WasmAbiType::Handle)]
+    {
+        let mut request = WasmBackendRequest {
+            external_package_registry: registry.clone(),
+            ..Default::default()
+        };
+        request.target_features.enable_reference_types = enable_reference_types;
+        let result = lower_hir_to_wasm_lir(
+            &module,
+            &default_borrow_facts(),
+            &request,
+            &string_table,
+            &type_environment,
+        )
+        .expect("Wasm lowering should succeed");
+
+        let imports = &result.lir_module.imports;
+        assert_eq!(imports.len(), 2);
+        assert!(imports.iter().all(|import| import.module_name == "host"));
+        let WasmImportKind::Function(get_signature) = &imports[0].kind;
+        let WasmImportKind::Function(width_signature) = &imports[1].kind;
+        assert_eq!(imports[0].item_name, "get_context");
+        assert_eq!(get_signature.results, vec![opaque_abi]);
+        assert_eq!(width_signature.params, vec![opaque_abi]);
+        assert_eq!(width_signature.results, vec![WasmAbiType::I64]);
+
+        let context_local = &result.lir_module.functions[0].locals[0];
+        assert_eq!(context_local.ty, opaque_abi);
+    }
+}
@@ -3,6 +3,7 @@ use crate::compiler_frontend::analysis::borrow_checker::{
 };
 use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
 use crate::compiler_frontend::datatypes::ids::TypeId;
+use crate::compiler_frontend::external_packages::CallTarget;
 use crate::compiler_frontend::hir::blocks::{HirBlock, HirLocal};
 use crate::compiler_frontend::hir::expressions::{HirExpression, HirExpressionKind, ValueKind};
 use crate::compiler_frontend::hir::functions::{HirFunction, HirFunctionOrigin};
@@ -13,6 +14,7 @@ use crate::compiler_frontend::hir::module::HirModule;
 use crate::compiler_frontend::hir::places::HirPlace;
 use crate::compiler_frontend::hir::regions::HirRegion;
 use crate::compiler_frontend::hir::statements::{HirStatement, HirStatementKind};
+use crate::compiler_frontend::hir::terminators::HirTerminator;
 use crate::compiler_frontend::symbols::interned_path::InternedPath;
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::tests::ast_fixture_support::test_source_location;
@@ -201,3 +203,110 @@ pub(crate) fn load_local(
         ValueKind::Place,
     )
 }
+
+/// Module where `main` multi-binds the `(Int, Bool)` result of `pair` and returns the Int slot.
+///
+/// Functions: `pair` = `FunctionId(0)`, `main` = `FunctionId(1)`. `main` locals: `0` is the tuple, `1` and `2` are the bound slots.
+pub(crate) fn build_multi_return_module(
+    string_table: &mut StringTable,
+    type_environment: &mut TypeEnvironment,
+    types: TypeIds,
+) -> HirModule {
+    let pair_type = type_environment.intern_tuple(vec![types.int, types.boolean]);
+    let pair_path = InternedPath::from_single_str("pair", string_table);
+    let main_path = InternedPath::from_single_str("main", string_table);
+
+    let pair_block = HirBlock {
+        id: BlockId(10),
+        region: RegionId(0),
+        locals: vec![],
+        statements: vec![],
+        terminator: HirTerminator::Return(expression(
+            100,
+            HirExpressionKind::TupleConstruct {
+                elements: vec![
+                    int_expression(101, 7, types.int, RegionId(0)),
+                    bool_expression(102, true, types.boolean, RegionId(0)),
+                ],
+            },
+            pair_type,
+            RegionId(0),
+            ValueKind::RValue,
+        )),
+    };
+
+    let tuple_get = |id: u32, index: usize, ty: TypeId| {
+        expression(
+            id,
+            HirExpressionKind::TupleGet {
+                tuple: Box::new(load_local(id + 1, LocalId(0), pair_type, RegionId(0))),
+                index,
+            },
+            ty,
+            RegionId(0),
+            ValueKind::RValue,
+        )
+    };
+    let main_block = HirBlock {
+        id: BlockId(20),
+        region: RegionId(0),
+        locals: vec![
+            local(0, pair_type, RegionId(0)),
+            local(1, types.int, RegionId(0)),
+            local(2, types.boolean, RegionId(0)),
+        ],
+        statements: vec![
+            statement(
+                1,
+                HirStatementKind::Call {
+                    target: CallTarget::UserFunction(FunctionId(0)),
+                    args: vec![],
+                    result: Some(LocalId(0)),
+                },
+                1,
+            ),
+            statement(
+                2,
+                HirStatementKind::Assign {
+                    target: HirPlace::Local(LocalId(1)),
+                    value: tuple_get(110, 0, types.int),
+                },
+                2,
+            ),
+            statement(
+                3,
+                HirStatementKind::Assign {
+                    target: HirPlace::Local(LocalId(2)),
+                    value: tuple_get(120, 1, types.boolean),
+                },
+                3,
+            ),
+        ],
+        terminator: HirTerminator::Return(load_local(130, LocalId(1), types.int, RegionId(0))),
+    };
+
+    let pair = HirFunction {
+        id: FunctionId(0),
+        entry: BlockId(10),
+        params: vec![],
+        return_type: pair_type,
+        return_aliases: vec![],
+    };
+    let main = HirFunction {
+        id: FunctionId(1),
+        entry: BlockId(20),
+        params: vec![],
+        return_type: types.int,
+        return_aliases: vec![],
+    };
+
+    build_module(
+        string_table,
+        vec![
+            (pair, pair_path, HirFunctionOrigin::Normal),
+            (main, main_path, HirFunctionOrigin::EntryStart),
+        ],
+        vec![pair_block, main_block],
+        FunctionId(1),
+    )
+}
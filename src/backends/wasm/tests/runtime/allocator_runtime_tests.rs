@@ -0,0 +1,376 @@
+//! Linear-memory allocator runtime tests.
+//!
+//! WHAT: executes the emitted allocator, vec helpers and drop sites under Node and watches the
+//! `bst_live_allocations` counter and the memory size.
+//! WHY: free-list reuse, `memory.grow` and ownership-chain releases only show up when the
+//!      synthesized helpers actually run.
+
+use crate::backends::wasm::emit::module::emit_lir_to_wasm_module;
+use crate::backends::wasm::hir_to_lir::ownership::retain_provably_unique_drops;
+use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
+use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
+use crate::backends::wasm::lir::linkage::{
+    WasmExport, WasmExportKind, WasmFunctionLinkage, WasmImport, WasmImportKind,
+};
+use crate::backends::wasm::lir::module::{WasmLirModule, WasmStaticData, WasmStaticDataKind};
+use crate::backends::wasm::lir::types::{
+    WasmAbiType, WasmImportId, WasmLirBlockId, WasmLirFunctionId, WasmLirLocal, WasmLirLocalId,
+    WasmLirSignature, WasmLocalRole, WasmStaticDataId,
+};
+use crate::backends::wasm::request::{
+    WasmBackendRequest, WasmExportPolicy, WasmHelperExportPolicy,
+};
+use crate::backends::wasm::runtime::maps::WasmMapKeyKind;
+use crate::backends::wasm::runtime::memory::WasmMemoryPlan;
+
+#[test]
+fn allocator_reuses_released_blocks_and_traps_on_double_release() {
+    let output = run_allocator_module(
+        vec![],
+        vec![],
+        "const baseline = live();\n\
+         const first = wasm.bst_vec_new();\n\
+         console.log(live() - baseline);\n\
+         wasm.bst_release(first);\n\
+         console.log(live() - baseline);\n\
+         const second = wasm.bst_vec_new();\n\
+         console.log(second === first);\n\
+         wasm.bst_release(second);\n\
+         try {\n\
+           wasm.bst_release(second);\n\
+           console.log('released twice');\n\
+         } catch (error) {\n\
+           console.log(error instanceof WebAssembly.RuntimeError);\n\
+         }",
+    );
+
+    assert_eq!(output, vec!["1", "0", "true", "true"]);
+}
+
+#[test]
+fn allocator_grows_memory_and_recycles_grown_storage() {
+    // 20k four-byte elements outgrow the initial page, so vec growth must call `memory.grow`.
+    let output = run_allocator_module(
+        vec![],
+        vec![],
+        "const baseline = live();\n\
+         const fill = () => {\n\
+           const vec = wasm.bst_vec_new();\n\
+           for (let index = 0; index < 20000; index++) wasm.bst_vec_push(vec, index);\n\
+           return vec;\n\
+         };\n\
+         const initialBytes = wasm.memory.buffer.byteLength;\n\
+         const first = fill();\n\
+         const grownBytes = wasm.memory.buffer.byteLength;\n\
+         console.log(grownBytes > initialBytes);\n\
+         console.log(wasm.bst_vec_get(first, 19999));\n\
+         wasm.bst_release(first);\n\
+         console.log(live() - baseline);\n\
+         wasm.bst_release(fill());\n\
+         console.log(live() - baseline);\n\
+         console.log(wasm.memory.buffer.byteLength === grownBytes);",
+    );
+
+    assert_eq!(output, vec!["true", "19999", "0", "0", "true"]);
+}
+
+#[test]
+fn drop_sites_return_live_allocations_to_baseline() {
+    let chunk = WasmStaticData {
+        id: WasmStaticDataId(0),
+        debug_name: "chunk".to_owned(),
+        bytes: b"abcdefgh".to_vec(),
+        kind: WasmStaticDataKind::Utf8StringBytes,
+    };
+
+    // A string, vec and map that each grow past their first block, then die at a drop site.
+    let mut churn = AllocatorTestFunction::new("churn");
+    let buffer = churn.local(WasmAbiType::Handle);
+    churn.push(WasmLirStmt::StringNewBuffer { dst: buffer });
+    for _ in 0..40 {
+        churn.push(WasmLirStmt::StringPushLiteral {
+            buffer,
+            data: WasmStaticDataId(0),
+        });
+    }
+    let string = churn.local(WasmAbiType::Handle);
+    churn.push(WasmLirStmt::StringFinish {
+        dst: string,
+        buffer,
+    });
+
+    let vec = churn.local(WasmAbiType::Handle);
+    churn.push(WasmLirStmt::VecNew { dst: vec });
+    let element = churn.local(WasmAbiType::Handle);
+    churn.push(WasmLirStmt::ConstStaticPtr {
+        dst: element,
+        data: WasmStaticDataId(0),
+    });
+    for _ in 0..20 {
+        churn.push(WasmLirStmt::VecPushHandle {
+            vec,
+            handle: element,
+        });
+    }
+
+    let map = churn.local(WasmAbiType::Handle);
+    churn.push(WasmLirStmt::MapNew {
+        dst: map,
+        key_kind: WasmMapKeyKind::Scalar,
+    });
+    for key in 0..20 {
+        let key_local = churn.local(WasmAbiType::I64);
+        churn.push(WasmLirStmt::ConstI64 {
+            dst: key_local,
+            value: key,
+        });
+        churn.push(WasmLirStmt::MapSet {
+            dst: None,
+            map,
+            key: key_local,
+            value: key_local,
+        });
+    }
+
+    for value in [string, vec, map] {
+        churn.push(WasmLirStmt::DropIfOwned { value });
+    }
+
+    let output = run_allocator_module(
+        vec![churn.finish()],
+        vec![chunk],
+        "const baseline = live();\n\
+         wasm.churn();\n\
+         console.log(live() - baseline);\n\
+         const settledBytes = wasm.memory.buffer.byteLength;\n\
+         for (let round = 0; round < 200; round++) wasm.churn();\n\
+         console.log(live() - baseline);\n\
+         console.log(wasm.memory.buffer.byteLength === settledBytes);",
+    );
+
+    assert_eq!(output, vec!["0", "0", "true"]);
+}
+
+#[test]
+fn strings_passed_to_host_imports_are_released_on_every_render() {
+    let chunk = WasmStaticData {
+        id: WasmStaticDataId(0),
+        debug_name: "chunk".to_owned(),
+        bytes: b"count: ".to_vec(),
+        kind: WasmStaticDataKind::Utf8StringBytes,
+    };
+
+    // Mirrors a rerender: build fresh text, hand it to a DOM host import, drop it at the exit.
+    let mut render = AllocatorTestFunction::new("render");
+    let buffer = render.local(WasmAbiType::Handle);
+    render.push(WasmLirStmt::StringNewBuffer { dst: buffer });
+    render.push(WasmLirStmt::StringPushLiteral {
+        buffer,
+        data: WasmStaticDataId(0),
+    });
+    let text = render.local(WasmAbiType::Handle);
+    render.push(WasmLirStmt::StringFinish { dst: text, buffer });
+    render.push(WasmLirStmt::Call {
+        dst: None,
+        callee: WasmCalleeRef::Import(WasmImportId(0)),
+        args: vec![text],
+    });
+    render.push(WasmLirStmt::DropIfOwned { value: text });
+
+    let mut render = render.finish();
+
+    // An internal callee may keep the handle, so the same drop is unsafe there.
+    let mut internal = render.clone();
+    for statement in &mut internal.blocks[0].statements {
+        if let WasmLirStmt::Call { callee, .. } = statement {
+            *callee = WasmCalleeRef::Function(WasmLirFunctionId(1));
+        }
+    }
+    retain_provably_unique_drops(&mut internal);
+    assert!(
+        !internal.blocks[0]
+            .statements
+            .iter()
+            .any(|statement| matches!(statement, WasmLirStmt::DropIfOwned { .. }))
+    );
+
+    retain_provably_unique_drops(&mut render);
+    assert!(
+        render.blocks[0].statements.iter().any(
+            |statement| matches!(statement, WasmLirStmt::DropIfOwned { value } if *value == text)
+        ),
+        "a host import only borrows its argument, so the drop must survive the ownership pass"
+    );
+
+    let set_text = WasmImport {
+        id: WasmImportId(0),
+        module_name: "host".to_owned(),
+        item_name: "dom_set_text".to_owned(),
+        kind: WasmImportKind::Function(WasmLirSignature {
+            params: vec![WasmAbiType::Handle],
+            results: vec![],
+        }),
+    };
+    let output = run_allocator_module_with_host(
+        vec![render],
+        vec![chunk],
+        vec![set_text],
+        "{ host: { dom_set_text: (handle) => { rendered.push(text(handle)); } } }",
+        "const baseline = live();\n\
+         for (let round = 0; round < 50; round++) wasm.render();\n\
+         console.log(live() - baseline);\n\
+         console.log(rendered.length);\n\
+         console.log(rendered[49]);",
+    );
+
+    assert_eq!(output, vec!["0", "50", "count: "]);
+}
+
+/// Straight-line LIR function builder with no params or results.
+struct AllocatorTestFunction {
+    name: String,
+    locals: Vec<WasmLirLocal>,
+    statements: Vec<WasmLirStmt>,
+}
+
+impl AllocatorTestFunction {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            locals: vec![],
+            statements: vec![],
+        }
+    }
+
+    fn local(&mut self, ty: WasmAbiType) -> WasmLirLocalId {
+        let id = WasmLirLocalId(self.locals.len() as u32);
+        self.locals.push(WasmLirLocal {
+            id,
+            name: None,
+            ty,
+            role: WasmLocalRole::Temp,
+        });
+        id
+    }
+
+    fn push(&mut self, statement: WasmLirStmt) {
+        self.statements.push(statement);
+    }
+
+    fn finish(self) -> WasmLirFunction {
+        WasmLirFunction {
+            id: WasmLirFunctionId(0),
+            debug_name: self.name,
+            origin: WasmLirFunctionOrigin::Normal,
+            signature: WasmLirSignature {
+                params: vec![],
+                results: vec![],
+            },
+            locals: self.locals,
+            blocks: vec![WasmLirBlock {
+                id: WasmLirBlockId(0),
+                statements: self.statements,
+                terminator: WasmLirTerminator::Return { value: None },
+            }],
+            linkage: WasmFunctionLinkage::ExportedWrapper,
+        }
+    }
+}
+
+fn run_allocator_module(
+    functions: Vec<WasmLirFunction>,
+    static_data: Vec<WasmStaticData>,
+    script: &str,
+) -> Vec<String> {
+    run_allocator_module_with_host(functions, static_data, vec![], "{}", script)
+}
+
+/// Like `run_allocator_module`, with `imports` satisfied by the `host_imports` JS object literal.
+fn run_allocator_module_with_host(
+    functions: Vec<WasmLirFunction>,
+    static_data: Vec<WasmStaticData>,
+    imports: Vec<WasmImport>,
+    host_imports: &str,
+    script: &str,
+) -> Vec<String> {
+    let exports = functions
+        .iter()
+        .map(|function| WasmExport {
+            export_name: function.debug_name.clone(),
+            kind: WasmExportKind::Function(function.id),
+        })
+        .collect();
+    let module = WasmLirModule {
+        functions,
+        imports,
+        exports,
+        static_data,
+        memory_plan: WasmMemoryPlan::default(),
+        record_types: vec![],
+        generic_instances: vec![],
+    };
+
+    let request = WasmBackendRequest {
+        export_policy: WasmExportPolicy {
+            helper_exports: WasmHelperExportPolicy {
+                export_memory: true,
+                export_vec_new: true,
+                export_vec_push: true,
+                export_vec_get: true,
+                export_release: true,
+                export_live_allocations: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let emitted =
+        emit_lir_to_wasm_module(&module, &request).expect("allocator runtime module should emit");
+
+    run_in_node(&emitted.wasm_bytes, host_imports, script)
+}
+
+/// Instantiates the module under Node and returns the script's stdout lines.
+///
+/// Host import callbacks can read string handles with `text(handle)` and record into `rendered`.
+fn run_in_node(wasm_bytes: &[u8], host_imports: &str, script: &str) -> Vec<String> {
+    let directory = tempfile::tempdir().expect("temp dir should be created");
+    let wasm_path = directory.path().join("module.wasm");
+    std::fs::write(&wasm_path, wasm_bytes).expect("wasm bytes should be written");
+
+    let harness = format!(
+        "const fs = require('fs');\n\
+         const bytes = fs.readFileSync({wasm_path});\n\
+         let wasm;\n\
+         const rendered = [];\n\
+         function text(handle) {{\n\
+           const memory = new DataView(wasm.memory.buffer);\n\
+           const ptr = memory.getInt32(handle, true);\n\
+           const len = memory.getInt32(handle + 4, true);\n\
+           return Buffer.from(wasm.memory.buffer, ptr, len).toString('utf8');\n\
+         }}\n\
+         wasm = new WebAssembly.Instance(new WebAssembly.Module(bytes), {host_imports}).exports;\n\
+         const live = () => wasm.bst_live_allocations.value;\n\
+         {script}\n",
+        wasm_path =
+            serde_json::to_string(&wasm_path.to_string_lossy()).expect("path should serialize"),
+    );
+    let harness_path = directory.path().join("harness.cjs");
+    std::fs::write(&harness_path, harness).expect("harness should be written");
+
+    let output = std::process::Command::new("node")
+        .arg(&harness_path)
+        .output()
+        .expect("node should be on PATH to run Wasm runtime tests");
+    assert!(
+        output.status.success(),
+        "node harness failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
@@ -0,0 +1,406 @@
+//! Builtin cast runtime parity tests.
+//!
+//! WHAT: executes emitted `Cast` lowering and `rt_cast_*` helpers under Node.
+//! WHY: `html_wasm` must accept and reject exactly the inputs `backends/js/runtime/casts.rs`
+//!      does, with the same error codes, so each helper is compared against the JS runtime's
+//!      own regexes, `trim`, `Math.trunc` and code-point handling over generated inputs.
+
+use crate::backends::wasm::emit::module::emit_lir_to_wasm_module;
+use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
+use crate::backends::wasm::lir::instructions::{WasmLirStmt, WasmLirTerminator};
+use crate::backends::wasm::lir::linkage::{
+    WasmExport, WasmExportKind, WasmFunctionLinkage, WasmImport, WasmImportKind,
+};
+use crate::backends::wasm::lir::module::WasmLirModule;
+use crate::backends::wasm::lir::types::{
+    WasmAbiType, WasmImportId, WasmLirBlockId, WasmLirFunctionId, WasmLirLocal, WasmLirLocalId,
+    WasmLirSignature, WasmLocalRole,
+};
+use crate::backends::wasm::request::{
+    WasmBackendRequest, WasmExportPolicy, WasmHelperExportPolicy,
+};
+use crate::backends::wasm::runtime::errors::WasmErrorField;
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::memory::WasmMemoryPlan;
+use crate::compiler_frontend::builtins::casts::targets::BuiltinCastPolicyId;
+
+/// Test strings are written into the last page, far above anything the bump allocator reaches.
+const TEST_MEMORY_PAGES: u32 = 32;
+
+#[test]
+fn string_casts_match_js_grammar_and_error_codes() {
+    let output = run_cast_module(
+        vec![
+            cast_function("to_int", BuiltinCastPolicyId::StringToInt),
+            cast_function("to_float", BuiltinCastPolicyId::StringToFloat),
+            cast_function("to_bool", BuiltinCastPolicyId::StringToBool),
+            cast_function("to_char", BuiltinCastPolicyId::StringToChar),
+        ],
+        "const ok = (value) => `ok:${value}`;\n\
+         const err = (code) => `err:${code}`;\n\
+         const expected = {\n\
+           to_int(value) {\n\
+             if (!/^-?(?:\\d+(?:_\\d+)*)$/.test(value)) return err(200);\n\
+             const parsed = Number.parseInt(value.replace(/_/g, ''), 10);\n\
+             return parsed >= -2147483648 && parsed <= 2147483647 ? ok(parsed) : err(201);\n\
+           },\n\
+           to_float(value) {\n\
+             if (!/^-?\\d+(?:_\\d+)*(?:\\.\\d+(?:_\\d+)*)?(?:e[+-]?\\d+(?:_\\d+)*)?$/.test(value)) {\n\
+               return err(210);\n\
+             }\n\
+             const parsed = Number.parseFloat(value.replace(/_/g, ''));\n\
+             return Number.isFinite(parsed) ? ok(parsed) : err(211);\n\
+           },\n\
+           to_bool(value) {\n\
+             const normalized = value.trim();\n\
+             if (normalized === 'true') return ok(1);\n\
+             if (normalized === 'false') return ok(0);\n\
+             return err(220);\n\
+           },\n\
+           to_char(value) {\n\
+             const codePoints = Array.from(value);\n\
+             return codePoints.length === 1 ? ok(codePoints[0].codePointAt(0)) : err(230);\n\
+           },\n\
+         };\n\
+         const read = { to_int: asInt, to_float: asFloat, to_bool: asInt, to_char: asInt };\n\
+         const inputs = [\n\
+           '', '0', '-0', '42', '-2147483648', '2147483648', '-2147483649', '1_000', '1__0',\n\
+           '_1', '1_', '-', '+1', '99999999999999999999', '1.5', '.5', '1.', '1e5', '1e+5',\n\
+           '1e-5', '1E5', '1e', '1e400', '-1e400', '1_0.2_5e-1', '1.2.3', '1e5e5', 'true',\n\
+           'false', ' true ', '\\tfalse\\n', 'True', 'truex', 'fals', '\\u00a0true\\u3000',\n\
+           '\\ufefffalse\\u2003', '\\u0085true', '\\u1680false\\u202f', 'a', 'é', '€', '😀',\n\
+           'ab', '\\u0301', 'e\\u0301', '٣', '１',\n\
+         ];\n\
+         const alphabet = ['0', '1', '9', '_', '-', '+', '.', 'e', 't', 'r', 'u', 'e', ' ',\n\
+           '\\u00a0', '\\u2028', 'λ', '😀'];\n\
+         let state = 0x9e3779b9;\n\
+         const next = (bound) => {\n\
+           state = (Math.imul(state, 1664525) + 1013904223) >>> 0;\n\
+           return state % bound;\n\
+         };\n\
+         for (let i = 0; i < 4000; i++) {\n\
+           let value = '';\n\
+           for (let length = next(9); length > 0; length--) value += alphabet[next(alphabet.length)];\n\
+           inputs.push(value);\n\
+         }\n\
+         let checked = 0;\n\
+         const mismatches = [];\n\
+         for (const value of inputs) {\n\
+           for (const name of Object.keys(expected)) {\n\
+             const actual = carrier(wasm[name](string(value)), read[name]);\n\
+             if (actual !== expected[name](value)) mismatches.push(`${name}(${JSON.stringify(value)})=${actual}`);\n\
+             checked++;\n\
+           }\n\
+         }\n\
+         console.log(`checked=${checked > 16000} mismatches=${mismatches.slice(0, 5)}`);",
+    );
+
+    assert_eq!(output, vec!["checked=true mismatches="]);
+}
+
+#[test]
+fn scalar_casts_match_js_ranges_and_encoding() {
+    let output = run_cast_module(
+        vec![
+            cast_function("float_to_int", BuiltinCastPolicyId::FloatToInt),
+            cast_function("int_to_char", BuiltinCastPolicyId::IntToChar),
+            cast_function("char_to_string", BuiltinCastPolicyId::CharToString),
+            cast_function("char_to_int", BuiltinCastPolicyId::CharToInt),
+            cast_function("int_to_float", BuiltinCastPolicyId::IntToFloat),
+        ],
+        "const floats = [0, -0, 0.5, -0.5, 1.9, -1.9, 2147483647.9, 2147483648, -2147483648.9,\n\
+           -2147483649, 1e300, -1e300, NaN, Infinity, -Infinity];\n\
+         const truncated = floats.map((value) => carrier(wasm.float_to_int(value), asInt));\n\
+         const expectedTruncated = floats.map((value) => {\n\
+           if (!Number.isFinite(value)) return 'err:240';\n\
+           const result = Math.trunc(value);\n\
+           return result >= -2147483648 && result <= 2147483647 ? `ok:${result}` : 'err:241';\n\
+         });\n\
+         const scalars = [-1, 0, 65, 127, 128, 2047, 2048, 55295, 55296, 57343, 57344, 65535,\n\
+           65536, 128512, 1114111, 1114112, 2 ** 40];\n\
+         const chars = scalars.map((value) => carrier(wasm.int_to_char(BigInt(value)), asInt));\n\
+         const expectedChars = scalars.map((value) =>\n\
+           value < 0 || value > 0x10ffff || (value >= 0xd800 && value <= 0xdfff)\n\
+             ? 'err:250'\n\
+             : `ok:${value}`);\n\
+         const valid = scalars.filter((value) => chars[scalars.indexOf(value)].startsWith('ok'));\n\
+         const encoded = valid.filter((value) => text(wasm.char_to_string(value)) !== String.fromCodePoint(value));\n\
+         const widened = valid.filter((value) => wasm.char_to_int(value) !== BigInt(value));\n\
+         console.log(`${JSON.stringify(truncated) === JSON.stringify(expectedTruncated)} ${truncated.join(' ')}`);\n\
+         console.log(`${JSON.stringify(chars) === JSON.stringify(expectedChars)} encoded=${encoded} widened=${widened}`);\n\
+         console.log([wasm.int_to_float(-7n), wasm.int_to_float(2147483647n)].join(' '));",
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "true ok:0 ok:0 ok:0 ok:0 ok:1 ok:-1 ok:2147483647 err:241 ok:-2147483648 err:241 \
+             err:241 err:241 err:240 err:240 err:240",
+            "true encoded= widened=",
+            "-7 2147483647",
+        ]
+    );
+}
+
+#[test]
+fn text_and_error_casts_use_runtime_strings_and_error_records() {
+    let mut error_code = CastTestFunction::new("error_code", WasmAbiType::Handle, WasmAbiType::I64);
+    let error = error_code.local(WasmAbiType::Handle);
+    let code = error_code.local(WasmAbiType::I64);
+    let text = error_code.params[0];
+    error_code.push(WasmLirStmt::Cast {
+        dst: error,
+        policy: BuiltinCastPolicyId::StringToError,
+        value: text,
+    });
+    error_code.push(WasmLirStmt::ErrorField {
+        dst: code,
+        error,
+        field: WasmErrorField::Code,
+    });
+
+    let output = run_cast_module(
+        vec![
+            cast_function("bool_text", BuiltinCastPolicyId::BoolToString),
+            cast_function("int_text", BuiltinCastPolicyId::IntToString),
+            cast_function("float_text", BuiltinCastPolicyId::FloatToString),
+            round_trip_function(),
+            error_code.finish(code),
+        ],
+        "console.log([text(wasm.bool_text(1)), text(wasm.bool_text(0)), text(wasm.int_text(-42n)),\n\
+           text(wasm.float_text(1e21)), text(wasm.float_text(0.1 + 0.2))].join(' '));\n\
+         console.log(`${text(wasm.error_round_trip(string('boom é')))} ${wasm.error_code(string('x'))}`);",
+    );
+
+    assert_eq!(
+        output,
+        vec!["true false -42 1e+21 0.30000000000000004", "boom é 0"]
+    );
+}
+
+/// Exported function applying one builtin cast to its parameter.
+fn cast_function(name: &str, policy: BuiltinCastPolicyId) -> WasmLirFunction {
+    let (param, result) = cast_abi(policy);
+    let mut function = CastTestFunction::new(name, param, result);
+    let dst = function.local(result);
+    let value = function.params[0];
+    function.push(WasmLirStmt::Cast { dst, policy, value });
+    function.finish(dst)
+}
+
+/// `String -> Error -> String`, returning the message handle.
+fn round_trip_function() -> WasmLirFunction {
+    let mut function =
+        CastTestFunction::new("error_round_trip", WasmAbiType::Handle, WasmAbiType::Handle);
+    let error = function.local(WasmAbiType::Handle);
+    let message = function.local(WasmAbiType::Handle);
+    let value = function.params[0];
+    function.push(WasmLirStmt::Cast {
+        dst: error,
+        policy: BuiltinCastPolicyId::StringToError,
+        value,
+    });
+    function.push(WasmLirStmt::Cast {
+        dst: message,
+        policy: BuiltinCastPolicyId::ErrorToString,
+        value: error,
+    });
+    function.finish(message)
+}
+
+/// Parameter and result ABI of a cast policy; fallible casts return a carrier handle.
+fn cast_abi(policy: BuiltinCastPolicyId) -> (WasmAbiType, WasmAbiType) {
+    match policy {
+        BuiltinCastPolicyId::IntToFloat => (WasmAbiType::I64, WasmAbiType::F64),
+        BuiltinCastPolicyId::IntToString => (WasmAbiType::I64, WasmAbiType::Handle),
+        BuiltinCastPolicyId::IntToChar => (WasmAbiType::I64, WasmAbiType::Handle),
+        BuiltinCastPolicyId::FloatToInt | BuiltinCastPolicyId::FloatToString => {
+            (WasmAbiType::F64, WasmAbiType::Handle)
+        }
+        BuiltinCastPolicyId::CharToInt => (WasmAbiType::I32, WasmAbiType::I64),
+        BuiltinCastPolicyId::CharToString | BuiltinCastPolicyId::BoolToString => {
+            (WasmAbiType::I32, WasmAbiType::Handle)
+        }
+        BuiltinCastPolicyId::StringToInt
+        | BuiltinCastPolicyId::StringToFloat
+        | BuiltinCastPolicyId::StringToBool
+        | BuiltinCastPolicyId::StringToChar
+        | BuiltinCastPolicyId::StringToError
+        | BuiltinCastPolicyId::ErrorToString => (WasmAbiType::Handle, WasmAbiType::Handle),
+    }
+}
+
+/// Straight-line LIR function builder with one parameter.
+struct CastTestFunction {
+    name: String,
+    params: Vec<WasmLirLocalId>,
+    signature: WasmLirSignature,
+    locals: Vec<WasmLirLocal>,
+    statements: Vec<WasmLirStmt>,
+}
+
+impl CastTestFunction {
+    fn new(name: &str, param: WasmAbiType, result: WasmAbiType) -> Self {
+        let mut function = Self {
+            name: name.to_owned(),
+            params: vec![],
+            signature: WasmLirSignature {
+                params: vec![param],
+                results: vec![result],
+            },
+            locals: vec![],
+            statements: vec![],
+        };
+        let id = function.local(param);
+        function.locals[id.0 as usize].role = WasmLocalRole::Param;
+        function.params.push(id);
+        function
+    }
+
+    fn local(&mut self, ty: WasmAbiType) -> WasmLirLocalId {
+        let id = WasmLirLocalId(self.locals.len() as u32);
+        self.locals.push(WasmLirLocal {
+            id,
+            name: None,
+            ty,
+            role: WasmLocalRole::Temp,
+        });
+        id
+    }
+
+    fn push(&mut self, statement: WasmLirStmt) {
+        self.statements.push(statement);
+    }
+
+    fn finish(self, returned: WasmLirLocalId) -> WasmLirFunction {
+        WasmLirFunction {
+            id: WasmLirFunctionId(0),
+            debug_name: self.name,
+            origin: WasmLirFunctionOrigin::Normal,
+            signature: self.signature,
+            locals: self.locals,
+            blocks: vec![WasmLirBlock {
+                id: WasmLirBlockId(0),
+                statements: self.statements,
+                terminator: WasmLirTerminator::Return {
+                    value: Some(returned),
+                },
+            }],
+            linkage: WasmFunctionLinkage::ExportedWrapper,
+        }
+    }
+}
+
+fn run_cast_module(functions: Vec<WasmLirFunction>, script: &str) -> Vec<String> {
+    let functions: Vec<WasmLirFunction> = functions
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut function)| {
+            function.id = WasmLirFunctionId(index as u32);
+            function
+        })
+        .collect();
+    let exports = functions
+        .iter()
+        .map(|function| WasmExport {
+            export_name: function.debug_name.clone(),
+            kind: WasmExportKind::Function(function.id),
+        })
+        .collect();
+    let parse_float = WasmHostFunction::ParseFloat;
+    let module = WasmLirModule {
+        functions,
+        imports: vec![WasmImport {
+            id: WasmImportId(0),
+            module_name: parse_float.module_name().to_owned(),
+            item_name: parse_float.item_name().to_owned(),
+            kind: WasmImportKind::Function(WasmLirSignature {
+                params: vec![WasmAbiType::Handle],
+                results: vec![WasmAbiType::F64],
+            }),
+        }],
+        exports,
+        static_data: vec![],
+        memory_plan: WasmMemoryPlan {
+            initial_pages: TEST_MEMORY_PAGES,
+            ..WasmMemoryPlan::default()
+        },
+        record_types: vec![],
+        generic_instances: vec![],
+    };
+
+    let request = WasmBackendRequest {
+        export_policy: WasmExportPolicy {
+            helper_exports: WasmHelperExportPolicy {
+                export_memory: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let emitted =
+        emit_lir_to_wasm_module(&module, &request).expect("cast runtime module should emit");
+
+    run_in_node(&emitted.wasm_bytes, script)
+}
+
+/// Instantiates the module under Node with a `parse_float` host import and returns stdout lines.
+fn run_in_node(wasm_bytes: &[u8], script: &str) -> Vec<String> {
+    let directory = tempfile::tempdir().expect("temp dir should be created");
+    let wasm_path = directory.path().join("module.wasm");
+    std::fs::write(&wasm_path, wasm_bytes).expect("wasm bytes should be written");
+
+    let harness = format!(
+        "const fs = require('fs');\n\
+         const bytes = fs.readFileSync({wasm_path});\n\
+         let wasm;\n\
+         const view = () => new DataView(wasm.memory.buffer);\n\
+         function text(handle) {{\n\
+           const memory = view();\n\
+           const ptr = memory.getInt32(handle, true);\n\
+           const len = memory.getInt32(handle + 4, true);\n\
+           return Buffer.from(wasm.memory.buffer, ptr, len).toString('utf8');\n\
+         }}\n\
+         const imports = {{ host: {{ parse_float: (handle) => Number.parseFloat(text(handle)) }} }};\n\
+         wasm = new WebAssembly.Instance(new WebAssembly.Module(bytes), imports).exports;\n\
+         const scratch = wasm.memory.buffer.byteLength - 65536;\n\
+         function string(value) {{\n\
+           const encoded = Buffer.from(value, 'utf8');\n\
+           new Uint8Array(wasm.memory.buffer).set(encoded, scratch + 8);\n\
+           view().setInt32(scratch, scratch + 8, true);\n\
+           view().setInt32(scratch + 4, encoded.length, true);\n\
+           return scratch;\n\
+         }}\n\
+         const asInt = (memory, offset) => memory.getBigInt64(offset, true);\n\
+         const asFloat = (memory, offset) => memory.getFloat64(offset, true);\n\
+         function carrier(handle, read) {{\n\
+           const memory = view();\n\
+           if (memory.getInt32(handle, true) !== 0) {{\n\
+             const error = memory.getInt32(handle + 8, true);\n\
+             return `err:${{memory.getInt32(error + 4, true)}}`;\n\
+           }}\n\
+           return `ok:${{read(memory, handle + 8)}}`;\n\
+         }}\n\
+         {script}\n",
+        wasm_path =
+            serde_json::to_string(&wasm_path.to_string_lossy()).expect("path should serialize"),
+    );
+    let harness_path = directory.path().join("harness.cjs");
+    std::fs::write(&harness_path, harness).expect("harness should be written");
+
+    let output = std::process::Command::new("node")
+        .arg(&harness_path)
+        .output()
+        .expect("node should be on PATH to run Wasm runtime tests");
+    assert!(
+        output.status.success(),
+        "node harness failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
@@ -0,0 +1,598 @@
+//! Map runtime parity tests.
+//!
+//! WHAT: executes emitted map helpers under Node and inspects linear memory directly.
+//! WHY: the Wasm map runtime must match `backends/js/runtime/maps.rs` on insertion order,
+//!      replace-in-place, string key equality and `MapKeyNotFound` errors, which only shows up
+//!      when the synthesized helpers actually run.
+
+use crate::backends::wasm::backend::lower_hir_to_wasm_module;
+use crate::backends::wasm::emit::module::emit_lir_to_wasm_module;
+use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
+use crate::backends::wasm::lir::instructions::{WasmLirStmt, WasmLirTerminator};
+use crate::backends::wasm::lir::linkage::{WasmExport, WasmExportKind, WasmFunctionLinkage};
+use crate::backends::wasm::lir::module::{WasmLirModule, WasmStaticData, WasmStaticDataKind};
+use crate::backends::wasm::lir::types::{
+    WasmAbiType, WasmLirBlockId, WasmLirFunctionId, WasmLirLocal, WasmLirLocalId, WasmLirSignature,
+    WasmLocalRole, WasmStaticDataId,
+};
+use crate::backends::wasm::request::{
+    WasmBackendRequest, WasmExportPolicy, WasmHelperExportPolicy,
+};
+use crate::backends::wasm::runtime::maps::WasmMapKeyKind;
+use crate::backends::wasm::runtime::memory::WasmMemoryPlan;
+use crate::backends::wasm::tests::lowering::test_support::{
+    build_module, build_type_environment, default_borrow_facts, expression, int_expression,
+    load_local, local, statement, string_expression,
+};
+use crate::compiler_frontend::hir::blocks::HirBlock;
+use crate::compiler_frontend::hir::expressions::{
+    HirExpressionKind, HirMapEntry, HirMapOp, ValueKind,
+};
+use crate::compiler_frontend::hir::functions::{HirFunction, HirFunctionOrigin};
+use crate::compiler_frontend::hir::ids::{BlockId, FunctionId, LocalId, RegionId};
+use crate::compiler_frontend::hir::places::HirPlace;
+use crate::compiler_frontend::hir::statements::HirStatementKind;
+use crate::compiler_frontend::hir::terminators::HirTerminator;
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use rustc_hash::FxHashMap;
+
+/// `BuiltinErrorCode::MapKeyNotFound` as stored in the `Error` record of error carriers.
+const MAP_KEY_NOT_FOUND: &str = "111";
+
+#[test]
+fn map_runtime_keeps_insertion_order_across_replace_remove_and_growth() {
+    let mut ordered = MapTestFunction::new("ordered", WasmAbiType::Handle);
+    let map = ordered.map(WasmMapKeyKind::Scalar);
+    ordered.set(map, 3, 30);
+    ordered.set(map, 1, 10);
+    ordered.set(map, 2, 20);
+    ordered.set(map, 3, 33);
+    ordered.remove(map, 1);
+    ordered.set(map, 1, 11);
+    for key in 4..=9 {
+        ordered.set(map, key, key * 10);
+    }
+
+    // Filling the initial capacity after removals compacts without growing.
+    let mut churn = MapTestFunction::new("churn", WasmAbiType::Handle);
+    let churned = churn.map(WasmMapKeyKind::Scalar);
+    for key in 1..=4 {
+        churn.set(churned, key, key);
+    }
+    for key in 1..=3 {
+        churn.remove(churned, key);
+    }
+    churn.set(churned, 5, 5);
+    churn.set(churned, 2, 2);
+
+    let output = run_map_module(
+        vec![ordered.finish(map), churn.finish(churned)],
+        vec![],
+        "console.log(mapEntries(wasm.ordered()));\n\
+         console.log(mapEntries(wasm.churn()));",
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "3=33,2=20,1=11,4=40,5=50,6=60,7=70,8=80,9=90 len=9",
+            "4=4,5=5,2=2 len=3",
+        ]
+    );
+}
+
+#[test]
+fn map_runtime_reports_missing_keys_as_map_key_not_found() {
+    let mut get_present = MapTestFunction::new("get_present", WasmAbiType::Handle);
+    let map = get_present.populated_map();
+    let carrier = get_present.get(map, 2);
+
+    let mut get_missing = MapTestFunction::new("get_missing", WasmAbiType::Handle);
+    let map_b = get_missing.populated_map();
+    let missing = get_missing.get(map_b, 7);
+
+    let mut remove_present = MapTestFunction::new("remove_present", WasmAbiType::Handle);
+    let map_c = remove_present.populated_map();
+    let removed = remove_present.remove(map_c, 2);
+
+    let mut remove_twice = MapTestFunction::new("remove_twice", WasmAbiType::Handle);
+    let map_d = remove_twice.populated_map();
+    remove_twice.remove(map_d, 2);
+    let removed_again = remove_twice.remove(map_d, 2);
+
+    let mut contains_removed = MapTestFunction::new("contains_removed", WasmAbiType::I32);
+    let map_e = contains_removed.populated_map();
+    contains_removed.remove(map_e, 1);
+    let contains = contains_removed.contains(map_e, 1);
+
+    let mut length_after_clear = MapTestFunction::new("length_after_clear", WasmAbiType::I64);
+    let map_f = length_after_clear.populated_map();
+    length_after_clear.clear(map_f);
+    length_after_clear.set(map_f, 8, 80);
+    let length = length_after_clear.length(map_f);
+
+    let output = run_map_module(
+        vec![
+            get_present.finish(carrier),
+            get_missing.finish(missing),
+            remove_present.finish(removed),
+            remove_twice.finish(removed_again),
+            contains_removed.finish(contains),
+            length_after_clear.finish(length),
+        ],
+        vec![],
+        "console.log(carrier(wasm.get_present()));\n\
+         console.log(carrier(wasm.get_missing()));\n\
+         console.log(carrier(wasm.remove_present()));\n\
+         console.log(carrier(wasm.remove_twice()));\n\
+         console.log(wasm.contains_removed());\n\
+         console.log(wasm.length_after_clear().toString());",
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "ok:20".to_owned(),
+            format!("err:{MAP_KEY_NOT_FOUND}"),
+            "ok:20".to_owned(),
+            format!("err:{MAP_KEY_NOT_FOUND}"),
+            "0".to_owned(),
+            "1".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn map_runtime_compares_string_keys_by_content() {
+    let name = WasmStaticData {
+        id: WasmStaticDataId(0),
+        debug_name: "name".to_owned(),
+        bytes: b"Priya".to_vec(),
+        kind: WasmStaticDataKind::Utf8StringBytes,
+    };
+
+    let mut lookup = MapTestFunction::new("lookup_by_copy", WasmAbiType::Handle);
+    let map = lookup.map(WasmMapKeyKind::String);
+    let first = lookup.string(WasmStaticDataId(0));
+    let second = lookup.string(WasmStaticDataId(0));
+    let ten = lookup.const_i64(10);
+    lookup.push(WasmLirStmt::MapSet {
+        dst: None,
+        map,
+        key: first,
+        value: ten,
+    });
+    let carrier = lookup.local(WasmAbiType::Handle);
+    lookup.push(WasmLirStmt::MapGet {
+        dst: Some(carrier),
+        map,
+        key: second,
+    });
+
+    let mut replace = MapTestFunction::new("replace_by_copy", WasmAbiType::I64);
+    let replaced = replace.map(WasmMapKeyKind::String);
+    for value in [1, 2] {
+        let key = replace.string(WasmStaticDataId(0));
+        let value = replace.const_i64(value);
+        replace.push(WasmLirStmt::MapSet {
+            dst: None,
+            map: replaced,
+            key,
+            value,
+        });
+    }
+    let length = replace.length(replaced);
+
+    let output = run_map_module(
+        vec![lookup.finish(carrier), replace.finish(length)],
+        vec![name],
+        "console.log(carrier(wasm.lookup_by_copy()));\n\
+         console.log(wasm.replace_by_copy().toString());",
+    );
+
+    assert_eq!(output, vec!["ok:10", "1"]);
+}
+
+#[test]
+fn lowered_map_lookup_takes_success_and_error_branches() {
+    let mut string_table = StringTable::new();
+    let (mut type_environment, types) = build_type_environment();
+    let map_type = type_environment.intern_map(types.string, types.int);
+    let carrier_type = type_environment.intern_fallible_carrier(types.int, types.string);
+    let region = RegionId(0);
+
+    let mut blocks = vec![];
+    let mut functions = vec![];
+    for (index, (name, key)) in [("lookup_present", "Priya"), ("lookup_missing", "Linus")]
+        .into_iter()
+        .enumerate()
+    {
+        let function_id = FunctionId(index as u32);
+        let entry = BlockId(index as u32 * 3);
+        let success = BlockId(entry.0 + 1);
+        let error = BlockId(entry.0 + 2);
+        let ids = index as u32 * 100;
+
+        // Duplicate keys keep the first position and the last value, matching the JS runtime.
+        let entries = [("Priya", 10), ("Grace", 12), ("Priya", 11)]
+            .into_iter()
+            .enumerate()
+            .map(|(offset, (entry_key, value))| HirMapEntry {
+                key: string_expression(ids + offset as u32 * 2, entry_key, types.string, region),
+                value: int_expression(ids + offset as u32 * 2 + 1, value, types.int, region),
+            })
+            .collect();
+
+        blocks.push(HirBlock {
+            id: entry,
+            region,
+            locals: vec![
+                local(0, map_type, region),
+                local(1, carrier_type, region),
+                local(2, types.int, region),
+            ],
+            statements: vec![
+                statement(
+                    ids + 10,
+                    HirStatementKind::Assign {
+                        target: HirPlace::Local(LocalId(0)),
+                        value: expression(
+                            ids + 11,
+                            HirExpressionKind::MapLiteral(entries),
+                            map_type,
+                            region,
+                            ValueKind::RValue,
+                        ),
+                    },
+                    1,
+                ),
+                statement(
+                    ids + 12,
+                    HirStatementKind::MapOp {
+                        op: HirMapOp::Get,
+                        receiver: load_local(ids + 13, LocalId(0), map_type, region),
+                        args: vec![string_expression(ids + 14, key, types.string, region)],
+                        result: Some(LocalId(1)),
+                    },
+                    2,
+                ),
+            ],
+            terminator: HirTerminator::FallibleBranch {
+                result: load_local(ids + 15, LocalId(1), carrier_type, region),
+                success_block: success,
+                error_block: error,
+            },
+        });
+        blocks.push(HirBlock {
+            id: success,
+            region,
+            locals: vec![],
+            statements: vec![statement(
+                ids + 16,
+                HirStatementKind::Assign {
+                    target: HirPlace::Local(LocalId(2)),
+                    value: expression(
+                        ids + 17,
+                        HirExpressionKind::FallibleUnwrapSuccess {
+                            result: Box::new(load_local(
+                                ids + 18,
+                                LocalId(1),
+                                carrier_type,
+                                region,
+                            )),
+                        },
+                        types.int,
+                        region,
+                        ValueKind::RValue,
+                    ),
+                },
+                3,
+            )],
+            terminator: HirTerminator::Return(load_local(ids + 19, LocalId(2), types.int, region)),
+        });
+        blocks.push(HirBlock {
+            id: error,
+            region,
+            locals: vec![],
+            statements: vec![],
+            terminator: HirTerminator::Return(int_expression(ids + 20, -1, types.int, region)),
+        });
+
+        let origin = if index == 0 {
+            HirFunctionOrigin::EntryStart
+        } else {
+            HirFunctionOrigin::Normal
+        };
+        functions.push((
+            HirFunction {
+                id: function_id,
+                entry,
+                params: vec![],
+                return_type: types.int,
+                return_aliases: vec![],
+            },
+            InternedPath::from_single_str(name, &mut string_table),
+            origin,
+        ));
+    }
+
+    let hir_module = build_module(&mut string_table, functions, blocks, FunctionId(0));
+
+    let mut export_names = FxHashMap::default();
+    export_names.insert(FunctionId(0), "lookup_present".to_owned());
+    export_names.insert(FunctionId(1), "lookup_missing".to_owned());
+    let request = WasmBackendRequest {
+        export_policy: WasmExportPolicy {
+            exported_functions: vec![FunctionId(0), FunctionId(1)],
+            export_names,
+            helper_exports: WasmHelperExportPolicy {
+                export_memory: true,
+                ..Default::default()
+            },
+            command_start: None,
+        },
+        ..Default::default()
+    };
+
+    let result = lower_hir_to_wasm_module(
+        &hir_module,
+        &default_borrow_facts(),
+        &request,
+        &string_table,
+        &type_environment,
+    )
+    .expect("map lookups should lower to the Wasm map runtime");
+    let wasm_bytes = result.wasm_bytes.expect("wasm bytes should be emitted");
+
+    let output = run_in_node(
+        &wasm_bytes,
+        "console.log(wasm.lookup_present().toString());\n\
+         console.log(wasm.lookup_missing().toString());",
+    );
+
+    assert_eq!(output, vec!["11", "-1"]);
+}
+
+/// Straight-line LIR function builder for driving map statements directly.
+struct MapTestFunction {
+    name: String,
+    result: WasmAbiType,
+    locals: Vec<WasmLirLocal>,
+    statements: Vec<WasmLirStmt>,
+}
+
+impl MapTestFunction {
+    fn new(name: &str, result: WasmAbiType) -> Self {
+        Self {
+            name: name.to_owned(),
+            result,
+            locals: vec![],
+            statements: vec![],
+        }
+    }
+
+    fn local(&mut self, ty: WasmAbiType) -> WasmLirLocalId {
+        let id = WasmLirLocalId(self.locals.len() as u32);
+        self.locals.push(WasmLirLocal {
+            id,
+            name: None,
+            ty,
+            role: WasmLocalRole::Temp,
+        });
+        id
+    }
+
+    fn push(&mut self, statement: WasmLirStmt) {
+        self.statements.push(statement);
+    }
+
+    fn const_i64(&mut self, value: i64) -> WasmLirLocalId {
+        let dst = self.local(WasmAbiType::I64);
+        self.push(WasmLirStmt::ConstI64 { dst, value });
+        dst
+    }
+
+    fn string(&mut self, data: WasmStaticDataId) -> WasmLirLocalId {
+        let buffer = self.local(WasmAbiType::Handle);
+        let dst = self.local(WasmAbiType::Handle);
+        self.push(WasmLirStmt::StringNewBuffer { dst: buffer });
+        self.push(WasmLirStmt::StringPushLiteral { buffer, data });
+        self.push(WasmLirStmt::StringFinish { dst, buffer });
+        dst
+    }
+
+    fn map(&mut self, key_kind: WasmMapKeyKind) -> WasmLirLocalId {
+        let dst = self.local(WasmAbiType::Handle);
+        self.push(WasmLirStmt::MapNew { dst, key_kind });
+        dst
+    }
+
+    /// Scalar map holding `1=10, 2=20, 3=30`.
+    fn populated_map(&mut self) -> WasmLirLocalId {
+        let map = self.map(WasmMapKeyKind::Scalar);
+        for key in 1..=3 {
+            self.set(map, key, key * 10);
+        }
+        map
+    }
+
+    fn set(&mut self, map: WasmLirLocalId, key: i64, value: i64) {
+        let key = self.const_i64(key);
+        let value = self.const_i64(value);
+        self.push(WasmLirStmt::MapSet {
+            dst: None,
+            map,
+            key,
+            value,
+        });
+    }
+
+    fn get(&mut self, map: WasmLirLocalId, key: i64) -> WasmLirLocalId {
+        let key = self.const_i64(key);
+        let dst = self.local(WasmAbiType::Handle);
+        self.push(WasmLirStmt::MapGet {
+            dst: Some(dst),
+            map,
+            key,
+        });
+        dst
+    }
+
+    fn remove(&mut self, map: WasmLirLocalId, key: i64) -> WasmLirLocalId {
+        let key = self.const_i64(key);
+        let dst = self.local(WasmAbiType::Handle);
+        self.push(WasmLirStmt::MapRemove {
+            dst: Some(dst),
+            map,
+            key,
+        });
+        dst
+    }
+
+    fn contains(&mut self, map: WasmLirLocalId, key: i64) -> WasmLirLocalId {
+        let key = self.const_i64(key);
+        let dst = self.local(WasmAbiType::I32);
+        self.push(WasmLirStmt::MapContains {
+            dst: Some(dst),
+            map,
+            key,
+        });
+        dst
+    }
+
+    fn clear(&mut self, map: WasmLirLocalId) {
+        self.push(WasmLirStmt::MapClear { map });
+    }
+
+    fn length(&mut self, map: WasmLirLocalId) -> WasmLirLocalId {
+        let dst = self.local(WasmAbiType::I64);
+        self.push(WasmLirStmt::MapLength {
+            dst: Some(dst),
+            map,
+        });
+        dst
+    }
+
+    fn finish(self, returned: WasmLirLocalId) -> WasmLirFunction {
+        WasmLirFunction {
+            id: WasmLirFunctionId(0),
+            debug_name: self.name,
+            origin: WasmLirFunctionOrigin::Normal,
+            signature: WasmLirSignature {
+                params: vec![],
+                results: vec![self.result],
+            },
+            locals: self.locals,
+            blocks: vec![WasmLirBlock {
+                id: WasmLirBlockId(0),
+                statements: self.statements,
+                terminator: WasmLirTerminator::Return {
+                    value: Some(returned),
+                },
+            }],
+            linkage: WasmFunctionLinkage::ExportedWrapper,
+        }
+    }
+}
+
+fn run_map_module(
+    functions: Vec<WasmLirFunction>,
+    static_data: Vec<WasmStaticData>,
+    script: &str,
+) -> Vec<String> {
+    let functions: Vec<WasmLirFunction> = functions
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut function)| {
+            function.id = WasmLirFunctionId(index as u32);
+            function
+        })
+        .collect();
+    let exports = functions
+        .iter()
+        .map(|function| WasmExport {
+            export_name: function.debug_name.clone(),
+            kind: WasmExportKind::Function(function.id),
+        })
+        .collect();
+    let module = WasmLirModule {
+        functions,
+        imports: vec![],
+        exports,
+        static_data,
+        memory_plan: WasmMemoryPlan::default(),
+        record_types: vec![],
+        generic_instances: vec![],
+    };
+
+    let request = WasmBackendRequest {
+        export_policy: WasmExportPolicy {
+            helper_exports: WasmHelperExportPolicy {
+                export_memory: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let emitted =
+        emit_lir_to_wasm_module(&module, &request).expect("map runtime module should emit");
+
+    run_in_node(&emitted.wasm_bytes, script)
+}
+
+/// Instantiates the module under Node and returns the script's stdout lines.
+fn run_in_node(wasm_bytes: &[u8], script: &str) -> Vec<String> {
+    let directory = tempfile::tempdir().expect("temp dir should be created");
+    let wasm_path = directory.path().join("module.wasm");
+    std::fs::write(&wasm_path, wasm_bytes).expect("wasm bytes should be written");
+
+    let harness = format!(
+        "const fs = require('fs');\n\
+         const bytes = fs.readFileSync({wasm_path});\n\
+         const wasm = new WebAssembly.Instance(new WebAssembly.Module(bytes), {{}}).exports;\n\
+         const view = () => new DataView(wasm.memory.buffer);\n\
+         function mapEntries(map) {{\n\
+           const memory = view();\n\
+           const entries = memory.getInt32(map, true);\n\
+           const count = memory.getInt32(map + 4, true);\n\
+           const live = [];\n\
+           for (let index = 0; index < count; index++) {{\n\
+             const entry = entries + index * 24;\n\
+             if (memory.getInt32(entry + 20, true) !== 0) {{\n\
+               live.push(`${{memory.getBigInt64(entry, true)}}=${{memory.getBigInt64(entry + 8, true)}}`);\n\
+             }}\n\
+           }}\n\
+           return `${{live.join(',')}} len=${{memory.getInt32(map + 8, true)}}`;\n\
+         }}\n\
+         function carrier(handle) {{\n\
+           const memory = view();\n\
+           if (memory.getInt32(handle, true) !== 0) {{\n\
+             const error = memory.getInt32(handle + 8, true);\n\
+             return `err:${{memory.getInt32(error + 4, true)}}`;\n\
+           }}\n\
+           return `ok:${{memory.getBigInt64(handle + 8, true)}}`;\n\
+         }}\n\
+         {script}\n",
+        wasm_path =
+            serde_json::to_string(&wasm_path.to_string_lossy()).expect("path should serialize"),
+    );
+    let harness_path = directory.path().join("harness.cjs");
+    std::fs::write(&harness_path, harness).expect("harness should be written");
+
+    let output = std::process::Command::new("node")
+        .arg(&harness_path)
+        .output()
+        .expect("node should be on PATH to run Wasm runtime tests");
+    assert!(
+        output.status.success(),
+        "node harness failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
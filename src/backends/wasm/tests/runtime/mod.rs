@@ -0,0 +1,5 @@
+mod allocator_runtime_tests;
+mod cast_runtime_tests;
+mod map_runtime_tests;
+mod numeric_runtime_tests;
+mod record_layout_tests;
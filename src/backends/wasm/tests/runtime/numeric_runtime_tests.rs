@@ -0,0 +1,495 @@
+//! Checked numeric runtime parity tests.
+//!
+//! WHAT: executes emitted checked Int/Float helpers and the Float formatter under Node.
+//! WHY: `html_wasm` output must be byte-identical to `backends/js/runtime/numeric.rs`, so the
+//!      helpers are compared against the JS engine's own `String(value)`, `%` and error codes.
+
+use crate::backends::wasm::emit::module::emit_lir_to_wasm_module;
+use crate::backends::wasm::lir::function::{WasmLirBlock, WasmLirFunction, WasmLirFunctionOrigin};
+use crate::backends::wasm::lir::instructions::{WasmCalleeRef, WasmLirStmt, WasmLirTerminator};
+use crate::backends::wasm::lir::linkage::{
+    WasmExport, WasmExportKind, WasmFunctionLinkage, WasmImport, WasmImportKind,
+};
+use crate::backends::wasm::lir::module::WasmLirModule;
+use crate::backends::wasm::lir::types::{
+    WasmAbiType, WasmImportId, WasmLirBlockId, WasmLirFunctionId, WasmLirLocal, WasmLirLocalId,
+    WasmLirSignature, WasmLocalRole,
+};
+use crate::backends::wasm::request::{
+    WasmBackendRequest, WasmExportPolicy, WasmHelperExportPolicy,
+};
+use crate::backends::wasm::runtime::imports::WasmHostFunction;
+use crate::backends::wasm::runtime::memory::WasmMemoryPlan;
+use crate::backends::wasm::runtime::numeric::{WasmNumericFailureMode, WasmNumericOp};
+
+/// Enough linear memory for thousands of formatted Floats under the bump allocator.
+const TEST_MEMORY_PAGES: u32 = 32;
+
+#[test]
+fn float_format_matches_js_number_to_string() {
+    let output = run_numeric_module(
+        vec![format_function("format", WasmNumericFailureMode::Trap)],
+        "const values = [\n\
+           0, -0, 1, -1, 1.5, 0.1, 0.2, 0.30000000000000004, 1 / 3, 2 / 3, 100, 123456789,\n\
+           1e21, 1e-7, 1e-6, 0.000001234, 123e-20, 1e20, 9007199254740993, 2 ** 53, 2 ** 64,\n\
+           Number.MAX_VALUE, Number.MIN_VALUE, -Number.MAX_VALUE, 2.2250738585072014e-308,\n\
+           2.225073858507201e-308, 5e-324, 1.7976931348623157e308, 4.35, 0.5, 1e23, 5e-7,\n\
+           123456789012345680000, 1.2345678901234567e-300, 9.5367431640625e-7, 0.1 + 0.7,\n\
+         ];\n\
+         let state = 0x2545f4914f6cdd1dn;\n\
+         const bits = new DataView(new ArrayBuffer(8));\n\
+         function next() {\n\
+           state ^= (state << 13n) & 0xffffffffffffffffn;\n\
+           state ^= state >> 7n;\n\
+           state ^= (state << 17n) & 0xffffffffffffffffn;\n\
+           return state;\n\
+         }\n\
+         for (let i = 0; i < 6000; i++) {\n\
+           bits.setBigUint64(0, next());\n\
+           const value = bits.getFloat64(0);\n\
+           if (Number.isFinite(value)) values.push(value);\n\
+           values.push(Number((Number(next() % 2000000n) / 1000 - 1000).toFixed(3)));\n\
+           values.push(Number(next() % 100000n) * 10 ** (Number(next() % 40n) - 20));\n\
+         }\n\
+         const mismatches = values.filter((value) => {\n\
+           const expected = Object.is(value, -0) ? '0' : String(value);\n\
+           return text(wasm.format(value)) !== expected;\n\
+         });\n\
+         console.log(`checked=${values.length > 15000} mismatches=${mismatches.slice(0, 5)}`);",
+    );
+
+    assert_eq!(output, vec!["checked=true mismatches="]);
+}
+
+#[test]
+fn int_helpers_match_js_range_and_error_codes() {
+    let output = run_numeric_module(
+        vec![
+            checked_function(
+                "add",
+                WasmNumericOp::IntAdd,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "sub",
+                WasmNumericOp::IntSub,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "mul",
+                WasmNumericOp::IntMul,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "div",
+                WasmNumericOp::IntDiv,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "mod",
+                WasmNumericOp::IntMod,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "pow",
+                WasmNumericOp::IntPow,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "neg",
+                WasmNumericOp::IntNeg,
+                WasmNumericFailureMode::ReturnError,
+            ),
+        ],
+        "const MIN = -2147483648n;\n\
+         const MAX = 2147483647n;\n\
+         const results = [\n\
+           wasm.add(2n, 3n), wasm.add(MAX, 1n), wasm.sub(MIN, 1n), wasm.sub(-5n, 7n),\n\
+           wasm.mul(65536n, 32768n), wasm.mul(-65536n, 32768n), wasm.div(-7n, 2n),\n\
+           wasm.div(7n, 0n), wasm.div(MIN, -1n), wasm.mod(-7n, 2n), wasm.mod(7n, 0n),\n\
+           wasm.mod(MIN, -1n), wasm.pow(2n, 30n), wasm.pow(2n, 31n), wasm.pow(-2n, 31n),\n\
+           wasm.pow(0n, 0n), wasm.pow(-1n, 9999999n), wasm.pow(3n, -1n), wasm.pow(7n, 100n),\n\
+           wasm.neg(MIN), wasm.neg(MAX),\n\
+         ];\n\
+         console.log(results.map((handle) => carrier(handle, asInt)).join(' '));",
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "ok:5 err:301 err:301 ok:-12 err:301 ok:-2147483648 ok:-3 err:300 err:301 ok:-1 \
+             err:300 err:301 ok:1073741824 err:301 ok:-2147483648 ok:1 ok:-1 err:302 err:301 \
+             err:301 ok:-2147483647"
+        ]
+    );
+}
+
+#[test]
+fn float_helpers_match_js_arithmetic_and_error_codes() {
+    let output = run_numeric_module(
+        vec![
+            checked_function(
+                "add",
+                WasmNumericOp::FloatAdd,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "mul",
+                WasmNumericOp::FloatMul,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "div",
+                WasmNumericOp::FloatDiv,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "mod",
+                WasmNumericOp::FloatMod,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "neg",
+                WasmNumericOp::FloatNeg,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function(
+                "validate",
+                WasmNumericOp::FloatValidate,
+                WasmNumericFailureMode::ReturnError,
+            ),
+            checked_function("rem", WasmNumericOp::FloatMod, WasmNumericFailureMode::Trap),
+            float_pow_function("pow"),
+        ],
+        "const results = [\n\
+           wasm.add(0.1, 0.2), wasm.mul(1e308, 10), wasm.div(1, 0), wasm.div(-7.5, 2),\n\
+           wasm.mod(5.5, 0), wasm.mod(-7.5, 2), wasm.neg(1.5), wasm.validate(Infinity),\n\
+           wasm.validate(NaN), wasm.validate(2.5), wasm.pow(2, 0.5), wasm.pow(10, 400),\n\
+           wasm.pow(-8, 1 / 3),\n\
+         ];\n\
+         console.log(results.map((handle) => carrier(handle, asFloat)).join(' '));\n\
+         const bits = new DataView(new ArrayBuffer(8));\n\
+         let seed = 7;\n\
+         const random = () => (seed = (seed * 1103515245 + 12345) % 2147483648) / 2147483648;\n\
+         let mismatches = 0;\n\
+         for (let i = 0; i < 4000; i++) {\n\
+           const a = (random() - 0.5) * 10 ** Math.floor(random() * 600 - 300);\n\
+           const b = (random() - 0.5) * 10 ** Math.floor(random() * 600 - 300);\n\
+           if (b === 0) continue;\n\
+           bits.setFloat64(0, wasm.rem(a, b));\n\
+           const actual = bits.getBigUint64(0);\n\
+           bits.setFloat64(0, a % b);\n\
+           if (actual !== bits.getBigUint64(0)) mismatches++;\n\
+         }\n\
+         console.log(`fmod mismatches=${mismatches}`);",
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            "ok:0.30000000000000004 err:303 err:300 ok:-3.75 err:300 ok:-1.5 ok:-1.5 err:304 \
+             err:304 ok:2.5 ok:1.4142135623730951 err:303 err:303",
+            "fmod mismatches=0",
+        ]
+    );
+}
+
+#[test]
+fn trap_mode_hits_unreachable_only_on_failure() {
+    let output = run_numeric_module(
+        vec![
+            checked_function("add", WasmNumericOp::IntAdd, WasmNumericFailureMode::Trap),
+            checked_function("div", WasmNumericOp::FloatDiv, WasmNumericFailureMode::Trap),
+            format_function("format", WasmNumericFailureMode::Trap),
+            format_function("format_or_error", WasmNumericFailureMode::ReturnError),
+        ],
+        "console.log([\n\
+           trap(() => wasm.add(40n, 2n)),\n\
+           trap(() => wasm.add(2147483647n, 1n)),\n\
+           trap(() => wasm.div(1, 4)),\n\
+           trap(() => wasm.div(1, 0)),\n\
+           trap(() => text(wasm.format(-2.5))),\n\
+           trap(() => text(wasm.format(NaN))),\n\
+           carrier(wasm.format_or_error(1e21), asText),\n\
+           carrier(wasm.format_or_error(-Infinity), asText),\n\
+         ].join(' '));",
+    );
+
+    assert_eq!(
+        output,
+        vec!["ok:42 trap ok:0.25 trap ok:-2.5 trap ok:1e+21 err:305"]
+    );
+}
+
+#[test]
+fn string_from_i64_formats_signed_decimals() {
+    let mut function =
+        NumericTestFunction::new("to_text", &[WasmAbiType::I64], WasmAbiType::Handle);
+    let dst = function.local(WasmAbiType::Handle);
+    let value = function.params[0];
+    function.push(WasmLirStmt::StringFromI64 { dst, value });
+
+    let output = run_numeric_module(
+        vec![function.finish(dst)],
+        "console.log([0n, 7n, -1n, 2147483647n, -2147483648n, 9223372036854775807n,\n\
+           -9223372036854775808n].map((value) => text(wasm.to_text(value))).join(' '));",
+    );
+
+    assert_eq!(
+        output,
+        vec!["0 7 -1 2147483647 -2147483648 9223372036854775807 -9223372036854775808".to_owned()]
+    );
+}
+
+/// Exported function applying one checked operation to its parameters.
+fn checked_function(
+    name: &str,
+    op: WasmNumericOp,
+    failure_mode: WasmNumericFailureMode,
+) -> WasmLirFunction {
+    let operand = match op {
+        WasmNumericOp::IntAdd
+        | WasmNumericOp::IntSub
+        | WasmNumericOp::IntMul
+        | WasmNumericOp::IntDiv
+        | WasmNumericOp::IntMod
+        | WasmNumericOp::IntPow
+        | WasmNumericOp::IntNeg => WasmAbiType::I64,
+        _ => WasmAbiType::F64,
+    };
+    let params = if op.is_unary() {
+        vec![operand]
+    } else {
+        vec![operand, operand]
+    };
+    let result = match failure_mode {
+        WasmNumericFailureMode::ReturnError => WasmAbiType::Handle,
+        WasmNumericFailureMode::Trap => operand,
+    };
+
+    let mut function = NumericTestFunction::new(name, &params, result);
+    let dst = function.local(result);
+    let lhs = function.params[0];
+    let rhs = function.params.get(1).copied();
+    function.push(WasmLirStmt::CheckedNumeric {
+        dst,
+        op,
+        lhs,
+        rhs,
+        failure_mode,
+    });
+    function.finish(dst)
+}
+
+fn format_function(name: &str, failure_mode: WasmNumericFailureMode) -> WasmLirFunction {
+    let mut function = NumericTestFunction::new(name, &[WasmAbiType::F64], WasmAbiType::Handle);
+    let dst = function.local(WasmAbiType::Handle);
+    let value = function.params[0];
+    function.push(WasmLirStmt::FormatFloat {
+        dst,
+        value,
+        failure_mode,
+    });
+    function.finish(dst)
+}
+
+/// Float `^` as lowered from HIR: host `Math.pow`, then a finiteness check.
+fn float_pow_function(name: &str) -> WasmLirFunction {
+    let mut function = NumericTestFunction::new(
+        name,
+        &[WasmAbiType::F64, WasmAbiType::F64],
+        WasmAbiType::Handle,
+    );
+    let power = function.local(WasmAbiType::F64);
+    let dst = function.local(WasmAbiType::Handle);
+    let args = function.params.clone();
+    function.push(WasmLirStmt::Call {
+        dst: Some(power),
+        callee: WasmCalleeRef::Import(WasmImportId(0)),
+        args,
+    });
+    function.push(WasmLirStmt::CheckedNumeric {
+        dst,
+        op: WasmNumericOp::FloatCheckFinite,
+        lhs: power,
+        rhs: None,
+        failure_mode: WasmNumericFailureMode::ReturnError,
+    });
+    function.finish(dst)
+}
+
+/// Straight-line LIR function builder with parameters.
+struct NumericTestFunction {
+    name: String,
+    params: Vec<WasmLirLocalId>,
+    signature: WasmLirSignature,
+    locals: Vec<WasmLirLocal>,
+    statements: Vec<WasmLirStmt>,
+}
+
+impl NumericTestFunction {
+    fn new(name: &str, params: &[WasmAbiType], result: WasmAbiType) -> Self {
+        let mut function = Self {
+            name: name.to_owned(),
+            params: vec![],
+            signature: WasmLirSignature {
+                params: params.to_vec(),
+                results: vec![result],
+            },
+            locals: vec![],
+            statements: vec![],
+        };
+        for ty in params {
+            let id = function.local(*ty);
+            function.locals[id.0 as usize].role = WasmLocalRole::Param;
+            function.params.push(id);
+        }
+        function
+    }
+
+    fn local(&mut self, ty: WasmAbiType) -> WasmLirLocalId {
+        let id = WasmLirLocalId(self.locals.len() as u32);
+        self.locals.push(WasmLirLocal {
+            id,
+            name: None,
+            ty,
+            role: WasmLocalRole::Temp,
+        });
+        id
+    }
+
+    fn push(&mut self, statement: WasmLirStmt) {
+        self.statements.push(statement);
+    }
+
+    fn finish(self, returned: WasmLirLocalId) -> WasmLirFunction {
+        WasmLirFunction {
+            id: WasmLirFunctionId(0),
+            debug_name: self.name,
+            origin: WasmLirFunctionOrigin::Normal,
+            signature: self.signature,
+            locals: self.locals,
+            blocks: vec![WasmLirBlock {
+                id: WasmLirBlockId(0),
+                statements: self.statements,
+                terminator: WasmLirTerminator::Return {
+                    value: Some(returned),
+                },
+            }],
+            linkage: WasmFunctionLinkage::ExportedWrapper,
+        }
+    }
+}
+
+fn run_numeric_module(functions: Vec<WasmLirFunction>, script: &str) -> Vec<String> {
+    let functions: Vec<WasmLirFunction> = functions
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut function)| {
+            function.id = WasmLirFunctionId(index as u32);
+            function
+        })
+        .collect();
+    let exports = functions
+        .iter()
+        .map(|function| WasmExport {
+            export_name: function.debug_name.clone(),
+            kind: WasmExportKind::Function(function.id),
+        })
+        .collect();
+    let math_pow = WasmHostFunction::MathPow;
+    let module = WasmLirModule {
+        functions,
+        imports: vec![WasmImport {
+            id: WasmImportId(0),
+            module_name: math_pow.module_name().to_owned(),
+            item_name: math_pow.item_name().to_owned(),
+            kind: WasmImportKind::Function(WasmLirSignature {
+                params: vec![WasmAbiType::F64, WasmAbiType::F64],
+                results: vec![WasmAbiType::F64],
+            }),
+        }],
+        exports,
+        static_data: vec![],
+        memory_plan: WasmMemoryPlan {
+            initial_pages: TEST_MEMORY_PAGES,
+            ..WasmMemoryPlan::default()
+        },
+        record_types: vec![],
+        generic_instances: vec![],
+    };
+
+    let request = WasmBackendRequest {
+        export_policy: WasmExportPolicy {
+            helper_exports: WasmHelperExportPolicy {
+                export_memory: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let emitted =
+        emit_lir_to_wasm_module(&module, &request).expect("numeric runtime module should emit");
+
+    run_in_node(&emitted.wasm_bytes, script)
+}
+
+/// Instantiates the module under Node with a `Math.pow` host import and returns stdout lines.
+fn run_in_node(wasm_bytes: &[u8], script: &str) -> Vec<String> {
+    let directory = tempfile::tempdir().expect("temp dir should be created");
+    let wasm_path = directory.path().join("module.wasm");
+    std::fs::write(&wasm_path, wasm_bytes).expect("wasm bytes should be written");
+
+    let harness = format!(
+        "const fs = require('fs');\n\
+         const bytes = fs.readFileSync({wasm_path});\n\
+         const imports = {{ host: {{ math_pow: Math.pow }} }};\n\
+         const wasm = new WebAssembly.Instance(new WebAssembly.Module(bytes), imports).exports;\n\
+         const view = () => new DataView(wasm.memory.buffer);\n\
+         function text(handle) {{\n\
+           const memory = view();\n\
+           const ptr = memory.getInt32(handle, true);\n\
+           const len = memory.getInt32(handle + 4, true);\n\
+           return Buffer.from(wasm.memory.buffer, ptr, len).toString('utf8');\n\
+         }}\n\
+         const asInt = (memory, offset) => memory.getBigInt64(offset, true);\n\
+         const asFloat = (memory, offset) => memory.getFloat64(offset, true);\n\
+         const asText = (memory, offset) => text(memory.getInt32(offset, true));\n\
+         function carrier(handle, read) {{\n\
+           const memory = view();\n\
+           if (memory.getInt32(handle, true) !== 0) {{\n\
+             const error = memory.getInt32(handle + 8, true);\n\
+             return `err:${{memory.getInt32(error + 4, true)}}`;\n\
+           }}\n\
+           return `ok:${{read(memory, handle + 8)}}`;\n\
+         }}\n\
+         function trap(run) {{\n\
+           try {{\n\
+             return `ok:${{run()}}`;\n\
+           }} catch (error) {{\n\
+             return error instanceof WebAssembly.RuntimeError ? 'trap' : `throw:${{error}}`;\n\
+           }}\n\
+         }}\n\
+         {script}\n",
+        wasm_path =
+            serde_json::to_string(&wasm_path.to_string_lossy()).expect("path should serialize"),
+    );
+    let harness_path = directory.path().join("harness.cjs");
+    std::fs::write(&harness_path, harness).expect("harness should be written");
+
+    let output = std::process::Command::new("node")
+        .arg(&harness_path)
+        .output()
+        .expect("node should be on PATH to run Wasm runtime tests");
+    assert!(
+        output.status.success(),
+        "node harness failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
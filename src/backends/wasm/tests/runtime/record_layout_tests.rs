@@ -0,0 +1,82 @@
+//! Struct and choice record layout tests.
+//!
+//! WHAT: checks field offsets, alignment and record sizes for the shared record layout rule.
+//! WHY: lowering writes fields at these offsets and the emitter reads them back, so a layout
+//!      drift between two monomorphized instances would silently corrupt linear memory.
+
+use crate::backends::wasm::lir::types::WasmAbiType;
+use crate::backends::wasm::runtime::records::{
+    CHOICE_TAG_OFFSET, WasmRecordField, plan_choice_layout, plan_struct_layout,
+};
+
+fn field(offset: u32, abi: WasmAbiType) -> WasmRecordField {
+    WasmRecordField { offset, abi }
+}
+
+#[test]
+fn struct_layout_aligns_fields_naturally_and_rounds_size() {
+    let layout = plan_struct_layout(&[WasmAbiType::I32, WasmAbiType::I64, WasmAbiType::Handle]);
+
+    assert_eq!(
+        layout.fields,
+        vec![
+            field(0, WasmAbiType::I32),
+            field(8, WasmAbiType::I64),
+            field(16, WasmAbiType::Handle),
+        ]
+    );
+    assert_eq!(layout.size, 24);
+}
+
+#[test]
+fn struct_layout_gives_unit_fields_no_space() {
+    let layout = plan_struct_layout(&[WasmAbiType::Void, WasmAbiType::F32, WasmAbiType::Void]);
+
+    assert_eq!(
+        layout.fields,
+        vec![
+            field(0, WasmAbiType::Void),
+            field(0, WasmAbiType::F32),
+            field(4, WasmAbiType::Void),
+        ]
+    );
+    assert_eq!(layout.size, 4);
+    assert_eq!(plan_struct_layout(&[]).size, 0);
+}
+
+#[test]
+fn monomorphized_struct_instances_get_distinct_layouts() {
+    // `Box of String` and `Box of Int` share one declaration but not one layout.
+    let string_box = plan_struct_layout(&[WasmAbiType::Handle]);
+    let int_box = plan_struct_layout(&[WasmAbiType::I64]);
+
+    assert_eq!(string_box.size, 4);
+    assert_eq!(int_box.size, 8);
+    assert_ne!(string_box.fields, int_box.fields);
+}
+
+#[test]
+fn choice_layout_overlaps_variant_payloads_after_the_tag() {
+    let layout = plan_choice_layout(&[
+        vec![],
+        vec![WasmAbiType::Handle],
+        vec![WasmAbiType::I32, WasmAbiType::F64],
+    ]);
+
+    assert_eq!(CHOICE_TAG_OFFSET, 0);
+    assert_eq!(layout.variants[0], vec![]);
+    assert_eq!(layout.variants[1], vec![field(4, WasmAbiType::Handle)]);
+    assert_eq!(
+        layout.variants[2],
+        vec![field(4, WasmAbiType::I32), field(8, WasmAbiType::F64)]
+    );
+    assert_eq!(layout.size, 16);
+}
+
+#[test]
+fn unit_only_choice_layout_holds_just_the_tag() {
+    let layout = plan_choice_layout(&[vec![], vec![]]);
+
+    assert_eq!(layout.size, 4);
+    assert!(layout.variants.iter().all(Vec::is_empty));
+}
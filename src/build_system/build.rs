@@ -4,7 +4,10 @@
 //! writer (`write_project_outputs`). Build tools can compile once and choose where artifacts are
 //! written without reimplementing frontend/backend orchestration.
 
-use crate::build_system::create_project_modules::compile_project_frontend;
+use crate::build_system::create_project_modules::compile_project_frontend_with_cache;
+use crate::build_system::incremental::{
+    IncrementalBuildCache, ModuleArtifactCache, ModuleReuseStats,
+};
 pub use crate::build_system::output_cleanup::CleanupPolicy;
 use crate::build_system::output_cleanup::{
     finalize_output_cleanup, prepare_output_cleanup, validate_relative_output_path,
@@ -18,10 +21,12 @@ use crate::compiler_frontend::analysis::borrow_checker::BorrowCheckReport;
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
 use crate::compiler_frontend::compiler_messages::CompilerDiagnostic;
 use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
+use crate::compiler_frontend::datatypes::ids::TypeId;
 use crate::compiler_frontend::hir::module::HirModule;
 use crate::compiler_frontend::instrumentation::{FrontendCounter, increment_frontend_counter};
 use crate::compiler_frontend::module_metadata::{HirLoweringMetadata, ModuleDocFragment};
 use crate::compiler_frontend::public_interface_draft::PublicInterfaceDraft;
+use crate::compiler_frontend::semantic_identity::OriginTypeId;
 use crate::compiler_frontend::style_directives::{StyleDirectiveRegistry, StyleDirectiveSpec};
 use crate::compiler_frontend::symbols::compiler_symbols::CompilerSymbolSet;
 use crate::compiler_frontend::symbols::string_interning::{StringIdRemap, StringTable};
@@ -35,6 +40,7 @@ use crate::compiler_frontend::external_packages::{ExternalPackageId, ExternalPac
 use crate::compiler_frontend::paths::rendered_path_usage::RenderedPathUsage;
 use crate::projects::settings::{Config, ProjectConfigError};
 
+use rustc_hash::FxHashMap;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -52,6 +58,7 @@ const FILE_MIN_UNIQUE_SYMBOLS_CAPACITY: usize = 32;
 /// runtime fragments that precede it in source order.
 /// WHY: builders merge const strings with the runtime fragment list returned by entry start()
 /// using the insertion index to reconstruct source-order interleaving.
+#[derive(Clone)]
 pub struct ResolvedConstFragment {
     /// Number of runtime fragments preceding this const fragment in source order.
     pub runtime_insertion_index: usize,
@@ -59,6 +66,44 @@ pub struct ResolvedConstFragment {
     pub rendered_text: String,
 }
 
+/// One entry of an entry file's top-level output in source order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EntryFragment<'a> {
+    Const(&'a str),
+    /// Index into the runtime fragment list returned by entry start().
+    Runtime(usize),
+}
+
+/// Interleaves const fragments with `runtime_fragment_count` runtime slots in source order.
+///
+/// WHAT: the ordering HTML slot rendering uses, for builders that print or serialize fragments
+/// instead of placing them in a page.
+/// WHY: the runtime count is a header-stage upper bound, so consumers must treat runtime entries
+/// past the end of the returned list as empty.
+pub(crate) fn order_entry_fragments(
+    const_fragments: &[ResolvedConstFragment],
+    runtime_fragment_count: usize,
+) -> Vec<EntryFragment<'_>> {
+    let mut sorted_const = const_fragments.iter().collect::<Vec<_>>();
+    sorted_const.sort_by_key(|fragment| fragment.runtime_insertion_index);
+    let mut const_iter = sorted_const.into_iter().peekable();
+    let mut ordered = Vec::with_capacity(const_fragments.len() + runtime_fragment_count);
+
+    for runtime_index in 0..=runtime_fragment_count {
+        while let Some(fragment) =
+            const_iter.next_if(|fragment| fragment.runtime_insertion_index <= runtime_index)
+        {
+            ordered.push(EntryFragment::Const(&fragment.rendered_text));
+        }
+        if runtime_index < runtime_fragment_count {
+            ordered.push(EntryFragment::Runtime(runtime_index));
+        }
+    }
+    ordered.extend(const_iter.map(|fragment| EntryFragment::Const(&fragment.rendered_text)));
+
+    ordered
+}
+
 /// Build-system-owned metadata for one external import used by a compiled module.
 ///
 /// WHAT: carries the backend-facing identity for a provider-resolved external import after
@@ -103,6 +148,7 @@ impl ModuleRootActivity {
 /// WHY: keeping these together in one executable lane makes the HIR/type/borrow pairing obvious
 ///      at every backend call site and lets string-ID remapping touch HIR and type identity
 ///      exactly once. Borrow facts carry only HIR IDs and need no string remap.
+#[derive(Clone)]
 pub(crate) struct ModuleExecutable {
     pub(crate) hir: HirModule,
     pub(crate) type_environment: TypeEnvironment,
@@ -129,6 +175,7 @@ impl ModuleExecutable {
 ///      effective registry is a current dependency carried here until Phase 7 replaces it with
 ///      immutable binding interfaces and per-function link facts; it is not itself a per-function
 ///      link fact.
+#[derive(Clone)]
 pub(crate) struct ModuleLinkFacts {
     /// Effective external package registry after provider resolution for this module.
     ///
@@ -154,6 +201,7 @@ pub(crate) struct ModuleLinkFacts {
 ///      them into one owned lane on the `Module` payload keeps HIR limited to executable/semantic
 ///      IR and gives string-ID remapping, warning collection, and tracked-asset planning a single
 ///      owner. The architecture assigns resolved root-local entry metadata to this lane.
+#[derive(Clone)]
 pub(crate) struct ModuleCompilerMetadata {
     /// Canonical entry file for the compiled module.
     pub(crate) entry_point: PathBuf,
@@ -178,6 +226,20 @@ pub(crate) struct ModuleCompilerMetadata {
     ///      `FunctionSignature` carries donor-local `StringId`s whose remap owner is not in scope
     ///      for the current slice.
     pub(crate) validated_generic_templates: ValidatedGenericTemplateStore,
+    /// The module's public-interface draft, moved here by the flat `Vec<Module>` handoff.
+    ///
+    /// WHAT: `None` until the handoff runs. The draft carries only owned stable values, so it
+    /// needs no string-ID remap.
+    /// WHY: builders that publish a module's `export:` surface, such as the JS module builder,
+    ///      read export names and declaration semantics from the same draft the frontend built.
+    pub(crate) public_interface: Option<PublicInterfaceDraft>,
+    /// Module-local type of each public source nominal the frontend assigned a stable origin.
+    ///
+    /// WHAT: joins the draft's `OriginTypeId`s back to this module's `TypeId`s, which stay valid
+    /// against `ModuleExecutable::type_environment` and `HirStruct::frontend_type_id`.
+    /// WHY: the draft deliberately carries no `TypeId`, but a builder describing exported types
+    ///      in its own runtime layout, such as JS module declarations, needs the lowered struct.
+    pub(crate) public_type_ids_by_origin: FxHashMap<OriginTypeId, TypeId>,
 }
 
 impl ModuleCompilerMetadata {
@@ -188,6 +250,7 @@ impl ModuleCompilerMetadata {
         const_top_level_fragments: Vec<ResolvedConstFragment>,
         root_activity: ModuleRootActivity,
         validated_generic_templates: ValidatedGenericTemplateStore,
+        public_type_ids_by_origin: FxHashMap<OriginTypeId, TypeId>,
     ) -> Self {
         Self {
             entry_point,
@@ -197,6 +260,8 @@ impl ModuleCompilerMetadata {
             const_top_level_fragments,
             root_activity,
             validated_generic_templates,
+            public_interface: None,
+            public_type_ids_by_origin,
         }
     }
 
@@ -248,6 +313,7 @@ impl ModuleCompilerMetadata {
 ///       rendered paths).
 /// WHY: backends consume one stable module payload shape regardless of project type, with
 ///      explicit ownership keeping HIR/type/borrow pairing obvious at call sites.
+#[derive(Clone)]
 pub struct Module {
     pub(crate) executable: ModuleExecutable,
     pub(crate) link_facts: ModuleLinkFacts,
@@ -277,9 +343,8 @@ pub(crate) struct CompiledModuleResult {
     /// explicit pending states for exported generic templates awaiting R3 sidecars.
     /// It carries only owned stable values: no `TypeId`, `NominalTypeId`,
     /// `GenericParameterId`, `TraitId`, `InternedPath` or `StringId` crosses this boundary. It
-    /// is not part of the accepted three-lane `Module` and is not the final
-    /// `PublicSemanticInterface`. The legacy flat `Vec<Module>` handoff explicitly drops it
-    /// until the graph consumer lands.
+    /// is not the final `PublicSemanticInterface`. The legacy flat `Vec<Module>` handoff moves
+    /// it into `ModuleCompilerMetadata::public_interface` for builders that publish exports.
     pub public_interface_draft: PublicInterfaceDraft,
 }
 
@@ -298,6 +363,22 @@ pub trait BackendBuilder {
         string_table: &mut StringTable,
     ) -> Result<Project, CompilerMessages>;
 
+    /// Build the project, reusing per-module artifacts from earlier builds where possible.
+    ///
+    /// Backends that emit one page per module can consult `artifacts` to skip re-emitting
+    /// unchanged pages. The default ignores the cache and runs a full `build_backend`.
+    fn build_backend_incremental(
+        &self,
+        modules: Vec<Module>,
+        config: &Config,
+        flags: &[Flag],
+        string_table: &mut StringTable,
+        artifacts: &mut ModuleArtifactCache,
+    ) -> Result<Project, CompilerMessages> {
+        let _ = artifacts;
+        self.build_backend(modules, config, flags, string_table)
+    }
+
     /// Validate the project configuration
     fn validate_project_config(
         &self,
@@ -347,11 +428,13 @@ pub(crate) struct BuildBootstrap {
 //  Output Payload
 // -------------------------
 
+#[derive(Clone)]
 pub struct OutputFile {
     relative_output_path: PathBuf,
     file_kind: FileKind,
 }
 
+#[derive(Clone)]
 pub enum FileKind {
     // This signals for the build system to not create this file.
     // Good for error checking / LSPs etc.
@@ -397,6 +480,8 @@ pub struct BuildResult {
     pub config: Config,
     pub warnings: Vec<CompilerDiagnostic>,
     pub string_table: StringTable,
+    /// Module and page reuse counts, present only for incremental builds.
+    pub module_reuse: Option<ModuleReuseStats>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -405,6 +490,15 @@ pub enum WriteMode {
     SkipUnchanged,
 }
 
+/// Files a call to [`write_project_outputs`] actually changed on disk.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OutputWriteReport {
+    /// Output-root-relative paths whose contents were written, in project output order.
+    ///
+    /// Under [`WriteMode::SkipUnchanged`] files with identical bytes on disk are left out.
+    pub changed_paths: Vec<PathBuf>,
+}
+
 /// Options for writing a compiled project to disk.
 pub struct WriteOptions {
     pub output_root: PathBuf,
@@ -450,6 +544,31 @@ pub fn build_project(
     project_builder: &ProjectBuilder,
     entry_path: &str,
     flags: &[Flag],
+) -> Result<BuildResult, CompilerMessages> {
+    build_project_with_cache(project_builder, entry_path, flags, None)
+}
+
+/// Build a Beanstalk project, reusing unchanged modules and pages from `cache`.
+///
+/// WHAT: runs the same pipeline as [`build_project`], but directory modules whose sources,
+///       settings and providers are unchanged since the last build skip the frontend, and the
+///       backend may reuse their emitted pages.
+/// WHY: the dev server rebuilds after every edit; recompiling only affected modules keeps
+///      rebuild time proportional to the change instead of the project size.
+pub fn build_project_incremental(
+    project_builder: &ProjectBuilder,
+    entry_path: &str,
+    flags: &[Flag],
+    cache: &mut IncrementalBuildCache,
+) -> Result<BuildResult, CompilerMessages> {
+    build_project_with_cache(project_builder, entry_path, flags, Some(cache))
+}
+
+fn build_project_with_cache(
+    project_builder: &ProjectBuilder,
+    entry_path: &str,
+    flags: &[Flag],
+    mut cache: Option<&mut IncrementalBuildCache>,
 ) -> Result<BuildResult, CompilerMessages> {
     let total_start = crate::timing::start_pipeline_timing();
     let mut path_string_table = StringTable::new();
@@ -489,13 +608,18 @@ pub fn build_project(
         }
     };
 
+    if let Some(cache) = cache.as_deref_mut() {
+        cache.begin_build(&config, flags);
+    }
+
     let compile_frontend_start = crate::timing::start_pipeline_timing();
-    let modules = match compile_project_frontend(
+    let modules = match compile_project_frontend_with_cache(
         &mut config,
         flags,
         &style_directives,
         &mut frontend_surface,
         &mut string_table,
+        cache.as_deref_mut(),
     ) {
         Ok(modules) => {
             log_stage_timing(
@@ -520,24 +644,33 @@ pub fn build_project(
     // --------------------------------------------
 
     let backend_start = crate::timing::start_pipeline_timing();
-    let project =
-        match project_builder
+    let backend_result = match cache.as_deref_mut() {
+        Some(cache) => project_builder.backend.build_backend_incremental(
+            modules,
+            &config,
+            flags,
+            &mut string_table,
+            &mut cache.artifacts,
+        ),
+        None => project_builder
             .backend
-            .build_backend(modules, &config, flags, &mut string_table)
-        {
-            Ok(project) => {
-                log_stage_timing("build_project.backend", backend_start);
-                project
-            }
-            Err(mut compiler_messages) => {
-                log_stage_timing("build_project.backend", backend_start);
-                log_stage_timing("build_project.total", total_start);
-                compiler_messages.string_table = string_table;
-                return Err(compiler_messages);
-            }
-        };
+            .build_backend(modules, &config, flags, &mut string_table),
+    };
+    let project = match backend_result {
+        Ok(project) => {
+            log_stage_timing("build_project.backend", backend_start);
+            project
+        }
+        Err(mut compiler_messages) => {
+            log_stage_timing("build_project.backend", backend_start);
+            log_stage_timing("build_project.total", total_start);
+            compiler_messages.string_table = string_table;
+            return Err(compiler_messages);
+        }
+    };
 
     warnings.extend(project.warnings.iter().cloned());
+    let module_reuse = cache.map(|cache| cache.finish_build());
 
     log_stage_timing("build_project.total", total_start);
 
@@ -546,9 +679,28 @@ pub fn build_project(
         config,
         warnings,
         string_table,
+        module_reuse,
     })
 }
 
+/// Read the `project` value of the config that a build of `entry_path` would load.
+///
+/// WHAT: runs path validation and the Stage 0 bootstrap with `project_builder`, without
+/// compiling any modules.
+/// WHY: the CLI picks the project builder from this value before the real build starts.
+/// Returns `None` when the value is unset or the bootstrap fails; the real build reports the
+/// failure.
+pub(crate) fn configured_project_kind(
+    project_builder: &ProjectBuilder,
+    entry_path: &str,
+) -> Option<String> {
+    let mut path_string_table = StringTable::new();
+    let valid_path = check_if_valid_path(entry_path, &mut path_string_table).ok()?;
+    let bootstrap = bootstrap_project_build(project_builder, valid_path).ok()?;
+
+    bootstrap.config.settings.get("project").cloned()
+}
+
 /// Build the shared Stage 0/bootstrap state used by both CLI builds and the dev server.
 ///
 /// WHAT: merges frontend/project directives, loads `config.bst`, and runs backend-specific
@@ -662,7 +814,7 @@ pub fn write_project_outputs(
     project: &Project,
     options: &WriteOptions,
     string_table: &StringTable,
-) -> Result<(), CompilerMessages> {
+) -> Result<OutputWriteReport, CompilerMessages> {
     let write_total_start = crate::timing::start_pipeline_timing();
 
     // Keep the aggregate output timing visible even when filesystem validation or writes fail.
@@ -676,7 +828,7 @@ fn write_project_outputs_inner(
     project: &Project,
     options: &WriteOptions,
     string_table: &StringTable,
-) -> Result<(), CompilerMessages> {
+) -> Result<OutputWriteReport, CompilerMessages> {
     // ---------------------------------------
     //  Prepare cleanup and create output root
     // ---------------------------------------
@@ -715,7 +867,7 @@ fn write_project_outputs_inner(
     //  Emit individual output files
     // ---------------------------------------
 
-    {
+    let report = {
         let emit_files_start = crate::timing::start_pipeline_timing();
         let result = emit_project_output_files(
             project,
@@ -724,8 +876,8 @@ fn write_project_outputs_inner(
             &mut current_managed_artifact_paths,
         );
         log_stage_timing("output.emit_files_total", emit_files_start);
-        result?;
-    }
+        result?
+    };
 
     // ---------------------------------------
     //  Finalize cleanup and write manifest
@@ -746,7 +898,7 @@ fn write_project_outputs_inner(
         result?;
     }
 
-    Ok(())
+    Ok(report)
 }
 
 fn emit_project_output_files(
@@ -754,7 +906,8 @@ fn emit_project_output_files(
     options: &WriteOptions,
     string_table: &StringTable,
     current_managed_artifact_paths: &mut HashSet<PathBuf>,
-) -> Result<(), CompilerMessages> {
+) -> Result<OutputWriteReport, CompilerMessages> {
+    let mut report = OutputWriteReport::default();
     for output_file in &project.output_files {
         if matches!(output_file.file_kind(), FileKind::NotBuilt) {
             continue;
@@ -775,18 +928,22 @@ fn emit_project_output_files(
 
         let emit_file_start = crate::timing::start_pipeline_timing();
         let emit_file_result = match output_file.file_kind() {
-            FileKind::NotBuilt => Ok(()),
-
-            FileKind::Directory => fs::create_dir_all(&destination).map_err(|error| {
-                file_error_messages(
-                    &destination,
-                    format!(
-                        "Failed to create output directory '{}': {error}",
-                        destination.display()
-                    ),
-                    string_table,
-                )
-            }),
+            FileKind::NotBuilt => Ok(false),
+
+            FileKind::Directory => {
+                fs::create_dir_all(&destination)
+                    .map(|()| false)
+                    .map_err(|error| {
+                        file_error_messages(
+                            &destination,
+                            format!(
+                                "Failed to create output directory '{}': {error}",
+                                destination.display()
+                            ),
+                            string_table,
+                        )
+                    })
+            }
 
             FileKind::Js(content) | FileKind::Html(content) => {
                 write_string_output(&destination, content, options.write_mode, string_table)
@@ -797,10 +954,14 @@ fn emit_project_output_files(
             }
         };
         log_stage_timing("output.emit_file", emit_file_start);
-        emit_file_result?;
+        if emit_file_result? {
+            report
+                .changed_paths
+                .push(relative_output_path.to_path_buf());
+        }
     }
 
-    Ok(())
+    Ok(report)
 }
 
 pub fn collect_frontend_warnings(modules: &[Module]) -> Vec<CompilerDiagnostic> {
@@ -840,32 +1001,35 @@ fn write_string_output(
     content: &str,
     write_mode: WriteMode,
     string_table: &StringTable,
-) -> Result<(), CompilerMessages> {
+) -> Result<bool, CompilerMessages> {
     write_bytes_output(destination, content.as_bytes(), write_mode, string_table)
 }
 
+/// Returns whether the destination was written, `false` when an unchanged write was skipped.
 fn write_bytes_output(
     destination: &Path,
     content: &[u8],
     write_mode: WriteMode,
     string_table: &StringTable,
-) -> Result<(), CompilerMessages> {
+) -> Result<bool, CompilerMessages> {
     create_parent_dir_if_needed(destination, string_table)?;
 
     if should_skip_unchanged_write(destination, content, write_mode) {
-        return Ok(());
+        return Ok(false);
     }
 
-    fs::write(destination, content).map_err(|error| {
-        file_error_messages(
-            destination,
-            format!(
-                "Failed to write output file '{}': {error}",
-                destination.display()
-            ),
-            string_table,
-        )
-    })
+    fs::write(destination, content)
+        .map(|()| true)
+        .map_err(|error| {
+            file_error_messages(
+                destination,
+                format!(
+                    "Failed to write output file '{}': {error}",
+                    destination.display()
+                ),
+                string_table,
+            )
+        })
 }
 
 #[cfg(test)]
@@ -4,6 +4,9 @@
 //! WHY: separating the two flows keeps each path readable as orchestration over named steps.
 
 use crate::build_system::build::{CompiledModuleResult, Module};
+use crate::build_system::incremental::{
+    IncrementalBuildCache, ModuleFingerprint, module_fingerprint,
+};
 
 use crate::compiler_frontend::FrontendBuildProfile;
 use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
@@ -36,7 +39,9 @@ use super::frontend_orchestration::{
     FrontendModuleBuildContext, ModuleCompilationOutcome, ModulePreparationContext,
     module_timing_label, record_module_input_counters,
 };
+use super::module_identity::ModuleId;
 use super::module_inventory;
+use super::project_module_graph::ProjectModuleGraph;
 use super::project_roots;
 use super::project_structure_diagnostics::non_utf8_filesystem_name_error;
 use super::reachable_file_discovery;
@@ -379,16 +384,15 @@ pub(crate) fn compile_single_file_frontend(
     // 6. Merge local results back into the global build context.
     let merge_delta_start = crate::timing::start_pipeline_timing();
     let remap = string_table.merge_delta_from(&result.string_table, base_len);
-    // The transient `CompiledModuleResult` also carries the aggregate public-interface draft
-    // for the next graph/interface slice. The legacy flat `Vec<Module>` handoff drops it here
-    // at the migration boundary; the accepted three-lane `Module` does not store it.
+    // The transient `CompiledModuleResult` also carries the aggregate public-interface draft.
+    // The legacy flat `Vec<Module>` handoff moves it into the module metadata, where builders
+    // that publish exports read it.
     let CompiledModuleResult {
         mut module,
         string_table: _,
         public_interface_draft,
     } = result;
-    // Explicit drop keeps production ownership honest until the graph consumer lands.
-    drop(public_interface_draft);
+    module.metadata.public_interface = Some(public_interface_draft);
     // The validated generic-template store is a body-artefact checkpoint for the future
     // generated sidecar worklist (R3). The legacy flat `Vec<Module>` handoff discards it here
     // before string-table remap because the retained `FunctionSignature` carries donor-local
@@ -420,12 +424,58 @@ pub(crate) fn compile_single_file_frontend(
 struct DirectoryModuleTaskResult {
     entry_path: PathBuf,
     string_table_base_len: usize,
+    /// Incremental-build fingerprint of the module inputs, when a cache is in use.
+    fingerprint: Option<ModuleFingerprint>,
     result: Result<CompiledModuleResult, CompilerMessages>,
 }
 
 struct SuccessfulModuleCompilation {
     string_table_base_len: usize,
-    compiled: CompiledModuleResult,
+    module: Module,
+    string_table: StringTable,
+}
+
+/// Per-build state for fingerprinting directory entry modules in wave order.
+struct ModuleFingerprintContext<'a> {
+    environment: u64,
+    graph: &'a ProjectModuleGraph,
+    module_id_by_entry: FxHashMap<PathBuf, ModuleId>,
+    fingerprints: FxHashMap<ModuleId, ModuleFingerprint>,
+}
+
+impl ModuleFingerprintContext<'_> {
+    /// Fingerprint `discovered` from its entry closure sources and its graph providers.
+    ///
+    /// Providers outside the normal entry waves (support roots, the package facade) have no
+    /// fingerprint of their own; their sources already appear in the entry closure.
+    fn fingerprint(
+        &mut self,
+        discovered: &module_inventory::DiscoveredModule,
+    ) -> ModuleFingerprint {
+        let module_id = self
+            .module_id_by_entry
+            .get(&discovered.entry_point)
+            .copied();
+        let providers = module_id
+            .into_iter()
+            .flat_map(|module_id| self.graph.providers_of(module_id))
+            .filter_map(|provider| self.fingerprints.get(&provider).copied())
+            .collect::<Vec<_>>();
+        let fingerprint = module_fingerprint(
+            self.environment,
+            &discovered.entry_point,
+            discovered
+                .input_files
+                .iter()
+                .map(|input| (input.source_path(), input.source_code())),
+            providers,
+        );
+
+        if let Some(module_id) = module_id {
+            self.fingerprints.insert(module_id, fingerprint);
+        }
+        fingerprint
+    }
 }
 
 struct FailedModuleCompilation {
@@ -445,7 +495,11 @@ struct DirectoryModuleCompileContext<'a> {
 }
 
 impl DirectoryModuleCompileContext<'_> {
-    fn compile(&self, discovered: module_inventory::DiscoveredModule) -> DirectoryModuleTaskResult {
+    fn compile(
+        &self,
+        discovered: module_inventory::DiscoveredModule,
+        fingerprint: Option<ModuleFingerprint>,
+    ) -> DirectoryModuleTaskResult {
         let string_table_fork = self.string_table_fork_source.fork_for_module();
         let (local_table, base_len) = string_table_fork.into_parts();
         let module_inventory::DiscoveredModule {
@@ -494,6 +548,7 @@ impl DirectoryModuleCompileContext<'_> {
                 return DirectoryModuleTaskResult {
                     entry_path: entry_point,
                     string_table_base_len: base_len,
+                    fingerprint,
                     result: Err(messages),
                 };
             }
@@ -549,6 +604,7 @@ impl DirectoryModuleCompileContext<'_> {
         DirectoryModuleTaskResult {
             entry_path: entry_point,
             string_table_base_len: base_len,
+            fingerprint,
             result,
         }
     }
@@ -561,6 +617,7 @@ pub(crate) fn compile_directory_frontend(
     style_directives: &StyleDirectiveRegistry,
     builder_surface: &mut BuilderSurface,
     string_table: &mut StringTable,
+    mut cache: Option<&mut IncrementalBuildCache>,
 ) -> Result<Vec<Module>, CompilerMessages> {
     let total_start = crate::timing::start_pipeline_timing();
 
@@ -623,6 +680,28 @@ pub(crate) fn compile_directory_frontend(
         }
     };
 
+    // Incremental builds fingerprint each entry module in wave order so a provider's fingerprint
+    // is known before its consumers are fingerprinted. Provider-backed external sources are
+    // discovered above and are not module inputs, so they feed the shared environment hash.
+    let mut module_fingerprint_context = cache.as_deref().map(|cache| ModuleFingerprintContext {
+        environment: cache
+            .environment_fingerprint(builder_surface.external_import_cache.source_paths()),
+        graph: &project_setup.project_module_graph,
+        module_id_by_entry: project_setup
+            .project_module_graph
+            .entry_modules()
+            .iter()
+            .map(|&module_id| {
+                let root_file = project_setup
+                    .project_module_graph
+                    .node(module_id)
+                    .root_file();
+                (root_file.to_path_buf(), module_id)
+            })
+            .collect(),
+        fingerprints: FxHashMap::default(),
+    });
+
     // Share the effective external package registry immutably across all module compilations;
     // directory modules may compile in parallel and can safely read the same Arc.
     let external_packages = Arc::new(builder_surface.binding_packages.clone());
@@ -670,14 +749,35 @@ pub(crate) fn compile_directory_frontend(
     // multiple ready jobs uses the existing Rayon indexed parallel iterator. Each wave finishes
     // before the next starts.
     let mut results: Vec<DirectoryModuleTaskResult> = Vec::new();
+    let mut reused_modules = Vec::new();
     for wave in module_waves.into_waves() {
-        let wave_results = if wave.len() > 1 {
-            wave.into_par_iter()
-                .map(|discovered| compile_context.compile(discovered))
+        let mut jobs = Vec::with_capacity(wave.len());
+        for discovered in wave {
+            let (Some(fingerprint_context), Some(cache)) =
+                (module_fingerprint_context.as_mut(), cache.as_deref_mut())
+            else {
+                jobs.push((discovered, None));
+                continue;
+            };
+            let fingerprint = fingerprint_context.fingerprint(&discovered);
+            cache.track_module(&discovered.entry_point, fingerprint);
+            match cache.reuse_frontend_module(&discovered.entry_point, fingerprint) {
+                Some((module, string_table)) => reused_modules.push(SuccessfulModuleCompilation {
+                    string_table_base_len: 0,
+                    module,
+                    string_table,
+                }),
+                None => jobs.push((discovered, Some(fingerprint))),
+            }
+        }
+
+        let wave_results = if jobs.len() > 1 {
+            jobs.into_par_iter()
+                .map(|(discovered, fingerprint)| compile_context.compile(discovered, fingerprint))
                 .collect::<Vec<DirectoryModuleTaskResult>>()
         } else {
-            wave.into_iter()
-                .map(|discovered| compile_context.compile(discovered))
+            jobs.into_iter()
+                .map(|(discovered, fingerprint)| compile_context.compile(discovered, fingerprint))
                 .collect::<Vec<DirectoryModuleTaskResult>>()
         };
         results.extend(wave_results);
@@ -693,15 +793,45 @@ pub(crate) fn compile_directory_frontend(
     log_stage_timing("stage0.directory.result_sort", result_sort_start);
 
     // 5. Partition into successes and failures.
-    let mut successes = Vec::with_capacity(results.len());
+    //
+    // The transient `CompiledModuleResult` also carries the aggregate public-interface draft. The
+    // legacy flat `Vec<Module>` handoff moves it into the module metadata before caching, so
+    // reused modules keep it too.
+    //
+    // The validated generic-template store is a body-artefact checkpoint for the future generated
+    // sidecar worklist (R3). The legacy handoff discards it here before string-table remap because
+    // the retained `FunctionSignature` carries donor-local `StringId`s whose remap owner is not in
+    // scope for this slice. Discarding before remap keeps stale local `StringId`s from reaching
+    // backends.
+    //
+    // Fresh successes are cached in their module-local ID space before any failure aggregation,
+    // so one broken page does not force its healthy siblings to recompile on the next build.
+    let mut successes = reused_modules;
     let mut failures = Vec::new();
 
     for outcome in results {
         match outcome.result {
-            Ok(compiled) => successes.push(SuccessfulModuleCompilation {
-                string_table_base_len: outcome.string_table_base_len,
-                compiled,
-            }),
+            Ok(compiled) => {
+                let CompiledModuleResult {
+                    mut module,
+                    string_table: module_string_table,
+                    public_interface_draft,
+                } = compiled;
+                module.metadata.public_interface = Some(public_interface_draft);
+                module.metadata.discard_validated_generic_templates();
+
+                if let (Some(cache), Some(fingerprint)) =
+                    (cache.as_deref_mut(), outcome.fingerprint)
+                {
+                    cache.store_frontend_module(fingerprint, &module, &module_string_table);
+                }
+
+                successes.push(SuccessfulModuleCompilation {
+                    string_table_base_len: outcome.string_table_base_len,
+                    module,
+                    string_table: module_string_table,
+                });
+            }
             Err(messages) => failures.push(FailedModuleCompilation {
                 string_table_base_len: outcome.string_table_base_len,
                 messages,
@@ -739,31 +869,23 @@ pub(crate) fn compile_directory_frontend(
     }
 
     // 7. All succeeded: merge each local table into the build table and remap.
+    //
+    // Reused modules were forked from an earlier build's base table, so they carry a zero base
+    // length and merge their whole local table. Sorting again keeps the merge order, and with it
+    // every build-table ID, independent of which modules came from the cache.
     let success_merge_start = crate::timing::start_pipeline_timing();
+    successes.sort_by(|a, b| {
+        a.module
+            .metadata
+            .entry_point
+            .cmp(&b.module.metadata.entry_point)
+    });
     let mut compiled_modules = Vec::with_capacity(successes.len());
 
     for success in successes {
-        let remap = string_table.merge_delta_from(
-            &success.compiled.string_table,
-            success.string_table_base_len,
-        );
-        // The transient `CompiledModuleResult` also carries the aggregate public-interface
-        // draft for the next graph/interface slice. The legacy flat `Vec<Module>` handoff
-        // drops it here at the migration boundary; the accepted three-lane `Module` does not
-        // store it.
-        let CompiledModuleResult {
-            mut module,
-            string_table: _,
-            public_interface_draft,
-        } = success.compiled;
-        // Explicit drop keeps production ownership honest until the graph consumer lands.
-        drop(public_interface_draft);
-        // The validated generic-template store is a body-artefact checkpoint for the future
-        // generated sidecar worklist (R3). The legacy flat `Vec<Module>` handoff discards it here
-        // before string-table remap because the retained `FunctionSignature` carries donor-local
-        // `StringId`s whose remap owner is not in scope for this slice. Discarding before remap
-        // keeps stale local `StringId`s from reaching backends.
-        module.metadata.discard_validated_generic_templates();
+        let remap =
+            string_table.merge_delta_from(&success.string_table, success.string_table_base_len);
+        let mut module = success.module;
         if !remap.is_identity() {
             module.remap_string_ids(&remap);
         }
@@ -1033,6 +1033,18 @@ impl FrontendModuleBuildContext<'_> {
                 metadata: lowering_metadata,
             } = hir_lowering;
 
+            // Join each public nominal origin to its module-local type after HIR lowering, so
+            // builders that describe exported types can find the lowered struct layout. Paths
+            // without a nominal in this module's environment are imported and skipped.
+            let public_type_ids_by_origin = public_source_nominal_type_origins
+                .iter()
+                .filter_map(|(path, origin)| {
+                    let nominal_id = type_environment.nominal_id_for_path(path)?;
+                    let type_id = type_environment.type_id_for_nominal_id(nominal_id)?;
+                    Some((origin.clone(), type_id))
+                })
+                .collect::<FxHashMap<_, _>>();
+
             // 7. Validate extracted non-HIR compiler metadata before a successful module is
             // returned. Invalid compiler metadata is an internal CompilerError.
             if let Err(error) = lowering_metadata.validate() {
@@ -1150,6 +1162,7 @@ impl FrontendModuleBuildContext<'_> {
                         const_top_level_fragments,
                         root_activity,
                         validated_generic_templates,
+                        public_type_ids_by_origin,
                     ),
                 },
                 public_interface_draft,
@@ -41,7 +41,7 @@ mod source_tree_index;
 #[cfg(test)]
 pub(super) use module_inventory::{ModuleEntryCompileWaves, discover_all_modules_in_project};
 
-pub(crate) use project_roots::resolve_project_entry_root;
+pub(crate) use project_roots::{frontend_source_roots, resolve_project_entry_root};
 pub(crate) use source_loading::extract_source_code;
 
 #[cfg(test)]
@@ -50,6 +50,7 @@ pub(crate) use crate::projects::settings;
 pub(crate) use std::fs;
 
 use crate::build_system::build::Module;
+use crate::build_system::incremental::IncrementalBuildCache;
 
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
 use crate::compiler_frontend::instrumentation::{log_frontend_counters, reset_frontend_counters};
@@ -74,6 +75,30 @@ pub fn compile_project_frontend(
     style_directives: &StyleDirectiveRegistry,
     builder_surface: &mut BuilderSurface,
     string_table: &mut StringTable,
+) -> Result<Vec<Module>, CompilerMessages> {
+    compile_project_frontend_with_cache(
+        config,
+        flags,
+        style_directives,
+        builder_surface,
+        string_table,
+        None,
+    )
+}
+
+/// Compile all project modules, reusing unchanged directory modules from `cache` when given.
+///
+/// WHAT: same flow as [`compile_project_frontend`]; directory entry modules whose fingerprint
+///       matches a cached result skip the frontend pipeline.
+/// WHY: long-running sessions such as the dev server rebuild after every edit and would
+///      otherwise recompile every page for a one-file change.
+pub(crate) fn compile_project_frontend_with_cache(
+    config: &mut Config,
+    flags: &[Flag],
+    style_directives: &StyleDirectiveRegistry,
+    builder_surface: &mut BuilderSurface,
+    string_table: &mut StringTable,
+    cache: Option<&mut IncrementalBuildCache>,
 ) -> Result<Vec<Module>, CompilerMessages> {
     // Frontend counters are command-scoped and gated by `benchmark_counters`.
     // The counter storage is atomic so directory module compilation can update
@@ -97,6 +122,7 @@ pub fn compile_project_frontend(
             style_directives,
             builder_surface,
             string_table,
+            cache,
         )
     } else if let Some(extension) = config.entry_dir.extension() {
         compilation::compile_single_file_frontend(
@@ -420,6 +420,11 @@ impl ProjectModuleGraph {
             .is_some_and(|providers| providers.contains(&provider))
     }
 
+    /// Providers the given consumer must compile after, in `ModuleId` order.
+    pub(crate) fn providers_of(&self, consumer: ModuleId) -> impl Iterator<Item = ModuleId> + '_ {
+        self.dependency_providers[consumer.index()].iter().copied()
+    }
+
     /// Deterministic topological compile waves over provider-before-consumer edges.
     ///
     /// Wave 0 contains every module with no outstanding providers. Each later wave contains
@@ -134,6 +134,30 @@ pub(crate) fn resolve_project_entry_root(config: &Config) -> PathBuf {
     }
 }
 
+/// Roots that frontend logical paths are relative to: the entry root, then the project root.
+///
+/// Single-file projects use the file's own directory for both.
+pub(crate) fn frontend_source_roots(config: &Config) -> Vec<PathBuf> {
+    let roots = if config.entry_dir.is_dir() {
+        vec![
+            resolve_project_entry_root(config),
+            config.entry_dir.to_owned(),
+        ]
+    } else {
+        config
+            .entry_dir
+            .parent()
+            .map(PathBuf::from)
+            .into_iter()
+            .collect()
+    };
+
+    roots
+        .into_iter()
+        .map(|root| fs::canonicalize(&root).unwrap_or(root))
+        .collect()
+}
+
 fn resolve_project_roots(
     config: &Config,
     string_table: &mut StringTable,
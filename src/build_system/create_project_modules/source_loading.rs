@@ -1,16 +1,15 @@
 //! Raw file I/O for Beanstalk source files.
 //!
-//! Reads source file content from disk with structured error diagnostics.
+//! Reads source file content from disk with structured error diagnostics. Editor tooling can
+//! scope a source overlay around a check so open, unsaved documents replace their files on disk.
 
 use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerErrorMetadataKey};
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-
-#[cfg(test)]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
 
 #[cfg(test)]
 static SOURCE_READ_COUNT_FOR_TEST: std::sync::atomic::AtomicUsize =
@@ -20,6 +19,56 @@ static SOURCE_READ_COUNT_FOR_TEST: std::sync::atomic::AtomicUsize =
 static SOURCE_READ_TRACK_PREFIX_FOR_TEST: std::sync::Mutex<Option<PathBuf>> =
     std::sync::Mutex::new(None);
 
+/// In-memory source text keyed by canonical file path.
+pub(crate) type SourceOverlay = HashMap<PathBuf, String>;
+
+static ACTIVE_SOURCE_OVERLAY: RwLock<Option<Arc<SourceOverlay>>> = RwLock::new(None);
+
+// Serializes overlay scopes so concurrent callers never observe each other's documents.
+static SOURCE_OVERLAY_SCOPE: Mutex<()> = Mutex::new(());
+
+// -------------------------
+//  Source Overlay
+// -------------------------
+
+/// Run `run` with `overlay` replacing the on-disk text of the files it names.
+///
+/// WHAT: every source read inside the scope, including reads on Rayon workers, returns the
+///       overlay text for a matching canonical path and falls back to disk otherwise.
+/// WHY: the language server checks unsaved editor buffers through the same pipeline as
+///      `bean check`; a process-wide scope reaches the Stage 0 workers without threading the
+///      overlay through every discovery and loading signature.
+pub(crate) fn with_source_overlay<R>(overlay: SourceOverlay, run: impl FnOnce() -> R) -> R {
+    struct ClearOnDrop;
+
+    impl Drop for ClearOnDrop {
+        fn drop(&mut self) {
+            *ACTIVE_SOURCE_OVERLAY
+                .write()
+                .unwrap_or_else(PoisonError::into_inner) = None;
+        }
+    }
+
+    let _scope = SOURCE_OVERLAY_SCOPE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    *ACTIVE_SOURCE_OVERLAY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(overlay));
+    let _clear = ClearOnDrop;
+
+    run()
+}
+
+fn overlay_source(file_path: &Path) -> Option<String> {
+    ACTIVE_SOURCE_OVERLAY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()?
+        .get(file_path)
+        .cloned()
+}
+
 // -------------------------
 //  Source Extraction
 // -------------------------
@@ -35,6 +84,10 @@ pub(crate) fn read_source_code(file_path: &Path) -> Result<String, std::io::Erro
         SOURCE_READ_COUNT_FOR_TEST.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
     }
 
+    if let Some(source) = overlay_source(file_path) {
+        return Ok(source);
+    }
+
     fs::read_to_string(file_path)
 }
 
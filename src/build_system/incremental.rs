@@ -0,0 +1,243 @@
+//! Cross-build module cache for incremental rebuilds.
+//!
+//! WHAT: keeps each entry module's frontend result and emitted page artifacts from earlier builds,
+//!       keyed by a fingerprint of the module's source contents, the build settings, external
+//!       import sources and the fingerprints of its `ProjectModuleGraph` providers.
+//! WHY: the dev server rebuilds the whole project on every stable change. Most edits touch one
+//!      page, so recompiling and re-emitting only the modules whose inputs changed keeps large
+//!      documentation sites responsive.
+//!
+//! Cached frontend modules stay in their module-local string-ID space next to the local table
+//! they were compiled against. Reuse merges that whole table into the new build table, so a
+//! cached module never depends on the previous build's shared string-table prefix.
+
+use crate::build_system::build::{Module, OutputFile};
+use crate::compiler_frontend::Flag;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::projects::settings::Config;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Content fingerprint of one entry module's compile inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ModuleFingerprint(u64);
+
+/// How much of the latest incremental build was reused.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ModuleReuseStats {
+    /// Entry modules whose frontend result came from the cache.
+    pub reused_modules: usize,
+    /// Entry modules compiled through the frontend in this build.
+    pub rebuilt_modules: usize,
+    /// Pages whose emitted artifacts came from the cache.
+    pub reused_pages: usize,
+    /// Pages emitted by the backend in this build.
+    pub emitted_pages: usize,
+}
+
+/// Module results kept between builds of one project.
+///
+/// Create one per long-running build session, such as the dev server, and pass it to
+/// [`crate::build_system::build::build_project_incremental`] for every rebuild.
+#[derive(Default)]
+pub struct IncrementalBuildCache {
+    frontend_modules: FxHashMap<PathBuf, CachedFrontendModule>,
+    pub(crate) artifacts: ModuleArtifactCache,
+    settings_fingerprint: u64,
+    reused_modules: usize,
+}
+
+struct CachedFrontendModule {
+    fingerprint: ModuleFingerprint,
+    module: Module,
+    string_table: StringTable,
+}
+
+/// Page artifacts from earlier builds, consulted by backends that emit one page per module.
+#[derive(Default)]
+pub struct ModuleArtifactCache {
+    /// Fingerprints of the modules in the build that is running now.
+    current_fingerprints: FxHashMap<PathBuf, ModuleFingerprint>,
+    entries: FxHashMap<PathBuf, CachedModuleArtifacts>,
+    reused_pages: usize,
+    emitted_pages: usize,
+}
+
+struct CachedModuleArtifacts {
+    fingerprint: ModuleFingerprint,
+    output_files: Vec<OutputFile>,
+    entry_page: PathBuf,
+}
+
+impl IncrementalBuildCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset per-build counters and record the settings every module fingerprint depends on.
+    pub(crate) fn begin_build(&mut self, config: &Config, flags: &[Flag]) {
+        self.reused_modules = 0;
+        self.artifacts.current_fingerprints.clear();
+        self.artifacts.reused_pages = 0;
+        self.artifacts.emitted_pages = 0;
+        self.settings_fingerprint = settings_fingerprint(config, flags);
+    }
+
+    /// Fingerprint shared by every module of this build.
+    ///
+    /// WHAT: combines the config and flags with the contents of every provider-backed external
+    ///       import, such as imported JS files, because those sources are not module inputs.
+    pub(crate) fn environment_fingerprint<'a>(
+        &self,
+        external_sources: impl IntoIterator<Item = &'a Path>,
+    ) -> u64 {
+        let mut sources = external_sources.into_iter().collect::<Vec<_>>();
+        sources.sort();
+        sources.dedup();
+
+        let mut hasher = DefaultHasher::new();
+        self.settings_fingerprint.hash(&mut hasher);
+        for path in sources {
+            path.hash(&mut hasher);
+            fs::read(path).ok().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Record the fingerprint a module has in the running build.
+    pub(crate) fn track_module(&mut self, entry_point: &Path, fingerprint: ModuleFingerprint) {
+        self.artifacts
+            .current_fingerprints
+            .insert(entry_point.to_path_buf(), fingerprint);
+    }
+
+    /// A copy of the cached frontend result when its inputs are unchanged.
+    ///
+    /// Returns the module in its module-local string-ID space and the local table to merge.
+    pub(crate) fn reuse_frontend_module(
+        &mut self,
+        entry_point: &Path,
+        fingerprint: ModuleFingerprint,
+    ) -> Option<(Module, StringTable)> {
+        let cached = self
+            .frontend_modules
+            .get(entry_point)
+            .filter(|cached| cached.fingerprint == fingerprint)?;
+        self.reused_modules += 1;
+        Some((cached.module.clone(), cached.string_table.clone()))
+    }
+
+    /// Keep a freshly compiled module, before its IDs are remapped into the build table.
+    pub(crate) fn store_frontend_module(
+        &mut self,
+        fingerprint: ModuleFingerprint,
+        module: &Module,
+        string_table: &StringTable,
+    ) {
+        self.frontend_modules.insert(
+            module.metadata.entry_point.clone(),
+            CachedFrontendModule {
+                fingerprint,
+                module: module.clone(),
+                string_table: string_table.clone(),
+            },
+        );
+    }
+
+    /// Drop entries for modules that are no longer in the project and report reuse.
+    pub(crate) fn finish_build(&mut self) -> ModuleReuseStats {
+        let live_modules = &self.artifacts.current_fingerprints;
+        self.frontend_modules
+            .retain(|entry_point, _| live_modules.contains_key(entry_point));
+        self.artifacts
+            .entries
+            .retain(|entry_point, _| live_modules.contains_key(entry_point));
+
+        ModuleReuseStats {
+            reused_modules: self.reused_modules,
+            rebuilt_modules: live_modules.len().saturating_sub(self.reused_modules),
+            reused_pages: self.artifacts.reused_pages,
+            emitted_pages: self.artifacts.emitted_pages,
+        }
+    }
+}
+
+impl ModuleArtifactCache {
+    /// Copies of the artifacts emitted for `entry_point` when its module is unchanged.
+    ///
+    /// Returns the output files and the page path that was recorded with them.
+    pub(crate) fn reuse(&mut self, entry_point: &Path) -> Option<(Vec<OutputFile>, PathBuf)> {
+        let fingerprint = self.current_fingerprints.get(entry_point)?;
+        let cached = self
+            .entries
+            .get(entry_point)
+            .filter(|cached| cached.fingerprint == *fingerprint)?;
+        self.reused_pages += 1;
+        Some((cached.output_files.clone(), cached.entry_page.clone()))
+    }
+
+    /// Record artifacts the backend just emitted for `entry_point`.
+    pub(crate) fn store(
+        &mut self,
+        entry_point: &Path,
+        output_files: &[OutputFile],
+        entry_page: &Path,
+    ) {
+        self.emitted_pages += 1;
+        let Some(fingerprint) = self.current_fingerprints.get(entry_point) else {
+            return;
+        };
+        self.entries.insert(
+            entry_point.to_path_buf(),
+            CachedModuleArtifacts {
+                fingerprint: *fingerprint,
+                output_files: output_files.to_vec(),
+                entry_page: entry_page.to_path_buf(),
+            },
+        );
+    }
+}
+
+/// Fingerprint one entry module from its sources and the fingerprints of its providers.
+pub(crate) fn module_fingerprint<'a>(
+    environment: u64,
+    entry_point: &Path,
+    sources: impl IntoIterator<Item = (&'a Path, &'a str)>,
+    providers: impl IntoIterator<Item = ModuleFingerprint>,
+) -> ModuleFingerprint {
+    let mut hasher = DefaultHasher::new();
+    environment.hash(&mut hasher);
+    entry_point.hash(&mut hasher);
+    for (path, source) in sources {
+        path.hash(&mut hasher);
+        source.hash(&mut hasher);
+    }
+    for provider in providers {
+        provider.hash(&mut hasher);
+    }
+    ModuleFingerprint(hasher.finish())
+}
+
+fn settings_fingerprint(config: &Config, flags: &[Flag]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    config.project_name.hash(&mut hasher);
+    config.entry_dir.hash(&mut hasher);
+    config.entry_root.hash(&mut hasher);
+    config.package_folders.hash(&mut hasher);
+    config.template_const_loop_iteration_limit.hash(&mut hasher);
+    config.version.hash(&mut hasher);
+    config.author.hash(&mut hasher);
+    config.license.hash(&mut hasher);
+    // Sorted so the fingerprint does not depend on `HashMap` iteration order.
+    config
+        .settings
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .hash(&mut hasher);
+    flags.hash(&mut hasher);
+    hasher.finish()
+}
@@ -270,6 +270,47 @@ fn skip_unchanged_mode_preserves_existing_output_mtime() {
     fs::remove_dir_all(&root).expect("should remove temp dir");
 }
 
+#[test]
+fn write_report_lists_only_files_whose_contents_changed() {
+    let root = temp_dir("write_report_changed_paths");
+    fs::create_dir_all(&root).expect("should create temp root");
+    let options = skip_unchanged_options(root.clone(), None);
+    let project_with_about = |about: &str| {
+        html_project(
+            vec![
+                OutputFile::new(
+                    PathBuf::from("index.html"),
+                    FileKind::Html(String::from("<html>home</html>")),
+                ),
+                OutputFile::new(
+                    PathBuf::from("about/index.html"),
+                    FileKind::Html(about.to_owned()),
+                ),
+            ],
+            Some(PathBuf::from("index.html")),
+        )
+    };
+
+    let first = write_project_outputs(&project_with_about("<html>about</html>"), &options)
+        .expect("first write should succeed");
+    assert_eq!(
+        first.changed_paths,
+        vec![
+            PathBuf::from("index.html"),
+            PathBuf::from("about/index.html")
+        ]
+    );
+
+    let second = write_project_outputs(&project_with_about("<html>about us</html>"), &options)
+        .expect("second write should succeed");
+    assert_eq!(
+        second.changed_paths,
+        vec![PathBuf::from("about/index.html")]
+    );
+
+    fs::remove_dir_all(&root).expect("should remove temp dir");
+}
+
 #[test]
 fn skip_unchanged_mode_still_cleans_stale_manifest_tracked_outputs() {
     let root = temp_dir("skip_unchanged_cleanup");
@@ -0,0 +1,165 @@
+//! Tests for incremental rebuilds that reuse unchanged modules and pages.
+// NOTE: temp file creation processes have to be explicitly dropped
+// Or these tests will fail on Windows due to attempts to delete non-empty temp directories while files are still open.
+
+use super::*;
+use crate::build_system::build::{
+    BuildResult, ProjectBuilder, build_project, build_project_incremental,
+};
+use crate::build_system::incremental::{IncrementalBuildCache, ModuleReuseStats};
+use crate::compiler_tests::test_support::temp_dir;
+use crate::projects::html_project::html_project_builder::HtmlProjectBuilder;
+use std::fs;
+use std::path::Path;
+
+fn write_two_page_project(root: &Path) {
+    fs::create_dir_all(root.join("about")).expect("should create about directory");
+    fs::create_dir_all(root.join("utils")).expect("should create utils directory");
+    fs::write(root.join("config.bst"), "project #= \"html\"\n").expect("should write config");
+    fs::write(
+        root.join("#page.bst"),
+        "import @utils/greeting { greet }\n[: Home [greet()]]\n",
+    )
+    .expect("should write home page");
+    fs::write(root.join("about/#page.bst"), "[: About]\n").expect("should write about page");
+    fs::write(
+        root.join("utils/greeting.bst"),
+        "greet || -> String:\n    return \"hello\"\n;\n",
+    )
+    .expect("should write greeting helper");
+}
+
+fn build_incrementally(root: &Path, cache: &mut IncrementalBuildCache) -> BuildResult {
+    let builder = ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+    build_project_incremental(
+        &builder,
+        root.to_str().expect("temp dir should be UTF-8"),
+        &[],
+        cache,
+    )
+    .expect("incremental build should succeed")
+}
+
+fn html_outputs(result: &BuildResult) -> Vec<(PathBuf, String)> {
+    let mut outputs = result
+        .project
+        .output_files
+        .iter()
+        .filter_map(|file| match file.file_kind() {
+            FileKind::Html(html) => Some((file.relative_output_path().to_path_buf(), html.clone())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    outputs.sort();
+    outputs
+}
+
+#[test]
+fn unchanged_rebuild_reuses_every_module_and_page() {
+    let root = temp_dir("incremental_unchanged");
+    write_two_page_project(&root);
+    let mut cache = IncrementalBuildCache::new();
+
+    let first = build_incrementally(&root, &mut cache);
+    assert_eq!(
+        first.module_reuse,
+        Some(ModuleReuseStats {
+            reused_modules: 0,
+            rebuilt_modules: 2,
+            reused_pages: 0,
+            emitted_pages: 2,
+        })
+    );
+
+    let second = build_incrementally(&root, &mut cache);
+    assert_eq!(
+        second.module_reuse,
+        Some(ModuleReuseStats {
+            reused_modules: 2,
+            rebuilt_modules: 0,
+            reused_pages: 2,
+            emitted_pages: 0,
+        })
+    );
+    assert_eq!(html_outputs(&first), html_outputs(&second));
+
+    fs::remove_dir_all(&root).expect("should remove temp root");
+}
+
+#[test]
+fn editing_one_page_rebuilds_only_that_module() {
+    let root = temp_dir("incremental_page_edit");
+    write_two_page_project(&root);
+    let mut cache = IncrementalBuildCache::new();
+    build_incrementally(&root, &mut cache);
+
+    fs::write(root.join("about/#page.bst"), "[: About us]\n").expect("should edit about page");
+    let result = build_incrementally(&root, &mut cache);
+
+    let reuse = result
+        .module_reuse
+        .expect("incremental builds report reuse");
+    assert_eq!((reuse.reused_modules, reuse.rebuilt_modules), (1, 1));
+    assert_eq!((reuse.reused_pages, reuse.emitted_pages), (1, 1));
+
+    let about_html = html_outputs(&result)
+        .into_iter()
+        .find(|(path, _)| path.starts_with("about"))
+        .map(|(_, html)| html)
+        .expect("about page should be emitted");
+    assert!(about_html.contains("About us"));
+
+    fs::remove_dir_all(&root).expect("should remove temp root");
+}
+
+#[test]
+fn editing_an_imported_file_rebuilds_the_importing_page() {
+    let root = temp_dir("incremental_import_edit");
+    write_two_page_project(&root);
+    let mut cache = IncrementalBuildCache::new();
+    build_incrementally(&root, &mut cache);
+
+    fs::write(
+        root.join("utils/greeting.bst"),
+        "greet || -> String:\n    return \"welcome\"\n;\n",
+    )
+    .expect("should edit greeting helper");
+    let incremental = build_incrementally(&root, &mut cache);
+
+    let reuse = incremental
+        .module_reuse
+        .expect("incremental builds report reuse");
+    assert_eq!((reuse.reused_modules, reuse.rebuilt_modules), (1, 1));
+
+    // Reused and rebuilt modules together must match a clean full build.
+    let builder = ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+    let full = build_project(
+        &builder,
+        root.to_str().expect("temp dir should be UTF-8"),
+        &[],
+    )
+    .expect("full build should succeed");
+    assert_eq!(full.module_reuse, None);
+    assert_eq!(html_outputs(&incremental), html_outputs(&full));
+
+    fs::remove_dir_all(&root).expect("should remove temp root");
+}
+
+#[test]
+fn removed_pages_are_dropped_from_the_cache() {
+    let root = temp_dir("incremental_page_removed");
+    write_two_page_project(&root);
+    let mut cache = IncrementalBuildCache::new();
+    build_incrementally(&root, &mut cache);
+
+    fs::remove_dir_all(root.join("about")).expect("should remove about page");
+    let result = build_incrementally(&root, &mut cache);
+
+    let reuse = result
+        .module_reuse
+        .expect("incremental builds report reuse");
+    assert_eq!((reuse.reused_modules, reuse.rebuilt_modules), (1, 0));
+    assert_eq!(html_outputs(&result).len(), 1);
+
+    fs::remove_dir_all(&root).expect("should remove temp root");
+}
@@ -4,7 +4,8 @@
 
 use super::{WriteOptions, write_project_outputs as write_project_outputs_with_table};
 use crate::build_system::build::{
-    BackendBuilder, CleanupPolicy, FileKind, ModuleRootActivity, OutputFile, Project, WriteMode,
+    BackendBuilder, CleanupPolicy, FileKind, ModuleRootActivity, OutputFile, OutputWriteReport,
+    Project, WriteMode,
 };
 use crate::builder_surface::BuilderSurface;
 use crate::compiler_frontend::Flag;
@@ -89,7 +90,7 @@ fn module_root_activity_html_policy_requires_any_root_activity() {
 fn write_project_outputs(
     project: &Project,
     options: &WriteOptions,
-) -> Result<(), CompilerMessages> {
+) -> Result<OutputWriteReport, CompilerMessages> {
     write_project_outputs_with_table(project, options, &StringTable::default())
 }
 
@@ -354,4 +355,5 @@ mod build_directive_tests;
 mod build_import_tests;
 mod build_infrastructure_tests;
 mod build_orchestration_tests;
+mod incremental_build_tests;
 mod module_lane_tests;
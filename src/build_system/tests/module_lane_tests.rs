@@ -110,6 +110,8 @@ fn remap_string_ids_routes_hir_through_executable_and_leaves_link_facts_untouche
             doc_fragments: vec![],
             rendered_path_usages: vec![],
             validated_generic_templates: ValidatedGenericTemplateStore::default(),
+            public_interface: None,
+            public_type_ids_by_origin: Default::default(),
         },
     };
 
@@ -196,6 +198,8 @@ fn legacy_handoff_discards_validated_generic_template_store_before_remap() {
             doc_fragments: vec![],
             rendered_path_usages: vec![],
             validated_generic_templates: store,
+            public_interface: None,
+            public_type_ids_by_origin: Default::default(),
         },
     };
 
@@ -10,13 +10,15 @@ use crate::compiler_frontend::external_packages::{
     ExternalAbiType, ExternalAccessKind, ExternalFunctionDef, ExternalFunctionId,
     ExternalFunctionLowerings, ExternalJsLowering, ExternalPackageRegistry, ExternalReturnAlias,
     ExternalReturnSlot, ExternalSignatureType, ExternalSymbolPath, ExternalTypeDef,
-    IO_INPUT_EXTERNAL_TYPE_ID, external_success_returns,
+    ExternalWasmLowering, IO_INPUT_EXTERNAL_TYPE_ID, external_success_returns,
 };
 
 struct IoConsoleFunctionSpec {
     id: ExternalFunctionId,
     path: &'static str,
     js_helper: &'static str,
+    /// WASI command lowering: every console call writes one line, like `console.log`.
+    wasi_adapter: &'static str,
 }
 
 const IO_CONSOLE_FUNCTIONS: &[IoConsoleFunctionSpec] = &[
@@ -24,26 +26,31 @@ const IO_CONSOLE_FUNCTIONS: &[IoConsoleFunctionSpec] = &[
         id: ExternalFunctionId::IoPrint,
         path: "print",
         js_helper: "__bs_io_print",
+        wasi_adapter: "stdout_line",
     },
     IoConsoleFunctionSpec {
         id: ExternalFunctionId::IoLine,
         path: "line",
         js_helper: "__bs_io_line",
+        wasi_adapter: "stdout_line",
     },
     IoConsoleFunctionSpec {
         id: ExternalFunctionId::IoDebug,
         path: "debug",
         js_helper: "__bs_io_debug",
+        wasi_adapter: "stdout_line",
     },
     IoConsoleFunctionSpec {
         id: ExternalFunctionId::IoWarn,
         path: "warn",
         js_helper: "__bs_io_warn",
+        wasi_adapter: "stderr_line",
     },
     IoConsoleFunctionSpec {
         id: ExternalFunctionId::IoError,
         path: "error",
         js_helper: "__bs_io_error",
+        wasi_adapter: "stderr_line",
     },
 ];
 
@@ -53,6 +60,10 @@ struct IoInputFunctionSpec {
     parameters: Vec<(ExternalSignatureType, ExternalAccessKind)>,
     returns: Vec<ExternalReturnSlot>,
     error_return_type: Option<ExternalSignatureType>,
+    /// WASI command lowering: terminal input reads one stdin line per `update` (see
+    /// `runtime::wasi`). Helpers returning optional values have none until Wasm has an
+    /// `Option` layout.
+    wasi_adapter: Option<&'static str>,
 }
 
 fn input_handle_param() -> Vec<(ExternalSignatureType, ExternalAccessKind)> {
@@ -92,6 +103,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
                 IO_INPUT_EXTERNAL_TYPE_ID,
             ))],
             error_return_type: Some(ExternalSignatureType::BuiltinError),
+            wasi_adapter: Some("stdin_input_new"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputUpdate,
@@ -99,6 +111,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_mutable_handle_param(),
             returns: external_success_returns(ExternalAbiType::Void, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("stdin_input_update"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputClose,
@@ -106,6 +119,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_mutable_handle_param(),
             returns: external_success_returns(ExternalAbiType::Void, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("stdin_input_close"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputKeyDown,
@@ -113,6 +127,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_and_string_param(),
             returns: external_success_returns(ExternalAbiType::Bool, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("stdin_key_current"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputKeyPressed,
@@ -120,6 +135,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_and_string_param(),
             returns: external_success_returns(ExternalAbiType::Bool, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("stdin_key_current"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputKeyReleased,
@@ -127,6 +143,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_and_string_param(),
             returns: external_success_returns(ExternalAbiType::Bool, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("stdin_key_previous"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputPointerX,
@@ -134,6 +151,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_param(),
             returns: external_success_returns(ExternalAbiType::F64, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("no_pointer_coordinate"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputPointerY,
@@ -141,6 +159,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_param(),
             returns: external_success_returns(ExternalAbiType::F64, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("no_pointer_coordinate"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputPointerDown,
@@ -148,6 +167,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_and_string_param(),
             returns: external_success_returns(ExternalAbiType::Bool, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("no_pointer_button"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputPointerPressed,
@@ -155,6 +175,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_and_string_param(),
             returns: external_success_returns(ExternalAbiType::Bool, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("no_pointer_button"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputPointerReleased,
@@ -162,6 +183,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
             parameters: input_handle_and_string_param(),
             returns: external_success_returns(ExternalAbiType::Bool, ExternalReturnAlias::Fresh),
             error_return_type: None,
+            wasi_adapter: Some("no_pointer_button"),
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputLastKeyPressed,
@@ -171,6 +193,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
                 Box::new(ExternalSignatureType::Abi(ExternalAbiType::Utf8Str)),
             ))],
             error_return_type: None,
+            wasi_adapter: None,
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputLastKeyReleased,
@@ -180,6 +203,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
                 Box::new(ExternalSignatureType::Abi(ExternalAbiType::Utf8Str)),
             ))],
             error_return_type: None,
+            wasi_adapter: None,
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputLastPointerPressed,
@@ -189,6 +213,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
                 Box::new(ExternalSignatureType::Abi(ExternalAbiType::Utf8Str)),
             ))],
             error_return_type: None,
+            wasi_adapter: None,
         },
         IoInputFunctionSpec {
             id: ExternalFunctionId::IoInputLastPointerReleased,
@@ -198,6 +223,7 @@ fn io_input_functions() -> Vec<IoInputFunctionSpec> {
                 Box::new(ExternalSignatureType::Abi(ExternalAbiType::Utf8Str)),
             ))],
             error_return_type: None,
+            wasi_adapter: None,
         },
     ]
 }
@@ -241,7 +267,7 @@ fn register_io_console_function(
             js: Some(ExternalJsLowering::RuntimeFunction(
                 spec.js_helper.to_owned(),
             )),
-            wasm: None,
+            wasm: Some(ExternalWasmLowering::WasiAdapter(spec.wasi_adapter)),
         },
     };
 
@@ -290,7 +316,7 @@ fn register_io_input_function(
             js: Some(ExternalJsLowering::RuntimeFunction(
                 spec.id.name().to_owned(),
             )),
-            wasm: None,
+            wasm: spec.wasi_adapter.map(ExternalWasmLowering::WasiAdapter),
         },
     };
 
@@ -14,7 +14,7 @@ use crate::compiler_frontend::external_packages::ExternalPackageRegistry;
 use crate::compiler_frontend::external_packages::{
     ExternalAbiType, ExternalAccessKind, ExternalFunctionLowerings, ExternalFunctionSpec,
     ExternalJsLowering, ExternalPackageId, ExternalParameter, ExternalReturnSlot,
-    ExternalSignatureType, ExternalTypeId, ExternalTypeSpec,
+    ExternalSignatureType, ExternalTypeId, ExternalTypeSpec, ExternalWasmLowering,
 };
 
 pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
@@ -51,6 +51,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             js_lowering: ExternalJsLowering::InlineExpression(
                 "globalThis.performance.now()".to_owned(),
             ),
+            wasi_adapter: Some("monotonic_now"),
         },
     );
 
@@ -65,6 +66,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             js_lowering: ExternalJsLowering::InlineExpression(
                 "(globalThis.performance.now() - #0)".to_owned(),
             ),
+            wasi_adapter: Some("elapsed_since"),
         },
     );
 
@@ -80,6 +82,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             returns: vec![ExternalReturnSlot::fresh(duration_type.clone())],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("(#1 - #0)".to_owned()),
+            wasi_adapter: Some("difference"),
         },
     );
 
@@ -94,6 +97,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             returns: vec![ExternalReturnSlot::fresh(timestamp_type.clone())],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("Date.now()".to_owned()),
+            wasi_adapter: Some("realtime_now"),
         },
     );
 
@@ -110,6 +114,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             returns: vec![ExternalReturnSlot::fresh(duration_type.clone())],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("(#0 * 1000.0)".to_owned()),
+            wasi_adapter: Some("seconds_to_millis"),
         },
     );
 
@@ -124,6 +129,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             returns: vec![ExternalReturnSlot::fresh(duration_type.clone())],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("#0".to_owned()),
+            wasi_adapter: Some("identity"),
         },
     );
 
@@ -140,6 +146,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             returns: vec![ExternalReturnSlot::fresh(timestamp_type.clone())],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("(#0 * 1000.0)".to_owned()),
+            wasi_adapter: Some("seconds_to_millis"),
         },
     );
 
@@ -154,6 +161,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             returns: vec![ExternalReturnSlot::fresh(timestamp_type.clone())],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("#0".to_owned()),
+            wasi_adapter: Some("identity"),
         },
     );
 
@@ -170,6 +178,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             js_lowering: ExternalJsLowering::RuntimeFunction(
                 "__bs_time_timestamp_from_iso_string".to_owned(),
             ),
+            wasi_adapter: None,
         },
     );
 
@@ -188,6 +197,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             ))],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("(#0 / 1000.0)".to_owned()),
+            wasi_adapter: Some("millis_to_seconds"),
         },
     );
 
@@ -202,6 +212,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             ))],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("#0".to_owned()),
+            wasi_adapter: Some("identity"),
         },
     );
 
@@ -216,6 +227,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             ))],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("(#0 < 0)".to_owned()),
+            wasi_adapter: Some("is_negative"),
         },
     );
 
@@ -228,6 +240,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             returns: vec![ExternalReturnSlot::fresh(duration_type.clone())],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("Math.abs(#0)".to_owned()),
+            wasi_adapter: Some("abs"),
         },
     );
 
@@ -246,6 +259,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             js_lowering: ExternalJsLowering::InlineExpression(
                 "Math.min(Math.max(#0, #1), #2)".to_owned(),
             ),
+            wasi_adapter: Some("clamp"),
         },
     );
 
@@ -264,6 +278,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             ))],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("(#0 / 1000.0)".to_owned()),
+            wasi_adapter: Some("millis_to_seconds"),
         },
     );
 
@@ -278,6 +293,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             ))],
             error_return_type: None,
             js_lowering: ExternalJsLowering::InlineExpression("#0".to_owned()),
+            wasi_adapter: Some("identity"),
         },
     );
 
@@ -294,6 +310,7 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
             js_lowering: ExternalJsLowering::InlineExpression(
                 "(new Date(#0)).toISOString()".to_owned(),
             ),
+            wasi_adapter: None,
         },
     );
 }
@@ -302,7 +319,10 @@ pub fn register_core_time_package(registry: &mut ExternalPackageRegistry) {
 //  Registration helpers
 // ------------------------
 
-/// Registers an opaque external type with the Handle ABI.
+/// Registers an opaque external type with the F64 ABI.
+///
+/// WHY: every backend carries time values as millisecond numbers, so Wasm can keep them in
+/// `f64` locals instead of boxing them behind a handle.
 fn register_external_time_type(
     registry: &mut ExternalPackageRegistry,
     package_id: ExternalPackageId,
@@ -313,7 +333,7 @@ fn register_external_time_type(
             package_id,
             ExternalTypeSpec {
                 name: name.to_owned(),
-                abi_type: ExternalAbiType::Handle,
+                abi_type: ExternalAbiType::F64,
             },
         )
         .expect("builtin time type registration should not collide")
@@ -338,6 +358,8 @@ struct TimeFunctionSpec {
     returns: Vec<ExternalReturnSlot>,
     error_return_type: Option<ExternalSignatureType>,
     js_lowering: ExternalJsLowering,
+    /// WASI adapter name, or `None` when the function needs a host date library.
+    wasi_adapter: Option<&'static str>,
 }
 
 /// Registers a single external function in the time package.
@@ -356,7 +378,7 @@ fn register_external_time_function(
                 error_return_type: spec.error_return_type,
                 lowerings: ExternalFunctionLowerings {
                     js: Some(spec.js_lowering),
-                    wasm: None,
+                    wasm: spec.wasi_adapter.map(ExternalWasmLowering::WasiAdapter),
                 },
             },
         )
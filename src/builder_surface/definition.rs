@@ -33,7 +33,7 @@ pub struct BuilderSurface {
 }
 
 const BUILTIN_SOURCE_PACKAGES_DIR: &str = "packages";
-const SUPPORTED_PROJECT_CONFIG_VALUES: &[&str] = &["html"];
+const SUPPORTED_PROJECT_CONFIG_VALUES: &[&str] = &["html", "wasi", "js"];
 
 impl BuilderSurface {
     /// Builds a builder surface with mandatory compiler core packages and no source-backed packages.
@@ -90,6 +90,16 @@ impl BuilderSurface {
         );
     }
 
+    /// Exposes the optional core packages the WASI command builder can lower.
+    ///
+    /// WHY: only packages with WASI adapters are visible, so unsupported imports fail at
+    /// resolution instead of at lowering.
+    pub fn expose_wasi_core_packages(&mut self) {
+        crate::builder_surface::core_packages::register_core_time_package(
+            &mut self.binding_packages,
+        );
+    }
+
     pub fn builtin_source_package_root(prefix: &str) -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR"))
             .join(BUILTIN_SOURCE_PACKAGES_DIR)
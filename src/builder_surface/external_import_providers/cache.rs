@@ -7,7 +7,7 @@
 
 use super::provider::{ExternalImportProviderKind, ResolvedExternalImport};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Cache key that uniquely identifies one external source file + provider combination.
 ///
@@ -52,4 +52,11 @@ impl ExternalImportProviderCache {
     pub fn contains_key(&self, key: &ExternalImportCacheKey) -> bool {
         self.entries.contains_key(key)
     }
+
+    /// Canonical source paths of every cached import.
+    pub fn source_paths(&self) -> impl Iterator<Item = &Path> {
+        self.entries
+            .keys()
+            .map(|key| key.canonical_source_path.as_path())
+    }
 }
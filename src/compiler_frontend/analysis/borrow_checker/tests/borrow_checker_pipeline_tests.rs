@@ -157,6 +157,8 @@ fn successful_borrow_report_can_be_stored_on_module() {
             doc_fragments: Vec::new(),
             rendered_path_usages: Vec::new(),
             validated_generic_templates: ValidatedGenericTemplateStore::default(),
+            public_interface: None,
+            public_type_ids_by_origin: Default::default(),
         },
     };
 
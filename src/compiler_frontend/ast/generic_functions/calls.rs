@@ -21,7 +21,8 @@ use crate::compiler_frontend::ast::generic_functions::diagnostics::{
     missing_generic_function_trait_evidence, recursive_generic_function_instantiation,
 };
 use crate::compiler_frontend::ast::generic_functions::{
-    GenericFunctionInstanceKey, GenericFunctionInstantiationRequest, GenericFunctionTemplate,
+    GENERIC_FUNCTION_INSTANCE_PREFIX, GenericFunctionInstanceKey,
+    GenericFunctionInstantiationRequest, GenericFunctionTemplate,
 };
 use crate::compiler_frontend::ast::module_ast::scope_context::ScopeContext;
 use crate::compiler_frontend::ast::statements::fallible_handling::{
@@ -813,7 +814,7 @@ pub(crate) fn generic_function_instance_path(
         .collect::<Vec<_>>()
         .join("_");
     function_path.join_str(
-        &format!("__generic_instance_{argument_suffix}"),
+        &format!("{GENERIC_FUNCTION_INSTANCE_PREFIX}{argument_suffix}"),
         string_table,
     )
 }
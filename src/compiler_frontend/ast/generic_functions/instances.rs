@@ -9,6 +9,11 @@ use crate::compiler_frontend::datatypes::ids::TypeId;
 use crate::compiler_frontend::symbols::interned_path::InternedPath;
 use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
 
+/// Final path component prefix of every concrete generic function instance.
+///
+/// WHY: backends report instances per template, and the template is the instance path's parent.
+pub(crate) const GENERIC_FUNCTION_INSTANCE_PREFIX: &str = "__generic_instance_";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct GenericFunctionInstanceKey {
     pub(crate) function_path: InternedPath,
@@ -22,7 +22,8 @@ pub(crate) use diagnostics::{
     with_generic_instantiation_context,
 };
 pub(crate) use instances::{
-    GenericFunctionInstance, GenericFunctionInstanceKey, GenericFunctionInstantiationRequest,
+    GENERIC_FUNCTION_INSTANCE_PREFIX, GenericFunctionInstance, GenericFunctionInstanceKey,
+    GenericFunctionInstantiationRequest,
 };
 pub(crate) use templates::GenericFunctionTemplate;
 
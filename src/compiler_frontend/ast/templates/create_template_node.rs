@@ -53,6 +53,7 @@ use crate::compiler_frontend::compiler_messages::{
 use crate::compiler_frontend::instrumentation::{
     AstCounter, FrontendCounter, add_ast_counter, increment_frontend_counter,
 };
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::tokenizer::tokens::FileTokens;
 #[cfg(test)]
@@ -103,7 +104,8 @@ impl Template {
         direct_child_wrappers: Vec<TemplateWrapperReference>,
         string_table: &mut StringTable,
     ) -> TemplateConstructionResult {
-        let default_style = default_nested_style_for_source_path(token_stream, string_table);
+        let default_style =
+            default_nested_style_for_source_path(token_stream, context, string_table);
         Self::new_nested_template(
             token_stream,
             context,
@@ -126,7 +128,8 @@ impl Template {
         direct_child_wrappers: Vec<TemplateWrapperReference>,
         string_table: &mut StringTable,
     ) -> ConstRequiredTemplateConstructionResult {
-        let default_style = default_nested_style_for_source_path(token_stream, string_table);
+        let default_style =
+            default_nested_style_for_source_path(token_stream, context, string_table);
         let template = Self::new_nested_template(
             token_stream,
             context,
@@ -239,7 +242,7 @@ impl Template {
         let body_mode = parsed_head.body_mode;
 
         if parsing_mode == TemplateParsingMode::DocComment {
-            apply_doc_comment_defaults(&mut build_state);
+            apply_doc_comment_defaults(&mut build_state, &context.style_directives);
         }
 
         // Stage 2: Parse the template body (strings, nested templates, slots)
@@ -536,13 +539,14 @@ impl Template {
 
 fn default_nested_style_for_source_path(
     token_stream: &FileTokens,
+    context: &ScopeContext,
     string_table: &StringTable,
 ) -> Option<Style> {
     if !is_beandown_content_constant_path(token_stream, string_table) {
         return None;
     }
 
-    Some(markdown_default_style())
+    Some(markdown_default_style(&context.style_directives))
 }
 
 fn is_beandown_content_constant_path(
@@ -562,10 +566,10 @@ fn is_beandown_content_constant_path(
         })
 }
 
-fn markdown_default_style() -> Style {
+fn markdown_default_style(style_directives: &StyleDirectiveRegistry) -> Style {
     let mut style = Style::default();
     style.id = "markdown";
-    style.formatter = Some(markdown_formatter());
+    style.formatter = Some(markdown_formatter(style_directives));
     style.body_whitespace_policy = BodyWhitespacePolicy::StyleDirectiveControlled;
     style
 }
@@ -117,11 +117,13 @@ pub(super) fn render_list_block(
     let mut consumed_lines = 0usize;
 
     while consumed_lines < lines.len() {
-        let line = &lines[consumed_lines];
-        if super::line_is_blank(line) || super::parsing::parse_heading_line(line).is_some() {
+        // The first line is the item that opened this list, so only later lines can end it.
+        if consumed_lines > 0 && super::line_interrupts_block(lines, consumed_lines) {
             break;
         }
 
+        let line = &lines[consumed_lines];
+
         let Some(list_item) = super::parsing::parse_list_item_line(line) else {
             break;
         };
@@ -141,13 +143,12 @@ pub(super) fn render_list_block(
         let mut fragments = vec![MarkdownListItemFragment::Line(list_item.content)];
 
         while consumed_lines < lines.len() {
-            let next_line = &lines[consumed_lines];
-            if super::line_is_blank(next_line)
-                || super::parsing::parse_heading_line(next_line).is_some()
-            {
+            if super::line_interrupts_block(lines, consumed_lines) {
                 break;
             }
 
+            let next_line = &lines[consumed_lines];
+
             if let Some(next_item) = super::parsing::parse_list_item_line(next_line) {
                 if next_item.indent_width <= current_indent {
                     break;
@@ -297,11 +298,76 @@ fn build_list_item_blocks(fragments: &[MarkdownListItemFragment]) -> Vec<Markdow
     blocks
 }
 
+/// Renders a heading with an `id` slugged from its visible text.
+///
+/// Child-template and dynamic anchors contribute nothing to the slug because their content is
+/// sealed; a heading made only of anchors gets no `id`.
 pub(super) fn render_heading_line(
     heading: &ParsedMarkdownHeadingLine,
+    context: &mut super::MarkdownRenderContext<'_>,
 ) -> Vec<FormatterOutputPiece> {
-    let heading_tag = format!("h{}", heading.level);
-    super::inline::render_inline_atoms(&heading.content, Some(heading_tag.as_str()), true)
+    let mut output = MarkdownOutputBuilder::default();
+    let slug = heading_slug(&heading.content);
+    if slug.is_empty() {
+        output.push_raw(&format!("<h{}>", heading.level));
+    } else {
+        output.push_raw(&format!("<h{} id=\"", heading.level));
+        output.push_escaped_text(&context.unique_heading_id(slug));
+        output.push_raw("\">");
+    }
+    output.append_pieces(super::inline::render_inline_atoms(
+        &heading.content,
+        None,
+        false,
+    ));
+    output.push_raw(&format!("</h{}>", heading.level));
+    output.finish()
+}
+
+/// Lowercases letters and digits, turns whitespace runs into single hyphens and drops other
+/// punctuation. Links contribute their label, not their target.
+fn heading_slug(atoms: &[MarkdownInlineAtom]) -> String {
+    let mut slug = String::new();
+    let mut pending_hyphen = false;
+    let mut previous_whitespace = true;
+    let mut index = 0usize;
+
+    while index < atoms.len() {
+        let MarkdownInlineAtom::Char(ch) = atoms[index] else {
+            index += 1;
+            continue;
+        };
+
+        if ch == '@'
+            && previous_whitespace
+            && let Some(link) = super::parsing::try_parse_link_at_atoms(atoms, index)
+        {
+            for label_char in link.label.chars() {
+                push_slug_char(&mut slug, &mut pending_hyphen, label_char);
+            }
+            previous_whitespace = false;
+            index += link.consumed_atoms;
+            continue;
+        }
+
+        push_slug_char(&mut slug, &mut pending_hyphen, ch);
+        previous_whitespace = ch.is_whitespace();
+        index += 1;
+    }
+
+    slug
+}
+
+fn push_slug_char(slug: &mut String, pending_hyphen: &mut bool, ch: char) {
+    if ch.is_alphanumeric() || ch == '_' {
+        if *pending_hyphen && !slug.is_empty() {
+            slug.push('-');
+        }
+        *pending_hyphen = false;
+        slug.extend(ch.to_lowercase());
+    } else if ch.is_whitespace() || ch == '-' {
+        *pending_hyphen = true;
+    }
 }
 
 fn classify_leading_child_template_line(atoms: &[MarkdownInlineAtom]) -> LeadingChildTemplateLine {
@@ -0,0 +1,154 @@
+//! Markdown fenced code blocks.
+//!
+//! WHAT: renders a fenced block as `<pre>` around the output of the registered `$code`
+//! formatter for the fence language, or around escaped text when no `$code` is registered or it
+//! rejects the language.
+//! WHY: `$code` belongs to the project builder, so `$md` reaches it through the style directive
+//! registry instead of depending on a particular highlighter. Anchors inside the fence are passed
+//! to `$code` as opaque pieces, exactly as a `[$code: ...]` template body would pass them.
+
+use super::output::MarkdownOutputBuilder;
+use super::{MarkdownInlineAtom, MarkdownLine, MarkdownRenderContext, ParsedMarkdownCodeFence};
+use crate::compiler_frontend::ast::templates::formatter_contract::{
+    FormatterInput, FormatterInputPiece, FormatterOutputPiece, FormatterTextPiece,
+};
+use crate::compiler_frontend::compiler_errors::CompilerMessages;
+use crate::compiler_frontend::style_directives::StyleDirectiveArgumentValue;
+use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
+
+/// Renders the fence opening at `lines[0]` and returns the output with the consumed line count.
+///
+/// An unclosed fence runs to the end of the template body.
+pub(super) fn render_fenced_code_block(
+    lines: &[MarkdownLine],
+    fence: &ParsedMarkdownCodeFence,
+    context: &mut MarkdownRenderContext<'_>,
+) -> Result<(Vec<FormatterOutputPiece>, usize), CompilerMessages> {
+    let mut body = Vec::new();
+    let mut consumed_lines = 1usize;
+
+    while let Some(line) = lines.get(consumed_lines) {
+        consumed_lines += 1;
+        if super::parsing::is_code_fence_close(line, fence) {
+            break;
+        }
+
+        if !body.is_empty() {
+            body.push(MarkdownInlineAtom::Char('\n'));
+        }
+        body.extend_from_slice(strip_fence_indentation(&line.atoms, fence.indent_width));
+    }
+
+    let mut output = MarkdownOutputBuilder::default();
+    output.push_raw("<pre>");
+    match highlight_with_code_directive(&body, &fence.language, context)? {
+        Some(highlighted) => output.append_pieces(highlighted),
+        None => render_plain_code(&mut output, &body, &fence.language),
+    }
+    output.push_raw("</pre>");
+
+    Ok((output.finish(), consumed_lines))
+}
+
+/// Removes up to `indent_width` leading spaces so body lines keep only their own indentation.
+fn strip_fence_indentation(
+    atoms: &[MarkdownInlineAtom],
+    indent_width: usize,
+) -> &[MarkdownInlineAtom] {
+    let mut start = 0usize;
+    while start < indent_width && matches!(atoms.get(start), Some(MarkdownInlineAtom::Char(' '))) {
+        start += 1;
+    }
+    &atoms[start..]
+}
+
+/// Formats the body with the registered `$code` directive.
+///
+/// Returns `None` when no `$code` is registered or its factory rejects the language, so the
+/// block still renders as plain code.
+fn highlight_with_code_directive(
+    body: &[MarkdownInlineAtom],
+    language: &str,
+    context: &mut MarkdownRenderContext<'_>,
+) -> Result<Option<Vec<FormatterOutputPiece>>, CompilerMessages> {
+    let Some(factory) = context.style_directives.formatter_factory("code") else {
+        return Ok(None);
+    };
+
+    let argument =
+        (!language.is_empty()).then(|| StyleDirectiveArgumentValue::String(language.to_owned()));
+    let Ok(code_formatter) = factory(argument.as_ref(), context.style_directives) else {
+        return Ok(None);
+    };
+
+    let input = FormatterInput {
+        pieces: formatter_input_pieces(body, context),
+    };
+    let result = code_formatter
+        .formatter
+        .format(input, context.string_table)?;
+    context.warnings.extend(result.warnings);
+
+    Ok(Some(result.output.pieces))
+}
+
+/// Groups body characters into text pieces between the anchors they surround.
+fn formatter_input_pieces(
+    body: &[MarkdownInlineAtom],
+    context: &mut MarkdownRenderContext<'_>,
+) -> Vec<FormatterInputPiece> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+
+    for atom in body {
+        match atom {
+            MarkdownInlineAtom::Char(ch) => text.push(*ch),
+            MarkdownInlineAtom::Opaque(anchor) => {
+                push_text_piece(&mut pieces, &mut text, context);
+                pieces.push(FormatterInputPiece::Opaque(*anchor));
+            }
+        }
+    }
+    push_text_piece(&mut pieces, &mut text, context);
+
+    pieces
+}
+
+fn push_text_piece(
+    pieces: &mut Vec<FormatterInputPiece>,
+    text: &mut String,
+    context: &mut MarkdownRenderContext<'_>,
+) {
+    if text.is_empty() {
+        return;
+    }
+
+    pieces.push(FormatterInputPiece::Text(FormatterTextPiece {
+        text: context.string_table.intern(&std::mem::take(text)),
+        // Markdown lines no longer carry source positions once split into atoms.
+        location: SourceLocation::default(),
+    }));
+}
+
+fn render_plain_code(
+    output: &mut MarkdownOutputBuilder,
+    body: &[MarkdownInlineAtom],
+    language: &str,
+) {
+    if language.is_empty() {
+        output.push_raw("<code>");
+    } else {
+        output.push_raw("<code class=\"language-");
+        output.push_escaped_text(language);
+        output.push_raw("\">");
+    }
+
+    for atom in body {
+        match atom {
+            MarkdownInlineAtom::Char(ch) => output.push_escaped_char(*ch),
+            MarkdownInlineAtom::Opaque(anchor) => output.push_opaque(*anchor),
+        }
+    }
+
+    output.push_raw("</code>");
+}
@@ -3,6 +3,9 @@
 //! WHAT:
 //! - Converts template body text into a narrow, deterministic HTML-flavoured markdown output.
 //! - Supports unordered and ordered list blocks with indentation-based nesting.
+//! - Supports GFM-style pipe tables, blockquotes, horizontal rules and fenced code blocks.
+//!   Fenced code is highlighted by the project's `$code` directive when one is registered.
+//! - Gives every heading a slugged `id` so pages can link to sections.
 //! - Preserves child-template and dynamic-expression anchors as opaque inline/block boundaries.
 //!
 //! WHY:
@@ -19,8 +22,12 @@ use crate::compiler_frontend::ast::templates::template::{
     Formatter, FormatterResult, TemplateFormatter,
 };
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
-use crate::compiler_frontend::style_directives::StyleDirectiveArgumentValue;
+use crate::compiler_frontend::compiler_messages::CompilerDiagnostic;
+use crate::compiler_frontend::style_directives::{
+    StyleDirectiveArgumentValue, StyleDirectiveRegistry,
+};
 use crate::compiler_frontend::symbols::string_interning::StringTable;
+use rustc_hash::FxHashSet;
 use std::sync::Arc;
 
 mod inline_code;
@@ -30,9 +37,16 @@ mod types;
 use output::MarkdownOutputBuilder;
 use types::*;
 mod blocks;
+mod fenced_code;
 mod inline;
 mod parsing;
-pub struct MarkdownTemplateFormatter;
+mod tables;
+
+pub struct MarkdownTemplateFormatter {
+    /// Directives visible where the `$md` template was parsed. Fenced code blocks are
+    /// formatted by the `$code` directive found here.
+    style_directives: StyleDirectiveRegistry,
+}
 
 impl TemplateFormatter for MarkdownTemplateFormatter {
     fn format(
@@ -41,32 +55,72 @@ impl TemplateFormatter for MarkdownTemplateFormatter {
         string_table: &mut StringTable,
     ) -> Result<FormatterResult, CompilerMessages> {
         let lines = split_formatter_input_into_lines(input, string_table);
-        let pieces = render_markdown_stream(&lines, "p");
+        let mut context = MarkdownRenderContext::new(&self.style_directives, string_table);
+        let pieces = render_markdown_stream(&lines, "p", &mut context)?;
 
         Ok(FormatterResult {
             output: FormatterOutput { pieces },
-            warnings: Vec::new(),
+            warnings: context.warnings,
         })
     }
 }
 
-pub fn markdown_formatter() -> Formatter {
+pub fn markdown_formatter(style_directives: &StyleDirectiveRegistry) -> Formatter {
     Formatter {
         // `$md` opts into the shared default body dedent/trim pass explicitly.
         pre_format_whitespace_passes: vec![TemplateWhitespacePassProfile::default_template_body()],
-        formatter: Arc::new(MarkdownTemplateFormatter),
+        formatter: Arc::new(MarkdownTemplateFormatter {
+            style_directives: style_directives.clone(),
+        }),
         post_format_whitespace_passes: Vec::new(),
     }
 }
 
 pub(crate) fn markdown_formatter_factory(
     argument: Option<&StyleDirectiveArgumentValue>,
+    style_directives: &StyleDirectiveRegistry,
 ) -> Result<Formatter, String> {
     if argument.is_some() {
         return Err("'$md' does not accept arguments.".to_string());
     }
 
-    Ok(markdown_formatter())
+    Ok(markdown_formatter(style_directives))
+}
+
+/// State shared by every block rendered during one `$md` format call.
+struct MarkdownRenderContext<'a> {
+    style_directives: &'a StyleDirectiveRegistry,
+    string_table: &'a mut StringTable,
+    /// Heading ids already issued in this template, so repeated titles get numbered suffixes.
+    /// Repeats across templates are numbered per page by the HTML builder's page index pass.
+    heading_ids: FxHashSet<String>,
+    /// Warnings raised by formatters that fenced code blocks delegate to.
+    warnings: Vec<CompilerDiagnostic>,
+}
+
+impl<'a> MarkdownRenderContext<'a> {
+    fn new(
+        style_directives: &'a StyleDirectiveRegistry,
+        string_table: &'a mut StringTable,
+    ) -> Self {
+        Self {
+            style_directives,
+            string_table,
+            heading_ids: FxHashSet::default(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Returns `slug`, or `slug-1`, `slug-2`, … when earlier headings already took it.
+    fn unique_heading_id(&mut self, slug: String) -> String {
+        let mut candidate = slug.clone();
+        let mut suffix = 0usize;
+        while !self.heading_ids.insert(candidate.clone()) {
+            suffix += 1;
+            candidate = format!("{slug}-{suffix}");
+        }
+        candidate
+    }
 }
 
 /// Converts formatter input into newline-delimited markdown lines without flattening anchors.
@@ -119,7 +173,14 @@ fn push_atom_to_current_line(lines: &mut Vec<MarkdownLine>, atom: MarkdownInline
 }
 
 /// Renders the full markdown line stream while keeping block/list state across anchors.
-fn render_markdown_stream(lines: &[MarkdownLine], default_tag: &str) -> Vec<FormatterOutputPiece> {
+///
+/// Blockquotes render their stripped lines through this same function, so every block kind
+/// nests inside a quote.
+fn render_markdown_stream(
+    lines: &[MarkdownLine],
+    default_tag: &str,
+    context: &mut MarkdownRenderContext<'_>,
+) -> Result<Vec<FormatterOutputPiece>, CompilerMessages> {
     let mut output = MarkdownOutputBuilder::default();
     let mut line_index = 0usize;
     let mut has_rendered_block = false;
@@ -143,6 +204,39 @@ fn render_markdown_stream(lines: &[MarkdownLine], default_tag: &str) -> Vec<Form
             continue;
         }
 
+        if let Some(fence) = parsing::parse_code_fence_open(&lines[line_index]) {
+            let (rendered, consumed_lines) =
+                fenced_code::render_fenced_code_block(&lines[line_index..], &fence, context)?;
+            output.append_pieces(rendered);
+            has_rendered_block = true;
+            line_index += consumed_lines;
+            continue;
+        }
+
+        if parsing::is_horizontal_rule_line(&lines[line_index]) {
+            output.push_raw("<hr>");
+            has_rendered_block = true;
+            line_index += 1;
+            continue;
+        }
+
+        if parsing::strip_blockquote_marker(&lines[line_index]).is_some() {
+            let mut quoted_lines = Vec::new();
+            while let Some(quoted) = lines
+                .get(line_index)
+                .and_then(parsing::strip_blockquote_marker)
+            {
+                quoted_lines.push(quoted);
+                line_index += 1;
+            }
+
+            output.push_raw("<blockquote>");
+            output.append_pieces(render_markdown_stream(&quoted_lines, default_tag, context)?);
+            output.push_raw("</blockquote>");
+            has_rendered_block = true;
+            continue;
+        }
+
         if parsing::parse_list_item_line(&lines[line_index]).is_some() {
             let (rendered, consumed_lines) =
                 blocks::render_list_block(&lines[line_index..], default_tag);
@@ -157,17 +251,23 @@ fn render_markdown_stream(lines: &[MarkdownLine], default_tag: &str) -> Vec<Form
         }
 
         if let Some(heading) = parsing::parse_heading_line(&lines[line_index]) {
-            output.append_pieces(blocks::render_heading_line(&heading));
+            output.append_pieces(blocks::render_heading_line(&heading, context));
             has_rendered_block = true;
             line_index += 1;
             continue;
         }
 
+        if let Some((rendered, consumed_lines)) = tables::render_table(&lines[line_index..]) {
+            output.append_pieces(rendered);
+            has_rendered_block = true;
+            line_index += consumed_lines;
+            continue;
+        }
+
         let region_start = line_index;
         while line_index < lines.len() {
-            if line_is_blank(&lines[line_index])
+            if line_interrupts_block(lines, line_index)
                 || parsing::parse_list_item_line(&lines[line_index]).is_some()
-                || parsing::parse_heading_line(&lines[line_index]).is_some()
             {
                 break;
             }
@@ -181,7 +281,18 @@ fn render_markdown_stream(lines: &[MarkdownLine], default_tag: &str) -> Vec<Form
         has_rendered_block = true;
     }
 
-    output.finish()
+    Ok(output.finish())
+}
+
+/// Whether `lines[index]` ends a running paragraph or list and starts a different block.
+fn line_interrupts_block(lines: &[MarkdownLine], index: usize) -> bool {
+    let line = &lines[index];
+    line_is_blank(line)
+        || parsing::parse_heading_line(line).is_some()
+        || parsing::parse_code_fence_open(line).is_some()
+        || parsing::is_horizontal_rule_line(line)
+        || parsing::strip_blockquote_marker(line).is_some()
+        || tables::table_starts_at(&lines[index..])
 }
 
 fn append_break_tags(output: &mut MarkdownOutputBuilder, break_count: usize) {
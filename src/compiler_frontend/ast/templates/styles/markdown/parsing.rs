@@ -1,12 +1,13 @@
-//! Markdown parsing helpers: list items, headings, links, and atom utilities.
+//! Markdown parsing helpers: list items, headings, fences, rules, quotes, links, and atom
+//! utilities.
 //!
 //! WHAT: parses markdown line structures and inline link syntax from atom streams.
 //! WHY: separating parsing from rendering keeps the grammar rules in one place
 //!      and makes the renderer's control flow easier to follow.
 
 use super::{
-    MarkdownInlineAtom, MarkdownLine, MarkdownListKind, ParsedMarkdownHeadingLine,
-    ParsedMarkdownLink, ParsedMarkdownListItemLine,
+    MarkdownInlineAtom, MarkdownLine, MarkdownListKind, ParsedMarkdownCodeFence,
+    ParsedMarkdownHeadingLine, ParsedMarkdownLink, ParsedMarkdownListItemLine,
 };
 
 pub(super) fn parse_heading_line(line: &MarkdownLine) -> Option<ParsedMarkdownHeadingLine> {
@@ -127,6 +128,99 @@ fn parse_ordered_list_item(
     })
 }
 
+/// Parses an opening fence: three or more backticks or tildes, then an optional language.
+///
+/// A backtick fence whose info text contains a backtick is inline code, not a fence. Anchors in
+/// the info text also reject the line, because a language name must be literal.
+pub(super) fn parse_code_fence_open(line: &MarkdownLine) -> Option<ParsedMarkdownCodeFence> {
+    let (indent_width, start) = consume_line_indentation(&line.atoms);
+    let (marker, length) = code_fence_run(&line.atoms, start)?;
+
+    let mut info = String::new();
+    for atom in &line.atoms[start + length..] {
+        match atom {
+            MarkdownInlineAtom::Char('`') if marker == '`' => return None,
+            MarkdownInlineAtom::Char(ch) => info.push(*ch),
+            MarkdownInlineAtom::Opaque(_) => return None,
+        }
+    }
+
+    Some(ParsedMarkdownCodeFence {
+        indent_width,
+        marker,
+        length,
+        language: info
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_owned(),
+    })
+}
+
+/// Whether `line` closes `fence`: a run of the same marker at least as long, and nothing else.
+pub(super) fn is_code_fence_close(line: &MarkdownLine, fence: &ParsedMarkdownCodeFence) -> bool {
+    let start = skip_leading_horizontal_whitespace(&line.atoms);
+    let Some((marker, length)) = code_fence_run(&line.atoms, start) else {
+        return false;
+    };
+
+    marker == fence.marker
+        && length >= fence.length
+        && line.atoms[start + length..]
+            .iter()
+            .all(|atom| matches!(atom, MarkdownInlineAtom::Char(ch) if ch.is_whitespace()))
+}
+
+fn code_fence_run(atoms: &[MarkdownInlineAtom], start: usize) -> Option<(char, usize)> {
+    let marker = super::types::atom_char(atoms, start)?;
+    if !matches!(marker, '`' | '~') {
+        return None;
+    }
+
+    let mut length = 0usize;
+    while super::types::atom_char(atoms, start + length) == Some(marker) {
+        length += 1;
+    }
+
+    (length >= 3).then_some((marker, length))
+}
+
+/// Whether `line` is a horizontal rule: three or more `-`, `*` or `_`, optionally spaced.
+pub(super) fn is_horizontal_rule_line(line: &MarkdownLine) -> bool {
+    let mut marker = None;
+    let mut count = 0usize;
+
+    for atom in &line.atoms {
+        match atom {
+            MarkdownInlineAtom::Char(' ' | '\t') => {}
+            MarkdownInlineAtom::Char(ch @ ('-' | '*' | '_')) if marker.is_none_or(|m| m == *ch) => {
+                marker = Some(*ch);
+                count += 1;
+            }
+            _ => return false,
+        }
+    }
+
+    count >= 3
+}
+
+/// Returns the quoted content of a `>` line without the marker and one following space.
+pub(super) fn strip_blockquote_marker(line: &MarkdownLine) -> Option<MarkdownLine> {
+    let start = skip_leading_horizontal_whitespace(&line.atoms);
+    if super::types::atom_char(&line.atoms, start)? != '>' {
+        return None;
+    }
+
+    let mut content_start = start + 1;
+    if super::types::atom_char(&line.atoms, content_start) == Some(' ') {
+        content_start += 1;
+    }
+
+    Some(MarkdownLine {
+        atoms: line.atoms[content_start..].to_vec(),
+    })
+}
+
 pub(super) fn join_lines_with_spaces(lines: &[Vec<MarkdownInlineAtom>]) -> Vec<MarkdownInlineAtom> {
     let mut joined = Vec::new();
 
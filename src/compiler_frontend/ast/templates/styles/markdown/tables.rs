@@ -0,0 +1,158 @@
+//! Markdown GFM-style pipe tables.
+//!
+//! WHAT: a header row, a delimiter row of `---` cells (with optional `:` alignment marks) and
+//! body rows until a blank line or another block. Cells split on `|`; `\|` is a literal pipe.
+//! WHY: docs tables previously needed hand-written `$html` helpers. Cells render through the
+//! normal inline renderer, so anchors inside a cell stay opaque.
+
+use super::output::MarkdownOutputBuilder;
+use super::{MarkdownInlineAtom, MarkdownLine, MarkdownTableAlignment};
+use crate::compiler_frontend::ast::templates::formatter_contract::FormatterOutputPiece;
+
+/// Whether `lines` starts with a table header row followed by a matching delimiter row.
+pub(super) fn table_starts_at(lines: &[MarkdownLine]) -> bool {
+    parse_table_head(lines).is_some()
+}
+
+/// Renders the table starting at `lines[0]` and returns it with the consumed line count.
+pub(super) fn render_table(lines: &[MarkdownLine]) -> Option<(Vec<FormatterOutputPiece>, usize)> {
+    let (header, alignments) = parse_table_head(lines)?;
+
+    let mut consumed_lines = 2usize;
+    let mut body_rows = Vec::new();
+    while consumed_lines < lines.len()
+        && !super::line_interrupts_block(lines, consumed_lines)
+        && super::parsing::parse_list_item_line(&lines[consumed_lines]).is_none()
+    {
+        body_rows.push(split_table_row(&lines[consumed_lines].atoms));
+        consumed_lines += 1;
+    }
+
+    let mut output = MarkdownOutputBuilder::default();
+    output.push_raw("<table><thead>");
+    render_table_row(&mut output, &header, &alignments, "th");
+    output.push_raw("</thead>");
+
+    if !body_rows.is_empty() {
+        output.push_raw("<tbody>");
+        for row in &body_rows {
+            render_table_row(&mut output, row, &alignments, "td");
+        }
+        output.push_raw("</tbody>");
+    }
+
+    output.push_raw("</table>");
+    Some((output.finish(), consumed_lines))
+}
+
+/// Parses the header and delimiter rows. The header must contain a pipe and have as many cells
+/// as the delimiter row, so a `---` rule under ordinary text never reads as a table.
+fn parse_table_head(
+    lines: &[MarkdownLine],
+) -> Option<(Vec<Vec<MarkdownInlineAtom>>, Vec<MarkdownTableAlignment>)> {
+    let [header_line, delimiter_line, ..] = lines else {
+        return None;
+    };
+
+    if !header_line
+        .atoms
+        .iter()
+        .any(|atom| matches!(atom, MarkdownInlineAtom::Char('|')))
+    {
+        return None;
+    }
+
+    let alignments = split_table_row(&delimiter_line.atoms)
+        .iter()
+        .map(|cell| parse_delimiter_cell(cell))
+        .collect::<Option<Vec<_>>>()?;
+
+    let header = split_table_row(&header_line.atoms);
+    (header.len() == alignments.len()).then_some((header, alignments))
+}
+
+fn parse_delimiter_cell(cell: &[MarkdownInlineAtom]) -> Option<MarkdownTableAlignment> {
+    let text = cell
+        .iter()
+        .map(|atom| match atom {
+            MarkdownInlineAtom::Char(ch) => Some(*ch),
+            MarkdownInlineAtom::Opaque(_) => None,
+        })
+        .collect::<Option<String>>()?;
+
+    let left = text.starts_with(':');
+    let right = text.ends_with(':') && text.len() > 1;
+    let dashes = text.trim_start_matches(':').trim_end_matches(':');
+    if dashes.is_empty() || !dashes.chars().all(|ch| ch == '-') {
+        return None;
+    }
+
+    Some(match (left, right) {
+        (true, true) => MarkdownTableAlignment::Center,
+        (true, false) => MarkdownTableAlignment::Left,
+        (false, true) => MarkdownTableAlignment::Right,
+        (false, false) => MarkdownTableAlignment::None,
+    })
+}
+
+/// Splits a row into trimmed cells, ignoring one leading and one trailing pipe.
+fn split_table_row(atoms: &[MarkdownInlineAtom]) -> Vec<Vec<MarkdownInlineAtom>> {
+    let mut row = super::parsing::trim_atoms(atoms);
+    if matches!(row.first(), Some(MarkdownInlineAtom::Char('|'))) {
+        row.remove(0);
+    }
+    if matches!(row.last(), Some(MarkdownInlineAtom::Char('|')))
+        && !matches!(
+            row.iter().rev().nth(1),
+            Some(MarkdownInlineAtom::Char('\\'))
+        )
+    {
+        row.pop();
+    }
+
+    let mut cells = Vec::new();
+    let mut cell = Vec::new();
+    let mut index = 0usize;
+    while index < row.len() {
+        match (row[index], row.get(index + 1)) {
+            (MarkdownInlineAtom::Char('\\'), Some(MarkdownInlineAtom::Char('|'))) => {
+                cell.push(MarkdownInlineAtom::Char('|'));
+                index += 2;
+                continue;
+            }
+            (MarkdownInlineAtom::Char('|'), _) => {
+                cells.push(super::parsing::trim_atoms(&std::mem::take(&mut cell)));
+            }
+            (atom, _) => cell.push(atom),
+        }
+        index += 1;
+    }
+    cells.push(super::parsing::trim_atoms(&cell));
+
+    cells
+}
+
+/// Renders one row with exactly one cell per column: short rows are padded with empty cells and
+/// extra cells are dropped.
+fn render_table_row(
+    output: &mut MarkdownOutputBuilder,
+    cells: &[Vec<MarkdownInlineAtom>],
+    alignments: &[MarkdownTableAlignment],
+    cell_tag: &str,
+) {
+    output.push_raw("<tr>");
+    for (column, alignment) in alignments.iter().enumerate() {
+        let style = match alignment {
+            MarkdownTableAlignment::None => "",
+            MarkdownTableAlignment::Left => " style=\"text-align: left\"",
+            MarkdownTableAlignment::Center => " style=\"text-align: center\"",
+            MarkdownTableAlignment::Right => " style=\"text-align: right\"",
+        };
+        output.push_raw(&format!("<{cell_tag}{style}>"));
+        if let Some(cell) = cells.get(column) {
+            output.append_pieces(super::inline::render_inline_atoms(cell, None, false));
+        }
+        output.push_raw(&format!("</{cell_tag}>"));
+    }
+    output.push_raw("</tr>");
+}
@@ -67,6 +67,24 @@ pub(super) struct ParsedMarkdownHeadingLine {
     pub(super) content: Vec<MarkdownInlineAtom>,
 }
 
+#[derive(Debug)]
+pub(super) struct ParsedMarkdownCodeFence {
+    /// Opening fence indentation, removed from each body line.
+    pub(super) indent_width: usize,
+    pub(super) marker: char,
+    pub(super) length: usize,
+    /// First word of the info text, or empty when the fence names no language.
+    pub(super) language: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MarkdownTableAlignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
 #[derive(Debug, Clone)]
 pub(super) enum MarkdownListItemFragment {
     Line(Vec<MarkdownInlineAtom>),
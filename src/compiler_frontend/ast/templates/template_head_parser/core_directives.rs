@@ -23,7 +23,9 @@ use crate::compiler_frontend::ast::templates::template_build_state::TemplateBuil
 use crate::compiler_frontend::ast::type_interner::AstTypeInterner;
 use crate::compiler_frontend::compiler_errors::{CompilerError, ErrorType};
 use crate::compiler_frontend::compiler_messages::CompilerDiagnostic;
-use crate::compiler_frontend::style_directives::{CoreStyleDirectiveKind, StyleDirectiveKind};
+use crate::compiler_frontend::style_directives::{
+    CoreStyleDirectiveKind, StyleDirectiveKind, StyleDirectiveRegistry,
+};
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::tokenizer::tokens::FileTokens;
 
@@ -116,7 +118,7 @@ pub(super) fn parse_core_style_directive(
         CoreStyleDirectiveKind::Doc => {
             let doc_name = string_table.intern("doc");
             reject_unexpected_directive_arguments(doc_name, token_stream)?;
-            apply_doc_comment_defaults(build_state);
+            apply_doc_comment_defaults(build_state, &context.style_directives);
         }
 
         CoreStyleDirectiveKind::Slot | CoreStyleDirectiveKind::Insert => {
@@ -136,20 +138,26 @@ pub(super) fn parse_core_style_directive(
     Ok(())
 }
 
-pub(crate) fn apply_doc_comment_defaults(build_state: &mut TemplateBuildState) {
+pub(crate) fn apply_doc_comment_defaults(
+    build_state: &mut TemplateBuildState,
+    style_directives: &StyleDirectiveRegistry,
+) {
     build_state.kind = TemplateType::Comment(CommentDirectiveKind::Doc);
     build_state.style = Style::default();
 
     // Doc comments use Markdown formatting with balanced bracket escaping.
     // Nested child templates are suppressed — `[...]` brackets in the body are
     // treated as literal text.
-    apply_markdown_style(build_state);
+    apply_markdown_style(build_state, style_directives);
     build_state.style.suppress_child_templates = true;
 }
 
-fn apply_markdown_style(build_state: &mut TemplateBuildState) {
+fn apply_markdown_style(
+    build_state: &mut TemplateBuildState,
+    style_directives: &StyleDirectiveRegistry,
+) {
     build_state.style.id = "markdown";
-    build_state.style.formatter = Some(markdown_formatter());
+    build_state.style.formatter = Some(markdown_formatter(style_directives));
 }
 
 pub(super) fn mark_template_body_whitespace_style_controlled(build_state: &mut TemplateBuildState) {
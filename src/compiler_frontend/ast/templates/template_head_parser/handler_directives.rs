@@ -56,13 +56,14 @@ pub(super) fn apply_handler_style_directive(
     apply_style_directive_effects(build_state, handler_spec.effects);
 
     if let Some(factory) = handler_spec.formatter_factory {
-        let formatter = factory(parsed_argument.value.as_ref()).map_err(|_message| {
-            CompilerDiagnostic::invalid_template_directive(
-                Some(string_table.intern(directive_name)),
-                InvalidTemplateDirectiveReason::InvalidArgument,
-                parsed_argument.error_location,
-            )
-        })?;
+        let formatter = factory(parsed_argument.value.as_ref(), &context.style_directives)
+            .map_err(|_message| {
+                CompilerDiagnostic::invalid_template_directive(
+                    Some(string_table.intern(directive_name)),
+                    InvalidTemplateDirectiveReason::InvalidArgument,
+                    parsed_argument.error_location,
+                )
+            })?;
 
         build_state.style.formatter = Some(formatter.clone());
     }
@@ -530,7 +530,11 @@ fn doc_templates_are_markdown_formatted_by_default() {
     ));
 
     let folded = fold_template_in_context(&template, &context, &mut string_table);
-    assert!(string_table.resolve(folded).contains("<h1>Heading</h1>"));
+    assert!(
+        string_table
+            .resolve(folded)
+            .contains("<h1 id=\"heading\">Heading</h1>")
+    );
 }
 
 #[test]
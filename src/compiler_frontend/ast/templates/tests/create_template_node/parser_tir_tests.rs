@@ -24,6 +24,7 @@ use crate::compiler_frontend::ast::{ContextKind, ScopeContext, TopLevelDeclarati
 use crate::compiler_frontend::datatypes::datatype::DataType;
 use crate::compiler_frontend::datatypes::ids::builtin_type_ids;
 use crate::compiler_frontend::external_packages::ExternalPackageRegistry;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
 use crate::compiler_frontend::symbols::interned_path::InternedPath;
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::tokenizer::tokens::{CharPosition, SourceLocation};
@@ -1159,7 +1160,7 @@ fn pure_direct_dynamic_formatter_template_records_formatted_tir_phase() {
         &context,
         TemplateType::String,
         Style {
-            formatter: Some(markdown_formatter()),
+            formatter: Some(markdown_formatter(&StyleDirectiveRegistry::built_ins())),
             ..Style::default()
         },
         location.clone(),
@@ -1239,7 +1240,7 @@ fn reactive_body_segment_records_formatted_tir_phase() {
         &context,
         TemplateType::String,
         Style {
-            formatter: Some(markdown_formatter()),
+            formatter: Some(markdown_formatter(&StyleDirectiveRegistry::built_ins())),
             ..Style::default()
         },
         location.clone(),
@@ -1335,7 +1336,7 @@ fn reactive_literal_text_segment_records_formatted_tir_phase() {
         &context,
         TemplateType::String,
         Style {
-            formatter: Some(markdown_formatter()),
+            formatter: Some(markdown_formatter(&StyleDirectiveRegistry::built_ins())),
             ..Style::default()
         },
         location.clone(),
@@ -1,13 +1,15 @@
-use super::MarkdownTemplateFormatter;
-use super::{MarkdownInlineAtom, MarkdownLine};
+use super::{MarkdownInlineAtom, MarkdownLine, MarkdownRenderContext};
 use crate::compiler_frontend::ast::templates::formatter_contract::{
     FormatterAnchorId, FormatterInput, FormatterInputPiece, FormatterOpaqueKind,
     FormatterOpaquePiece, FormatterOutputPiece, FormatterTextPiece,
 };
-use crate::compiler_frontend::ast::templates::styles::markdown::render_markdown_stream;
-use crate::compiler_frontend::ast::templates::template::TemplateFormatter;
+use crate::compiler_frontend::ast::templates::styles::markdown::{
+    markdown_formatter, render_markdown_stream,
+};
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
+use crate::projects::html_project::style_directives::html_project_style_directives;
 
 fn child_anchor(id: usize) -> FormatterOpaquePiece {
     FormatterOpaquePiece {
@@ -50,8 +52,19 @@ fn split_text_into_lines(content: &str) -> Vec<MarkdownLine> {
 }
 
 fn to_markdown(content: &str, default_tag: &str) -> String {
+    to_markdown_with_directives(content, default_tag, &StyleDirectiveRegistry::built_ins())
+}
+
+fn to_markdown_with_directives(
+    content: &str,
+    default_tag: &str,
+    style_directives: &StyleDirectiveRegistry,
+) -> String {
     let lines = split_text_into_lines(content);
-    let pieces = render_markdown_stream(&lines, default_tag);
+    let mut string_table = StringTable::new();
+    let mut context = MarkdownRenderContext::new(style_directives, &mut string_table);
+    let pieces = render_markdown_stream(&lines, default_tag, &mut context)
+        .expect("markdown rendering should succeed");
 
     pieces
         .into_iter()
@@ -77,7 +90,8 @@ fn markdown_formatter_output_from_text_and_anchors(
         })
         .collect();
 
-    MarkdownTemplateFormatter
+    markdown_formatter(&StyleDirectiveRegistry::built_ins())
+        .formatter
         .format(
             FormatterInput {
                 pieces: input_pieces,
@@ -177,7 +191,7 @@ fn malformed_candidate_keeps_literal_at_symbol() {
 #[test]
 fn link_parsing_works_inside_heading_and_emphasis() {
     let heading = to_markdown("\n# @/docs (Docs)\n", "p");
-    assert_eq!(heading, "<h1><a href=\"/docs\">Docs</a></h1>");
+    assert_eq!(heading, "<h1 id=\"docs\"><a href=\"/docs\">Docs</a></h1>");
 
     let emphasis = to_markdown("\n*@/docs (Docs)*\n", "p");
     assert_eq!(emphasis, "<p><em><a href=\"/docs\">Docs</a></em></p>");
@@ -197,7 +211,7 @@ fn escapes_html_characters_inside_heading_and_emphasis_content() {
     let heading = to_markdown("\n# <h1> & \"q\" 'x'\n", "p");
     assert_eq!(
         heading,
-        "<h1>&lt;h1&gt; &amp; &quot;q&quot; &#39;x&#39;</h1>"
+        "<h1 id=\"h1-q-x\">&lt;h1&gt; &amp; &quot;q&quot; &#39;x&#39;</h1>"
     );
 
     let emphasis = to_markdown("\n*<tag>&\"'*\n", "p");
@@ -293,7 +307,7 @@ fn heading_line_breaks_out_of_list_without_blank_line() {
 
     assert_eq!(
         rendered,
-        "<ul><li>first line</li></ul><h2>Heading</h2><p>plain paragraph</p>"
+        "<ul><li>first line</li></ul><h2 id=\"heading\">Heading</h2><p>plain paragraph</p>"
     );
 }
 
@@ -523,7 +537,7 @@ fn code_spans_inside_active_emphasis() {
 #[test]
 fn inline_code_in_headings() {
     let rendered = to_markdown("# `Title`", "p");
-    assert_eq!(rendered, "<h1><code>Title</code></h1>");
+    assert_eq!(rendered, "<h1 id=\"title\"><code>Title</code></h1>");
 }
 
 #[test]
@@ -586,3 +600,160 @@ fn code_spans_do_not_cross_soft_line_boundaries() {
     let rendered = to_markdown("`foo\nbar`", "p");
     assert_eq!(rendered, "<p>`foo bar`</p>");
 }
+
+#[test]
+fn headings_get_slugged_ids_with_numbered_repeats() {
+    let rendered = to_markdown(
+        "# Getting *Started*!\n## Getting Started\n### @/docs (Read the Docs)",
+        "p",
+    );
+    assert_eq!(
+        rendered,
+        "<h1 id=\"getting-started\">Getting <em>Started</em>!</h1>\
+         <h2 id=\"getting-started-1\">Getting Started</h2>\
+         <h3 id=\"read-the-docs\"><a href=\"/docs\">Read the Docs</a></h3>"
+    );
+}
+
+#[test]
+fn heading_anchors_stay_opaque_and_do_not_feed_the_slug() {
+    let only_anchor = markdown_formatter_output_from_text_and_anchors(&[
+        (Some("# "), None),
+        (None, Some(dynamic_anchor(1))),
+    ]);
+    assert_eq!(only_anchor, "<h1>{dynamic:1}</h1>");
+
+    let text_and_anchor = markdown_formatter_output_from_text_and_anchors(&[
+        (Some("## Hello "), None),
+        (None, Some(child_anchor(2))),
+    ]);
+    assert_eq!(text_and_anchor, "<h2 id=\"hello\">Hello {child:2}</h2>");
+}
+
+#[test]
+fn pipe_tables_render_header_alignment_and_body_rows() {
+    let rendered = to_markdown(
+        "| Name | Count | Note |\n|:-----|:----:|----:|\n| `a\\|b` | 2 |\n| *c* | 3 | x | extra |",
+        "p",
+    );
+    assert_eq!(
+        rendered,
+        "<table><thead><tr>\
+         <th style=\"text-align: left\">Name</th>\
+         <th style=\"text-align: center\">Count</th>\
+         <th style=\"text-align: right\">Note</th>\
+         </tr></thead><tbody><tr>\
+         <td style=\"text-align: left\"><code>a|b</code></td>\
+         <td style=\"text-align: center\">2</td>\
+         <td style=\"text-align: right\"></td>\
+         </tr><tr>\
+         <td style=\"text-align: left\"><em>c</em></td>\
+         <td style=\"text-align: center\">3</td>\
+         <td style=\"text-align: right\">x</td>\
+         </tr></tbody></table>"
+    );
+}
+
+#[test]
+fn table_needs_a_matching_delimiter_row() {
+    assert_eq!(to_markdown("a | b\n---", "p"), "<p>a | b</p><hr>");
+    assert_eq!(
+        to_markdown("Intro text\na | b\n--- | ---\n1 | 2\n\nAfter", "p"),
+        "<p>Intro text</p><table><thead><tr><th>a</th><th>b</th></tr></thead>\
+         <tbody><tr><td>1</td><td>2</td></tr></tbody></table><p>After</p>"
+    );
+}
+
+#[test]
+fn table_cells_keep_child_template_anchors_opaque() {
+    let rendered = markdown_formatter_output_from_text_and_anchors(&[
+        (Some("| Key | Value |\n| --- | --- |\n| a | "), None),
+        (None, Some(child_anchor(4))),
+        (Some(" |"), None),
+    ]);
+    assert_eq!(
+        rendered,
+        "<table><thead><tr><th>Key</th><th>Value</th></tr></thead>\
+         <tbody><tr><td>a</td><td>{child:4}</td></tr></tbody></table>"
+    );
+}
+
+#[test]
+fn horizontal_rules_end_paragraphs_and_lists() {
+    assert_eq!(
+        to_markdown("Above\n---\n- item\n* * *\nBelow", "p"),
+        "<p>Above</p><hr><ul><li>item</li></ul><hr><p>Below</p>"
+    );
+}
+
+#[test]
+fn blockquotes_render_nested_blocks() {
+    assert_eq!(
+        to_markdown(
+            "> # Note\n> Quoted *text*\n> continues\n>\n> - point\nAfter",
+            "p"
+        ),
+        "<blockquote><h1 id=\"note\">Note</h1><p>Quoted <em>text</em> continues</p>\
+         <ul><li>point</li></ul></blockquote><p>After</p>"
+    );
+}
+
+#[test]
+fn fenced_code_without_a_code_directive_renders_escaped_text() {
+    assert_eq!(
+        to_markdown(
+            "Before\n```rs\nlet x = a < b;\n\n  # not a heading\n```\nAfter",
+            "p"
+        ),
+        "<p>Before</p><pre><code class=\"language-rs\">let x = a &lt; b;\n\n  # not a heading</code></pre>\
+         <p>After</p>"
+    );
+    assert_eq!(
+        to_markdown("~~~\nunclosed | fence\n---", "p"),
+        "<pre><code>unclosed | fence\n---</code></pre>"
+    );
+}
+
+#[test]
+fn backtick_runs_with_backticks_in_the_info_text_are_not_fences() {
+    assert_eq!(
+        to_markdown("```code``` here", "p"),
+        "<p>```code``` here</p>"
+    );
+}
+
+#[test]
+fn fenced_code_routes_through_the_registered_code_directive() {
+    let style_directives = StyleDirectiveRegistry::merged(&html_project_style_directives())
+        .expect("HTML project directives should merge");
+
+    let highlighted = to_markdown_with_directives("```bst\n-- note\n```", "p", &style_directives);
+    assert_eq!(
+        highlighted,
+        "<pre><code class='codeblock'><span class='bst-code-comment'>-- note</span></code></pre>"
+    );
+
+    // Plain-text fences are shown verbatim rather than rendered as markup.
+    let text = to_markdown_with_directives("```text\n<p>hi</p>\n```", "p", &style_directives);
+    assert_eq!(
+        text,
+        "<pre><code class='codeblock'>&lt;p&gt;hi&lt;/p&gt;</code></pre>"
+    );
+
+    // Languages `$code` does not know still render, just without highlighting.
+    let unknown = to_markdown_with_directives("```cobol\nA < B\n```", "p", &style_directives);
+    assert_eq!(
+        unknown,
+        "<pre><code class=\"language-cobol\">A &lt; B</code></pre>"
+    );
+}
+
+#[test]
+fn fenced_code_keeps_anchors_opaque() {
+    let rendered = markdown_formatter_output_from_text_and_anchors(&[
+        (Some("```\nvalue = "), None),
+        (None, Some(dynamic_anchor(3))),
+        (Some("\n```"), None),
+    ]);
+    assert_eq!(rendered, "<pre><code>value = {dynamic:3}</code></pre>");
+}
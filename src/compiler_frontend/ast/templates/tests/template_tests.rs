@@ -22,6 +22,7 @@ use crate::compiler_frontend::datatypes::DataType;
 use crate::compiler_frontend::headers::parse_file_headers::TopLevelConstFragment;
 use crate::compiler_frontend::paths::path_format::PathStringFormatConfig;
 use crate::compiler_frontend::paths::path_resolution::ProjectPathResolver;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
 use crate::compiler_frontend::symbols::interned_path::InternedPath;
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::tests::parse_support::{
@@ -224,7 +225,7 @@ fn formatted_doc_template_with_direct_tir(
     let byte_len = text.len() as u32;
 
     let style = Style {
-        formatter: Some(markdown_formatter()),
+        formatter: Some(markdown_formatter(&StyleDirectiveRegistry::built_ins())),
         ..Style::default()
     };
 
@@ -45,6 +45,7 @@ use crate::compiler_frontend::datatypes::DataType;
 use crate::compiler_frontend::datatypes::ids::builtin_type_ids;
 use crate::compiler_frontend::paths::path_format::PathStringFormatConfig;
 use crate::compiler_frontend::paths::path_resolution::ProjectPathResolver;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
 use crate::compiler_frontend::symbols::interned_path::InternedPath;
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::synthetic_interface_provenance::{
@@ -657,7 +658,7 @@ fn build_formatted_markdown_fixture(string_table: &mut StringTable) -> FinalView
     let store = Rc::new(RefCell::new(TemplateIrStore::new()));
     let context = TemplateViewContext::default();
     let style = Style {
-        formatter: Some(markdown_formatter()),
+        formatter: Some(markdown_formatter(&StyleDirectiveRegistry::built_ins())),
         ..Style::default()
     };
 
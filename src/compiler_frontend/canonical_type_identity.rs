@@ -148,13 +148,11 @@ impl CollectionTypeIdentity {
     }
 
     /// The canonical element identity.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn element(&self) -> &CanonicalTypeIdentity {
         &self.element
     }
 
     /// The fixed capacity, or `None` for a growable collection.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn fixed_capacity(&self) -> Option<usize> {
         self.fixed_capacity
     }
@@ -181,13 +179,11 @@ impl OrderedMapTypeIdentity {
     }
 
     /// The canonical key identity.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn key(&self) -> &CanonicalTypeIdentity {
         &self.key
     }
 
     /// The canonical value identity.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn value(&self) -> &CanonicalTypeIdentity {
         &self.value
     }
@@ -213,13 +209,11 @@ impl FallibleCarrierTypeIdentity {
     }
 
     /// The canonical success-channel identity.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn success(&self) -> &CanonicalTypeIdentity {
         &self.success
     }
 
     /// The canonical error-channel identity.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn error(&self) -> &CanonicalTypeIdentity {
         &self.error
     }
@@ -247,13 +241,11 @@ impl GenericInstanceTypeIdentity {
     }
 
     /// The stable base nominal origin identity.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn base(&self) -> &OriginTypeId {
         &self.base
     }
 
     /// The recursively canonical concrete arguments.
-    #[allow(dead_code)] // The R2j encoder is exposed before R7 wires bytes into invalidation.
     pub(crate) fn arguments(&self) -> &[CanonicalTypeIdentity] {
         &self.arguments
     }
@@ -389,7 +381,6 @@ impl ExportedGenericParameterIdentity {
     }
 
     /// The owned authored parameter name.
-    #[allow(dead_code)]
     pub(crate) fn authored_name(&self) -> &str {
         &self.authored_name
     }
@@ -37,6 +37,8 @@ pub struct CompilerDiagnostic {
     pub primary_location: SourceLocation,
     pub labels: Vec<DiagnosticLabel>,
     pub payload: DiagnosticPayload,
+    /// Span a machine-applicable edit replaces when it differs from the rendered primary span.
+    pub(crate) edit_location: Option<SourceLocation>,
 }
 
 impl CompilerDiagnostic {
@@ -64,6 +66,7 @@ impl CompilerDiagnostic {
             labels: vec![DiagnosticLabel::primary(primary_location.clone())],
             primary_location,
             payload,
+            edit_location: None,
         }
     }
 
@@ -72,6 +75,15 @@ impl CompilerDiagnostic {
         self
     }
 
+    /// Set the span `suggested_edit` replaces, in token-start columns.
+    ///
+    /// Only needed when the rendered span starts somewhere else, such as a span widened back to
+    /// cover a character the tokenizer had already consumed.
+    pub(crate) fn with_edit_location(mut self, edit_location: SourceLocation) -> Self {
+        self.edit_location = Some(edit_location);
+        self
+    }
+
     // ------------------------------------------------------------------
     //  Syntax Constructors
     // ------------------------------------------------------------------
@@ -749,6 +761,27 @@ impl CompilerDiagnostic {
         )
     }
 
+    pub(crate) fn prerender_excluded_page(
+        function_path: StringId,
+        location: SourceLocation,
+    ) -> Self {
+        Self::with_severity(
+            DiagnosticKind::Rule(RuleDiagnosticKind::PrerenderExcludedPage),
+            DiagnosticSeverity::Warning,
+            location,
+            DiagnosticPayload::PrerenderExcludedPage { function_path },
+        )
+    }
+
+    pub(crate) fn prerender_module_script_page(location: SourceLocation) -> Self {
+        Self::with_severity(
+            DiagnosticKind::Rule(RuleDiagnosticKind::PrerenderModuleScriptPage),
+            DiagnosticSeverity::Warning,
+            location,
+            DiagnosticPayload::PrerenderModuleScriptPage,
+        )
+    }
+
     pub(crate) fn identifier_naming_convention(
         name: StringId,
         expected_style: NamingConvention,
@@ -1882,6 +1915,9 @@ impl CompilerDiagnostic {
 
     pub(crate) fn remap_string_ids(&mut self, remap: &StringIdRemap) {
         self.primary_location.remap_string_ids(remap);
+        if let Some(edit_location) = &mut self.edit_location {
+            edit_location.remap_string_ids(remap);
+        }
 
         for label in &mut self.labels {
             label.remap_string_ids(remap);
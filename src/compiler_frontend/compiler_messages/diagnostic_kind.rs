@@ -198,6 +198,8 @@ pub enum RuleDiagnosticKind {
     UnusedFunctionParameterDefaultValue,
     BstFilePathInTemplateOutput,
     LargeTrackedAsset,
+    PrerenderExcludedPage,
+    PrerenderModuleScriptPage,
     IdentifierNamingConvention,
     UnreachableMatchArm,
     InvalidTopLevelRuntimeStatement,
@@ -276,6 +278,8 @@ impl RuleDiagnosticKind {
             Self::UnusedFunctionParameterDefaultValue,
             Self::BstFilePathInTemplateOutput,
             Self::LargeTrackedAsset,
+            Self::PrerenderExcludedPage,
+            Self::PrerenderModuleScriptPage,
             Self::IdentifierNamingConvention,
             Self::UnreachableMatchArm,
             Self::InvalidTopLevelRuntimeStatement,
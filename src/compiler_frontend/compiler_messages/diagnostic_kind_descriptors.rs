@@ -289,6 +289,16 @@ fn rule_descriptor(kind: RuleDiagnosticKind) -> DiagnosticDescriptor {
             "Large tracked asset",
             DiagnosticSeverity::Warning,
         ),
+        RuleDiagnosticKind::PrerenderExcludedPage => DiagnosticDescriptor::new(
+            "BST-RULE-0086",
+            "Page excluded from prerendering",
+            DiagnosticSeverity::Warning,
+        ),
+        RuleDiagnosticKind::PrerenderModuleScriptPage => DiagnosticDescriptor::new(
+            "BST-RULE-0087",
+            "Module script page not prerendered",
+            DiagnosticSeverity::Warning,
+        ),
         RuleDiagnosticKind::IdentifierNamingConvention => DiagnosticDescriptor::new(
             "BST-RULE-0021",
             "Identifier naming convention",
@@ -253,6 +253,12 @@ pub enum DiagnosticPayload {
         byte_size: u64,
     },
 
+    PrerenderExcludedPage {
+        function_path: StringId,
+    },
+
+    PrerenderModuleScriptPage,
+
     IdentifierNamingConvention {
         name: StringId,
         expected_style: NamingConvention,
@@ -102,6 +102,9 @@ define_stable_reason_keys! {
     &UnsupportedBackendFeatureReason::FloatFormatting => "unsupported_backend_feature.float_formatting",
     &UnsupportedBackendFeatureReason::FloatBoundaryValidation => "unsupported_backend_feature.float_boundary_validation",
     &UnsupportedBackendFeatureReason::GenericRuntimeValues => "unsupported_backend_feature.generic_runtime_values",
+    &UnsupportedBackendFeatureReason::OptionValues => "unsupported_backend_feature.option_values",
+    &UnsupportedBackendFeatureReason::ScalarCollectionElements => "unsupported_backend_feature.scalar_collection_elements",
+    &UnsupportedBackendFeatureReason::FallibleResults => "unsupported_backend_feature.fallible_results",
     &UnsupportedBackendFeatureReason::ReactiveExternalCallSink => "unsupported_backend_feature.reactive_external_call_sink",
     },
 
@@ -682,6 +685,7 @@ define_stable_reason_keys! {
     InvalidPageMetadataReason => {
     &InvalidPageMetadataReason::NotAString => "invalid_page_metadata.not_a_string",
     &InvalidPageMetadataReason::DuplicateDeclaration => "invalid_page_metadata.duplicate_declaration",
+    &InvalidPageMetadataReason::InvalidDate => "invalid_page_metadata.invalid_date",
     },
 
     InvalidExpressionReason => {
@@ -14,7 +14,8 @@ impl DiagnosticPayload {
             | DiagnosticPayload::UnexpectedTrailingComma
             | DiagnosticPayload::UnescapedImplicitTemplateClose { .. }
             | DiagnosticPayload::TypeMismatch { .. }
-            | DiagnosticPayload::UnreachableMatchArm => {}
+            | DiagnosticPayload::UnreachableMatchArm
+            | DiagnosticPayload::PrerenderModuleScriptPage => {}
 
             DiagnosticPayload::ExpectedToken { expected, found } => {
                 expected.remap_string_ids(remap);
@@ -32,6 +33,9 @@ impl DiagnosticPayload {
             | DiagnosticPayload::UnusedName { name }
             | DiagnosticPayload::BstFilePathInTemplateOutput { path: name }
             | DiagnosticPayload::LargeTrackedAsset { path: name, .. }
+            | DiagnosticPayload::PrerenderExcludedPage {
+                function_path: name,
+            }
             | DiagnosticPayload::IdentifierNamingConvention { name, .. }
             | DiagnosticPayload::MalformedTemplate { message: name } => {
                 *name = remap.get(*name);
@@ -105,6 +105,9 @@ pub enum UnsupportedBackendFeatureReason {
     FloatFormatting,
     FloatBoundaryValidation,
     GenericRuntimeValues,
+    OptionValues,
+    ScalarCollectionElements,
+    FallibleResults,
     ReactiveExternalCallSink,
 }
 
@@ -119,6 +122,9 @@ impl UnsupportedBackendFeatureReason {
             Self::FloatFormatting => "Float formatting",
             Self::FloatBoundaryValidation => "Float boundary validation",
             Self::GenericRuntimeValues => "generic runtime values",
+            Self::OptionValues => "Option values",
+            Self::ScalarCollectionElements => "collection literals with scalar elements",
+            Self::FallibleResults => "fallible results and Error values",
             Self::ReactiveExternalCallSink => "reactive external-call sink",
         }
     }
@@ -1501,6 +1507,7 @@ impl DeferredFeatureReason {
 pub enum InvalidPageMetadataReason {
     NotAString,
     DuplicateDeclaration,
+    InvalidDate,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -37,6 +37,40 @@ pub fn format_terse_compiler_messages(messages: &CompilerMessages) -> Vec<String
     )
 }
 
+/// How CLI commands print compiler diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageFormat {
+    /// Terminal rendering with source excerpts and colours.
+    #[default]
+    Human,
+    /// Newline-delimited JSON, one record per diagnostic.
+    Json,
+}
+
+impl MessageFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Invalid --message-format value: '{value}'. Supported formats are human and json."
+            )),
+        }
+    }
+}
+
+pub fn print_json_compiler_messages(messages: &CompilerMessages) {
+    for line in format_json_compiler_messages(messages) {
+        println!("{line}");
+    }
+}
+
+pub fn format_json_compiler_messages(messages: &CompilerMessages) -> Vec<String> {
+    crate::compiler_frontend::compiler_messages::render::json::format_json_compiler_messages(
+        messages,
+    )
+}
+
 pub fn print_formatted_error(e: CompilerError, string_table: &StringTable) {
     // Resolve synthetic header scopes back to source files before choosing a human-readable path.
     let relative_dir = resolved_display_path(&e.location.scope, string_table);
@@ -26,6 +26,7 @@ pub(crate) mod display_messages;
 pub(crate) mod module_diagnostics;
 pub(crate) mod render;
 pub(crate) mod source_location;
+pub(crate) mod suggested_edits;
 pub(crate) mod trait_keyword_diagnostics;
 
 pub(crate) use compiler_diagnostic::CompilerDiagnostic;
@@ -0,0 +1,246 @@
+//! JSON rendering for `CompilerDiagnostic`.
+//!
+//! WHAT: produces one newline-delimited JSON record per diagnostic with its stable code,
+//! severity, qualified reason key, source locations, labels, guidance and structured
+//! suggestions, including the machine-applicable edit `bean fix` would make.
+//! WHY: CI bots and editor plugins need the structured diagnostic identity directly instead of
+//! scraping terminal or terse output. Records are a projection of the existing diagnostic model;
+//! prose still comes from the shared payload renderer.
+
+use crate::compiler_frontend::compiler_errors::{CompilerErrorMetadataKey, CompilerMessages};
+use crate::compiler_frontend::compiler_messages::render::terminal::diagnostic_label_message_text;
+use crate::compiler_frontend::compiler_messages::render::{
+    DiagnosticRenderContext, display_column_number, display_line_number,
+    relative_display_path_from_root, render_payload, resolve_source_file_path,
+};
+use crate::compiler_frontend::compiler_messages::source_location::SourceLocation;
+use crate::compiler_frontend::compiler_messages::suggested_edits::{
+    SuggestedEdit, SuggestedEditTarget,
+};
+use crate::compiler_frontend::compiler_messages::{
+    CompilerDiagnostic, DiagnosticLabelStyle, DiagnosticPayload, DiagnosticSeverity,
+};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    code: &'static str,
+    severity: &'static str,
+    /// Qualified typed reason key, or `null` for reasonless payloads.
+    reason: Option<&'static str>,
+    title: &'static str,
+    message: String,
+    guidance: Vec<String>,
+    suggestions: Vec<JsonSuggestion>,
+    location: JsonLocation,
+    labels: Vec<JsonLabel<'a>>,
+}
+
+/// One structured suggestion. `edit` is present only when a tool may apply it without asking.
+#[derive(Serialize)]
+struct JsonSuggestion {
+    /// `replacement`, `insertion` or `location`.
+    kind: &'static str,
+    text: String,
+    edit: Option<JsonEdit>,
+}
+
+#[derive(Serialize)]
+struct JsonEdit {
+    /// The exact replaced span in one-based character columns, end inclusive.
+    location: JsonLocation,
+    /// Source text that must still be at `location` before the edit is applied, or `null` when
+    /// the whole span is replaced.
+    expected: Option<String>,
+    replacement: String,
+}
+
+#[derive(Serialize)]
+struct JsonLocation {
+    /// Display path relative to the working directory, or `null` for file-less diagnostics.
+    file: Option<String>,
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+/// One-based line and column, matching terminal and terse output.
+#[derive(Serialize)]
+struct JsonPosition {
+    line: i32,
+    column: i32,
+}
+
+#[derive(Serialize)]
+struct JsonLabel<'a> {
+    style: &'a str,
+    location: JsonLocation,
+    message: Option<String>,
+}
+
+pub(crate) fn format_json_compiler_messages(messages: &CompilerMessages) -> Vec<String> {
+    messages
+        .diagnostic_display_order()
+        .into_iter()
+        .map(|diagnostic_index| {
+            format_json_diagnostic_with_context(
+                &messages.diagnostic_slice()[diagnostic_index],
+                messages.diagnostic_render_context(diagnostic_index),
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn format_json_diagnostic_with_context(
+    diagnostic: &CompilerDiagnostic,
+    context: DiagnosticRenderContext<'_>,
+) -> String {
+    let identity = diagnostic.identity();
+    let descriptor = diagnostic.kind.descriptor();
+    let rendered_payload = render_payload(&diagnostic.payload, context);
+    let working_directory = std::env::current_dir().unwrap_or_default();
+
+    let message = if rendered_payload.message.is_empty() {
+        descriptor.title.to_owned()
+    } else {
+        rendered_payload.message
+    };
+
+    let labels = diagnostic
+        .labels
+        .iter()
+        .map(|label| JsonLabel {
+            style: match label.style {
+                DiagnosticLabelStyle::Primary => "primary",
+                DiagnosticLabelStyle::Secondary => "secondary",
+            },
+            location: json_location(&label.location, context, &working_directory),
+            message: label
+                .message
+                .as_ref()
+                .map(|label_message| diagnostic_label_message_text(label_message, context)),
+        })
+        .collect();
+
+    let record = JsonDiagnostic {
+        code: identity.code,
+        severity: severity_name(identity.severity),
+        reason: identity.reason_key,
+        title: descriptor.title,
+        message,
+        guidance: rendered_payload.guidance,
+        suggestions: json_suggestions(diagnostic, context, &working_directory),
+        location: json_location(&diagnostic.primary_location, context, &working_directory),
+        labels,
+    };
+
+    // Every field is a plain string, number, array or option, so serialization cannot fail.
+    serde_json::to_string(&record).unwrap_or_default()
+}
+
+fn json_suggestions(
+    diagnostic: &CompilerDiagnostic,
+    context: DiagnosticRenderContext<'_>,
+    working_directory: &Path,
+) -> Vec<JsonSuggestion> {
+    let mut suggestions = Vec::new();
+    if let DiagnosticPayload::InfrastructureError { metadata, .. } = &diagnostic.payload {
+        for (key, kind) in [
+            (
+                CompilerErrorMetadataKey::SuggestedReplacement,
+                "replacement",
+            ),
+            (CompilerErrorMetadataKey::SuggestedInsertion, "insertion"),
+            (CompilerErrorMetadataKey::SuggestedLocation, "location"),
+        ] {
+            if let Some(text) = metadata.get(&key) {
+                suggestions.push(JsonSuggestion {
+                    kind,
+                    text: text.to_owned(),
+                    edit: None,
+                });
+            }
+        }
+    }
+
+    let Some(edit) = diagnostic.suggested_edit(context.string_table) else {
+        return suggestions;
+    };
+    let json_edit = json_edit(&edit, context, working_directory);
+    // Attach the edit to the suggestion it applies; typed payloads have no metadata entry.
+    match suggestions
+        .iter_mut()
+        .find(|suggestion| suggestion.kind != "location" && suggestion.text == edit.replacement)
+    {
+        Some(suggestion) => suggestion.edit = Some(json_edit),
+        None => suggestions.push(JsonSuggestion {
+            kind: "replacement",
+            text: edit.replacement,
+            edit: Some(json_edit),
+        }),
+    }
+
+    suggestions
+}
+
+fn json_edit(
+    edit: &SuggestedEdit,
+    context: DiagnosticRenderContext<'_>,
+    working_directory: &Path,
+) -> JsonEdit {
+    let mut location = edit.location.clone();
+    let expected = match &edit.target {
+        SuggestedEditTarget::Text(expected) => {
+            // Detector spans may cover only the first token, so the edit span is the text itself.
+            location.end_pos = location.start_pos;
+            location.end_pos.char_column += expected.chars().count() as i32 - 1;
+            Some(expected.to_owned())
+        }
+        SuggestedEditTarget::Span => None,
+    };
+
+    // Edit columns are already the one-based character columns `bean fix` applies, so they skip
+    // the display conversion used for diagnostic locations.
+    let mut json_location = json_location(&location, context, working_directory);
+    json_location.start.column = location.start_pos.char_column;
+    json_location.end.column = location.end_pos.char_column;
+
+    JsonEdit {
+        location: json_location,
+        expected,
+        replacement: edit.replacement.to_owned(),
+    }
+}
+
+fn json_location(
+    location: &SourceLocation,
+    context: DiagnosticRenderContext<'_>,
+    working_directory: &Path,
+) -> JsonLocation {
+    let file = (!location.scope.as_components().is_empty()).then(|| {
+        relative_display_path_from_root(
+            &resolve_source_file_path(&location.scope, context.string_table),
+            working_directory,
+        )
+    });
+
+    JsonLocation {
+        file,
+        start: JsonPosition {
+            line: display_line_number(location.start_pos.line_number),
+            column: display_column_number(location.start_pos.char_column),
+        },
+        end: JsonPosition {
+            line: display_line_number(location.end_pos.line_number),
+            column: display_column_number(location.end_pos.char_column),
+        },
+    }
+}
+
+fn severity_name(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Error => "error",
+        DiagnosticSeverity::Warning => "warning",
+        DiagnosticSeverity::Note => "note",
+    }
+}
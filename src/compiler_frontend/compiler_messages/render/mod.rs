@@ -2,9 +2,10 @@
 //!
 //! WHAT: owns user-visible text generation for typed diagnostics.
 //! WHY: frontend stages emit facts; this module is the only normal place where those facts become
-//! prose, terminal output, terse records, or dev-server HTML.
+//! prose, terminal output, terse or JSON records, or dev-server HTML.
 
 pub(crate) mod dev_server;
+pub(crate) mod json;
 pub(crate) mod terminal;
 pub(crate) mod terse;
 
@@ -604,6 +605,11 @@ pub(crate) fn invalid_page_metadata_message(
                 "Reserved HTML page metadata constant '{key_str}' is declared more than once for this entry page."
             )
         }
+        InvalidPageMetadataReason::InvalidDate => {
+            format!(
+                "Reserved HTML page metadata constant '{key_str}' must be a date in 'YYYY-MM-DD' form, for example \"2026-01-31\"."
+            )
+        }
     }
 }
 
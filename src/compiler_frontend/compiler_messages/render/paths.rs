@@ -37,7 +37,40 @@ pub(crate) fn resolve_source_file_path(
     scope: &InternedPath,
     string_table: &StringTable,
 ) -> PathBuf {
-    let mut source_file = normalize_path(&scope.to_path_buf(string_table));
+    let source_file = source_file_scope_path(scope, string_table);
+
+    match std::fs::canonicalize(&source_file) {
+        Ok(canonical_path) => normalize_path(&canonical_path),
+        Err(_) => source_file,
+    }
+}
+
+/// Resolve a diagnostic scope to a file under the checked project's source roots.
+///
+/// WHAT: relative scopes are joined onto each root in order and the first existing file wins;
+/// absolute scopes resolve as-is. Falls back to the first root when nothing exists yet.
+/// WHY: frontend scopes are logical paths relative to the entry or project root, so tools that
+/// edit or publish files must not resolve them against the process working directory.
+pub(crate) fn resolve_source_file_path_in_roots(
+    scope: &InternedPath,
+    string_table: &StringTable,
+    source_roots: &[PathBuf],
+) -> PathBuf {
+    let source_file = source_file_scope_path(scope, string_table);
+    if source_file.is_absolute() {
+        return resolve_source_file_path(scope, string_table);
+    }
+
+    source_roots
+        .iter()
+        .find_map(|root| std::fs::canonicalize(root.join(&source_file)).ok())
+        .map(|canonical_path| normalize_path(&canonical_path))
+        .or_else(|| source_roots.first().map(|root| root.join(&source_file)))
+        .unwrap_or(source_file)
+}
+
+pub(crate) fn source_file_scope_path(scope: &InternedPath, string_table: &StringTable) -> PathBuf {
+    let source_file = normalize_path(&scope.to_path_buf(string_table));
 
     // Header diagnostics use a synthetic "file.bst/header_name.header" scope so the terminal and
     // dev-server error pages both need to strip that suffix back to the original source file.
@@ -46,14 +79,11 @@ pub(crate) fn resolve_source_file_path(
         .and_then(|file_name| file_name.to_str())
         .is_some_and(|file_name| file_name.ends_with(".header"))
     {
-        source_file = match source_file.parent() {
+        return match source_file.parent() {
             Some(parent) => parent.to_path_buf(),
             None => source_file,
         };
     }
 
-    match std::fs::canonicalize(&source_file) {
-        Ok(canonical_path) => normalize_path(&canonical_path),
-        Err(_) => source_file,
-    }
+    source_file
 }
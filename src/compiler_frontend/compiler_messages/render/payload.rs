@@ -191,6 +191,14 @@ fn render_payload_message(
                 string_table.resolve(*path)
             )
         }
+        DiagnosticPayload::PrerenderExcludedPage { function_path } => format!(
+            "Page is not prerendered because it calls '{}', whose result at build time would not match the browser",
+            string_table.resolve(*function_path)
+        ),
+        DiagnosticPayload::PrerenderModuleScriptPage => {
+            "Page is not prerendered because it loads external JS modules, which only run in the browser"
+                .to_owned()
+        }
         DiagnosticPayload::IdentifierNamingConvention {
             name,
             expected_style,
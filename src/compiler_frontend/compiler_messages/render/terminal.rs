@@ -156,7 +156,7 @@ pub(crate) fn format_payload_guidance(
     lines
 }
 
-fn diagnostic_label_message_text(
+pub(crate) fn diagnostic_label_message_text(
     message: &DiagnosticLabelMessage,
     context: DiagnosticRenderContext<'_>,
 ) -> String {
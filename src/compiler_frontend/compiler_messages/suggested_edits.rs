@@ -0,0 +1,111 @@
+//! Machine-applicable source edits derived from diagnostics.
+//!
+//! WHAT: maps the diagnostics whose fix is unambiguous to a concrete text replacement at the
+//! diagnostic's source location.
+//! WHY: guidance text is written for people. Tools such as `bean fix` need the same knowledge as
+//! typed data, and only for fixes that cannot change program meaning. Everything else stays
+//! guidance-only.
+
+use crate::compiler_frontend::compiler_errors::CompilerErrorMetadataKey;
+use crate::compiler_frontend::compiler_messages::source_location::SourceLocation;
+use crate::compiler_frontend::compiler_messages::{
+    CommonSyntaxMistakeReason, CompilerDiagnostic, DiagnosticPayload,
+};
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+
+/// One replacement a tool may apply without asking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SuggestedEdit {
+    /// File scope and start position of the replaced text.
+    pub(crate) location: SourceLocation,
+    pub(crate) target: SuggestedEditTarget,
+    pub(crate) replacement: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum SuggestedEditTarget {
+    /// Exact source text expected at the start of the location.
+    ///
+    /// Detector locations usually cover only the first token of a multi-token mistake such as
+    /// `==`, so the edit names the full spelling and appliers verify it before replacing.
+    Text(String),
+    /// Everything the location spans. Only applied to single-line spans.
+    Span,
+}
+
+impl CompilerDiagnostic {
+    /// The machine-applicable edit for this diagnostic, if the compiler knows one.
+    pub(crate) fn suggested_edit(&self, string_table: &StringTable) -> Option<SuggestedEdit> {
+        let (target, replacement) = match &self.payload {
+            DiagnosticPayload::CommonSyntaxMistake { reason } => {
+                common_syntax_mistake_edit(reason, string_table)?
+            }
+            // `SuggestedInsertion` and `SuggestedLocation` name their position in prose ("before
+            // the closing ')'"), not as a span, so they stay guidance-only.
+            DiagnosticPayload::InfrastructureError { metadata, .. } => {
+                let replacement = metadata.get(&CompilerErrorMetadataKey::SuggestedReplacement)?;
+                (SuggestedEditTarget::Span, replacement.to_owned())
+            }
+            _ => return None,
+        };
+
+        if replacement.contains('\n') {
+            return None;
+        }
+
+        Some(SuggestedEdit {
+            location: self
+                .edit_location
+                .as_ref()
+                .unwrap_or(&self.primary_location)
+                .clone(),
+            target,
+            replacement,
+        })
+    }
+}
+
+fn common_syntax_mistake_edit(
+    reason: &CommonSyntaxMistakeReason,
+    string_table: &StringTable,
+) -> Option<(SuggestedEditTarget, String)> {
+    let (original, replacement) = match reason {
+        CommonSyntaxMistakeReason::EqualityOperator => ("==", "is"),
+        CommonSyntaxMistakeReason::InequalityOperator => ("!=", "is not"),
+        CommonSyntaxMistakeReason::LogicalAndOperator => ("&&", "and"),
+        CommonSyntaxMistakeReason::LogicalOrOperator => ("||", "or"),
+        CommonSyntaxMistakeReason::BooleanBangNegation => ("!", "not"),
+        CommonSyntaxMistakeReason::StatementLineComment => ("//", "--"),
+        CommonSyntaxMistakeReason::ImportPathMissingAtPrefix { authored_path } => {
+            let authored_path = string_table.resolve(*authored_path);
+            return Some((
+                SuggestedEditTarget::Text(authored_path.to_owned()),
+                format!("@{authored_path}"),
+            ));
+        }
+
+        // These need surrounding context (a binding name, a body shape or a type) to rewrite,
+        // so they stay guidance-only.
+        CommonSyntaxMistakeReason::ExpressionAssignment
+        | CommonSyntaxMistakeReason::RustBorrowPrefix
+        | CommonSyntaxMistakeReason::InvalidAsOperator
+        | CommonSyntaxMistakeReason::FunctionKeyword { .. }
+        | CommonSyntaxMistakeReason::LetOrVarKeyword
+        | CommonSyntaxMistakeReason::ConstKeyword
+        | CommonSyntaxMistakeReason::MatchKeyword
+        | CommonSyntaxMistakeReason::StructKeyword { .. }
+        | CommonSyntaxMistakeReason::SignatureParenthesisDelimiter
+        | CommonSyntaxMistakeReason::SignatureAsKeyword
+        | CommonSyntaxMistakeReason::InvalidCompileTimeBindingSpacing
+        | CommonSyntaxMistakeReason::InvalidMutableBindingSpacing
+        | CommonSyntaxMistakeReason::InvalidReactiveBindingSpacing
+        | CommonSyntaxMistakeReason::InvalidSymbolicSpacing { .. }
+        | CommonSyntaxMistakeReason::InvalidUnaryNegationSpacing
+        | CommonSyntaxMistakeReason::UnsupportedUnaryPlus => return None,
+    };
+
+    Some((
+        SuggestedEditTarget::Text(original.to_owned()),
+        replacement.to_owned(),
+    ))
+}
@@ -18,18 +18,22 @@ use super::{
     SyntaxDiagnosticKind, TypeAnnotationContext, TypeDiagnosticKind, TypeMismatchContext,
     UnsupportedBackendFeatureReason, UnsupportedOperatorCategory, is_well_formed_reason_key,
 };
-use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
+use crate::compiler_frontend::compiler_errors::{
+    CompilerError, CompilerErrorMetadataKey, CompilerMessages, ErrorType,
+    compiler_error_to_diagnostic,
+};
 use crate::compiler_frontend::compiler_messages::render::{
-    DiagnosticRenderContext, dev_server, terminal, terse,
+    DiagnosticRenderContext, dev_server, json, terminal, terse,
 };
 use crate::compiler_frontend::compiler_messages::source_location::{CharPosition, SourceLocation};
+use crate::compiler_frontend::compiler_messages::suggested_edits::SuggestedEditTarget;
 use crate::compiler_frontend::datatypes::definitions::StructTypeDefinition;
 use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
 use crate::compiler_frontend::datatypes::ids::{NominalTypeId, builtin_type_ids};
 use crate::compiler_frontend::symbols::interned_path::InternedPath;
 use crate::compiler_frontend::symbols::string_interning::{StringId, StringTable};
 use crate::compiler_frontend::tokenizer::tokens::{PathTokenItem, TokenKind};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 const DIAGNOSTIC_PAYLOAD_SOURCE: &str = include_str!("../diagnostic_payload/mod.rs");
@@ -716,6 +720,84 @@ fn remap_string_ids_updates_missing_at_prefix_authored_path() {
     }
 }
 
+#[test]
+fn suggested_edits_cover_only_unambiguous_syntax_mistakes() {
+    let mut string_table = StringTable::new();
+    let source_path = InternedPath::from_single_str("main.bst", &mut string_table);
+    let authored_path = string_table.intern("vendor/drawing.js");
+
+    let equality = CompilerDiagnostic::common_syntax_mistake(
+        CommonSyntaxMistakeReason::EqualityOperator,
+        location(source_path.to_owned()),
+    )
+    .suggested_edit(&string_table)
+    .expect("`==` should have a machine-applicable edit");
+    assert_eq!(
+        equality.target,
+        SuggestedEditTarget::Text(String::from("=="))
+    );
+    assert_eq!(equality.replacement, "is");
+    assert_eq!(equality.location, location(source_path.to_owned()));
+
+    let import_path = CompilerDiagnostic::common_syntax_mistake(
+        CommonSyntaxMistakeReason::ImportPathMissingAtPrefix { authored_path },
+        location(source_path.to_owned()),
+    )
+    .suggested_edit(&string_table)
+    .expect("a missing `@` import prefix should have a machine-applicable edit");
+    assert_eq!(
+        import_path.target,
+        SuggestedEditTarget::Text(String::from("vendor/drawing.js"))
+    );
+    assert_eq!(import_path.replacement, "@vendor/drawing.js");
+
+    let guidance_only = CompilerDiagnostic::common_syntax_mistake(
+        CommonSyntaxMistakeReason::ExpressionAssignment,
+        location(source_path),
+    );
+    assert_eq!(guidance_only.suggested_edit(&string_table), None);
+}
+
+#[test]
+fn suggested_insertions_and_locations_are_not_machine_applicable() {
+    let mut string_table = StringTable::new();
+    let source_path = InternedPath::from_single_str("main.bst", &mut string_table);
+    let error = CompilerError::new("bad config", location(source_path), ErrorType::Config)
+        .with_metadata(HashMap::from([
+            (
+                CompilerErrorMetadataKey::SuggestedInsertion,
+                String::from(","),
+            ),
+            (
+                CompilerErrorMetadataKey::SuggestedLocation,
+                String::from("before the closing ')'"),
+            ),
+        ]));
+
+    assert_eq!(
+        compiler_error_to_diagnostic(&error).suggested_edit(&string_table),
+        None
+    );
+}
+
+#[test]
+fn suggested_edits_prefer_the_diagnostic_edit_location() {
+    let mut string_table = StringTable::new();
+    let source_path = InternedPath::from_single_str("main.bst", &mut string_table);
+    let mut edit_location = location(source_path.to_owned());
+    edit_location.start_pos.char_column += 1;
+
+    let edit = CompilerDiagnostic::common_syntax_mistake(
+        CommonSyntaxMistakeReason::EqualityOperator,
+        location(source_path),
+    )
+    .with_edit_location(edit_location.to_owned())
+    .suggested_edit(&string_table)
+    .expect("`==` should have a machine-applicable edit");
+
+    assert_eq!(edit.location, edit_location);
+}
+
 #[test]
 fn duplicate_declaration_with_previous_location_keeps_secondary_label() {
     let mut string_table = StringTable::new();
@@ -870,6 +952,164 @@ fn type_mismatch_terse_renderer_renders_type_names_with_context() {
     assert!(!line.contains("Found type id"));
 }
 
+#[test]
+fn json_renderer_projects_identity_locations_labels_and_guidance() {
+    let mut string_table = StringTable::new();
+    let source_path = InternedPath::from_single_str("main.bst", &mut string_table);
+    let type_environment = TypeEnvironment::new();
+
+    let diagnostic = CompilerDiagnostic::type_mismatch(
+        type_environment.builtins().int,
+        type_environment.builtins().string,
+        TypeMismatchContext::FunctionArgument,
+        location(source_path.clone()),
+    )
+    .with_labels(vec![
+        DiagnosticLabel::primary(location(source_path.clone())),
+        DiagnosticLabel::secondary(
+            location(source_path),
+            Some(DiagnosticLabelMessage::ExpectedTypeDeclaredHere),
+        ),
+    ]);
+    let render_context = DiagnosticRenderContext::new(&string_table)
+        .with_optional_type_environment(Some(&type_environment));
+
+    let line = json::format_json_diagnostic_with_context(&diagnostic, render_context);
+    assert!(!line.contains('\n'), "JSON records must stay on one line");
+
+    let record: serde_json::Value = serde_json::from_str(&line).expect("record should be JSON");
+    let identity = diagnostic.identity();
+    assert_eq!(record["code"], identity.code);
+    assert_eq!(record["severity"], "error");
+    assert_eq!(record["reason"], serde_json::json!(identity.reason_key));
+    assert_eq!(record["location"]["file"], "main.bst");
+    assert_eq!(
+        record["location"]["start"],
+        serde_json::json!({ "line": 2, "column": 3 })
+    );
+    assert_eq!(record["guidance"][0], "Expected: Int");
+    assert_eq!(record["labels"][0]["style"], "primary");
+    assert_eq!(record["labels"][0]["message"], serde_json::Value::Null);
+    assert_eq!(record["labels"][1]["style"], "secondary");
+    assert!(
+        record["labels"][1]["message"].is_string(),
+        "secondary label text should be rendered, got {record}"
+    );
+}
+
+#[test]
+fn json_renderer_projects_machine_applicable_edits_as_suggestions() {
+    let mut string_table = StringTable::new();
+    let source_path = InternedPath::from_single_str("main.bst", &mut string_table);
+    let diagnostic = CompilerDiagnostic::common_syntax_mistake(
+        CommonSyntaxMistakeReason::InequalityOperator,
+        location(source_path),
+    );
+
+    let line = json::format_json_diagnostic_with_context(
+        &diagnostic,
+        DiagnosticRenderContext::new(&string_table),
+    );
+    let record: serde_json::Value = serde_json::from_str(&line).expect("record should be JSON");
+
+    // The edit span covers the whole `!=` even though the detector span starts at its first byte,
+    // and uses the applier's one-based columns rather than display columns.
+    assert_eq!(
+        record["suggestions"],
+        serde_json::json!([{
+            "kind": "replacement",
+            "text": "is not",
+            "edit": {
+                "location": {
+                    "file": "main.bst",
+                    "start": { "line": 2, "column": 2 },
+                    "end": { "line": 2, "column": 3 },
+                },
+                "expected": "!=",
+                "replacement": "is not",
+            },
+        }])
+    );
+
+    let guidance_only = CompilerDiagnostic::common_syntax_mistake(
+        CommonSyntaxMistakeReason::ExpressionAssignment,
+        location(InternedPath::from_single_str("main.bst", &mut string_table)),
+    );
+    let line = json::format_json_diagnostic_with_context(
+        &guidance_only,
+        DiagnosticRenderContext::new(&string_table),
+    );
+    let record: serde_json::Value = serde_json::from_str(&line).expect("record should be JSON");
+    assert_eq!(record["suggestions"], serde_json::json!([]));
+}
+
+#[test]
+fn json_renderer_projects_suggestion_metadata() {
+    let mut string_table = StringTable::new();
+    let source_path = InternedPath::from_single_str("main.bst", &mut string_table);
+    let error = CompilerError::new("bad config", location(source_path), ErrorType::Config)
+        .with_metadata(HashMap::from([
+            (
+                CompilerErrorMetadataKey::SuggestedReplacement,
+                String::from("value"),
+            ),
+            (
+                CompilerErrorMetadataKey::SuggestedLocation,
+                String::from("before the closing ')'"),
+            ),
+        ]));
+    let diagnostic = compiler_error_to_diagnostic(&error);
+
+    let line = json::format_json_diagnostic_with_context(
+        &diagnostic,
+        DiagnosticRenderContext::new(&string_table),
+    );
+    let record: serde_json::Value = serde_json::from_str(&line).expect("record should be JSON");
+
+    // The replacement spans the whole diagnostic location, so it carries its edit.
+    assert_eq!(
+        record["suggestions"],
+        serde_json::json!([
+            {
+                "kind": "replacement",
+                "text": "value",
+                "edit": {
+                    "location": {
+                        "file": "main.bst",
+                        "start": { "line": 2, "column": 2 },
+                        "end": { "line": 2, "column": 4 },
+                    },
+                    "expected": null,
+                    "replacement": "value",
+                },
+            },
+            { "kind": "location", "text": "before the closing ')'", "edit": null },
+        ])
+    );
+}
+
+#[test]
+fn json_renderer_reports_qualified_reason_keys() {
+    let mut string_table = StringTable::new();
+    let source_path = InternedPath::from_single_str("main.bst", &mut string_table);
+    let diagnostic = CompilerDiagnostic::invalid_string_escape(
+        InvalidStringEscapeReason::TrailingBackslash,
+        location(source_path),
+    );
+
+    let line = json::format_json_diagnostic_with_context(
+        &diagnostic,
+        DiagnosticRenderContext::new(&string_table),
+    );
+    let record: serde_json::Value = serde_json::from_str(&line).expect("record should be JSON");
+
+    let reason = record["reason"]
+        .as_str()
+        .expect("reason key should be present");
+    assert!(is_well_formed_reason_key(reason));
+    assert_eq!(Some(reason), diagnostic.identity().reason_key);
+}
+
 #[test]
 fn invalid_string_escape_renderer_preserves_the_authored_escape_spelling() {
     let mut string_table = StringTable::new();
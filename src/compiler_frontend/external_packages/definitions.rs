@@ -33,7 +33,11 @@ pub enum ExternalJsLowering {
 /// Placeholder: Wasm external support is still experimental.
 #[derive(Debug, Clone)]
 pub enum ExternalWasmLowering {
+    /// Import the named function from the embedder's `host` module (HTML-Wasm).
     HostFunction(&'static str),
+    /// Lower to the named WASI preview1 adapter (see `backends::wasm::runtime::wasi`).
+    /// Only the WASI command target accepts these.
+    WasiAdapter(&'static str),
 }
 
 /// Full definition of a single external function.
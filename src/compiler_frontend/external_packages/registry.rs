@@ -580,6 +580,14 @@ impl ExternalPackageRegistry {
         path: ExternalSymbolPath,
         spec: ExternalTypeSpec,
     ) -> Result<ExternalTypeId, CompilerError> {
+        // Skip ids taken by fixed-id types (e.g. `IO_INPUT_EXTERNAL_TYPE_ID`). Which synthetic ids
+        // are free depends on how many packages a builder surface registered first.
+        while self
+            .type_reverse_identity_by_id
+            .contains_key(&ExternalTypeId(self.next_synthetic_id))
+        {
+            self.next_synthetic_id += 1;
+        }
         let id = ExternalTypeId(self.next_synthetic_id);
         self.next_synthetic_id += 1;
         self.register_type_at_path(
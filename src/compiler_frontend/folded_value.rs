@@ -73,7 +73,6 @@ impl FiniteFloat {
     ///
     /// The encoder consumes this read-only value rather than formatting a float, so distinct
     /// finite semantic values remain distinct while `-0.0` and `0.0` share one canonical form.
-    #[allow(dead_code)]
     pub(crate) fn normalized_bits(&self) -> u64 {
         self.0.to_bits()
     }
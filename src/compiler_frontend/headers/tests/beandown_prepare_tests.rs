@@ -642,7 +642,7 @@ fn simple_markdown_body_folds_like_markdown_template() {
 
     assert_eq!(
         folded_content_value(&ast, &string_table),
-        "<h1>Heading</h1>"
+        "<h1 id=\"heading\">Heading</h1>"
     );
 }
 
@@ -650,7 +650,10 @@ fn simple_markdown_body_folds_like_markdown_template() {
 fn nested_beandown_template_defaults_to_markdown_formatting() {
     let (ast, string_table) = ast_from_beandown_source("[:# Nested]");
 
-    assert_eq!(folded_content_value(&ast, &string_table), "<h1>Nested</h1>");
+    assert_eq!(
+        folded_content_value(&ast, &string_table),
+        "<h1 id=\"nested\">Nested</h1>"
+    );
 }
 
 #[test]
@@ -1102,7 +1105,7 @@ fn beanstalk_grouped_imports_beandown_content_as_folded_string_constant() {
 
     assert_eq!(
         folded_constant_value(&ast, &string_table, "from_intro"),
-        "<h1>Intro</h1>"
+        "<h1 id=\"intro\">Intro</h1>"
     );
 }
 
@@ -1127,7 +1130,7 @@ fn beanstalk_namespace_imports_beandown_content_as_folded_string_constant() {
 
     assert_eq!(
         folded_constant_value(&ast, &string_table, "from_intro"),
-        "<h1>Intro</h1>"
+        "<h1 id=\"intro\">Intro</h1>"
     );
 }
 
@@ -76,7 +76,6 @@ pub enum HirMapOp {
 }
 
 impl HirMapOp {
-    #[cfg(any(test, feature = "show_hir"))]
     pub(crate) fn source_name(self) -> &'static str {
         match self {
             HirMapOp::Get => "get",
@@ -5,67 +5,46 @@
 //! This will be used to help the rest of the HIR and borrow checker stages to create and return useful errors and warnings.
 //! (CompilerMessages)
 //! It will also enable printing out Hir structures for easy debugging also.
+//! Always compiled, because the REPL `:hir` command renders HIR in normal builds.
 
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::datatypes::definitions::{
     BuiltinTypeDefinition, ChoiceTypeDefinition, ConstructedTypeDefinition, FunctionTypeDefinition,
     StructTypeDefinition, TypeDefinition,
 };
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::datatypes::display::display_type;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::datatypes::ids::TypeId;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::datatypes::ids::{
     BuiltinTypeConstructor, BuiltinTypeKey, TypeConstructor,
 };
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::external_packages::CallTarget;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::blocks::{HirBlock, HirLocal};
 #[cfg(test)]
 use crate::compiler_frontend::hir::expressions::FallibleCarrierVariant;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::expressions::{
     HirExpression, HirExpressionKind, HirVariantCarrier, ValueKind,
 };
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::functions::HirFunction;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::hir_side_table::HirSideTable;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::ids::ChoiceId;
 use crate::compiler_frontend::hir::ids::{
     BlockId, FieldId, FunctionId, HirNodeId, HirValueId, LocalId, RegionId, StructId,
 };
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::module::HirModule;
 use crate::compiler_frontend::hir::numeric::HirNumericOp;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::numeric::{HirNumericOperands, NumericFailureMode};
 use crate::compiler_frontend::hir::operators::{HirBinOp, HirUnaryOp};
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::patterns::{HirMatchArm, HirPattern, HirRelationalPatternOp};
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::places::HirPlace;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::statements::{HirStatement, HirStatementKind};
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::structs::{HirField, HirStruct};
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::hir::terminators::HirTerminator;
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::symbols::string_interning::StringTable;
-#[cfg(any(test, feature = "show_hir"))]
 use std::fmt::Write as _;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
-#[cfg(any(test, feature = "show_hir"))]
 const MAX_TYPE_RENDER_DEPTH: usize = 24;
 
-#[cfg(any(test, feature = "show_hir"))]
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct HirDisplayOptions {
     pub include_ids: bool,
@@ -75,7 +54,6 @@ pub(crate) struct HirDisplayOptions {
     pub multiline_match_arms: bool,
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 impl Default for HirDisplayOptions {
     fn default() -> Self {
         Self {
@@ -88,7 +66,6 @@ impl Default for HirDisplayOptions {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[derive(Clone, Copy)]
 pub(crate) struct HirDisplayContext<'a> {
     string_table: &'a StringTable,
@@ -97,7 +74,6 @@ pub(crate) struct HirDisplayContext<'a> {
     options: HirDisplayOptions,
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 impl<'a> HirDisplayContext<'a> {
     pub(crate) fn new(string_table: &'a StringTable) -> Self {
         Self {
@@ -108,20 +84,17 @@ impl<'a> HirDisplayContext<'a> {
         }
     }
 
-    #[cfg(feature = "show_hir")]
     pub(crate) fn with_side_table(mut self, side_table: &'a HirSideTable) -> Self {
         self.side_table = Some(side_table);
         self
     }
 
-    #[cfg(feature = "show_hir")]
     pub(crate) fn with_type_environment(mut self, type_environment: &'a TypeEnvironment) -> Self {
         self.type_environment = Some(type_environment);
         self
     }
 
-    #[cfg(any(test, feature = "show_hir"))]
-    #[allow(dead_code)] // show_hir builds keep this test helper for focused display rendering.
+    #[allow(dead_code)] // Test helper for focused display rendering.
     pub(crate) fn with_options(mut self, options: HirDisplayOptions) -> Self {
         self.options = options;
         self
@@ -1003,12 +976,9 @@ impl<'a> HirDisplayContext<'a> {
 
     fn variant_carrier_label(&self, carrier: &HirVariantCarrier) -> String {
         match carrier {
-            #[cfg(any(test, feature = "show_hir"))]
             HirVariantCarrier::Choice { choice_id } => {
                 format!("choice={}", self.choice_label(*choice_id))
             }
-            #[cfg(not(any(test, feature = "show_hir")))]
-            HirVariantCarrier::Choice { .. } => "choice".to_owned(),
             HirVariantCarrier::Option => "option".to_owned(),
             #[cfg(test)]
             HirVariantCarrier::Fallible => "result".to_owned(),
@@ -1034,10 +1004,9 @@ impl<'a> HirDisplayContext<'a> {
 //  Convenience Display Hooks
 // -----------------------------
 
-// Debug display helpers: gated to test builds and the "show_hir" feature.
+// Debug display helpers used by tests, `hir_log!` tracing and the REPL `:hir` command.
 // The `#[allow(dead_code)]` annotations are needed because rustc does not see
 // calls made through the `hir_log!` macro as usages for dead-code analysis.
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirModule {
     pub(crate) fn display_with_table(&self, string_table: &StringTable) -> String {
@@ -1053,7 +1022,6 @@ impl HirModule {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirBlock {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1061,7 +1029,6 @@ impl HirBlock {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirFunction {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1069,7 +1036,6 @@ impl HirFunction {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirStruct {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1077,7 +1043,6 @@ impl HirStruct {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirStatement {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1085,7 +1050,6 @@ impl HirStatement {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirTerminator {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1093,7 +1057,6 @@ impl HirTerminator {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirExpression {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1101,7 +1064,6 @@ impl HirExpression {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirPlace {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1109,7 +1071,6 @@ impl HirPlace {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirPattern {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
@@ -1117,7 +1078,6 @@ impl HirPattern {
     }
 }
 
-#[cfg(any(test, feature = "show_hir"))]
 #[allow(dead_code)]
 impl HirMatchArm {
     pub(crate) fn display_with_context(&self, display: &HirDisplayContext<'_>) -> String {
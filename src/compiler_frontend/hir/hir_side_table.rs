@@ -9,7 +9,6 @@
 //! - Track human-readable names for locals, functions, and types.
 //! - Intern source locations to save memory and allow O(1) identity checks.
 
-#[cfg(any(test, feature = "show_hir"))]
 use crate::compiler_frontend::datatypes::generic_identity_bridge::display_generic_instantiation_key;
 use crate::compiler_frontend::datatypes::generic_identity_bridge::{
     GenericInstantiationKey, TypeIdentityKey,
@@ -733,14 +732,12 @@ impl HirSideTable {
 
     /// Returns the interned path for a choice.
     #[inline]
-    #[cfg(any(test, feature = "show_hir"))]
     pub(crate) fn choice_name_path(&self, choice_id: ChoiceId) -> Option<&InternedPath> {
         self.choice_names.get(&choice_id)
     }
 
     /// Resolves a choice name to a string.
     #[inline]
-    #[cfg(any(test, feature = "show_hir"))]
     pub(crate) fn resolve_choice_name<'a>(
         &self,
         choice_id: ChoiceId,
@@ -751,7 +748,6 @@ impl HirSideTable {
     }
 
     /// Returns a human-readable display name for a choice, including generic arguments if applicable.
-    #[cfg(any(test, feature = "show_hir"))]
     pub(crate) fn display_choice_name(
         &self,
         choice_id: ChoiceId,
@@ -789,7 +785,6 @@ impl HirSideTable {
 
     /// Resolves a struct name to a string.
     #[inline]
-    #[cfg(any(test, feature = "show_hir"))]
     pub(crate) fn resolve_struct_name<'a>(
         &self,
         struct_id: StructId,
@@ -800,7 +795,6 @@ impl HirSideTable {
     }
 
     /// Returns a human-readable display name for a struct, including generic arguments if applicable.
-    #[cfg(any(test, feature = "show_hir"))]
     pub(crate) fn display_struct_name(
         &self,
         struct_id: StructId,
@@ -816,7 +810,6 @@ impl HirSideTable {
 
     /// Resolves a field name to a string.
     #[inline]
-    #[cfg(any(test, feature = "show_hir"))]
     pub(crate) fn resolve_field_name<'a>(
         &self,
         field_id: FieldId,
@@ -71,10 +71,12 @@ pub(crate) use pipeline::{
 };
 
 /// Flags change the behavior of the core `compiler_frontend` pipeline.
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum Flag {
     Release,
     HtmlWasm,
+    /// Set by `bean dev` so backends can emit dev-client support such as source line tables.
+    DevServer,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -5,7 +5,9 @@
 
 use crate::compiler_frontend::compiler_errors::CompilerError;
 use crate::compiler_frontend::style_directives::builtins::frontend_built_in_directives;
-use crate::compiler_frontend::style_directives::specs::StyleDirectiveSpec;
+use crate::compiler_frontend::style_directives::specs::{
+    FormatterFactory, StyleDirectiveKind, StyleDirectiveSpec,
+};
 use crate::compiler_frontend::tokenizer::tokens::TemplateBodyMode;
 use std::fmt::Write as _;
 
@@ -68,6 +70,14 @@ impl StyleDirectiveRegistry {
         self.ordered.iter().find(|spec| spec.name == name)
     }
 
+    /// Formatter factory of a registered handler directive, if it has one.
+    pub fn formatter_factory(&self, name: &str) -> Option<FormatterFactory> {
+        match &self.find(name)?.kind {
+            StyleDirectiveKind::Handler(handler) => handler.formatter_factory,
+            StyleDirectiveKind::Core(_) => None,
+        }
+    }
+
     /// Resolve only the template-body tokenization mode for a directive.
     pub fn body_mode_for(&self, name: &str) -> Option<TemplateBodyMode> {
         self.find(name).map(|spec| spec.body_mode)
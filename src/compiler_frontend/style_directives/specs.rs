@@ -7,6 +7,7 @@
 use crate::compiler_frontend::ast::templates::template::Template;
 use crate::compiler_frontend::ast::templates::template::{BodyWhitespacePolicy, Formatter};
 use crate::compiler_frontend::style_directives::compatibility::TemplateHeadCompatibility;
+use crate::compiler_frontend::style_directives::registry::StyleDirectiveRegistry;
 use crate::compiler_frontend::tokenizer::tokens::TemplateBodyMode;
 
 /// Core language directive behavior handled directly by compiler-owned template parsing.
@@ -59,10 +60,14 @@ pub struct StyleDirectiveEffects {
 
 /// Formatter factory used by handler-based directives.
 ///
+/// Receives the parsed optional argument and the merged directive registry, so a formatter can
+/// delegate embedded content to another registered directive (`$md` fenced code uses `$code`).
+///
 /// Returns:
 /// - `Ok(formatter)` to set/replace the active formatter.
 /// - `Err(message)` for user-facing directive argument/configuration errors.
-pub type FormatterFactory = fn(Option<&StyleDirectiveArgumentValue>) -> Result<Formatter, String>;
+pub type FormatterFactory =
+    fn(Option<&StyleDirectiveArgumentValue>, &StyleDirectiveRegistry) -> Result<Formatter, String>;
 
 /// Full behavior contract for one handler-based style directive.
 ///
@@ -192,12 +192,21 @@ fn recognise_missing_at_import_prefix(
 
     let authored_path_id = string_table.intern(&authored_path);
 
-    Some(CompilerDiagnostic::common_syntax_mistake(
-        CommonSyntaxMistakeReason::ImportPathMissingAtPrefix {
-            authored_path: authored_path_id,
-        },
-        SourceLocation::new(stream.file_path.to_owned(), path_start, stream.position),
-    ))
+    // Edits address text in token-start columns, which is where `stream.start_position` still
+    // points, so they get the unshifted span.
+    Some(
+        CompilerDiagnostic::common_syntax_mistake(
+            CommonSyntaxMistakeReason::ImportPathMissingAtPrefix {
+                authored_path: authored_path_id,
+            },
+            SourceLocation::new(stream.file_path.to_owned(), path_start, stream.position),
+        )
+        .with_edit_location(SourceLocation::new(
+            stream.file_path.to_owned(),
+            stream.start_position,
+            stream.position,
+        )),
+    )
 }
 
 /// WHAT: characters that end the narrow missing-`@` import-path scan.
@@ -1935,6 +1935,28 @@ fn rejects_missing_at_prefix_paths_with_complete_spelling_and_span() {
     }
 }
 
+#[test]
+fn missing_at_prefix_edit_rewrites_the_authored_path() {
+    for (source, expected) in [
+        ("import core\n", "import @core\n"),
+        (
+            "import vendor/drawing.js as drawing\n",
+            "import @vendor/drawing.js as drawing\n",
+        ),
+        ("import\n./utils\n", "import\n@./utils\n"),
+    ] {
+        let (diagnostic, string_table) = tokenize_source_error(source);
+        let edit = diagnostic
+            .suggested_edit(&string_table)
+            .expect("a missing `@` import prefix should have a machine-applicable edit");
+
+        assert_eq!(
+            crate::projects::fix::apply_suggested_edits(source, &[edit]),
+            (expected.to_owned(), 1)
+        );
+    }
+}
+
 #[test]
 fn parent_relative_path_does_not_receive_missing_at_prefix_correction() {
     // `../` is not supported with `@`, so the tokenizer must not suggest `@../`.
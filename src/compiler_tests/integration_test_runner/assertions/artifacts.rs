@@ -1,7 +1,7 @@
 //! Output-artifact assertions and shared project-output lookup.
 //!
 //! WHAT: validates artifact presence, absence, kind and text or binary content, including the
-//!       universal HTML output baseline.
+//!       universal HTML output baseline. `text` assertions read UTF-8 byte artifacts.
 //! WHY: all output lookup belongs here so golden, rendered-output and Wasm checks inspect the
 //!      same normalized set of emitted artifacts.
 
@@ -58,7 +58,7 @@ fn validate_single_artifact_assertion(
     assertion: &ArtifactAssertion,
 ) -> Option<String> {
     match assertion.kind {
-        ArtifactKind::Html | ArtifactKind::Js => {
+        ArtifactKind::Html | ArtifactKind::Js | ArtifactKind::Text => {
             let Some(text) = output_text_content(output, assertion.kind) else {
                 return Some(format!(
                     "Artifact '{}' expected kind '{}', but produced a different file kind.",
@@ -208,6 +208,7 @@ fn artifact_kind_name(kind: ArtifactKind) -> &'static str {
         ArtifactKind::Js => "js",
         ArtifactKind::Wasm => "wasm",
         ArtifactKind::Binary => "binary",
+        ArtifactKind::Text => "text",
     }
 }
 
@@ -290,6 +291,12 @@ pub(super) fn output_text_content(
         return Some(content.as_str());
     }
 
+    if matches!(expected_kind, ArtifactKind::Text)
+        && let FileKind::Bytes(bytes) = output.file_kind()
+    {
+        return std::str::from_utf8(bytes).ok();
+    }
+
     None
 }
 
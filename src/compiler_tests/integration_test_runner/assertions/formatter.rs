@@ -0,0 +1,80 @@
+//! `bean fmt` golden validation for integration fixtures.
+//!
+//! WHAT: formats every `input/` file that has a counterpart under `golden/fmt/`, compares the
+//!       result strictly and checks that the golden itself formats to the same text.
+//! WHY: canonical layout is a user-facing contract like emitted HTML, and the fixed-point check
+//!      keeps the formatter idempotent on every layout the suite pins.
+
+use super::super::types::GoldenMode;
+use super::super::{FORMATTER_GOLDEN_DIR_NAME, FailureKind, GOLDEN_DIR_NAME, INPUT_DIR_NAME};
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::projects::formatter::format_source;
+use crate::projects::html_project::style_directives::html_project_style_directives;
+use std::fs;
+use std::path::Path;
+
+pub(super) fn validate_formatter_goldens(fixture_root: &Path) -> Option<(String, FailureKind)> {
+    let golden_dir = fixture_root
+        .join(GOLDEN_DIR_NAME)
+        .join(FORMATTER_GOLDEN_DIR_NAME);
+    let inventory = match super::goldens::discover_golden_files(&golden_dir) {
+        Ok(inventory) => inventory,
+        Err(reason) => return Some((reason, FailureKind::HarnessFailed)),
+    };
+    if inventory.is_empty() {
+        return None;
+    }
+
+    let Ok(style_directives) = StyleDirectiveRegistry::merged(&html_project_style_directives())
+    else {
+        return Some((
+            "Failed to build the HTML project style directive registry.".to_owned(),
+            FailureKind::HarnessFailed,
+        ));
+    };
+
+    for file in &inventory.files {
+        let relative = &file.relative_path;
+        let input_path = fixture_root.join(INPUT_DIR_NAME).join(relative);
+
+        let (Ok(input), Ok(golden)) = (
+            fs::read_to_string(&input_path),
+            fs::read_to_string(&file.absolute_path),
+        ) else {
+            return Some((
+                format!(
+                    "Formatter golden '{relative}' needs a readable input file at '{}'.",
+                    input_path.display()
+                ),
+                FailureKind::HarnessFailed,
+            ));
+        };
+
+        let Ok(formatted) = format_source(&input, &input_path, &style_directives) else {
+            return Some((
+                format!("Formatting input '{relative}' failed."),
+                FailureKind::ExpectationViolation,
+            ));
+        };
+        if let Some(detail) =
+            super::goldens::compare_text_golden(&golden, &formatted, GoldenMode::Strict)
+        {
+            return Some((
+                format!(
+                    "Formatter golden '{relative}' did not match the formatted input.\n{detail}"
+                ),
+                FailureKind::StrictGoldenMismatch,
+            ));
+        }
+
+        let reformatted = format_source(&golden, &file.absolute_path, &style_directives);
+        if reformatted.as_deref().ok() != Some(golden.as_str()) {
+            return Some((
+                format!("Formatter golden '{relative}' changes when formatted again."),
+                FailureKind::StrictGoldenMismatch,
+            ));
+        }
+    }
+
+    None
+}
@@ -44,7 +44,7 @@ pub(crate) fn discover_golden_expectation(
 ///      silently treated as absent. Symlink entries are rejected so a golden tree
 ///      cannot follow an authored link outside its owning backend or inventory the same
 ///      file twice.
-fn discover_golden_files(root: &Path) -> Result<GoldenFileInventory, String> {
+pub(super) fn discover_golden_files(root: &Path) -> Result<GoldenFileInventory, String> {
     // The direct golden parent (e.g. case/golden) must not be a symlink. Without
     // this guard, a symlinked parent whose target contains the backend directory
     // would bypass the backend-root symlink rejection below and let outside files
@@ -6,16 +6,19 @@
 
 mod artifacts;
 mod diagnostics;
+mod formatter;
 mod goldens;
 mod rendered_output;
 mod warnings;
 mod wasm;
 
-pub(crate) use goldens::discover_golden_expectation;
 #[cfg(test)]
-pub(crate) use rendered_output::{
-    RuntimeEvent, SlotOutput, extract_script_blocks, parse_harness_output,
+pub(crate) use crate::projects::node_runner::{
+    RuntimeEvent, extract_script_blocks, parse_harness_output,
 };
+pub(crate) use goldens::discover_golden_expectation;
+#[cfg(test)]
+pub(crate) use rendered_output::SlotOutput;
 
 #[cfg(test)]
 use super::GoldenMode;
@@ -162,6 +165,10 @@ pub(crate) fn validate_success_result(
         return fail(build_result, reason, kind);
     }
 
+    if let Some((reason, kind)) = formatter::validate_formatter_goldens(&case.fixture_root) {
+        return fail(build_result, reason, kind);
+    }
+
     if expectation.rendered_output.is_present()
         && let Some((reason, kind)) =
             rendered_output::validate_rendered_output(&build_result, &expectation.rendered_output)
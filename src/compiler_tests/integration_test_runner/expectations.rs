@@ -657,7 +657,7 @@ fn validate_artifact_assertion_shape(
     assertion: &ArtifactAssertionToml,
 ) -> Result<(), String> {
     match kind {
-        ArtifactKind::Html | ArtifactKind::Js => {
+        ArtifactKind::Html | ArtifactKind::Js | ArtifactKind::Text => {
             if assertion.validate_wasm
                 || !assertion.must_export.is_empty()
                 || !assertion.must_import.is_empty()
@@ -927,6 +927,7 @@ fn parse_artifact_kind(
         "js" => Ok(ArtifactKind::Js),
         "wasm" => Ok(ArtifactKind::Wasm),
         "binary" => Ok(ArtifactKind::Binary),
+        "text" => Ok(ArtifactKind::Text),
         other => Err(format!(
             "Expectation file '{}' {} has unsupported artifact kind '{}'.",
             path.display(),
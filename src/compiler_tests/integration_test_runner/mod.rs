@@ -38,6 +38,8 @@ pub(crate) const MANIFEST_FILE_NAME: &str = "manifest.toml";
 pub(crate) const EXPECT_FILE_NAME: &str = "expect.toml";
 pub(crate) const INPUT_DIR_NAME: &str = "input";
 pub(crate) const GOLDEN_DIR_NAME: &str = "golden";
+/// `golden/fmt/` holds `bean fmt` output for the matching `input/` files.
+pub(crate) const FORMATTER_GOLDEN_DIR_NAME: &str = "fmt";
 pub(crate) const FAILURE_TRIAGE_REPORT_PATH: &str =
     "target/test-reports/integration_failure_triage.json";
 pub(crate) const SUITE_INVENTORY_REPORT_PATH: &str =
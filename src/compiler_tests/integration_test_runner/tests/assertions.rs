@@ -14,8 +14,9 @@ use super::super::types::{
     SecondaryLabelAssertion, SuccessContract,
 };
 use super::super::{
-    BackendId, DiagnosticMatchMode, ExpectedOutcome, FailureExpectation, FailureKind, GoldenMode,
-    SuccessExpectation, TestCaseSpec, WarningExpectation,
+    ArtifactAssertion, ArtifactKind, BackendId, DiagnosticMatchMode, ExpectedOutcome,
+    FailureExpectation, FailureKind, GoldenMode, SuccessExpectation, TestCaseSpec,
+    WarningExpectation,
 };
 use crate::build_system::build::{BuildResult, CleanupPolicy, FileKind, OutputFile, Project};
 use crate::compiler_frontend::compiler_messages::compiler_errors::CompilerMessages;
@@ -868,6 +869,7 @@ fn build_result_with_output_files(files: Vec<(PathBuf, FileKind)>) -> BuildResul
         config: Config::new(PathBuf::from("main.bst")),
         warnings: Vec::new(),
         string_table: StringTable::new(),
+        module_reuse: None,
     }
 }
 
@@ -996,6 +998,57 @@ fn acceptance_only_html_wasm_baseline_rejects_invalid_wasm() {
     );
 }
 
+fn text_artifact_expectation(must_contain: &[&str]) -> SuccessExpectation {
+    SuccessExpectation {
+        artifact_assertions: vec![ArtifactAssertion {
+            path: "robots.txt".to_string(),
+            kind: ArtifactKind::Text,
+            must_contain: must_contain.iter().map(|text| text.to_string()).collect(),
+            must_not_contain: Vec::new(),
+            must_contain_in_order: Vec::new(),
+            must_contain_exactly_once: Vec::new(),
+            normalized_contains: Vec::new(),
+            normalized_not_contains: Vec::new(),
+            validate_wasm: false,
+            must_export: Vec::new(),
+            must_import: Vec::new(),
+        }],
+        ..absence_expectation(Vec::new())
+    }
+}
+
+#[test]
+fn text_artifact_assertions_read_utf8_byte_artifacts() {
+    let build_result = || {
+        build_result_with_output_files(vec![
+            (
+                PathBuf::from("index.html"),
+                FileKind::Html(VALID_HTML.to_owned()),
+            ),
+            (
+                PathBuf::from("robots.txt"),
+                FileKind::Bytes(b"User-agent: *\nDisallow:\n".to_vec()),
+            ),
+        ])
+    };
+
+    let expectation = text_artifact_expectation(&["User-agent: *"]);
+    let case = success_test_case(BackendId::Html, expectation.clone());
+    let result = validate_success_result(&case, build_result(), &expectation);
+    assert!(result.passed, "{:?}", result.failure_reason);
+
+    let expectation = text_artifact_expectation(&["Sitemap:"]);
+    let case = success_test_case(BackendId::Html, expectation.clone());
+    let result = validate_success_result(&case, build_result(), &expectation);
+    assert!(!result.passed);
+    assert!(
+        result
+            .failure_reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("did not contain required fragment 'Sitemap:'"))
+    );
+}
+
 #[test]
 fn absence_contract_passes_when_forbidden_path_not_built() {
     let expectation = absence_expectation(vec!["api/index.html".to_string()]);
@@ -1393,6 +1446,7 @@ fn nested_golden_validation_compares_relative_paths() {
         config: Config::new(PathBuf::from("main.bst")),
         warnings: Vec::new(),
         string_table: StringTable::new(),
+        module_reuse: None,
     };
     let golden = discover_golden_expectation(&golden_dir, None)
         .expect("golden inventory should be discovered");
@@ -178,6 +178,8 @@ pub(crate) enum ArtifactKind {
     Js,
     Wasm,
     Binary,
+    /// UTF-8 content emitted as a byte artifact, such as `sitemap.xml` or `robots.txt`.
+    Text,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -32,10 +32,14 @@ pub mod projects {
     pub mod check;
     pub mod cli;
     pub mod dev_server;
+    pub mod fix;
+    pub mod formatter;
     pub(crate) mod html_project;
-    // Kept intentionally in pre-alpha as the future CLI entrypoint for interactive
-    // template experimentation. This remains outside the default command surface.
+    pub(crate) mod js_module_project;
+    pub mod language_server;
+    pub(crate) mod node_runner;
     pub(crate) mod repl;
     pub(crate) mod routing;
     pub mod settings;
+    pub(crate) mod wasi_project;
 }
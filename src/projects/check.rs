@@ -6,22 +6,27 @@
 //! remaining backend-agnostic.
 
 use crate::build_system::build::{
-    BuildBootstrap, ProjectBuilder, bootstrap_project_build, collect_frontend_warnings,
+    BuildBootstrap, Module, ProjectBuilder, bootstrap_project_build, collect_frontend_warnings,
+};
+use crate::build_system::create_project_modules::{
+    compile_project_frontend, frontend_source_roots,
 };
-use crate::build_system::create_project_modules::compile_project_frontend;
 use crate::build_system::path_validation::check_if_valid_path;
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
 use crate::compiler_frontend::display_messages::{
-    print_compiler_messages, print_terse_compiler_messages,
+    MessageFormat, print_compiler_messages, print_json_compiler_messages,
+    print_terse_compiler_messages,
 };
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::projects::html_project::html_project_builder::HtmlProjectBuilder;
 use saying::say;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct CheckOptions {
     pub terse: bool,
+    pub message_format: MessageFormat,
 }
 
 struct CheckOutcome {
@@ -29,6 +34,22 @@ struct CheckOutcome {
     duration: Duration,
 }
 
+/// Frontend-only compilation result shared by `bean check` and editor tooling.
+///
+/// WHAT: keeps the compiled modules beside the diagnostics and the string table they resolve
+///       against.
+/// WHY: the language server needs the same validated HIR and type environments that `check`
+///      discards, without running a second frontend flow with different project semantics.
+pub(crate) struct FrontendCheck {
+    /// Compiled modules. Empty when the frontend stopped with errors.
+    pub(crate) modules: Vec<Module>,
+    /// Errors or warnings plus the string table shared by every module.
+    pub(crate) messages: CompilerMessages,
+    /// Directories that relative diagnostic scopes resolve against, most specific first.
+    /// Empty when the check stopped before the project config was loaded.
+    pub(crate) source_roots: Vec<PathBuf>,
+}
+
 pub fn run_check(path: &str, options: CheckOptions) {
     crate::timing::start_command_timing();
     let command_start = crate::timing::start_pipeline_timing();
@@ -37,7 +58,10 @@ pub fn run_check(path: &str, options: CheckOptions) {
     let warning_count = outcome.messages.warning_count();
 
     let rendering_start = crate::timing::start_pipeline_timing();
-    if options.terse {
+    if options.message_format == MessageFormat::Json {
+        // JSON output is the diagnostic stream only, so consumers can parse every line.
+        print_json_compiler_messages(&outcome.messages);
+    } else if options.terse {
         print_terse_compiler_messages(&outcome.messages);
         println!(
             "{}",
@@ -58,6 +82,21 @@ pub fn run_check(path: &str, options: CheckOptions) {
 
 fn execute_check(path: &str) -> CheckOutcome {
     let start = Instant::now();
+    let FrontendCheck { messages, .. } = check_project_frontend(path);
+
+    CheckOutcome {
+        messages,
+        duration: start.elapsed(),
+    }
+}
+
+/// Run Stage 0 and the full frontend for `path` without any backend lowering.
+///
+/// WHAT: validates the path, bootstraps the HTML project builder surface and compiles every module
+///       through borrow checking.
+/// WHY: one owner for the check flow keeps `bean check` and `bean lsp` reporting identical
+///      diagnostics for the same project.
+pub(crate) fn check_project_frontend(path: &str) -> FrontendCheck {
     let normalized_path = normalize_entry_path(path);
 
     let mut path_string_table = StringTable::new();
@@ -69,9 +108,10 @@ fn execute_check(path: &str) -> CheckOutcome {
         }
         Err(error) => {
             log_check_timing("command.check.path_validation", path_validation_start);
-            return CheckOutcome {
+            return FrontendCheck {
+                modules: Vec::new(),
                 messages: CompilerMessages::from_error(error, path_string_table),
-                duration: start.elapsed(),
+                source_roots: Vec::new(),
             };
         }
     };
@@ -96,15 +136,17 @@ fn execute_check(path: &str) -> CheckOutcome {
         }
         Err(messages) => {
             log_check_timing("command.check.bootstrap", bootstrap_start);
-            return CheckOutcome {
+            return FrontendCheck {
+                modules: Vec::new(),
                 messages,
-                duration: start.elapsed(),
+                source_roots: Vec::new(),
             };
         }
     };
 
+    let source_roots = frontend_source_roots(&config);
     let compile_frontend_start = crate::timing::start_pipeline_timing();
-    let messages = match compile_project_frontend(
+    match compile_project_frontend(
         &mut config,
         &[],
         &style_directives,
@@ -117,20 +159,23 @@ fn execute_check(path: &str) -> CheckOutcome {
                 compile_frontend_start,
             );
             let warnings = collect_frontend_warnings(&modules);
-            CompilerMessages::from_diagnostics(warnings, string_table)
+            FrontendCheck {
+                modules,
+                messages: CompilerMessages::from_diagnostics(warnings, string_table),
+                source_roots,
+            }
         }
         Err(messages) => {
             log_check_timing(
                 "command.check.compile_project_frontend",
                 compile_frontend_start,
             );
-            messages
+            FrontendCheck {
+                modules: Vec::new(),
+                messages,
+                source_roots,
+            }
         }
-    };
-
-    CheckOutcome {
-        messages,
-        duration: start.elapsed(),
     }
 }
 
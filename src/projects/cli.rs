@@ -7,14 +7,22 @@ use crate::build_system::build;
 use crate::build_system::build::BuildResult;
 use crate::compiler_frontend::Flag;
 use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
-use crate::compiler_frontend::display_messages::{print_compiler_messages, print_formatted_error};
+use crate::compiler_frontend::display_messages::{
+    MessageFormat, print_compiler_messages, print_formatted_error, print_json_compiler_messages,
+};
 use crate::compiler_tests::integration_test_runner::{
     BackendId, IntegrationRunSummary, TestRunnerOptions, run_all_test_cases,
 };
 use crate::projects::check::{self, CheckOptions};
 use crate::projects::dev_server::{self, DevServerOptions};
+use crate::projects::fix::{self, FixOptions};
+use crate::projects::formatter::{self, FmtOptions};
 use crate::projects::html_project::html_project_builder::HtmlProjectBuilder;
 use crate::projects::html_project::new_html_project::NewHtmlProjectOptions;
+use crate::projects::js_module_project::js_module_project_builder::JsModuleProjectBuilder;
+use crate::projects::language_server;
+use crate::projects::repl;
+use crate::projects::wasi_project::wasi_project_builder::WasiProjectBuilder;
 use saying::say;
 use std::time::Instant;
 use std::{env, process};
@@ -29,13 +37,26 @@ enum Command {
     Build {
         path: String,
         flags: Vec<Flag>,
+        message_format: MessageFormat,
+        target: Option<BuildTarget>,
     }, // Builds a file or project
 
     Check {
         path: String,
         terse: bool,
+        message_format: MessageFormat,
     }, // Runs frontend-only compilation without writing artefacts
 
+    Fix {
+        path: String,
+        dry_run: bool,
+    }, // Applies machine-applicable compiler suggestions to source files
+
+    Fmt {
+        path: String,
+        check: bool,
+    }, // Re-prints source files in canonical layout
+
     // Runs a hot reloading dev server that can be accessed in the browser
     // Will only support HTML projects for now
     Dev {
@@ -44,12 +65,61 @@ enum Command {
         flags: Vec<Flag>,
     },
 
+    // Runs the language server over stdio for editor integrations
+    LanguageServer,
+
+    // Starts an interactive session that evaluates snippets through the frontend
+    Repl,
+
     Help,
     CompilerTests {
         options: TestRunnerOptions,
     }, // Runs or lists compiler integration tests with composable selection filters
 }
 
+/// Project builder selected by `bean build --target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BuildTarget {
+    Html,
+    Wasi,
+    Js,
+}
+
+impl BuildTarget {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "html" => Some(BuildTarget::Html),
+            "wasi" => Some(BuildTarget::Wasi),
+            "js" => Some(BuildTarget::Js),
+            _ => None,
+        }
+    }
+
+    fn project_builder(self) -> build::ProjectBuilder {
+        match self {
+            BuildTarget::Html => build::ProjectBuilder::new(Box::new(HtmlProjectBuilder::new())),
+            BuildTarget::Wasi => build::ProjectBuilder::new(Box::new(WasiProjectBuilder::new())),
+            BuildTarget::Js => build::ProjectBuilder::new(Box::new(JsModuleProjectBuilder::new())),
+        }
+    }
+}
+
+/// Resolves the build target from the flag, falling back to the `project` config value.
+///
+/// WHY: config errors are reported by the real build, so an unreadable config falls back to
+/// the HTML builder here instead of failing twice.
+fn resolve_build_target(target: Option<BuildTarget>, path: &str) -> BuildTarget {
+    if let Some(target) = target {
+        return target;
+    }
+
+    let html_builder = BuildTarget::Html.project_builder();
+    build::configured_project_kind(&html_builder, path)
+        .as_deref()
+        .and_then(BuildTarget::parse)
+        .unwrap_or(BuildTarget::Html)
+}
+
 pub fn start_cli() {
     let compiler_args: Vec<String> = env::args().collect();
     let cli_args = &compiler_args[1..];
@@ -101,10 +171,15 @@ pub fn start_cli() {
             }
         }
 
-        Command::Build { path, flags } => {
+        Command::Build {
+            path,
+            flags,
+            message_format,
+            target,
+        } => {
             crate::timing::start_command_timing();
             let start = Instant::now();
-            let project_builder = build::ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+            let project_builder = resolve_build_target(target, &path).project_builder();
             match build::build_project(&project_builder, &path, &flags) {
                 Ok(build_result) => {
                     let output_root = if build_result.config.entry_dir.is_dir() {
@@ -114,12 +189,20 @@ pub fn start_cli() {
                             Ok(path) => path,
                             Err(error) => {
                                 log_cli_total("command.build.total", start);
-                                print_formatted_error(
-                                    CompilerError::compiler_error(format!(
-                                        "Could not resolve current directory for build outputs: {error}"
-                                    )),
-                                    &build_result.string_table,
-                                );
+                                let error = CompilerError::compiler_error(format!(
+                                    "Could not resolve current directory for build outputs: {error}"
+                                ));
+                                match message_format {
+                                    MessageFormat::Human => {
+                                        print_formatted_error(error, &build_result.string_table)
+                                    }
+                                    MessageFormat::Json => {
+                                        print_json_compiler_messages(&CompilerMessages::from_error(
+                                            error,
+                                            build_result.string_table,
+                                        ))
+                                    }
+                                }
                                 crate::timing::print_command_timing_summary();
                                 return;
                             }
@@ -139,28 +222,49 @@ pub fn start_cli() {
                     log_cli_timing("command.build.output_write", output_write_start);
 
                     match write_result {
-                        Ok(()) => {
+                        Ok(_) => {
                             let duration = start.elapsed();
                             log_cli_total("command.build.total", start);
-                            print_build_message(build_result, duration);
+                            print_build_message(build_result, duration, message_format);
                         }
                         Err(mut messages) => {
                             log_cli_total("command.build.total", start);
                             messages.extend_diagnostics(build_result.warnings);
-                            print_compiler_messages(messages);
+                            print_messages(messages, message_format);
                         }
                     }
                 }
                 Err(messages) => {
                     log_cli_total("command.build.total", start);
-                    print_compiler_messages(messages);
+                    print_messages(messages, message_format);
                 }
             }
             crate::timing::print_command_timing_summary();
         }
 
-        Command::Check { path, terse } => {
-            check::run_check(&path, CheckOptions { terse });
+        Command::Check {
+            path,
+            terse,
+            message_format,
+        } => {
+            check::run_check(
+                &path,
+                CheckOptions {
+                    terse,
+                    message_format,
+                },
+            );
+        }
+
+        Command::Fix { path, dry_run } => {
+            fix::run_fix(&path, FixOptions { dry_run });
+        }
+
+        Command::Fmt { path, check } => {
+            let exit_code = formatter::run_fmt(&path, FmtOptions { check });
+            if exit_code != 0 {
+                process::exit(exit_code);
+            }
         }
 
         Command::Dev {
@@ -176,6 +280,16 @@ pub fn start_cli() {
             }
         }
 
+        Command::LanguageServer => {
+            // Stdout carries the protocol stream, so failures are reported on stderr.
+            if let Err(error) = language_server::run_language_server() {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        }
+
+        Command::Repl => repl::start_repl_session(),
+
         Command::CompilerTests { options } => match run_all_test_cases(options) {
             Ok(summary) => {
                 let exit_code = integration_tests_exit_code(summary);
@@ -244,8 +358,17 @@ fn get_command(args: &[String]) -> Result<Command, String> {
 
         Some("check") => parse_check_command(args),
 
+        Some("fix") => parse_fix_command(args),
+
+        Some("fmt") => parse_fmt_command(args),
+
         Some("dev") => parse_dev_command(args),
 
+        Some("lsp") => parse_lsp_command(args),
+
+        Some("repl") if args.len() == 1 => Ok(Command::Repl),
+        Some("repl") => Err(String::from("Repl command accepts no arguments.")),
+
         Some("tests") => parse_tests_command(args),
 
         Some(other) => Err(format!("Invalid command: '{other}'")),
@@ -295,10 +418,26 @@ fn parse_new_command(args: &[String]) -> Result<Command, String> {
 fn parse_build_command(args: &[String]) -> Result<Command, String> {
     let mut path = String::new();
     let mut flags = Vec::new();
+    let mut message_format = MessageFormat::default();
+    let mut target = None;
     let mut index = 1usize;
 
     while let Some(arg) = args.get(index) {
         match arg.as_str() {
+            "--message-format" => {
+                message_format = parse_message_format_value(args, index)?;
+                index += 2;
+            }
+            "--target" => {
+                let value = args.get(index + 1).map(String::as_str).unwrap_or_default();
+                let Some(parsed) = BuildTarget::parse(value) else {
+                    return Err(format!(
+                        "Invalid value for --target: '{value}'. Expected 'html', 'wasi' or 'js'."
+                    ));
+                };
+                target = Some(parsed);
+                index += 2;
+            }
             "--release" => {
                 flags.push(Flag::Release);
                 index += 1;
@@ -309,7 +448,7 @@ fn parse_build_command(args: &[String]) -> Result<Command, String> {
             }
             _ if arg.starts_with("--") => {
                 return Err(format!(
-                    "Unknown build flag: '{arg}'. Supported build flags are {}.",
+                    "Unknown build flag: '{arg}'. Supported build flags are {}, --target <html|wasi|js>, and --message-format <human|json>.",
                     BUILD_FLAGS.join(", ")
                 ));
             }
@@ -326,7 +465,12 @@ fn parse_build_command(args: &[String]) -> Result<Command, String> {
         }
     }
 
-    Ok(Command::Build { path, flags })
+    Ok(Command::Build {
+        path,
+        flags,
+        message_format,
+        target,
+    })
 }
 
 fn parse_tests_command(args: &[String]) -> Result<Command, String> {
@@ -436,6 +580,7 @@ fn parse_tests_command(args: &[String]) -> Result<Command, String> {
 fn parse_check_command(args: &[String]) -> Result<Command, String> {
     let mut path = String::new();
     let mut terse = false;
+    let mut message_format = MessageFormat::default();
     let mut index = 1usize;
 
     while let Some(arg) = args.get(index) {
@@ -444,9 +589,13 @@ fn parse_check_command(args: &[String]) -> Result<Command, String> {
                 terse = true;
                 index += 1;
             }
+            "--message-format" => {
+                message_format = parse_message_format_value(args, index)?;
+                index += 2;
+            }
             _ if arg.starts_with("--") => {
                 return Err(format!(
-                    "Unknown check flag: '{arg}'. Supported check flag is --terse."
+                    "Unknown check flag: '{arg}'. Supported check flags are --terse and --message-format <human|json>."
                 ));
             }
             _ => {
@@ -462,7 +611,83 @@ fn parse_check_command(args: &[String]) -> Result<Command, String> {
         }
     }
 
-    Ok(Command::Check { path, terse })
+    if terse && message_format == MessageFormat::Json {
+        return Err(String::from(
+            "Check command cannot combine --terse with --message-format json.",
+        ));
+    }
+
+    Ok(Command::Check {
+        path,
+        terse,
+        message_format,
+    })
+}
+
+/// Parse the value following `--message-format` at `flag_index`.
+fn parse_message_format_value(args: &[String], flag_index: usize) -> Result<MessageFormat, String> {
+    match args.get(flag_index + 1) {
+        Some(value) if !value.starts_with("--") => MessageFormat::parse(value),
+        _ => Err(String::from("Missing value for --message-format.")),
+    }
+}
+
+fn parse_fix_command(args: &[String]) -> Result<Command, String> {
+    let mut path = String::new();
+    let mut dry_run = false;
+
+    for arg in &args[1..] {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            _ if arg.starts_with("--") => {
+                return Err(format!(
+                    "Unknown fix flag: '{arg}'. Supported fix flags are --dry-run."
+                ));
+            }
+            _ if path.is_empty() => path = arg.to_owned(),
+            _ => {
+                return Err(String::from(
+                    "Fix command accepts at most one path argument.",
+                ));
+            }
+        }
+    }
+
+    Ok(Command::Fix { path, dry_run })
+}
+
+fn parse_fmt_command(args: &[String]) -> Result<Command, String> {
+    let mut path = String::new();
+    let mut check = false;
+
+    for arg in &args[1..] {
+        match arg.as_str() {
+            "--check" => check = true,
+            _ if arg.starts_with("--") => {
+                return Err(format!(
+                    "Unknown fmt flag: '{arg}'. Supported fmt flags are --check."
+                ));
+            }
+            _ if path.is_empty() => path = arg.to_owned(),
+            _ => {
+                return Err(String::from(
+                    "Fmt command accepts at most one path argument.",
+                ));
+            }
+        }
+    }
+
+    Ok(Command::Fmt { path, check })
+}
+
+fn parse_lsp_command(args: &[String]) -> Result<Command, String> {
+    // Editor clients commonly pass `--stdio`; stdio is the only transport, so accept it as a no-op.
+    match args.get(1).map(String::as_str) {
+        None | Some("--stdio") if args.len() <= 2 => Ok(Command::LanguageServer),
+        _ => Err(String::from(
+            "Lsp command accepts no arguments other than --stdio.",
+        )),
+    }
 }
 
 fn parse_dev_command(args: &[String]) -> Result<Command, String> {
@@ -570,7 +795,11 @@ fn print_help() {
     say!("  build [path]      - Builds a project");
     say!("  check [path]      - Runs frontend-only diagnostics (no artifacts)");
     say!("  dev [path]        - Runs the hot reloading dev server");
+    say!("  fix [path]        - Applies compiler-suggested fixes to source files");
+    say!("  fmt [path]        - Formats source files in canonical layout");
+    say!("  lsp               - Runs the language server over stdio");
     say!("  new html [path] [--force] - Creates an HTML project scaffold");
+    say!("  repl              - Starts an interactive session");
     say!("  tests [options]     - Runs or lists the integration test suite");
 
     say!(Green Bold "\nBuild and dev flags:");
@@ -586,6 +815,14 @@ fn print_help() {
     say!("  --audit                 (write the full suite inventory without compiling cases)");
     say!("\nCheck command options:");
     say!("  --terse                (compact one-line diagnostics)");
+    say!("\nBuild command options:");
+    say!("  --target <target>      (html, wasi or js; defaults to the config.bst project value)");
+    say!("\nBuild and check options:");
+    say!("  --message-format <fmt> (human or json; json prints one diagnostic record per line)");
+    say!("\nFix command options:");
+    say!("  --dry-run              (print a unified diff instead of writing files)");
+    say!("\nFmt command options:");
+    say!("  --check                (list unformatted files and exit non-zero without writing)");
     say!("\nNew command options:");
     say!("  --force                (allows replacing existing scaffold files)");
     say!("\nDev command options:");
@@ -594,7 +831,27 @@ fn print_help() {
     say!("  --poll-interval-ms <ms>  (default: 300)");
 }
 
-fn print_build_message(build_result: BuildResult, duration: std::time::Duration) {
+/// Print diagnostics in the format selected on the command line.
+fn print_messages(messages: CompilerMessages, message_format: MessageFormat) {
+    match message_format {
+        MessageFormat::Human => print_compiler_messages(messages),
+        MessageFormat::Json => print_json_compiler_messages(&messages),
+    }
+}
+
+fn print_build_message(
+    build_result: BuildResult,
+    duration: std::time::Duration,
+    message_format: MessageFormat,
+) {
+    if message_format == MessageFormat::Json {
+        // Keep stdout a pure diagnostic stream; success is signalled by the absence of errors.
+        let messages =
+            CompilerMessages::from_diagnostics(build_result.warnings, build_result.string_table);
+        print_json_compiler_messages(&messages);
+        return;
+    }
+
     say!(
         "\n🌱 Built ",
         Blue build_result.project.output_files.len(),
@@ -1,19 +1,23 @@
 //! Build execution and watch-triggered rebuild coordination for the dev server.
 //!
 //! This module delegates compilation and artifact writing to the core build APIs, then translates
-//! build outcomes into dev-server state updates and SSE reload broadcasts.
+//! build outcomes into dev-server state updates and typed SSE update broadcasts.
 
-use crate::build_system::build::{self, BuildResult, ProjectBuilder, WriteMode, WriteOptions};
+use crate::build_system::build::{
+    self, BuildResult, OutputWriteReport, ProjectBuilder, WriteMode, WriteOptions,
+};
+use crate::build_system::incremental::{IncrementalBuildCache, ModuleReuseStats};
 use crate::compiler_frontend::Flag;
 use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages, ErrorType};
 use crate::compiler_frontend::display_messages::print_compiler_messages;
 use crate::projects::dev_server::error_page::{
-    format_compiler_messages, render_compiler_error_page, render_runtime_error_page,
+    BuildErrorOverlay, compiler_error_overlay, format_compiler_messages, render_error_page,
+    runtime_error_overlay,
 };
-use crate::projects::dev_server::sse;
+use crate::projects::dev_server::sse::{self, DevClientEvent};
 use crate::projects::dev_server::state::DevServerState;
 use crate::projects::dev_server::watch;
-use crate::projects::routing::{HtmlSiteConfig, parse_html_site_config};
+use crate::projects::routing::{HtmlSiteConfig, parse_html_site_config, prefix_origin};
 use saying::say;
 use std::io;
 use std::path::{Path, PathBuf};
@@ -28,6 +32,8 @@ pub struct BuildCycleReport {
     /// Structured warnings for successful dev builds, carried to the rebuild loop so terminal
     /// output can print full diagnostics after the success summary.
     pub success_messages: Option<CompilerMessages>,
+    /// Module and page reuse counts for successful incremental builds.
+    pub module_reuse: Option<ModuleReuseStats>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +43,8 @@ pub struct RebuildRunReport {
 
 struct BuildOutcome {
     build_succeeded: bool,
+    /// Output-root-relative files the build's write changed on disk.
+    changed_outputs: Vec<PathBuf>,
     entry_page_rel: Option<PathBuf>,
     html_site_config: Option<HtmlSiteConfig>,
     diagnostics_summary: String,
@@ -47,6 +55,7 @@ struct BuildOutcome {
     /// WHY: `diagnostics_summary` is intentionally brief for SSE/state consumers; terminal output
     /// needs the full diagnostic cards without rebuilding them from summary titles.
     success_messages: Option<CompilerMessages>,
+    module_reuse: Option<ModuleReuseStats>,
     failed_build: Option<BuildFailure>,
     watch_scope: Option<watch::WatchScope>,
 }
@@ -66,16 +75,27 @@ pub trait DevBuildExecutor: Send {
         entry_file: &Path,
         flags: &[Flag],
         output_dir: &Path,
-    ) -> Result<BuildResult, CompilerMessages>;
+    ) -> Result<WrittenBuild, CompilerMessages>;
+}
+
+/// A successful dev build plus the outputs its write changed, used to pick client events.
+pub struct WrittenBuild {
+    pub build_result: BuildResult,
+    pub write_report: OutputWriteReport,
 }
 
+/// Builds through the core pipeline, keeping unchanged modules cached between rebuilds.
 pub struct ProjectBuildExecutor {
     builder: ProjectBuilder,
+    cache: IncrementalBuildCache,
 }
 
 impl ProjectBuildExecutor {
     pub fn new(builder: ProjectBuilder) -> Self {
-        Self { builder }
+        Self {
+            builder,
+            cache: IncrementalBuildCache::new(),
+        }
     }
 }
 
@@ -85,7 +105,7 @@ impl DevBuildExecutor for ProjectBuildExecutor {
         entry_file: &Path,
         flags: &[Flag],
         output_dir: &Path,
-    ) -> Result<BuildResult, CompilerMessages> {
+    ) -> Result<WrittenBuild, CompilerMessages> {
         let entry_path = entry_file.to_str().ok_or_else(|| {
             dev_server_error_messages(
                 entry_file,
@@ -93,14 +113,15 @@ impl DevBuildExecutor for ProjectBuildExecutor {
             )
         })?;
 
-        let build_result = build::build_project(&self.builder, entry_path, flags)?;
+        let build_result =
+            build::build_project_incremental(&self.builder, entry_path, flags, &mut self.cache)?;
         let project_entry_dir = entry_file
             .parent()
             .filter(|parent| parent.is_dir())
             .map(Path::to_path_buf)
             .or_else(|| Some(entry_file.to_path_buf()))
             .filter(|path| path.is_dir());
-        if let Err(mut messages) = build::write_project_outputs(
+        let write_report = match build::write_project_outputs(
             &build_result.project,
             &WriteOptions {
                 output_root: output_dir.to_path_buf(),
@@ -109,11 +130,17 @@ impl DevBuildExecutor for ProjectBuildExecutor {
             },
             &build_result.string_table,
         ) {
-            messages.extend_diagnostics(build_result.warnings);
-            return Err(messages);
-        }
+            Ok(write_report) => write_report,
+            Err(mut messages) => {
+                messages.extend_diagnostics(build_result.warnings);
+                return Err(messages);
+            }
+        };
 
-        Ok(build_result)
+        Ok(WrittenBuild {
+            build_result,
+            write_report,
+        })
     }
 }
 
@@ -132,15 +159,17 @@ pub fn run_single_build_cycle(
     let project_root = dev_server_project_root(entry_file);
     let BuildOutcome {
         build_succeeded,
+        changed_outputs,
         entry_page_rel,
         html_site_config,
         diagnostics_summary,
         success_messages,
+        module_reuse,
         failed_build,
         watch_scope,
     } = build_outcome;
 
-    let version = {
+    let (version, events) = {
         // If a previous dev-server task panicked while holding the lock, keep the latest state and
         // continue serving rebuild results instead of crashing the entire watcher loop.
         let mut build_state = match state.build_state.lock() {
@@ -152,51 +181,59 @@ pub fn run_single_build_cycle(
                 poisoned.into_inner()
             }
         };
+        let previous_build_ok = build_state.last_build_ok;
         build_state.last_build_version = build_state.last_build_version.saturating_add(1);
         build_state.last_build_ok = build_succeeded;
         build_state.last_build_messages_summary = diagnostics_summary;
 
-        if build_succeeded {
+        let build_error = if build_succeeded {
             build_state.last_error_html = None;
             build_state.entry_page_rel = entry_page_rel;
             if let Some(html_site_config) = html_site_config {
                 build_state.html_site_config = html_site_config;
             }
+            None
         } else {
-            // Render compiler diagnostics only after the version increments so the error page and
-            // the SSE reload event always point at the same build number.
-            build_state.last_error_html = Some(match failed_build {
-                Some(BuildFailure::CompilerMessages(messages)) => render_compiler_error_page(
-                    &messages,
-                    &project_root,
-                    &build_state.html_site_config.origin,
-                    build_state.last_build_version,
-                ),
-                Some(BuildFailure::RuntimeError { title, details }) => render_runtime_error_page(
-                    &title,
-                    &details,
-                    &build_state.html_site_config.origin,
-                    build_state.last_build_version,
-                ),
-                None => render_runtime_error_page(
+            let overlay = match failed_build {
+                Some(BuildFailure::CompilerMessages(messages)) => {
+                    compiler_error_overlay(&messages, &project_root)
+                }
+                Some(BuildFailure::RuntimeError { title, details }) => {
+                    runtime_error_overlay(&title, &details)
+                }
+                None => runtime_error_overlay(
                     "Build Failed",
                     "The latest build failed, but no diagnostics were stored.",
-                    &build_state.html_site_config.origin,
-                    build_state.last_build_version,
                 ),
-            });
-        }
-
-        build_state.last_build_version
+            };
+            // Render the page only after the version increments so the error page and the SSE
+            // event always point at the same build number.
+            build_state.last_error_html = Some(render_error_page(
+                &overlay,
+                &build_state.html_site_config.origin,
+                build_state.last_build_version,
+            ));
+            Some(overlay)
+        };
+        build_state.last_error_overlay = build_error.clone();
+
+        let events = client_events_for_build(
+            previous_build_ok,
+            build_error.as_ref(),
+            &changed_outputs,
+            &build_state.html_site_config.origin,
+        );
+        (build_state.last_build_version, events)
     };
 
-    let clients_notified = sse::broadcast_reload(state, version);
+    let clients_notified = sse::broadcast_events(state, version, &events);
     BuildCycleReport {
         version,
         build_ok: build_succeeded,
         clients_notified,
         watch_scope,
         success_messages,
+        module_reuse,
     }
 }
 
@@ -233,6 +270,13 @@ pub fn run_builds_until_stable(
                 Blue report.clients_notified,
                 Reset " clients."
             );
+            if let Some(reuse) = report.module_reuse {
+                say!(
+                    Bright Black "  Modules: ", reuse.rebuilt_modules, " rebuilt, ",
+                    reuse.reused_modules, " reused. Pages: ",
+                    reuse.emitted_pages, " emitted, ", reuse.reused_pages, " reused."
+                );
+            }
             if let Some(messages) = report.success_messages {
                 print_compiler_messages(messages);
             }
@@ -325,20 +369,26 @@ fn build_once(
     flags: &[Flag],
     output_dir: &Path,
 ) -> BuildOutcome {
-    let mut build_result = match executor.build_and_write(entry_file, flags, output_dir) {
-        Ok(build_result) => build_result,
+    let WrittenBuild {
+        mut build_result,
+        write_report,
+    } = match executor.build_and_write(entry_file, flags, output_dir) {
+        Ok(written_build) => written_build,
         Err(messages) => {
             return BuildOutcome {
                 build_succeeded: false,
+                changed_outputs: Vec::new(),
                 entry_page_rel: None,
                 html_site_config: None,
                 diagnostics_summary: format_compiler_messages(&messages),
                 success_messages: None,
+                module_reuse: None,
                 failed_build: Some(BuildFailure::CompilerMessages(messages)),
                 watch_scope: None,
             };
         }
     };
+    let module_reuse = build_result.module_reuse;
     let watch_scope = watch::WatchScope::derive(
         &build_result.config.entry_dir,
         Some(&build_result.config),
@@ -352,10 +402,12 @@ fn build_once(
                 let messages = error.into_messages(build_result.string_table.clone());
                 return BuildOutcome {
                     build_succeeded: false,
+                    changed_outputs: write_report.changed_paths,
                     entry_page_rel: None,
                     html_site_config: None,
                     diagnostics_summary: format_compiler_messages(&messages),
                     success_messages: None,
+                    module_reuse: None,
                     failed_build: Some(BuildFailure::CompilerMessages(messages)),
                     watch_scope: Some(watch_scope),
                 };
@@ -387,22 +439,26 @@ fn build_once(
 
         BuildOutcome {
             build_succeeded: true,
+            changed_outputs: write_report.changed_paths,
             entry_page_rel: Some(entry_page_rel),
             html_site_config: Some(html_site_config),
             diagnostics_summary,
             success_messages,
+            module_reuse,
             failed_build: None,
             watch_scope: Some(watch_scope),
         }
     } else {
         BuildOutcome {
             build_succeeded: false,
+            changed_outputs: write_report.changed_paths,
             entry_page_rel: None,
             html_site_config: None,
             diagnostics_summary: String::from(
                 "Build completed, but the project builder did not declare a dev entry page.",
             ),
             success_messages: None,
+            module_reuse: None,
             failed_build: Some(BuildFailure::RuntimeError {
                 title: String::from("Missing Dev Entry"),
                 details: String::from(
@@ -414,6 +470,54 @@ fn build_once(
     }
 }
 
+/// Pick the client events for one finished build.
+///
+/// WHAT: failed builds send their overlay, and the first success after a failure fully reloads so
+///       clients drop the overlay or leave the error page. Otherwise changed stylesheets are swapped in place, changed HTML
+///       pages reload only the clients viewing them, and any other changed output (scripts,
+///       Wasm, images) reloads everything because pages may share it.
+/// WHY: most edits touch one page or one stylesheet, and a full reload loses scroll position and
+///      page state in every open tab.
+fn client_events_for_build(
+    previous_build_ok: bool,
+    build_error: Option<&BuildErrorOverlay>,
+    changed_outputs: &[PathBuf],
+    origin: &str,
+) -> Vec<DevClientEvent> {
+    if let Some(build_error) = build_error {
+        return vec![DevClientEvent::BuildError(build_error.clone())];
+    }
+    if !previous_build_ok {
+        return vec![DevClientEvent::FullReload];
+    }
+
+    let mut stylesheets = Vec::new();
+    let mut routes = Vec::new();
+    for output in changed_outputs {
+        let public_path = prefix_origin(
+            origin,
+            &format!("/{}", output.to_string_lossy().replace('\\', "/")),
+        );
+        match output.extension().and_then(|extension| extension.to_str()) {
+            Some("css") => stylesheets.push(public_path),
+            Some("html") => routes.push(public_path),
+            // Source maps only change together with the page they describe.
+            Some("map") => {}
+            _ => return vec![DevClientEvent::FullReload],
+        }
+    }
+
+    let mut events = Vec::with_capacity(2);
+    if !stylesheets.is_empty() {
+        events.push(DevClientEvent::CssUpdate { stylesheets });
+    }
+    // A page update is sent even when nothing changed so every build still reaches clients.
+    if !routes.is_empty() || events.is_empty() {
+        events.push(DevClientEvent::PageUpdate { routes });
+    }
+    events
+}
+
 fn dev_server_project_root(entry_file: &Path) -> PathBuf {
     if entry_file.is_dir() {
         return entry_file.to_path_buf();
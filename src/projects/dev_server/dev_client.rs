@@ -1,16 +1,156 @@
 //! Shared dev-client snippet helpers for hot reload pages.
 //!
 //! WHAT: owns the injected EventSource marker and script HTML used by both normal pages and
-//! dev-server error pages.
+//! dev-server error pages, plus the error overlay drawn over the page.
 //! WHY: keeping one origin-aware source of truth prevents drift between success and failure views.
 
+use crate::projects::dev_server::error_page::ERROR_STYLES;
+use crate::projects::html_project::js_path::SOURCE_LINE_TABLE_ID;
 use crate::projects::routing::prefix_origin;
 
 pub const DEV_CLIENT_MARKER: &str = "<!-- beanstalk-dev-client -->";
 
+/// Element ID of the failed-build report embedded in pages served while the build is failing.
+pub const BUILD_ERROR_DATA_ID: &str = "bst-build-error";
+
+/// Registers the runtime error listeners and buffers errors until the overlay is ready.
+///
+/// Kept on one line because it is injected into `<head>` ahead of the page bundle, and any added
+/// line would shift the document lines the source line table refers to.
+const ERROR_CAPTURE_JS: &str = "(() => { if (window.__bstDev) return; const dev = window.__bstDev = { errors: [], report(error) { dev.errors.push(error); if (dev.render) dev.render(); } }; window.addEventListener('error', (event) => dev.report({ message: event.message, file: event.filename, line: event.lineno, stack: event.error && event.error.stack })); window.addEventListener('unhandledrejection', (event) => { const reason = event.reason; dev.report({ message: 'Unhandled rejection: ' + ((reason && reason.message) || String(reason)), stack: reason && reason.stack }); }); })();";
+
+/// Overlay placement on top of [`ERROR_STYLES`].
+const OVERLAY_STYLES: &str = "
+.backdrop { position: fixed; inset: 0; z-index: 2147483647; overflow: auto; background: rgba(4, 8, 5, 0.88); color: var(--fg); font-family: Menlo, Monaco, Consolas, \"Liberation Mono\", monospace; }
+header { display: flex; justify-content: space-between; align-items: center; }
+.dismiss { background: none; border: 0; color: var(--muted); font: inherit; font-size: 1.2rem; cursor: pointer; }
+";
+
+/// Early error capture, injected right after the opening `<head>` tag.
+pub fn dev_error_capture_snippet() -> String {
+    format!("<script>{ERROR_CAPTURE_JS}</script>")
+}
+
+/// Injected hot-reload client.
+///
+/// WHAT: reloads on `full-reload`, reloads only when `page-update` lists the current page, and
+/// re-fetches matching `<link rel="stylesheet">` elements on `css-update` without a reload.
+/// `build-error` and captured runtime errors open an overlay over the page. Runtime errors are
+/// located by walking the stack for frames in this page and mapping their lines through the
+/// source line table the HTML builder embeds in dev-server pages.
+/// WHY: keeping scroll position and page state across stylesheet and unrelated-page edits, and
+/// keeping the last good page visible while the build is broken.
 pub fn dev_client_snippet(origin: &str) -> String {
     let sse_path = prefix_origin(origin, "/__beanstalk/events");
+    let styles = serde_json::to_string(&format!("{ERROR_STYLES}{OVERLAY_STYLES}"))
+        .unwrap_or_else(|_| String::from("''"));
     format!(
-        "\n{DEV_CLIENT_MARKER}\n<script>\n  (() => {{\n    const source = new EventSource('{sse_path}');\n    source.addEventListener('reload', () => window.location.reload());\n  }})();\n</script>\n"
+        r#"
+{DEV_CLIENT_MARKER}
+<script>
+  (() => {{
+    {ERROR_CAPTURE_JS}
+    const dev = window.__bstDev;
+    const source = new EventSource('{sse_path}');
+    const routeOf = (path) => path.replace(/\/index\.html$/, '/').replace(/(.)\/$/, '$1');
+    const readJson = (id) => {{
+      const element = document.getElementById(id);
+      return element ? JSON.parse(element.textContent) : null;
+    }};
+    const sourceLines = readJson('{SOURCE_LINE_TABLE_ID}');
+    let buildError = readJson('{BUILD_ERROR_DATA_ID}');
+    const page = window.location.href.split('#')[0];
+    const sourceOf = (line) => {{
+      if (!sourceLines) return null;
+      let found = null;
+      for (const entry of sourceLines.lines) {{
+        if (entry[0] > line - sourceLines.offset) break;
+        found = entry;
+      }}
+      return found && found.length > 1 ? found[1] + ':' + found[2] + ':' + found[3] : null;
+    }};
+    const locate = (error) => {{
+      for (const frame of String(error.stack || '').split('\n')) {{
+        const match = frame.match(/(\S+):(\d+):\d+\)?$/);
+        const located = match && match[1].replace(/^.*[(@]/, '') === page && sourceOf(Number(match[2]));
+        if (located) return located;
+      }}
+      return error.line && (error.file || page).split('#')[0] === page ? sourceOf(error.line) : null;
+    }};
+    let overlay = null;
+    dev.render = () => {{
+      if (!buildError && dev.errors.length === 0) {{
+        if (overlay) overlay.remove();
+        overlay = null;
+        return;
+      }}
+      if (!overlay) {{
+        overlay = document.createElement('bst-error-overlay');
+        overlay.attachShadow({{ mode: 'open' }});
+        document.body.appendChild(overlay);
+      }}
+      const root = overlay.shadowRoot;
+      root.innerHTML = '<style>' + {styles} + '</style><div class="backdrop"><main><section class="card"><header><h1></h1><button class="dismiss" type="button" title="Dismiss">&times;</button></header><div class="meta"></div></section></main></div>';
+      const card = root.querySelector('.card');
+      root.querySelector('h1').textContent = buildError ? buildError.title : 'Runtime Error';
+      root.querySelector('.meta').textContent = buildError
+        ? 'Showing the last successful build. Save a fix to rebuild.'
+        : 'The page threw while running.';
+      root.querySelector('.dismiss').addEventListener('click', () => {{
+        buildError = null;
+        dev.errors.length = 0;
+        dev.render();
+      }});
+      if (buildError) card.insertAdjacentHTML('beforeend', buildError.html);
+      if (dev.errors.length === 0) return;
+      const list = document.createElement('section');
+      list.className = 'diagnostics';
+      for (const error of dev.errors) {{
+        const item = document.createElement('div');
+        item.className = 'diagnostic';
+        const message = document.createElement('p');
+        message.className = 'diagnostic-message';
+        message.textContent = error.message;
+        item.appendChild(message);
+        const location = locate(error);
+        if (location) {{
+          const frame = document.createElement('div');
+          frame.className = 'source-frame source-location';
+          frame.textContent = '--> ' + location;
+          item.appendChild(frame);
+        }}
+        list.appendChild(item);
+      }}
+      card.appendChild(list);
+    }};
+    dev.render();
+    source.addEventListener('full-reload', () => window.location.reload());
+    source.addEventListener('build-error', (event) => {{
+      if (document.body.hasAttribute('data-bst-error-page')) {{
+        window.location.reload();
+        return;
+      }}
+      buildError = JSON.parse(event.data);
+      dev.render();
+    }});
+    source.addEventListener('page-update', (event) => {{
+      const current = routeOf(window.location.pathname);
+      if (JSON.parse(event.data).routes.some((route) => routeOf(route) === current)) {{
+        window.location.reload();
+      }}
+    }});
+    source.addEventListener('css-update', (event) => {{
+      const {{ version, stylesheets }} = JSON.parse(event.data);
+      for (const link of document.querySelectorAll('link[rel="stylesheet"]')) {{
+        const url = new URL(link.href, window.location.href);
+        if (stylesheets.includes(url.pathname)) {{
+          url.searchParams.set('bst-v', version);
+          link.href = url.href;
+        }}
+      }}
+    }});
+  }})();
+</script>
+"#
     )
 }
@@ -1,7 +1,8 @@
-//! Error page rendering for dev-server build/runtime failures.
+//! Error page and overlay rendering for dev-server build/runtime failures.
 //!
-//! Compiler diagnostics are converted into escaped HTML so failed builds still render safely and
-//! remain connected to hot reload via the injected SSE client snippet.
+//! Compiler diagnostics are converted into escaped HTML so failed builds still render safely.
+//! The same markup feeds the dev client overlay drawn over the last good page, and the
+//! standalone error page used when no page has been built yet.
 
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
 use crate::compiler_frontend::compiler_messages::render::dev_server;
@@ -10,6 +11,163 @@ use std::fmt::Write;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Styles for the error page and the dev client overlay.
+///
+/// `:host` applies the palette inside the overlay's shadow root, which `:root` cannot reach.
+pub const ERROR_STYLES: &str = r#":root,
+:host {
+  color-scheme: dark;
+  --bg: #0a120b;
+  --bg-glow: rgba(80, 180, 100, 0.20);
+  --card: #061004e0;
+  --panel: #0d160e;
+  --fg: #f2f5f8;
+  --accent: #fd494b;
+  --muted: #9bb09b;
+  --border: #243a25;
+  --link: #7fd97f;
+  --warning: #ff8f57;
+  --shadow: rgba(0, 0, 0, 0.35);
+}
+body {
+  margin: 0;
+  min-height: 100vh;
+  background:
+    radial-gradient(circle at top, var(--bg-glow), transparent 36%),
+    var(--bg);
+  color: var(--fg);
+  font-family: Menlo, Monaco, Consolas, "Liberation Mono", monospace;
+}
+main {
+  max-width: 1040px;
+  margin: 2.5rem auto;
+  padding: 0 1rem;
+}
+.card {
+  background: var(--card);
+  border: 1px solid var(--border);
+  border-radius: 14px;
+  box-shadow: 0 20px 60px var(--shadow);
+  overflow: hidden;
+}
+header {
+  padding: 1rem 1.2rem 0.95rem;
+  border-bottom: 1px solid var(--border);
+  background: rgba(255, 255, 255, 0.02);
+}
+h1 {
+  margin: 0;
+  font-size: 1.1rem;
+  color: var(--accent);
+}
+.meta {
+  padding: 0.8rem 1.2rem;
+  color: var(--muted);
+  font-size: 0.9rem;
+  border-bottom: 1px solid var(--border);
+  background: rgba(255, 255, 255, 0.02);
+}
+.msg {
+  margin: 0;
+  padding: 1.3rem;
+  line-height: 1.45;
+  font-size: 0.92rem;
+  white-space: pre-wrap;
+}
+.diagnostics {
+  padding: 1.1rem;
+  display: grid;
+  gap: 0.9rem;
+}
+.diagnostic {
+  border: 1px solid var(--border);
+  border-radius: 10px;
+  background: var(--panel);
+  padding: 1rem 1rem 0.95rem;
+}
+.diagnostic-head {
+  display: flex;
+  flex-wrap: wrap;
+  gap: 0.55rem;
+  align-items: center;
+  margin-bottom: 0.7rem;
+}
+.badge {
+  display: inline-flex;
+  align-items: center;
+  border-radius: 999px;
+  padding: 0.22rem 0.55rem;
+  font-size: 0.74rem;
+  letter-spacing: 0.04em;
+  background: rgba(255, 122, 144, 0.16);
+  color: var(--accent);
+}
+.badge.warning {
+  background: rgba(255, 212, 121, 0.16);
+  color: var(--warning);
+}
+.kind {
+  color: var(--muted);
+  font-size: 0.86rem;
+}
+.diagnostic-message {
+  margin: 0 0 0.8rem;
+  line-height: 1.5;
+}
+.detail-list {
+  margin: 0;
+  padding: 0;
+  list-style: none;
+  display: grid;
+  gap: 0.45rem;
+}
+.detail-list li {
+  color: var(--muted);
+  line-height: 1.45;
+}
+.detail-label {
+  color: var(--fg);
+  margin-right: 0.45rem;
+}
+a {
+  color: var(--link);
+  text-decoration: none;
+}
+a:hover {
+  text-decoration: underline;
+}
+.empty-state {
+  padding: 1.3rem;
+  color: var(--muted);
+}
+.source-frame {
+  margin: 0.8rem 0;
+  padding: 0.85rem;
+  border: 1px solid var(--border);
+  border-radius: 8px;
+  background: rgba(0, 0, 0, 0.18);
+  white-space: pre;
+  overflow-x: auto;
+}
+.source-location {
+  color: var(--link);
+}
+.source-line-number {
+  color: var(--muted);
+}
+.source-caret {
+  color: var(--accent);
+}
+.guidance {
+  margin-top: 0.7rem;
+  color: var(--muted);
+}
+.open-source {
+  display: inline-block;
+  margin-top: 0.8rem;
+}
+"#;
+
 fn current_timestamp_unix_seconds() -> u64 {
     match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(duration) => duration.as_secs(),
@@ -54,14 +212,30 @@ fn write_structured_diagnostic_summary(messages: &CompilerMessages, output: &mut
     }
 }
 
-pub fn render_compiler_error_page(
+/// Failed-build report shown by the dev client overlay and the standalone error page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildErrorOverlay {
+    pub title: String,
+    /// Escaped diagnostics markup styled by [`ERROR_STYLES`].
+    pub body_html: String,
+}
+
+pub fn compiler_error_overlay(
     messages: &CompilerMessages,
     project_root: &Path,
-    origin: &str,
-    build_version: u64,
-) -> String {
-    let diagnostics_html = render_compiler_diagnostics(messages, project_root);
-    render_error_page_shell("Build Failed", &diagnostics_html, origin, build_version)
+) -> BuildErrorOverlay {
+    BuildErrorOverlay {
+        title: String::from("Build Failed"),
+        body_html: render_compiler_diagnostics(messages, project_root),
+    }
+}
+
+pub fn runtime_error_overlay(title: &str, details: &str) -> BuildErrorOverlay {
+    let escaped_details = escape_html(details);
+    BuildErrorOverlay {
+        title: title.to_owned(),
+        body_html: format!("<pre class=\"msg\">{escaped_details}</pre>"),
+    }
 }
 
 pub fn render_runtime_error_page(
@@ -70,18 +244,17 @@ pub fn render_runtime_error_page(
     origin: &str,
     build_version: u64,
 ) -> String {
-    let escaped_details = escape_html(details);
-    let details_html = format!("<pre class=\"msg\">{escaped_details}</pre>");
-    render_error_page_shell(title, &details_html, origin, build_version)
+    render_error_page(
+        &runtime_error_overlay(title, details),
+        origin,
+        build_version,
+    )
 }
 
-fn render_error_page_shell(
-    title: &str,
-    body_html: &str,
-    origin: &str,
-    build_version: u64,
-) -> String {
-    let escaped_title = escape_html(title);
+/// Standalone error page, used when there is no successfully built page to overlay.
+pub fn render_error_page(overlay: &BuildErrorOverlay, origin: &str, build_version: u64) -> String {
+    let escaped_title = escape_html(&overlay.title);
+    let body_html = &overlay.body_html;
     let timestamp = current_timestamp_unix_seconds();
     let dev_client = dev_client_snippet(origin);
 
@@ -93,160 +266,9 @@ fn render_error_page_shell(
   <meta name="viewport" content="width=device-width, initial-scale=1" />
   <title>Beanstalk Dev Server Error</title>
   <style>
-    :root {{
-      color-scheme: dark;
-      --bg: #0a120b;
-      --bg-glow: rgba(80, 180, 100, 0.20);
-      --card: #061004e0;
-      --panel: #0d160e;
-      --fg: #f2f5f8;
-      --accent: #fd494b;
-      --muted: #9bb09b;
-      --border: #243a25;
-      --link: #7fd97f;
-      --warning: #ff8f57;
-      --shadow: rgba(0, 0, 0, 0.35);
-    }}
-    body {{
-      margin: 0;
-      min-height: 100vh;
-      background:
-        radial-gradient(circle at top, var(--bg-glow), transparent 36%),
-        var(--bg);
-      color: var(--fg);
-      font-family: Menlo, Monaco, Consolas, "Liberation Mono", monospace;
-    }}
-    main {{
-      max-width: 1040px;
-      margin: 2.5rem auto;
-      padding: 0 1rem;
-    }}
-    .card {{
-      background: var(--card);
-      border: 1px solid var(--border);
-      border-radius: 14px;
-      box-shadow: 0 20px 60px var(--shadow);
-      overflow: hidden;
-    }}
-    header {{
-      padding: 1rem 1.2rem 0.95rem;
-      border-bottom: 1px solid var(--border);
-      background: rgba(255, 255, 255, 0.02);
-    }}
-    h1 {{
-      margin: 0;
-      font-size: 1.1rem;
-      color: var(--accent);
-    }}
-    .meta {{
-      padding: 0.8rem 1.2rem;
-      color: var(--muted);
-      font-size: 0.9rem;
-      border-bottom: 1px solid var(--border);
-      background: rgba(255, 255, 255, 0.02);
-    }}
-    .msg {{
-      margin: 0;
-      padding: 1.3rem;
-      line-height: 1.45;
-      font-size: 0.92rem;
-      white-space: pre-wrap;
-    }}
-    .diagnostics {{
-      padding: 1.1rem;
-      display: grid;
-      gap: 0.9rem;
-    }}
-    .diagnostic {{
-      border: 1px solid var(--border);
-      border-radius: 10px;
-      background: var(--panel);
-      padding: 1rem 1rem 0.95rem;
-    }}
-    .diagnostic-head {{
-      display: flex;
-      flex-wrap: wrap;
-      gap: 0.55rem;
-      align-items: center;
-      margin-bottom: 0.7rem;
-    }}
-    .badge {{
-      display: inline-flex;
-      align-items: center;
-      border-radius: 999px;
-      padding: 0.22rem 0.55rem;
-      font-size: 0.74rem;
-      letter-spacing: 0.04em;
-      background: rgba(255, 122, 144, 0.16);
-      color: var(--accent);
-    }}
-    .badge.warning {{
-      background: rgba(255, 212, 121, 0.16);
-      color: var(--warning);
-    }}
-    .kind {{
-      color: var(--muted);
-      font-size: 0.86rem;
-    }}
-    .diagnostic-message {{
-      margin: 0 0 0.8rem;
-      line-height: 1.5;
-    }}
-    .detail-list {{
-      margin: 0;
-      padding: 0;
-      list-style: none;
-      display: grid;
-      gap: 0.45rem;
-    }}
-    .detail-list li {{
-      color: var(--muted);
-      line-height: 1.45;
-    }}
-    .detail-label {{
-      color: var(--fg);
-      margin-right: 0.45rem;
-    }}
-    a {{
-      color: var(--link);
-      text-decoration: none;
-    }}
-    a:hover {{
-      text-decoration: underline;
-    }}
-    .empty-state {{
-      padding: 1.3rem;
-      color: var(--muted);
-    }}
-    .source-frame {{
-      margin: 0.8rem 0;
-      padding: 0.85rem;
-      border: 1px solid var(--border);
-      border-radius: 8px;
-      background: rgba(0, 0, 0, 0.18);
-      white-space: pre;
-      overflow-x: auto;
-    }}
-    .source-location {{
-      color: var(--link);
-    }}
-    .source-line-number {{
-      color: var(--muted);
-    }}
-    .source-caret {{
-      color: var(--accent);
-    }}
-    .guidance {{
-      margin-top: 0.7rem;
-      color: var(--muted);
-    }}
-    .open-source {{
-      display: inline-block;
-      margin-top: 0.8rem;
-    }}
-  </style>
+{ERROR_STYLES}  </style>
 </head>
-<body>
+<body data-bst-error-page>
   <main>
     <section class="card">
       <header><h1>{escaped_title}</h1></header>
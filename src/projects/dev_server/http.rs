@@ -1,7 +1,8 @@
 //! Minimal HTTP routing for the std-only dev server.
 //!
-//! Routes SSE and ping endpoints, serves static files from the dev output directory, and falls
-//! back to a generated error page when the latest build failed.
+//! Routes SSE and ping endpoints, serves static files from the dev output directory, and overlays
+//! failed-build diagnostics on the last good pages, falling back to a generated error page when
+//! no page has been built.
 
 use crate::projects::dev_server::error_page::render_runtime_error_page;
 use crate::projects::dev_server::sse;
@@ -256,8 +257,8 @@ fn prepare_static_response(
         }
     };
 
-    // Failed builds replace resolved page HTML requests with diagnostics while still allowing
-    // supporting assets to load so the browser can keep the previous shell alive.
+    // Failed builds keep serving the last good pages with the diagnostics overlay on top. Only a
+    // failure without a stored overlay falls back to the standalone error page.
     if should_serve_failed_build_html(resolved_kind, build_state) {
         let error_page = build_state.last_error_html.clone().unwrap_or_else(|| {
             render_runtime_error_page(
@@ -286,8 +287,15 @@ fn prepare_static_response(
             }
         };
 
-        let injected_html =
-            static_files::inject_dev_client(&html, &build_state.html_site_config.origin);
+        let build_error = build_state
+            .last_error_overlay
+            .as_ref()
+            .filter(|_| !build_state.last_build_ok);
+        let injected_html = static_files::inject_dev_client(
+            &html,
+            &build_state.html_site_config.origin,
+            build_error,
+        );
         return PreparedResponse::text("200 OK", content_type, injected_html);
     }
 
@@ -301,7 +309,9 @@ fn should_serve_failed_build_html(
     resolved_kind: ResolvedRequestKind,
     build_state: &BuildState,
 ) -> bool {
-    !build_state.last_build_ok && resolved_kind == ResolvedRequestKind::PageHtml
+    !build_state.last_build_ok
+        && build_state.last_error_overlay.is_none()
+        && resolved_kind == ResolvedRequestKind::PageHtml
 }
 
 fn stream_file_response(
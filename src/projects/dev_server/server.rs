@@ -33,6 +33,8 @@ pub fn run_dev_server(
     flags: &[Flag],
     options: DevServerOptions,
 ) -> Result<(), CompilerMessages> {
+    let flags = &[flags, &[Flag::DevServer]].concat();
+
     let entry_target = validate_dev_entry_path(entry_path)?;
     let resolved_paths = resolve_dev_runtime_paths(&builder, &entry_target, flags)?;
     let mut watch_scope = resolved_paths.watch_scope;
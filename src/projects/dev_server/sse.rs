@@ -1,52 +1,131 @@
 //! Server-Sent Events helpers for hot reload.
 //!
-//! Each connected client receives reload events over a channel-backed writer loop, and failed
-//! clients are pruned from shared state during broadcast.
+//! Each connected client receives typed update events through a latest-wins slot drained by its
+//! writer loop, and failed clients are pruned from shared state during broadcast. When a build
+//! lands before the previous payload was written, partial updates coalesce into `full-reload`.
+//!
+//! Protocol: every event carries a JSON `data` payload with the build `version`.
+//! - `full-reload`: the client reloads the page (recovery from a failed build, script/asset changes).
+//! - `build-error`: `title` and escaped diagnostics `html` for the overlay over the current page.
+//! - `css-update`: `stylesheets` lists changed stylesheet URLs to swap in place.
+//! - `page-update`: `routes` lists page URLs whose HTML changed; other pages ignore it.
 
-use crate::projects::dev_server::state::{DevServerState, SseClient};
+use crate::projects::dev_server::error_page::BuildErrorOverlay;
+use crate::projects::dev_server::state::{DevServerState, SseClient, SseClientInbox};
+use serde_json::json;
 use std::io::{self, Write};
 use std::net::TcpStream;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
-use std::sync::mpsc::{self, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::mpsc::{RecvTimeoutError, TrySendError};
 use std::time::Duration;
 
 const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
 const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(5);
 
-pub fn format_reload_event(version: u64) -> String {
-    format!("event: reload\ndata: {version}\n\n")
+/// One hot-reload instruction sent to connected dev clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevClientEvent {
+    FullReload,
+    BuildError(BuildErrorOverlay),
+    CssUpdate { stylesheets: Vec<String> },
+    PageUpdate { routes: Vec<String> },
 }
 
-pub fn broadcast_reload(state: &Arc<DevServerState>, version: u64) -> usize {
-    let event = format_reload_event(version);
+impl DevClientEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            DevClientEvent::FullReload => "full-reload",
+            DevClientEvent::BuildError(_) => "build-error",
+            DevClientEvent::CssUpdate { .. } => "css-update",
+            DevClientEvent::PageUpdate { .. } => "page-update",
+        }
+    }
+
+    fn data(&self, version: u64) -> serde_json::Value {
+        match self {
+            DevClientEvent::FullReload => json!({ "version": version }),
+            DevClientEvent::BuildError(overlay) => json!({
+                "version": version,
+                "title": overlay.title,
+                "html": overlay.body_html,
+            }),
+            DevClientEvent::CssUpdate { stylesheets } => {
+                json!({ "version": version, "stylesheets": stylesheets })
+            }
+            DevClientEvent::PageUpdate { routes } => {
+                json!({ "version": version, "routes": routes })
+            }
+        }
+    }
+}
+
+pub fn format_event(event: &DevClientEvent, version: u64) -> String {
+    format!("event: {}\ndata: {}\n\n", event.name(), event.data(version))
+}
+
+/// Send one build's events to every client as a single payload and return how many were reached.
+pub fn broadcast_events(
+    state: &Arc<DevServerState>,
+    version: u64,
+    events: &[DevClientEvent],
+) -> usize {
+    // One payload per build keeps a build's events together in the latest-wins client slot.
+    let payload = events
+        .iter()
+        .map(|event| format_event(event, version))
+        .collect::<String>();
+    // Partial updates only describe the difference from the previous build, so they cannot
+    // replace an undelivered one without losing changes.
+    let supersedes_pending = events.iter().any(|event| {
+        matches!(
+            event,
+            DevClientEvent::FullReload | DevClientEvent::BuildError(_)
+        )
+    });
+    let coalesced_payload = if supersedes_pending {
+        payload.clone()
+    } else {
+        format_event(&DevClientEvent::FullReload, version)
+    };
     let mut clients = match state.clients.lock() {
         Ok(guard) => guard,
         Err(_) => return 0,
     };
 
     let mut notified_count = 0usize;
-    // Broadcast and prune disconnected clients in one pass. Full queues already have a reload
-    // pending, so they count as notified without stacking redundant work.
-    clients.retain(|client| match client.sender.try_send(event.clone()) {
-        Ok(()) | Err(TrySendError::Full(_)) => {
-            notified_count += 1;
-            true
+    // Broadcast and prune disconnected clients in one pass. A full wake-up queue means the
+    // writer has not taken the pending payload yet, and it will read the replaced one instead.
+    clients.retain(|client| {
+        let Ok(mut pending) = client.pending.lock() else {
+            return false;
+        };
+        let next_payload = if pending.is_some() {
+            coalesced_payload.clone()
+        } else {
+            payload.clone()
+        };
+        *pending = Some(next_payload);
+        drop(pending);
+
+        match client.sender.try_send(()) {
+            Ok(()) | Err(TrySendError::Full(())) => {
+                notified_count += 1;
+                true
+            }
+            Err(TrySendError::Disconnected(())) => false,
         }
-        Err(TrySendError::Disconnected(_)) => false,
     });
 
     notified_count
 }
 
-fn register_client(state: &Arc<DevServerState>, sender: SyncSender<String>) -> Option<u64> {
+fn register_client(state: &Arc<DevServerState>) -> Option<(u64, SseClientInbox)> {
     let client_id = state.next_client_id.fetch_add(1, Ordering::Relaxed);
+    let (client, inbox) = SseClient::new(client_id);
     let mut clients = state.clients.lock().ok()?;
-    clients.push(SseClient {
-        id: client_id,
-        sender,
-    });
-    Some(client_id)
+    clients.push(client);
+    Some((client_id, inbox))
 }
 
 pub fn remove_client(state: &Arc<DevServerState>, client_id: u64) {
@@ -78,16 +157,16 @@ fn handle_sse_connection_with_timeouts(
     stream.write_all(b": connected\n\n")?;
     stream.flush()?;
 
-    let (sender, receiver) = mpsc::sync_channel::<String>(1);
-    let Some(client_id) = register_client(&state, sender) else {
+    let Some((client_id, inbox)) = register_client(&state) else {
         return Err(io::Error::other(
             "Failed to register SSE client due to state lock poisoning",
         ));
     };
 
     loop {
-        match receiver.recv_timeout(keep_alive_interval) {
-            Ok(event_payload) => {
+        match inbox.recv_timeout(keep_alive_interval) {
+            Ok(None) => {}
+            Ok(Some(event_payload)) => {
                 if stream.write_all(event_payload.as_bytes()).is_err() || stream.flush().is_err() {
                     break;
                 }
@@ -2,22 +2,63 @@
 //!
 //! HTTP handlers, SSE broadcast logic, and the watcher/build loop coordinate through this state.
 
+use crate::projects::dev_server::error_page::BuildErrorOverlay;
 use crate::projects::routing::HtmlSiteConfig;
 use std::path::PathBuf;
-use std::sync::Mutex;
 use std::sync::atomic::AtomicU64;
-use std::sync::mpsc::SyncSender;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+/// Broadcast-side end of one connected client's latest-wins update slot.
 #[derive(Debug)]
 pub struct SseClient {
     pub id: u64,
-    pub sender: SyncSender<String>,
+    /// Wakes the client's writer loop; the payload itself waits in `pending`.
+    pub sender: SyncSender<()>,
+    /// Latest undelivered payload. A newer build replaces it rather than queueing behind it.
+    pub pending: Arc<Mutex<Option<String>>>,
+}
+
+/// Writer-side end of an `SseClient` slot.
+pub struct SseClientInbox {
+    receiver: Receiver<()>,
+    pending: Arc<Mutex<Option<String>>>,
+}
+
+impl SseClient {
+    pub fn new(id: u64) -> (Self, SseClientInbox) {
+        // One wake-up is enough: the writer always reads the newest payload when it wakes.
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let pending = Arc::new(Mutex::new(None));
+        let client = Self {
+            id,
+            sender,
+            pending: Arc::clone(&pending),
+        };
+        (client, SseClientInbox { receiver, pending })
+    }
+}
+
+impl SseClientInbox {
+    /// Wait for a wake-up and take the pending payload.
+    ///
+    /// `Ok(None)` means an earlier wake-up already took the payload this one announced.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Option<String>, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)?;
+        match self.pending.lock() {
+            Ok(mut pending) => Ok(pending.take()),
+            Err(_) => Err(RecvTimeoutError::Disconnected),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct BuildState {
     pub last_build_ok: bool,
     pub last_error_html: Option<String>,
+    /// Overlay for the latest failed build, drawn over the last good pages.
+    pub last_error_overlay: Option<BuildErrorOverlay>,
     pub last_build_version: u64,
     pub entry_page_rel: Option<PathBuf>,
     pub output_dir: PathBuf,
@@ -30,6 +71,7 @@ impl BuildState {
         Self {
             last_build_ok: false,
             last_error_html: None,
+            last_error_overlay: None,
             last_build_version: 0,
             entry_page_rel: None,
             output_dir,
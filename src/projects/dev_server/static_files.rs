@@ -1,10 +1,15 @@
 //! Static file and HTML injection helpers for the dev server.
 //!
-//! This module resolves safe output-relative paths, maps content types, and injects the tiny
-//! EventSource client into HTML responses.
-
-use crate::projects::dev_server::dev_client::{DEV_CLIENT_MARKER, dev_client_snippet};
+//! This module resolves safe output-relative paths, maps content types, and injects the
+//! EventSource client and error overlay into HTML responses.
+
+use crate::projects::dev_server::dev_client::{
+    BUILD_ERROR_DATA_ID, DEV_CLIENT_MARKER, dev_client_snippet, dev_error_capture_snippet,
+};
+use crate::projects::dev_server::error_page::BuildErrorOverlay;
+use crate::projects::html_project::js_path::escape_inline_script;
 use crate::projects::routing::{HtmlSiteConfig, PageUrlStyle, prefix_origin};
+use serde_json::json;
 use std::path::{Component, Path, PathBuf};
 
 /// Classification used to keep page-routing behavior separate from exact asset serving behavior.
@@ -29,24 +34,52 @@ pub enum ResolvedRequest {
     InvalidPath,
 }
 
-pub fn inject_dev_client(html: &str, origin: &str) -> String {
+/// Inject the dev client into a served HTML page.
+///
+/// WHAT: puts the error capture script on the `<head>` line, then the optional failed-build report
+///       and the hot-reload client before `</body>`.
+/// WHY: errors thrown while the page bundle first runs must already be captured, and neither
+///      insertion adds a line in front of the bundle, so the source line table stays aligned.
+pub fn inject_dev_client(
+    html: &str,
+    origin: &str,
+    build_error: Option<&BuildErrorOverlay>,
+) -> String {
     if html.contains(DEV_CLIENT_MARKER) {
         return html.to_owned();
     }
 
-    let snippet = dev_client_snippet(origin);
-    if let Some(body_index) = html.rfind("</body>") {
-        let mut injected = String::with_capacity(html.len() + snippet.len());
-        injected.push_str(&html[..body_index]);
-        injected.push_str(&snippet);
-        injected.push_str(&html[body_index..]);
-        injected
-    } else {
-        let mut injected = String::with_capacity(html.len() + snippet.len());
-        injected.push_str(html);
-        injected.push_str(&snippet);
-        injected
+    let mut injected = html.to_owned();
+    if let Some(head_end) = opening_head_tag_end(&injected) {
+        injected.insert_str(head_end, &dev_error_capture_snippet());
+    }
+
+    let mut snippet = String::new();
+    if let Some(build_error) = build_error {
+        let data = json!({ "title": build_error.title, "html": build_error.body_html });
+        snippet.push_str(&format!(
+            "<script type=\"application/json\" id=\"{BUILD_ERROR_DATA_ID}\">{}</script>",
+            escape_inline_script(&data.to_string())
+        ));
+    }
+    snippet.push_str(&dev_client_snippet(origin));
+
+    match injected.rfind("</body>") {
+        Some(body_index) => injected.insert_str(body_index, &snippet),
+        None => injected.push_str(&snippet),
     }
+    injected
+}
+
+/// Byte index just past the `>` of the opening `<head>` tag, if the page has one.
+fn opening_head_tag_end(html: &str) -> Option<usize> {
+    let head_start = html.match_indices("<head").find_map(|(index, _)| {
+        let after = html[index + "<head".len()..].chars().next()?;
+        (after == '>' || after.is_whitespace()).then_some(index)
+    })?;
+    html[head_start..]
+        .find('>')
+        .map(|tag_end| head_start + tag_end + 1)
 }
 
 pub fn content_type_for_path(path: &Path) -> &'static str {
@@ -54,7 +87,7 @@ pub fn content_type_for_path(path: &Path) -> &'static str {
         Some("html") | Some("htm") => "text/html; charset=utf-8",
         Some("js") => "application/javascript; charset=utf-8",
         Some("css") => "text/css; charset=utf-8",
-        Some("json") => "application/json; charset=utf-8",
+        Some("json") | Some("map") => "application/json; charset=utf-8",
         Some("wasm") => "application/wasm",
         Some("png") => "image/png",
         Some("jpg") | Some("jpeg") => "image/jpeg",
@@ -1,8 +1,8 @@
 //! Tests for build-loop state transitions and queued rebuild behavior.
 
 use super::{
-    DevBuildExecutor, ProjectBuildExecutor, build_once, dev_server_error_messages,
-    run_builds_until_stable, run_single_build_cycle,
+    DevBuildExecutor, ProjectBuildExecutor, WrittenBuild, build_once, client_events_for_build,
+    dev_server_error_messages, run_builds_until_stable, run_single_build_cycle,
 };
 use crate::build_system::build::{
     self, BackendBuilder, BuildResult, CleanupPolicy, FileKind, OutputFile, Project,
@@ -18,13 +18,16 @@ use crate::compiler_frontend::compiler_messages::{
 use crate::compiler_frontend::style_directives::StyleDirectiveSpec;
 use crate::compiler_frontend::symbols::string_interning::{StringId, StringTable};
 use crate::compiler_tests::test_support::temp_dir;
-use crate::projects::dev_server::state::DevServerState;
+use crate::projects::dev_server::error_page::runtime_error_overlay;
+use crate::projects::dev_server::sse::DevClientEvent;
+use crate::projects::dev_server::state::{DevServerState, SseClient};
 use crate::projects::dev_server::watch;
 use crate::projects::settings::{Config, ProjectConfigError};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 fn unused_variable_warning(name: StringId, location: SourceLocation) -> CompilerDiagnostic {
     CompilerDiagnostic::with_severity(
@@ -49,6 +52,7 @@ fn html_build_result() -> BuildResult {
         config: Config::new(PathBuf::from("main.bst")),
         warnings: vec![],
         string_table: StringTable::new(),
+        module_reuse: None,
     }
 }
 
@@ -83,6 +87,7 @@ fn multi_page_html_build_result() -> BuildResult {
         config: Config::new(PathBuf::from("project")),
         warnings: vec![],
         string_table: StringTable::new(),
+        module_reuse: None,
     }
 }
 
@@ -100,6 +105,7 @@ fn html_build_result_without_entry_page() -> BuildResult {
         config: Config::new(PathBuf::from("main.bst")),
         warnings: vec![],
         string_table: StringTable::new(),
+        module_reuse: None,
     }
 }
 
@@ -123,6 +129,7 @@ fn html_build_result_with_warning() -> BuildResult {
         config: Config::new(PathBuf::from("main.bst")),
         warnings: vec![warning],
         string_table,
+        module_reuse: None,
     }
 }
 
@@ -159,7 +166,7 @@ impl DevBuildExecutor for FakeExecutor {
         _entry_file: &Path,
         _flags: &[crate::compiler_frontend::Flag],
         output_dir: &Path,
-    ) -> Result<BuildResult, CompilerMessages> {
+    ) -> Result<WrittenBuild, CompilerMessages> {
         let call_index = self.call_count.fetch_add(1, Ordering::SeqCst) + 1;
         if let Some(ref callback) = self.on_call {
             callback(call_index);
@@ -173,16 +180,19 @@ impl DevBuildExecutor for FakeExecutor {
 
         match response {
             Ok(build_result) => {
-                build::write_project_outputs(
+                let write_report = build::write_project_outputs(
                     &build_result.project,
                     &WriteOptions {
                         output_root: output_dir.to_path_buf(),
                         project_entry_dir: None,
-                        write_mode: WriteMode::AlwaysWrite,
+                        write_mode: WriteMode::SkipUnchanged,
                     },
                     &build_result.string_table,
                 )?;
-                Ok(build_result)
+                Ok(WrittenBuild {
+                    build_result,
+                    write_report,
+                })
             }
             Err(messages) => Err(messages),
         }
@@ -261,7 +271,7 @@ fn successful_build_marks_state_ok_and_uses_declared_entry_page() {
 }
 
 #[test]
-fn failed_build_marks_state_and_stores_error_page() {
+fn failed_build_marks_state_and_stores_error_page_and_overlay() {
     let root = temp_dir("failure");
     fs::create_dir_all(&root).expect("should create temp root");
     let state = Arc::new(DevServerState::new(root.join("dev")));
@@ -279,6 +289,12 @@ fn failed_build_marks_state_and_stores_error_page() {
         .expect("build state should not be poisoned");
     assert!(!build_state.last_build_ok);
     assert!(build_state.last_error_html.is_some());
+    let overlay = build_state
+        .last_error_overlay
+        .as_ref()
+        .expect("failed build should store an overlay");
+    assert_eq!(overlay.title, "Build Failed");
+    assert!(overlay.body_html.contains("boom"));
 
     fs::remove_dir_all(&root).expect("should remove temp dir");
 }
@@ -543,3 +559,156 @@ fn project_build_executor_preserves_warnings_when_output_write_fails() {
 
     fs::remove_dir_all(&root).expect("should remove temp dir");
 }
+
+#[test]
+fn client_events_send_the_overlay_on_failure_and_reload_on_recovery() {
+    let changed = [PathBuf::from("index.html")];
+    let overlay = runtime_error_overlay("Build Failed", "boom");
+
+    assert_eq!(
+        client_events_for_build(true, Some(&overlay), &changed, "/"),
+        vec![DevClientEvent::BuildError(overlay.clone())]
+    );
+    assert_eq!(
+        client_events_for_build(false, None, &changed, "/"),
+        vec![DevClientEvent::FullReload]
+    );
+}
+
+#[test]
+fn client_events_classify_changed_outputs() {
+    assert_eq!(
+        client_events_for_build(true, None, &[PathBuf::from("assets/site.css")], "/docs"),
+        vec![DevClientEvent::CssUpdate {
+            stylesheets: vec![String::from("/docs/assets/site.css")],
+        }]
+    );
+    assert_eq!(
+        client_events_for_build(
+            true,
+            None,
+            &[
+                PathBuf::from("about/index.html"),
+                PathBuf::from("about/index.js.map"),
+                PathBuf::from("theme.css")
+            ],
+            "/"
+        ),
+        vec![
+            DevClientEvent::CssUpdate {
+                stylesheets: vec![String::from("/theme.css")],
+            },
+            DevClientEvent::PageUpdate {
+                routes: vec![String::from("/about/index.html")],
+            },
+        ]
+    );
+    assert_eq!(
+        client_events_for_build(
+            true,
+            None,
+            &[
+                PathBuf::from("index.html"),
+                PathBuf::from("_beanstalk/js/glue/module-0.js")
+            ],
+            "/"
+        ),
+        vec![DevClientEvent::FullReload]
+    );
+    assert_eq!(
+        client_events_for_build(true, None, &[], "/"),
+        vec![DevClientEvent::PageUpdate { routes: Vec::new() }]
+    );
+}
+
+#[test]
+fn unchanged_pages_are_left_out_of_page_updates() {
+    let root = temp_dir("page_update_routes");
+    fs::create_dir_all(&root).expect("should create temp root");
+    let state = Arc::new(DevServerState::new(root.join("dev")));
+    let (client, inbox) = SseClient::new(1);
+    state
+        .clients
+        .lock()
+        .expect("clients mutex should not be poisoned")
+        .push(client);
+
+    let mut edited = multi_page_html_build_result();
+    edited.project.output_files[1] = OutputFile::new(
+        PathBuf::from("docs/basics/index.html"),
+        FileKind::Html(String::from("<html><body>Edited docs</body></html>")),
+    );
+    let mut executor = FakeExecutor::new(vec![Ok(multi_page_html_build_result()), Ok(edited)]);
+
+    run_single_build_cycle(&state, &mut executor, &root.join("main.bst"), &Vec::new());
+    assert!(
+        inbox
+            .recv_timeout(Duration::from_secs(1))
+            .expect("first build should notify")
+            .is_some_and(|payload| payload.starts_with("event: full-reload\n"))
+    );
+
+    run_single_build_cycle(&state, &mut executor, &root.join("main.bst"), &Vec::new());
+    assert_eq!(
+        inbox
+            .recv_timeout(Duration::from_secs(1))
+            .expect("second build should notify"),
+        Some(String::from(
+            "event: page-update\ndata: {\"routes\":[\"/docs/basics/index.html\"],\"version\":2}\n\n"
+        ))
+    );
+
+    fs::remove_dir_all(&root).expect("should remove temp dir");
+}
+
+#[test]
+fn back_to_back_builds_reload_a_client_that_has_not_drained() {
+    let root = temp_dir("undrained_client");
+    fs::create_dir_all(&root).expect("should create temp root");
+    let state = Arc::new(DevServerState::new(root.join("dev")));
+
+    let with_stylesheet = |css: &str, body: &str| {
+        let mut result = html_build_result();
+        result.project.output_files[0] = OutputFile::new(
+            PathBuf::from("index.html"),
+            FileKind::Html(format!("<html><body>{body}</body></html>")),
+        );
+        result.project.output_files.push(OutputFile::new(
+            PathBuf::from("style.css"),
+            FileKind::Bytes(css.as_bytes().to_vec()),
+        ));
+        result
+    };
+    let mut executor = FakeExecutor::new(vec![
+        Ok(with_stylesheet("body { color: red; }", "Hello")),
+        Ok(with_stylesheet("body { color: blue; }", "Hello")),
+        Ok(with_stylesheet("body { color: blue; }", "Edited")),
+    ]);
+    run_single_build_cycle(&state, &mut executor, &root.join("main.bst"), &Vec::new());
+
+    // The client connects after the first build and then stops draining its slot.
+    let (client, inbox) = SseClient::new(1);
+    state
+        .clients
+        .lock()
+        .expect("clients mutex should not be poisoned")
+        .push(client);
+
+    let css_only =
+        run_single_build_cycle(&state, &mut executor, &root.join("main.bst"), &Vec::new());
+    let html_only =
+        run_single_build_cycle(&state, &mut executor, &root.join("main.bst"), &Vec::new());
+    assert_eq!(css_only.clients_notified, 1);
+    assert_eq!(html_only.clients_notified, 1);
+
+    assert_eq!(
+        inbox
+            .recv_timeout(Duration::from_secs(1))
+            .expect("client should be woken"),
+        Some(String::from(
+            "event: full-reload\ndata: {\"version\":3}\n\n"
+        ))
+    );
+
+    fs::remove_dir_all(&root).expect("should remove temp dir");
+}
@@ -1,7 +1,8 @@
 //! Tests for dev-server error page rendering helpers.
 
 use super::{
-    escape_html, format_compiler_messages, render_compiler_error_page, render_runtime_error_page,
+    compiler_error_overlay, escape_html, format_compiler_messages, render_error_page,
+    render_runtime_error_page,
 };
 use crate::compiler_frontend::compiler_errors::{CompilerMessages, SourceLocation};
 use crate::compiler_frontend::compiler_messages::{CompilerDiagnostic, InvalidConfigReason};
@@ -28,6 +29,8 @@ fn rendered_runtime_page_includes_version_error_text_and_dark_mode() {
     assert!(page.contains("Timestamp (unix):"));
     assert!(page.contains("color-scheme: dark"));
     assert!(page.contains("EventSource('/preview/__beanstalk/events')"));
+    // The client reloads error pages on `build-error` instead of stacking an overlay on them.
+    assert!(page.contains("<body data-bst-error-page>"));
 }
 
 #[test]
@@ -77,7 +80,7 @@ fn compiler_error_page_links_to_project_relative_resolved_source_path() {
     );
     let messages = CompilerMessages::from_diagnostic(diagnostic, string_table);
 
-    let page = render_compiler_error_page(&messages, &root, "/docs", 7);
+    let page = render_error_page(&compiler_error_overlay(&messages, &root), "/docs", 7);
 
     // The browser card should not visibly show BST-* codes but should
     // carry them as data attributes for debugging.
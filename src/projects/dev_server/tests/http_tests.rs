@@ -2,6 +2,7 @@
 
 use super::{PreparedResponse, handle_connection_with_timeouts, prepare_static_response};
 use crate::compiler_tests::test_support::temp_dir;
+use crate::projects::dev_server::error_page::runtime_error_overlay;
 use crate::projects::dev_server::state::{BuildState, DevServerState};
 use std::fs;
 use std::io::{Read, Write};
@@ -28,25 +29,29 @@ fn configure_failed_build_state(
     let mut build_state = BuildState::new(output_dir.to_path_buf());
     build_state.last_build_ok = false;
     build_state.last_error_html = Some(last_error_html.to_owned());
+    build_state.last_error_overlay =
+        Some(runtime_error_overlay("Build Failed", "compiler exploded"));
     build_state.last_build_version = 9;
     build_state.entry_page_rel = entry_page_rel;
     build_state
 }
 
 #[test]
-fn nested_html_request_uses_stored_error_page_during_failed_build() {
+fn nested_html_request_keeps_last_good_page_with_overlay_during_failed_build() {
     let root = temp_dir("nested_html");
     let output_dir = root.join("dev");
     fs::create_dir_all(output_dir.join("docs/basics")).expect("should create docs output dir");
     fs::write(
         output_dir.join("docs/basics/index.html"),
-        "<html><body>stale success</body></html>",
+        "<html><head><title>Docs</title></head><body>stale success</body></html>",
     )
     .expect("should write stale html");
 
-    let error_html = "<html><body>compiler exploded</body></html>";
-    let build_state =
-        configure_failed_build_state(&output_dir, error_html, Some(PathBuf::from("index.html")));
+    let build_state = configure_failed_build_state(
+        &output_dir,
+        "<html><body>error page</body></html>",
+        Some(PathBuf::from("index.html")),
+    );
 
     match prepare_static_response("/docs/basics/", None, &build_state) {
         PreparedResponse::Text {
@@ -56,11 +61,13 @@ fn nested_html_request_uses_stored_error_page_during_failed_build() {
         } => {
             assert_eq!(status_line, "200 OK");
             assert_eq!(content_type, "text/html; charset=utf-8");
+            assert!(body.contains("stale success"));
+            assert!(!body.contains("error page"));
+            assert!(body.contains("id=\"bst-build-error\""));
             assert!(body.contains("compiler exploded"));
-            assert!(!body.contains("stale success"));
         }
         PreparedResponse::File { .. } => {
-            panic!("nested html route should render stored error page")
+            panic!("nested html route should be served with the dev client injected")
         }
         PreparedResponse::Redirect { .. } => {
             panic!("nested html route should not redirect in this scenario")
@@ -70,6 +77,35 @@ fn nested_html_request_uses_stored_error_page_during_failed_build() {
     fs::remove_dir_all(&root).expect("should remove temp dir");
 }
 
+#[test]
+fn failed_build_without_overlay_uses_stored_error_page() {
+    let root = temp_dir("nested_html_no_overlay");
+    let output_dir = root.join("dev");
+    fs::create_dir_all(&output_dir).expect("should create output dir");
+    fs::write(
+        output_dir.join("index.html"),
+        "<html><body>stale success</body></html>",
+    )
+    .expect("should write stale html");
+
+    let mut build_state = configure_failed_build_state(
+        &output_dir,
+        "<html><body>compiler exploded</body></html>",
+        Some(PathBuf::from("index.html")),
+    );
+    build_state.last_error_overlay = None;
+
+    match prepare_static_response("/", None, &build_state) {
+        PreparedResponse::Text { body, .. } => {
+            assert!(body.contains("compiler exploded"));
+            assert!(!body.contains("stale success"));
+        }
+        _ => panic!("page request should render the stored error page"),
+    }
+
+    fs::remove_dir_all(&root).expect("should remove temp dir");
+}
+
 #[test]
 fn failed_build_keeps_css_js_and_image_assets_reachable() {
     let root = temp_dir("assets");
@@ -1,6 +1,7 @@
-//! Tests for SSE payload formatting and disconnected-client pruning.
+//! Tests for SSE payload formatting, update coalescing and disconnected-client pruning.
 
-use super::{broadcast_reload, format_reload_event, handle_sse_connection_with_timeouts};
+use super::{DevClientEvent, broadcast_events, format_event, handle_sse_connection_with_timeouts};
+use crate::projects::dev_server::error_page::BuildErrorOverlay;
 use crate::projects::dev_server::state::{DevServerState, SseClient};
 use std::io::Read;
 use std::net::{Shutdown, TcpListener, TcpStream};
@@ -20,39 +21,63 @@ fn bind_loopback_listener() -> Option<TcpListener> {
 }
 
 #[test]
-fn reload_event_uses_expected_sse_format() {
-    let formatted = format_reload_event(42);
-    assert_eq!(formatted, "event: reload\ndata: 42\n\n");
+fn typed_events_use_expected_sse_format() {
+    assert_eq!(
+        format_event(&DevClientEvent::FullReload, 42),
+        "event: full-reload\ndata: {\"version\":42}\n\n"
+    );
+    assert_eq!(
+        format_event(
+            &DevClientEvent::BuildError(BuildErrorOverlay {
+                title: String::from("Build Failed"),
+                body_html: String::from("<p>line one\nline two</p>"),
+            }),
+            5
+        ),
+        "event: build-error\ndata: {\"html\":\"<p>line one\\nline two</p>\",\"title\":\"Build Failed\",\"version\":5}\n\n"
+    );
+    assert_eq!(
+        format_event(
+            &DevClientEvent::CssUpdate {
+                stylesheets: vec![String::from("/style.css")],
+            },
+            3
+        ),
+        "event: css-update\ndata: {\"stylesheets\":[\"/style.css\"],\"version\":3}\n\n"
+    );
+    assert_eq!(
+        format_event(
+            &DevClientEvent::PageUpdate {
+                routes: vec![String::from("/about/index.html")],
+            },
+            4
+        ),
+        "event: page-update\ndata: {\"routes\":[\"/about/index.html\"],\"version\":4}\n\n"
+    );
 }
 
 #[test]
 fn broadcast_prunes_disconnected_clients() {
     let state = Arc::new(DevServerState::new(PathBuf::from("dev")));
 
-    let (sender_ok, receiver_ok) = mpsc::sync_channel::<String>(1);
     let client_id_ok = state.next_client_id.fetch_add(1, Ordering::Relaxed);
+    let (client_ok, inbox_ok) = SseClient::new(client_id_ok);
     state
         .clients
         .lock()
         .expect("clients mutex should not be poisoned")
-        .push(SseClient {
-            id: client_id_ok,
-            sender: sender_ok,
-        });
+        .push(client_ok);
 
-    let (sender_dead, receiver_dead) = mpsc::sync_channel::<String>(1);
-    drop(receiver_dead);
     let client_id_dead = state.next_client_id.fetch_add(1, Ordering::Relaxed);
+    let (client_dead, inbox_dead) = SseClient::new(client_id_dead);
+    drop(inbox_dead);
     state
         .clients
         .lock()
         .expect("clients mutex should not be poisoned")
-        .push(SseClient {
-            id: client_id_dead,
-            sender: sender_dead,
-        });
+        .push(client_dead);
 
-    let notified = broadcast_reload(&state, 7);
+    let notified = broadcast_events(&state, 7, &[DevClientEvent::FullReload]);
     assert_eq!(notified, 1);
 
     let remaining = state
@@ -62,10 +87,72 @@ fn broadcast_prunes_disconnected_clients() {
     assert_eq!(remaining.len(), 1);
     assert_eq!(remaining[0].id, client_id_ok);
     assert_eq!(
-        receiver_ok
-            .recv()
+        inbox_ok
+            .recv_timeout(Duration::from_secs(1))
             .expect("connected client should receive event"),
-        "event: reload\ndata: 7\n\n"
+        Some(String::from(
+            "event: full-reload\ndata: {\"version\":7}\n\n"
+        ))
+    );
+}
+
+#[test]
+fn undrained_partial_updates_coalesce_into_full_reload() {
+    let state = Arc::new(DevServerState::new(PathBuf::from("dev")));
+    let (client, inbox) = SseClient::new(1);
+    state
+        .clients
+        .lock()
+        .expect("clients mutex should not be poisoned")
+        .push(client);
+
+    let css_update = DevClientEvent::CssUpdate {
+        stylesheets: vec![String::from("/style.css")],
+    };
+    let page_update = DevClientEvent::PageUpdate {
+        routes: vec![String::from("/index.html")],
+    };
+    assert_eq!(broadcast_events(&state, 1, &[css_update]), 1);
+    assert_eq!(broadcast_events(&state, 2, &[page_update]), 1);
+
+    // The client never saw the CSS swap, so the page update alone would leave stale styles.
+    assert_eq!(
+        inbox
+            .recv_timeout(Duration::from_secs(1))
+            .expect("client should be woken"),
+        Some(format_event(&DevClientEvent::FullReload, 2))
+    );
+    assert!(inbox.recv_timeout(Duration::from_millis(20)).is_err());
+}
+
+#[test]
+fn undrained_update_is_replaced_by_a_newer_build_error() {
+    let state = Arc::new(DevServerState::new(PathBuf::from("dev")));
+    let (client, inbox) = SseClient::new(1);
+    state
+        .clients
+        .lock()
+        .expect("clients mutex should not be poisoned")
+        .push(client);
+
+    let overlay = DevClientEvent::BuildError(BuildErrorOverlay {
+        title: String::from("Build Failed"),
+        body_html: String::from("<p>boom</p>"),
+    });
+    broadcast_events(
+        &state,
+        1,
+        &[DevClientEvent::PageUpdate {
+            routes: vec![String::from("/index.html")],
+        }],
+    );
+    broadcast_events(&state, 2, std::slice::from_ref(&overlay));
+
+    assert_eq!(
+        inbox
+            .recv_timeout(Duration::from_secs(1))
+            .expect("client should be woken"),
+        Some(format_event(&overlay, 2))
     );
 }
 
@@ -120,7 +207,7 @@ fn loopback_disconnect_prunes_sse_client_promptly() {
         .expect("client should close the SSE connection");
     drop(client);
 
-    let notified = broadcast_reload(&state, 3);
+    let notified = broadcast_events(&state, 3, &[DevClientEvent::FullReload]);
     assert_eq!(notified, 1);
     done_receiver
         .recv_timeout(Duration::from_secs(1))
@@ -4,6 +4,7 @@ use super::{
     ResolvedRequest, ResolvedRequestKind, content_type_for_path, inject_dev_client, resolve_request,
 };
 use crate::compiler_tests::test_support::temp_dir;
+use crate::projects::dev_server::error_page::BuildErrorOverlay;
 use crate::projects::routing::{HtmlSiteConfig, PageUrlStyle};
 use std::fs;
 use std::path::Path;
@@ -19,7 +20,7 @@ fn site_config(page_url_style: PageUrlStyle, redirect_index_html: bool) -> HtmlS
 #[test]
 fn injection_happens_before_closing_body_once() {
     let html = "<html><body><h1>Hello</h1></body></html>";
-    let injected = inject_dev_client(html, "/");
+    let injected = inject_dev_client(html, "/", None);
     assert!(injected.contains("EventSource('/__beanstalk/events')"));
     assert_eq!(
         injected
@@ -29,7 +30,7 @@ fn injection_happens_before_closing_body_once() {
     );
     assert!(injected.find("</body>").expect("should contain body close") > 0);
 
-    let reinjected = inject_dev_client(&injected, "/");
+    let reinjected = inject_dev_client(&injected, "/", None);
     assert_eq!(
         reinjected
             .matches("EventSource('/__beanstalk/events')")
@@ -39,6 +40,39 @@ fn injection_happens_before_closing_body_once() {
     );
 }
 
+#[test]
+fn injection_keeps_document_lines_and_embeds_the_build_error() {
+    let html = "<html>\n<head>\n<title>Docs</title>\n</head>\n<body>\n<script>start();</script>\n</body>\n</html>";
+    let overlay = BuildErrorOverlay {
+        title: String::from("Build Failed"),
+        body_html: String::from("<p>bad</script></p>"),
+    };
+    let injected = inject_dev_client(html, "/", Some(&overlay));
+
+    // The capture script shares the `<head>` line, so the page script keeps its line number.
+    let line_of = |document: &str, needle: &str| {
+        document[..document.find(needle).expect("needle should be present")]
+            .matches('\n')
+            .count()
+    };
+    assert_eq!(
+        line_of(&injected, "<script>start();"),
+        line_of(html, "<script>start();")
+    );
+    assert!(injected.contains("<head><script>(() => { if (window.__bstDev) return;"));
+    assert!(injected.find("addEventListener('error'") < injected.find("<script>start();"));
+
+    assert!(injected.contains("id=\"bst-build-error\""));
+    assert!(injected.contains("<p>bad<\\/script><\\/p>"));
+    assert!(!injected.contains("bad</script>"));
+}
+
+#[test]
+fn header_elements_are_not_mistaken_for_the_head_tag() {
+    let injected = inject_dev_client("<body><header>Top</header></body>", "/", None);
+    assert!(injected.starts_with("<body><header>Top</header>"));
+}
+
 #[test]
 fn content_type_map_covers_common_extensions() {
     assert_eq!(
@@ -0,0 +1,340 @@
+//! `bean fix` command orchestration.
+//!
+//! WHAT: runs the frontend check, applies every machine-applicable suggested edit that does not
+//! overlap another, writes the changed files and re-checks until no further edit applies.
+//! WHY: the tokenizer and parsers stop at the first mistake in a file, so fixing one often reveals
+//! the next. Re-checking keeps every applied edit grounded in a diagnostic from the current text.
+
+use crate::compiler_frontend::compiler_errors::CompilerMessages;
+use crate::compiler_frontend::compiler_messages::render::{
+    relative_display_path_from_root, resolve_source_file_path_in_roots,
+};
+use crate::compiler_frontend::compiler_messages::suggested_edits::{
+    SuggestedEdit, SuggestedEditTarget,
+};
+use crate::compiler_frontend::display_messages::print_compiler_messages;
+use crate::projects::check::{FrontendCheck, check_project_frontend};
+use saying::say;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Upper bound on check-and-apply rounds.
+///
+/// Every round that continues has applied at least one edit, so this only guards against a
+/// suggestion that keeps reappearing after it was applied.
+const MAX_FIX_PASSES: usize = 16;
+
+/// Unchanged lines shown around each change in `--dry-run` diffs.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FixOptions {
+    /// Print a unified diff of the first round of edits instead of writing files.
+    pub dry_run: bool,
+}
+
+/// Source text of one file before and after every applied round.
+pub(crate) struct FileFix {
+    pub(crate) path: PathBuf,
+    pub(crate) original: String,
+    pub(crate) fixed: String,
+    pub(crate) applied_edits: usize,
+}
+
+pub(crate) struct FixOutcome {
+    pub(crate) files: Vec<FileFix>,
+    /// Diagnostics from the last check, i.e. everything `fix` could not resolve.
+    pub(crate) messages: CompilerMessages,
+}
+
+impl FixOutcome {
+    fn applied_edits(&self) -> usize {
+        self.files.iter().map(|file| file.applied_edits).sum()
+    }
+}
+
+pub fn run_fix(path: &str, options: FixOptions) {
+    let outcome = match execute_fix(path, options) {
+        Ok(outcome) => outcome,
+        Err(messages) => {
+            print_compiler_messages(messages);
+            return;
+        }
+    };
+
+    let working_directory = std::env::current_dir().unwrap_or_default();
+    let applied_edits = outcome.applied_edits();
+
+    if options.dry_run {
+        for file in &outcome.files {
+            let display_path = relative_display_path_from_root(&file.path, &working_directory);
+            print!(
+                "{}",
+                unified_diff(&display_path, &file.original, &file.fixed)
+            );
+        }
+        say!(
+            "Would apply ",
+            Blue applied_edits,
+            Reset " fixes to ",
+            Blue outcome.files.len(),
+            Reset " files."
+        );
+        return;
+    }
+
+    if applied_edits == 0 {
+        say!("No machine-applicable fixes found.");
+    } else {
+        say!(
+            "🌱 Applied ",
+            Blue applied_edits,
+            Reset " fixes to ",
+            Blue outcome.files.len(),
+            Reset " files."
+        );
+    }
+
+    if outcome.messages.error_count() > 0 || outcome.messages.warning_count() > 0 {
+        print_compiler_messages(outcome.messages);
+    }
+}
+
+/// Check, apply and re-check until stable. `dry_run` stops after planning the first round.
+pub(crate) fn execute_fix(path: &str, options: FixOptions) -> Result<FixOutcome, CompilerMessages> {
+    let mut files: BTreeMap<PathBuf, FileFix> = BTreeMap::new();
+    let mut pass = 0;
+
+    loop {
+        pass += 1;
+        let FrontendCheck {
+            messages,
+            source_roots,
+            ..
+        } = check_project_frontend(path);
+
+        let mut applied_this_pass = 0;
+        for (file, edits) in suggested_edits_by_file(&messages, &source_roots) {
+            let Ok(source) = fs::read_to_string(&file) else {
+                continue;
+            };
+            let (fixed, applied) = apply_suggested_edits(&source, &edits);
+            if applied == 0 {
+                continue;
+            }
+
+            if !options.dry_run
+                && let Err(error) = fs::write(&file, &fixed)
+            {
+                return Err(CompilerMessages::file_error(
+                    &file,
+                    format!("Failed to write fixed source file: {error}"),
+                    &messages.string_table,
+                ));
+            }
+
+            let file_fix = files.entry(file.to_owned()).or_insert_with(|| FileFix {
+                path: file,
+                original: source,
+                fixed: String::new(),
+                applied_edits: 0,
+            });
+            file_fix.fixed = fixed;
+            file_fix.applied_edits += applied;
+            applied_this_pass += applied;
+        }
+
+        if applied_this_pass == 0 || options.dry_run || pass == MAX_FIX_PASSES {
+            return Ok(FixOutcome {
+                files: files.into_values().collect(),
+                messages,
+            });
+        }
+    }
+}
+
+fn suggested_edits_by_file(
+    messages: &CompilerMessages,
+    source_roots: &[PathBuf],
+) -> BTreeMap<PathBuf, Vec<SuggestedEdit>> {
+    let mut edits: BTreeMap<PathBuf, Vec<SuggestedEdit>> = BTreeMap::new();
+
+    for diagnostic in messages.diagnostic_slice() {
+        let Some(edit) = diagnostic.suggested_edit(&messages.string_table) else {
+            continue;
+        };
+        if edit.location.scope.as_components().is_empty() {
+            continue;
+        }
+
+        let file = resolve_source_file_path_in_roots(
+            &edit.location.scope,
+            &messages.string_table,
+            source_roots,
+        );
+        edits.entry(file).or_default().push(edit);
+    }
+
+    edits
+}
+
+/// Apply every edit that still matches `source` and does not overlap an earlier one.
+///
+/// Returns the new text and the number of edits applied. Edits whose expected text is no longer
+/// at their location are skipped rather than guessed at.
+pub(crate) fn apply_suggested_edits(source: &str, edits: &[SuggestedEdit]) -> (String, usize) {
+    let mut resolved = edits
+        .iter()
+        .filter_map(|edit| Some((edit_byte_range(source, edit)?, edit.replacement.as_str())))
+        .collect::<Vec<_>>();
+    // At a shared start, the longest edit wins so `!=` is not split by a nested `!` edit.
+    resolved.sort_by_key(|(range, _)| (range.start, Reverse(range.end)));
+
+    let mut accepted: Vec<(Range<usize>, &str)> = Vec::with_capacity(resolved.len());
+    for (range, replacement) in resolved {
+        if accepted
+            .last()
+            .is_some_and(|(previous, _)| previous.end > range.start)
+        {
+            continue;
+        }
+        accepted.push((range, replacement));
+    }
+
+    let mut fixed = source.to_owned();
+    for (range, replacement) in accepted.iter().rev() {
+        let replacement = separated_from_neighbours(source, range, replacement);
+        fixed.replace_range(range.clone(), &replacement);
+    }
+
+    (fixed, accepted.len())
+}
+
+/// Byte range in `source` that `edit` replaces, or `None` if the edit no longer matches.
+fn edit_byte_range(source: &str, edit: &SuggestedEdit) -> Option<Range<usize>> {
+    let location = &edit.location;
+    let start = byte_offset(
+        source,
+        location.start_pos.line_number,
+        location.start_pos.char_column,
+    )?;
+
+    match &edit.target {
+        SuggestedEditTarget::Text(expected) => source[start..]
+            .starts_with(expected.as_str())
+            .then(|| start..start + expected.len()),
+        SuggestedEditTarget::Span => {
+            if location.end_pos.line_number != location.start_pos.line_number {
+                return None;
+            }
+            // Span end columns are inclusive, so the column after the span is one past them.
+            let end = byte_offset(
+                source,
+                location.end_pos.line_number,
+                location.end_pos.char_column.checked_add(1)?,
+            )?;
+            (end > start).then_some(start..end)
+        }
+    }
+}
+
+/// Byte offset of a zero-based line and one-based character column.
+fn byte_offset(source: &str, line_number: i32, char_column: i32) -> Option<usize> {
+    let line_index = usize::try_from(line_number).ok()?;
+    let char_index = usize::try_from(char_column.checked_sub(1)?).ok()?;
+
+    let line_start = if line_index == 0 {
+        0
+    } else {
+        source
+            .match_indices('\n')
+            .nth(line_index - 1)
+            .map(|(index, _)| index + 1)?
+    };
+    let line = source[line_start..].split('\n').next().unwrap_or_default();
+
+    if char_index == line.chars().count() {
+        return Some(line_start + line.len());
+    }
+    line.char_indices()
+        .nth(char_index)
+        .map(|(offset, _)| line_start + offset)
+}
+
+/// Pad word replacements so `a&&b` becomes `a and b` rather than `aandb`.
+fn separated_from_neighbours(source: &str, range: &Range<usize>, replacement: &str) -> String {
+    let is_word = |character: char| character.is_alphanumeric() || character == '_';
+    let mut padded = String::with_capacity(replacement.len() + 2);
+
+    if replacement.starts_with(is_word) && source[..range.start].ends_with(is_word) {
+        padded.push(' ');
+    }
+    padded.push_str(replacement);
+    if replacement.ends_with(is_word) && source[range.end..].starts_with(is_word) {
+        padded.push(' ');
+    }
+
+    padded
+}
+
+/// Line-based unified diff for edits that never add or remove lines.
+pub(crate) fn unified_diff(display_path: &str, before: &str, after: &str) -> String {
+    let old_lines = before.lines().collect::<Vec<_>>();
+    let new_lines = after.lines().collect::<Vec<_>>();
+    let mut diff = format!("--- a/{display_path}\n+++ b/{display_path}\n");
+
+    if old_lines.len() != new_lines.len() {
+        diff.push_str(&format!(
+            "@@ -1,{} +1,{} @@\n",
+            old_lines.len(),
+            new_lines.len()
+        ));
+        old_lines
+            .iter()
+            .for_each(|line| diff.push_str(&format!("-{line}\n")));
+        new_lines
+            .iter()
+            .for_each(|line| diff.push_str(&format!("+{line}\n")));
+        return diff;
+    }
+
+    let changed = (0..old_lines.len())
+        .filter(|&index| old_lines[index] != new_lines[index])
+        .collect::<Vec<_>>();
+
+    // Group changed lines into hunks whose context windows touch or overlap.
+    let mut hunks: Vec<Range<usize>> = Vec::new();
+    for &index in &changed {
+        let start = index.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (index + DIFF_CONTEXT_LINES + 1).min(old_lines.len());
+        match hunks.last_mut() {
+            Some(hunk) if hunk.end >= start => hunk.end = end,
+            _ => hunks.push(start..end),
+        }
+    }
+
+    for hunk in hunks {
+        let line_count = hunk.len();
+        diff.push_str(&format!(
+            "@@ -{},{line_count} +{},{line_count} @@\n",
+            hunk.start + 1,
+            hunk.start + 1
+        ));
+        for index in hunk {
+            if old_lines[index] == new_lines[index] {
+                diff.push_str(&format!(" {}\n", old_lines[index]));
+            } else {
+                diff.push_str(&format!("-{}\n+{}\n", old_lines[index], new_lines[index]));
+            }
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+#[path = "tests/fix_tests.rs"]
+mod tests;
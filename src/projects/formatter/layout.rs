@@ -0,0 +1,477 @@
+//! Token-driven source layout for `bean fmt`.
+//!
+//! WHAT: re-prints one `.bst` file from its token stream. Indentation comes from open `:`/`::`
+//! blocks, open brackets and operator continuations. Spacing between tokens is normalised to at
+//! most one space and runs of blank lines collapse to one. `--` comments are read from the source
+//! text between tokens and keep their own-line or trailing position.
+//! WHY: token spans already separate code from the text around it. Only that surrounding text is
+//! rewritten; token text, string literals, paths and whole templates are copied byte-for-byte.
+//! Re-tokenizing the result and comparing token streams keeps a layout bug from changing code.
+
+use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::compiler_frontend::tokenizer::lexer::tokenize;
+use crate::compiler_frontend::tokenizer::tokens::{
+    CharPosition, Token, TokenKind, TokenizerEntryMode,
+};
+use std::ops::Range;
+use std::path::Path;
+
+const INDENT: &str = "    ";
+const COMMENT_PREFIX: &str = "--";
+
+/// Format one source file. Fails with the tokenizer diagnostic when the file does not lex.
+pub(crate) fn format_source(
+    source: &str,
+    path: &Path,
+    style_directives: &StyleDirectiveRegistry,
+) -> Result<String, CompilerMessages> {
+    let mut string_table = StringTable::new();
+    let Ok(interned_path) = InternedPath::try_from_filesystem_path(path, &mut string_table) else {
+        return Err(CompilerMessages::file_error(
+            path,
+            "Source path cannot be represented as a Beanstalk path.",
+            &string_table,
+        ));
+    };
+
+    // Printed output always uses `\n`; normalising first keeps token columns and gaps aligned.
+    let source = source.replace("\r\n", "\n");
+    let tokenize_text = |text: &str, string_table: &mut StringTable| {
+        tokenize(
+            text,
+            &interned_path,
+            TokenizerEntryMode::SourceFile,
+            style_directives,
+            string_table,
+            None,
+        )
+    };
+
+    let original_tokens = match tokenize_text(&source, &mut string_table) {
+        Ok(file_tokens) => file_tokens.tokens,
+        Err(diagnostic) => {
+            return Err(CompilerMessages::from_diagnostic(*diagnostic, string_table));
+        }
+    };
+
+    let formatted = print_layout(&source, &original_tokens);
+
+    let unchanged_tokens = tokenize_text(&formatted, &mut string_table)
+        .is_ok_and(|file_tokens| same_token_stream(&original_tokens, &file_tokens.tokens));
+    if !unchanged_tokens {
+        return Err(CompilerMessages::from_error(
+            CompilerError::compiler_error(format!(
+                "Formatting '{}' would change its tokens, so the file was left unchanged. This is a formatter bug.",
+                path.display()
+            )),
+            string_table,
+        ));
+    }
+
+    Ok(formatted)
+}
+
+// ------------------------
+//  Source items
+// ------------------------
+
+/// One printable unit: a code token, or a whole template copied verbatim.
+struct SourceItem<'a> {
+    token: Option<&'a TokenKind>,
+    range: Range<usize>,
+    /// A `::` that declares a choice body and so opens a block closed by `;`.
+    opens_choice_block: bool,
+}
+
+impl SourceItem<'_> {
+    fn is(&self, kind: &TokenKind) -> bool {
+        self.token == Some(kind)
+    }
+}
+
+fn source_items<'a>(source: &str, tokens: &'a [Token]) -> Vec<SourceItem<'a>> {
+    let line_starts = line_start_offsets(source);
+    let mut items = Vec::with_capacity(tokens.len());
+    let mut template_start: Option<usize> = None;
+    let mut template_depth = 0usize;
+
+    for token in tokens {
+        let start = byte_offset(source, &line_starts, token.location.start_pos, 1);
+        let end = byte_offset(source, &line_starts, token.location.end_pos, 0);
+
+        match &token.kind {
+            TokenKind::ModuleStart | TokenKind::Eof | TokenKind::Newline => {}
+            TokenKind::TemplateHead => {
+                if template_depth == 0 {
+                    template_start = Some(start);
+                }
+                template_depth += 1;
+            }
+            TokenKind::TemplateClose if template_depth > 0 => {
+                template_depth -= 1;
+                if template_depth == 0
+                    && let Some(start) = template_start.take()
+                {
+                    items.push(SourceItem {
+                        token: None,
+                        range: start..end.max(start),
+                        opens_choice_block: false,
+                    });
+                }
+            }
+            _ if template_depth > 0 => {}
+            kind => items.push(SourceItem {
+                token: Some(kind),
+                range: start..end.max(start),
+                opens_choice_block: false,
+            }),
+        }
+    }
+
+    // `Name :: Variant` is spaced and runs to `;`. `Name::Variant` access is written tight.
+    for index in 0..items.len() {
+        if !items[index].is(&TokenKind::DoubleColon) {
+            continue;
+        }
+
+        let spaced_before = index > 0 && gap(source, &items, index).contains(char::is_whitespace);
+        let ends_line = items
+            .get(index + 1)
+            .is_none_or(|_| gap(source, &items, index + 1).contains('\n'));
+        items[index].opens_choice_block = spaced_before || ends_line;
+    }
+
+    // An unclosed template runs to the end of the file; the tokenizer reports it elsewhere.
+    if let Some(start) = template_start {
+        let end = source.trim_end().len().max(start);
+        items.push(SourceItem {
+            token: None,
+            range: start..end,
+            opens_choice_block: false,
+        });
+    }
+
+    items
+}
+
+/// Text between the previous item (or the start of the file) and `items[index]`.
+fn gap<'s>(source: &'s str, items: &[SourceItem<'_>], index: usize) -> &'s str {
+    let start = index
+        .checked_sub(1)
+        .map_or(0, |previous| items[previous].range.end);
+    &source[start..items[index].range.start.max(start)]
+}
+
+fn line_start_offsets(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(offset, _)| offset + 1))
+        .collect()
+}
+
+/// Byte offset of a token position. Start columns are one past the first character, so callers
+/// pass `column_bias = 1` for starts and `0` for exclusive ends.
+fn byte_offset(
+    source: &str,
+    line_starts: &[usize],
+    position: CharPosition,
+    column_bias: i32,
+) -> usize {
+    let line_index = usize::try_from(position.line_number).unwrap_or_default();
+    let Some(&line_start) = line_starts.get(line_index) else {
+        return source.len();
+    };
+    let char_index = usize::try_from(position.char_column - column_bias).unwrap_or_default();
+
+    source[line_start..]
+        .char_indices()
+        .nth(char_index)
+        .map_or(source.len(), |(offset, _)| line_start + offset)
+}
+
+// ------------------------
+//  Printing
+// ------------------------
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Bracket {
+    Parenthesis,
+    Curly,
+    Bar,
+}
+
+#[derive(Default)]
+struct Printer {
+    output: String,
+    line: String,
+    block_depth: usize,
+    /// Block depth before the first item of the line being printed.
+    line_block_depth: usize,
+    open_brackets: Vec<Bracket>,
+    /// The last code line ended with an operator, so the next one is a hanging continuation.
+    continuation: bool,
+    /// The last printed line opened a block; blank lines right after it are dropped.
+    opened_block: bool,
+    pending_blank_line: bool,
+}
+
+fn print_layout(source: &str, tokens: &[Token]) -> String {
+    let items = source_items(source, tokens);
+    let mut printer = Printer::default();
+
+    for index in 0..items.len() {
+        let item = &items[index];
+        let mut gap_lines = gap(source, &items, index).split('\n').collect::<Vec<_>>();
+
+        if index > 0 {
+            let previous = &items[index - 1];
+            if let [inline_space] = gap_lines[..] {
+                if space_between(previous, item, !inline_space.is_empty()) {
+                    printer.line.push(' ');
+                }
+                printer.push_item(source, item);
+                continue;
+            }
+
+            printer.push_trailing_comment(gap_lines.remove(0));
+            printer.finish_code_line(previous);
+        }
+
+        // The last gap line is the indentation in front of this item; the rest are whole lines.
+        gap_lines.pop();
+        for gap_line in gap_lines {
+            printer.push_gap_line(gap_line);
+        }
+
+        let next_is_fat_arrow = items
+            .get(index + 1)
+            .is_some_and(|next| next.is(&TokenKind::FatArrow));
+        printer.start_code_line(item, next_is_fat_arrow);
+        printer.push_item(source, item);
+    }
+
+    match items.last() {
+        Some(last) => {
+            let mut tail_lines = source[last.range.end..].split('\n');
+            printer.push_trailing_comment(tail_lines.next().unwrap_or_default());
+            printer.finish_code_line(last);
+            tail_lines.for_each(|tail_line| printer.push_gap_line(tail_line));
+        }
+        None => source
+            .split('\n')
+            .for_each(|source_line| printer.push_gap_line(source_line)),
+    }
+
+    printer.output
+}
+
+impl Printer {
+    fn push_item(&mut self, source: &str, item: &SourceItem<'_>) {
+        self.line.push_str(&source[item.range.clone()]);
+
+        let Some(kind) = item.token else {
+            return;
+        };
+        match kind {
+            TokenKind::Colon => self.block_depth += 1,
+            TokenKind::DoubleColon if item.opens_choice_block => self.block_depth += 1,
+            TokenKind::End => self.block_depth = self.block_depth.saturating_sub(1),
+            TokenKind::OpenParenthesis => self.open_brackets.push(Bracket::Parenthesis),
+            TokenKind::OpenCurly => self.open_brackets.push(Bracket::Curly),
+            TokenKind::CloseParenthesis | TokenKind::CloseCurly => {
+                self.open_brackets.pop();
+            }
+            TokenKind::TypeParameterBracket => {
+                if self.open_brackets.last() == Some(&Bracket::Bar) {
+                    self.open_brackets.pop();
+                } else {
+                    self.open_brackets.push(Bracket::Bar);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Indent a new code line. `;` and a branch `else` sit one level out from their block, and a
+    /// leading closing bracket sits at the level of the line that opened it.
+    fn start_code_line(&mut self, first: &SourceItem<'_>, next_is_fat_arrow: bool) {
+        let closes_block =
+            first.is(&TokenKind::End) || (first.is(&TokenKind::Else) && !next_is_fat_arrow);
+        let closes_bracket = match first.token {
+            Some(TokenKind::CloseParenthesis | TokenKind::CloseCurly) => true,
+            Some(TokenKind::TypeParameterBracket) => {
+                self.open_brackets.last() == Some(&Bracket::Bar)
+            }
+            _ => false,
+        };
+
+        if self.pending_blank_line && !self.opened_block && !closes_block {
+            self.output.push('\n');
+        }
+        self.pending_blank_line = false;
+
+        let depth = self.block_depth.saturating_sub(usize::from(closes_block))
+            + self
+                .open_brackets
+                .len()
+                .saturating_sub(usize::from(closes_bracket))
+            + usize::from(self.continuation && !closes_bracket);
+        self.line = INDENT.repeat(depth);
+        self.line_block_depth = self.block_depth;
+    }
+
+    fn finish_code_line(&mut self, last: &SourceItem<'_>) {
+        self.output.push_str(self.line.trim_end());
+        self.output.push('\n');
+        self.line.clear();
+
+        self.continuation = last.token.is_some_and(continues_on_next_line);
+        self.opened_block = self.block_depth > self.line_block_depth;
+    }
+
+    fn push_trailing_comment(&mut self, trailing: &str) {
+        let comment = trailing.trim();
+        if comment.starts_with(COMMENT_PREFIX) {
+            self.line.push(' ');
+            self.line.push_str(comment);
+        }
+    }
+
+    /// A full source line between two code lines: blank, or an own-line comment.
+    fn push_gap_line(&mut self, gap_line: &str) {
+        let comment = gap_line.trim();
+        if !comment.starts_with(COMMENT_PREFIX) {
+            self.pending_blank_line |= comment.is_empty() && !self.output.is_empty();
+            return;
+        }
+
+        if self.pending_blank_line && !self.opened_block {
+            self.output.push('\n');
+        }
+        self.pending_blank_line = false;
+        self.opened_block = false;
+
+        let depth = self.block_depth + self.open_brackets.len();
+        self.output.push_str(&INDENT.repeat(depth));
+        self.output.push_str(comment);
+        self.output.push('\n');
+    }
+}
+
+/// Whether one space separates two items printed on the same line.
+///
+/// Rules only fix spacing the tokenizer does not already constrain. Everywhere else the author's
+/// choice between no space and some space is kept, collapsed to a single space.
+fn space_between(previous: &SourceItem<'_>, next: &SourceItem<'_>, had_space: bool) -> bool {
+    use TokenKind as T;
+
+    match (previous.token, next.token) {
+        (_, Some(T::Comma | T::CloseParenthesis | T::CloseCurly | T::Dot | T::QuestionMark))
+        | (Some(T::OpenParenthesis | T::OpenCurly | T::Dot | T::Negative), _) => false,
+        // `-> :` is a signature error reported at the colon; keep it where it was written.
+        (Some(previous_kind), Some(T::Colon)) => had_space && is_spaced_operator(previous_kind),
+        (None, Some(T::Colon)) => false,
+        // `~ =`, `# =` and `$ =` are rejected by the parser rather than the tokenizer, so the
+        // space after a binding marker is meaningful.
+        (Some(T::Mutable | T::Hash | T::Reactive), _) => had_space,
+        (Some(T::DoubleColon), _) => previous.opens_choice_block,
+        (_, Some(T::DoubleColon)) => next.opens_choice_block,
+        (Some(previous_kind), Some(T::Bang)) if previous_kind.can_end_expression() => false,
+        (Some(T::Symbol(_) | T::CloseParenthesis | T::This), Some(T::OpenParenthesis)) => false,
+        (Some(T::Comma), _) => true,
+        (Some(kind), _) | (_, Some(kind)) if is_spaced_operator(kind) => true,
+        _ => had_space,
+    }
+}
+
+/// Binary and assignment operators that always take one space on each side.
+///
+/// `<` and `>` are left alone because their spacing decides generic and template-tag lexing.
+fn is_spaced_operator(kind: &TokenKind) -> bool {
+    kind.is_assignment_operator()
+        || matches!(
+            kind,
+            TokenKind::Add
+                | TokenKind::Subtract
+                | TokenKind::Multiply
+                | TokenKind::Divide
+                | TokenKind::Modulus
+                | TokenKind::IntDivide
+                | TokenKind::Exponent
+                | TokenKind::Is
+                | TokenKind::LessThanOrEqual
+                | TokenKind::GreaterThanOrEqual
+                | TokenKind::And
+                | TokenKind::Or
+                | TokenKind::Arrow
+                | TokenKind::FatArrow
+        )
+}
+
+/// Trailing tokens that continue the statement on the next line with a hanging indent.
+fn continues_on_next_line(kind: &TokenKind) -> bool {
+    kind.continues_expression()
+        && !matches!(
+            kind,
+            TokenKind::Colon
+                | TokenKind::End
+                | TokenKind::Comma
+                | TokenKind::OpenParenthesis
+                | TokenKind::TypeParameterBracket
+        )
+}
+
+/// Token streams match when every token kind and payload matches, ignoring source locations and
+/// how many line breaks separate two lines, which is the part of the stream layout owns.
+fn same_token_stream(original: &[Token], formatted: &[Token]) -> bool {
+    let original = layout_independent_kinds(original);
+    let formatted = layout_independent_kinds(formatted);
+
+    original.len() == formatted.len()
+        && original
+            .iter()
+            .zip(&formatted)
+            .all(|(original, formatted)| match (original, formatted) {
+                (TokenKind::Path(original_items), TokenKind::Path(formatted_items)) => {
+                    original_items.len() == formatted_items.len()
+                        && original_items.iter().zip(formatted_items).all(
+                            |(original_item, formatted_item)| {
+                                original_item.path == formatted_item.path
+                                    && original_item.alias == formatted_item.alias
+                                    && original_item.from_grouped == formatted_item.from_grouped
+                            },
+                        )
+                }
+                (original_kind, formatted_kind) => original_kind == formatted_kind,
+            })
+}
+
+/// Token kinds with leading, repeated and trailing `Newline` tokens dropped.
+fn layout_independent_kinds(tokens: &[Token]) -> Vec<&TokenKind> {
+    let mut kinds: Vec<&TokenKind> = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        match &token.kind {
+            TokenKind::Newline
+                if matches!(
+                    kinds.last(),
+                    None | Some(TokenKind::ModuleStart | TokenKind::Newline)
+                ) => {}
+            TokenKind::Eof => {
+                while kinds.last() == Some(&&TokenKind::Newline) {
+                    kinds.pop();
+                }
+                kinds.push(&token.kind);
+            }
+            kind => kinds.push(kind),
+        }
+    }
+
+    kinds
+}
+
+#[cfg(test)]
+#[path = "tests/layout_tests.rs"]
+mod tests;
@@ -0,0 +1,181 @@
+//! `bean fmt` command orchestration.
+//!
+//! WHAT: collects `.bst` files under a path, re-prints each one in canonical layout and either
+//! writes the result or, with `--check`, reports which files are not formatted.
+//! WHY: formatting needs only the token stream, so it runs without a project config and never
+//! compiles. `--check` gives CI a non-zero exit code without touching the tree.
+
+mod layout;
+
+use crate::compiler_frontend::compiler_errors::CompilerMessages;
+use crate::compiler_frontend::compiler_messages::render::relative_display_path_from_root;
+use crate::compiler_frontend::display_messages::print_compiler_messages;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::projects::html_project::style_directives::html_project_style_directives;
+use crate::projects::settings::BEANSTALK_FILE_EXTENSION;
+use saying::say;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) use layout::format_source;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FmtOptions {
+    /// Report unformatted files and fail instead of writing them.
+    pub check: bool,
+}
+
+pub(crate) struct FmtOutcome {
+    pub(crate) checked_files: usize,
+    /// Files whose formatted text differs from their source.
+    pub(crate) changed: Vec<PathBuf>,
+    /// Files that could not be formatted, e.g. because they do not tokenize.
+    pub(crate) failures: Vec<CompilerMessages>,
+}
+
+/// Run `bean fmt` and return the process exit code.
+pub fn run_fmt(path: &str, options: FmtOptions) -> i32 {
+    let outcome = match execute_fmt(path, options) {
+        Ok(outcome) => outcome,
+        Err(messages) => {
+            print_compiler_messages(messages);
+            return 1;
+        }
+    };
+
+    let working_directory = std::env::current_dir().unwrap_or_default();
+    let failed = !outcome.failures.is_empty();
+    for messages in outcome.failures {
+        print_compiler_messages(messages);
+    }
+
+    if options.check {
+        for file in &outcome.changed {
+            let display_path = relative_display_path_from_root(file, &working_directory);
+            say!(Yellow "Not formatted: ", Reset display_path);
+        }
+        if outcome.changed.is_empty() && !failed {
+            say!(
+                "🌱 All ",
+                Blue outcome.checked_files,
+                Reset " files are formatted."
+            );
+            return 0;
+        }
+        return 1;
+    }
+
+    say!(
+        "🌱 Formatted ",
+        Blue outcome.changed.len(),
+        Reset " of ",
+        Blue outcome.checked_files,
+        Reset " files."
+    );
+    i32::from(failed)
+}
+
+/// Format every `.bst` file under `path`. Files are only written when `check` is off.
+pub(crate) fn execute_fmt(path: &str, options: FmtOptions) -> Result<FmtOutcome, CompilerMessages> {
+    let string_table = StringTable::new();
+    let root = if path.is_empty() {
+        std::env::current_dir().unwrap_or_default()
+    } else {
+        PathBuf::from(path)
+    };
+
+    let files = if root.is_dir() {
+        let mut files = Vec::new();
+        collect_source_files(&root, &mut files);
+        files.sort();
+        files
+    } else if root.is_file() {
+        vec![root]
+    } else {
+        return Err(CompilerMessages::file_error(
+            &root,
+            "Path to format does not exist.",
+            &string_table,
+        ));
+    };
+
+    let style_directives = StyleDirectiveRegistry::merged(&html_project_style_directives())
+        .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
+
+    let mut outcome = FmtOutcome {
+        checked_files: files.len(),
+        changed: Vec::new(),
+        failures: Vec::new(),
+    };
+
+    for file in files {
+        let source = match fs::read_to_string(&file) {
+            Ok(source) => source,
+            Err(error) => {
+                outcome.failures.push(CompilerMessages::file_error(
+                    &file,
+                    format!("Failed to read source file: {error}"),
+                    &string_table,
+                ));
+                continue;
+            }
+        };
+
+        let formatted = match format_source(&source, &file, &style_directives) {
+            Ok(formatted) => formatted,
+            Err(messages) => {
+                outcome.failures.push(messages);
+                continue;
+            }
+        };
+        if formatted == source {
+            continue;
+        }
+
+        if !options.check
+            && let Err(error) = fs::write(&file, &formatted)
+        {
+            outcome.failures.push(CompilerMessages::file_error(
+                &file,
+                format!("Failed to write formatted source file: {error}"),
+                &string_table,
+            ));
+            continue;
+        }
+
+        outcome.changed.push(file);
+    }
+
+    Ok(outcome)
+}
+
+fn collect_source_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_source_files(&path, files);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == BEANSTALK_FILE_EXTENSION)
+        {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/fmt_tests.rs"]
+mod tests;
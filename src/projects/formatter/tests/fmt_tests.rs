@@ -0,0 +1,63 @@
+//! Tests for `bean fmt` file collection, check mode and writing.
+
+use super::{FmtOptions, execute_fmt};
+use crate::compiler_tests::test_support::temp_dir;
+use std::fs;
+
+const UNFORMATTED: &str = "run ||:\n  x = 1\n;\n";
+const FORMATTED: &str = "run ||:\n    x = 1\n;\n";
+
+#[test]
+fn check_mode_reports_unformatted_files_without_writing() {
+    let root = temp_dir("fmt_check_mode");
+    fs::create_dir_all(root.join("src")).expect("should create source dir");
+    fs::write(root.join("src/#page.bst"), UNFORMATTED).expect("should write source");
+    fs::write(root.join("src/done.bst"), FORMATTED).expect("should write source");
+
+    let outcome = execute_fmt(
+        root.to_str().expect("temp path should be UTF-8"),
+        FmtOptions { check: true },
+    )
+    .unwrap_or_else(|_| panic!("fmt should run"));
+
+    assert_eq!(outcome.checked_files, 2);
+    assert_eq!(outcome.changed, vec![root.join("src/#page.bst")]);
+    assert!(outcome.failures.is_empty());
+    assert_eq!(
+        fs::read_to_string(root.join("src/#page.bst")).expect("should read source"),
+        UNFORMATTED
+    );
+
+    fs::remove_dir_all(&root).expect("should remove temp dir");
+}
+
+#[test]
+fn write_mode_formats_source_files_and_skips_hidden_and_other_files() {
+    let root = temp_dir("fmt_write_mode");
+    fs::create_dir_all(root.join(".cache")).expect("should create hidden dir");
+    fs::write(root.join("#page.bst"), UNFORMATTED).expect("should write source");
+    fs::write(root.join(".cache/#page.bst"), UNFORMATTED).expect("should write hidden source");
+    fs::write(root.join("notes.md"), UNFORMATTED).expect("should write other file");
+    fs::write(root.join("broken.bst"), "x =1\n").expect("should write broken source");
+
+    let outcome = execute_fmt(
+        root.to_str().expect("temp path should be UTF-8"),
+        FmtOptions::default(),
+    )
+    .unwrap_or_else(|_| panic!("fmt should run"));
+
+    assert_eq!(outcome.checked_files, 2);
+    assert_eq!(outcome.failures.len(), 1);
+    assert_eq!(
+        fs::read_to_string(root.join("#page.bst")).expect("should read source"),
+        FORMATTED
+    );
+    for untouched in [".cache/#page.bst", "notes.md"] {
+        assert_eq!(
+            fs::read_to_string(root.join(untouched)).expect("should read file"),
+            UNFORMATTED
+        );
+    }
+
+    fs::remove_dir_all(&root).expect("should remove temp dir");
+}
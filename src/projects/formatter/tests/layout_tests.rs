@@ -0,0 +1,98 @@
+//! Tests for token-driven source layout.
+
+use super::format_source;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::projects::html_project::style_directives::html_project_style_directives;
+use std::path::Path;
+
+fn format(source: &str) -> String {
+    let registry = StyleDirectiveRegistry::merged(&html_project_style_directives())
+        .expect("HTML project style directives should merge");
+    let formatted = format_source(source, Path::new("main.bst"), &registry)
+        .unwrap_or_else(|_| panic!("source should format:\n{source}"));
+
+    assert_eq!(
+        format_source(&formatted, Path::new("main.bst"), &registry)
+            .unwrap_or_else(|_| panic!("formatted source should format again")),
+        formatted,
+        "formatting should be idempotent"
+    );
+    formatted
+}
+
+#[test]
+fn blocks_indent_by_depth_and_close_at_their_opening_level() {
+    let source = "add |a Int, b Int| -> Int:\n  if a is 0:\n        return b\n  else\n   return a  +  b\n  ;\n;\n";
+
+    assert_eq!(
+        format(source),
+        "add |a Int, b Int| -> Int:\n    if a is 0:\n        return b\n    else\n        return a + b\n    ;\n;\n"
+    );
+}
+
+#[test]
+fn match_arms_and_else_arms_stay_inside_the_match_block() {
+    let source = "if value is:\n< 5 => label = \"low\"\n        else => label = \"high\"\n;\n";
+
+    assert_eq!(
+        format(source),
+        "if value is:\n    < 5 => label = \"low\"\n    else => label = \"high\"\n;\n"
+    );
+}
+
+#[test]
+fn choice_declarations_open_blocks_but_variant_access_stays_tight() {
+    let source = "Status ::\n  Ready,\n      Busy | reason String |,\n;\n\nstate = Status::Ready\n";
+
+    assert_eq!(
+        format(source),
+        "Status ::\n    Ready,\n    Busy | reason String |,\n;\n\nstate = Status::Ready\n"
+    );
+}
+
+#[test]
+fn struct_fields_and_continuations_use_hanging_indents() {
+    let source = "Point = |\nx Int,\n        y Int,\n|\n\ntotal = add(1,\n2)\nlong = 1 +\n2\n";
+
+    assert_eq!(
+        format(source),
+        "Point = |\n    x Int,\n    y Int,\n|\n\ntotal = add(1,\n    2)\nlong = 1 +\n    2\n"
+    );
+}
+
+#[test]
+fn comments_keep_their_position_and_blank_lines_collapse() {
+    let source = "-- header\n\n\n\nrun ||:\n\n      -- own line\n  x = 1    -- trailing\n\n\n  y = 2\n\n;\n\n\n";
+
+    assert_eq!(
+        format(source),
+        "-- header\n\nrun ||:\n    -- own line\n    x = 1 -- trailing\n\n    y = 2\n;\n"
+    );
+}
+
+#[test]
+fn template_bodies_are_copied_byte_for_byte() {
+    let source = "page = [:\n   hello   [name]\n\n\n  world  \n]\n#[$md:\n#   Title\n  text\n]\n";
+
+    assert_eq!(format(source), source);
+}
+
+#[test]
+fn comment_only_files_and_missing_final_newlines_are_normalised() {
+    assert_eq!(format("-- one\n\n\n-- two"), "-- one\n\n-- two\n");
+    assert_eq!(format("x = 1"), "x = 1\n");
+    assert_eq!(format("x = 1\r\ny = 2\r\n"), "x = 1\ny = 2\n");
+}
+
+#[test]
+fn files_that_do_not_tokenize_are_rejected() {
+    let registry = StyleDirectiveRegistry::merged(&html_project_style_directives())
+        .expect("HTML project style directives should merge");
+
+    let result = format_source("total =1\n", Path::new("main.bst"), &registry);
+
+    let Err(messages) = result else {
+        panic!("a tokenizer error should stop formatting");
+    };
+    assert!(messages.has_errors());
+}
@@ -53,7 +53,7 @@ fn file_input_compiles_one_beandown_file() {
     let output = compile_ok(BeandownInput::File(source_path.clone()));
 
     assert_eq!(output.documents.len(), 1);
-    assert_eq!(output.documents[0].content, "<h1>Intro</h1>");
+    assert_eq!(output.documents[0].content, "<h1 id=\"intro\">Intro</h1>");
     assert_eq!(output.documents[0].relative_path, None);
     assert_eq!(
         output.documents[0].source_path,
@@ -267,7 +267,7 @@ fn source_input_compiles_bd_nested_authored_template() {
     }]));
 
     assert_eq!(output.documents.len(), 1);
-    assert_eq!(output.documents[0].content, "<h1>Nested</h1>");
+    assert_eq!(output.documents[0].content, "<h1 id=\"nested\">Nested</h1>");
 }
 
 #[test]
@@ -10,6 +10,7 @@ use crate::compiler_frontend::datatypes::environment::TypeEnvironment;
 use crate::compiler_frontend::external_packages::ExternalPackageRegistry;
 use crate::compiler_frontend::hir::module::HirModule;
 use crate::projects::html_project::document_config::HtmlDocumentConfig;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Module-level inputs shared by all HTML builder compilation paths.
@@ -21,6 +22,16 @@ pub(crate) struct HtmlModuleCompileInput<'a> {
     pub project_name: &'a str,
     pub document_config: &'a HtmlDocumentConfig,
     pub release_build: bool,
+    /// Served by `bean dev`: JS pages carry a source line table for the dev client error overlay.
+    pub dev_server: bool,
+    /// Write a `.js.map` source map next to each JS page: every dev build, and release builds
+    /// with `html_release_source_maps`.
+    pub source_maps: bool,
+    /// Roots the logical source paths in HIR locations resolve against, for `sourcesContent`.
+    pub source_roots: &'a [PathBuf],
     pub root_activity: &'a ModuleRootActivity,
+    /// Render runtime fragments at build time. The builder has already checked the config switch
+    /// and that no reachable call blocks prerendering.
+    pub prerender: bool,
     pub external_package_registry: Arc<ExternalPackageRegistry>,
 }
@@ -19,6 +19,19 @@ pub(crate) struct HtmlDocumentConfig {
     pub inject_color_scheme: bool,
     pub inject_core_css: bool,
     pub body_style: String,
+    /// Also write `.js.map` source maps for page bundles in release builds.
+    pub release_source_maps: bool,
+    /// Run each JS page under Node at build time and bake its runtime fragments into the HTML.
+    pub prerender: bool,
+    /// Absolute site URL (`https://example.com`) that `origin` paths are appended to. Sitemap and
+    /// feed entries must be absolute, so both require it.
+    pub site_url: Option<String>,
+    /// Emit `sitemap.xml` listing every page.
+    pub sitemap: bool,
+    /// Emit a `robots.txt` that allows all crawlers and points at the sitemap when one is emitted.
+    pub robots_txt: bool,
+    /// Emit an Atom `feed.xml` from the pages that declare `page_date`.
+    pub feed: bool,
 }
 
 impl Default for HtmlDocumentConfig {
@@ -33,6 +46,12 @@ impl Default for HtmlDocumentConfig {
             inject_color_scheme: true,
             inject_core_css: true,
             body_style: String::new(),
+            release_source_maps: false,
+            prerender: false,
+            site_url: None,
+            sitemap: false,
+            robots_txt: false,
+            feed: false,
         }
     }
 }
@@ -41,6 +60,21 @@ pub(crate) fn parse_html_document_config(
     config: &Config,
     string_table: &mut StringTable,
 ) -> Result<HtmlDocumentConfig, ProjectConfigError> {
+    let site_url = parse_site_url(config, string_table)?;
+    let sitemap = parse_bool(config, "html_sitemap", false, string_table)?;
+    let feed = parse_bool(config, "html_feed", false, string_table)?;
+    for (key, enabled) in [("html_sitemap", sitemap), ("html_feed", feed)] {
+        if enabled && site_url.is_none() {
+            return Err(config_value_error(
+                config,
+                key,
+                "true",
+                "'false' unless 'html_site_url' is set, because entries need absolute URLs",
+                string_table,
+            ));
+        }
+    }
+
     Ok(HtmlDocumentConfig {
         lang: parse_required_string(config, "html_lang", "en", true, string_table)?,
         title_prefix: parse_required_string(config, "html_title_prefix", "", false, string_table)?,
@@ -57,9 +91,47 @@ pub(crate) fn parse_html_document_config(
         inject_color_scheme: parse_bool(config, "html_inject_color_scheme", true, string_table)?,
         inject_core_css: parse_bool(config, "html_inject_core_css", true, string_table)?,
         body_style: parse_required_string(config, "html_body_style", "", false, string_table)?,
+        release_source_maps: parse_bool(config, "html_release_source_maps", false, string_table)?,
+        prerender: parse_bool(config, "html_prerender", false, string_table)?,
+        site_url,
+        sitemap,
+        robots_txt: parse_bool(config, "html_robots_txt", false, string_table)?,
+        feed,
     })
 }
 
+/// `html_site_url` must be an absolute `http(s)` URL with a host and nothing after it, because
+/// page paths (already carrying `origin`) are appended to it verbatim.
+fn parse_site_url(
+    config: &Config,
+    string_table: &mut StringTable,
+) -> Result<Option<String>, ProjectConfigError> {
+    let Some(site_url) = parse_optional_string(config, "html_site_url", string_table)? else {
+        return Ok(None);
+    };
+
+    let host = site_url
+        .strip_prefix("https://")
+        .or_else(|| site_url.strip_prefix("http://"));
+    let valid = host.is_some_and(|host| {
+        !host.is_empty()
+            && !host.contains(['/', '?', '#', '\\'])
+            && !host.chars().any(char::is_whitespace)
+    });
+
+    if !valid {
+        return Err(config_value_error(
+            config,
+            "html_site_url",
+            &site_url,
+            "an absolute 'https://' or 'http://' URL with no path or trailing slash, for example 'https://example.com'",
+            string_table,
+        ));
+    }
+
+    Ok(Some(site_url))
+}
+
 fn parse_required_string(
     config: &Config,
     key: &str,
@@ -275,14 +275,14 @@ fn indent_html_block(input: &str, indent: &str) -> String {
     output
 }
 
-fn escape_html_text(value: &str) -> String {
+pub(crate) fn escape_html_text(value: &str) -> String {
     value
         .replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
 }
 
-fn escape_html_attribute(value: &str) -> String {
+pub(crate) fn escape_html_attribute(value: &str) -> String {
     escape_html_text(value).replace('"', "&quot;")
 }
 
@@ -99,6 +99,18 @@ pub(crate) fn generate_module_glue(
     })
 }
 
+/// Whether `output_files` hold the glue module generated for `module`.
+///
+/// WHAT: glue is only emitted alongside an import preamble, so its presence means the page loads
+/// its bundle as an ES module script.
+/// WHY: lets the builder recognise module-script pages from reused artifacts as well as fresh ones.
+pub(crate) fn outputs_include_module_glue(module: &Module, output_files: &[OutputFile]) -> bool {
+    let glue_output_path = paths::glue_module_output_path(module);
+    output_files
+        .iter()
+        .any(|file| file.relative_output_path() == glue_output_path)
+}
+
 /// Build a map from package ID to the relative URL path of its emitted JS asset.
 ///
 /// WHAT: computes paths relative to the glue module so ES module imports resolve correctly
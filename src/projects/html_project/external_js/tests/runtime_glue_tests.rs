@@ -1,7 +1,7 @@
 //! Tests for generated HTML JS glue and runtime module resolution.
 
 use super::import_map::build_import_map_html;
-use super::paths::relative_url_path;
+use super::paths::{glue_module_output_path, relative_url_path};
 use super::runtime_modules::emit_build_runtime_modules;
 use super::source::{generate_fallible_wrapper, generate_infallible_wrapper};
 use super::*;
@@ -205,6 +205,23 @@ fn generate_module_glue_nested_html_output_path() {
     );
 }
 
+#[test]
+fn module_glue_in_outputs_marks_module_script_pages() {
+    let mut string_table = StringTable::new();
+    let module = create_test_module(PathBuf::from("#page.bst"), &mut string_table);
+    let page = OutputFile::new(PathBuf::from("index.html"), FileKind::Html(String::new()));
+    let glue = OutputFile::new(
+        glue_module_output_path(&module),
+        FileKind::Js(String::new()),
+    );
+
+    assert!(!outputs_include_module_glue(
+        &module,
+        std::slice::from_ref(&page)
+    ));
+    assert!(outputs_include_module_glue(&module, &[page, glue]));
+}
+
 #[test]
 fn generate_module_glue_asset_import_relative_to_glue_module() {
     let mut string_table = StringTable::new();
@@ -10,11 +10,15 @@ use crate::backends::external_package_validation::{
     BackendTarget, ExternalPackageValidationError, validate_hir_external_package_support,
 };
 use crate::build_system::build::{BackendBuilder, CleanupPolicy, Module, OutputFile, Project};
+use crate::build_system::create_project_modules::frontend_source_roots;
+use crate::build_system::incremental::ModuleArtifactCache;
 use crate::builder_surface::{BuilderSurface, SourceFileKind};
 use crate::compiler_frontend::Flag;
 use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
+use crate::compiler_frontend::compiler_messages::CompilerDiagnostic;
 use crate::compiler_frontend::style_directives::StyleDirectiveSpec;
 use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
 use crate::projects::html_project::binding_packages::web::canvas::register_web_canvas_package;
 use crate::projects::html_project::compile_input::HtmlModuleCompileInput;
 use crate::projects::html_project::diagnostics::{
@@ -25,9 +29,15 @@ use crate::projects::html_project::document_config::parse_html_document_config;
 use crate::projects::html_project::external_js::js_import_provider::JsExternalImportProvider;
 use crate::projects::html_project::external_js::runtime_assets::emit_external_js_runtime_assets;
 use crate::projects::html_project::external_js::runtime_emission_plan::HtmlExternalRuntimeEmissionPlan;
-use crate::projects::html_project::external_js::runtime_glue::emit_build_runtime_modules;
-use crate::projects::html_project::js_path::{compile_html_module_js, html_output_path};
+use crate::projects::html_project::external_js::runtime_glue::{
+    emit_build_runtime_modules, outputs_include_module_glue,
+};
+use crate::projects::html_project::js_path::{
+    compile_html_module_js, html_output_path, prerender_blocking_call,
+};
+use crate::projects::html_project::page_index::HtmlPageIndex;
 use crate::projects::html_project::path_policy::HtmlEntryPathPlan;
+use crate::projects::html_project::site_files::render_site_files;
 use crate::projects::html_project::style_directives::html_project_style_directives;
 use crate::projects::html_project::tracked_assets::{
     emit_tracked_assets, plan_module_tracked_assets,
@@ -78,16 +88,101 @@ impl BackendBuilder for HtmlProjectBuilder {
         config: &Config,
         flags: &[Flag],
         string_table: &mut StringTable,
+    ) -> Result<Project, CompilerMessages> {
+        self.build_html_project(modules, config, flags, string_table, None)
+    }
+
+    fn build_backend_incremental(
+        &self,
+        modules: Vec<Module>,
+        config: &Config,
+        flags: &[Flag],
+        string_table: &mut StringTable,
+        artifacts: &mut ModuleArtifactCache,
+    ) -> Result<Project, CompilerMessages> {
+        self.build_html_project(modules, config, flags, string_table, Some(artifacts))
+    }
+
+    fn validate_project_config(
+        &self,
+        config: &Config,
+        string_table: &mut StringTable,
+    ) -> Result<(), ProjectConfigError> {
+        // Validate HTML-specific configuration up front so build/dev runtime behavior stays
+        // deterministic and all routing-policy mistakes are surfaced as config errors.
+        parse_html_site_config(config, string_table)?;
+        parse_html_document_config(config, string_table)?;
+
+        // Empty dev/release folders are allowed and resolved by core build output logic.
+        Ok(())
+    }
+
+    fn frontend_style_directives(&self) -> Vec<StyleDirectiveSpec> {
+        html_project_style_directives()
+    }
+
+    fn frontend_surface(&self) -> BuilderSurface {
+        let mut builder_surface = BuilderSurface::with_mandatory_core();
+        builder_surface.source_packages.register_filesystem_root(
+            HTML_SOURCE_PACKAGE_PREFIX,
+            BuilderSurface::builtin_source_package_root(HTML_SOURCE_PACKAGE_PREFIX),
+            crate::builder_surface::PackageOrigin::Builder,
+        );
+
+        builder_surface.expose_html_core_packages();
+
+        let canvas_metadata = register_web_canvas_package(&mut builder_surface.binding_packages);
+        builder_surface
+            .builder_runtime_packages
+            .push(canvas_metadata);
+
+        Self::register_html_config_keys(&mut builder_surface);
+
+        builder_surface.source_file_kinds.register(
+            SourceFileKind::Beandown.extension(),
+            SourceFileKind::Beandown,
+        );
+        builder_surface.source_file_kinds.register(
+            SourceFileKind::PlainMarkdown.extension(),
+            SourceFileKind::PlainMarkdown,
+        );
+
+        builder_surface
+            .external_import_providers
+            .register(std::sync::Arc::new(JsExternalImportProvider::new()));
+
+        if self.include_test_packages {
+            builder_surface.binding_packages = builder_surface
+                .binding_packages
+                .with_test_packages_for_integration();
+        }
+
+        builder_surface
+    }
+}
+
+impl HtmlProjectBuilder {
+    /// Shared HTML build, optionally reusing page artifacts of unchanged modules.
+    ///
+    /// Runtime assets, glue and tracked assets are always planned from the full module set; only
+    /// per-page compilation consults `artifacts`.
+    fn build_html_project(
+        &self,
+        modules: Vec<Module>,
+        config: &Config,
+        flags: &[Flag],
+        string_table: &mut StringTable,
+        mut artifacts: Option<&mut ModuleArtifactCache>,
     ) -> Result<Project, CompilerMessages> {
         // Record the full backend build duration on every exit path (success or error).
         let _total_guard = crate::timing::PipelineTimingGuard::new("backend.html.total");
 
-        {
+        let site_config = {
             let _site_config_guard =
                 crate::timing::PipelineTimingGuard::new("backend.html.site_config");
             parse_html_site_config(config, string_table)
-                .map_err(|error| error.into_messages(string_table.clone()))?;
-        }
+                .map_err(|error| error.into_messages(string_table.clone()))?
+        };
 
         let document_config = {
             let _document_config_guard =
@@ -107,6 +202,8 @@ impl BackendBuilder for HtmlProjectBuilder {
 
         let release_build = flags.contains(&Flag::Release);
         let wasm_enabled = flags.contains(&Flag::HtmlWasm);
+        let dev_server = flags.contains(&Flag::DevServer);
+        let source_roots = frontend_source_roots(config);
         let entry_paths = {
             let _entry_path_guard =
                 crate::timing::PipelineTimingGuard::new("backend.html.entry_path_plan");
@@ -138,15 +235,64 @@ impl BackendBuilder for HtmlProjectBuilder {
                 )
                 .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
 
-                let compiled_artifacts = self.compile_one_module(
-                    module,
-                    &logical_html_output_path,
-                    config.project_name.as_str(),
-                    &document_config,
-                    release_build,
-                    wasm_enabled,
-                    string_table,
-                )?;
+                // Checked outside the artifact cache so reused pages keep reporting the warning.
+                let mut prerender = document_config.prerender
+                    && !wasm_enabled
+                    && module.metadata.root_activity.runtime_fragment_count > 0;
+                if prerender
+                    && let Some(blocking_call) = prerender_blocking_call(
+                        &module.executable.hir,
+                        &module.link_facts.external_package_registry,
+                    )
+                    .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?
+                {
+                    warnings.push(CompilerDiagnostic::prerender_excluded_page(
+                        string_table.intern(&blocking_call.function_path),
+                        blocking_call.location,
+                    ));
+                    prerender = false;
+                }
+
+                let reused_artifacts = artifacts
+                    .as_deref_mut()
+                    .and_then(|artifacts| artifacts.reuse(&module.metadata.entry_point));
+                let compiled_artifacts = match reused_artifacts {
+                    Some((output_files, html_output_path)) => CompiledHtmlModuleArtifacts {
+                        output_files,
+                        html_output_path,
+                    },
+                    None => {
+                        let compiled_artifacts = self.compile_one_module(
+                            module,
+                            &logical_html_output_path,
+                            config.project_name.as_str(),
+                            &document_config,
+                            release_build,
+                            dev_server,
+                            &source_roots,
+                            wasm_enabled,
+                            prerender,
+                            string_table,
+                        )?;
+                        if let Some(artifacts) = artifacts.as_deref_mut() {
+                            artifacts.store(
+                                &module.metadata.entry_point,
+                                &compiled_artifacts.output_files,
+                                &compiled_artifacts.html_output_path,
+                            );
+                        }
+                        compiled_artifacts
+                    }
+                };
+
+                // The JS path skips module-script pages; glue output marks them on reuse too.
+                if prerender
+                    && outputs_include_module_glue(module, &compiled_artifacts.output_files)
+                {
+                    warnings.push(CompilerDiagnostic::prerender_module_script_page(
+                        SourceLocation::from_path(&module.metadata.entry_point, string_table),
+                    ));
+                }
 
                 let html_output_path = compiled_artifacts.html_output_path.clone();
                 for output_file in compiled_artifacts.output_files {
@@ -175,6 +321,40 @@ impl BackendBuilder for HtmlProjectBuilder {
             }
         }
 
+        let page_index = {
+            let _page_index_guard =
+                crate::timing::PipelineTimingGuard::new("backend.html.page_index");
+            HtmlPageIndex::from_compiled_pages(
+                &compiled_html_output_paths,
+                &site_config,
+                string_table,
+            )?
+        };
+
+        {
+            // Filled after the module loop because `$pages` lists every route, and after artifact
+            // caching so cached pages keep their placeholders for the next build.
+            let _navigation_guard =
+                crate::timing::PipelineTimingGuard::new("backend.html.navigation");
+            page_index.fill_output_files(&mut output_files);
+        }
+
+        {
+            let _site_files_guard =
+                crate::timing::PipelineTimingGuard::new("backend.html.site_files");
+            // Page routes always end in `.html`, so site files cannot collide with them. Claiming
+            // the paths here lets later runtime and tracked assets report a conflict instead.
+            for site_file in render_site_files(
+                &page_index,
+                &document_config,
+                &site_config.origin,
+                config.project_name.as_str(),
+            ) {
+                output_paths.insert(site_file.relative_output_path().to_path_buf());
+                output_files.push(site_file);
+            }
+        }
+
         entry_paths.require_homepage_if_directory_build(
             config,
             has_directory_homepage,
@@ -259,65 +439,6 @@ impl BackendBuilder for HtmlProjectBuilder {
         })
     }
 
-    fn validate_project_config(
-        &self,
-        config: &Config,
-        string_table: &mut StringTable,
-    ) -> Result<(), ProjectConfigError> {
-        // Validate HTML-specific configuration up front so build/dev runtime behavior stays
-        // deterministic and all routing-policy mistakes are surfaced as config errors.
-        parse_html_site_config(config, string_table)?;
-        parse_html_document_config(config, string_table)?;
-
-        // Empty dev/release folders are allowed and resolved by core build output logic.
-        Ok(())
-    }
-
-    fn frontend_style_directives(&self) -> Vec<StyleDirectiveSpec> {
-        html_project_style_directives()
-    }
-
-    fn frontend_surface(&self) -> BuilderSurface {
-        let mut builder_surface = BuilderSurface::with_mandatory_core();
-        builder_surface.source_packages.register_filesystem_root(
-            HTML_SOURCE_PACKAGE_PREFIX,
-            BuilderSurface::builtin_source_package_root(HTML_SOURCE_PACKAGE_PREFIX),
-            crate::builder_surface::PackageOrigin::Builder,
-        );
-
-        builder_surface.expose_html_core_packages();
-
-        let canvas_metadata = register_web_canvas_package(&mut builder_surface.binding_packages);
-        builder_surface
-            .builder_runtime_packages
-            .push(canvas_metadata);
-
-        Self::register_html_config_keys(&mut builder_surface);
-
-        builder_surface.source_file_kinds.register(
-            SourceFileKind::Beandown.extension(),
-            SourceFileKind::Beandown,
-        );
-        builder_surface.source_file_kinds.register(
-            SourceFileKind::PlainMarkdown.extension(),
-            SourceFileKind::PlainMarkdown,
-        );
-
-        builder_surface
-            .external_import_providers
-            .register(std::sync::Arc::new(JsExternalImportProvider::new()));
-
-        if self.include_test_packages {
-            builder_surface.binding_packages = builder_surface
-                .binding_packages
-                .with_test_packages_for_integration();
-        }
-
-        builder_surface
-    }
-}
-
-impl HtmlProjectBuilder {
     /// Register HTML-backend-specific config keys into the builder surface's key registry.
     ///
     /// WHY: Stage 0 config loading must know which keys are valid before backend semantic
@@ -340,6 +461,14 @@ impl HtmlProjectBuilder {
         registry.register_backend_bool("html_inject_color_scheme");
         registry.register_backend_bool("html_inject_core_css");
         registry.register_backend_string("html_body_style");
+        registry.register_backend_bool("html_release_source_maps");
+        registry.register_backend_bool("html_prerender");
+
+        // Site file keys
+        registry.register_backend_string("html_site_url");
+        registry.register_backend_bool("html_sitemap");
+        registry.register_backend_bool("html_robots_txt");
+        registry.register_backend_bool("html_feed");
     }
 
     /// Compile one module through the appropriate builder path (JS-only or HTML+Wasm).
@@ -351,7 +480,10 @@ impl HtmlProjectBuilder {
         project_name: &str,
         document_config: &crate::projects::html_project::document_config::HtmlDocumentConfig,
         release_build: bool,
+        dev_server: bool,
+        source_roots: &[PathBuf],
         wasm_enabled: bool,
+        prerender: bool,
         string_table: &mut StringTable,
     ) -> Result<CompiledHtmlModuleArtifacts, CompilerMessages> {
         // Validate that every external function call in the HIR has lowering metadata for the
@@ -422,7 +554,11 @@ impl HtmlProjectBuilder {
             project_name,
             document_config,
             release_build,
+            dev_server,
+            source_maps: !release_build || document_config.release_source_maps,
+            source_roots,
             root_activity: &module.metadata.root_activity,
+            prerender,
             external_package_registry: Arc::clone(&module.link_facts.external_package_registry),
         };
         if wasm_enabled {
@@ -12,25 +12,72 @@
 //!      that would prematurely close the script tag.
 //!   4. A second inline `<script>` calls entry `start()` once. start() returns the
 //!      runtime fragment array and each element is hydrated into its slot in source order.
-
-use crate::backends::js::{JsLoweringConfig, lower_hir_to_js};
+//!   5. Dev-server builds append a JSON source line table at the end of `<body>` so the dev
+//!      client can map runtime errors back to `.bst` lines.
+//!   6. Builds with source maps end the bundle with a `//# sourceMappingURL` comment and write
+//!      `<page>.js.map` next to the HTML file, mapping the inline script back to `.bst` files.
+//!
+//! With `html_prerender` enabled, the bundle is also run under Node at build time and the initial
+//! HTML of every runtime fragment is baked into its slot. The bootstrap then hydrates: plain
+//! fragments are left as rendered and reactive fragments are mounted against the existing DOM.
+//! Pages whose output depends on the browser (clock reads, random numbers, `@core/io` input or
+//! external JS bindings) and pages that load their bundle as an ES module script are left
+//! unrendered with a warning. A page that does not finish rendering in time fails the build.
+
+use crate::backends::js::{
+    JsLoweringConfig, JsSourceLine, JsSourceMap, JsSourceOrigin, lower_hir_to_js,
+};
 use crate::build_system::build::{FileKind, Module, OutputFile, ResolvedConstFragment};
 use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
+use crate::compiler_frontend::external_packages::{
+    CORE_IO_PACKAGE_PATH, ExternalFunctionId, ExternalPackageRegistry,
+};
 use crate::compiler_frontend::hir::ids::FunctionId;
 use crate::compiler_frontend::hir::module::HirModule;
 use crate::compiler_frontend::hir::reachability::{
     ReachableReactiveSinkKind, collect_reachability_from_start,
 };
 use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
 use crate::projects::html_project::compile_input::HtmlModuleCompileInput;
 use crate::projects::html_project::document_config::HtmlDocumentConfig;
 use crate::projects::html_project::document_shell::render_html_document_shell;
 use crate::projects::html_project::external_js::runtime_glue::generate_module_glue;
 use crate::projects::html_project::output_plan::derive_logical_html_path;
 use crate::projects::html_project::page_metadata::extract_html_page_metadata;
+use serde_json::json;
 use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Element ID of the dev-server source line table read by the dev client.
+pub(crate) const SOURCE_LINE_TABLE_ID: &str = "bst-source-lines";
+
+/// Comment line written in front of the lowered bundle when source lines are tracked.
+const BUNDLE_START_MARKER: &str = "// bst-bundle-start";
+
+/// How long the build waits for one page to render under Node before killing it.
+const PRERENDER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval between checks of whether the Node process has exited.
+const PRERENDER_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// `@core/io` functions that only write to the console, so they are safe to run at build time.
+const PRERENDER_SAFE_IO_FUNCTIONS: &[ExternalFunctionId] = &[
+    ExternalFunctionId::IoPrint,
+    ExternalFunctionId::IoLine,
+    ExternalFunctionId::IoDebug,
+    ExternalFunctionId::IoWarn,
+    ExternalFunctionId::IoError,
+];
+
+/// `@core/time` functions that read a clock. The rest of the package is pure arithmetic.
+const CLOCK_READING_TIME_FUNCTIONS: &[&str] = &["mark_now", "elapsed_since", "timestamp_now"];
 
 /// Inputs for rendering a JS-backed HTML document.
 ///
@@ -54,6 +101,15 @@ pub(crate) struct HtmlDocumentRenderInput<'a> {
     pub import_map_html: Option<String>,
     /// Whether the runtime bundle must be emitted as an ES module script.
     pub use_module_script: bool,
+    /// Initial HTML of each runtime fragment when the page was prerendered at build time.
+    pub prerendered_fragments: Option<Vec<String>>,
+}
+
+/// A reachable external call whose result at build time would not match the browser.
+pub(crate) struct PrerenderBlockingCall {
+    /// Call path as written in source, such as `time.mark_now`.
+    pub function_path: String,
+    pub location: SourceLocation,
 }
 
 /// Artifacts produced by the JS-only HTML compilation path.
@@ -72,10 +128,12 @@ pub(crate) fn compile_html_module_js(
     string_table: &mut StringTable,
     output_path: PathBuf,
 ) -> Result<CompiledHtmlJsModule, CompilerMessages> {
-    let js_lowering_config = JsLoweringConfig::html_page_bundle(
+    let mut js_lowering_config = JsLoweringConfig::html_page_bundle(
         input.release_build,
         Arc::clone(&input.external_package_registry),
     );
+    let track_source_lines = input.dev_server || input.source_maps;
+    js_lowering_config.track_source_lines = track_source_lines;
 
     let js_module = {
         let _lower_hir_guard = crate::timing::PipelineTimingGuard::new("backend.js.lower_hir");
@@ -109,11 +167,62 @@ pub(crate) fn compile_html_module_js(
     };
 
     let use_module_script = glue_result.bundle_import_preamble.is_some();
-    let bundle_with_imports = if let Some(ref preamble) = glue_result.bundle_import_preamble {
-        format!("{preamble}{}", js_module.source)
+    // Node runs the bundle as a classic script, so module-script pages are left to the browser.
+    let prerender = input.prerender && !use_module_script;
+    let prerendered_fragments = match js_module
+        .function_name_by_id
+        .get(&input.hir_module.start_function)
+    {
+        Some(start_function_name) if prerender => {
+            let _prerender_guard =
+                crate::timing::PipelineTimingGuard::new("backend.js.prerender_fragments");
+            let fragments = prerender_runtime_fragments(
+                &js_module.source,
+                start_function_name,
+                PRERENDER_TIMEOUT,
+            )
+            .map_err(|reason| {
+                CompilerMessages::file_error(
+                    &module.metadata.entry_point,
+                    format!("Prerendering this page failed: {reason}"),
+                    string_table,
+                )
+            })?;
+            if fragments.len() != input.root_activity.runtime_fragment_count {
+                return Err(CompilerMessages::from_error(
+                    CompilerError::compiler_error(format!(
+                        "Prerendering produced {} runtime fragments, expected {}.",
+                        fragments.len(),
+                        input.root_activity.runtime_fragment_count
+                    )),
+                    string_table.clone(),
+                ));
+            }
+            Some(fragments)
+        }
+        _ => None,
+    };
+    // Maps are only worth writing when lowering recorded real source positions.
+    let source_map_path = (input.source_maps && !js_module.source_lines.is_empty())
+        .then(|| output_path.with_extension("js.map"));
+    // The marker anchors source lines to the rendered script once the shell is rendered.
+    let mut bundle_source = if track_source_lines {
+        format!("{BUNDLE_START_MARKER}\n{}", js_module.source)
     } else {
         js_module.source.clone()
     };
+    if let Some(map_path) = &source_map_path {
+        let map_url = map_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        bundle_source.push_str(&format!("\n//# sourceMappingURL={map_url}"));
+    }
+    let bundle_with_imports = if let Some(ref preamble) = glue_result.bundle_import_preamble {
+        format!("{preamble}{bundle_source}")
+    } else {
+        bundle_source
+    };
 
     let html = {
         let _render_guard =
@@ -131,11 +240,31 @@ pub(crate) fn compile_html_module_js(
             uses_reactive_runtime_fragments,
             import_map_html: glue_result.import_map_html,
             use_module_script,
+            prerendered_fragments,
         })?
     };
+    let html = if input.dev_server {
+        append_source_line_table(html, &js_module.source_lines)
+    } else {
+        html
+    };
 
-    let mut output_files = Vec::with_capacity(1 + glue_result.glue_output_files.len());
+    let source_map = source_map_path.and_then(|map_path| {
+        let json = render_source_map(
+            &html,
+            &js_module.source_lines,
+            &output_path,
+            input.source_roots,
+        )?;
+        Some(OutputFile::new(
+            map_path,
+            FileKind::Bytes(json.into_bytes()),
+        ))
+    });
+
+    let mut output_files = Vec::with_capacity(2 + glue_result.glue_output_files.len());
     output_files.push(OutputFile::new(output_path.clone(), FileKind::Html(html)));
+    output_files.extend(source_map);
     output_files.extend(glue_result.glue_output_files);
 
     Ok(CompiledHtmlJsModule {
@@ -187,15 +316,148 @@ fn html_module_uses_reactive_runtime_fragments(
     }))
 }
 
+/// Finds the first reachable external call that keeps this page from being prerendered.
+///
+/// WHAT: clock reads, `@core/random`, `@core/io` input and functions from non-core packages
+/// (external JS bindings) all depend on the browser the page runs in.
+/// WHY: baking their build-time results into the document would show a stale value until
+///      hydration replaced it, or never replace it at all for plain fragments.
+pub(crate) fn prerender_blocking_call(
+    hir_module: &HirModule,
+    registry: &ExternalPackageRegistry,
+) -> Result<Option<PrerenderBlockingCall>, CompilerError> {
+    let reachability = collect_reachability_from_start(hir_module)?;
+
+    Ok(reachability
+        .reachable_external_calls
+        .iter()
+        .find_map(|call| {
+            let package_path = registry.resolve_function_package(call.function_id)?;
+            let function_name = registry.get_function_by_id(call.function_id)?.name.as_str();
+            let blocks_prerender = match package_path {
+                "@core/random" => true,
+                "@core/time" => CLOCK_READING_TIME_FUNCTIONS.contains(&function_name),
+                CORE_IO_PACKAGE_PATH => !PRERENDER_SAFE_IO_FUNCTIONS.contains(&call.function_id),
+                core_package if core_package.starts_with("@core/") => false,
+                _ => true,
+            };
+            if !blocks_prerender {
+                return None;
+            }
+
+            let symbol_path = registry
+                .resolve_function_symbol_path(call.function_id)
+                .map(|path| path.display_text())
+                .unwrap_or_else(|| function_name.to_owned());
+            let namespace = package_path.rsplit('/').next().unwrap_or(package_path);
+            Some(PrerenderBlockingCall {
+                function_path: format!("{namespace}.{symbol_path}"),
+                location: call.location.clone(),
+            })
+        }))
+}
+
+/// Runs the page bundle under Node and returns the initial HTML of each runtime fragment.
+///
+/// WHAT: console output is silenced, `start()` runs once and every fragment is flattened to its
+/// snapshot, the same HTML the bootstrap would insert into the slot. Node is killed when it
+/// has not exited after `timeout`.
+/// WHY: Node is the engine the integration harness already drives, so a page that passes its
+///      rendered-output tests prerenders the same way. The timeout keeps a page that never
+///      returns from `start()` from hanging the build.
+fn prerender_runtime_fragments(
+    js_bundle: &str,
+    start_function_name: &str,
+    timeout: Duration,
+) -> Result<Vec<String>, String> {
+    let script = format!(
+        "const __bst_stdout = process.stdout;\n\
+         for (const method of [\"log\", \"info\", \"debug\", \"warn\", \"error\"]) {{\n\
+         \x20   console[method] = () => {{}};\n\
+         }}\n\
+         {js_bundle}\n\
+         const __bst_fragments = {start_function_name}() || [];\n\
+         const __bst_html = __bst_fragments.map((fragment) => {{\n\
+         \x20   if (fragment === null || fragment === undefined) return \"\";\n\
+         \x20   if (fragment.__bs_template === true && typeof fragment.snapshot === \"function\") {{\n\
+         \x20       return fragment.snapshot();\n\
+         \x20   }}\n\
+         \x20   return String(fragment);\n\
+         }});\n\
+         __bst_stdout.write(JSON.stringify(__bst_html), () => process.exit(0));\n"
+    );
+
+    let mut child = Command::new("node")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("could not start 'node' ({error}); it must be on PATH"))?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| String::from("could not write to the node process"))?
+        .write_all(script.as_bytes())
+        .map_err(|error| format!("could not write to the node process ({error})"))?;
+
+    // Drain both pipes while waiting so a page that logs a lot cannot stall on a full pipe.
+    let stdout = read_pipe_in_background(child.stdout.take());
+    let stderr = read_pipe_in_background(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(PRERENDER_POLL_INTERVAL),
+            Ok(None) => {
+                // Best effort: the process may exit on its own between the check and the kill.
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "the page did not finish rendering within {} ms",
+                    timeout.as_millis()
+                ));
+            }
+            Err(error) => return Err(format!("the node process failed ({error})")),
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        return Err(format!(
+            "the page threw while rendering:\n{}",
+            String::from_utf8_lossy(&stderr).trim_end()
+        ));
+    }
+    serde_json::from_slice(&stdout)
+        .map_err(|error| format!("node returned malformed fragment output ({error})"))
+}
+
+/// Reads a child pipe to its end on a separate thread.
+fn read_pipe_in_background(
+    pipe: Option<impl Read + Send + 'static>,
+) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
 /// Renders entry-file start fragments into static HTML and an ordered list of slot IDs.
 ///
 /// WHAT: merges const fragments (with runtime insertion indices) and runtime slot placeholders
 /// into source-order HTML. Returns slot IDs so the bootstrap script can hydrate them in order.
+/// Prerendered fragments, when given, are written inside their slots.
 /// WHY: source order requires interleaving const strings at their indexed positions
 ///      relative to runtime slots. Slot count is supplied by the caller from HIR.
 pub(crate) fn render_entry_fragments(
     const_fragments: &[ResolvedConstFragment],
     slot_count: usize,
+    prerendered_fragments: Option<&[String]>,
 ) -> (String, Vec<String>) {
     let mut html = String::new();
     let mut slot_ids: Vec<String> = Vec::new();
@@ -219,7 +481,10 @@ pub(crate) fn render_entry_fragments(
     // Interleave runtime slots and const fragments.
     for _ in 0..slot_count {
         let slot_id = format!("bst-slot-{runtime_index}");
-        html.push_str(&format!("<div id=\"{slot_id}\"></div>\n"));
+        let slot_html = prerendered_fragments
+            .and_then(|fragments| fragments.get(runtime_index))
+            .map_or("", String::as_str);
+        html.push_str(&format!("<div id=\"{slot_id}\">{slot_html}</div>\n"));
         slot_ids.push(slot_id);
         runtime_index += 1;
 
@@ -242,8 +507,11 @@ pub(crate) fn render_entry_fragments(
 pub(crate) fn render_html_document(
     input: &mut HtmlDocumentRenderInput<'_>,
 ) -> Result<String, CompilerMessages> {
-    let (body_html, slot_ids) =
-        render_entry_fragments(input.const_fragments, input.entry_runtime_fragment_count);
+    let (body_html, slot_ids) = render_entry_fragments(
+        input.const_fragments,
+        input.entry_runtime_fragment_count,
+        input.prerendered_fragments.as_deref(),
+    );
     let page_metadata =
         extract_html_page_metadata(input.hir_module, input.string_table).map_err(|diagnostic| {
             CompilerMessages::from_diagnostic_ref(*diagnostic, input.string_table)
@@ -266,6 +534,7 @@ pub(crate) fn render_html_document(
         &slot_ids,
         input.use_module_script,
         input.uses_reactive_runtime_fragments,
+        input.prerendered_fragments.is_some(),
     );
 
     render_html_document_shell(
@@ -286,6 +555,7 @@ fn render_runtime_bootstrap_script_html(
     slot_ids: &[String],
     is_module_script: bool,
     uses_reactive_runtime_fragments: bool,
+    prerendered: bool,
 ) -> String {
     // Escape the bundle so any `</script>` sequence inside string literals or comments cannot
     // prematurely terminate the HTML script tag and corrupt the page.
@@ -304,6 +574,7 @@ fn render_runtime_bootstrap_script_html(
             slot_ids,
             "",
             uses_reactive_runtime_fragments,
+            prerendered,
         );
         html.push_str("</script>\n");
         html
@@ -321,6 +592,7 @@ fn render_runtime_bootstrap_script_html(
             slot_ids,
             "  ",
             uses_reactive_runtime_fragments,
+            prerendered,
         );
         html.push_str("})();\n");
         html.push_str("</script>\n");
@@ -334,8 +606,10 @@ fn append_runtime_bootstrap(
     slot_ids: &[String],
     indent: &str,
     uses_reactive_runtime_fragments: bool,
+    prerendered: bool,
 ) {
-    if slot_ids.is_empty() {
+    // Prerendered plain fragments are already in their slots, so start() only runs the lifecycle.
+    if slot_ids.is_empty() || (prerendered && !uses_reactive_runtime_fragments) {
         html.push_str(&format!(
             "{indent}if (typeof {start_function_name} === \"function\") {start_function_name}();\n"
         ));
@@ -363,7 +637,13 @@ fn append_runtime_bootstrap(
         "{indent}  if (!el) throw new Error(\"Missing runtime mount slot: \" + bst_slots[i]);\n"
     ));
 
-    if uses_reactive_runtime_fragments {
+    if uses_reactive_runtime_fragments && prerendered {
+        // Prerendered reactive pages hydrate: the helper registers each template fragment and
+        // patches the baked HTML in place instead of inserting it again.
+        html.push_str(&format!(
+            "{indent}  __bs_mount_template_fragment(el, bst_frags[i], true);\n"
+        ));
+    } else if uses_reactive_runtime_fragments {
         // Reactive pages use the backend mount helper so template fragments can register for
         // rerendering. The helper also handles plain-string fragments, preserving source order.
         html.push_str(&format!(
@@ -380,6 +660,113 @@ fn append_runtime_bootstrap(
     html.push_str(&format!("{indent}}}\n"));
 }
 
+/// Appends the source line table of the lowered bundle to a rendered dev-server page.
+///
+/// WHAT: records the document line of the bundle marker as `offset`, so generated JS line `n`
+///       sits on document line `offset + n`, followed by `[line, file, source line, column]`
+///       entries (`[line]` for code without a source origin).
+/// WHY: inline scripts report errors against document lines. The table goes after the bundle so
+///      it never shifts the lines it describes.
+fn append_source_line_table(html: String, source_lines: &[JsSourceLine]) -> String {
+    let Some(marker_index) = html.find(BUNDLE_START_MARKER) else {
+        return html;
+    };
+    let offset = html[..marker_index].matches('\n').count() + 1;
+    let lines = source_lines
+        .iter()
+        .map(|entry| match &entry.origin {
+            Some(JsSourceOrigin { file, line, column }) => json!([
+                entry.generated_line,
+                file.to_string_lossy().replace('\\', "/"),
+                line,
+                column
+            ]),
+            None => json!([entry.generated_line]),
+        })
+        .collect::<Vec<_>>();
+    let table = json!({ "offset": offset, "lines": lines });
+    let script = format!(
+        "    <script type=\"application/json\" id=\"{SOURCE_LINE_TABLE_ID}\">{}</script>\n",
+        escape_inline_script(&table.to_string())
+    );
+
+    let insert_at = html.rfind("</body>").map_or(html.len(), |body_index| {
+        html[..body_index]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1)
+    });
+    let mut with_table = html;
+    with_table.insert_str(insert_at, &script);
+    with_table
+}
+
+/// Renders the v3 source map for the inline bundle of a rendered page.
+///
+/// WHAT: positions are relative to the text of the `<script>` element holding the bundle marker,
+///       which is what browsers map inline scripts against. Sources are written relative to the
+///       map with their contents embedded, since `.bst` files are not part of the output.
+/// WHY: mapping against the final HTML keeps the map correct after shell indentation and any
+///      glue import preamble.
+fn render_source_map(
+    html: &str,
+    source_lines: &[JsSourceLine],
+    output_path: &Path,
+    source_roots: &[PathBuf],
+) -> Option<String> {
+    let marker_index = html.find(BUNDLE_START_MARKER)?;
+    let script_tag = html[..marker_index].rfind("<script")?;
+    let script_start = script_tag + html[script_tag..].find('>')? + 1;
+    let script_end = marker_index + html[marker_index..].find("</script>")?;
+    let bundle_start_line = html[script_start..marker_index].matches('\n').count() + 1;
+
+    let source_map = JsSourceMap::from_source_lines(
+        &html[script_start..script_end],
+        bundle_start_line,
+        source_lines,
+    );
+
+    let up_to_output_root = "../".repeat(output_path.components().count().saturating_sub(1));
+    let source_urls = source_map
+        .sources
+        .iter()
+        .map(|file| {
+            let url = file.to_string_lossy().replace('\\', "/");
+            format!("{up_to_output_root}{}", encode_source_url(&url))
+        })
+        .collect::<Vec<_>>();
+    let sources_content = source_map
+        .sources
+        .iter()
+        .map(|file| {
+            source_roots
+                .iter()
+                .find_map(|root| fs::read_to_string(root.join(file)).ok())
+        })
+        .collect::<Vec<_>>();
+
+    let file = output_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Some(source_map.to_json(&file, &source_urls, &sources_content))
+}
+
+/// Percent-encodes the characters of a logical source path that URLs would treat specially,
+/// such as the `#` of `#page.bst`.
+fn encode_source_url(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for character in path.chars() {
+        match character {
+            '%' => encoded.push_str("%25"),
+            '#' => encoded.push_str("%23"),
+            '?' => encoded.push_str("%3F"),
+            ' ' => encoded.push_str("%20"),
+            _ => encoded.push(character),
+        }
+    }
+    encoded
+}
+
 /// Derive the logical HTML output path for this entry file.
 ///
 /// Delegates to the canonical output planner so JS-only and Wasm paths agree on route derivation.
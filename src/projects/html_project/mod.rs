@@ -15,8 +15,10 @@ pub mod html_project_builder;
 pub(crate) mod js_path;
 pub mod new_html_project;
 pub(crate) mod output_plan;
+pub(crate) mod page_index;
 pub(crate) mod page_metadata;
 pub(crate) mod path_policy;
+pub(crate) mod site_files;
 pub(crate) mod style_directives;
 pub(crate) mod styles;
 pub(crate) mod tracked_assets;
@@ -0,0 +1,485 @@
+//! Page heading outlines and the project page list for HTML navigation.
+//!
+//! WHAT: fills the `$toc` and `$pages` placeholders in rendered page documents. `$toc` becomes a
+//! nested list of the page's `<h1>`-`<h6>` headings that carry an `id`; `$pages` becomes a nested
+//! list of every page route, titled by `page_title` and nested under ancestor routes. Repeated
+//! heading ids on one page are numbered first, so every outline entry links to its own heading.
+//! WHY: both lists depend on output the frontend never sees as a whole, so the builder fills them
+//! after every page document exists instead of authors maintaining navigation by hand. The same
+//! index feeds `sitemap.xml` and the Atom feed in `site_files`.
+//!
+//! Only the static document is scanned: headings produced by runtime fragments that were not
+//! prerendered do not appear in the outline. Placeholders inside `<script>` or `<style>` elements
+//! are left untouched so inline code is never rewritten.
+
+use crate::build_system::build::{FileKind, Module, OutputFile};
+use crate::compiler_frontend::compiler_errors::CompilerMessages;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::projects::html_project::document_shell::{escape_html_attribute, escape_html_text};
+use crate::projects::html_project::page_metadata::extract_html_page_metadata;
+use crate::projects::html_project::styles::navigation::{PAGE_LIST_PLACEHOLDER, TOC_PLACEHOLDER};
+use crate::projects::routing::{HtmlSiteConfig, PageUrlStyle, prefix_origin};
+use rustc_hash::FxHashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// One page in the project page list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HtmlIndexedPage {
+    /// Emitted HTML file path, used to mark the current page.
+    pub html_path: PathBuf,
+    /// Public URL including the configured origin.
+    pub url: String,
+    /// `page_title`, or the URL when the page declares none. Already HTML-escaped.
+    pub title: String,
+    /// `page_description`, already HTML-escaped.
+    pub description: Option<String>,
+    /// `page_date` in `YYYY-MM-DD` form.
+    pub date: Option<String>,
+    /// Route directory for directory-backed pages (`docs/intro`, empty for the homepage).
+    /// Flat single-file pages have none, so they never contain other pages.
+    pub(super) route_directory: Option<PathBuf>,
+}
+
+/// Every page route of one build, sorted so child routes follow their ancestors.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct HtmlPageIndex {
+    pages: Vec<HtmlIndexedPage>,
+}
+
+/// One heading of a page outline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HtmlHeading {
+    pub level: u8,
+    /// The `id` attribute value exactly as written in the document.
+    pub id: String,
+    /// Heading content with tags removed and whitespace collapsed, still HTML-escaped.
+    pub text: String,
+}
+
+impl HtmlPageIndex {
+    /// Builds the index from the compiled pages and their emitted HTML paths.
+    pub(crate) fn from_compiled_pages(
+        pages: &[(&Module, PathBuf)],
+        site_config: &HtmlSiteConfig,
+        string_table: &mut StringTable,
+    ) -> Result<Self, CompilerMessages> {
+        let mut indexed_pages = Vec::with_capacity(pages.len());
+
+        for (module, html_path) in pages {
+            let metadata = extract_html_page_metadata(&module.executable.hir, string_table)
+                .map_err(|diagnostic| {
+                    CompilerMessages::from_diagnostic_ref(*diagnostic, string_table)
+                })?;
+
+            let url = html_page_url(html_path, site_config);
+            let title = match metadata.title {
+                Some(title) => escape_html_text(&title),
+                None => escape_html_text(&url),
+            };
+
+            indexed_pages.push(HtmlIndexedPage {
+                html_path: html_path.to_owned(),
+                url,
+                title,
+                description: metadata.description.as_deref().map(escape_html_attribute),
+                date: metadata.date,
+                route_directory: route_directory(html_path),
+            });
+        }
+
+        Ok(Self::new(indexed_pages))
+    }
+
+    pub(crate) fn pages(&self) -> &[HtmlIndexedPage] {
+        &self.pages
+    }
+
+    pub(crate) fn new(mut pages: Vec<HtmlIndexedPage>) -> Self {
+        // Component-wise ordering keeps `docs/intro` directly after `docs`.
+        pages.sort_by(|left, right| {
+            route_sort_key(&left.html_path).cmp(&route_sort_key(&right.html_path))
+        });
+        Self { pages }
+    }
+
+    /// Numbers repeated heading ids and replaces the navigation placeholders in every emitted
+    /// HTML document.
+    pub(crate) fn fill_output_files(&self, output_files: &mut [OutputFile]) {
+        for output_file in output_files {
+            let FileKind::Html(html) = output_file.file_kind() else {
+                continue;
+            };
+            let deduplicated = deduplicate_heading_ids(html);
+            let html = deduplicated.as_deref().unwrap_or(html);
+            let Some(filled) = self
+                .fill_navigation(html, output_file.relative_output_path())
+                .or(deduplicated)
+            else {
+                continue;
+            };
+
+            *output_file = OutputFile::new(
+                output_file.relative_output_path().to_path_buf(),
+                FileKind::Html(filled),
+            );
+        }
+    }
+
+    /// Returns the document with its navigation placeholders filled, or `None` when it has none.
+    pub(crate) fn fill_navigation(&self, html: &str, html_path: &Path) -> Option<String> {
+        let markup_ranges = markup_ranges(html);
+        let contains_placeholder = |placeholder: &str| {
+            markup_ranges
+                .iter()
+                .any(|range| html[range.clone()].contains(placeholder))
+        };
+
+        let has_toc = contains_placeholder(TOC_PLACEHOLDER);
+        let has_page_list = contains_placeholder(PAGE_LIST_PLACEHOLDER);
+        if !has_toc && !has_page_list {
+            return None;
+        }
+
+        let toc = if has_toc {
+            render_heading_outline(&collect_heading_outline(html))
+        } else {
+            String::new()
+        };
+        let page_list = if has_page_list {
+            self.render_page_list(html_path)
+        } else {
+            String::new()
+        };
+
+        let mut filled = String::with_capacity(html.len() + toc.len() + page_list.len());
+        let mut copied_until = 0usize;
+        for range in markup_ranges {
+            filled.push_str(&html[copied_until..range.start]);
+            filled.push_str(
+                &html[range.clone()]
+                    .replace(TOC_PLACEHOLDER, &toc)
+                    .replace(PAGE_LIST_PLACEHOLDER, &page_list),
+            );
+            copied_until = range.end;
+        }
+        filled.push_str(&html[copied_until..]);
+
+        Some(filled)
+    }
+
+    /// Renders the page list with the page at `current_html_path` marked as current.
+    pub(crate) fn render_page_list(&self, current_html_path: &Path) -> String {
+        let mut ancestors: Vec<&Path> = Vec::new();
+        let mut items = Vec::with_capacity(self.pages.len());
+
+        for page in &self.pages {
+            let route = route_sort_key(&page.html_path);
+            while let Some(ancestor) = ancestors.last() {
+                if route.starts_with(ancestor) {
+                    break;
+                }
+                ancestors.pop();
+            }
+
+            let current = if page.html_path == current_html_path {
+                " aria-current=\"page\""
+            } else {
+                ""
+            };
+            items.push((
+                ancestors.len(),
+                format!(
+                    "<a href=\"{}\"{current}>{}</a>",
+                    escape_html_attribute(&page.url),
+                    page.title
+                ),
+            ));
+
+            if let Some(route_directory) = &page.route_directory {
+                ancestors.push(route_directory);
+            }
+        }
+
+        render_nested_list(&items)
+    }
+}
+
+/// Public URL of an emitted page, following `page_url_style` and `origin`.
+///
+/// Directory-backed pages (`docs/index.html`) link to their folder; flat single-file pages
+/// (`about.html`) link to the file.
+pub(crate) fn html_page_url(html_path: &Path, site_config: &HtmlSiteConfig) -> String {
+    let site_path = match route_directory(html_path) {
+        Some(directory) if directory.as_os_str().is_empty() => String::from("/"),
+        Some(directory) => {
+            let directory = directory.to_string_lossy().replace('\\', "/");
+            match site_config.page_url_style {
+                PageUrlStyle::NoTrailingSlash => format!("/{directory}"),
+                PageUrlStyle::TrailingSlash | PageUrlStyle::Ignore => format!("/{directory}/"),
+            }
+        }
+        None => format!("/{}", html_path.to_string_lossy().replace('\\', "/")),
+    };
+
+    prefix_origin(&site_config.origin, &site_path)
+}
+
+/// Collects the headings with an `id` attribute, in document order.
+pub(crate) fn collect_heading_outline(html: &str) -> Vec<HtmlHeading> {
+    scan_headings(html)
+        .into_iter()
+        .map(|(heading, _)| heading)
+        .collect()
+}
+
+/// Returns the document with repeated heading ids numbered, or `None` when every id is unique.
+///
+/// `$md` numbers repeated slugs within one template, but a page can hold several templates that
+/// each emit `id="intro"`. Later repeats become `intro-1`, `intro-2`, … skipping ids another
+/// heading on the page already uses, the same scheme `$md` applies inside a template.
+pub(crate) fn deduplicate_heading_ids(html: &str) -> Option<String> {
+    let headings = scan_headings(html);
+    let mut taken: FxHashSet<String> = headings
+        .iter()
+        .map(|(heading, _)| heading.id.to_owned())
+        .collect();
+    let mut seen = FxHashSet::default();
+    let mut renamed = Vec::new();
+
+    for (heading, id_range) in &headings {
+        if seen.insert(heading.id.as_str()) {
+            continue;
+        }
+
+        let mut suffix = 0usize;
+        let unique_id = loop {
+            suffix += 1;
+            let candidate = format!("{}-{suffix}", heading.id);
+            if !taken.contains(&candidate) {
+                break candidate;
+            }
+        };
+        taken.insert(unique_id.to_owned());
+        renamed.push((id_range.clone(), unique_id));
+    }
+
+    if renamed.is_empty() {
+        return None;
+    }
+
+    let mut deduplicated = String::with_capacity(html.len() + renamed.len() * 2);
+    let mut copied_until = 0usize;
+    for (id_range, unique_id) in &renamed {
+        deduplicated.push_str(&html[copied_until..id_range.start]);
+        deduplicated.push_str(unique_id);
+        copied_until = id_range.end;
+    }
+    deduplicated.push_str(&html[copied_until..]);
+
+    Some(deduplicated)
+}
+
+/// Headings with an `id` attribute in document order, each with the byte range of its id value.
+fn scan_headings(html: &str) -> Vec<(HtmlHeading, Range<usize>)> {
+    let lowered = html.to_ascii_lowercase();
+    let mut headings = Vec::new();
+
+    for range in markup_ranges(html) {
+        let mut cursor = range.start;
+        while let Some(offset) = lowered[cursor..range.end].find("<h") {
+            let tag_start = cursor + offset;
+            cursor = tag_start + 2;
+
+            let Some(level) = heading_level(&lowered[tag_start..range.end]) else {
+                continue;
+            };
+            let Some(tag_length) = lowered[tag_start..range.end].find('>') else {
+                break;
+            };
+            let content_start = tag_start + tag_length + 1;
+            let closing_tag = format!("</h{level}>");
+            let Some(content_length) = lowered[content_start..range.end].find(&closing_tag) else {
+                continue;
+            };
+            cursor = content_start + content_length + closing_tag.len();
+
+            let Some(id_range) = id_attribute(&html[tag_start..content_start]) else {
+                continue;
+            };
+            let id_range = tag_start + id_range.start..tag_start + id_range.end;
+            headings.push((
+                HtmlHeading {
+                    level,
+                    id: html[id_range.clone()].to_owned(),
+                    text: heading_text(&html[content_start..content_start + content_length]),
+                },
+                id_range,
+            ));
+        }
+    }
+
+    headings
+}
+
+/// Renders an outline as nested lists. A heading nests under the closest earlier heading with a
+/// smaller level, so skipped levels (`h2` then `h4`) only nest one step.
+pub(crate) fn render_heading_outline(headings: &[HtmlHeading]) -> String {
+    let mut open_levels: Vec<u8> = Vec::new();
+    let mut items = Vec::with_capacity(headings.len());
+
+    for heading in headings {
+        while open_levels
+            .last()
+            .is_some_and(|level| *level >= heading.level)
+        {
+            open_levels.pop();
+        }
+        items.push((
+            open_levels.len(),
+            format!("<a href=\"#{}\">{}</a>", heading.id, heading.text),
+        ));
+        open_levels.push(heading.level);
+    }
+
+    render_nested_list(&items)
+}
+
+/// Renders `(depth, item_html)` pairs as nested `<ul>` lists. Each depth is at most one more
+/// than the previous item's depth.
+fn render_nested_list(items: &[(usize, String)]) -> String {
+    if items.is_empty() {
+        return String::new();
+    }
+
+    let mut html = String::from("<ul>");
+    let mut current_depth = 0usize;
+    for (index, (depth, item_html)) in items.iter().enumerate() {
+        if index > 0 {
+            if *depth > current_depth {
+                html.push_str("<ul>");
+            } else {
+                html.push_str("</li>");
+                for _ in *depth..current_depth {
+                    html.push_str("</ul></li>");
+                }
+            }
+        }
+        html.push_str("<li>");
+        html.push_str(item_html);
+        current_depth = *depth;
+    }
+
+    html.push_str("</li>");
+    for _ in 0..current_depth {
+        html.push_str("</ul></li>");
+    }
+    html.push_str("</ul>");
+    html
+}
+
+/// Byte ranges of `html` outside `<script>` and `<style>` element contents.
+fn markup_ranges(html: &str) -> Vec<Range<usize>> {
+    let lowered = html.to_ascii_lowercase();
+    let mut ranges = Vec::new();
+    let mut cursor = 0usize;
+
+    loop {
+        let next_raw_text = ["<script", "<style"]
+            .into_iter()
+            .filter_map(|tag| {
+                lowered[cursor..]
+                    .find(tag)
+                    .map(|offset| (cursor + offset, &tag[1..]))
+            })
+            .min_by_key(|(start, _)| *start);
+
+        let Some((start, tag_name)) = next_raw_text else {
+            ranges.push(cursor..html.len());
+            return ranges;
+        };
+
+        let Some(open_tag_length) = lowered[start..].find('>') else {
+            ranges.push(cursor..html.len());
+            return ranges;
+        };
+        let content_start = start + open_tag_length + 1;
+        ranges.push(cursor..content_start);
+
+        let closing_tag = format!("</{tag_name}");
+        match lowered[content_start..].find(&closing_tag) {
+            Some(content_length) => cursor = content_start + content_length,
+            None => return ranges,
+        }
+    }
+}
+
+/// The level of an `<hN` opening tag at the start of `lowered_tag`, if it is one.
+fn heading_level(lowered_tag: &str) -> Option<u8> {
+    let bytes = lowered_tag.as_bytes();
+    let level = match bytes.get(2)? {
+        digit @ b'1'..=b'6' => digit - b'0',
+        _ => return None,
+    };
+
+    match bytes.get(3)? {
+        b'>' | b'/' => Some(level),
+        byte if byte.is_ascii_whitespace() => Some(level),
+        _ => None,
+    }
+}
+
+/// The byte range of the double-quoted `id` attribute value of an opening tag.
+fn id_attribute(opening_tag: &str) -> Option<Range<usize>> {
+    let mut search_from = 0usize;
+    while let Some(offset) = opening_tag[search_from..].find("id=\"") {
+        let attribute_start = search_from + offset;
+        search_from = attribute_start + 4;
+
+        let preceded_by_whitespace = opening_tag[..attribute_start]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_whitespace);
+        if !preceded_by_whitespace {
+            continue;
+        }
+
+        let value_length = opening_tag[search_from..].find('"')?;
+        return (value_length > 0).then_some(search_from..search_from + value_length);
+    }
+
+    None
+}
+
+fn heading_text(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    let mut in_tag = false;
+    for ch in content.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Route directory of a directory-backed page, `None` for a flat `<name>.html` page.
+fn route_directory(html_path: &Path) -> Option<PathBuf> {
+    (html_path.file_name()? == "index.html").then(|| {
+        html_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    })
+}
+
+fn route_sort_key(html_path: &Path) -> PathBuf {
+    route_directory(html_path).unwrap_or_else(|| html_path.to_path_buf())
+}
+
+#[cfg(test)]
+#[path = "tests/page_index_tests.rs"]
+mod tests;
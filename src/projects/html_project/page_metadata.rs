@@ -16,6 +16,7 @@ const PAGE_LANG: &str = "page_lang";
 const PAGE_FAVICON: &str = "page_favicon";
 const PAGE_BODY_STYLE: &str = "page_body_style";
 const PAGE_HEAD: &str = "page_head";
+const PAGE_DATE: &str = "page_date";
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct HtmlPageMetadata {
@@ -24,6 +25,8 @@ pub(crate) struct HtmlPageMetadata {
     pub lang: Option<String>,
     pub favicon: Option<String>,
     pub body_style: Option<String>,
+    /// Publication date in `YYYY-MM-DD` form. Pages with a date become Atom feed entries.
+    pub date: Option<String>,
 
     // This is used for <style> and to extend any html inside the <head> tag.
     pub extra_head_html: Option<String>,
@@ -76,9 +79,18 @@ pub(crate) fn extract_html_page_metadata(
             PAGE_FAVICON => &mut metadata.favicon,
             PAGE_BODY_STYLE => &mut metadata.body_style,
             PAGE_HEAD => &mut metadata.extra_head_html,
+            PAGE_DATE => &mut metadata.date,
             _ => continue,
         };
 
+        if reserved_name == PAGE_DATE && !is_iso_calendar_date(&value) {
+            return Err(Box::new(CompilerDiagnostic::invalid_page_metadata(
+                key_id,
+                InvalidPageMetadataReason::InvalidDate,
+                error_location.clone(),
+            )));
+        }
+
         if target_slot.is_some() {
             return Err(Box::new(CompilerDiagnostic::invalid_page_metadata(
                 key_id,
@@ -111,10 +123,34 @@ fn reserved_metadata_name<'a>(
 fn is_reserved_page_key(name: &str) -> bool {
     matches!(
         name,
-        PAGE_TITLE | PAGE_DESCRIPTION | PAGE_LANG | PAGE_FAVICON | PAGE_BODY_STYLE | PAGE_HEAD
+        PAGE_TITLE
+            | PAGE_DESCRIPTION
+            | PAGE_LANG
+            | PAGE_FAVICON
+            | PAGE_BODY_STYLE
+            | PAGE_HEAD
+            | PAGE_DATE
     )
 }
 
+/// Whether `value` is a `YYYY-MM-DD` date with a month in 1-12 and a day in 1-31.
+///
+/// Feed timestamps are written as `<date>T00:00:00Z`, so only the shape and ranges matter.
+fn is_iso_calendar_date(value: &str) -> bool {
+    let shape_matches = value.len() == 10
+        && value.bytes().enumerate().all(|(index, byte)| match index {
+            4 | 7 => byte == b'-',
+            _ => byte.is_ascii_digit(),
+        });
+    if !shape_matches {
+        return false;
+    }
+
+    let month = value[5..7].parse::<u32>().unwrap_or_default();
+    let day = value[8..10].parse::<u32>().unwrap_or_default();
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
 #[cfg(test)]
 #[path = "tests/page_metadata_tests.rs"]
 mod tests;
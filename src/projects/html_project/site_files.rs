@@ -0,0 +1,186 @@
+//! Site-level crawler and syndication files for HTML projects.
+//!
+//! WHAT: renders `sitemap.xml`, `robots.txt` and an Atom `feed.xml` from the build's page index
+//! when the matching `html_*` config keys are enabled.
+//! WHY: the builder already knows every route, the site origin and each page's metadata, so
+//! publishing a site should not need external scripts to rebuild that list.
+//!
+//! The files are emitted as byte artifacts so the output manifest tracks them and stale cleanup
+//! removes them once their config key is turned off. They are written to the output root, which
+//! is served at `origin`; crawlers only read `robots.txt` at the host root, so projects served
+//! under a path prefix must copy it there themselves.
+
+use crate::build_system::build::{FileKind, OutputFile};
+use crate::projects::html_project::document_config::HtmlDocumentConfig;
+use crate::projects::html_project::page_index::{HtmlIndexedPage, HtmlPageIndex};
+use crate::projects::routing::prefix_origin;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+pub(crate) const SITEMAP_PATH: &str = "sitemap.xml";
+pub(crate) const ROBOTS_TXT_PATH: &str = "robots.txt";
+pub(crate) const FEED_PATH: &str = "feed.xml";
+
+/// Renders every enabled site file.
+///
+/// The feed is skipped when no page declares `page_date`, because an Atom feed needs an
+/// `updated` time and the build has no clock it may read deterministically.
+pub(crate) fn render_site_files(
+    page_index: &HtmlPageIndex,
+    document_config: &HtmlDocumentConfig,
+    origin: &str,
+    project_name: &str,
+) -> Vec<OutputFile> {
+    let mut output_files = Vec::new();
+    let site_url = document_config.site_url.as_deref();
+
+    if let (true, Some(site_url)) = (document_config.sitemap, site_url) {
+        output_files.push(site_file(
+            SITEMAP_PATH,
+            render_sitemap(page_index.pages(), site_url),
+        ));
+    }
+
+    if document_config.robots_txt {
+        let sitemap_url = site_url
+            .filter(|_| document_config.sitemap)
+            .map(|site_url| absolute_url(site_url, &prefix_origin(origin, "/sitemap.xml")));
+        output_files.push(site_file(
+            ROBOTS_TXT_PATH,
+            render_robots_txt(sitemap_url.as_deref()),
+        ));
+    }
+
+    if let (true, Some(site_url)) = (document_config.feed, site_url)
+        && let Some(feed) = render_atom_feed(page_index.pages(), site_url, origin, project_name)
+    {
+        output_files.push(site_file(FEED_PATH, feed));
+    }
+
+    output_files
+}
+
+pub(crate) fn render_sitemap(pages: &[HtmlIndexedPage], site_url: &str) -> String {
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+
+    for page in pages {
+        let _ = write!(
+            sitemap,
+            "  <url>\n    <loc>{}</loc>\n",
+            escape_xml(&absolute_url(site_url, &page.url))
+        );
+        if let Some(date) = &page.date {
+            let _ = writeln!(sitemap, "    <lastmod>{date}</lastmod>");
+        }
+        sitemap.push_str("  </url>\n");
+    }
+
+    sitemap.push_str("</urlset>\n");
+    sitemap
+}
+
+pub(crate) fn render_robots_txt(sitemap_url: Option<&str>) -> String {
+    let mut robots = String::from("User-agent: *\nDisallow:\n");
+    if let Some(sitemap_url) = sitemap_url {
+        let _ = write!(robots, "\nSitemap: {sitemap_url}\n");
+    }
+    robots
+}
+
+/// Renders an Atom feed of the dated pages, newest first. Returns `None` when no page is dated.
+///
+/// The feed title and author are the project name, or the site host when the project has none.
+pub(crate) fn render_atom_feed(
+    pages: &[HtmlIndexedPage],
+    site_url: &str,
+    origin: &str,
+    project_name: &str,
+) -> Option<String> {
+    let mut dated_pages: Vec<(&HtmlIndexedPage, &str)> = pages
+        .iter()
+        .filter_map(|page| Some((page, page.date.as_deref()?)))
+        .collect();
+    // Newest first; the page index order breaks ties so the feed stays deterministic.
+    dated_pages.sort_by(|(_, left), (_, right)| right.cmp(left));
+    let (_, latest_date) = dated_pages.first()?;
+
+    let site_root = absolute_url(site_url, &prefix_origin(origin, "/"));
+    let feed_url = absolute_url(site_url, &prefix_origin(origin, "/feed.xml"));
+    // Atom requires a non-empty feed title and author name; unnamed projects use their host.
+    let project_name = match project_name.trim() {
+        "" => site_url_host(site_url),
+        name => name,
+    };
+    let project_name = escape_xml(project_name);
+
+    let mut feed = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n",
+    );
+    let _ = write!(
+        feed,
+        "  <title>{project_name}</title>\n  \
+         <id>{site_root}</id>\n  \
+         <link href=\"{site_root}\"/>\n  \
+         <link rel=\"self\" href=\"{feed_url}\"/>\n  \
+         <updated>{}</updated>\n  \
+         <author><name>{project_name}</name></author>\n",
+        atom_timestamp(latest_date),
+        site_root = escape_xml(&site_root),
+        feed_url = escape_xml(&feed_url),
+    );
+
+    for (page, date) in dated_pages {
+        let page_url = escape_xml(&absolute_url(site_url, &page.url));
+        let _ = write!(
+            feed,
+            "  <entry>\n    \
+             <title>{}</title>\n    \
+             <id>{page_url}</id>\n    \
+             <link href=\"{page_url}\"/>\n    \
+             <updated>{}</updated>\n",
+            page.title,
+            atom_timestamp(date),
+        );
+        if let Some(description) = &page.description {
+            let _ = writeln!(feed, "    <summary>{description}</summary>");
+        }
+        feed.push_str("  </entry>\n");
+    }
+
+    feed.push_str("</feed>\n");
+    Some(feed)
+}
+
+fn site_file(path: &str, content: String) -> OutputFile {
+    OutputFile::new(PathBuf::from(path), FileKind::Bytes(content.into_bytes()))
+}
+
+/// Joins the configured site URL and a site path that already carries `origin`.
+fn absolute_url(site_url: &str, site_path: &str) -> String {
+    format!("{site_url}{site_path}")
+}
+
+fn site_url_host(site_url: &str) -> &str {
+    site_url
+        .split_once("://")
+        .map_or(site_url, |(_, host)| host)
+}
+
+fn atom_timestamp(date: &str) -> String {
+    format!("{date}T00:00:00Z")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+#[path = "tests/site_files_tests.rs"]
+mod tests;
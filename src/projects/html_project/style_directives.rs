@@ -16,6 +16,9 @@ use crate::projects::html_project::styles::code::code_formatter_factory;
 use crate::projects::html_project::styles::css::css_formatter_factory;
 use crate::projects::html_project::styles::escape_html::escape_html_formatter_factory;
 use crate::projects::html_project::styles::html::html_formatter_factory;
+use crate::projects::html_project::styles::navigation::{
+    pages_formatter_factory, toc_formatter_factory,
+};
 
 /// Full project-owned style-directive set for the HTML project builder.
 pub(crate) fn html_project_style_directives() -> Vec<StyleDirectiveSpec> {
@@ -79,5 +82,31 @@ pub(crate) fn html_project_style_directives() -> Vec<StyleDirectiveSpec> {
                 Some(code_formatter_factory),
             ),
         ),
+        StyleDirectiveSpec::handler(
+            "toc",
+            TemplateBodyMode::Normal,
+            TemplateHeadCompatibility::blocks_same(TemplateHeadTag::FORMATTER_DIRECTIVE),
+            StyleDirectiveHandlerSpec::new(
+                None,
+                StyleDirectiveEffects {
+                    style_id: Some("toc"),
+                    ..StyleDirectiveEffects::default()
+                },
+                Some(toc_formatter_factory),
+            ),
+        ),
+        StyleDirectiveSpec::handler(
+            "pages",
+            TemplateBodyMode::Normal,
+            TemplateHeadCompatibility::blocks_same(TemplateHeadTag::FORMATTER_DIRECTIVE),
+            StyleDirectiveHandlerSpec::new(
+                None,
+                StyleDirectiveEffects {
+                    style_id: Some("pages"),
+                    ..StyleDirectiveEffects::default()
+                },
+                Some(pages_formatter_factory),
+            ),
+        ),
     ]
 }
@@ -11,12 +11,15 @@ use crate::compiler_frontend::ast::templates::template::{
     Formatter, FormatterResult, TemplateFormatter,
 };
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
-use crate::compiler_frontend::style_directives::StyleDirectiveArgumentValue;
+use crate::compiler_frontend::style_directives::{
+    StyleDirectiveArgumentValue, StyleDirectiveRegistry,
+};
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::compiler_frontend::utilities::basic::CharacterParsing;
 use std::sync::Arc;
 pub(crate) fn code_formatter_factory(
     argument: Option<&StyleDirectiveArgumentValue>,
+    _style_directives: &StyleDirectiveRegistry,
 ) -> Result<Formatter, String> {
     let language = match argument {
         Some(StyleDirectiveArgumentValue::String(language_name)) => {
@@ -103,8 +106,10 @@ impl TemplateFormatter for CodeTemplateFormatter {
             match piece {
                 FormatterInputPiece::Text(text_piece) => {
                     let text = string_table.resolve(text_piece.text);
+                    // Plain text is not highlighted, but it is still shown verbatim, so any
+                    // markup in it must be escaped rather than rendered.
                     let highlighted = match self.language {
-                        CodeLanguage::Text => text.to_owned(),
+                        CodeLanguage::Text => escape_html(text),
                         _ => highlight_code_html(text, self.language),
                     };
 
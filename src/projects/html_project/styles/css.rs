@@ -15,7 +15,9 @@ use crate::compiler_frontend::ast::templates::template::{
 };
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
 
-use crate::compiler_frontend::style_directives::StyleDirectiveArgumentValue;
+use crate::compiler_frontend::style_directives::{
+    StyleDirectiveArgumentValue, StyleDirectiveRegistry,
+};
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::projects::html_project::styles::validation::{PassThroughFormatterInput, SourceWarning};
 use std::sync::Arc;
@@ -41,6 +43,7 @@ pub(crate) fn css_validation_formatter(mode: CssFormatterMode) -> Formatter {
 
 pub(crate) fn css_formatter_factory(
     argument: Option<&StyleDirectiveArgumentValue>,
+    _style_directives: &StyleDirectiveRegistry,
 ) -> Result<Formatter, String> {
     let mode = match argument {
         None => CssFormatterMode::Block,
@@ -15,7 +15,9 @@ use crate::compiler_frontend::ast::templates::template::{
     Formatter, FormatterResult, TemplateFormatter,
 };
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
-use crate::compiler_frontend::style_directives::StyleDirectiveArgumentValue;
+use crate::compiler_frontend::style_directives::{
+    StyleDirectiveArgumentValue, StyleDirectiveRegistry,
+};
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use std::sync::Arc;
 
@@ -72,6 +74,7 @@ pub(crate) fn escape_html_formatter() -> Formatter {
 
 pub(crate) fn escape_html_formatter_factory(
     argument: Option<&StyleDirectiveArgumentValue>,
+    _style_directives: &StyleDirectiveRegistry,
 ) -> Result<Formatter, String> {
     if argument.is_some() {
         return Err("'$escape_html' does not accept arguments.".to_string());
@@ -14,7 +14,9 @@ use crate::compiler_frontend::ast::templates::template::{
 };
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
 
-use crate::compiler_frontend::style_directives::StyleDirectiveArgumentValue;
+use crate::compiler_frontend::style_directives::{
+    StyleDirectiveArgumentValue, StyleDirectiveRegistry,
+};
 use crate::compiler_frontend::symbols::string_interning::StringTable;
 use crate::projects::html_project::styles::validation::{PassThroughFormatterInput, SourceWarning};
 use std::sync::Arc;
@@ -32,6 +34,7 @@ pub(crate) fn html_validation_formatter() -> Formatter {
 
 pub(crate) fn html_formatter_factory(
     argument: Option<&StyleDirectiveArgumentValue>,
+    _style_directives: &StyleDirectiveRegistry,
 ) -> Result<Formatter, String> {
     if argument.is_some() {
         return Err("'$html' does not accept arguments.".to_string());
@@ -0,0 +1,118 @@
+//! HTML-project-owned `$toc` and `$pages` navigation formatters.
+//!
+//! WHAT:
+//! - Wrap the template body in a `<nav>` element followed by a builder placeholder comment.
+//! - The HTML project builder replaces the placeholder once every page has been rendered:
+//!   `$toc` with the page's heading outline and `$pages` with the project route list.
+//!
+//! WHY:
+//! - Headings and routes are only known after all modules are compiled, so the formatter can only
+//!   mark where the generated list goes. The body labels the list (for example `On this page`).
+
+use crate::compiler_frontend::ast::templates::formatter_contract::{
+    FormatterInput, FormatterInputPiece, FormatterOutput, FormatterOutputPiece,
+};
+use crate::compiler_frontend::ast::templates::styles::whitespace::TemplateWhitespacePassProfile;
+use crate::compiler_frontend::ast::templates::template::{
+    Formatter, FormatterResult, TemplateFormatter,
+};
+use crate::compiler_frontend::compiler_errors::CompilerMessages;
+use crate::compiler_frontend::style_directives::{
+    StyleDirectiveArgumentValue, StyleDirectiveRegistry,
+};
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use std::sync::Arc;
+
+/// Placeholder replaced with the page heading outline.
+pub(crate) const TOC_PLACEHOLDER: &str = "<!--bst:toc-->";
+/// Placeholder replaced with the project page list.
+pub(crate) const PAGE_LIST_PLACEHOLDER: &str = "<!--bst:pages-->";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NavigationKind {
+    Toc,
+    Pages,
+}
+
+impl NavigationKind {
+    fn class_name(self) -> &'static str {
+        match self {
+            NavigationKind::Toc => "bst-toc",
+            NavigationKind::Pages => "bst-pages",
+        }
+    }
+
+    fn placeholder(self) -> &'static str {
+        match self {
+            NavigationKind::Toc => TOC_PLACEHOLDER,
+            NavigationKind::Pages => PAGE_LIST_PLACEHOLDER,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NavigationTemplateFormatter {
+    kind: NavigationKind,
+}
+
+impl TemplateFormatter for NavigationTemplateFormatter {
+    fn format(
+        &self,
+        input: FormatterInput,
+        string_table: &mut StringTable,
+    ) -> Result<FormatterResult, CompilerMessages> {
+        let mut pieces = Vec::with_capacity(input.pieces.len() + 2);
+        pieces.push(FormatterOutputPiece::Text(format!(
+            "<nav class=\"{}\">",
+            self.kind.class_name()
+        )));
+
+        // The label is authored HTML, so it passes through exactly like a `$html` body.
+        pieces.extend(input.pieces.into_iter().map(|piece| match piece {
+            FormatterInputPiece::Text(text_piece) => {
+                FormatterOutputPiece::Text(string_table.resolve(text_piece.text).to_owned())
+            }
+            FormatterInputPiece::Opaque(id) => FormatterOutputPiece::Opaque(id),
+        }));
+
+        pieces.push(FormatterOutputPiece::Text(format!(
+            "{}</nav>",
+            self.kind.placeholder()
+        )));
+
+        Ok(FormatterResult {
+            output: FormatterOutput { pieces },
+            warnings: Vec::new(),
+        })
+    }
+}
+
+fn navigation_formatter(kind: NavigationKind) -> Formatter {
+    Formatter {
+        pre_format_whitespace_passes: vec![TemplateWhitespacePassProfile::default_template_body()],
+        formatter: Arc::new(NavigationTemplateFormatter { kind }),
+        post_format_whitespace_passes: Vec::new(),
+    }
+}
+
+pub(crate) fn toc_formatter_factory(
+    argument: Option<&StyleDirectiveArgumentValue>,
+    _style_directives: &StyleDirectiveRegistry,
+) -> Result<Formatter, String> {
+    if argument.is_some() {
+        return Err("'$toc' does not accept arguments.".to_string());
+    }
+
+    Ok(navigation_formatter(NavigationKind::Toc))
+}
+
+pub(crate) fn pages_formatter_factory(
+    argument: Option<&StyleDirectiveArgumentValue>,
+    _style_directives: &StyleDirectiveRegistry,
+) -> Result<Formatter, String> {
+    if argument.is_some() {
+        return Err("'$pages' does not accept arguments.".to_string());
+    }
+
+    Ok(navigation_formatter(NavigationKind::Pages))
+}
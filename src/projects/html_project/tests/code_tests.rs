@@ -100,3 +100,28 @@ fn code_formatter_wrapper_preserves_newlines_after_dedent() {
     assert!(content.ends_with("</code>"));
     assert!(content.contains("x\ny"));
 }
+
+#[test]
+fn text_code_blocks_escape_markup_instead_of_rendering_it() {
+    let mut string_table = StringTable::new();
+    let input = FormatterInput {
+        pieces: vec![FormatterInputPiece::Text(FormatterTextPiece {
+            text: string_table.intern("golden/<backend>/ & <p>"),
+            location: SourceLocation::default(),
+        })],
+    };
+
+    let output = code_formatter(CodeLanguage::Text)
+        .formatter
+        .format(input, &mut string_table)
+        .expect("text code formatting should succeed")
+        .output;
+
+    let [FormatterOutputPiece::Text(rendered)] = output.pieces.as_slice() else {
+        panic!("text code output should be one text piece");
+    };
+    assert_eq!(
+        rendered,
+        "<code class='codeblock'>golden/&lt;backend&gt;/ &amp; &lt;p&gt;</code>"
+    );
+}
@@ -89,6 +89,75 @@ fn parser_rejects_invalid_bool_values() {
     ));
 }
 
+#[test]
+fn parser_accepts_site_files_with_a_site_url() {
+    let mut config = project_config();
+    set_setting(&mut config, "html_site_url", "https://example.com");
+    set_setting(&mut config, "html_sitemap", "true");
+    set_setting(&mut config, "html_robots_txt", "true");
+    set_setting(&mut config, "html_feed", "true");
+
+    let mut string_table = StringTable::new();
+    let parsed = parse_html_document_config(&config, &mut string_table)
+        .expect("site file settings should parse");
+    assert_eq!(parsed.site_url.as_deref(), Some("https://example.com"));
+    assert!(parsed.sitemap && parsed.robots_txt && parsed.feed);
+}
+
+#[test]
+fn parser_rejects_site_urls_that_are_not_bare_origins() {
+    for site_url in [
+        "example.com",
+        "https://",
+        "https://example.com/",
+        "https://example.com/blog",
+        "https://example.com?x=1",
+    ] {
+        let mut config = project_config();
+        set_setting(&mut config, "html_site_url", site_url);
+
+        let mut string_table = StringTable::new();
+        let error = parse_html_document_config(&config, &mut string_table)
+            .expect_err("malformed site URL should fail");
+        let diagnostic = error.diagnostic().expect("config error should be typed");
+        assert!(
+            matches!(
+                diagnostic.payload,
+                DiagnosticPayload::InvalidConfig {
+                    reason: InvalidConfigReason::InvalidProjectSettingValue { .. },
+                    ..
+                }
+            ),
+            "{site_url} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn parser_requires_a_site_url_for_sitemap_and_feed() {
+    for key in ["html_sitemap", "html_feed"] {
+        let mut config = project_config();
+        set_setting(&mut config, key, "true");
+
+        let mut string_table = StringTable::new();
+        let error = parse_html_document_config(&config, &mut string_table)
+            .expect_err("absolute entries need a site URL");
+        let diagnostic = error.diagnostic().expect("config error should be typed");
+        assert!(matches!(
+            diagnostic.payload,
+            DiagnosticPayload::InvalidConfig {
+                reason: InvalidConfigReason::InvalidProjectSettingValue { .. },
+                ..
+            }
+        ));
+    }
+
+    let mut config = project_config();
+    set_setting(&mut config, "html_robots_txt", "true");
+    let mut string_table = StringTable::new();
+    assert!(parse_html_document_config(&config, &mut string_table).is_ok());
+}
+
 #[test]
 fn parser_uses_precise_location_from_setting_locations() {
     let mut config = project_config();
@@ -4,7 +4,7 @@ use super::*;
 use crate::backends::js::test_symbol_helpers::expected_dev_function_name;
 use crate::build_system::build::ModuleExternalImport;
 use crate::build_system::build::ResolvedConstFragment;
-use crate::build_system::build::{FileKind, ModuleRootActivity, Project};
+use crate::build_system::build::{FileKind, ModuleRootActivity, OutputFile, Project};
 use crate::builder_surface::external_import_providers::provider::RuntimeAssetIdentity;
 use crate::compiler_frontend::Flag;
 use crate::compiler_frontend::compiler_errors::CompilerMessages;
@@ -21,7 +21,7 @@ use crate::projects::html_project::tests::test_support::{
 };
 use crate::projects::settings::Config;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn build_with_test_modules(
     builder: &HtmlProjectBuilder,
@@ -944,3 +944,234 @@ fn build_backend_rejects_tracked_asset_output_that_matches_generated_html() {
 
     fs::remove_dir_all(&root).expect("should remove temp dir");
 }
+
+#[test]
+fn dev_server_builds_embed_a_source_line_table_for_the_page_bundle() {
+    let root = temp_dir("dev_server_source_lines");
+    fs::create_dir_all(&root).expect("should create temp root");
+    fs::write(root.join("config.bst"), "project #= \"html\"\n").expect("should write config");
+    fs::write(
+        root.join("#page.bst"),
+        "double |n Int| -> Int:\n    doubled = n * 2\n    return doubled\n;\n\nvalue = double(21)\n[: [value]]\n",
+    )
+    .expect("should write page");
+
+    let build_page = |flags: &[Flag]| {
+        let builder =
+            crate::build_system::build::ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+        let result = crate::build_system::build::build_project(
+            &builder,
+            root.to_str().expect("temp dir should be UTF-8"),
+            flags,
+        )
+        .expect("build should succeed");
+        expect_html_output(&result.project.output_files, "index.html").to_owned()
+    };
+
+    let html = build_page(&[Flag::DevServer]);
+    let table_start = html
+        .find("id=\"bst-source-lines\">")
+        .expect("dev-server page should embed the source line table")
+        + "id=\"bst-source-lines\">".len();
+    let table_end = table_start
+        + html[table_start..]
+            .find("</script>")
+            .expect("table should close");
+    let table: serde_json::Value =
+        serde_json::from_str(&html[table_start..table_end]).expect("table should be JSON");
+    let offset = table["offset"]
+        .as_u64()
+        .expect("table should have an offset") as usize;
+
+    // Generated line `n` of the bundle sits on document line `offset + n`.
+    let multiply_entry = table["lines"]
+        .as_array()
+        .expect("table should list lines")
+        .iter()
+        .find(|entry| entry[1] == "#page.bst" && entry[2] == 2)
+        .expect("the multiplication on line 2 should be mapped");
+    let document_line = offset + multiply_entry[0].as_u64().expect("generated line") as usize;
+    assert!(
+        html.lines()
+            .nth(document_line - 1)
+            .expect("mapped line should exist")
+            .contains("__bs_int_mul")
+    );
+
+    assert!(!build_page(&[]).contains("bst-source-lines"));
+
+    fs::remove_dir_all(&root).expect("should remove temp root");
+}
+
+#[test]
+fn dev_builds_write_a_source_map_next_to_each_js_page() {
+    let root = temp_dir("html_source_maps");
+    fs::create_dir_all(root.join("docs")).expect("should create docs directory");
+    fs::write(root.join("config.bst"), "project #= \"html\"\n").expect("should write config");
+    fs::write(root.join("#page.bst"), "[: Home]\n").expect("should write home page");
+    let page_source =
+        "double |n Int| -> Int:\n    return n * 2\n;\n\nvalue = double(21)\n[: [value]]\n";
+    fs::write(root.join("docs/#page.bst"), page_source).expect("should write page");
+
+    let build = |flags: &[Flag]| {
+        let builder =
+            crate::build_system::build::ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+        crate::build_system::build::build_project(
+            &builder,
+            root.to_str().expect("temp dir should be UTF-8"),
+            flags,
+        )
+        .expect("build should succeed")
+        .project
+        .output_files
+    };
+    let source_map = |output_files: &[OutputFile]| {
+        output_files.iter().find_map(|file| match file.file_kind() {
+            FileKind::Bytes(bytes)
+                if file.relative_output_path() == Path::new("docs/index.js.map") =>
+            {
+                Some(
+                    serde_json::from_slice::<serde_json::Value>(bytes).expect("map should be JSON"),
+                )
+            }
+            _ => None,
+        })
+    };
+
+    let output_files = build(&[]);
+    let map = source_map(&output_files).expect("dev builds should write a source map");
+    assert_eq!(map["version"], 3);
+    assert_eq!(map["sources"], serde_json::json!(["../docs/%23page.bst"]));
+    assert_eq!(map["sourcesContent"], serde_json::json!([page_source]));
+    assert!(!map["mappings"].as_str().unwrap_or_default().is_empty());
+    assert!(
+        expect_html_output(&output_files, "docs/index.html")
+            .contains("//# sourceMappingURL=index.js.map")
+    );
+
+    let release_files = build(&[Flag::Release]);
+    assert!(source_map(&release_files).is_none());
+    assert!(!expect_html_output(&release_files, "docs/index.html").contains("sourceMappingURL"));
+
+    fs::write(
+        root.join("config.bst"),
+        "project #= \"html\"\nhtml_release_source_maps #= true\n",
+    )
+    .expect("should rewrite config");
+    assert!(source_map(&build(&[Flag::Release])).is_some());
+
+    fs::remove_dir_all(&root).expect("should remove temp root");
+}
+
+#[test]
+fn prerender_bakes_runtime_fragments_and_skips_pages_that_read_random() {
+    let root = temp_dir("html_prerender");
+    fs::create_dir_all(root.join("dice")).expect("should create dice directory");
+    fs::write(
+        root.join("config.bst"),
+        "project #= \"html\"\nhtml_prerender #= true\n",
+    )
+    .expect("should write config");
+    fs::write(
+        root.join("#page.bst"),
+        "value = 21 * 2\n[:<p>[value]</p>]\n",
+    )
+    .expect("should write home page");
+    fs::write(
+        root.join("dice/#page.bst"),
+        "import @core/random {random_int}\n\nroll = random_int(1, 6)\n[:<p>[roll]</p>]\n",
+    )
+    .expect("should write dice page");
+
+    let builder =
+        crate::build_system::build::ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+    let result = crate::build_system::build::build_project(
+        &builder,
+        root.to_str().expect("temp dir should be UTF-8"),
+        &[],
+    )
+    .expect("build should succeed");
+    fs::remove_dir_all(&root).expect("should remove temp root");
+
+    let output_files = &result.project.output_files;
+    let home = expect_html_output(output_files, "index.html");
+    assert!(
+        home.contains("<div id=\"bst-slot-0\"><p>42</p></div>"),
+        "{home}"
+    );
+    assert!(
+        !home.contains("insertAdjacentHTML(\"beforeend\", bst_frags[i]"),
+        "prerendered plain fragments must not be inserted again"
+    );
+
+    let dice = expect_html_output(output_files, "dice/index.html");
+    assert!(dice.contains("<div id=\"bst-slot-0\"></div>"), "{dice}");
+
+    let excluded = result
+        .warnings
+        .iter()
+        .filter_map(|warning| match &warning.payload {
+            DiagnosticPayload::PrerenderExcludedPage { function_path } => {
+                Some(result.string_table.resolve(*function_path))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(excluded, vec!["random.random_int"]);
+}
+
+#[test]
+fn site_files_are_emitted_from_page_metadata_when_enabled() {
+    let root = temp_dir("html_site_files");
+    fs::create_dir_all(root.join("posts")).expect("should create posts directory");
+    fs::write(
+        root.join("config.bst"),
+        "project #= \"html\"\n\
+         html_site_url #= \"https://example.com\"\n\
+         html_sitemap #= true\n\
+         html_robots_txt #= true\n\
+         html_feed #= true\n",
+    )
+    .expect("should write config");
+    fs::write(root.join("#page.bst"), "[: Home]\n").expect("should write home page");
+    fs::write(
+        root.join("posts/#page.bst"),
+        "page_title #= \"First post\"\n\
+         page_description #= \"Notes & news\"\n\
+         page_date #= \"2026-01-31\"\n\
+         [: Post]\n",
+    )
+    .expect("should write post page");
+
+    let builder =
+        crate::build_system::build::ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+    let output_files = crate::build_system::build::build_project(
+        &builder,
+        root.to_str().expect("temp dir should be UTF-8"),
+        &[],
+    )
+    .expect("build should succeed")
+    .project
+    .output_files;
+
+    let sitemap = String::from_utf8_lossy(expect_bytes_output(&output_files, "sitemap.xml"));
+    assert!(sitemap.contains("<loc>https://example.com/</loc>"));
+    assert!(
+        sitemap
+            .contains("<loc>https://example.com/posts/</loc>\n    <lastmod>2026-01-31</lastmod>")
+    );
+
+    let robots = String::from_utf8_lossy(expect_bytes_output(&output_files, "robots.txt"));
+    assert!(robots.ends_with("Sitemap: https://example.com/sitemap.xml\n"));
+
+    let feed = String::from_utf8_lossy(expect_bytes_output(&output_files, "feed.xml"));
+    assert!(feed.contains("<title>First post</title>"));
+    assert!(feed.contains("<summary>Notes &amp; news</summary>"));
+    assert_eq!(
+        feed.matches("<entry>").count(),
+        1,
+        "undated pages stay out of the feed"
+    );
+
+    fs::remove_dir_all(&root).expect("should remove temp root");
+}
@@ -7,7 +7,7 @@ use crate::projects::html_project::tests::test_support::{
     create_test_hir_module, create_test_module,
 };
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[test]
 fn bootstrap_script_calls_start_once_and_hydrates_slots() {
@@ -20,6 +20,7 @@ fn bootstrap_script_calls_start_once_and_hydrates_slots() {
         &slot_ids,
         false,
         false,
+        false,
     );
 
     assert!(
@@ -47,7 +48,7 @@ fn bootstrap_script_calls_start_once_and_hydrates_slots() {
 
 #[test]
 fn render_entry_fragments_preserves_runtime_slot_order() {
-    let (body_html, slot_ids) = render_entry_fragments(&[], 2);
+    let (body_html, slot_ids) = render_entry_fragments(&[], 2, None);
 
     let slot0_pos = body_html
         .find("bst-slot-0")
@@ -88,6 +89,7 @@ fn no_runtime_fragments_still_emits_start_call() {
             uses_reactive_runtime_fragments: false,
             import_map_html: None,
             use_module_script: false,
+            prerendered_fragments: None,
         },
     )
     .expect("render_html_document should succeed");
@@ -134,6 +136,7 @@ fn inline_js_bundle_with_closing_script_tag_is_escaped_in_html() {
             uses_reactive_runtime_fragments: false,
             import_map_html: None,
             use_module_script: false,
+            prerendered_fragments: None,
         },
     )
     .expect("render_html_document should succeed");
@@ -159,6 +162,7 @@ fn bootstrap_uses_mount_helper_for_reactive_runtime_fragments() {
         &slot_ids,
         false,
         true,
+        false,
     );
 
     assert!(
@@ -181,6 +185,7 @@ fn bootstrap_uses_plain_insertion_for_non_reactive_runtime_fragments() {
         &slot_ids,
         false,
         false,
+        false,
     );
 
     assert!(
@@ -192,3 +197,154 @@ fn bootstrap_uses_plain_insertion_for_non_reactive_runtime_fragments() {
         "non-reactive bootstrap must not reference the mount helper"
     );
 }
+
+#[test]
+fn prerendered_bootstrap_hydrates_instead_of_inserting() {
+    // WHAT: prerendered slots already hold their HTML, so plain fragments are not inserted again
+    // and reactive fragments are mounted in hydrate mode.
+    let slot_ids = vec![String::from("bst-slot-0")];
+    let plain = render_runtime_bootstrap_script_html(
+        "start_entry",
+        "function start_entry() { return []; }",
+        &slot_ids,
+        false,
+        false,
+        true,
+    );
+    assert!(plain.contains("start_entry()"), "start() must still run");
+    assert!(
+        !plain.contains("insertAdjacentHTML"),
+        "prerendered plain fragments must not be inserted again"
+    );
+
+    let reactive = render_runtime_bootstrap_script_html(
+        "start_entry",
+        "function start_entry() { return []; }",
+        &slot_ids,
+        false,
+        true,
+        true,
+    );
+    assert!(
+        reactive.contains("__bs_mount_template_fragment(el, bst_frags[i], true)"),
+        "prerendered reactive fragments must hydrate through the mount helper"
+    );
+}
+
+#[test]
+fn render_entry_fragments_writes_prerendered_html_inside_slots() {
+    let prerendered = [String::from("<p>1</p>"), String::new()];
+    let (body_html, _) = render_entry_fragments(&[], 2, Some(&prerendered));
+
+    assert_eq!(
+        body_html,
+        "<div id=\"bst-slot-0\"><p>1</p></div>\n<div id=\"bst-slot-1\"></div>\n"
+    );
+}
+
+#[test]
+fn prerender_runs_start_and_snapshots_template_fragments() {
+    let bundle = "function start_entry() {\n\
+                  \x20   console.log(\"not captured\");\n\
+                  \x20   return [\"<p>a</p>\", { __bs_template: true, snapshot: () => \"<p>b</p>\" }, null];\n\
+                  }";
+
+    assert_eq!(
+        prerender_runtime_fragments(bundle, "start_entry", PRERENDER_TIMEOUT)
+            .expect("prerender should succeed"),
+        vec!["<p>a</p>", "<p>b</p>", ""]
+    );
+
+    let error = prerender_runtime_fragments(
+        "function start_entry() { throw new Error(\"boom\"); }",
+        "start_entry",
+        PRERENDER_TIMEOUT,
+    )
+    .expect_err("a throwing page should fail to prerender");
+    assert!(error.contains("boom"), "{error}");
+}
+
+#[test]
+fn prerender_kills_pages_that_do_not_finish_in_time() {
+    let started = Instant::now();
+    let error = prerender_runtime_fragments(
+        "function start_entry() { while (true) {} }",
+        "start_entry",
+        Duration::from_millis(500),
+    )
+    .expect_err("a page that never returns should fail to prerender");
+
+    assert!(
+        error.contains("did not finish rendering within 500 ms"),
+        "{error}"
+    );
+    assert!(started.elapsed() < PRERENDER_TIMEOUT);
+}
+
+#[test]
+fn source_line_table_is_anchored_at_the_bundle_marker_and_placed_after_it() {
+    use crate::backends::js::{JsSourceLine, JsSourceOrigin};
+
+    let html = format!(
+        "<html>\n  <body>\n    <script>\n    {BUNDLE_START_MARKER}\n    one();\n    two();\n    </script>\n  </body>\n</html>"
+    );
+    let source_lines = vec![
+        JsSourceLine {
+            generated_line: 2,
+            origin: Some(JsSourceOrigin {
+                file: PathBuf::from("docs/#page.bst"),
+                line: 4,
+                column: 9,
+            }),
+        },
+        JsSourceLine {
+            generated_line: 3,
+            origin: None,
+        },
+    ];
+
+    let with_table = append_source_line_table(html, &source_lines);
+
+    assert!(with_table.contains(
+        "    <script type=\"application/json\" id=\"bst-source-lines\">{\"lines\":[[2,\"docs/#page.bst\",4,9],[3]],\"offset\":4}</script>\n  </body>"
+    ));
+    assert!(
+        with_table
+            .lines()
+            .nth(4 + 2 - 1)
+            .is_some_and(|line| line.contains("two();"))
+    );
+}
+
+#[test]
+fn source_maps_are_relative_to_the_inline_script_holding_the_bundle() {
+    use crate::backends::js::{JsSourceLine, JsSourceOrigin};
+
+    let html = format!(
+        "<html>\n  <body>\n    <script type=\"module\">\n    import {{ a }} from \"./a.js\";\n    {BUNDLE_START_MARKER}\n    one();\n    two();\n    </script>\n  </body>\n</html>"
+    );
+    let source_lines = vec![
+        JsSourceLine {
+            generated_line: 2,
+            origin: Some(JsSourceOrigin {
+                file: PathBuf::from("docs/#page.bst"),
+                line: 4,
+                column: 9,
+            }),
+        },
+        JsSourceLine {
+            generated_line: 3,
+            origin: None,
+        },
+    ];
+
+    let json = render_source_map(&html, &source_lines, Path::new("docs/index.html"), &[])
+        .expect("page with a bundle marker should get a source map");
+    let map: serde_json::Value = serde_json::from_str(&json).expect("map should be JSON");
+
+    assert_eq!(map["file"], "index.html");
+    assert_eq!(map["sources"], serde_json::json!(["../docs/%23page.bst"]));
+    assert_eq!(map["sourcesContent"], serde_json::json!([null]));
+    // Script line 0 is the rest of the `<script>` line; `two();` is script line 4, column 4.
+    assert_eq!(map["mappings"], ";;;;IAGQ;");
+}
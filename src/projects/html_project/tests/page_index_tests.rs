@@ -0,0 +1,196 @@
+//! Tests for page heading outlines and the project page list.
+
+use super::*;
+use crate::projects::routing::{HtmlSiteConfig, PageUrlStyle};
+use std::path::{Path, PathBuf};
+
+fn page(html_path: &str, title: &str) -> HtmlIndexedPage {
+    let html_path = PathBuf::from(html_path);
+    HtmlIndexedPage {
+        url: html_page_url(&html_path, &HtmlSiteConfig::default()),
+        title: title.to_owned(),
+        description: None,
+        date: None,
+        route_directory: route_directory(&html_path),
+        html_path,
+    }
+}
+
+#[test]
+fn page_urls_follow_route_shape_url_style_and_origin() {
+    let mut site_config = HtmlSiteConfig::default();
+    assert_eq!(html_page_url(Path::new("index.html"), &site_config), "/");
+    assert_eq!(
+        html_page_url(Path::new("docs/intro/index.html"), &site_config),
+        "/docs/intro/"
+    );
+    assert_eq!(
+        html_page_url(Path::new("about.html"), &site_config),
+        "/about.html"
+    );
+
+    site_config.origin = String::from("/beanstalk");
+    site_config.page_url_style = PageUrlStyle::NoTrailingSlash;
+    assert_eq!(
+        html_page_url(Path::new("index.html"), &site_config),
+        "/beanstalk/"
+    );
+    assert_eq!(
+        html_page_url(Path::new("docs/index.html"), &site_config),
+        "/beanstalk/docs"
+    );
+}
+
+#[test]
+fn heading_outline_collects_identified_headings_outside_scripts() {
+    let html = "<h1 id=\"guide\">Guide</h1>\
+                <h2>No id</h2>\
+                <H2 class=\"title\" id=\"setup\">Set <code>up</code>\n  now</H2>\
+                <script>const fragment = \"<h2 id=\\\"hidden\\\">Hidden</h2>\";</script>\
+                <h3 data-id=\"x\" id=\"a&amp;b\">A &amp; B</h3>";
+
+    assert_eq!(
+        collect_heading_outline(html),
+        vec![
+            HtmlHeading {
+                level: 1,
+                id: String::from("guide"),
+                text: String::from("Guide"),
+            },
+            HtmlHeading {
+                level: 2,
+                id: String::from("setup"),
+                text: String::from("Set up now"),
+            },
+            HtmlHeading {
+                level: 3,
+                id: String::from("a&amp;b"),
+                text: String::from("A &amp; B"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn heading_outline_nests_by_level_and_tolerates_skipped_levels() {
+    let heading = |level, id: &str| HtmlHeading {
+        level,
+        id: id.to_owned(),
+        text: id.to_owned(),
+    };
+
+    assert_eq!(
+        render_heading_outline(&[
+            heading(2, "a"),
+            heading(4, "b"),
+            heading(3, "c"),
+            heading(2, "d"),
+        ]),
+        "<ul><li><a href=\"#a\">a</a><ul><li><a href=\"#b\">b</a></li>\
+         <li><a href=\"#c\">c</a></li></ul></li><li><a href=\"#d\">d</a></li></ul>"
+    );
+    assert_eq!(render_heading_outline(&[]), "");
+}
+
+#[test]
+fn page_list_nests_routes_under_ancestor_pages_and_marks_the_current_page() {
+    let index = HtmlPageIndex::new(vec![
+        page("docs/intro/index.html", "Intro"),
+        page("docs-archive/index.html", "Archive"),
+        page("index.html", "Home"),
+        page("docs/index.html", "Docs"),
+    ]);
+
+    assert_eq!(
+        index.render_page_list(Path::new("docs/intro/index.html")),
+        "<ul><li><a href=\"/\">Home</a><ul>\
+         <li><a href=\"/docs/\">Docs</a><ul>\
+         <li><a href=\"/docs/intro/\" aria-current=\"page\">Intro</a></li></ul></li>\
+         <li><a href=\"/docs-archive/\">Archive</a></li></ul></li></ul>"
+    );
+}
+
+#[test]
+fn filling_replaces_placeholders_in_markup_but_not_in_scripts() {
+    let index = HtmlPageIndex::new(vec![page("index.html", "Home")]);
+    let html = format!(
+        "<nav class=\"bst-toc\">{TOC_PLACEHOLDER}</nav><h2 id=\"intro\">Intro</h2>\
+         <nav class=\"bst-pages\">{PAGE_LIST_PLACEHOLDER}</nav>\
+         <script>const html = \"{TOC_PLACEHOLDER}\";</script>"
+    );
+
+    let filled = index
+        .fill_navigation(&html, Path::new("index.html"))
+        .expect("placeholders should be filled");
+
+    assert_eq!(
+        filled,
+        format!(
+            "<nav class=\"bst-toc\"><ul><li><a href=\"#intro\">Intro</a></li></ul></nav>\
+             <h2 id=\"intro\">Intro</h2>\
+             <nav class=\"bst-pages\"><ul><li><a href=\"/\" aria-current=\"page\">Home</a></li></ul></nav>\
+             <script>const html = \"{TOC_PLACEHOLDER}\";</script>"
+        )
+    );
+    assert_eq!(
+        index.fill_navigation("<p>No navigation</p>", Path::new("index.html")),
+        None
+    );
+}
+
+#[test]
+fn repeated_heading_ids_are_numbered_across_the_page() {
+    // Two `$md` templates each numbered their own headings; `intro-1` is already taken.
+    let html = "<h1 id=\"intro\">Intro</h1><h2 id=\"intro-1\">Intro 1</h2>\
+                <script>const html = \"<h2 id=\\\"intro\\\">Hidden</h2>\";</script>\
+                <h1 id=\"intro\">Intro</h1><h2 id=\"intro\">Intro</h2>";
+
+    assert_eq!(
+        deduplicate_heading_ids(html).as_deref(),
+        Some(
+            "<h1 id=\"intro\">Intro</h1><h2 id=\"intro-1\">Intro 1</h2>\
+             <script>const html = \"<h2 id=\\\"intro\\\">Hidden</h2>\";</script>\
+             <h1 id=\"intro-2\">Intro</h1><h2 id=\"intro-3\">Intro</h2>"
+        )
+    );
+    assert_eq!(
+        deduplicate_heading_ids("<h1 id=\"a\">A</h1><h2 id=\"b\">B</h2>"),
+        None
+    );
+}
+
+#[test]
+fn filling_outputs_numbers_repeated_ids_before_building_the_outline() {
+    let index = HtmlPageIndex::new(vec![page("index.html", "Home")]);
+    let mut output_files = vec![
+        OutputFile::new(
+            PathBuf::from("index.html"),
+            FileKind::Html(format!(
+                "<nav>{TOC_PLACEHOLDER}</nav><h2 id=\"setup\">Setup</h2><h2 id=\"setup\">Setup</h2>"
+            )),
+        ),
+        OutputFile::new(
+            PathBuf::from("plain.html"),
+            FileKind::Html(String::from(
+                "<h2 id=\"setup\">Setup</h2><h2 id=\"setup\">Setup</h2>",
+            )),
+        ),
+    ];
+
+    index.fill_output_files(&mut output_files);
+
+    let html = |file: &OutputFile| match file.file_kind() {
+        FileKind::Html(html) => html.to_owned(),
+        _ => panic!("output should stay HTML"),
+    };
+    assert_eq!(
+        html(&output_files[0]),
+        "<nav><ul><li><a href=\"#setup\">Setup</a></li><li><a href=\"#setup-1\">Setup</a></li></ul></nav>\
+         <h2 id=\"setup\">Setup</h2><h2 id=\"setup-1\">Setup</h2>"
+    );
+    assert_eq!(
+        html(&output_files[1]),
+        "<h2 id=\"setup\">Setup</h2><h2 id=\"setup-1\">Setup</h2>",
+        "pages without navigation placeholders should still get unique ids"
+    );
+}
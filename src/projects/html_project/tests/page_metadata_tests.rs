@@ -127,3 +127,33 @@ fn rejects_duplicate_reserved_values() {
         other => panic!("expected InvalidPageMetadata payload, got {other:?}"),
     }
 }
+
+#[test]
+fn extracts_page_date_and_rejects_malformed_dates() {
+    let mut string_table = StringTable::new();
+    let mut module = test_module(&mut string_table);
+    module.module_constants = vec![string_constant("docs/#page.bst/page_date", "2026-02-28")];
+
+    let metadata =
+        extract_html_page_metadata(&module, &mut string_table).expect("date should parse");
+    assert_eq!(metadata.date, Some(String::from("2026-02-28")));
+
+    for invalid in [
+        "2026-2-28",
+        "2026-13-01",
+        "2026-01-00",
+        "28/02/2026",
+        "２０２６-01-01",
+    ] {
+        module.module_constants = vec![string_constant("page_date", invalid)];
+        let error = extract_html_page_metadata(&module, &mut string_table)
+            .expect_err("malformed date should fail");
+        match &error.payload {
+            crate::compiler_frontend::compiler_messages::DiagnosticPayload::InvalidPageMetadata {
+                reason,
+                ..
+            } => assert_eq!(*reason, InvalidPageMetadataReason::InvalidDate, "{invalid}"),
+            other => panic!("expected InvalidPageMetadata payload, got {other:?}"),
+        }
+    }
+}
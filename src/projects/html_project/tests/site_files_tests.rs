@@ -0,0 +1,155 @@
+//! Tests for sitemap, robots.txt and Atom feed rendering.
+
+use super::*;
+use crate::projects::html_project::page_index::html_page_url;
+use crate::projects::routing::HtmlSiteConfig;
+
+fn page(html_path: &str, title: &str, date: Option<&str>) -> HtmlIndexedPage {
+    let html_path = PathBuf::from(html_path);
+    let site_config = HtmlSiteConfig {
+        origin: String::from("/blog"),
+        ..HtmlSiteConfig::default()
+    };
+    HtmlIndexedPage {
+        url: html_page_url(&html_path, &site_config),
+        title: title.to_owned(),
+        description: None,
+        date: date.map(str::to_owned),
+        route_directory: None,
+        html_path,
+    }
+}
+
+fn site_config(sitemap: bool, robots_txt: bool, feed: bool) -> HtmlDocumentConfig {
+    HtmlDocumentConfig {
+        site_url: Some(String::from("https://example.com")),
+        sitemap,
+        robots_txt,
+        feed,
+        ..HtmlDocumentConfig::default()
+    }
+}
+
+#[test]
+fn sitemap_lists_absolute_page_urls_with_optional_lastmod() {
+    let sitemap = render_sitemap(
+        &[
+            page("index.html", "Home", None),
+            page("posts/first/index.html", "First", Some("2026-01-31")),
+        ],
+        "https://example.com",
+    );
+
+    assert_eq!(
+        sitemap,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+         \x20 <url>\n    <loc>https://example.com/blog/</loc>\n  </url>\n\
+         \x20 <url>\n    <loc>https://example.com/blog/posts/first/</loc>\n    \
+         <lastmod>2026-01-31</lastmod>\n  </url>\n\
+         </urlset>\n"
+    );
+}
+
+#[test]
+fn robots_txt_allows_everything_and_points_at_the_sitemap_when_present() {
+    assert_eq!(render_robots_txt(None), "User-agent: *\nDisallow:\n");
+    assert_eq!(
+        render_robots_txt(Some("https://example.com/sitemap.xml")),
+        "User-agent: *\nDisallow:\n\nSitemap: https://example.com/sitemap.xml\n"
+    );
+}
+
+#[test]
+fn feed_lists_dated_pages_newest_first_and_skips_undated_pages() {
+    let mut described = page(
+        "posts/second/index.html",
+        "Second &amp; last",
+        Some("2026-03-01"),
+    );
+    described.description = Some(String::from("The &quot;second&quot; post"));
+    let pages = [
+        page("index.html", "Home", None),
+        page("posts/first/index.html", "First", Some("2026-01-31")),
+        described,
+    ];
+
+    let feed = render_atom_feed(&pages, "https://example.com", "/blog", "Bean & Co")
+        .expect("dated pages should produce a feed");
+
+    assert!(feed.contains("<title>Bean &amp; Co</title>"));
+    assert!(feed.contains("<id>https://example.com/blog/</id>"));
+    assert!(feed.contains("<link rel=\"self\" href=\"https://example.com/blog/feed.xml\"/>"));
+    assert!(feed.contains("  <updated>2026-03-01T00:00:00Z</updated>\n  <author>"));
+    assert!(feed.contains(
+        "  <entry>\n    <title>Second &amp; last</title>\n    \
+         <id>https://example.com/blog/posts/second/</id>\n    \
+         <link href=\"https://example.com/blog/posts/second/\"/>\n    \
+         <updated>2026-03-01T00:00:00Z</updated>\n    \
+         <summary>The &quot;second&quot; post</summary>\n  </entry>\n"
+    ));
+
+    let second = feed.find("posts/second/").expect("second entry");
+    let first = feed.find("posts/first/").expect("first entry");
+    assert!(second < first, "newer pages should come first");
+    assert!(!feed.contains("<title>Home</title>"));
+}
+
+#[test]
+fn unnamed_projects_title_their_feed_with_the_site_host() {
+    let feed = render_atom_feed(
+        &[page("index.html", "Home", Some("2026-01-31"))],
+        "https://example.com",
+        "/",
+        "",
+    )
+    .expect("dated pages should produce a feed");
+
+    assert!(feed.contains("<title>example.com</title>"));
+    assert!(feed.contains("<author><name>example.com</name></author>"));
+}
+
+#[test]
+fn feed_is_omitted_without_dated_pages() {
+    assert_eq!(
+        render_atom_feed(
+            &[page("index.html", "Home", None)],
+            "https://example.com",
+            "/",
+            "Site"
+        ),
+        None
+    );
+}
+
+#[test]
+fn site_files_follow_their_config_switches() {
+    let index = HtmlPageIndex::new(vec![page("index.html", "Home", Some("2026-01-31"))]);
+    let paths = |files: Vec<OutputFile>| {
+        files
+            .iter()
+            .map(|file| file.relative_output_path().to_path_buf())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        paths(render_site_files(
+            &index,
+            &site_config(true, true, true),
+            "/blog",
+            "Site"
+        )),
+        vec![
+            PathBuf::from(SITEMAP_PATH),
+            PathBuf::from(ROBOTS_TXT_PATH),
+            PathBuf::from(FEED_PATH),
+        ]
+    );
+    assert!(render_site_files(&index, &site_config(false, false, false), "/", "Site").is_empty());
+
+    let robots_only = render_site_files(&index, &site_config(false, true, false), "/", "Site");
+    let FileKind::Bytes(robots) = robots_only[0].file_kind() else {
+        panic!("robots.txt should be a byte artifact");
+    };
+    assert_eq!(robots.as_slice(), b"User-agent: *\nDisallow:\n");
+}
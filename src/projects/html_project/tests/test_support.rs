@@ -98,6 +98,8 @@ pub(crate) fn create_test_module(entry_point: PathBuf, string_table: &mut String
             doc_fragments: vec![],
             rendered_path_usages: vec![],
             validated_generic_templates: ValidatedGenericTemplateStore::default(),
+            public_interface: None,
+            public_type_ids_by_origin: Default::default(),
         },
     }
 }
@@ -123,6 +123,7 @@ pub(crate) fn compile_html_module_wasm(
     let (entry_fragment_html, slot_ids) = render_entry_fragments(
         input.const_fragments,
         input.root_activity.runtime_fragment_count,
+        None,
     );
 
     let mut build_plan = build_html_wasm_plan(input.hir_module, slot_ids)
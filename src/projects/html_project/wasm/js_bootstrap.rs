@@ -21,23 +21,6 @@ pub(crate) fn generate_wasm_bootstrap_js(
     out.push('\n');
     out.push('\n');
     out.push_str("const __bst_decoder = new TextDecoder(\"utf-8\");\n");
-    out.push_str("const __bst_dom_registry = new Map();\n");
-    out.push_str("let __bst_next_dom_handle = 1;\n");
-    out.push('\n');
-    out.push_str("function __bst_register_dom_node(node) {\n");
-    out.push_str("  const handle = __bst_next_dom_handle;\n");
-    out.push_str("  __bst_next_dom_handle += 1;\n");
-    out.push_str("  __bst_dom_registry.set(handle, node);\n");
-    out.push_str("  return handle;\n");
-    out.push_str("}\n");
-    out.push('\n');
-    out.push_str("function __bst_lookup_dom_node(handle) {\n");
-    out.push_str("  const node = __bst_dom_registry.get(handle);\n");
-    out.push_str(
-        "  if (!node) throw new Error(\"Unknown DOM node handle from Wasm host call: \" + handle);\n",
-    );
-    out.push_str("  return node;\n");
-    out.push_str("}\n");
     out.push('\n');
     out.push_str("function __bst_read_string(instance, handle) {\n");
     out.push_str("  if (handle === 0 || handle === undefined || handle === null) return \"\";\n");
@@ -47,31 +30,35 @@ pub(crate) fn generate_wasm_bootstrap_js(
     out.push_str("  return __bst_decoder.decode(bytes);\n");
     out.push_str("}\n");
     out.push('\n');
-    out.push_str("function __bst_take_string(instance, handle) {\n");
-    out.push_str("  if (handle === 0 || handle === undefined || handle === null) return \"\";\n");
-    out.push_str("  try {\n");
-    out.push_str("    return __bst_read_string(instance, handle);\n");
-    out.push_str("  } finally {\n");
-    out.push_str("    instance.exports.bst_release(handle);\n");
-    out.push_str("  }\n");
-    out.push_str("}\n");
-    out.push('\n');
+    // Host imports only borrow string handles: the module still owns them and releases them at
+    // its own drop sites, so the glue must not call `bst_release` on arguments. DOM nodes cross
+    // as `externref`, so the glue receives and returns the JS objects themselves.
     out.push_str("function __bst_build_imports(instance_ref) {\n");
     out.push_str("  return {\n");
     out.push_str("    host: {\n");
     out.push_str("      dom_create_text(handle) {\n");
     out.push_str(
-        "        const text = __bst_take_string(instance_ref.current, handle);\n        return __bst_register_dom_node(document.createTextNode(text));\n",
+        "        return document.createTextNode(__bst_read_string(instance_ref.current, handle));\n",
     );
     out.push_str("      },\n");
-    out.push_str("      dom_set_text(node_handle, text_handle) {\n");
+    out.push_str("      dom_set_text(node, text_handle) {\n");
     out.push_str(
-        "        const node = __bst_lookup_dom_node(node_handle);\n        node.textContent = __bst_take_string(instance_ref.current, text_handle);\n",
+        "        node.textContent = __bst_read_string(instance_ref.current, text_handle);\n",
     );
     out.push_str("      },\n");
-    out.push_str("      dom_set_html(node_handle, html_handle) {\n");
+    out.push_str("      dom_set_html(node, html_handle) {\n");
+    out.push_str(
+        "        node.innerHTML = __bst_read_string(instance_ref.current, html_handle);\n",
+    );
+    out.push_str("      },\n");
+    // Float `^` defers to the host so results match the JS backend's `Math.pow` exactly.
+    out.push_str("      math_pow(base, exponent) {\n");
+    out.push_str("        return Math.pow(base, exponent);\n");
+    out.push_str("      },\n");
+    // `String -> Float` casts validate text in Wasm, then share the JS backend's parser.
+    out.push_str("      parse_float(handle) {\n");
     out.push_str(
-        "        const node = __bst_lookup_dom_node(node_handle);\n        node.innerHTML = __bst_take_string(instance_ref.current, html_handle);\n",
+        "        return Number.parseFloat(__bst_read_string(instance_ref.current, handle));\n",
     );
     out.push_str("      },\n");
     out.push_str("    },\n");
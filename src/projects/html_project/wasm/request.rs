@@ -5,6 +5,7 @@
 
 use crate::backends::wasm::request::{
     WasmBackendRequest, WasmExportPolicy, WasmFunctionEmissionPolicy, WasmHelperExportPolicy,
+    WasmTargetFeatures,
 };
 use crate::projects::html_project::wasm::export_plan::HtmlWasmExportPlan;
 use rustc_hash::FxHashMap;
@@ -38,9 +39,20 @@ pub(crate) fn build_wasm_backend_request(export_plan: &HtmlWasmExportPlan) -> Wa
                 export_vec_len: export_plan.helper_exports.export_vec_len,
                 export_vec_get: export_plan.helper_exports.export_vec_get,
                 export_release: export_plan.helper_exports.export_release,
+                export_str_byte: false,
+                export_live_allocations: false,
             },
+            command_start: None,
         },
         function_emission_policy: WasmFunctionEmissionPolicy::ReachableFromExports,
+        // Every current browser engine runs multi-value and reference types, so multi-return
+        // functions keep their tuples on the stack and host objects (DOM nodes, canvas
+        // contexts) cross the boundary as `externref` with no JS-side lookup table.
+        target_features: WasmTargetFeatures {
+            enable_multi_value: true,
+            enable_reference_types: true,
+            ..WasmTargetFeatures::default()
+        },
         ..WasmBackendRequest::default()
     }
 }
@@ -24,7 +24,11 @@ fn compile_html_module_wasm_exports_bst_start_directly() {
         project_name: "",
         document_config: &HtmlDocumentConfig::default(),
         release_build: false,
+        dev_server: false,
+        source_maps: false,
+        source_roots: &[],
         root_activity: &module.metadata.root_activity,
+        prerender: false,
         external_package_registry: Arc::new(
             crate::compiler_frontend::external_packages::ExternalPackageRegistry::new(),
         ),
@@ -98,6 +102,28 @@ fn wasm_export_plan_wires_required_helper_exports() {
     assert!(helper.export_release);
 }
 
+#[test]
+fn wasm_request_passes_host_objects_as_externref() {
+    let mut string_table = StringTable::new();
+    let module = create_test_module(PathBuf::from("#page.bst"), &mut string_table);
+
+    let plan =
+        build_html_wasm_plan(&module.executable.hir, Vec::new()).expect("wasm plan should build");
+    let features = plan.wasm_request.target_features;
+    assert!(features.enable_multi_value);
+    assert!(features.enable_reference_types);
+
+    // With `externref` DOM nodes, the glue takes and returns the JS objects themselves.
+    let js = generate_wasm_bootstrap_js("", &[], "instance.exports.bst_start()")
+        .expect("bootstrap should generate");
+    assert!(js.contains("return document.createTextNode("));
+    assert!(js.contains("dom_set_text(node, text_handle)"));
+    assert!(js.contains("dom_set_html(node, html_handle)"));
+    assert!(!js.contains("__bst_dom_registry"));
+    assert!(!js.contains("__bst_register_dom_node"));
+    assert!(!js.contains("__bst_lookup_dom_node"));
+}
+
 #[test]
 fn compile_html_module_wasm_preserves_nested_logical_html_route() {
     let mut string_table = StringTable::new();
@@ -111,7 +137,11 @@ fn compile_html_module_wasm_preserves_nested_logical_html_route() {
         project_name: "",
         document_config: &HtmlDocumentConfig::default(),
         release_build: false,
+        dev_server: false,
+        source_maps: false,
+        source_roots: &[],
         root_activity: &module.metadata.root_activity,
+        prerender: false,
         external_package_registry: Arc::new(
             crate::compiler_frontend::external_packages::ExternalPackageRegistry::new(),
         ),
@@ -0,0 +1,523 @@
+//! JS module project builder.
+//!
+//! WHAT: lowers every module through the JS backend into one ES module, runs the entry start
+//! function when the module is evaluated and exports the module's public functions and
+//! constants under their Beanstalk names. A `.d.mts` declaration file describing the exports is
+//! written next to each module.
+//! WHY: a module is either a script (`node tool.mjs` prints its fragments and console output in
+//! source order) or a library (`import { render } from "./lib.mjs"`), and often both.
+use crate::backends::backend_feature_validation::{
+    BackendFeatureValidationError, BackendFeatureValidationInput, BackendFeatureValidationRoot,
+    validate_hir_backend_feature_support,
+};
+use crate::backends::external_package_validation::{
+    BackendTarget, ExternalPackageValidationError, validate_hir_external_package_support,
+};
+use crate::backends::js::{JsLoweringConfig, JsModule, escape_js_string, lower_hir_to_js};
+use crate::build_system::build::{
+    BackendBuilder, CleanupPolicy, EntryFragment, FileKind, Module, OutputFile, Project,
+    order_entry_fragments,
+};
+use crate::build_system::create_project_modules::resolve_project_entry_root;
+use crate::builder_surface::BuilderSurface;
+use crate::compiler_frontend::Flag;
+use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
+use crate::compiler_frontend::folded_value::PublicFoldedValue;
+use crate::compiler_frontend::hir::ids::FunctionId;
+use crate::compiler_frontend::public_interface_draft::PublicDeclarationSemantics;
+use crate::compiler_frontend::semantic_identity::OriginDeclarationId;
+use crate::compiler_frontend::style_directives::StyleDirectiveSpec;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::projects::js_module_project::type_declarations::{
+    StructFieldKeys, render_type_declarations,
+};
+use crate::projects::settings::{Config, ProjectConfigError};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Output file name of a directory-build module.
+const JS_MODULE_OUTPUT_NAME: &str = "index.mjs";
+
+/// Extension replacing `mjs` on a module's TypeScript declaration file.
+const JS_MODULE_DECLARATION_EXTENSION: &str = "d.mts";
+
+#[derive(Debug, Default)]
+pub struct JsModuleProjectBuilder;
+
+impl JsModuleProjectBuilder {
+    /// Constructs the JS module builder.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BackendBuilder for JsModuleProjectBuilder {
+    fn build_backend(
+        &self,
+        modules: Vec<Module>,
+        config: &Config,
+        flags: &[Flag],
+        string_table: &mut StringTable,
+    ) -> Result<Project, CompilerMessages> {
+        let _total_guard = crate::timing::PipelineTimingGuard::new("backend.js_module.total");
+
+        if modules.is_empty() {
+            return Err(CompilerMessages::from_error(
+                CompilerError::compiler_error(
+                    "JS module builder expected at least one compiled module but got 0.",
+                ),
+                string_table.clone(),
+            ));
+        }
+
+        let release_build = flags.contains(&Flag::Release);
+        let entry_root = resolve_canonical_entry_root(config, string_table)?;
+        let mut output_files = Vec::new();
+        let mut output_path_owners: HashMap<PathBuf, PathBuf> = HashMap::new();
+        let mut entry_page_rel = None;
+
+        // Library modules without root activity still export their public API, so every module
+        // gets an output file.
+        for module in &modules {
+            let output_path =
+                js_module_output_path(&module.metadata.entry_point, entry_root.as_deref())
+                    .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
+            if let Some(existing_entry_point) = output_path_owners.get(&output_path) {
+                return Err(CompilerMessages::from_error(
+                    CompilerError::compiler_error(format!(
+                        "JS modules '{}' and '{}' both compile to '{}'.",
+                        existing_entry_point.display(),
+                        module.metadata.entry_point.display(),
+                        output_path.display()
+                    )),
+                    string_table.clone(),
+                ));
+            }
+            output_path_owners.insert(output_path.clone(), module.metadata.entry_point.clone());
+
+            let compiled = compile_js_module(module, release_build, string_table)?;
+            // The root module is the project entry.
+            if output_path.parent() == Some(Path::new("")) {
+                entry_page_rel = Some(output_path.clone());
+            }
+            if let Some(declarations) = compiled.declarations {
+                output_files.push(OutputFile::new(
+                    output_path.with_extension(JS_MODULE_DECLARATION_EXTENSION),
+                    FileKind::Js(declarations),
+                ));
+            }
+            output_files.push(OutputFile::new(output_path, FileKind::Js(compiled.source)));
+        }
+
+        Ok(Project {
+            output_files,
+            entry_page_rel,
+            // Cleanup matches the last extension, so `.d.mts` files are owned through `.mts`.
+            cleanup_policy: CleanupPolicy::generic([".mjs", ".mts"]),
+            warnings: Vec::new(),
+        })
+    }
+
+    fn validate_project_config(
+        &self,
+        _config: &Config,
+        _string_table: &mut StringTable,
+    ) -> Result<(), ProjectConfigError> {
+        // JS modules have no backend-specific config keys; core keys are validated in Stage 0.
+        Ok(())
+    }
+
+    fn frontend_style_directives(&self) -> Vec<StyleDirectiveSpec> {
+        Vec::new()
+    }
+
+    fn frontend_surface(&self) -> BuilderSurface {
+        let mut builder_surface = BuilderSurface::with_mandatory_core();
+        builder_surface.expose_html_core_packages();
+        builder_surface
+    }
+}
+
+/// One public binding of the emitted module.
+enum JsModuleExport {
+    /// An emitted function, exported under its public name.
+    Function {
+        public_name: String,
+        function_id: FunctionId,
+    },
+    /// A folded constant, emitted as a literal binding.
+    Constant {
+        public_name: String,
+        literal: String,
+    },
+}
+
+/// One module's emitted ES module and its TypeScript declarations.
+struct CompiledJsModule {
+    source: String,
+    /// `None` only when the module reached the builder without a public-interface draft.
+    declarations: Option<String>,
+}
+
+/// Validates and lowers one module to ES module source and declarations.
+fn compile_js_module(
+    module: &Module,
+    release_build: bool,
+    string_table: &mut StringTable,
+) -> Result<CompiledJsModule, CompilerMessages> {
+    let hir_module = &module.executable.hir;
+    let exports = collect_module_exports(module, string_table)?;
+
+    // Exported functions are entry points of their own, so they are validated alongside start.
+    let mut root_functions = vec![hir_module.start_function];
+    root_functions.extend(exports.iter().filter_map(|export| match export {
+        JsModuleExport::Function { function_id, .. } => Some(*function_id),
+        JsModuleExport::Constant { .. } => None,
+    }));
+
+    validate_hir_external_package_support(
+        hir_module,
+        module.link_facts.external_package_registry.as_ref(),
+        BackendTarget::Js,
+        string_table,
+    )
+    .map_err(|error| match error {
+        ExternalPackageValidationError::Diagnostic(diagnostic) => {
+            CompilerMessages::from_diagnostic_ref(*diagnostic, string_table)
+        }
+        ExternalPackageValidationError::Infrastructure(error) => {
+            CompilerMessages::from_error_ref(*error, string_table)
+        }
+    })?;
+
+    validate_hir_backend_feature_support(
+        BackendFeatureValidationInput {
+            hir: hir_module,
+            target: BackendTarget::Js,
+            root: BackendFeatureValidationRoot::ExplicitRoots(root_functions),
+            type_environment: Some(&module.executable.type_environment),
+        },
+        string_table,
+    )
+    .map_err(|error| match error {
+        BackendFeatureValidationError::Diagnostic(diagnostic) => {
+            CompilerMessages::from_diagnostic_ref(*diagnostic, string_table)
+                .with_type_context_for_all_diagnostics(module.executable.type_environment.clone())
+        }
+        BackendFeatureValidationError::Infrastructure(error) => {
+            CompilerMessages::from_error_ref(*error, string_table)
+        }
+    })?;
+
+    let js_module = {
+        let _lower_guard = crate::timing::PipelineTimingGuard::new("backend.js_module.lower_js");
+        lower_hir_to_js(
+            hir_module,
+            &module.executable.borrow_analysis,
+            string_table,
+            JsLoweringConfig::es_module(
+                release_build,
+                Arc::clone(&module.link_facts.external_package_registry),
+            ),
+            &module.executable.type_environment,
+        )
+        .map_err(|error| CompilerMessages::from_error_ref(error, string_table))?
+    };
+
+    let emitted_name = |function_id: FunctionId| {
+        js_module
+            .function_name_by_id
+            .get(&function_id)
+            .map(String::as_str)
+            .ok_or_else(|| {
+                CompilerMessages::from_error(
+                    CompilerError::compiler_error(format!(
+                        "JS module builder found no emitted function for {function_id:?}."
+                    )),
+                    string_table.clone(),
+                )
+            })
+    };
+
+    let mut source = js_module.source.clone();
+    if module.metadata.root_activity.has_html_artifact_activity() {
+        source.push_str(&entry_bootstrap(
+            module,
+            emitted_name(hir_module.start_function)?,
+        ));
+    }
+
+    let mut export_specifiers = Vec::with_capacity(exports.len());
+    for (index, export) in exports.iter().enumerate() {
+        match export {
+            JsModuleExport::Function {
+                public_name,
+                function_id,
+            } => {
+                let js_name = emitted_name(*function_id)?;
+                export_specifiers.push(format!("{js_name} as {public_name}"));
+            }
+            JsModuleExport::Constant {
+                public_name,
+                literal,
+            } => {
+                let binding = format!("__bs_export_{index}");
+                source.push_str(&format!("const {binding} = {literal};\n"));
+                export_specifiers.push(format!("{binding} as {public_name}"));
+            }
+        }
+    }
+
+    if !export_specifiers.is_empty() {
+        source.push_str(&format!("export {{ {} }};\n", export_specifiers.join(", ")));
+    }
+
+    let declarations = module
+        .metadata
+        .public_interface
+        .as_ref()
+        .map(|draft| {
+            render_type_declarations(draft, &struct_field_keys(module, &js_module, string_table))
+        })
+        .transpose()
+        .map_err(|error| CompilerMessages::from_error_ref(error, string_table))?;
+
+    Ok(CompiledJsModule {
+        source,
+        declarations,
+    })
+}
+
+/// Emitted object keys of each public struct's fields, joined through the struct's `TypeId`.
+fn struct_field_keys(
+    module: &Module,
+    js_module: &JsModule,
+    string_table: &StringTable,
+) -> StructFieldKeys {
+    let hir_module = &module.executable.hir;
+    module
+        .metadata
+        .public_type_ids_by_origin
+        .iter()
+        .filter_map(|(origin, type_id)| {
+            let hir_struct = hir_module
+                .structs
+                .iter()
+                .find(|hir_struct| hir_struct.frontend_type_id == *type_id)?;
+            let keys = hir_struct
+                .fields
+                .iter()
+                .filter_map(|field| {
+                    let name = hir_module
+                        .side_table
+                        .field_name_path(field.id)?
+                        .name_str(string_table)?;
+                    let js_key = js_module.field_name_by_id.get(&field.id)?;
+                    Some((name.to_owned(), js_key.to_owned()))
+                })
+                .collect();
+            Some((origin.clone(), keys))
+        })
+        .collect()
+}
+
+/// Evaluation-time entry code: runs `start` and prints each fragment as one stdout line.
+///
+/// WHAT: const fragments are inlined as literals and runtime fragments are read from the array
+/// `start` returns, interleaved in source order.
+/// WHY: matches the WASI command output, so a script prints the same lines on either target.
+fn entry_bootstrap(module: &Module, start_name: &str) -> String {
+    let fragments = order_entry_fragments(
+        &module.metadata.const_top_level_fragments,
+        module.metadata.root_activity.runtime_fragment_count,
+    );
+
+    if fragments.is_empty() {
+        return format!("{start_name}();\n");
+    }
+
+    let mut bootstrap = format!("const __bs_fragments = {start_name}();\n");
+    for fragment in fragments {
+        match fragment {
+            EntryFragment::Const(text) => bootstrap.push_str(&format!(
+                "process.stdout.write({} + \"\\n\");\n",
+                escape_js_string(text)
+            )),
+            EntryFragment::Runtime(index) => bootstrap.push_str(&format!(
+                "if ({index} < __bs_fragments.length) \
+                 process.stdout.write(__bs_value_to_string(__bs_fragments[{index}]) + \"\\n\");\n"
+            )),
+        }
+    }
+    bootstrap
+}
+
+/// Collects the module's exported functions and constants in `export:` block order.
+///
+/// WHAT: type and trait exports have no runtime value and are skipped.
+/// WHY: generic functions and constants without a plain JS value shape are rejected here
+/// rather than exported in a form JS callers could not use.
+fn collect_module_exports(
+    module: &Module,
+    string_table: &StringTable,
+) -> Result<Vec<JsModuleExport>, CompilerMessages> {
+    let Some(public_interface) = &module.metadata.public_interface else {
+        return Ok(Vec::new());
+    };
+
+    let mut exports = Vec::new();
+    for binding in &public_interface.export_bindings {
+        let public_name = binding.public_name().to_owned();
+        match binding.origin() {
+            OriginDeclarationId::Function(origin) => {
+                // Only non-generic functions are lowered with a local function id.
+                let Some(function_id) = module.executable.hir.function_ids_by_origin.get(origin)
+                else {
+                    return Err(CompilerMessages::file_error(
+                        &module.metadata.entry_point,
+                        format!(
+                            "Generic function '{public_name}' cannot be exported from a JS module. \
+                             Export a non-generic wrapper for each type JS callers need."
+                        ),
+                        string_table,
+                    ));
+                };
+                exports.push(JsModuleExport::Function {
+                    public_name,
+                    function_id: *function_id,
+                });
+            }
+
+            OriginDeclarationId::Constant(_) => {
+                let semantics = public_interface
+                    .declarations
+                    .iter()
+                    .find(|declaration| &declaration.origin == binding.origin())
+                    .map(|declaration| &declaration.semantics);
+                let Some(PublicDeclarationSemantics::Constant(constant)) = semantics else {
+                    return Err(CompilerMessages::from_error(
+                        CompilerError::compiler_error(format!(
+                            "JS module builder found no constant declaration for export '{public_name}'."
+                        )),
+                        string_table.clone(),
+                    ));
+                };
+                let Some(literal) = js_constant_literal(&constant.folded_value) else {
+                    return Err(CompilerMessages::file_error(
+                        &module.metadata.entry_point,
+                        format!(
+                            "Constant '{public_name}' cannot be exported from a JS module. \
+                             Only numbers, booleans, chars, strings, ranges and collections of \
+                             those have a JS value shape; export a function returning it instead."
+                        ),
+                        string_table,
+                    ));
+                };
+                exports.push(JsModuleExport::Constant {
+                    public_name,
+                    literal,
+                });
+            }
+
+            OriginDeclarationId::Type(_) | OriginDeclarationId::Trait(_) => {}
+        }
+    }
+
+    Ok(exports)
+}
+
+/// JS literal of an exported constant, or `None` when the value has no plain JS shape.
+///
+/// WHY: records, choices and options lower to backend-private carrier objects whose field keys
+/// are not the Beanstalk names, so exporting them would leak that layout to JS callers.
+fn js_constant_literal(value: &PublicFoldedValue) -> Option<String> {
+    match value {
+        PublicFoldedValue::Int(value) => Some(value.to_string()),
+        PublicFoldedValue::Float(value) => {
+            Some(f64::from_bits(value.normalized_bits()).to_string())
+        }
+        PublicFoldedValue::Bool(value) => Some(value.to_string()),
+        PublicFoldedValue::Char(value) => Some(escape_js_string(&value.to_string())),
+        PublicFoldedValue::String(value) => Some(escape_js_string(value)),
+        PublicFoldedValue::Collection(items) => {
+            let items = items
+                .iter()
+                .map(js_constant_literal)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("[{}]", items.join(", ")))
+        }
+        PublicFoldedValue::Range { start, end } => Some(format!(
+            "{{ start: {}, end: {} }}",
+            js_constant_literal(start)?,
+            js_constant_literal(end)?
+        )),
+        PublicFoldedValue::Record(_)
+        | PublicFoldedValue::Choice { .. }
+        | PublicFoldedValue::OptionSome(_)
+        | PublicFoldedValue::OptionNone => None,
+    }
+}
+
+/// Output path of one module.
+///
+/// WHAT: single-file builds name the module after the entry stem without a cosmetic leading
+/// `#`; directory builds put `index.mjs` in each module's entry-root-relative directory.
+pub(crate) fn js_module_output_path(
+    entry_point: &Path,
+    entry_root: Option<&Path>,
+) -> Result<PathBuf, CompilerError> {
+    let Some(entry_root) = entry_root else {
+        let stem = entry_point
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| stem.trim_start_matches('#'))
+            .filter(|stem| !stem.is_empty())
+            .ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "JS module single-file entry '{}' needs a non-empty UTF-8 file stem.",
+                    entry_point.display()
+                ))
+            })?;
+        return Ok(PathBuf::from(format!("{stem}.mjs")));
+    };
+
+    let relative_entry = entry_point.strip_prefix(entry_root).map_err(|_| {
+        CompilerError::compiler_error(format!(
+            "JS module entry '{}' is not inside the configured entry root '{}'.",
+            entry_point.display(),
+            entry_root.display()
+        ))
+    })?;
+    let parent = relative_entry.parent().unwrap_or_else(|| Path::new(""));
+
+    Ok(parent.join(JS_MODULE_OUTPUT_NAME))
+}
+
+/// Canonical entry root of a directory build, or `None` for single-file builds.
+fn resolve_canonical_entry_root(
+    config: &Config,
+    string_table: &StringTable,
+) -> Result<Option<PathBuf>, CompilerMessages> {
+    if !config.entry_dir.is_dir() {
+        return Ok(None);
+    }
+
+    let entry_root_path = resolve_project_entry_root(config);
+    fs::canonicalize(&entry_root_path)
+        .map(Some)
+        .map_err(|error| {
+            CompilerMessages::from_error(
+                CompilerError::compiler_error(format!(
+                    "Failed to resolve configured JS module entry root '{}': {error}",
+                    entry_root_path.display()
+                )),
+                string_table.clone(),
+            )
+        })
+}
+
+#[cfg(test)]
+#[path = "tests/js_module_project_builder_tests.rs"]
+mod tests;
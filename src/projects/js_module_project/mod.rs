@@ -0,0 +1,9 @@
+//! Node.js / plain JS module project backend.
+//!
+//! WHAT: builds each module into a standalone ES module (`.mjs`) that exports the module's
+//! public functions and constants, with a `.d.mts` TypeScript declaration file beside it.
+//! WHY: Beanstalk code can be imported from existing JS or TS tooling or run directly under Node
+//! without an HTML page.
+
+pub mod js_module_project_builder;
+pub(crate) mod type_declarations;
@@ -0,0 +1,160 @@
+//! Tests for the JS module project builder.
+
+use super::*;
+use crate::build_system::build::{ProjectBuilder, build_project};
+use crate::compiler_tests::test_support::temp_dir;
+use std::process::Stdio;
+
+/// Builds `source` as a single-file JS module project and returns the emitted module.
+fn build_module(name: &str, source: &str) -> Result<(PathBuf, String), CompilerMessages> {
+    let root = temp_dir(name);
+    fs::create_dir_all(&root).expect("should create temp root");
+    let entry = root.join("tool.bst");
+    fs::write(&entry, source).expect("should write source");
+
+    let builder = ProjectBuilder::new(Box::new(JsModuleProjectBuilder::new()));
+    let result = build_project(&builder, entry.to_str().expect("UTF-8 path"), &[]);
+    fs::remove_dir_all(&root).expect("should remove temp root");
+
+    let project = result?.project;
+    let [declarations, module] = project.output_files.as_slice() else {
+        panic!("a single-file JS module build should emit the module and its declarations");
+    };
+    assert_eq!(
+        declarations.relative_output_path(),
+        module.relative_output_path().with_extension("d.mts")
+    );
+    let FileKind::Js(source) = module.file_kind() else {
+        panic!("the JS module should be a JS file");
+    };
+    Ok((module.relative_output_path().to_path_buf(), source.clone()))
+}
+
+/// Writes the module next to `runner` (an ES module importing `./tool.mjs`), runs the runner
+/// under Node and returns its stdout and stderr lines.
+fn run_with_node(module_source: &str, runner: &str) -> (Vec<String>, Vec<String>) {
+    let directory = tempfile::tempdir().expect("temp dir should be created");
+    fs::write(directory.path().join("tool.mjs"), module_source).expect("module should be written");
+    let runner_path = directory.path().join("runner.mjs");
+    fs::write(&runner_path, runner).expect("runner should be written");
+
+    let output = std::process::Command::new("node")
+        .arg(&runner_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("node should be on PATH to run JS module tests");
+    assert!(
+        output.status.success(),
+        "JS module failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let lines = |bytes: &[u8]| {
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    };
+    (lines(&output.stdout), lines(&output.stderr))
+}
+
+#[test]
+fn output_paths_follow_the_entry_stem_or_module_directory() {
+    assert_eq!(
+        js_module_output_path(Path::new("/work/#tool.bst"), None).expect("stem should resolve"),
+        PathBuf::from("tool.mjs")
+    );
+
+    let root = Path::new("/work/lib");
+    assert_eq!(
+        js_module_output_path(&root.join("#page.bst"), Some(root)).expect("root should resolve"),
+        PathBuf::from("index.mjs")
+    );
+    assert_eq!(
+        js_module_output_path(&root.join("fmt/#page.bst"), Some(root))
+            .expect("nested should resolve"),
+        PathBuf::from("fmt/index.mjs")
+    );
+}
+
+#[test]
+fn module_prints_console_output_and_fragments_in_order_when_run() {
+    let (output_path, source) = build_module(
+        "js_module_script",
+        "import @core/io\n\
+         \n\
+         io.line(\"hello from node\")\n\
+         io.error(\"to stderr\")\n\
+         [:const fragment]\n\
+         count = 2\n\
+         [:runtime [count]]\n",
+    )
+    .expect("JS module build should succeed");
+    assert_eq!(output_path, PathBuf::from("tool.mjs"));
+
+    let (stdout, stderr) = run_with_node(&source, "import \"./tool.mjs\";\n");
+
+    assert_eq!(
+        stdout,
+        vec!["hello from node", "const fragment", "runtime 2"]
+    );
+    assert_eq!(stderr, vec!["to stderr"]);
+}
+
+#[test]
+fn exported_functions_and_constants_are_importable() {
+    let (_, source) = build_module(
+        "js_module_library",
+        "private_suffix || -> String:\n\
+         \x20   return \"!\"\n\
+         ;\n\
+         \n\
+         export:\n\
+         \x20   greeting #= \"Hello\"\n\
+         \x20   limits #= {1, 2, 3}\n\
+         \n\
+         \x20   greet |name String| -> String:\n\
+         \x20       return [:[greeting], [name][private_suffix()]]\n\
+         \x20   ;\n\
+         \n\
+         \x20   add |a Int, b Int| -> Int:\n\
+         \x20       return a + b\n\
+         \x20   ;\n\
+         ;\n",
+    )
+    .expect("JS module build should succeed");
+
+    let (stdout, _) = run_with_node(
+        &source,
+        "import { greet, add, greeting, limits } from \"./tool.mjs\";\n\
+         console.log(greet(\"Ada\"));\n\
+         console.log(add(2, 3));\n\
+         console.log(greeting, limits.join(\",\"));\n",
+    );
+
+    assert_eq!(stdout, vec!["Hello, Ada!", "5", "Hello 1,2,3"]);
+    assert!(
+        !source.contains("private_suffix as"),
+        "private functions must not be exported:\n{source}"
+    );
+}
+
+#[test]
+fn constants_without_a_plain_js_shape_are_rejected() {
+    let messages = build_module(
+        "js_module_rejects_option_constant",
+        "export:\n\
+         \x20   fallback #Int? = 3\n\
+         ;\n",
+    )
+    .expect_err("option constants lower to a backend-private carrier");
+
+    let (_, message, _) = messages
+        .first_infrastructure_error_for_tests()
+        .expect("expected an error diagnostic");
+    assert!(
+        message.contains("Constant 'fallback' cannot be exported from a JS module"),
+        "{message}"
+    );
+}
@@ -0,0 +1,237 @@
+//! Tests for JS module TypeScript declarations.
+//!
+//! Declarations are rendered from a real build and checked against the values the emitted module
+//! returns under Node, so a runtime carrier change that the declarations miss fails here.
+
+use crate::build_system::build::{FileKind, ProjectBuilder, build_project};
+use crate::compiler_tests::test_support::temp_dir;
+use crate::projects::js_module_project::js_module_project_builder::JsModuleProjectBuilder;
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+
+const LIBRARY_SOURCE: &str = "\
+export:
+    limits #= {1, 2, 3}
+
+    Point = |
+        x Int,
+        label String = \"p\",
+    |
+
+    Shape ::
+        Circle | radius Float |,
+        Empty,
+    ;
+
+    Box type T = |
+        value T,
+    |
+
+    Problem = |
+        reason String,
+    |
+
+    make |x Int| -> Point:
+        return Point(x)
+    ;
+
+    circle |r Float| -> Shape:
+        return Shape::Circle(r)
+    ;
+
+    nothing || -> Shape:
+        return Shape::Empty
+    ;
+
+    parse |text String| -> Int, Problem!:
+        if text is \"\":
+            return! Problem(\"Missing number\")
+        ;
+        return 42
+    ;
+
+    maybe |x Int| -> Int?:
+        if x is 0:
+            return none
+        ;
+        return x
+    ;
+
+    pair || -> Int, String:
+        return 1, \"a\"
+    ;
+
+    unbox |b Box of Int| -> Int:
+        return b.value
+    ;
+;
+";
+
+/// Builds `LIBRARY_SOURCE` as a directory JS module project and returns the emitted module and
+/// its declarations.
+fn build_library(name: &str) -> (String, String) {
+    let root = temp_dir(name);
+    fs::create_dir_all(&root).expect("should create temp root");
+    fs::write(root.join("#page.bst"), LIBRARY_SOURCE).expect("should write source");
+    fs::write(root.join("config.bst"), "project #= \"js\"\n").expect("should write config");
+
+    let builder = ProjectBuilder::new(Box::new(JsModuleProjectBuilder::new()));
+    let result = build_project(&builder, root.to_str().expect("UTF-8 path"), &[]);
+    fs::remove_dir_all(&root).expect("should remove temp root");
+
+    let project = result.expect("JS module build should succeed").project;
+    let output = |file_name: &str| {
+        project
+            .output_files
+            .iter()
+            .find(|output| output.relative_output_path() == Path::new(file_name))
+            .and_then(|output| match output.file_kind() {
+                FileKind::Js(source) => Some(source.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("the build should emit '{file_name}'"))
+    };
+    (output("index.mjs"), output("index.d.mts"))
+}
+
+/// Member names of the declared interface `name`, in declaration order.
+fn interface_members(declarations: &str, name: &str) -> Vec<String> {
+    let header = format!("export interface {name} {{");
+    declarations
+        .lines()
+        .skip_while(|line| *line != header)
+        .skip(1)
+        .take_while(|line| *line != "}")
+        .filter(|line| !line.trim_start().starts_with("/**"))
+        .map(|line| declared_key(line.trim()))
+        .collect()
+}
+
+/// Member lists of each variant of the declared union `name`, in variant order.
+fn union_variants(declarations: &str, name: &str) -> Vec<Vec<String>> {
+    let header = format!("export type {name} =");
+    declarations
+        .lines()
+        .skip_while(|line| *line != header)
+        .skip(1)
+        .take_while(|line| line.starts_with("    "))
+        .filter_map(|line| line.trim().strip_prefix("| { "))
+        .map(|members| {
+            members
+                .trim_end_matches(';')
+                .trim_end_matches(" }")
+                .split("; ")
+                .map(str::to_owned)
+                .collect()
+        })
+        .collect()
+}
+
+fn declared_key(member: &str) -> String {
+    member
+        .split(':')
+        .next()
+        .expect("a member has a key")
+        .trim_matches('"')
+        .to_owned()
+}
+
+/// Runs `runner` (an ES module importing `./index.mjs`) under Node and returns its stdout lines.
+fn run_with_node(module_source: &str, runner: &str) -> Vec<String> {
+    let directory = tempfile::tempdir().expect("temp dir should be created");
+    fs::write(directory.path().join("index.mjs"), module_source).expect("module should be written");
+    let runner_path = directory.path().join("runner.mjs");
+    fs::write(&runner_path, runner).expect("runner should be written");
+
+    let output = std::process::Command::new("node")
+        .arg(&runner_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("node should be on PATH to run JS module tests");
+    assert!(
+        output.status.success(),
+        "JS module failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+fn declarations_describe_functions_constants_and_types() {
+    let (_, declarations) = build_library("js_module_declarations_text");
+
+    for expected in [
+        "export type BsOption<T> = { tag: \"none\" } | { tag: \"some\"; value: T };",
+        "export declare const limits: number[];",
+        "export declare function make(x: number): Point;",
+        "export declare function parse(text: string): BsResult<number, Problem>;",
+        "export declare function maybe(x: number): BsOption<number>;",
+        "export declare function pair(): [number, string];",
+        "export declare function unbox(b: Box<number>): number;",
+        "export type Box<T> = { readonly [field: string]: unknown };",
+    ] {
+        assert!(
+            declarations.contains(expected),
+            "missing `{expected}` in:\n{declarations}"
+        );
+    }
+
+    assert_eq!(
+        union_variants(&declarations, "Shape"),
+        vec![
+            vec!["tag: 0".to_owned(), "\"radius\": number".to_owned()],
+            vec!["tag: 1".to_owned()],
+        ]
+    );
+}
+
+#[test]
+fn declared_shapes_match_emitted_runtime_values() {
+    let (module_source, declarations) = build_library("js_module_declarations_runtime");
+
+    // Each line is the sorted key list of one returned value, then the carrier tags.
+    let stdout = run_with_node(
+        &module_source,
+        "import { make, circle, nothing, parse, maybe, pair, limits } from \"./index.mjs\";\n\
+         const keys = (value) => Object.keys(value).sort().join(\",\");\n\
+         console.log(keys(make(1)));\n\
+         console.log(keys(parse(\"\").value));\n\
+         console.log(keys(circle(2.5)), circle(2.5).tag, keys(nothing()), nothing().tag);\n\
+         console.log(keys(parse(\"\")), parse(\"\").tag, parse(\"7\").tag, parse(\"7\").value);\n\
+         console.log(keys(maybe(2)), maybe(2).tag, keys(maybe(0)), maybe(0).tag);\n\
+         console.log(Array.isArray(pair()), pair().length, Array.isArray(limits));\n",
+    );
+
+    let sorted = |mut keys: Vec<String>| {
+        keys.sort();
+        keys.join(",")
+    };
+    assert_eq!(stdout[0], sorted(interface_members(&declarations, "Point")));
+    assert_eq!(
+        stdout[1],
+        sorted(interface_members(&declarations, "Problem"))
+    );
+
+    let shape = union_variants(&declarations, "Shape");
+    let variant_keys =
+        |variant: &[String]| sorted(variant.iter().map(|member| declared_key(member)).collect());
+    assert_eq!(
+        stdout[2],
+        format!(
+            "{} 0 {} 1",
+            variant_keys(&shape[0]),
+            variant_keys(&shape[1])
+        )
+    );
+
+    // `BsResult` and `BsOption` members, as declared in the carrier prelude.
+    assert_eq!(stdout[3], "tag,value err ok 42");
+    assert_eq!(stdout[4], "tag,value some tag none");
+    assert_eq!(stdout[5], "true 2 true");
+}
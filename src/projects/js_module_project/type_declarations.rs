@@ -0,0 +1,323 @@
+//! TypeScript declarations for JS module exports.
+//!
+//! WHAT: renders the `.d.mts` file published next to each emitted `.mjs`, describing the module's
+//! exported functions, constants, structs, choices and aliases from its public-interface draft.
+//! WHY: TS code calling into a Beanstalk module gets checked calls. Every declared shape is the
+//! JS backend's runtime carrier, not an idealised TS model, so a declaration that disagrees with
+//! the emitted code is a bug here.
+
+use crate::backends::js::{escape_js_string, is_js_reserved};
+use crate::compiler_frontend::canonical_type_identity::{
+    CanonicalBuiltinType, CanonicalTypeIdentity,
+};
+use crate::compiler_frontend::compiler_errors::CompilerError;
+use crate::compiler_frontend::defined_public_type_surface::{
+    PublicFieldTypeSlot, PublicGenericParameterSurface, PublicParameterTypeSlot,
+    PublicReturnTypeSlot,
+};
+use crate::compiler_frontend::public_interface_draft::{
+    PublicChoiceSemantics, PublicDeclarationSemantics, PublicFunctionSemantics,
+    PublicInterfaceDraft, PublicStructSemantics,
+};
+use crate::compiler_frontend::semantic_identity::{OriginDeclarationId, OriginTypeId};
+use rustc_hash::FxHashMap;
+
+/// Emitted JS object key of each field of a lowered public struct, by Beanstalk field name.
+pub(crate) type StructFieldKeys = FxHashMap<OriginTypeId, FxHashMap<String, String>>;
+
+/// Runtime carrier types shared by every declaration file.
+///
+/// WHAT: options, fallible returns, fixed collections, maps and ranges as the JS runtime builds
+/// them (`backends/js/runtime`). Growable collections are plain arrays and need no helper.
+const CARRIER_DECLARATIONS: &str = "\
+// Generated by the Beanstalk JS module builder. Shapes mirror the JS runtime carriers.
+
+export type BsOption<T> = { tag: \"none\" } | { tag: \"some\"; value: T };
+export type BsResult<T, E> = { tag: \"ok\"; value: T } | { tag: \"err\"; value: E };
+export type BsFixedCollection<T> = {
+    __bst_kind: \"fixed_collection\";
+    items: T[];
+    fixedCapacity: number;
+};
+export type BsOrderedMap<K, V> = { __bst_kind: \"ordered_map\"; map: Map<K, V> };
+export type BsRange = { start: number; end: number };
+";
+
+/// Renders the declaration file of one module's exports in `export:` binding order.
+///
+/// WHAT: generic functions and traits are skipped because the module does not export them.
+/// Nominal types declared outside this module surface as `unknown`.
+pub(crate) fn render_type_declarations(
+    draft: &PublicInterfaceDraft,
+    struct_field_keys: &StructFieldKeys,
+) -> Result<String, CompilerError> {
+    let type_names = draft
+        .export_bindings
+        .iter()
+        .filter_map(|binding| match binding.origin() {
+            OriginDeclarationId::Type(origin) => Some((origin, binding.public_name())),
+            _ => None,
+        })
+        .collect::<FxHashMap<_, _>>();
+    let renderer = DeclarationRenderer { type_names };
+
+    let mut declarations = String::from(CARRIER_DECLARATIONS);
+    for binding in &draft.export_bindings {
+        let public_name = binding.public_name();
+        let semantics = draft
+            .declarations
+            .iter()
+            .find(|declaration| &declaration.origin == binding.origin())
+            .map(|declaration| &declaration.semantics)
+            .ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "TS declarations found no declaration record for export '{public_name}'."
+                ))
+            })?;
+
+        let declaration = match semantics {
+            PublicDeclarationSemantics::Function(function) => {
+                if function.generic_template.is_some() {
+                    continue;
+                }
+                renderer.function(public_name, function)
+            }
+            PublicDeclarationSemantics::Constant(constant) => format!(
+                "export declare const {public_name}: {};\n",
+                renderer.constant_type(&constant.type_identity)
+            ),
+            PublicDeclarationSemantics::Struct(structure) => {
+                let OriginDeclarationId::Type(origin) = binding.origin() else {
+                    return Err(CompilerError::compiler_error(format!(
+                        "TS declarations found struct semantics on non-type export '{public_name}'."
+                    )));
+                };
+                renderer.structure(public_name, structure, struct_field_keys.get(origin))?
+            }
+            PublicDeclarationSemantics::Choice(choice) => renderer.choice(public_name, choice),
+            PublicDeclarationSemantics::TransparentAlias(alias) => format!(
+                "export type {public_name} = {};\n",
+                renderer.ts_type(&alias.target_type_identity)
+            ),
+            PublicDeclarationSemantics::Trait(_) => continue,
+        };
+
+        declarations.push('\n');
+        declarations.push_str(&declaration);
+    }
+
+    Ok(declarations)
+}
+
+struct DeclarationRenderer<'a> {
+    /// Public name of every type this module exports.
+    type_names: FxHashMap<&'a OriginTypeId, &'a str>,
+}
+
+impl DeclarationRenderer<'_> {
+    fn function(&self, public_name: &str, function: &PublicFunctionSemantics) -> String {
+        let parameters = function
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(index, parameter)| self.parameter(index, parameter))
+            .collect::<Vec<_>>();
+
+        // Fallible functions return a result carrier around the normal returns.
+        let returns = match &function.error_return {
+            Some(error) => format!(
+                "BsResult<{}, {}>",
+                self.success_type(&function.returns, "undefined"),
+                self.ts_type(error)
+            ),
+            None => self.success_type(&function.returns, "void"),
+        };
+
+        format!(
+            "export declare function {public_name}({}): {returns};\n",
+            parameters.join(", ")
+        )
+    }
+
+    fn parameter(&self, index: usize, parameter: &PublicParameterTypeSlot) -> String {
+        let name = match &parameter.name {
+            Some(name) if is_js_reserved(name) => format!("_{name}"),
+            Some(name) => name.to_owned(),
+            None => format!("arg{index}"),
+        };
+        format!("{name}: {}", self.ts_type(&parameter.type_identity))
+    }
+
+    /// Unit is `unit_type`, one return is its value and several are the returned array.
+    fn success_type(&self, returns: &[PublicReturnTypeSlot], unit_type: &str) -> String {
+        match returns {
+            [] => unit_type.to_owned(),
+            [single] => self.ts_type(&single.type_identity),
+            multiple => {
+                let elements = multiple
+                    .iter()
+                    .map(|slot| self.ts_type(&slot.type_identity))
+                    .collect::<Vec<_>>();
+                format!("[{}]", elements.join(", "))
+            }
+        }
+    }
+
+    /// Generic structs are lowered once per instance, each with its own field keys, so only
+    /// non-generic structs can be described as interfaces.
+    fn structure(
+        &self,
+        public_name: &str,
+        structure: &PublicStructSemantics,
+        field_keys: Option<&FxHashMap<String, String>>,
+    ) -> Result<String, CompilerError> {
+        if !structure.generic_parameters.is_empty() {
+            return Ok(format!(
+                "// Field keys of `{public_name}` differ per instance, so its values are opaque.\n\
+                 export type {public_name}{} = {{ readonly [field: string]: unknown }};\n",
+                generic_parameter_list(&structure.generic_parameters)
+            ));
+        }
+
+        let field_keys = field_keys.ok_or_else(|| {
+            CompilerError::compiler_error(format!(
+                "TS declarations found no lowered struct for exported struct '{public_name}'."
+            ))
+        })?;
+
+        let mut interface = format!("export interface {public_name} {{\n");
+        for field in &structure.fields {
+            let js_key = field_keys.get(&field.name).ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "TS declarations found no emitted key for field '{}' of '{public_name}'.",
+                    field.name
+                ))
+            })?;
+            interface.push_str(&format!(
+                "    /** Beanstalk field `{}`. */\n    {js_key}: {};\n",
+                field.name,
+                self.ts_type(&field.type_identity)
+            ));
+        }
+        interface.push_str("}\n");
+        Ok(interface)
+    }
+
+    /// Choice carriers hold the variant index in `tag` and each payload field under its
+    /// Beanstalk name.
+    fn choice(&self, public_name: &str, choice: &PublicChoiceSemantics) -> String {
+        let mut union = format!(
+            "export type {public_name}{} =\n",
+            generic_parameter_list(&choice.generic_parameters)
+        );
+        for (index, variant) in choice.variants.iter().enumerate() {
+            let mut members = vec![format!("tag: {index}")];
+            members.extend(
+                variant
+                    .payload_fields
+                    .iter()
+                    .map(|field| self.payload_field(field)),
+            );
+            union.push_str(&format!(
+                "    /** {} */\n    | {{ {} }}",
+                variant.name,
+                members.join("; ")
+            ));
+            union.push_str(if index + 1 == choice.variants.len() {
+                ";\n"
+            } else {
+                "\n"
+            });
+        }
+        union
+    }
+
+    fn payload_field(&self, field: &PublicFieldTypeSlot) -> String {
+        format!(
+            "{}: {}",
+            escape_js_string(&field.name),
+            self.ts_type(&field.type_identity)
+        )
+    }
+
+    /// Exported constants are emitted as literals, so their collections are plain arrays even
+    /// when the Beanstalk type is fixed.
+    fn constant_type(&self, identity: &CanonicalTypeIdentity) -> String {
+        match identity {
+            CanonicalTypeIdentity::Collection(collection) => {
+                format!("{}[]", self.constant_type(collection.element()))
+            }
+            _ => self.ts_type(identity),
+        }
+    }
+
+    fn ts_type(&self, identity: &CanonicalTypeIdentity) -> String {
+        match identity {
+            CanonicalTypeIdentity::Builtin(builtin) => match builtin {
+                CanonicalBuiltinType::Bool => "boolean",
+                CanonicalBuiltinType::Int
+                | CanonicalBuiltinType::Float
+                | CanonicalBuiltinType::Decimal => "number",
+                CanonicalBuiltinType::String | CanonicalBuiltinType::Char => "string",
+                CanonicalBuiltinType::Range => "BsRange",
+                CanonicalBuiltinType::None => "undefined",
+            }
+            .to_owned(),
+            CanonicalTypeIdentity::SourceNominal(origin) => self.type_name(origin).to_owned(),
+            CanonicalTypeIdentity::GenericInstance(instance) => {
+                let Some(name) = self.type_names.get(instance.base()) else {
+                    return "unknown".to_owned();
+                };
+                let arguments = instance
+                    .arguments()
+                    .iter()
+                    .map(|argument| self.ts_type(argument))
+                    .collect::<Vec<_>>();
+                format!("{name}<{}>", arguments.join(", "))
+            }
+            CanonicalTypeIdentity::GenericParameter(parameter) => {
+                parameter.authored_name().to_owned()
+            }
+            CanonicalTypeIdentity::Collection(collection) => {
+                let element = self.ts_type(collection.element());
+                match collection.fixed_capacity() {
+                    Some(_) => format!("BsFixedCollection<{element}>"),
+                    None => format!("{element}[]"),
+                }
+            }
+            CanonicalTypeIdentity::OrderedMap(map) => format!(
+                "BsOrderedMap<{}, {}>",
+                self.ts_type(map.key()),
+                self.ts_type(map.value())
+            ),
+            CanonicalTypeIdentity::Option(inner) => format!("BsOption<{}>", self.ts_type(inner)),
+            CanonicalTypeIdentity::FallibleCarrier(carrier) => format!(
+                "BsResult<{}, {}>",
+                self.ts_type(carrier.success()),
+                self.ts_type(carrier.error())
+            ),
+            // Host handles pass through JS untouched and have no declared shape.
+            CanonicalTypeIdentity::ExternalOpaque(_) => "unknown".to_owned(),
+        }
+    }
+
+    /// Types exported by another module have no declaration in this file.
+    fn type_name(&self, origin: &OriginTypeId) -> &str {
+        self.type_names.get(origin).copied().unwrap_or("unknown")
+    }
+}
+
+fn generic_parameter_list(parameters: &[PublicGenericParameterSurface]) -> String {
+    if parameters.is_empty() {
+        return String::new();
+    }
+    let names = parameters
+        .iter()
+        .map(|parameter| parameter.identity.authored_name())
+        .collect::<Vec<_>>();
+    format!("<{}>", names.join(", "))
+}
+
+#[cfg(test)]
+#[path = "tests/type_declarations_tests.rs"]
+mod tests;
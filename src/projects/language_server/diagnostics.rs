@@ -0,0 +1,115 @@
+//! Compiler diagnostics to LSP `publishDiagnostics` conversion.
+//!
+//! WHAT: groups one check run's `CompilerMessages` by source file and renders each diagnostic
+//! into protocol form: range, severity, stable code, message plus guidance, and secondary labels
+//! as related information.
+//! WHY: editors publish diagnostics per document URI, while the compiler reports one ordered
+//! project-wide sequence. Rendering goes through the same payload renderer as the terminal so
+//! wording never drifts between `bean check` and the editor.
+
+use crate::compiler_frontend::compiler_errors::CompilerMessages;
+use crate::compiler_frontend::compiler_messages::CompilerDiagnostic;
+use crate::compiler_frontend::compiler_messages::render::terminal::diagnostic_label_message_text;
+use crate::compiler_frontend::compiler_messages::render::{
+    DiagnosticRenderContext, render_payload, resolve_source_file_path_in_roots,
+};
+use crate::projects::language_server::protocol::{
+    Diagnostic, DiagnosticRelatedInformation, Location, file_uri_from_path, lsp_severity,
+    range_from_source_location,
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const DIAGNOSTIC_SOURCE: &str = "beanstalk";
+
+/// Render every diagnostic in `messages`, grouped by the file that owns its primary location.
+///
+/// Diagnostics without a source file (config bootstrap or infrastructure failures) are attached
+/// to `fallback_file` so the user still sees them on the document that triggered the check.
+pub(super) fn diagnostics_by_file(
+    messages: &CompilerMessages,
+    source_roots: &[PathBuf],
+    fallback_file: &Path,
+) -> BTreeMap<PathBuf, Vec<Diagnostic>> {
+    let mut grouped: BTreeMap<PathBuf, Vec<Diagnostic>> = BTreeMap::new();
+
+    for diagnostic_index in messages.diagnostic_display_order() {
+        let diagnostic = &messages.diagnostic_slice()[diagnostic_index];
+        let context = messages.diagnostic_render_context(diagnostic_index);
+
+        let file = diagnostic_file(diagnostic, context, source_roots)
+            .unwrap_or_else(|| fallback_file.to_owned());
+        grouped
+            .entry(file)
+            .or_default()
+            .push(render_diagnostic(diagnostic, context, source_roots));
+    }
+
+    grouped
+}
+
+fn diagnostic_file(
+    diagnostic: &CompilerDiagnostic,
+    context: DiagnosticRenderContext<'_>,
+    source_roots: &[PathBuf],
+) -> Option<PathBuf> {
+    if diagnostic.primary_location.scope.as_components().is_empty() {
+        return None;
+    }
+
+    Some(resolve_source_file_path_in_roots(
+        &diagnostic.primary_location.scope,
+        context.string_table,
+        source_roots,
+    ))
+}
+
+fn render_diagnostic(
+    diagnostic: &CompilerDiagnostic,
+    context: DiagnosticRenderContext<'_>,
+    source_roots: &[PathBuf],
+) -> Diagnostic {
+    let descriptor = diagnostic.kind.descriptor();
+    let rendered_payload = render_payload(&diagnostic.payload, context);
+
+    let mut message = if rendered_payload.message.is_empty() {
+        descriptor.title.to_owned()
+    } else {
+        rendered_payload.message
+    };
+    for guidance in &rendered_payload.guidance {
+        message.push_str("\nHint: ");
+        message.push_str(guidance);
+    }
+
+    let related_information = diagnostic
+        .labels
+        .iter()
+        .filter(|label| !label.location.scope.as_components().is_empty())
+        .filter_map(|label| {
+            let label_message = label.message.as_ref()?;
+            let label_file = resolve_source_file_path_in_roots(
+                &label.location.scope,
+                context.string_table,
+                source_roots,
+            );
+
+            Some(DiagnosticRelatedInformation {
+                location: Location {
+                    uri: file_uri_from_path(&label_file),
+                    range: range_from_source_location(&label.location),
+                },
+                message: diagnostic_label_message_text(label_message, context),
+            })
+        })
+        .collect();
+
+    Diagnostic {
+        range: range_from_source_location(&diagnostic.primary_location),
+        severity: lsp_severity(diagnostic.severity),
+        code: descriptor.code,
+        source: DIAGNOSTIC_SOURCE,
+        message,
+        related_information,
+    }
+}
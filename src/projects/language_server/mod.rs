@@ -0,0 +1,17 @@
+//! `bean lsp` language server entry point and module map.
+//!
+//! WHAT: exposes `run_language_server`, a Language Server Protocol server over stdio.
+//! WHY: editors need diagnostics, hover types, go-to-definition and document outlines from the
+//! same compiler stages the CLI runs. The implementation is split by concern: `transport` owns
+//! message framing, `protocol` owns LSP shapes and position conversion, `server` owns session
+//! state and dispatch, `diagnostics` renders check results, `symbol_index` reads header symbol
+//! tables for navigation, and `semantic_index` renders resolved HIR types for hover.
+
+mod diagnostics;
+mod protocol;
+mod semantic_index;
+mod server;
+mod symbol_index;
+mod transport;
+
+pub use server::run_language_server;
@@ -0,0 +1,367 @@
+//! Language Server Protocol message shapes used by `bean lsp`.
+//!
+//! WHAT: serde models for the LSP subset the server implements, plus conversions between
+//! compiler source locations, file URIs and protocol ranges.
+//! WHY: the compiler stores 1-based inclusive columns on interned paths while LSP uses 0-based
+//! half-open ranges on `file://` URIs, counted in UTF-16 code units unless the client agrees to
+//! another encoding. Keeping every conversion here stops off-by-one drift between diagnostics,
+//! hover and navigation.
+
+use crate::compiler_frontend::compiler_messages::DiagnosticSeverity;
+use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+// ------------------------
+//  Positions and ranges
+// ------------------------
+
+/// Zero-based line and character offset.
+///
+/// Inside the server characters are counted in Unicode scalar values, matching tokenizer
+/// columns. `PositionEncoding` converts them at the protocol boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(super) struct Position {
+    pub(super) line: u32,
+    pub(super) character: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct Range {
+    pub(super) start: Position,
+    pub(super) end: Position,
+}
+
+impl Range {
+    /// Whether a cursor position touches this range.
+    ///
+    /// The end is inclusive so a cursor placed directly after an identifier still resolves it.
+    pub(super) fn touches(&self, position: Position) -> bool {
+        self.start <= position && position <= self.end
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct Location {
+    pub(super) uri: String,
+    pub(super) range: Range,
+}
+
+/// Convert a compiler source location into an LSP range.
+///
+/// WHAT: token columns start at 1 with an inclusive end column, so the start shifts down by one
+/// and the end column becomes the exclusive end.
+pub(super) fn range_from_source_location(location: &SourceLocation) -> Range {
+    let start = Position {
+        line: non_negative(location.start_pos.line_number),
+        character: non_negative(location.start_pos.char_column.saturating_sub(1)),
+    };
+    let end = Position {
+        line: non_negative(location.end_pos.line_number),
+        character: non_negative(location.end_pos.char_column),
+    };
+
+    Range {
+        start,
+        end: end.max(start),
+    }
+}
+
+fn non_negative(value: i32) -> u32 {
+    value.max(0) as u32
+}
+
+/// How the client counts `character` offsets within a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum PositionEncoding {
+    /// UTF-16 code units, the protocol default every client supports.
+    #[default]
+    Utf16,
+    /// Unicode scalar values, identical to the server's own columns.
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Pick the encoding for a session from the client's `initialize` parameters.
+    ///
+    /// `utf-32` is preferred when offered because it needs no conversion.
+    pub(super) fn negotiate(initialize_params: &Value) -> Self {
+        let offers_utf32 = initialize_params
+            .pointer("/capabilities/general/positionEncodings")
+            .and_then(Value::as_array)
+            .is_some_and(|encodings| encodings.iter().any(|encoding| encoding == "utf-32"));
+
+        if offers_utf32 {
+            Self::Utf32
+        } else {
+            Self::Utf16
+        }
+    }
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Utf16 => "utf-16",
+            Self::Utf32 => "utf-32",
+        }
+    }
+
+    /// Convert a server position on `text` into this encoding.
+    ///
+    /// Offsets past the end of the line keep their excess so clamping stays with the client.
+    pub(super) fn position_to_client(self, text: &str, position: Position) -> Position {
+        if self == Self::Utf32 {
+            return position;
+        }
+
+        let line = line_text(text, position.line);
+        let mut scalars = 0u32;
+        let mut units = 0u32;
+        for character in line.chars() {
+            if scalars == position.character {
+                break;
+            }
+            scalars += 1;
+            units += character.len_utf16() as u32;
+        }
+
+        Position {
+            line: position.line,
+            character: units + (position.character - scalars),
+        }
+    }
+
+    /// Convert a client position on `text` in this encoding into a server position.
+    ///
+    /// An offset that splits a surrogate pair resolves to the character it falls inside.
+    pub(super) fn position_from_client(self, text: &str, position: Position) -> Position {
+        if self == Self::Utf32 {
+            return position;
+        }
+
+        let line = line_text(text, position.line);
+        let mut scalars = 0u32;
+        let mut units = 0u32;
+        for character in line.chars() {
+            let next_units = units + character.len_utf16() as u32;
+            if next_units > position.character {
+                break;
+            }
+            scalars += 1;
+            units = next_units;
+        }
+
+        let past_line_end = if scalars == line.chars().count() as u32 {
+            position.character - units
+        } else {
+            0
+        };
+
+        Position {
+            line: position.line,
+            character: scalars + past_line_end,
+        }
+    }
+
+    pub(super) fn range_to_client(self, text: &str, range: Range) -> Range {
+        Range {
+            start: self.position_to_client(text, range.start),
+            end: self.position_to_client(text, range.end),
+        }
+    }
+}
+
+/// The text of one zero-based line, without its line terminator.
+fn line_text(text: &str, line: u32) -> &str {
+    let line = text.split('\n').nth(line as usize).unwrap_or_default();
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+// ------------------------
+//  File URIs
+// ------------------------
+
+/// Build a `file://` URI for a filesystem path, percent-encoding reserved bytes.
+pub(super) fn file_uri_from_path(path: &Path) -> String {
+    let mut path_text = path.to_string_lossy().replace('\\', "/");
+    if !path_text.starts_with('/') {
+        path_text.insert(0, '/');
+    }
+
+    let mut uri = String::from("file://");
+    for byte in path_text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'/' | b'-' | b'_' | b'.' | b'~' | b':') {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    uri
+}
+
+/// Resolve a `file://` URI back to a filesystem path.
+///
+/// Returns `None` for non-file schemes or malformed percent escapes.
+pub(super) fn path_from_file_uri(uri: &str) -> Option<PathBuf> {
+    let without_scheme = uri.strip_prefix("file://")?;
+    let path_part = without_scheme
+        .strip_prefix("localhost")
+        .unwrap_or(without_scheme);
+
+    let decoded = percent_decode(path_part)?;
+
+    // `file:///C:/project` carries a leading slash before the Windows drive letter.
+    if cfg!(windows) && decoded.len() > 2 && decoded.as_bytes()[2] == b':' {
+        return Some(PathBuf::from(&decoded[1..]));
+    }
+
+    Some(PathBuf::from(decoded))
+}
+
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0usize;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = text.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+// ------------------------
+//  Outgoing payloads
+// ------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Diagnostic {
+    pub(super) range: Range,
+    pub(super) severity: u8,
+    pub(super) code: &'static str,
+    pub(super) source: &'static str,
+    pub(super) message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(super) related_information: Vec<DiagnosticRelatedInformation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct DiagnosticRelatedInformation {
+    pub(super) location: Location,
+    pub(super) message: String,
+}
+
+/// Map compiler severities onto LSP `DiagnosticSeverity` numbers.
+pub(super) fn lsp_severity(severity: DiagnosticSeverity) -> u8 {
+    match severity {
+        DiagnosticSeverity::Error => 1,
+        DiagnosticSeverity::Warning => 2,
+        DiagnosticSeverity::Note => 3,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct PublishDiagnosticsParams {
+    pub(super) uri: String,
+    pub(super) diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct Hover {
+    pub(super) contents: MarkupContent,
+    pub(super) range: Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(super) struct MarkupContent {
+    pub(super) kind: &'static str,
+    pub(super) value: String,
+}
+
+impl MarkupContent {
+    /// Wrap a Beanstalk snippet in a fenced markdown code block.
+    pub(super) fn beanstalk_code(snippet: &str) -> Self {
+        Self {
+            kind: "markdown",
+            value: format!("```beanstalk\n{snippet}\n```"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DocumentSymbol {
+    pub(super) name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) detail: Option<String>,
+    pub(super) kind: u8,
+    pub(super) range: Range,
+    pub(super) selection_range: Range,
+}
+
+// LSP `SymbolKind` numbers used for top-level Beanstalk declarations.
+pub(super) const SYMBOL_KIND_ENUM: u8 = 10;
+pub(super) const SYMBOL_KIND_INTERFACE: u8 = 11;
+pub(super) const SYMBOL_KIND_FUNCTION: u8 = 12;
+pub(super) const SYMBOL_KIND_CONSTANT: u8 = 14;
+pub(super) const SYMBOL_KIND_STRUCT: u8 = 23;
+pub(super) const SYMBOL_KIND_TYPE_PARAMETER: u8 = 26;
+
+// ------------------------
+//  Incoming parameters
+// ------------------------
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct TextDocumentIdentifier {
+    pub(super) uri: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct TextDocumentItem {
+    pub(super) uri: String,
+    pub(super) text: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DidOpenTextDocumentParams {
+    pub(super) text_document: TextDocumentItem,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct TextDocumentContentChangeEvent {
+    pub(super) text: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DidChangeTextDocumentParams {
+    pub(super) text_document: TextDocumentIdentifier,
+    pub(super) content_changes: Vec<TextDocumentContentChangeEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TextDocumentParams {
+    pub(super) text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TextDocumentPositionParams {
+    pub(super) text_document: TextDocumentIdentifier,
+    pub(super) position: Position,
+}
+
+#[cfg(test)]
+#[path = "tests/protocol_tests.rs"]
+mod tests;
@@ -0,0 +1,185 @@
+//! Typed binding index built from one successful frontend check.
+//!
+//! WHAT: walks each module's HIR once and records every user-declared local and function with
+//! its declaration range and a rendered Beanstalk signature using the resolved `TypeId`.
+//! WHY: hover and local go-to-definition must show the types the compiler actually inferred.
+//! Rendering eagerly means the server does not keep whole HIR modules alive between requests,
+//! and the last good index stays usable while a later edit has errors.
+
+use crate::build_system::build::Module;
+use crate::compiler_frontend::compiler_messages::render::resolve_source_file_path_in_roots;
+use crate::compiler_frontend::datatypes::display::display_type;
+use crate::compiler_frontend::datatypes::ids::builtin_type_ids;
+use crate::compiler_frontend::hir::blocks::HirLocal;
+use crate::compiler_frontend::hir::functions::HirFunctionOrigin;
+use crate::compiler_frontend::hir::hir_side_table::{HirLocalOriginKind, HirLocation};
+use crate::compiler_frontend::hir::ids::LocalId;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::projects::language_server::protocol::{Position, Range, range_from_source_location};
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BindingKind {
+    Local,
+    Function,
+}
+
+/// One named, typed declaration recovered from HIR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SemanticBinding {
+    pub(super) name: String,
+    pub(super) kind: BindingKind,
+    pub(super) file: PathBuf,
+    pub(super) declaration: Range,
+    /// Beanstalk-style signature, e.g. `count ~Int` or `add |a Int, b Int| -> Int`.
+    pub(super) signature: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct SemanticIndex {
+    bindings: Vec<SemanticBinding>,
+}
+
+impl SemanticIndex {
+    pub(super) fn from_modules(
+        modules: &[Module],
+        string_table: &StringTable,
+        source_roots: &[PathBuf],
+    ) -> Self {
+        let mut bindings = Vec::new();
+
+        for module in modules {
+            collect_module_bindings(module, string_table, source_roots, &mut bindings);
+        }
+
+        Self { bindings }
+    }
+
+    /// Resolve `name` used at `position` in `file`.
+    ///
+    /// WHAT: prefers the closest local declared on or before the cursor line in the same file,
+    /// then a function with that name, preferring one declared in the same file.
+    /// WHY: the AST forbids shadowing, so the nearest preceding declaration is the binding in
+    /// scope for every position the check accepted. Lines are compared rather than columns
+    /// because local declaration spans start at the binding operator, after the name.
+    pub(super) fn resolve(
+        &self,
+        file: &Path,
+        name: &str,
+        position: Position,
+    ) -> Option<&SemanticBinding> {
+        let local = self
+            .bindings
+            .iter()
+            .filter(|binding| {
+                binding.kind == BindingKind::Local
+                    && binding.name == name
+                    && binding.file == file
+                    && binding.declaration.start.line <= position.line
+            })
+            .max_by_key(|binding| binding.declaration.start);
+        if local.is_some() {
+            return local;
+        }
+
+        let mut functions = self
+            .bindings
+            .iter()
+            .filter(|binding| binding.kind == BindingKind::Function && binding.name == name);
+        let first_function = functions.next()?;
+        if first_function.file == file {
+            return Some(first_function);
+        }
+
+        functions
+            .find(|binding| binding.file == file)
+            .or(Some(first_function))
+    }
+}
+
+fn collect_module_bindings(
+    module: &Module,
+    string_table: &StringTable,
+    source_roots: &[PathBuf],
+    bindings: &mut Vec<SemanticBinding>,
+) {
+    let hir = &module.executable.hir;
+    let type_environment = &module.executable.type_environment;
+    let side_table = &hir.side_table;
+
+    let locals_by_id: FxHashMap<LocalId, &HirLocal> = hir
+        .blocks
+        .iter()
+        .flat_map(|block| block.locals.iter())
+        .map(|local| (local.id, local))
+        .collect();
+
+    let binding_text = |local: &HirLocal| -> Option<String> {
+        let name = side_table.resolve_local_name(local.id, string_table)?;
+        let mutability = if local.mutable { "~" } else { "" };
+        let type_name = display_type(local.ty, type_environment, string_table);
+        Some(format!("{name} {mutability}{type_name}"))
+    };
+
+    for local in locals_by_id.values() {
+        if side_table.local_origin_kind(local.id) != Some(HirLocalOriginKind::User) {
+            continue;
+        }
+        let (Some(name), Some(location)) = (
+            side_table.resolve_local_name(local.id, string_table),
+            local.source_info.as_ref(),
+        ) else {
+            continue;
+        };
+        let Some(signature) = binding_text(local) else {
+            continue;
+        };
+
+        bindings.push(SemanticBinding {
+            name: name.to_owned(),
+            kind: BindingKind::Local,
+            file: resolve_source_file_path_in_roots(&location.scope, string_table, source_roots),
+            declaration: range_from_source_location(location),
+            signature,
+        });
+    }
+
+    for function in &hir.functions {
+        if hir.function_origins.get(&function.id) == Some(&HirFunctionOrigin::EntryStart) {
+            continue;
+        }
+        let (Some(name), Some(location)) = (
+            side_table.resolve_function_name(function.id, string_table),
+            side_table.ast_location_for_hir(HirLocation::Function(function.id)),
+        ) else {
+            continue;
+        };
+
+        let parameters = function
+            .params
+            .iter()
+            .filter_map(|parameter| locals_by_id.get(parameter).copied())
+            .filter_map(binding_text)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut signature = format!("{name} |{parameters}|");
+        if function.return_type != builtin_type_ids::NONE {
+            signature.push_str(" -> ");
+            signature.push_str(&display_type(
+                function.return_type,
+                type_environment,
+                string_table,
+            ));
+        }
+
+        bindings.push(SemanticBinding {
+            name: name.to_owned(),
+            kind: BindingKind::Function,
+            file: resolve_source_file_path_in_roots(&location.scope, string_table, source_roots),
+            declaration: range_from_source_location(location),
+            signature,
+        });
+    }
+}
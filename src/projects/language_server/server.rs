@@ -0,0 +1,594 @@
+//! Language server session state and request dispatch.
+//!
+//! WHAT: owns open document text, the last successful semantic index and the set of files with
+//! published diagnostics, and answers the LSP lifecycle, document sync, hover, definition and
+//! document-symbol messages. Edits are rechecked once typing pauses for `CHANGE_DEBOUNCE`.
+//! WHY: the server is a thin editor-facing shell over existing compiler stages. Diagnostics come
+//! from the same frontend check as `bean check`; navigation comes from header symbol tables;
+//! hover comes from resolved HIR types. Message handling returns outgoing messages instead of
+//! writing them so tests can drive a whole session from memory.
+
+use crate::build_system::build::BackendBuilder;
+use crate::build_system::create_project_modules::source_loading::with_source_overlay;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::projects::check::{FrontendCheck, check_project_frontend};
+use crate::projects::html_project::html_project_builder::HtmlProjectBuilder;
+use crate::projects::language_server::diagnostics::diagnostics_by_file;
+use crate::projects::language_server::protocol::{
+    Diagnostic, DidChangeTextDocumentParams, DidOpenTextDocumentParams, Hover, Location,
+    MarkupContent, Position, PositionEncoding, PublishDiagnosticsParams, Range, TextDocumentParams,
+    TextDocumentPositionParams, file_uri_from_path, path_from_file_uri,
+};
+use crate::projects::language_server::semantic_index::{BindingKind, SemanticIndex};
+use crate::projects::language_server::symbol_index::{
+    DocumentAnalysis, analyze_document, find_declaration, index_workspace,
+};
+use crate::projects::language_server::transport::{Frame, read_message, write_message};
+use crate::projects::settings::CONFIG_FILE_NAME;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+// JSON-RPC error codes from the LSP specification.
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+const INVALID_REQUEST: i64 = -32600;
+
+/// Quiet period after the last `didChange` before the edited project is rechecked.
+const CHANGE_DEBOUNCE: Duration = Duration::from_millis(250);
+
+type IncomingFrame = io::Result<Option<Frame>>;
+
+/// How the message loop ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SessionEnd {
+    /// `exit` arrived after `shutdown`.
+    Clean,
+    /// The client exited or closed the stream without requesting shutdown first.
+    WithoutShutdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionState {
+    AwaitingInitialize,
+    Running,
+    ShuttingDown,
+}
+
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+/// Run the language server over stdin/stdout until the client exits.
+pub fn run_language_server() -> Result<(), String> {
+    // The reader runs on its own thread, so it takes the `Send` handle rather than a lock.
+    let mut reader = BufReader::new(io::stdin());
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+
+    let mut server = LanguageServer::new();
+    match server.serve(&mut reader, &mut writer) {
+        Ok(SessionEnd::Clean) => Ok(()),
+        Ok(SessionEnd::WithoutShutdown) => {
+            Err(String::from("Client exited without a shutdown request."))
+        }
+        Err(error) => Err(format!("Language server transport failed: {error}")),
+    }
+}
+
+pub(super) struct LanguageServer {
+    state: SessionState,
+    position_encoding: PositionEncoding,
+    documents: HashMap<PathBuf, String>,
+    style_directives: StyleDirectiveRegistry,
+    semantic_index: SemanticIndex,
+    published_files: BTreeSet<PathBuf>,
+    /// Document edited since the last check, waiting for typing to pause.
+    pending_check: Option<PathBuf>,
+}
+
+impl LanguageServer {
+    pub(super) fn new() -> Self {
+        // Match the tokenizer surface of the HTML builder that `bean check` compiles against so
+        // builder-provided style directives do not read as unknown in the editor.
+        let builder_directives = HtmlProjectBuilder::new().frontend_style_directives();
+        let style_directives = StyleDirectiveRegistry::merged(&builder_directives)
+            .unwrap_or_else(|_| StyleDirectiveRegistry::built_ins());
+
+        Self {
+            state: SessionState::AwaitingInitialize,
+            position_encoding: PositionEncoding::default(),
+            documents: HashMap::new(),
+            style_directives,
+            semantic_index: SemanticIndex::default(),
+            published_files: BTreeSet::new(),
+            pending_check: None,
+        }
+    }
+
+    /// Process framed messages until `exit` or end of input.
+    ///
+    /// Frames are read on a scoped thread so the loop can tell when the client has gone quiet
+    /// and run the check deferred by `didChange`.
+    pub(super) fn serve(
+        &mut self,
+        reader: &mut (impl BufRead + Send),
+        writer: &mut impl Write,
+    ) -> io::Result<SessionEnd> {
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+            scope.spawn(move || forward_frames(reader, &sender));
+            self.dispatch_frames(&receiver, writer)
+        })
+    }
+
+    fn dispatch_frames(
+        &mut self,
+        receiver: &Receiver<IncomingFrame>,
+        writer: &mut impl Write,
+    ) -> io::Result<SessionEnd> {
+        loop {
+            let received = if self.pending_check.is_some() {
+                match receiver.recv_timeout(CHANGE_DEBOUNCE) {
+                    Ok(frame) => Some(frame),
+                    Err(RecvTimeoutError::Timeout) => {
+                        for outgoing in self.run_pending_check() {
+                            write_message(writer, &outgoing)?;
+                        }
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => None,
+                }
+            } else {
+                receiver.recv().ok()
+            };
+
+            let Some(frame) = received.transpose()?.flatten() else {
+                return Ok(self.session_end());
+            };
+
+            let message = match frame {
+                Frame::Message(message) => message,
+                Frame::Unparseable(reason) => {
+                    // The request id is unreadable, so the error answers with a null id.
+                    write_message(writer, &parse_error_response(reason))?;
+                    continue;
+                }
+            };
+
+            if is_exit(&message) {
+                return Ok(self.session_end());
+            }
+
+            for outgoing in self.handle_message(&message) {
+                write_message(writer, &outgoing)?;
+            }
+        }
+    }
+
+    fn session_end(&self) -> SessionEnd {
+        if self.state == SessionState::ShuttingDown {
+            SessionEnd::Clean
+        } else {
+            SessionEnd::WithoutShutdown
+        }
+    }
+
+    /// Handle one decoded message and return every message to send back, in order.
+    pub(super) fn handle_message(&mut self, message: &Value) -> Vec<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Responses to server-initiated requests; the server sends none.
+            return Vec::new();
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match message.get("id") {
+            Some(id) => {
+                let response = match self.handle_request(method, params) {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err(error) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": error.code, "message": error.message },
+                    }),
+                };
+                vec![response]
+            }
+            None => self.handle_notification(method, params),
+        }
+    }
+
+    // ------------------------
+    //  Requests
+    // ------------------------
+
+    fn handle_request(&mut self, method: &str, params: Value) -> Result<Value, ResponseError> {
+        match (self.state, method) {
+            (SessionState::AwaitingInitialize, "initialize") => {
+                self.state = SessionState::Running;
+                self.position_encoding = PositionEncoding::negotiate(&params);
+                Ok(initialize_result(self.position_encoding))
+            }
+            (SessionState::AwaitingInitialize, _) => Err(ResponseError {
+                code: SERVER_NOT_INITIALIZED,
+                message: String::from("Server has not been initialized."),
+            }),
+            (SessionState::ShuttingDown, _) => Err(ResponseError {
+                code: INVALID_REQUEST,
+                message: String::from("Server is shutting down."),
+            }),
+            (SessionState::Running, "shutdown") => {
+                self.state = SessionState::ShuttingDown;
+                Ok(Value::Null)
+            }
+            (SessionState::Running, "textDocument/hover") => {
+                let params: TextDocumentPositionParams = parse_params(params)?;
+                Ok(to_json(self.hover(&params)))
+            }
+            (SessionState::Running, "textDocument/definition") => {
+                let params: TextDocumentPositionParams = parse_params(params)?;
+                Ok(to_json(self.definition(&params)))
+            }
+            (SessionState::Running, "textDocument/documentSymbol") => {
+                let params: TextDocumentParams = parse_params(params)?;
+                let symbols = match document_path(&params.text_document.uri) {
+                    Some(path) => {
+                        let text = self.conversion_text(&path);
+                        let mut symbols = self.analyze(&path).document_symbols();
+                        for symbol in &mut symbols {
+                            symbol.range = self.range_to_client(text.as_deref(), symbol.range);
+                            symbol.selection_range =
+                                self.range_to_client(text.as_deref(), symbol.selection_range);
+                        }
+                        symbols
+                    }
+                    None => Vec::new(),
+                };
+                Ok(to_json(symbols))
+            }
+            (SessionState::Running, _) => Err(ResponseError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Unsupported request '{method}'."),
+            }),
+        }
+    }
+
+    fn hover(&self, params: &TextDocumentPositionParams) -> Option<Hover> {
+        let path = document_path(&params.text_document.uri)?;
+        let text = self.conversion_text(&path);
+        let position = self.position_from_client(text.as_deref(), params.position);
+        let analysis = self.analyze(&path);
+        let identifier = analysis.identifier_at(position)?;
+        let range = self.range_to_client(text.as_deref(), identifier.range);
+
+        if let Some(binding) = self
+            .semantic_index
+            .resolve(&path, &identifier.name, position)
+        {
+            return Some(Hover {
+                contents: MarkupContent::beanstalk_code(&binding.signature),
+                range,
+            });
+        }
+
+        // Declarations without a typed HIR binding (structs, choices, aliases, traits, or any
+        // symbol while the project has errors) still describe their declaration kind.
+        let declaration = analysis.declaration_named(&identifier.name)?;
+        Some(Hover {
+            contents: MarkupContent::beanstalk_code(&format!(
+                "{} {}",
+                declaration.kind.label(),
+                declaration.name
+            )),
+            range,
+        })
+    }
+
+    fn definition(&self, params: &TextDocumentPositionParams) -> Option<Location> {
+        let path = document_path(&params.text_document.uri)?;
+        let position =
+            self.position_from_client(self.conversion_text(&path).as_deref(), params.position);
+        let analysis = self.analyze(&path);
+        let identifier = analysis.identifier_at(position)?;
+
+        if let Some(binding) = self
+            .semantic_index
+            .resolve(&path, &identifier.name, position)
+            .filter(|binding| binding.kind == BindingKind::Local)
+        {
+            // Local declaration spans start at the binding operator; point at the name itself.
+            let name_range = analysis
+                .identifiers
+                .iter()
+                .filter(|token| {
+                    token.name == binding.name
+                        && token.range.start.line == binding.declaration.start.line
+                        && token.range.start <= binding.declaration.start
+                })
+                .map(|token| token.range)
+                .next_back()
+                .unwrap_or(binding.declaration);
+
+            return Some(self.location_to_client(&binding.file, name_range));
+        }
+
+        let workspace_root = project_directory(&path)
+            .or_else(|| path.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+        let workspace = index_workspace(&workspace_root, &self.documents, &self.style_directives);
+        let (file, range) = find_declaration(&workspace, &path, &identifier.name)?;
+
+        Some(self.location_to_client(&file, range))
+    }
+
+    // ------------------------
+    //  Position encoding
+    // ------------------------
+
+    /// Text needed to convert positions in `path`, or `None` when the client counts characters
+    /// the way the server does.
+    ///
+    /// Open documents are read from memory; other files from disk.
+    fn conversion_text(&self, path: &Path) -> Option<Cow<'_, str>> {
+        if self.position_encoding == PositionEncoding::Utf32 {
+            return None;
+        }
+
+        match self.documents.get(path) {
+            Some(text) => Some(Cow::Borrowed(text)),
+            None => fs::read_to_string(path).ok().map(Cow::Owned),
+        }
+    }
+
+    fn position_from_client(&self, text: Option<&str>, position: Position) -> Position {
+        text.map_or(position, |text| {
+            self.position_encoding.position_from_client(text, position)
+        })
+    }
+
+    fn range_to_client(&self, text: Option<&str>, range: Range) -> Range {
+        text.map_or(range, |text| {
+            self.position_encoding.range_to_client(text, range)
+        })
+    }
+
+    fn location_to_client(&self, file: &Path, range: Range) -> Location {
+        Location {
+            uri: file_uri_from_path(file),
+            range: self.range_to_client(self.conversion_text(file).as_deref(), range),
+        }
+    }
+
+    fn diagnostics_to_client(&self, file: &Path, diagnostics: &mut [Diagnostic]) {
+        let text = self.conversion_text(file);
+        for diagnostic in diagnostics {
+            diagnostic.range = self.range_to_client(text.as_deref(), diagnostic.range);
+            for related in &mut diagnostic.related_information {
+                if let Some(related_file) = path_from_file_uri(&related.location.uri) {
+                    related.location.range = self.range_to_client(
+                        self.conversion_text(&related_file).as_deref(),
+                        related.location.range,
+                    );
+                }
+            }
+        }
+    }
+
+    fn analyze(&self, path: &Path) -> DocumentAnalysis {
+        match self.documents.get(path) {
+            Some(text) => analyze_document(path, text, &self.style_directives),
+            None => match fs::read_to_string(path) {
+                Ok(text) => analyze_document(path, &text, &self.style_directives),
+                Err(_) => DocumentAnalysis::default(),
+            },
+        }
+    }
+
+    // ------------------------
+    //  Notifications
+    // ------------------------
+
+    fn handle_notification(&mut self, method: &str, params: Value) -> Vec<Value> {
+        if self.state != SessionState::Running {
+            return Vec::new();
+        }
+
+        match method {
+            "textDocument/didOpen" => {
+                let Ok(params) = serde_json::from_value::<DidOpenTextDocumentParams>(params) else {
+                    return Vec::new();
+                };
+                let Some(path) = document_path(&params.text_document.uri) else {
+                    return Vec::new();
+                };
+                self.documents
+                    .insert(path.to_owned(), params.text_document.text);
+                self.check_and_publish(&path)
+            }
+            "textDocument/didChange" => {
+                let Ok(params) = serde_json::from_value::<DidChangeTextDocumentParams>(params)
+                else {
+                    return Vec::new();
+                };
+                // Full document sync: the last change carries the whole current text.
+                if let (Some(path), Some(change)) = (
+                    document_path(&params.text_document.uri),
+                    params.content_changes.into_iter().last(),
+                ) {
+                    self.documents.insert(path.to_owned(), change.text);
+                    self.pending_check = Some(path);
+                }
+                Vec::new()
+            }
+            "textDocument/didSave" => {
+                let Ok(params) = serde_json::from_value::<TextDocumentParams>(params) else {
+                    return Vec::new();
+                };
+                match document_path(&params.text_document.uri) {
+                    Some(path) => self.check_and_publish(&path),
+                    None => Vec::new(),
+                }
+            }
+            "textDocument/didClose" => {
+                if let Ok(params) = serde_json::from_value::<TextDocumentParams>(params)
+                    && let Some(path) = document_path(&params.text_document.uri)
+                {
+                    self.documents.remove(&path);
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Run the check deferred by the last `didChange`, if it has not been superseded.
+    pub(super) fn run_pending_check(&mut self) -> Vec<Value> {
+        match self.pending_check.take() {
+            Some(path) => self.check_and_publish(&path),
+            None => Vec::new(),
+        }
+    }
+
+    /// Run the frontend check for the project owning `path` and publish its diagnostics.
+    ///
+    /// Open documents replace their files on disk for the check, so unsaved edits are
+    /// diagnosed. Files that had diagnostics in the previous run are cleared when they no
+    /// longer do.
+    fn check_and_publish(&mut self, path: &Path) -> Vec<Value> {
+        if self.pending_check.as_deref() == Some(path) {
+            self.pending_check = None;
+        }
+
+        let check_target = project_directory(path).unwrap_or_else(|| path.to_path_buf());
+        let FrontendCheck {
+            modules,
+            messages,
+            source_roots,
+        } = with_source_overlay(self.documents.clone(), || {
+            check_project_frontend(&check_target.to_string_lossy())
+        });
+
+        if !messages.has_errors() {
+            self.semantic_index =
+                SemanticIndex::from_modules(&modules, &messages.string_table, &source_roots);
+        }
+
+        let mut grouped = diagnostics_by_file(&messages, &source_roots, path);
+        for stale_file in &self.published_files {
+            grouped.entry(stale_file.to_owned()).or_default();
+        }
+
+        self.published_files = grouped
+            .iter()
+            .filter(|(_, diagnostics)| !diagnostics.is_empty())
+            .map(|(file, _)| file.to_owned())
+            .collect();
+
+        grouped
+            .into_iter()
+            .map(|(file, mut diagnostics)| {
+                self.diagnostics_to_client(&file, &mut diagnostics);
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": to_json(PublishDiagnosticsParams {
+                        uri: file_uri_from_path(&file),
+                        diagnostics,
+                    }),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Read frames into `sender` until the stream ends, fails or delivers `exit`.
+fn forward_frames(reader: &mut impl BufRead, sender: &Sender<IncomingFrame>) {
+    loop {
+        let frame = read_message(reader);
+        let keep_reading = match &frame {
+            Ok(Some(Frame::Message(message))) => !is_exit(message),
+            Ok(Some(Frame::Unparseable(_))) => true,
+            Ok(None) | Err(_) => false,
+        };
+
+        if sender.send(frame).is_err() || !keep_reading {
+            return;
+        }
+    }
+}
+
+fn is_exit(message: &Value) -> bool {
+    message.get("method").and_then(Value::as_str) == Some("exit")
+}
+
+fn parse_error_response(reason: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": Value::Null,
+        "error": { "code": PARSE_ERROR, "message": reason },
+    })
+}
+
+fn initialize_result(position_encoding: PositionEncoding) -> Value {
+    json!({
+        "capabilities": {
+            "positionEncoding": position_encoding.name(),
+            "textDocumentSync": {
+                "openClose": true,
+                "change": 1,
+                "save": { "includeText": false },
+            },
+            "hoverProvider": true,
+            "definitionProvider": true,
+            "documentSymbolProvider": true,
+        },
+        "serverInfo": {
+            "name": "bean",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    })
+}
+
+/// Canonical filesystem path for a document URI.
+///
+/// Compiler diagnostics and HIR locations resolve to canonical paths, so documents are keyed the
+/// same way. Unsaved files that do not exist yet keep their literal path.
+fn document_path(uri: &str) -> Option<PathBuf> {
+    let path = path_from_file_uri(uri)?;
+    Some(fs::canonicalize(&path).unwrap_or(path))
+}
+
+/// Nearest ancestor directory holding a project config file.
+///
+/// Files outside any project are checked as single-file programs.
+fn project_directory(file: &Path) -> Option<PathBuf> {
+    file.ancestors()
+        .skip(1)
+        .find(|directory| directory.join(CONFIG_FILE_NAME).is_file())
+        .map(Path::to_path_buf)
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, ResponseError> {
+    serde_json::from_value(params).map_err(|error| ResponseError {
+        code: INVALID_PARAMS,
+        message: format!("Invalid request parameters: {error}"),
+    })
+}
+
+fn to_json(value: impl serde::Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+#[path = "tests/server_tests.rs"]
+mod tests;
@@ -0,0 +1,381 @@
+//! Header-driven symbol index for editor navigation.
+//!
+//! WHAT: tokenizes one document, records its identifier tokens, and runs syntax-only header
+//! preparation to collect top-level declarations and file imports. A workspace index is the same
+//! analysis repeated for every `.bst` file under the project root.
+//! WHY: document symbols and go-to-definition only need the header stage's per-file symbol
+//! table. Running it per document keeps navigation available while a file has semantic errors
+//! that stop the full frontend, and it reads unsaved editor text instead of disk.
+
+use crate::compiler_frontend::headers::parse_file_headers::{
+    HeaderKind, HeaderParseOptions, prepare_file_from_tokens,
+};
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::compiler_frontend::tokenizer::lexer::tokenize;
+use crate::compiler_frontend::tokenizer::tokens::{TokenKind, TokenizerEntryMode};
+use crate::projects::language_server::protocol::{
+    DocumentSymbol, Position, Range, SYMBOL_KIND_CONSTANT, SYMBOL_KIND_ENUM, SYMBOL_KIND_FUNCTION,
+    SYMBOL_KIND_INTERFACE, SYMBOL_KIND_STRUCT, SYMBOL_KIND_TYPE_PARAMETER,
+    range_from_source_location,
+};
+use crate::projects::settings::BEANSTALK_FILE_EXTENSION;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ------------------------
+//  Document analysis
+// ------------------------
+
+/// Navigation facts for one source document.
+#[derive(Debug, Clone, Default)]
+pub(super) struct DocumentAnalysis {
+    pub(super) identifiers: Vec<IdentifierToken>,
+    pub(super) declarations: Vec<DeclarationSymbol>,
+    pub(super) imports: Vec<ImportedSymbol>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct IdentifierToken {
+    pub(super) name: String,
+    pub(super) range: Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct DeclarationSymbol {
+    pub(super) name: String,
+    pub(super) kind: DeclarationKind,
+    pub(super) exported: bool,
+    pub(super) name_range: Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DeclarationKind {
+    Function,
+    Constant,
+    Struct,
+    Choice,
+    TypeAlias,
+    Trait,
+}
+
+impl DeclarationKind {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            DeclarationKind::Function => "function",
+            DeclarationKind::Constant => "constant",
+            DeclarationKind::Struct => "struct",
+            DeclarationKind::Choice => "choice",
+            DeclarationKind::TypeAlias => "type alias",
+            DeclarationKind::Trait => "trait",
+        }
+    }
+
+    fn lsp_symbol_kind(self) -> u8 {
+        match self {
+            DeclarationKind::Function => SYMBOL_KIND_FUNCTION,
+            DeclarationKind::Constant => SYMBOL_KIND_CONSTANT,
+            DeclarationKind::Struct => SYMBOL_KIND_STRUCT,
+            DeclarationKind::Choice => SYMBOL_KIND_ENUM,
+            DeclarationKind::TypeAlias => SYMBOL_KIND_TYPE_PARAMETER,
+            DeclarationKind::Trait => SYMBOL_KIND_INTERFACE,
+        }
+    }
+}
+
+/// One name brought into file scope by an `import` clause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ImportedSymbol {
+    /// Name visible in the importing file (the alias when one is given).
+    pub(super) local_name: String,
+    /// Declaration name in the provider.
+    pub(super) imported_name: String,
+    /// Provider path components before the imported name, without `@` or `.` segments.
+    pub(super) provider_components: Vec<String>,
+}
+
+impl DocumentAnalysis {
+    /// The identifier token under `position`, if any.
+    pub(super) fn identifier_at(&self, position: Position) -> Option<&IdentifierToken> {
+        self.identifiers
+            .iter()
+            .find(|identifier| identifier.range.touches(position))
+    }
+
+    pub(super) fn declaration_named(&self, name: &str) -> Option<&DeclarationSymbol> {
+        self.declarations
+            .iter()
+            .find(|declaration| declaration.name == name)
+    }
+
+    pub(super) fn document_symbols(&self) -> Vec<DocumentSymbol> {
+        self.declarations
+            .iter()
+            .map(|declaration| DocumentSymbol {
+                name: declaration.name.to_owned(),
+                detail: Some(if declaration.exported {
+                    format!("exported {}", declaration.kind.label())
+                } else {
+                    declaration.kind.label().to_owned()
+                }),
+                kind: declaration.kind.lsp_symbol_kind(),
+                range: declaration.name_range,
+                selection_range: declaration.name_range,
+            })
+            .collect()
+    }
+}
+
+/// Tokenize and header-parse one document.
+///
+/// Tokenizer failures yield an empty analysis. Header failures keep the identifier tokens so
+/// hover can still resolve names while the file is mid-edit.
+pub(super) fn analyze_document(
+    path: &Path,
+    source: &str,
+    style_directives: &StyleDirectiveRegistry,
+) -> DocumentAnalysis {
+    let mut string_table = StringTable::new();
+    let Ok(interned_path) = InternedPath::try_from_filesystem_path(path, &mut string_table) else {
+        return DocumentAnalysis::default();
+    };
+
+    let Ok(file_tokens) = tokenize(
+        source,
+        &interned_path,
+        TokenizerEntryMode::SourceFile,
+        style_directives,
+        &mut string_table,
+        None,
+    ) else {
+        return DocumentAnalysis::default();
+    };
+
+    let identifiers = file_tokens
+        .tokens
+        .iter()
+        .filter_map(|token| match token.kind {
+            TokenKind::Symbol(name) => Some(IdentifierToken {
+                name: string_table.resolve(name).to_owned(),
+                range: range_from_source_location(&token.location),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    let mut analysis = DocumentAnalysis {
+        identifiers,
+        ..DocumentAnalysis::default()
+    };
+
+    // Syntax-only preparation: no project path resolver, so every file parses as a normal
+    // source file and no provider binding runs.
+    let Ok(prepared) = prepare_file_from_tokens(
+        file_tokens,
+        path,
+        &HeaderParseOptions::default(),
+        &mut string_table,
+        0,
+        0,
+    ) else {
+        return analysis;
+    };
+
+    for header in &prepared.headers {
+        let kind = match header.kind {
+            HeaderKind::Function { .. } => DeclarationKind::Function,
+            HeaderKind::Constant { .. } => DeclarationKind::Constant,
+            HeaderKind::Struct { .. } => DeclarationKind::Struct,
+            HeaderKind::Choice { .. } => DeclarationKind::Choice,
+            HeaderKind::TypeAlias { .. } => DeclarationKind::TypeAlias,
+            HeaderKind::Trait { .. } => DeclarationKind::Trait,
+            _ => continue,
+        };
+        let Some(name) = header.tokens.src_path.name_str(&string_table) else {
+            continue;
+        };
+
+        analysis.declarations.push(DeclarationSymbol {
+            name: name.to_owned(),
+            kind,
+            exported: header.export_mode.is_public(),
+            name_range: range_from_source_location(&header.name_location),
+        });
+    }
+
+    for file_import in &prepared.file_imports {
+        let components = file_import
+            .provider
+            .path
+            .as_components()
+            .iter()
+            .map(|component| string_table.resolve(*component).to_owned())
+            .collect::<Vec<_>>();
+        let Some((imported_name, provider_components)) = components.split_last() else {
+            continue;
+        };
+
+        let local_name = match file_import.alias {
+            Some(alias) => string_table.resolve(alias).to_owned(),
+            None => imported_name.to_owned(),
+        };
+
+        analysis.imports.push(ImportedSymbol {
+            local_name,
+            imported_name: imported_name.to_owned(),
+            provider_components: provider_components
+                .iter()
+                .map(|component| component.trim_start_matches('@').to_owned())
+                .filter(|component| !component.is_empty() && component != "." && component != "..")
+                .collect(),
+        });
+    }
+
+    analysis
+}
+
+// ------------------------
+//  Workspace index
+// ------------------------
+
+/// Analyze every Beanstalk file under `root`, preferring open editor text over disk contents.
+///
+/// Hidden directories are skipped so VCS metadata and editor caches stay out of the index.
+pub(super) fn index_workspace(
+    root: &Path,
+    open_documents: &HashMap<PathBuf, String>,
+    style_directives: &StyleDirectiveRegistry,
+) -> BTreeMap<PathBuf, DocumentAnalysis> {
+    let mut source_files = Vec::new();
+    collect_source_files(root, &mut source_files);
+
+    let mut index = BTreeMap::new();
+    for file in source_files {
+        let analysis = match open_documents.get(&file) {
+            Some(text) => analyze_document(&file, text, style_directives),
+            None => match fs::read_to_string(&file) {
+                Ok(text) => analyze_document(&file, &text, style_directives),
+                Err(_) => continue,
+            },
+        };
+        index.insert(file, analysis);
+    }
+
+    for (path, text) in open_documents {
+        if !index.contains_key(path) {
+            index.insert(
+                path.to_owned(),
+                analyze_document(path, text, style_directives),
+            );
+        }
+    }
+
+    index
+}
+
+fn collect_source_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_source_files(&path, files);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == BEANSTALK_FILE_EXTENSION)
+        {
+            files.push(path);
+        }
+    }
+}
+
+/// Resolve `name` as seen from `current_file` to a top-level declaration.
+///
+/// WHAT: checks the file's own declarations, then its imports, then any project declaration
+/// with the same name.
+/// WHY: the header stage has not bound providers, so imports resolve by matching the provider
+/// path against file locations. Module-root files (`#mod.bst`, `#page.bst`) are addressed by
+/// their directory, matching how import paths name them.
+pub(super) fn find_declaration(
+    workspace: &BTreeMap<PathBuf, DocumentAnalysis>,
+    current_file: &Path,
+    name: &str,
+) -> Option<(PathBuf, Range)> {
+    if let Some(declaration) = workspace
+        .get(current_file)
+        .and_then(|analysis| analysis.declaration_named(name))
+    {
+        return Some((current_file.to_owned(), declaration.name_range));
+    }
+
+    let imported = workspace.get(current_file).and_then(|analysis| {
+        analysis
+            .imports
+            .iter()
+            .find(|imported| imported.local_name == name)
+    });
+
+    if let Some(imported) = imported {
+        let provider_match = workspace.iter().find_map(|(path, analysis)| {
+            if !provider_path_matches(path, &imported.provider_components) {
+                return None;
+            }
+            let declaration = analysis.declaration_named(&imported.imported_name)?;
+            Some((path.to_owned(), declaration.name_range))
+        });
+        if provider_match.is_some() {
+            return provider_match;
+        }
+
+        return find_declaration_anywhere(workspace, &imported.imported_name);
+    }
+
+    find_declaration_anywhere(workspace, name)
+}
+
+fn find_declaration_anywhere(
+    workspace: &BTreeMap<PathBuf, DocumentAnalysis>,
+    name: &str,
+) -> Option<(PathBuf, Range)> {
+    workspace.iter().find_map(|(path, analysis)| {
+        let declaration = analysis.declaration_named(name)?;
+        Some((path.to_owned(), declaration.name_range))
+    })
+}
+
+fn provider_path_matches(file: &Path, provider_components: &[String]) -> bool {
+    if provider_components.is_empty() {
+        return false;
+    }
+
+    let mut file_components = file
+        .with_extension("")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+
+    if file_components
+        .last()
+        .is_some_and(|stem| stem.starts_with('#'))
+    {
+        file_components.pop();
+    }
+
+    file_components.ends_with(provider_components)
+}
+
+#[cfg(test)]
+#[path = "tests/symbol_index_tests.rs"]
+mod tests;
@@ -0,0 +1,171 @@
+//! Tests for LSP position conversion, file URIs and message framing.
+
+use super::{
+    Position, PositionEncoding, Range, file_uri_from_path, path_from_file_uri,
+    range_from_source_location,
+};
+use crate::compiler_frontend::compiler_messages::source_location::CharPosition;
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_frontend::tokenizer::tokens::SourceLocation;
+use crate::projects::language_server::transport::{Frame, read_message, write_message};
+use serde_json::json;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+fn char_position(line_number: i32, char_column: i32) -> CharPosition {
+    CharPosition {
+        line_number,
+        char_column,
+    }
+}
+
+#[test]
+fn token_columns_convert_to_zero_based_half_open_ranges() {
+    // `value` at the start of line 3 spans token columns 1..=5.
+    let location = SourceLocation::new(
+        InternedPath::new(),
+        char_position(2, 1),
+        char_position(2, 5),
+    );
+
+    assert_eq!(
+        range_from_source_location(&location),
+        Range {
+            start: Position {
+                line: 2,
+                character: 0
+            },
+            end: Position {
+                line: 2,
+                character: 5
+            },
+        }
+    );
+}
+
+#[test]
+fn file_level_locations_collapse_to_the_document_start() {
+    let range = range_from_source_location(&SourceLocation::default());
+    assert_eq!(
+        range.start,
+        Position {
+            line: 0,
+            character: 0
+        }
+    );
+    assert_eq!(range.end, range.start);
+}
+
+#[test]
+fn utf16_positions_count_surrogate_pairs_as_two_units() {
+    let text = "first\r\nvalue = \"\u{1F600}\u{E9}\" + name\n";
+    let encoding = PositionEncoding::Utf16;
+    let position = |line, character| Position { line, character };
+
+    // `name` starts at scalar 15; the emoji adds one extra UTF-16 unit before it.
+    assert_eq!(
+        encoding.position_to_client(text, position(1, 15)),
+        position(1, 16)
+    );
+    assert_eq!(
+        encoding.position_from_client(text, position(1, 16)),
+        position(1, 15)
+    );
+    assert_eq!(
+        encoding.position_to_client(text, position(1, 9)),
+        position(1, 9)
+    );
+
+    // An offset inside the surrogate pair resolves to the emoji itself.
+    assert_eq!(
+        encoding.position_from_client(text, position(1, 10)),
+        position(1, 9)
+    );
+
+    // Past the end of a line the excess is kept in both directions.
+    assert_eq!(
+        encoding.position_to_client(text, position(0, 7)),
+        position(0, 7)
+    );
+    assert_eq!(
+        encoding.position_to_client(text, position(1, 21)),
+        position(1, 22)
+    );
+    assert_eq!(
+        encoding.position_from_client(text, position(1, 22)),
+        position(1, 21)
+    );
+
+    let utf32 = PositionEncoding::Utf32;
+    assert_eq!(
+        utf32.position_to_client(text, position(1, 15)),
+        position(1, 15)
+    );
+    assert_eq!(
+        utf32.position_from_client(text, position(1, 15)),
+        position(1, 15)
+    );
+}
+
+#[test]
+fn utf32_is_negotiated_only_when_the_client_offers_it() {
+    let offering = json!({
+        "capabilities": { "general": { "positionEncodings": ["utf-16", "utf-32"] } }
+    });
+    assert_eq!(
+        PositionEncoding::negotiate(&offering),
+        PositionEncoding::Utf32
+    );
+    assert_eq!(
+        PositionEncoding::negotiate(&json!({})),
+        PositionEncoding::Utf16
+    );
+}
+
+#[test]
+fn file_uris_round_trip_through_percent_encoding() {
+    let path = Path::new("/projects/my site/#page.bst");
+    let uri = file_uri_from_path(path);
+
+    assert_eq!(uri, "file:///projects/my%20site/%23page.bst");
+    assert_eq!(path_from_file_uri(&uri), Some(PathBuf::from(path)));
+    assert_eq!(path_from_file_uri("untitled:Untitled-1"), None);
+    assert_eq!(path_from_file_uri("file:///broken%2"), None);
+}
+
+#[test]
+fn framed_messages_round_trip() {
+    let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" });
+    let mut buffer = Vec::new();
+    write_message(&mut buffer, &message).expect("message should write");
+
+    let mut reader = Cursor::new(buffer);
+    let decoded = read_message(&mut reader).expect("message should read");
+    assert_eq!(decoded, Some(Frame::Message(message)));
+    assert_eq!(
+        read_message(&mut reader).expect("clean end of stream"),
+        None
+    );
+}
+
+#[test]
+fn framing_rejects_missing_content_length() {
+    let mut reader = Cursor::new(b"Content-Type: application/json\r\n\r\n{}".to_vec());
+    assert!(read_message(&mut reader).is_err());
+}
+
+#[test]
+fn invalid_json_bodies_keep_the_stream_aligned() {
+    let mut reader =
+        Cursor::new(b"Content-Length: 8\r\n\r\n{\"id\": 1Content-Length: 2\r\n\r\n{}".to_vec());
+
+    let unparseable = read_message(&mut reader).expect("framing is intact");
+    assert!(
+        matches!(unparseable, Some(Frame::Unparseable(_))),
+        "a bad body should be reported without a transport error, got {unparseable:?}"
+    );
+    assert_eq!(
+        read_message(&mut reader).expect("next message should read"),
+        Some(Frame::Message(json!({})))
+    );
+}
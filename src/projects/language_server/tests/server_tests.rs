@@ -0,0 +1,500 @@
+//! End-to-end tests for language server sessions driven from memory.
+
+use super::{LanguageServer, SessionEnd};
+use crate::compiler_tests::test_support::temp_dir;
+use crate::projects::language_server::protocol::file_uri_from_path;
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+fn frame(message: &Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{body}", body.len())
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn initialized_server() -> LanguageServer {
+    let mut server = LanguageServer::new();
+    let responses = server.handle_message(&request(1, "initialize", json!({})));
+    assert!(
+        responses[0]["result"]["capabilities"]["hoverProvider"]
+            .as_bool()
+            .unwrap_or(false),
+        "initialize should advertise hover support"
+    );
+    server
+}
+
+/// Write `source` to a single-file program and open it in `server`.
+fn open_single_file(
+    server: &mut LanguageServer,
+    prefix: &str,
+    source: &str,
+) -> (PathBuf, String, Vec<Value>) {
+    let root = temp_dir(prefix);
+    fs::create_dir_all(&root).expect("should create temp root");
+    let file = root.join("main.bst");
+    fs::write(&file, source).expect("should write source file");
+    let file = file.canonicalize().expect("temp file should canonicalize");
+    let uri = file_uri_from_path(&file);
+
+    let published = server.handle_message(&notification(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": uri, "languageId": "beanstalk", "version": 1, "text": source } }),
+    ));
+
+    (root, uri, published)
+}
+
+fn position_params(uri: &str, line: u32, character: u32) -> Value {
+    json!({ "textDocument": { "uri": uri }, "position": { "line": line, "character": character } })
+}
+
+fn change_params(uri: &str, version: u32, text: &str) -> Value {
+    json!({
+        "textDocument": { "uri": uri, "version": version },
+        "contentChanges": [{ "text": text }],
+    })
+}
+
+/// Byte source that stalls before each chunk, standing in for a client pausing between edits.
+struct PausingReader {
+    chunks: VecDeque<(Duration, Vec<u8>)>,
+    current: Cursor<Vec<u8>>,
+}
+
+impl PausingReader {
+    fn new(chunks: Vec<(Duration, String)>) -> Self {
+        Self {
+            chunks: chunks
+                .into_iter()
+                .map(|(pause, text)| (pause, text.into_bytes()))
+                .collect(),
+            current: Cursor::new(Vec::new()),
+        }
+    }
+}
+
+impl Read for PausingReader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let read = self.current.read(buffer)?;
+            if read > 0 {
+                return Ok(read);
+            }
+            let Some((pause, chunk)) = self.chunks.pop_front() else {
+                return Ok(0);
+            };
+            thread::sleep(pause);
+            self.current = Cursor::new(chunk);
+        }
+    }
+}
+
+fn remove_root(root: &Path) {
+    fs::remove_dir_all(root).expect("should remove temp dir");
+}
+
+#[test]
+fn requests_before_initialize_are_rejected() {
+    let mut server = LanguageServer::new();
+    let responses = server.handle_message(&request(
+        7,
+        "textDocument/hover",
+        position_params("file:///tmp/main.bst", 0, 0),
+    ));
+
+    assert_eq!(responses[0]["id"], 7);
+    assert_eq!(responses[0]["error"]["code"], -32002);
+}
+
+#[test]
+fn open_publishes_compiler_diagnostics_with_codes_and_ranges() {
+    let mut server = initialized_server();
+    let (root, uri, published) =
+        open_single_file(&mut server, "lsp_diagnostics", "value = missing_name\n");
+
+    let params = &published
+        .iter()
+        .find(|message| message["params"]["uri"] == uri.as_str())
+        .expect("diagnostics should publish for the opened file")["params"];
+    let diagnostic = &params["diagnostics"][0];
+    assert_eq!(diagnostic["severity"], 1);
+    assert_eq!(diagnostic["source"], "beanstalk");
+    assert!(
+        diagnostic["code"]
+            .as_str()
+            .is_some_and(|code| code.starts_with("BST-")),
+        "diagnostics should carry the stable code, got {diagnostic}"
+    );
+    assert_eq!(diagnostic["range"]["start"]["line"], 0);
+
+    // Fixing the file and saving clears the previously published diagnostics. Open documents
+    // override disk, so the editor buffer is updated before the save lands.
+    server.handle_message(&notification(
+        "textDocument/didChange",
+        change_params(&uri, 2, "value = 1\n"),
+    ));
+    fs::write(root.join("main.bst"), "value = 1\n").expect("should rewrite source file");
+    let cleared = server.handle_message(&notification(
+        "textDocument/didSave",
+        json!({ "textDocument": { "uri": uri } }),
+    ));
+    let cleared_params = &cleared
+        .iter()
+        .find(|message| message["params"]["uri"] == uri.as_str())
+        .expect("stale diagnostics should be cleared")["params"];
+    assert_eq!(cleared_params["diagnostics"], json!([]));
+
+    remove_root(&root);
+}
+
+#[test]
+fn diagnostic_ranges_follow_the_negotiated_position_encoding() {
+    let source = "value = \"\u{1F600}\" + missing_name\n";
+    let published_columns = |server: &mut LanguageServer, prefix| {
+        let (root, uri, published) = open_single_file(server, prefix, source);
+        remove_root(&root);
+        let params = &published
+            .iter()
+            .find(|message| message["params"]["uri"] == uri.as_str())
+            .expect("diagnostics should publish for the opened file")["params"];
+        (
+            params["diagnostics"][0]["range"]["start"]["character"].clone(),
+            params["diagnostics"][0]["range"]["end"]["character"].clone(),
+        )
+    };
+
+    // UTF-16 is the default: the emoji before `missing_name` takes two code units.
+    let mut utf16_server = initialized_server();
+    assert_eq!(
+        published_columns(&mut utf16_server, "lsp_utf16"),
+        (json!(15), json!(27))
+    );
+
+    let mut utf32_server = LanguageServer::new();
+    let responses = utf32_server.handle_message(&request(
+        1,
+        "initialize",
+        json!({ "capabilities": { "general": { "positionEncodings": ["utf-32", "utf-16"] } } }),
+    ));
+    assert_eq!(
+        responses[0]["result"]["capabilities"]["positionEncoding"],
+        "utf-32"
+    );
+    assert_eq!(
+        published_columns(&mut utf32_server, "lsp_utf32"),
+        (json!(14), json!(26))
+    );
+}
+
+#[test]
+fn project_diagnostics_resolve_files_under_the_entry_root() {
+    let mut server = initialized_server();
+    let root = temp_dir("lsp_project_diagnostics");
+    fs::create_dir_all(root.join("src")).expect("should create entry root");
+    fs::write(root.join("config.bst"), "entry_root #= \"src\"\n").expect("should write config");
+    let page = root.join("src").join("#page.bst");
+    let source = "value = missing_name\n";
+    fs::write(&page, source).expect("should write page");
+    let page_uri = file_uri_from_path(&page.canonicalize().expect("page should canonicalize"));
+
+    let published = server.handle_message(&notification(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": page_uri, "languageId": "beanstalk", "version": 1, "text": source } }),
+    ));
+
+    assert!(
+        published.iter().any(|message| {
+            message["params"]["uri"] == page_uri.as_str()
+                && message["params"]["diagnostics"]
+                    .as_array()
+                    .is_some_and(|diagnostics| !diagnostics.is_empty())
+        }),
+        "diagnostics should publish against the page inside the entry root, got {published:?}"
+    );
+
+    remove_root(&root);
+}
+
+#[test]
+fn hover_renders_resolved_types_for_locals_and_functions() {
+    let mut server = initialized_server();
+    let source =
+        "add |a Int, b Int| -> Int:\n    return a + b\n;\n\ntotal ~= add(1, 2)\nlabel = \"sum\"\n";
+    let (root, uri, _) = open_single_file(&mut server, "lsp_hover", source);
+
+    let local_hover = server.handle_message(&request(
+        2,
+        "textDocument/hover",
+        position_params(&uri, 4, 2),
+    ));
+    assert_eq!(
+        local_hover[0]["result"]["contents"]["value"],
+        "```beanstalk\ntotal ~Int\n```"
+    );
+
+    let function_hover = server.handle_message(&request(
+        3,
+        "textDocument/hover",
+        position_params(&uri, 4, 10),
+    ));
+    assert_eq!(
+        function_hover[0]["result"]["contents"]["value"],
+        "```beanstalk\nadd |a Int, b Int| -> Int\n```"
+    );
+
+    let string_hover = server.handle_message(&request(
+        4,
+        "textDocument/hover",
+        position_params(&uri, 5, 1),
+    ));
+    assert_eq!(
+        string_hover[0]["result"]["contents"]["value"],
+        "```beanstalk\nlabel String\n```"
+    );
+
+    let empty_hover = server.handle_message(&request(
+        5,
+        "textDocument/hover",
+        position_params(&uri, 2, 0),
+    ));
+    assert_eq!(empty_hover[0]["result"], Value::Null);
+
+    let parameter_definition = server.handle_message(&request(
+        6,
+        "textDocument/definition",
+        position_params(&uri, 1, 11),
+    ));
+    assert_eq!(
+        parameter_definition[0]["result"]["range"]["start"],
+        json!({ "line": 0, "character": 5 })
+    );
+
+    remove_root(&root);
+}
+
+#[test]
+fn definition_and_document_symbols_use_header_symbols() {
+    let mut server = initialized_server();
+    let source = "double |value Int| -> Int:\n    return value * 2\n;\n\nresult = double(4)\n";
+    let (root, uri, _) = open_single_file(&mut server, "lsp_definition", source);
+
+    let definition = server.handle_message(&request(
+        2,
+        "textDocument/definition",
+        position_params(&uri, 4, 11),
+    ));
+    assert_eq!(definition[0]["result"]["uri"], uri.as_str());
+    assert_eq!(
+        definition[0]["result"]["range"]["start"],
+        json!({ "line": 0, "character": 0 })
+    );
+
+    let symbols = server.handle_message(&request(
+        3,
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": uri } }),
+    ));
+    let names = symbols[0]["result"]
+        .as_array()
+        .expect("document symbols should be an array")
+        .iter()
+        .map(|symbol| symbol["name"].as_str().unwrap_or_default().to_owned())
+        .collect::<Vec<_>>();
+    assert!(names.contains(&String::from("double")));
+
+    remove_root(&root);
+}
+
+#[test]
+fn unsaved_edits_drive_navigation_before_the_deferred_check() {
+    let mut server = initialized_server();
+    let (root, uri, _) = open_single_file(&mut server, "lsp_did_change", "value = 1\n");
+
+    let edited = "value = 1\n\nhelper |x Int| -> Int:\n    return x\n;\n";
+    let published = server.handle_message(&notification(
+        "textDocument/didChange",
+        change_params(&uri, 2, edited),
+    ));
+    assert!(
+        published.is_empty(),
+        "didChange should defer its check until typing pauses"
+    );
+
+    let symbols = server.handle_message(&request(
+        2,
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": uri } }),
+    ));
+    assert!(
+        symbols[0]["result"]
+            .as_array()
+            .is_some_and(|symbols| symbols.iter().any(|symbol| symbol["name"] == "helper")),
+        "document symbols should reflect unsaved editor text"
+    );
+
+    remove_root(&root);
+}
+
+#[test]
+fn deferred_checks_diagnose_unsaved_text() {
+    let mut server = initialized_server();
+    let (root, uri, published) = open_single_file(&mut server, "lsp_overlay", "value = 1\n");
+    assert!(published.is_empty(), "a clean file has nothing to publish");
+
+    server.handle_message(&notification(
+        "textDocument/didChange",
+        change_params(&uri, 2, "value = missing_name\n"),
+    ));
+    let published = server.run_pending_check();
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0]["params"]["uri"], uri);
+    assert!(
+        published[0]["params"]["diagnostics"]
+            .as_array()
+            .is_some_and(|diagnostics| !diagnostics.is_empty()),
+        "the unsaved error should be diagnosed although the file on disk is clean"
+    );
+    assert!(
+        server.run_pending_check().is_empty(),
+        "a pending check should run once"
+    );
+
+    server.handle_message(&notification(
+        "textDocument/didChange",
+        change_params(&uri, 3, "value = 1\n"),
+    ));
+    let cleared = server.run_pending_check();
+    assert_eq!(cleared.len(), 1);
+    assert_eq!(cleared[0]["params"]["diagnostics"], json!([]));
+
+    remove_root(&root);
+}
+
+#[test]
+fn serve_coalesces_rapid_changes_into_one_check() {
+    let root = temp_dir("lsp_debounce");
+    fs::create_dir_all(&root).expect("should create temp root");
+    let file = root.join("main.bst");
+    fs::write(&file, "value = 1\n").expect("should write source file");
+    let uri = file_uri_from_path(&file.canonicalize().expect("temp file should canonicalize"));
+
+    let typing = [
+        frame(&request(1, "initialize", json!({}))),
+        frame(&notification(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": uri, "languageId": "beanstalk", "version": 1, "text": "value = 1\n" } }),
+        )),
+        frame(&notification(
+            "textDocument/didChange",
+            change_params(&uri, 2, "value = first_missing\n"),
+        )),
+        frame(&notification(
+            "textDocument/didChange",
+            change_params(&uri, 3, "value = second_missing\n"),
+        )),
+    ]
+    .concat();
+    let closing = [
+        frame(&request(2, "shutdown", Value::Null)),
+        frame(&notification("exit", Value::Null)),
+    ]
+    .concat();
+    let mut reader = BufReader::new(PausingReader::new(vec![
+        (Duration::ZERO, typing),
+        (Duration::from_secs(2), closing),
+    ]));
+    let mut output = Vec::new();
+
+    let end = LanguageServer::new()
+        .serve(&mut reader, &mut output)
+        .expect("session should run");
+    assert_eq!(end, SessionEnd::Clean);
+
+    let output = String::from_utf8(output).expect("responses should be UTF-8");
+    assert_eq!(
+        output.matches("textDocument/publishDiagnostics").count(),
+        1,
+        "both edits should share one check once typing pauses: {output}"
+    );
+    assert!(output.contains("second_missing") && !output.contains("first_missing"));
+
+    remove_root(&root);
+}
+
+#[test]
+fn serve_reports_clean_exit_only_after_shutdown() {
+    let input = [
+        frame(&request(1, "initialize", json!({}))),
+        frame(&notification("initialized", json!({}))),
+        frame(&request(2, "shutdown", Value::Null)),
+        frame(&notification("exit", Value::Null)),
+    ]
+    .concat();
+    let mut reader = Cursor::new(input.into_bytes());
+    let mut output = Vec::new();
+
+    let end = LanguageServer::new()
+        .serve(&mut reader, &mut output)
+        .expect("session should run");
+    assert_eq!(end, SessionEnd::Clean);
+
+    let output = String::from_utf8(output).expect("responses should be UTF-8");
+    assert_eq!(output.matches("Content-Length:").count(), 2);
+    assert!(
+        output.contains(r#""result":null"#),
+        "shutdown should answer with a null result"
+    );
+
+    let abrupt_input = frame(&notification("exit", Value::Null));
+    let mut abrupt_reader = Cursor::new(abrupt_input.into_bytes());
+    let abrupt_end = LanguageServer::new()
+        .serve(&mut abrupt_reader, &mut Vec::new())
+        .expect("session should run");
+    assert_eq!(abrupt_end, SessionEnd::WithoutShutdown);
+}
+
+#[test]
+fn serve_answers_invalid_json_with_a_parse_error_and_continues() {
+    let garbage = "{\"jsonrpc\": \"2.0\", \"id\": 2,";
+    let input = [
+        frame(&request(1, "initialize", json!({}))),
+        format!("Content-Length: {}\r\n\r\n{garbage}", garbage.len()),
+        frame(&request(3, "shutdown", Value::Null)),
+        frame(&notification("exit", Value::Null)),
+    ]
+    .concat();
+    let mut reader = Cursor::new(input.into_bytes());
+    let mut output = Vec::new();
+
+    let end = LanguageServer::new()
+        .serve(&mut reader, &mut output)
+        .expect("an unparseable body should not end the session");
+    assert_eq!(end, SessionEnd::Clean);
+
+    let output = String::from_utf8(output).expect("responses should be UTF-8");
+    assert_eq!(output.matches("Content-Length:").count(), 3);
+    assert!(
+        output.contains(r#""code":-32700"#) && output.contains(r#""id":null"#),
+        "the bad body should get a parse error with a null id: {output}"
+    );
+}
+
+#[test]
+fn unknown_requests_return_method_not_found() {
+    let mut server = initialized_server();
+    let responses = server.handle_message(&request(9, "textDocument/rename", json!({})));
+    assert_eq!(responses[0]["error"]["code"], -32601);
+}
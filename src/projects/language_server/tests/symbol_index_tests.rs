@@ -0,0 +1,178 @@
+//! Tests for header-driven document analysis and declaration lookup.
+
+use super::{DeclarationKind, analyze_document, find_declaration};
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::projects::language_server::protocol::{
+    Position, SYMBOL_KIND_CONSTANT, SYMBOL_KIND_FUNCTION, SYMBOL_KIND_STRUCT,
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+fn position(line: u32, character: u32) -> Position {
+    Position { line, character }
+}
+
+#[test]
+fn analysis_collects_top_level_declarations_with_name_ranges() {
+    let source = "limit #= 10\n\nPoint = |\n    x Int,\n    y Int,\n|\n\ndouble |value Int| -> Int:\n    return value * 2\n;\n";
+    let analysis = analyze_document(
+        Path::new("src/main.bst"),
+        source,
+        &StyleDirectiveRegistry::built_ins(),
+    );
+
+    let summary = analysis
+        .declarations
+        .iter()
+        .map(|declaration| (declaration.name.as_str(), declaration.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("limit", DeclarationKind::Constant),
+            ("Point", DeclarationKind::Struct),
+            ("double", DeclarationKind::Function),
+        ]
+    );
+
+    let double = analysis
+        .declaration_named("double")
+        .expect("function declaration should be indexed");
+    assert_eq!(double.name_range.start, position(7, 0));
+    assert_eq!(double.name_range.end, position(7, 6));
+
+    let symbol_kinds = analysis
+        .document_symbols()
+        .iter()
+        .map(|symbol| symbol.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        symbol_kinds,
+        vec![
+            SYMBOL_KIND_CONSTANT,
+            SYMBOL_KIND_STRUCT,
+            SYMBOL_KIND_FUNCTION
+        ]
+    );
+}
+
+#[test]
+fn identifier_lookup_accepts_cursor_inside_or_directly_after_a_name() {
+    let analysis = analyze_document(
+        Path::new("main.bst"),
+        "total = 1\nnext = total\n",
+        &StyleDirectiveRegistry::built_ins(),
+    );
+
+    let inside = analysis
+        .identifier_at(position(1, 9))
+        .expect("cursor inside `total` should resolve");
+    assert_eq!(inside.name, "total");
+
+    let after = analysis
+        .identifier_at(position(1, 12))
+        .expect("cursor directly after `total` should resolve");
+    assert_eq!(after.name, "total");
+
+    assert!(analysis.identifier_at(position(1, 5)).is_none());
+}
+
+#[test]
+fn tokenizer_failures_produce_an_empty_analysis() {
+    let analysis = analyze_document(
+        Path::new("main.bst"),
+        "value = \"unterminated\n",
+        &StyleDirectiveRegistry::built_ins(),
+    );
+
+    assert!(analysis.identifiers.is_empty());
+    assert!(analysis.declarations.is_empty());
+}
+
+#[test]
+fn imports_record_local_alias_and_provider_path() {
+    let analysis = analyze_document(
+        Path::new("#page.bst"),
+        "import @utils/math {add as plus}\n\nvalue = plus(1, 2)\n",
+        &StyleDirectiveRegistry::built_ins(),
+    );
+
+    let imported = analysis
+        .imports
+        .iter()
+        .find(|imported| imported.local_name == "plus")
+        .expect("aliased import should be recorded");
+    assert_eq!(imported.imported_name, "add");
+    assert_eq!(imported.provider_components, vec!["utils", "math"]);
+}
+
+#[test]
+fn find_declaration_prefers_same_file_then_import_provider() {
+    let styles = StyleDirectiveRegistry::built_ins();
+    let page = PathBuf::from("/project/#page.bst");
+    let math = PathBuf::from("/project/lib/utils/math.bst");
+    let other = PathBuf::from("/project/lib/other.bst");
+
+    let mut workspace = BTreeMap::new();
+    workspace.insert(
+        page.to_owned(),
+        analyze_document(
+            &page,
+            "import @utils/math {add as plus}\n\nlocal_value #= plus(1, 2)\n",
+            &styles,
+        ),
+    );
+    workspace.insert(
+        other.to_owned(),
+        analyze_document(&other, "add |a Int| -> Int:\n    return a\n;\n", &styles),
+    );
+    workspace.insert(
+        math.to_owned(),
+        analyze_document(
+            &math,
+            "\nadd |a Int, b Int| -> Int:\n    return a + b\n;\n",
+            &styles,
+        ),
+    );
+
+    let (same_file, same_range) = find_declaration(&workspace, &page, "local_value")
+        .expect("same-file declaration should resolve");
+    assert_eq!(same_file, page);
+    assert_eq!(same_range.start, position(2, 0));
+
+    let (provider_file, provider_range) = find_declaration(&workspace, &page, "plus")
+        .expect("aliased import should resolve through the provider path");
+    assert_eq!(provider_file, math);
+    assert_eq!(provider_range.start, position(1, 0));
+}
+
+#[test]
+fn module_root_files_are_addressed_by_their_directory() {
+    let styles = StyleDirectiveRegistry::built_ins();
+    let page = PathBuf::from("/project/#page.bst");
+    let module_root = PathBuf::from("/project/lib/utils/#mod.bst");
+
+    let mut workspace = BTreeMap::new();
+    workspace.insert(
+        page.to_owned(),
+        analyze_document(&page, "import @utils {identity}\n", &styles),
+    );
+    workspace.insert(
+        module_root.to_owned(),
+        analyze_document(
+            &module_root,
+            "export:\n    identity |value Int| -> Int:\n        return value\n    ;\n;\n",
+            &styles,
+        ),
+    );
+
+    let (file, _) =
+        find_declaration(&workspace, &page, "identity").expect("module root export should resolve");
+    assert_eq!(file, module_root);
+    assert!(
+        workspace[&module_root]
+            .declaration_named("identity")
+            .is_some_and(|declaration| declaration.exported),
+        "declarations inside `export:` should be marked exported"
+    );
+}
@@ -0,0 +1,95 @@
+//! JSON-RPC message framing for the language server.
+//!
+//! WHAT: reads and writes `Content-Length` framed JSON-RPC payloads over any byte stream.
+//! WHY: the Language Server Protocol base layer is transport-agnostic; keeping framing generic
+//! over `BufRead`/`Write` lets the server run over stdio and lets tests drive it from memory.
+
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+
+const CONTENT_LENGTH_HEADER: &str = "content-length";
+
+/// One framed payload read from the stream.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Frame {
+    Message(Value),
+    /// The framing was intact but the body is not JSON. The stream is still aligned on the next
+    /// message, so the server answers with a parse error instead of ending the session.
+    Unparseable(String),
+}
+
+/// Read one framed JSON-RPC message.
+///
+/// Returns `Ok(None)` when the stream closes cleanly between messages. Errors are reserved for
+/// framing failures that leave the stream unreadable: malformed headers, a missing or invalid
+/// `Content-Length`, or end of input inside a message.
+pub(super) fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Frame>> {
+    let mut content_length = None;
+    let mut saw_header = false;
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader.read_line(&mut line)?;
+
+        if bytes_read == 0 {
+            if saw_header {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "stream closed inside a message header",
+                ));
+            }
+            return Ok(None);
+        }
+
+        let header_line = line.trim_end_matches(['\r', '\n']);
+        if header_line.is_empty() {
+            // Tolerate stray blank lines between messages.
+            if !saw_header {
+                continue;
+            }
+            break;
+        }
+
+        saw_header = true;
+        let Some((name, value)) = header_line.split_once(':') else {
+            return Err(invalid_data(format!(
+                "malformed header line '{header_line}'"
+            )));
+        };
+
+        if name.trim().eq_ignore_ascii_case(CONTENT_LENGTH_HEADER) {
+            let length = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| invalid_data(format!("invalid Content-Length '{}'", value.trim())))?;
+            content_length = Some(length);
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(invalid_data(String::from(
+            "message header is missing Content-Length",
+        )));
+    };
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Some(match serde_json::from_slice(&body) {
+        Ok(message) => Frame::Message(message),
+        Err(error) => Frame::Unparseable(format!("Message body is not valid JSON: {error}")),
+    }))
+}
+
+/// Write one framed JSON-RPC message and flush the stream.
+pub(super) fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_string(message)
+        .map_err(|error| invalid_data(format!("failed to serialize message: {error}")))?;
+
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,319 @@
+//! Runs compiled HTML page bundles under a local `node` process.
+//!
+//! WHAT: extracts a page's scripts (or its `page.js` + `page.wasm` pair), executes them in a
+//!       minimal harness with DOM and console stubs, and returns the console and fragment events
+//!       in the order they happened.
+//! WHY: the REPL and the rendered-output integration assertions must observe runtime semantics
+//!      through one execution path, so neither inspects generated JavaScript structure.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+static RENDER_HARNESS_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug)]
+pub(crate) struct RenderedOutput {
+    events: Vec<RuntimeEvent>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RuntimeEvent {
+    Console { text: String },
+    FragmentInsert { id: String, html: String },
+}
+
+impl RenderedOutput {
+    #[cfg(test)]
+    pub(crate) fn events(&self) -> &[RuntimeEvent] {
+        &self.events
+    }
+
+    /// Console lines in the order the page printed them.
+    pub(crate) fn console_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for event in &self.events {
+            if let RuntimeEvent::Console { text } = event {
+                lines.push(text.to_owned());
+            }
+        }
+        lines
+    }
+
+    pub(crate) fn combined_output(&self) -> String {
+        let mut parts = Vec::with_capacity(self.events.len());
+        for event in &self.events {
+            match event {
+                RuntimeEvent::Console { text } => parts.push(text.to_owned()),
+                RuntimeEvent::FragmentInsert { html, .. } => parts.push(html.to_owned()),
+            }
+        }
+
+        parts.join("\n")
+    }
+}
+
+/// Executes the script blocks from compiled HTML through a minimal Node.js harness.
+///
+/// The harness stubs `document.getElementById` to capture `insertAdjacentHTML` calls, intercepts
+/// `console.log` and emits a JSON summary after one microtask tick so callers can observe batched
+/// reactive flushes queued by the page bundle.
+pub(crate) fn execute_html_in_node(html: &str) -> Result<RenderedOutput, String> {
+    let scripts = extract_script_blocks(html);
+    if scripts.is_empty() {
+        return Err(String::from("the page has no <script> blocks to run"));
+    }
+
+    run_node_harness(&build_node_harness(&scripts))
+}
+
+/// Executes an HTML-Wasm page bundle through the same Node harness as inline scripts.
+///
+/// WHAT: serves `page.wasm` through a stubbed `fetch`, runs `page.js` and reports once the
+///       asynchronous instantiation and hydration have drained the event loop.
+/// WHY: `html` and `html_wasm` cases share rendered-output expectations, so both backends must be
+///      observed through the same slot and console capture.
+pub(crate) fn execute_wasm_page_in_node(
+    page_js: &str,
+    wasm_bytes: &[u8],
+) -> Result<RenderedOutput, String> {
+    run_node_harness(&build_wasm_node_harness(page_js, wasm_bytes))
+}
+
+fn run_node_harness(harness: &str) -> Result<RenderedOutput, String> {
+    let unique = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    let sequence = RENDER_HARNESS_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let temp_path = std::env::temp_dir().join(format!(
+        "bst_render_harness_{}_{}_{}.js",
+        std::process::id(),
+        unique,
+        sequence
+    ));
+
+    std::fs::write(&temp_path, harness)
+        .map_err(|error| format!("failed to write node harness: {error}"))?;
+
+    let output = std::process::Command::new("node")
+        .arg(&temp_path)
+        .output()
+        .map_err(|error| {
+            let _ = remove_temp_harness_file_with_retry(&temp_path);
+            format!("failed to invoke node: {error}. Ensure 'node' is on PATH.")
+        })?;
+
+    let _ = remove_temp_harness_file_with_retry(&temp_path);
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("node harness execution failed:\n{stderr}"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_harness_output(stdout.trim())
+}
+
+/// Best-effort cleanup for temporary Node harness files.
+///
+/// WHAT: retries removal briefly to tolerate Windows file-sharing race windows after process exit.
+/// WHY: cleanup races must not surface as page execution failures.
+fn remove_temp_harness_file_with_retry(path: &Path) -> Result<(), std::io::Error> {
+    const MAX_ATTEMPTS: usize = 6;
+    const BASE_RETRY_DELAY_MS: u64 = 8;
+
+    let mut last_error = None;
+    for attempt in 0..MAX_ATTEMPTS {
+        match std::fs::remove_file(path) {
+            Ok(()) => return Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => {
+                last_error = Some(error);
+                if attempt + 1 < MAX_ATTEMPTS {
+                    std::thread::sleep(Duration::from_millis(
+                        BASE_RETRY_DELAY_MS * (attempt as u64 + 1),
+                    ));
+                }
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| std::io::Error::other("failed to remove file")))
+}
+
+/// DOM and console stubs shared by every page harness.
+const NODE_HARNESS_PRELUDE: &str = r#"const __bst_events = [];
+const __bst_slot_by_id = new Map();
+console.log = (...args) => __bst_events.push({ type: 'console', text: args.map(String).join(' ') });
+function __bst_get_slot(id) {
+    if (!__bst_slot_by_id.has(id)) {
+        const slot = {
+            id,
+            innerHTML: "",
+            insertAdjacentHTML: (_, html) => {
+                const text = String(html);
+                slot.innerHTML += text;
+                __bst_events.push({ type: 'fragment_insert', id: String(id), html: text });
+            }
+        };
+        __bst_slot_by_id.set(id, slot);
+    }
+    return __bst_slot_by_id.get(id);
+}
+const document = {
+    getElementById: __bst_get_slot
+};
+"#;
+
+fn build_node_harness(scripts: &[String]) -> String {
+    let suffix = r#"
+Promise.resolve().then(() => {
+    process.stdout.write(JSON.stringify({ events: __bst_events }) + '\n');
+});
+"#;
+
+    format!("{NODE_HARNESS_PRELUDE}{}\n{suffix}", scripts.join("\n"))
+}
+
+fn build_wasm_node_harness(page_js: &str, wasm_bytes: &[u8]) -> String {
+    let wasm_bytes_json = serde_json::to_string(wasm_bytes).unwrap_or_else(|_| "[]".to_owned());
+    let fetch_stub = format!(
+        r#"const __bst_wasm_bytes = new Uint8Array({wasm_bytes_json});
+globalThis.fetch = async () => new Response(__bst_wasm_bytes, {{
+    headers: {{ 'Content-Type': 'application/wasm' }}
+}});
+"#
+    );
+
+    // Instantiation is asynchronous, so report once the page has nothing left to run.
+    let suffix = r#"
+process.once('beforeExit', () => {
+    process.stdout.write(JSON.stringify({ events: __bst_events }) + '\n');
+});
+"#;
+
+    format!("{NODE_HARNESS_PRELUDE}{fetch_stub}{page_js}\n{suffix}")
+}
+
+/// Extracts the text content between `<script>` and `</script>` tag pairs.
+pub(crate) fn extract_script_blocks(html: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(open_end) = find_script_open_end(html, search_from) {
+        let close_tag = "</script>";
+        let Some(close_start) = html[open_end..].find(close_tag) else {
+            break;
+        };
+        let block = &html[open_end..open_end + close_start];
+        if !block.trim().is_empty() {
+            blocks.push(block.to_owned());
+        }
+        search_from = open_end + close_start + close_tag.len();
+    }
+
+    blocks
+}
+
+/// Finds the end position of a `<script>` opening tag starting from `from`.
+fn find_script_open_end(html: &str, from: usize) -> Option<usize> {
+    let slice = &html[from..];
+    let tag_start = slice.find("<script")?;
+    let tag_slice = &slice[tag_start..];
+    let close_bracket = tag_slice.find('>')?;
+    Some(from + tag_start + close_bracket + 1)
+}
+
+pub(crate) fn parse_harness_output(json: &str) -> Result<RenderedOutput, String> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|error| {
+        format!("failed to parse node harness JSON output: {error}\nRaw: {json}")
+    })?;
+
+    let invalid_harness_output =
+        |reason: String| format!("invalid node harness output: {reason}\nRaw: {json}");
+
+    let Some(object) = value.as_object() else {
+        return Err(invalid_harness_output(
+            "top-level value must be an object".to_owned(),
+        ));
+    };
+
+    if let Err(reason) = reject_unknown_fields(object, &["events"], "harness output") {
+        return Err(invalid_harness_output(reason));
+    }
+
+    let Some(events_value) = object.get("events") else {
+        return Err(invalid_harness_output("missing field 'events'".to_owned()));
+    };
+    let Some(events_array) = events_value.as_array() else {
+        return Err(invalid_harness_output(
+            "field 'events' must be an array".to_owned(),
+        ));
+    };
+
+    let mut events = Vec::with_capacity(events_array.len());
+    for (index, event_value) in events_array.iter().enumerate() {
+        let event = decode_runtime_event(index, event_value).map_err(invalid_harness_output)?;
+        events.push(event);
+    }
+
+    Ok(RenderedOutput { events })
+}
+
+fn decode_runtime_event(index: usize, value: &serde_json::Value) -> Result<RuntimeEvent, String> {
+    let Some(object) = value.as_object() else {
+        return Err(format!("event {index} must be an object"));
+    };
+
+    let event_type = required_string_field(object, "type", &format!("event {index}"))?;
+    match event_type.as_str() {
+        "console" => {
+            reject_unknown_fields(object, &["type", "text"], &format!("event {index}"))?;
+            let text = required_string_field(object, "text", &format!("event {index}"))?;
+            Ok(RuntimeEvent::Console { text })
+        }
+
+        "fragment_insert" => {
+            reject_unknown_fields(object, &["type", "id", "html"], &format!("event {index}"))?;
+            let id = required_string_field(object, "id", &format!("event {index}"))?;
+            let html = required_string_field(object, "html", &format!("event {index}"))?;
+            Ok(RuntimeEvent::FragmentInsert { id, html })
+        }
+
+        other => Err(format!("event {index} has unknown type '{other}'")),
+    }
+}
+
+fn required_string_field(
+    object: &serde_json::Map<String, serde_json::Value>,
+    field: &str,
+    context: &str,
+) -> Result<String, String> {
+    let Some(value) = object.get(field) else {
+        return Err(format!("{context} is missing string field '{field}'"));
+    };
+
+    let Some(value) = value.as_str() else {
+        return Err(format!("{context} field '{field}' must be a string"));
+    };
+
+    Ok(value.to_owned())
+}
+
+fn reject_unknown_fields(
+    object: &serde_json::Map<String, serde_json::Value>,
+    allowed_fields: &[&str],
+    context: &str,
+) -> Result<(), String> {
+    for field in object.keys() {
+        if !allowed_fields
+            .iter()
+            .any(|allowed_field| *allowed_field == field)
+        {
+            return Err(format!("{context} has unknown field '{field}'"));
+        }
+    }
+
+    Ok(())
+}
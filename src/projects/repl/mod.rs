@@ -0,0 +1,217 @@
+//! Interactive `bean repl` session.
+//!
+//! WHAT: reads snippets from stdin, keeps reading lines until blocks, brackets and templates are
+//! closed, and hands each complete snippet to a [`ReplSession`] that accumulates declarations.
+//! Lines starting with `:` are session commands such as `:type`, `:hir` and `:reset`.
+//! WHY: the REPL is a learning and debugging tool, so it evaluates snippets through the normal
+//! frontend and JS backend instead of a separate interpreter with its own semantics.
+
+mod session;
+
+use crate::compiler_frontend::display_messages::print_compiler_messages;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::compiler_frontend::tokenizer::lexer::tokenize;
+use crate::compiler_frontend::tokenizer::tokens::{TokenKind, TokenizerEntryMode};
+use crate::projects::html_project::style_directives::html_project_style_directives;
+use saying::say;
+use session::{ReplOutcome, ReplSession};
+use std::io::{self, BufRead, Write};
+
+#[derive(Debug, PartialEq, Eq)]
+enum ReplCommand<'a> {
+    Help,
+    Quit,
+    Reset,
+    Show,
+    Type(&'a str),
+    Hir(&'a str),
+    Unknown(&'a str),
+}
+
+/// Start an interactive session on stdin. Returns when the user quits or stdin closes.
+pub fn start_repl_session() {
+    say!(Green Bold "Beanstalk REPL", Reset " version ", Blue Bold env!("CARGO_PKG_VERSION"));
+    say!(Bright Black "Type :help for commands and :quit to exit.\n");
+
+    let mut session = ReplSession::new();
+    let stdin = io::stdin();
+    let mut input = String::new();
+
+    loop {
+        print!("{}", if input.is_empty() { ">>> " } else { "... " });
+        if let Err(error) = io::stdout().flush() {
+            say!(Red "Error flushing prompt: ", error);
+            break;
+        }
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(error) => {
+                say!(Red "Error reading input: ", error);
+                break;
+            }
+        }
+
+        if input.is_empty() {
+            if let Some(command) = parse_repl_command(&line) {
+                if !run_command(command, &mut session) {
+                    break;
+                }
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+        }
+
+        // A blank line submits an unfinished snippet so the compiler can report what is missing.
+        let submit = line.trim().is_empty();
+        input.push_str(&line);
+        if !submit && !is_input_complete(&input) {
+            continue;
+        }
+
+        print_outcome(session.evaluate(&input));
+        input.clear();
+    }
+}
+
+/// Run one session command. Returns false when the session should end.
+fn run_command(command: ReplCommand, session: &mut ReplSession) -> bool {
+    match command {
+        ReplCommand::Help => print_repl_help(),
+        ReplCommand::Quit => return false,
+        ReplCommand::Reset => {
+            session.reset();
+            say!(Bright Black "Session cleared.");
+        }
+        ReplCommand::Show => print!("{}", session.source()),
+        ReplCommand::Type("") => {
+            say!(Red "Usage: :type <expression>");
+        }
+        ReplCommand::Type(expression) => match session.type_of(expression) {
+            Ok(type_name) => say!(Blue type_name),
+            Err(messages) => print_compiler_messages(messages),
+        },
+        ReplCommand::Hir(snippet) => print_hir(session, snippet),
+        ReplCommand::Unknown(name) => {
+            say!(Red "Unknown command ':", name, "'. Type :help for commands.");
+        }
+    }
+
+    true
+}
+
+fn print_hir(session: &ReplSession, snippet: &str) {
+    match session.hir(snippet) {
+        Ok(hir) => println!("{hir}"),
+        Err(messages) => print_compiler_messages(messages),
+    }
+}
+
+fn print_outcome(outcome: ReplOutcome) {
+    match outcome {
+        ReplOutcome::Value { text, output } => {
+            for line in output {
+                println!("{line}");
+            }
+            say!(Blue text);
+        }
+        ReplOutcome::Accepted { output } => {
+            for line in output {
+                println!("{line}");
+            }
+        }
+        ReplOutcome::Rejected(messages) => print_compiler_messages(messages),
+        ReplOutcome::RuntimeUnavailable(reason) => {
+            say!(Red "Could not run the snippet: ", reason);
+        }
+    }
+}
+
+fn print_repl_help() {
+    say!(Green Bold "REPL commands:");
+    say!("  :type <expr>   - Shows the inferred type of an expression");
+    say!("  :hir [snippet] - Shows the HIR for the session plus an optional snippet");
+    say!("  :show          - Prints the declarations accepted so far");
+    say!("  :reset         - Clears all declarations");
+    say!("  :quit          - Ends the session (also :q or exit)");
+    say!(Bright Black "Blocks, brackets and templates continue onto the next line until closed.");
+    say!(Bright Black "Enter a blank line to submit an unfinished snippet.");
+}
+
+fn parse_repl_command(line: &str) -> Option<ReplCommand<'_>> {
+    let line = line.trim();
+    if line == "exit" {
+        return Some(ReplCommand::Quit);
+    }
+
+    let command = line.strip_prefix(':')?;
+    let (name, argument) = command
+        .split_once(char::is_whitespace)
+        .map_or((command, ""), |(name, argument)| (name, argument.trim()));
+
+    Some(match name {
+        "help" | "h" => ReplCommand::Help,
+        "quit" | "q" => ReplCommand::Quit,
+        "reset" => ReplCommand::Reset,
+        "show" => ReplCommand::Show,
+        "type" | "t" => ReplCommand::Type(argument),
+        "hir" => ReplCommand::Hir(argument),
+        other => ReplCommand::Unknown(other),
+    })
+}
+
+/// Whether the collected input closes every block, bracket and template it opens.
+///
+/// WHAT: counts `:` block openers against `;` closers outside templates, plus parentheses,
+/// curly braces, `|` parameter lists and template nesting.
+/// WHY: multi-line functions and templates are common, and sending them to the compiler line
+/// by line would only produce "missing `;`" errors.
+fn is_input_complete(input: &str) -> bool {
+    let Ok(style_directives) = StyleDirectiveRegistry::merged(&html_project_style_directives())
+    else {
+        return true;
+    };
+    let mut string_table = StringTable::new();
+    let Ok(tokens) = tokenize(
+        input,
+        &InternedPath::new(),
+        TokenizerEntryMode::SourceFile,
+        &style_directives,
+        &mut string_table,
+        None,
+    ) else {
+        // Let the compiler report the tokenizer error.
+        return true;
+    };
+
+    let mut blocks = 0i32;
+    let mut brackets = 0i32;
+    let mut open_bars = false;
+    let mut template_depth = 0i32;
+
+    for token in &tokens.tokens {
+        match token.kind {
+            TokenKind::TemplateHead => template_depth += 1,
+            TokenKind::TemplateClose => template_depth -= 1,
+            _ if template_depth > 0 => {}
+            TokenKind::Colon | TokenKind::DoubleColon => blocks += 1,
+            TokenKind::End => blocks -= 1,
+            TokenKind::OpenParenthesis | TokenKind::OpenCurly => brackets += 1,
+            TokenKind::CloseParenthesis | TokenKind::CloseCurly => brackets -= 1,
+            TokenKind::TypeParameterBracket => open_bars = !open_bars,
+            _ => {}
+        }
+    }
+
+    blocks <= 0 && brackets <= 0 && !open_bars && template_depth <= 0
+}
+
+#[cfg(test)]
+#[path = "tests/repl_tests.rs"]
+mod tests;
@@ -0,0 +1,322 @@
+//! REPL session state and snippet evaluation.
+//!
+//! WHAT: keeps the source accepted so far and compiles each new snippet appended to it as a
+//! single-file HTML project in a private temp directory. Expressions are first tried as a
+//! compile-time constant and read back from the folded top-level fragment. Otherwise they run
+//! through the JS backend in the shared Node runner that rendered-output assertions also use.
+//! WHY: compiling the whole accumulated file every time means each snippet is checked by exactly
+//! the frontend `bean build` uses, including borrow checking, with no REPL-only semantics.
+
+use crate::build_system::build::{FileKind, Module, ProjectBuilder, build_project};
+use crate::compiler_frontend::compiler_errors::CompilerMessages;
+use crate::compiler_frontend::datatypes::display::display_type;
+use crate::compiler_frontend::style_directives::StyleDirectiveRegistry;
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::compiler_frontend::tokenizer::lexer::tokenize;
+use crate::compiler_frontend::tokenizer::tokens::{TokenKind, TokenizerEntryMode};
+use crate::projects::check::{FrontendCheck, check_project_frontend};
+use crate::projects::html_project::html_project_builder::HtmlProjectBuilder;
+use crate::projects::html_project::style_directives::html_project_style_directives;
+use crate::projects::node_runner::{execute_html_in_node, extract_script_blocks};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Binding that holds the value of an expression snippet.
+const VALUE_BINDING: &str = "__repl_value";
+const SESSION_FILE_NAME: &str = "#page.bst";
+
+static SESSION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Result of evaluating one snippet.
+pub(crate) enum ReplOutcome {
+    /// An expression. `output` is console output produced while computing it.
+    Value { text: String, output: Vec<String> },
+    /// Declarations or statements that were added to the session.
+    Accepted { output: Vec<String> },
+    /// The snippet did not compile. The session is unchanged.
+    Rejected(CompilerMessages),
+    /// The snippet compiled but could not be run, e.g. because `node` is not installed.
+    RuntimeUnavailable(String),
+}
+
+pub(crate) struct ReplSession {
+    workspace: PathBuf,
+    source: String,
+    /// Console lines the accepted source already printed, so re-runs only show new output.
+    seen_output_lines: usize,
+}
+
+impl ReplSession {
+    pub(crate) fn new() -> Self {
+        let workspace = std::env::temp_dir().join(format!(
+            "bean_repl_{}_{}",
+            std::process::id(),
+            SESSION_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        Self {
+            workspace,
+            source: String::new(),
+            seen_output_lines: 0,
+        }
+    }
+
+    /// Source accepted so far.
+    pub(crate) fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.source.clear();
+        self.seen_output_lines = 0;
+    }
+
+    pub(crate) fn evaluate(&mut self, snippet: &str) -> ReplOutcome {
+        let snippet = snippet.trim_end();
+        let mut expression_messages = None;
+
+        if !looks_like_statement(snippet) {
+            let constant = self.with_snippet(&format!(
+                "{VALUE_BINDING} #= {snippet}\n[:[{VALUE_BINDING}]]"
+            ));
+            if let Ok(check) = self.check(&constant)
+                && let Some(fragment) = check
+                    .modules
+                    .iter()
+                    .flat_map(|module| &module.metadata.const_top_level_fragments)
+                    .last()
+            {
+                return ReplOutcome::Value {
+                    text: fragment.rendered_text.to_owned(),
+                    output: Vec::new(),
+                };
+            }
+
+            let runtime = self.with_snippet(&format!(
+                "{VALUE_BINDING} = {snippet}\nio.line([:[{VALUE_BINDING}]])"
+            ));
+            match self.check(&runtime) {
+                Ok(_) => {
+                    return match self.run(&runtime) {
+                        Ok(mut lines) => {
+                            let text = lines.pop().unwrap_or_default();
+                            ReplOutcome::Value {
+                                text,
+                                output: lines.split_off(self.seen_output_lines.min(lines.len())),
+                            }
+                        }
+                        Err(outcome) => outcome,
+                    };
+                }
+                Err(messages) => expression_messages = Some(messages),
+            }
+        }
+
+        let program = self.with_snippet(snippet);
+        if let Err(messages) = self.check(&program) {
+            return ReplOutcome::Rejected(expression_messages.unwrap_or(messages));
+        }
+
+        self.source = program;
+        match self.run(&self.source) {
+            Ok(mut lines) => {
+                let output = lines.split_off(self.seen_output_lines.min(lines.len()));
+                self.seen_output_lines += output.len();
+                ReplOutcome::Accepted { output }
+            }
+            Err(outcome) => outcome,
+        }
+    }
+
+    /// Inferred type of `expression` in the current session.
+    pub(crate) fn type_of(&self, expression: &str) -> Result<String, CompilerMessages> {
+        let program = self.with_snippet(&format!("{VALUE_BINDING} = {}", expression.trim()));
+        let check = self.check(&program)?;
+
+        Ok(check
+            .modules
+            .iter()
+            .find_map(|module| value_binding_type(module, &check.messages.string_table))
+            .unwrap_or_else(|| "<unknown type>".to_owned()))
+    }
+
+    /// HIR reachable from the session entry, plus an optional snippet. Expressions are bound to
+    /// a local. Library functions the session never calls are left out.
+    pub(crate) fn hir(&self, snippet: &str) -> Result<String, CompilerMessages> {
+        use crate::compiler_frontend::hir::hir_display::HirDisplayContext;
+        use crate::compiler_frontend::hir::reachability::collect_reachability_from_start;
+
+        let program = if snippet.trim().is_empty() || looks_like_statement(snippet) {
+            self.with_snippet(snippet)
+        } else {
+            self.with_snippet(&format!("{VALUE_BINDING} = {snippet}"))
+        };
+        let check = self.check(&program)?;
+        let string_table = &check.messages.string_table;
+
+        let mut rendered = Vec::new();
+        for module in &check.modules {
+            let hir = &module.executable.hir;
+            let reachability = collect_reachability_from_start(hir)
+                .map_err(|error| CompilerMessages::from_error_ref(error, string_table))?;
+            let display = HirDisplayContext::new(string_table)
+                .with_side_table(&hir.side_table)
+                .with_type_environment(&module.executable.type_environment);
+
+            rendered.extend(
+                hir.functions
+                    .iter()
+                    .filter(|function| reachability.reachable_functions.contains(&function.id))
+                    .map(|function| display.render_function(function)),
+            );
+            rendered.extend(
+                hir.blocks
+                    .iter()
+                    .filter(|block| reachability.reachable_blocks.contains(&block.id))
+                    .map(|block| display.render_block(block)),
+            );
+        }
+
+        Ok(rendered.join("\n"))
+    }
+
+    fn with_snippet(&self, snippet: &str) -> String {
+        let mut program = self.source.to_owned();
+        program.push_str(snippet.trim_end());
+        program.push('\n');
+        program
+    }
+
+    fn write_program(&self, program: &str) -> Result<String, CompilerMessages> {
+        let file = self.workspace.join(SESSION_FILE_NAME);
+        fs::create_dir_all(&self.workspace)
+            .and_then(|()| fs::write(&file, program))
+            .map_err(|error| {
+                CompilerMessages::file_error(
+                    &file,
+                    format!("Failed to write the REPL session file: {error}"),
+                    &StringTable::new(),
+                )
+            })?;
+
+        Ok(file.to_string_lossy().into_owned())
+    }
+
+    fn check(&self, program: &str) -> Result<FrontendCheck, CompilerMessages> {
+        let check = check_project_frontend(&self.write_program(program)?);
+        if check.messages.has_errors() {
+            return Err(check.messages);
+        }
+        Ok(check)
+    }
+
+    /// Build `program` with the HTML backend and return its console output.
+    fn run(&self, program: &str) -> Result<Vec<String>, ReplOutcome> {
+        let path = self.write_program(program).map_err(ReplOutcome::Rejected)?;
+        let builder = ProjectBuilder::new(Box::new(HtmlProjectBuilder::new()));
+        let build_result = build_project(&builder, &path, &[]).map_err(ReplOutcome::Rejected)?;
+
+        let Some(html) =
+            build_result
+                .project
+                .output_files
+                .iter()
+                .find_map(|output| match output.file_kind() {
+                    FileKind::Html(html) => Some(html),
+                    _ => None,
+                })
+        else {
+            return Ok(Vec::new());
+        };
+
+        // Snippets that fold completely leave no script to run.
+        if extract_script_blocks(html).is_empty() {
+            return Ok(Vec::new());
+        }
+
+        execute_html_in_node(html)
+            .map(|rendered| rendered.console_lines())
+            .map_err(ReplOutcome::RuntimeUnavailable)
+    }
+}
+
+impl Drop for ReplSession {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.workspace);
+    }
+}
+
+fn value_binding_type(module: &Module, string_table: &StringTable) -> Option<String> {
+    let hir = &module.executable.hir;
+
+    hir.blocks
+        .iter()
+        .flat_map(|block| &block.locals)
+        .find(|local| {
+            hir.side_table.resolve_local_name(local.id, string_table) == Some(VALUE_BINDING)
+        })
+        .map(|local| display_type(local.ty, &module.executable.type_environment, string_table))
+}
+
+/// Whether a snippet declares or mutates something rather than producing a value.
+///
+/// WHAT: bindings, assignments, blocks, choice declarations and imports are statements.
+/// WHY: only these may change the session, and trying them as an expression first would report
+/// an unrelated "expected expression" error when they contain a real mistake.
+pub(crate) fn looks_like_statement(snippet: &str) -> bool {
+    let first_word = snippet
+        .split(|character: char| !character.is_alphanumeric() && character != '_')
+        .find(|word| !word.is_empty())
+        .unwrap_or_default();
+    if matches!(
+        first_word,
+        "import" | "if" | "loop" | "return" | "break" | "continue"
+    ) {
+        return true;
+    }
+
+    let Ok(style_directives) = StyleDirectiveRegistry::merged(&html_project_style_directives())
+    else {
+        return false;
+    };
+    let mut string_table = StringTable::new();
+    let Ok(tokens) = tokenize(
+        snippet,
+        &InternedPath::new(),
+        TokenizerEntryMode::SourceFile,
+        &style_directives,
+        &mut string_table,
+        None,
+    ) else {
+        return false;
+    };
+
+    let mut template_depth = 0usize;
+    tokens.tokens.iter().any(|token| match &token.kind {
+        TokenKind::TemplateHead => {
+            template_depth += 1;
+            false
+        }
+        TokenKind::TemplateClose => {
+            template_depth = template_depth.saturating_sub(1);
+            false
+        }
+        kind => {
+            template_depth == 0
+                && (kind.is_assignment_operator()
+                    || matches!(
+                        kind,
+                        TokenKind::Mutable
+                            | TokenKind::Hash
+                            | TokenKind::Colon
+                            | TokenKind::DoubleColon
+                    ))
+        }
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/session_tests.rs"]
+mod tests;
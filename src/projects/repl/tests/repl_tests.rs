@@ -0,0 +1,43 @@
+//! Tests for REPL command parsing and multi-line input collection.
+
+use super::{ReplCommand, is_input_complete, parse_repl_command};
+
+#[test]
+fn commands_parse_names_aliases_and_arguments() {
+    assert_eq!(parse_repl_command(":help\n"), Some(ReplCommand::Help));
+    assert_eq!(parse_repl_command(":q\n"), Some(ReplCommand::Quit));
+    assert_eq!(parse_repl_command("exit\n"), Some(ReplCommand::Quit));
+    assert_eq!(parse_repl_command(":reset"), Some(ReplCommand::Reset));
+    assert_eq!(
+        parse_repl_command(":type  a + 1 \n"),
+        Some(ReplCommand::Type("a + 1"))
+    );
+    assert_eq!(parse_repl_command(":hir"), Some(ReplCommand::Hir("")));
+    assert_eq!(
+        parse_repl_command(":nope x"),
+        Some(ReplCommand::Unknown("nope"))
+    );
+    assert_eq!(parse_repl_command("x = 1\n"), None);
+}
+
+#[test]
+fn input_is_incomplete_until_blocks_brackets_and_templates_close() {
+    assert!(is_input_complete("x = 1\n"));
+    assert!(!is_input_complete("add |a Int, b Int| -> Int:\n"));
+    assert!(!is_input_complete(
+        "add |a Int, b Int| -> Int:\n    return a + b\n"
+    ));
+    assert!(is_input_complete(
+        "add |a Int, b Int| -> Int:\n    return a + b\n;\n"
+    ));
+    assert!(!is_input_complete("total = add(1,\n"));
+    assert!(!is_input_complete("Point = |\n    x Int,\n"));
+    assert!(!is_input_complete("Status ::\n    Ready,\n"));
+}
+
+#[test]
+fn colons_inside_templates_do_not_open_blocks() {
+    assert!(is_input_complete("greeting = [: hello]\n"));
+    assert!(!is_input_complete("page = [:\n    hello\n"));
+    assert!(is_input_complete("page = [:\n    hello\n]\n"));
+}
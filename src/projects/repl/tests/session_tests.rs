@@ -0,0 +1,106 @@
+//! Tests for REPL snippet evaluation and session state.
+
+use super::{ReplOutcome, ReplSession, looks_like_statement};
+
+fn expect_value(outcome: ReplOutcome) -> String {
+    match outcome {
+        ReplOutcome::Value { text, .. } => text,
+        ReplOutcome::Accepted { .. } => panic!("expected a value, the snippet was accepted"),
+        ReplOutcome::Rejected(messages) => panic!(
+            "expected a value, got {} compiler error(s)",
+            messages.error_count()
+        ),
+        ReplOutcome::RuntimeUnavailable(reason) => panic!("expected a value, got: {reason}"),
+    }
+}
+
+#[test]
+fn statements_are_recognised_by_their_tokens() {
+    assert!(looks_like_statement("x = 1"));
+    assert!(looks_like_statement("count ~= 0"));
+    assert!(looks_like_statement("limit #= 10"));
+    assert!(looks_like_statement(
+        "double |n Int| -> Int:\n    return n * 2\n;"
+    ));
+    assert!(looks_like_statement("import @core/math"));
+    assert!(!looks_like_statement("1 + 2"));
+    assert!(!looks_like_statement("double(4)"));
+    assert!(!looks_like_statement("[: total = [1 + 2]]"));
+}
+
+#[test]
+fn constant_expressions_fold_without_running_code() {
+    let mut session = ReplSession::new();
+
+    assert_eq!(expect_value(session.evaluate("1 + 2 * 3\n")), "7");
+    assert_eq!(expect_value(session.evaluate("[:hello]\n")), "hello");
+}
+
+#[test]
+fn declarations_accumulate_and_reset_clears_them() {
+    let mut session = ReplSession::new();
+
+    assert!(matches!(
+        session.evaluate("base #= 40\n"),
+        ReplOutcome::Accepted { .. }
+    ));
+    assert_eq!(expect_value(session.evaluate("base + 2\n")), "42");
+    assert_eq!(session.source(), "base #= 40\n");
+
+    session.reset();
+
+    assert!(matches!(
+        session.evaluate("base + 2\n"),
+        ReplOutcome::Rejected(_)
+    ));
+}
+
+#[test]
+fn rejected_snippets_leave_the_session_unchanged() {
+    let mut session = ReplSession::new();
+
+    let ReplOutcome::Rejected(messages) = session.evaluate("x Int = \"text\"\n") else {
+        panic!("a type mismatch should be rejected");
+    };
+    assert!(messages.has_errors());
+    assert!(session.source().is_empty());
+}
+
+#[test]
+fn type_command_reports_the_inferred_type() {
+    let mut session = ReplSession::new();
+    assert!(matches!(
+        session.evaluate("name #= \"bean\"\n"),
+        ReplOutcome::Accepted { .. }
+    ));
+
+    assert_eq!(
+        session
+            .type_of("name")
+            .unwrap_or_else(|_| panic!("name should type check")),
+        "String"
+    );
+    assert_eq!(
+        session
+            .type_of("2.5")
+            .unwrap_or_else(|_| panic!("literal should type check")),
+        "Float"
+    );
+    assert!(session.type_of("missing").is_err());
+}
+
+#[test]
+fn hir_command_renders_reachable_functions_in_default_builds() {
+    let mut session = ReplSession::new();
+    assert!(matches!(
+        session.evaluate("double |n Int| -> Int:\n    return n * 2\n;\n"),
+        ReplOutcome::Accepted { .. }
+    ));
+
+    let hir = session
+        .hir("double(4)")
+        .unwrap_or_else(|_| panic!("the snippet should lower to HIR"));
+    assert!(hir.contains("double(n) -> Int"), "{hir}");
+    assert!(hir.contains("call double("), "{hir}");
+    assert!(session.hir("missing(1)").is_err());
+}
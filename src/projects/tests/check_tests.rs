@@ -1,7 +1,10 @@
 //! Tests for the frontend-only `check` command flow.
 
-use super::{execute_check, format_terse_summary_line};
+use super::{check_project_frontend, execute_check, format_terse_summary_line};
 use crate::build_system::build::{ProjectBuilder, build_project};
+use crate::build_system::create_project_modules::source_loading::{
+    SourceOverlay, with_source_overlay,
+};
 use crate::compiler_frontend::compiler_messages::CompilerDiagnostic;
 use crate::compiler_frontend::compiler_messages::render::{
     display_line_number, relative_display_path_from_root, resolve_source_file_path,
@@ -43,6 +46,35 @@ fn check_compiles_single_file_without_writing_artifacts() {
     fs::remove_dir_all(&root).expect("should remove temp dir");
 }
 
+#[test]
+fn source_overlay_replaces_disk_text_only_inside_its_scope() {
+    let root = temp_dir("source_overlay");
+    fs::create_dir_all(&root).expect("should create temp root");
+    let entry_file = root.join("main.bst");
+    fs::write(&entry_file, "value = 1\n").expect("should write source file");
+    let entry_file = entry_file
+        .canonicalize()
+        .expect("temp file should canonicalize");
+    let entry_path = entry_file
+        .to_str()
+        .expect("temp file path should be valid UTF-8 for this test")
+        .to_owned();
+
+    let overlay = SourceOverlay::from([(entry_file, String::from("value = missing_name\n"))]);
+    let overlaid = with_source_overlay(overlay, || check_project_frontend(&entry_path));
+    assert!(
+        overlaid.messages.has_errors(),
+        "the check should compile the overlay text instead of the saved file"
+    );
+
+    assert!(
+        !check_project_frontend(&entry_path).messages.has_errors(),
+        "the saved file should be read again once the overlay scope ends"
+    );
+
+    fs::remove_dir_all(&root).expect("should remove temp dir");
+}
+
 /// Stable source-facing identity for one frontend diagnostic.
 type DiagnosticIdentityRow = (&'static str, Option<&'static str>, String, i32);
 
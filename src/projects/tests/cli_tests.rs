@@ -1,11 +1,12 @@
 //! Tests for CLI command parsing and validation.
 
 use super::{
-    Command, build_warnings_messages, get_command, help_build_flag_entries,
-    integration_tests_exit_code, is_standalone_version_request,
+    BuildTarget, Command, build_warnings_messages, get_command, help_build_flag_entries,
+    integration_tests_exit_code, is_standalone_version_request, resolve_build_target,
 };
 use crate::build_system::build::{BuildResult, CleanupPolicy, FileKind, OutputFile, Project};
 use crate::compiler_frontend::Flag;
+use crate::compiler_frontend::compiler_messages::display_messages::MessageFormat;
 use crate::compiler_frontend::compiler_messages::{
     CompilerDiagnostic, DiagnosticKind, DiagnosticPayload, DiagnosticSeverity, RuleDiagnosticKind,
 };
@@ -17,6 +18,7 @@ use crate::compiler_tests::integration_test_runner::{
 use crate::projects::dev_server::DevServerOptions;
 use crate::projects::html_project::new_html_project::NewHtmlProjectOptions;
 use crate::projects::settings::Config;
+use std::fs;
 use std::path::PathBuf;
 
 fn args(values: &[&str]) -> Vec<String> {
@@ -44,6 +46,8 @@ fn build_command_uses_current_directory_when_path_is_missing() {
         Command::Build {
             path: String::new(),
             flags: Vec::new(),
+            message_format: MessageFormat::Human,
+            target: None,
         }
     );
 }
@@ -57,6 +61,8 @@ fn build_command_supports_mixed_path_and_flag_ordering() {
         Command::Build {
             path: String::from("main.bst"),
             flags: vec![Flag::Release],
+            message_format: MessageFormat::Human,
+            target: None,
         }
     );
 }
@@ -70,6 +76,125 @@ fn build_command_rejects_unknown_flags() {
     assert!(error.contains("--html-wasm"));
 }
 
+#[test]
+fn build_command_parses_target_flag() {
+    assert_eq!(
+        get_command(&args(&["build", "tool.bst", "--target", "wasi"]))
+            .expect("wasi target should parse"),
+        Command::Build {
+            path: String::from("tool.bst"),
+            flags: Vec::new(),
+            message_format: MessageFormat::Human,
+            target: Some(BuildTarget::Wasi),
+        }
+    );
+    assert_eq!(BuildTarget::parse("js"), Some(BuildTarget::Js));
+
+    for value in [&["--target", "node"][..], &["--target"][..]] {
+        let mut command = vec!["build"];
+        command.extend_from_slice(value);
+        let error = get_command(&args(&command)).expect_err("invalid target should fail");
+        assert!(error.contains("Expected 'html', 'wasi' or 'js'"), "{error}");
+    }
+}
+
+#[test]
+fn build_target_defaults_to_the_config_project_value() {
+    let project = tempfile::tempdir().expect("temp dir should be created");
+    fs::write(project.path().join("config.bst"), "project #= \"wasi\"\n")
+        .expect("config should be written");
+    let path = project.path().to_string_lossy().into_owned();
+
+    assert_eq!(resolve_build_target(None, &path), BuildTarget::Wasi);
+    assert_eq!(
+        resolve_build_target(Some(BuildTarget::Html), &path),
+        BuildTarget::Html
+    );
+    assert_eq!(
+        resolve_build_target(None, "missing/entry.bst"),
+        BuildTarget::Html
+    );
+}
+
+#[test]
+fn lsp_command_accepts_optional_stdio_flag() {
+    assert_eq!(
+        get_command(&args(&["lsp"])).expect("lsp command should parse"),
+        Command::LanguageServer
+    );
+    assert_eq!(
+        get_command(&args(&["lsp", "--stdio"])).expect("lsp --stdio should parse"),
+        Command::LanguageServer
+    );
+}
+
+#[test]
+fn lsp_command_rejects_other_arguments() {
+    let error = get_command(&args(&["lsp", "--socket=9000"]))
+        .expect_err("unsupported lsp transport should fail");
+    assert!(error.contains("--stdio"));
+
+    get_command(&args(&["lsp", "--stdio", "extra"])).expect_err("extra arguments should fail");
+}
+
+#[test]
+fn repl_command_takes_no_arguments() {
+    assert_eq!(
+        get_command(&args(&["repl"])).expect("repl command should parse"),
+        Command::Repl
+    );
+    get_command(&args(&["repl", "main.bst"])).expect_err("repl arguments should fail");
+}
+
+#[test]
+fn fix_command_parses_path_and_dry_run_in_any_order() {
+    assert_eq!(
+        get_command(&args(&["fix"])).expect("fix command should parse"),
+        Command::Fix {
+            path: String::new(),
+            dry_run: false,
+        }
+    );
+    assert_eq!(
+        get_command(&args(&["fix", "--dry-run", "src/main.bst"]))
+            .expect("fix --dry-run should parse"),
+        Command::Fix {
+            path: String::from("src/main.bst"),
+            dry_run: true,
+        }
+    );
+}
+
+#[test]
+fn fix_command_rejects_unknown_flags_and_extra_paths() {
+    let error = get_command(&args(&["fix", "--all"])).expect_err("unknown fix flag should fail");
+    assert!(error.contains("--dry-run"));
+
+    get_command(&args(&["fix", "a.bst", "b.bst"])).expect_err("extra paths should fail");
+}
+
+#[test]
+fn fmt_command_parses_path_and_check_flag() {
+    assert_eq!(
+        get_command(&args(&["fmt"])).expect("fmt command should parse"),
+        Command::Fmt {
+            path: String::new(),
+            check: false,
+        }
+    );
+    assert_eq!(
+        get_command(&args(&["fmt", "src", "--check"])).expect("fmt --check should parse"),
+        Command::Fmt {
+            path: String::from("src"),
+            check: true,
+        }
+    );
+
+    let error = get_command(&args(&["fmt", "--write"])).expect_err("unknown fmt flag should fail");
+    assert!(error.contains("--check"));
+    get_command(&args(&["fmt", "a.bst", "b.bst"])).expect_err("extra paths should fail");
+}
+
 #[test]
 fn new_html_command_uses_current_directory_when_path_is_missing() {
     let command = get_command(&args(&["new", "html"])).expect("new html command should parse");
@@ -394,6 +519,7 @@ fn check_command_uses_default_options() {
         Command::Check {
             path: String::new(),
             terse: false,
+            message_format: MessageFormat::Human,
         }
     );
 }
@@ -407,6 +533,7 @@ fn check_command_parses_path_and_terse_flag() {
         Command::Check {
             path: String::from("main.bst"),
             terse: true,
+            message_format: MessageFormat::Human,
         }
     );
 }
@@ -420,6 +547,7 @@ fn check_command_supports_mixed_argument_ordering() {
         Command::Check {
             path: String::from("main.bst"),
             terse: true,
+            message_format: MessageFormat::Human,
         }
     );
 }
@@ -431,6 +559,59 @@ fn check_command_rejects_multiple_paths() {
     assert!(error.contains("at most one path"));
 }
 
+#[test]
+fn check_command_parses_json_message_format() {
+    let command = get_command(&args(&["check", "--message-format", "json", "main.bst"]))
+        .expect("check command should parse json message format");
+    assert_eq!(
+        command,
+        Command::Check {
+            path: String::from("main.bst"),
+            terse: false,
+            message_format: MessageFormat::Json,
+        }
+    );
+}
+
+#[test]
+fn check_command_rejects_terse_with_json_message_format() {
+    let error = get_command(&args(&["check", "--terse", "--message-format", "json"]))
+        .expect_err("terse and json output should conflict");
+    assert!(error.contains("--terse"));
+}
+
+#[test]
+fn message_format_rejects_missing_and_unknown_values() {
+    let missing = get_command(&args(&["build", "--message-format"]))
+        .expect_err("missing message format should fail");
+    assert!(missing.contains("Missing value for --message-format"));
+
+    let unknown = get_command(&args(&["check", "--message-format", "xml"]))
+        .expect_err("unknown message format should fail");
+    assert!(unknown.contains("human and json"));
+}
+
+#[test]
+fn build_command_parses_json_message_format_with_flags() {
+    let command = get_command(&args(&[
+        "build",
+        "--release",
+        "--message-format",
+        "json",
+        "main.bst",
+    ]))
+    .expect("build command should parse json message format");
+    assert_eq!(
+        command,
+        Command::Build {
+            path: String::from("main.bst"),
+            flags: vec![Flag::Release],
+            message_format: MessageFormat::Json,
+            target: None,
+        }
+    );
+}
+
 #[test]
 fn build_command_returns_exact_flags() {
     let release = get_command(&args(&["build", "--release"])).expect("release flag should parse");
@@ -439,6 +620,8 @@ fn build_command_returns_exact_flags() {
         Command::Build {
             path: String::new(),
             flags: vec![Flag::Release],
+            message_format: MessageFormat::Human,
+            target: None,
         }
     );
 
@@ -448,6 +631,8 @@ fn build_command_returns_exact_flags() {
         Command::Build {
             path: String::new(),
             flags: vec![Flag::HtmlWasm],
+            message_format: MessageFormat::Human,
+            target: None,
         }
     );
 
@@ -458,6 +643,8 @@ fn build_command_returns_exact_flags() {
         Command::Build {
             path: String::new(),
             flags: vec![Flag::Release, Flag::HtmlWasm],
+            message_format: MessageFormat::Human,
+            target: None,
         }
     );
 }
@@ -625,6 +812,7 @@ fn build_result_with_warnings(warnings: Vec<CompilerDiagnostic>) -> BuildResult
         config: Config::new(PathBuf::from("main.bst")),
         warnings,
         string_table: StringTable::new(),
+        module_reuse: None,
     }
 }
 
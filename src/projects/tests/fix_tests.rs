@@ -0,0 +1,173 @@
+//! Tests for `bean fix` edit application and the check-apply-recheck loop.
+
+use super::{FixOptions, apply_suggested_edits, execute_fix, unified_diff};
+use crate::compiler_frontend::compiler_messages::source_location::{CharPosition, SourceLocation};
+use crate::compiler_frontend::compiler_messages::suggested_edits::{
+    SuggestedEdit, SuggestedEditTarget,
+};
+use crate::compiler_frontend::symbols::interned_path::InternedPath;
+use crate::compiler_tests::test_support::temp_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn edit_at(line_number: i32, char_column: i32, original: &str, replacement: &str) -> SuggestedEdit {
+    SuggestedEdit {
+        location: SourceLocation::new(
+            InternedPath::new(),
+            CharPosition {
+                line_number,
+                char_column,
+            },
+            CharPosition {
+                line_number,
+                char_column,
+            },
+        ),
+        target: SuggestedEditTarget::Text(original.to_owned()),
+        replacement: replacement.to_owned(),
+    }
+}
+
+fn write_single_file(prefix: &str, source: &str) -> (PathBuf, PathBuf) {
+    let root = temp_dir(prefix);
+    fs::create_dir_all(&root).expect("should create temp root");
+    let file = root.join("main.bst");
+    fs::write(&file, source).expect("should write source file");
+    (root, file)
+}
+
+fn remove_root(root: &Path) {
+    fs::remove_dir_all(root).expect("should remove temp dir");
+}
+
+#[test]
+fn edits_apply_in_place_and_separate_words_from_neighbours() {
+    let source = "ready = a&&b\nsame = x == y\n";
+    let edits = [edit_at(0, 10, "&&", "and"), edit_at(1, 10, "==", "is")];
+
+    let (fixed, applied) = apply_suggested_edits(source, &edits);
+
+    assert_eq!(applied, 2);
+    assert_eq!(fixed, "ready = a and b\nsame = x is y\n");
+}
+
+#[test]
+fn stale_and_overlapping_edits_are_skipped() {
+    let source = "flag = x != y\n";
+    let edits = [
+        edit_at(0, 10, "!=", "is not"),
+        // Overlaps the first edit.
+        edit_at(0, 10, "!", "not"),
+        // The expected text is no longer at this position.
+        edit_at(0, 1, "&&", "and"),
+    ];
+
+    let (fixed, applied) = apply_suggested_edits(source, &edits);
+
+    assert_eq!(applied, 1);
+    assert_eq!(fixed, "flag = x is not y\n");
+}
+
+#[test]
+fn unified_diff_reports_changed_lines_with_context() {
+    let before = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+    let after = "a\nB\nc\nd\ne\nf\ng\nh\ni\nJ\n";
+
+    let diff = unified_diff("main.bst", before, after);
+
+    assert_eq!(
+        diff,
+        "--- a/main.bst\n+++ b/main.bst\n\
+         @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+         @@ -7,4 +7,4 @@\n g\n h\n i\n-j\n+J\n"
+    );
+}
+
+#[test]
+fn fix_rewrites_common_syntax_mistakes_until_the_file_checks() {
+    let (root, file) = write_single_file(
+        "fix_common_mistakes",
+        "x = 1\ny = 2\nsame = x == y\nboth = true && false\n\nlog ||:\n    // note\n;\n",
+    );
+
+    let outcome = execute_fix(
+        file.to_str().expect("temp path should be UTF-8"),
+        FixOptions::default(),
+    )
+    .unwrap_or_else(|_| panic!("fix should run"));
+
+    let fixed = fs::read_to_string(&file).expect("should read fixed source");
+    assert_eq!(
+        fixed,
+        "x = 1\ny = 2\nsame = x is y\nboth = true and false\n\nlog ||:\n    -- note\n;\n"
+    );
+    assert_eq!(outcome.files.len(), 1);
+    assert_eq!(outcome.files[0].applied_edits, 3);
+    assert!(
+        !outcome.messages.has_errors(),
+        "the fixed file should check cleanly"
+    );
+
+    remove_root(&root);
+}
+
+#[test]
+fn fix_adds_missing_import_path_prefix() {
+    let (root, file) =
+        write_single_file("fix_missing_at_prefix", "import utils/helpers\n\nx = 1\n");
+
+    execute_fix(
+        file.to_str().expect("temp path should be UTF-8"),
+        FixOptions::default(),
+    )
+    .unwrap_or_else(|_| panic!("fix should run"));
+
+    assert_eq!(
+        fs::read_to_string(&file).expect("should read fixed source"),
+        "import @utils/helpers\n\nx = 1\n"
+    );
+
+    remove_root(&root);
+}
+
+#[test]
+fn dry_run_plans_edits_without_writing() {
+    let source = "x = 1\ny = 2\nsame = x == y\n";
+    let (root, file) = write_single_file("fix_dry_run", source);
+
+    let outcome = execute_fix(
+        file.to_str().expect("temp path should be UTF-8"),
+        FixOptions { dry_run: true },
+    )
+    .unwrap_or_else(|_| panic!("fix should run"));
+
+    assert_eq!(
+        fs::read_to_string(&file).expect("should read source"),
+        source
+    );
+    assert_eq!(outcome.files.len(), 1);
+    assert_eq!(outcome.files[0].fixed, "x = 1\ny = 2\nsame = x is y\n");
+
+    remove_root(&root);
+}
+
+#[test]
+fn fix_leaves_guidance_only_diagnostics_untouched() {
+    let source = "value = missing_name\n";
+    let (root, file) = write_single_file("fix_guidance_only", source);
+
+    let outcome = execute_fix(
+        file.to_str().expect("temp path should be UTF-8"),
+        FixOptions::default(),
+    )
+    .unwrap_or_else(|_| panic!("fix should run"));
+
+    assert!(outcome.files.is_empty());
+    assert!(outcome.messages.has_errors());
+    assert_eq!(
+        fs::read_to_string(&file).expect("should read source"),
+        source
+    );
+
+    remove_root(&root);
+}
@@ -0,0 +1,6 @@
+//! WASI command project backend.
+//!
+//! WHAT: builds each active module into a standalone WASI preview1 `.wasm` command.
+//! WHY: tooling scripts run under any local WASI runtime, without an HTML page or JS glue.
+
+pub mod wasi_project_builder;
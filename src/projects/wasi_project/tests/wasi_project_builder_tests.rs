@@ -0,0 +1,246 @@
+//! Tests for the WASI command project builder.
+
+use super::*;
+use crate::build_system::build::{ProjectBuilder, build_project};
+use crate::compiler_frontend::compiler_messages::diagnostic_payload::DiagnosticPayload;
+use crate::compiler_tests::test_support::temp_dir;
+use std::io::Write as _;
+use std::process::Stdio;
+
+fn fragment(runtime_insertion_index: usize, text: &str) -> ResolvedConstFragment {
+    ResolvedConstFragment {
+        runtime_insertion_index,
+        rendered_text: text.to_owned(),
+    }
+}
+
+/// Builds `source` as a single-file WASI project and returns the emitted command.
+fn build_command(name: &str, source: &str) -> Result<(PathBuf, Vec<u8>), CompilerMessages> {
+    let root = temp_dir(name);
+    fs::create_dir_all(&root).expect("should create temp root");
+    let entry = root.join("tool.bst");
+    fs::write(&entry, source).expect("should write source");
+
+    let builder = ProjectBuilder::new(Box::new(WasiProjectBuilder::new()));
+    let result = build_project(&builder, entry.to_str().expect("UTF-8 path"), &[]);
+    fs::remove_dir_all(&root).expect("should remove temp root");
+
+    let project = result?.project;
+    let [output] = project.output_files.as_slice() else {
+        panic!("a single-file WASI build should emit exactly one file");
+    };
+    let FileKind::Wasm(bytes) = output.file_kind() else {
+        panic!("the WASI command should be a Wasm file");
+    };
+    Ok((output.relative_output_path().to_path_buf(), bytes.clone()))
+}
+
+/// Runs a command under Node's WASI preview1 host and returns its stdout and stderr lines.
+fn run_wasi_command(wasm_bytes: &[u8], stdin: &str) -> (Vec<String>, Vec<String>) {
+    let directory = tempfile::tempdir().expect("temp dir should be created");
+    let wasm_path = directory.path().join("tool.wasm");
+    fs::write(&wasm_path, wasm_bytes).expect("wasm bytes should be written");
+
+    let harness = format!(
+        "const fs = require('fs');\n\
+         const {{ WASI }} = require('node:wasi');\n\
+         const wasi = new WASI({{ version: 'preview1', args: [], env: {{}} }});\n\
+         const wasmModule = new WebAssembly.Module(fs.readFileSync({wasm_path}));\n\
+         const instance = new WebAssembly.Instance(wasmModule, wasi.getImportObject());\n\
+         wasi.start(instance);\n",
+        wasm_path =
+            serde_json::to_string(&wasm_path.to_string_lossy()).expect("path should serialize"),
+    );
+    let harness_path = directory.path().join("harness.cjs");
+    fs::write(&harness_path, harness).expect("harness should be written");
+
+    let mut child = std::process::Command::new("node")
+        .arg("--no-warnings")
+        .arg(&harness_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("node should be on PATH to run WASI command tests");
+    child
+        .stdin
+        .take()
+        .expect("stdin should be piped")
+        .write_all(stdin.as_bytes())
+        .expect("stdin should be written");
+    let output = child.wait_with_output().expect("node should finish");
+    assert!(
+        output.status.success(),
+        "WASI command failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let lines = |bytes: &[u8]| {
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    };
+    (lines(&output.stdout), lines(&output.stderr))
+}
+
+#[test]
+fn command_output_interleaves_const_and_runtime_fragments_in_source_order() {
+    let output = command_output(
+        &[
+            fragment(2, "footer"),
+            fragment(0, "header"),
+            fragment(1, "middle"),
+        ],
+        2,
+    );
+
+    assert_eq!(
+        output,
+        vec![
+            WasmCommandOutput::Const("header".to_owned()),
+            WasmCommandOutput::RuntimeFragment(0),
+            WasmCommandOutput::Const("middle".to_owned()),
+            WasmCommandOutput::RuntimeFragment(1),
+            WasmCommandOutput::Const("footer".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn output_paths_follow_the_entry_stem_or_module_directory() {
+    assert_eq!(
+        wasi_output_path(Path::new("/work/#tool.bst"), None).expect("stem should resolve"),
+        PathBuf::from("tool.wasm")
+    );
+
+    let root = Path::new("/work/scripts");
+    assert_eq!(
+        wasi_output_path(&root.join("#page.bst"), Some(root)).expect("root should resolve"),
+        PathBuf::from("main.wasm")
+    );
+    assert_eq!(
+        wasi_output_path(&root.join("fmt/#page.bst"), Some(root)).expect("nested should resolve"),
+        PathBuf::from("fmt/main.wasm")
+    );
+}
+
+#[test]
+fn command_prints_console_output_and_fragments_in_order() {
+    let (output_path, wasm_bytes) = build_command(
+        "wasi_console",
+        "import @core/io\n\
+         import @core/time { duration_from_seconds, as_milliseconds }\n\
+         \n\
+         io.line(\"hello from wasi\")\n\
+         io.error(\"to stderr\")\n\
+         millis = as_milliseconds(duration_from_seconds(1.5))\n\
+         io.line([:millis=[millis]])\n\
+         [:const fragment]\n\
+         count = 2\n\
+         [:runtime [count]]\n",
+    )
+    .expect("WASI build should succeed");
+    assert_eq!(output_path, PathBuf::from("tool.wasm"));
+
+    let (stdout, stderr) = run_wasi_command(&wasm_bytes, "");
+
+    assert_eq!(
+        stdout,
+        vec![
+            "hello from wasi",
+            "millis=1500",
+            "const fragment",
+            "runtime 2"
+        ]
+    );
+    assert_eq!(stderr, vec!["to stderr"]);
+}
+
+#[test]
+fn input_polling_treats_each_stdin_line_as_one_key_press() {
+    let (_, wasm_bytes) = build_command(
+        "wasi_input_polling",
+        "import @core/io\n\
+         \n\
+         poll || -> String, Error!:\n\
+         \x20   input ~= io.input.new()!\n\
+         \x20   io.input.update(~input)\n\
+         \x20   if io.input.key_pressed(input, \"d\"):\n\
+         \x20       io.line(\"pressed d\")\n\
+         \x20   ;\n\
+         \x20   io.input.update(~input)\n\
+         \x20   if io.input.key_released(input, \"D\"):\n\
+         \x20       io.line(\"released d\")\n\
+         \x20   ;\n\
+         \x20   if io.input.key_down(input, \"Enter\"):\n\
+         \x20       io.line(\"enter down\")\n\
+         \x20   ;\n\
+         \x20   io.input.update(~input)\n\
+         \x20   if io.input.key_pressed(input, \" \"):\n\
+         \x20       io.line(\"pressed space\")\n\
+         \x20   ;\n\
+         \x20   io.input.update(~input)\n\
+         \x20   if io.input.key_down(input, \"Space\"):\n\
+         \x20       io.line(\"space still down\")\n\
+         \x20   ;\n\
+         \x20   if io.input.key_released(input, \"Space\"):\n\
+         \x20       io.line(\"released space\")\n\
+         \x20   ;\n\
+         \x20   if io.input.pointer_down(input, \"left\"):\n\
+         \x20       io.line(\"pointer down\")\n\
+         \x20   ;\n\
+         \x20   io.input.close(~input)\n\
+         \x20   return \"closed\"\n\
+         ;\n\
+         \n\
+         result = poll() catch:\n\
+         \x20   then \"error\"\n\
+         ;\n\
+         io.line(result)\n",
+    )
+    .expect("WASI build should succeed");
+
+    let (stdout, _) = run_wasi_command(&wasm_bytes, "D\n\r\n \n");
+
+    assert_eq!(
+        stdout,
+        vec![
+            "pressed d",
+            "released d",
+            "enter down",
+            "pressed space",
+            "released space",
+            "closed"
+        ]
+    );
+}
+
+#[test]
+fn functions_without_wasi_adapters_are_rejected() {
+    let messages = build_command(
+        "wasi_rejects_iso_strings",
+        "import @core/io\n\
+         import @core/time { timestamp_from_unix_seconds, to_iso_string }\n\
+         \n\
+         io.line(to_iso_string(timestamp_from_unix_seconds(0.0)))\n",
+    )
+    .expect_err("ISO formatting needs a host date library");
+
+    let error = messages
+        .first_error()
+        .expect("expected an error diagnostic");
+    let DiagnosticPayload::UnsupportedExternalFunction {
+        function_name,
+        backend_name,
+        ..
+    } = &error.payload
+    else {
+        panic!("expected an unsupported external function diagnostic, got {error:?}");
+    };
+    assert_eq!(
+        messages.string_table.resolve(*function_name),
+        "to_iso_string"
+    );
+    assert_eq!(messages.string_table.resolve(*backend_name), "WASI");
+}
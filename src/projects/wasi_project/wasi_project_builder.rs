@@ -0,0 +1,289 @@
+//! WASI command project builder.
+//!
+//! WHAT: lowers every active module through the Wasm backend with the WASI host interface and
+//! writes one `.wasm` command per module.
+//! WHY: the entry start function and its top-level fragments become the command's `_start`,
+//! so a Beanstalk file runs like a script: fragments print to stdout in source order.
+use crate::backends::backend_feature_validation::{
+    BackendFeatureValidationError, BackendFeatureValidationInput, BackendFeatureValidationRoot,
+    validate_hir_backend_feature_support,
+};
+use crate::backends::external_package_validation::{
+    BackendTarget, ExternalPackageValidationError, validate_hir_external_package_support,
+};
+use crate::backends::wasm::backend::lower_hir_to_wasm_module;
+use crate::backends::wasm::request::{
+    WasmBackendRequest, WasmCommandOutput, WasmCommandStart, WasmExportPolicy,
+    WasmFunctionEmissionPolicy, WasmHelperExportPolicy, WasmHostInterface, WasmTargetFeatures,
+};
+use crate::build_system::build::{
+    BackendBuilder, CleanupPolicy, EntryFragment, FileKind, Module, OutputFile, Project,
+    ResolvedConstFragment, order_entry_fragments,
+};
+use crate::build_system::create_project_modules::resolve_project_entry_root;
+use crate::builder_surface::BuilderSurface;
+use crate::compiler_frontend::Flag;
+use crate::compiler_frontend::compiler_errors::{CompilerError, CompilerMessages};
+use crate::compiler_frontend::style_directives::StyleDirectiveSpec;
+use crate::compiler_frontend::symbols::string_interning::StringTable;
+use crate::projects::settings::{Config, ProjectConfigError};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Output file name of a directory-build module command.
+const WASI_MODULE_OUTPUT_NAME: &str = "main.wasm";
+
+#[derive(Debug, Default)]
+pub struct WasiProjectBuilder;
+
+impl WasiProjectBuilder {
+    /// Constructs the WASI command builder.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BackendBuilder for WasiProjectBuilder {
+    fn build_backend(
+        &self,
+        modules: Vec<Module>,
+        config: &Config,
+        _flags: &[Flag],
+        string_table: &mut StringTable,
+    ) -> Result<Project, CompilerMessages> {
+        let _total_guard = crate::timing::PipelineTimingGuard::new("backend.wasi.total");
+
+        if modules.is_empty() {
+            return Err(CompilerMessages::from_error(
+                CompilerError::compiler_error(
+                    "WASI builder expected at least one compiled module but got 0.",
+                ),
+                string_table.clone(),
+            ));
+        }
+
+        let entry_root = resolve_canonical_entry_root(config, string_table)?;
+        let mut output_files = Vec::new();
+        let mut output_path_owners: HashMap<PathBuf, PathBuf> = HashMap::new();
+        let mut entry_page_rel = None;
+
+        for module in modules
+            .iter()
+            .filter(|module| module.metadata.root_activity.has_html_artifact_activity())
+        {
+            let output_path = wasi_output_path(&module.metadata.entry_point, entry_root.as_deref())
+                .map_err(|error| CompilerMessages::from_error(error, string_table.clone()))?;
+            if let Some(existing_entry_point) = output_path_owners.get(&output_path) {
+                return Err(CompilerMessages::from_error(
+                    CompilerError::compiler_error(format!(
+                        "WASI modules '{}' and '{}' both compile to '{}'.",
+                        existing_entry_point.display(),
+                        module.metadata.entry_point.display(),
+                        output_path.display()
+                    )),
+                    string_table.clone(),
+                ));
+            }
+            output_path_owners.insert(output_path.clone(), module.metadata.entry_point.clone());
+
+            let wasm_bytes = compile_wasi_module(module, string_table)?;
+            // The root module's command is the project entry.
+            if output_path.parent() == Some(Path::new("")) {
+                entry_page_rel = Some(output_path.clone());
+            }
+            output_files.push(OutputFile::new(output_path, FileKind::Wasm(wasm_bytes)));
+        }
+
+        Ok(Project {
+            output_files,
+            entry_page_rel,
+            cleanup_policy: CleanupPolicy::generic([".wasm"]),
+            warnings: Vec::new(),
+        })
+    }
+
+    fn validate_project_config(
+        &self,
+        _config: &Config,
+        _string_table: &mut StringTable,
+    ) -> Result<(), ProjectConfigError> {
+        // Commands have no backend-specific config keys; core keys are validated in Stage 0.
+        Ok(())
+    }
+
+    fn frontend_style_directives(&self) -> Vec<StyleDirectiveSpec> {
+        Vec::new()
+    }
+
+    fn frontend_surface(&self) -> BuilderSurface {
+        let mut builder_surface = BuilderSurface::with_mandatory_core();
+        builder_surface.expose_wasi_core_packages();
+        builder_surface
+    }
+}
+
+/// Validates and lowers one module to WASI command bytes.
+fn compile_wasi_module(
+    module: &Module,
+    string_table: &mut StringTable,
+) -> Result<Vec<u8>, CompilerMessages> {
+    let hir_module = &module.executable.hir;
+
+    validate_hir_external_package_support(
+        hir_module,
+        module.link_facts.external_package_registry.as_ref(),
+        BackendTarget::Wasi,
+        string_table,
+    )
+    .map_err(|error| match error {
+        ExternalPackageValidationError::Diagnostic(diagnostic) => {
+            CompilerMessages::from_diagnostic_ref(*diagnostic, string_table)
+        }
+        ExternalPackageValidationError::Infrastructure(error) => {
+            CompilerMessages::from_error_ref(*error, string_table)
+        }
+    })?;
+
+    validate_hir_backend_feature_support(
+        BackendFeatureValidationInput {
+            hir: hir_module,
+            target: BackendTarget::Wasi,
+            root: BackendFeatureValidationRoot::StartFunction,
+            type_environment: Some(&module.executable.type_environment),
+        },
+        string_table,
+    )
+    .map_err(|error| match error {
+        BackendFeatureValidationError::Diagnostic(diagnostic) => {
+            CompilerMessages::from_diagnostic_ref(*diagnostic, string_table)
+                .with_type_context_for_all_diagnostics(module.executable.type_environment.clone())
+        }
+        BackendFeatureValidationError::Infrastructure(error) => {
+            CompilerMessages::from_error_ref(*error, string_table)
+        }
+    })?;
+
+    let request = WasmBackendRequest {
+        export_policy: WasmExportPolicy {
+            // Runtimes read and write command I/O through the exported memory.
+            helper_exports: WasmHelperExportPolicy {
+                export_memory: true,
+                ..WasmHelperExportPolicy::default()
+            },
+            command_start: Some(WasmCommandStart {
+                function: hir_module.start_function,
+                output: command_output(
+                    &module.metadata.const_top_level_fragments,
+                    module.metadata.root_activity.runtime_fragment_count,
+                ),
+            }),
+            ..WasmExportPolicy::default()
+        },
+        function_emission_policy: WasmFunctionEmissionPolicy::ReachableFromExports,
+        target_features: WasmTargetFeatures {
+            enable_multi_value: true,
+            ..WasmTargetFeatures::default()
+        },
+        host_interface: WasmHostInterface::WasiPreview1,
+        external_package_registry: Arc::clone(&module.link_facts.external_package_registry),
+        ..WasmBackendRequest::default()
+    };
+
+    let result = {
+        let _lower_guard = crate::timing::PipelineTimingGuard::new("backend.wasi.lower_wasm");
+        lower_hir_to_wasm_module(
+            hir_module,
+            module.executable.borrow_analysis.borrow_facts(),
+            &request,
+            string_table,
+            &module.executable.type_environment,
+        )?
+    };
+
+    result.wasm_bytes.ok_or_else(|| {
+        CompilerMessages::from_error(
+            CompilerError::compiler_error(
+                "WASI builder expected emitted wasm bytes, but the backend returned none",
+            ),
+            string_table.clone(),
+        )
+    })
+}
+
+/// Command output of an entry file: each fragment is printed as one line, in source order.
+pub(crate) fn command_output(
+    const_fragments: &[ResolvedConstFragment],
+    runtime_fragment_count: usize,
+) -> Vec<WasmCommandOutput> {
+    order_entry_fragments(const_fragments, runtime_fragment_count)
+        .into_iter()
+        .map(|fragment| match fragment {
+            EntryFragment::Const(text) => WasmCommandOutput::Const(text.to_owned()),
+            EntryFragment::Runtime(index) => WasmCommandOutput::RuntimeFragment(index as u32),
+        })
+        .collect()
+}
+
+/// Output path of one module command.
+///
+/// WHAT: single-file builds name the command after the entry stem without a cosmetic leading
+/// `#`; directory builds put `main.wasm` in each module's entry-root-relative directory.
+pub(crate) fn wasi_output_path(
+    entry_point: &Path,
+    entry_root: Option<&Path>,
+) -> Result<PathBuf, CompilerError> {
+    let Some(entry_root) = entry_root else {
+        let stem = entry_point
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| stem.trim_start_matches('#'))
+            .filter(|stem| !stem.is_empty())
+            .ok_or_else(|| {
+                CompilerError::compiler_error(format!(
+                    "WASI single-file entry '{}' needs a non-empty UTF-8 file stem.",
+                    entry_point.display()
+                ))
+            })?;
+        return Ok(PathBuf::from(format!("{stem}.wasm")));
+    };
+
+    let relative_entry = entry_point.strip_prefix(entry_root).map_err(|_| {
+        CompilerError::compiler_error(format!(
+            "WASI entry '{}' is not inside the configured entry root '{}'.",
+            entry_point.display(),
+            entry_root.display()
+        ))
+    })?;
+    let parent = relative_entry.parent().unwrap_or_else(|| Path::new(""));
+
+    Ok(parent.join(WASI_MODULE_OUTPUT_NAME))
+}
+
+/// Canonical entry root of a directory build, or `None` for single-file builds.
+fn resolve_canonical_entry_root(
+    config: &Config,
+    string_table: &StringTable,
+) -> Result<Option<PathBuf>, CompilerMessages> {
+    if !config.entry_dir.is_dir() {
+        return Ok(None);
+    }
+
+    let entry_root_path = resolve_project_entry_root(config);
+    fs::canonicalize(&entry_root_path)
+        .map(Some)
+        .map_err(|error| {
+            CompilerMessages::from_error(
+                CompilerError::compiler_error(format!(
+                    "Failed to resolve configured WASI entry root '{}': {error}",
+                    entry_root_path.display()
+                )),
+                string_table.clone(),
+            )
+        })
+}
+
+#[cfg(test)]
+#[path = "tests/wasi_project_builder_tests.rs"]
+mod tests;